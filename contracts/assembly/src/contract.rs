@@ -1,32 +1,64 @@
+use std::collections::{BTreeMap, HashSet};
 use std::str::FromStr;
 
 use astroport::asset::addr_opt_validate;
 use astroport::staking;
+use cosmwasm_schema::serde::Serialize;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, coins, ensure, wasm_execute, Addr, Api, BankMsg, CosmosMsg, Decimal, DepsMut, Env,
-    MessageInfo, QuerierWrapper, Response, StdError, Storage, SubMsg, Uint128, Uint64, WasmMsg,
+    attr, coins, ensure, to_json_binary, to_json_string, wasm_execute, Addr, Api, BankMsg, Binary,
+    CosmosMsg, Decimal, DepsMut, Env, Event, MessageInfo, Order, QuerierWrapper, Reply, Response,
+    StdError, StdResult, Storage, SubMsg, SubMsgResult, Uint128, Uint64, WasmMsg,
 };
 use cw2::set_contract_version;
-use cw_utils::must_pay;
+use cw_utils::{must_pay, nonpayable};
 use ibc_controller_package::ExecuteMsg as ControllerExecuteMsg;
 
 use astroport_governance::assembly::{
-    validate_links, Config, ExecuteMsg, InstantiateMsg, Proposal, ProposalStatus,
-    ProposalVoteOption, UpdateConfig,
+    validate_links, validate_tags, vote_commitment, ConclusionHook, ConclusionHookInfo,
+    ConclusionHookMsg, Config, ExecuteMsg, GovernableParam, InstantiateMsg, MigrationPlan,
+    MigrationPlanStatus, MigrationStep, MigrationStepStatus, ParamChange, ParamValue,
+    PausableTarget, PausableTargetInfo, PausedTarget, Proposal, ProposalPhase, ProposalStatus,
+    ProposalTemplate, ProposalVoteOption, RelayVotePayload, RelayedVote, ScheduledProposal,
+    UpdateConfig, VoteCommitment, VoteRelayer, VoteRelayerInfo, VotingPowerBreakdown,
+    CRITICAL_PROPOSAL_TAG, DEFAULT_REVEAL_PERIOD, MAX_BATCH_VOTES, MAX_CONCLUSION_HOOKS,
+    MAX_PROPOSAL_TAGS, MAX_USER_RELAYED_VOTES, RELAY_QUOTA_PERIOD,
 };
 use astroport_governance::emissions_controller::hub::HubMsg;
 use astroport_governance::utils::check_contract_supports_channel;
 use astroport_governance::{emissions_controller, voting_escrow};
 
 use crate::error::ContractError;
-use crate::state::{CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_VOTERS};
-use crate::utils::{calc_total_voting_power_at, calc_voting_power};
+use crate::state::{
+    ACTIVE_MIGRATION_PLAN, ACTIVE_PROPOSALS, CONCLUSION_HOOKS, CONFIG, GOVERNANCE_STATS,
+    MIGRATION_PLANS, MIGRATION_PLAN_COUNT, PARAM_REGISTRY, PAUSABLE_TARGETS, PAUSED_TARGETS,
+    PROPOSALS, PROPOSALS_BY_TAG, PROPOSAL_COUNT, PROPOSAL_PAUSED_TARGETS, PROPOSAL_TURNOUT,
+    PROPOSAL_VOTERS, RELAY_NONCES, RELAY_PUBKEYS, SCHEDULED_PROPOSALS, SCHEDULED_PROPOSAL_COUNT,
+    USER_RELAY_USAGE, VOTER_STATS, VOTE_COMMITMENTS, VOTE_RELAYERS, VOTING_POWER_BREAKDOWNS,
+};
+use crate::utils::{
+    calc_adaptive_quorum, calc_aged_xastro_balance, calc_total_voting_power_at,
+    calc_voting_power_breakdown, fee_allowance_msg,
+};
 
 // Contract name and version used for migration.
 pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Reply id for migration plan steps
+pub const MIGRATION_STEP_REPLY_ID: u64 = 1;
+/// Reply id for proposal status notifications sent via the Emissions Controller
+pub const NOTIFY_OUTPOSTS_REPLY_ID: u64 = 2;
+/// Reply id for pause/unpause calls sent to pausable targets
+pub const PAUSABLE_TARGET_REPLY_ID: u64 = 3;
+/// Reply id for notifications sent to conclusion hooks
+pub const CONCLUSION_HOOK_REPLY_ID: u64 = 4;
+/// Gas limit for calls to external contracts whose failures must not revert the proposal lifecycle.
+/// Without the limit an out-of-gas error can't be caught by the reply
+pub const EXTERNAL_CALL_GAS_LIMIT: u64 = 1_000_000;
+/// Gas limit for proposal status notifications. Higher than [`EXTERNAL_CALL_GAS_LIMIT`]
+/// as the Emissions Controller sends an IBC packet to every subscribed outpost
+pub const NOTIFY_OUTPOSTS_GAS_LIMIT: u64 = 3_000_000;
 
 /// Creates a new contract with the specified parameters in the `msg` variable.
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -67,6 +99,13 @@ pub fn instantiate(
         proposal_required_quorum: Decimal::from_str(&msg.proposal_required_quorum)?,
         proposal_required_threshold: Decimal::from_str(&msg.proposal_required_threshold)?,
         whitelisted_links: msg.whitelisted_links,
+        include_builder_vp: true,
+        impact_classes: Default::default(),
+        proposal_reveal_period: DEFAULT_REVEAL_PERIOD,
+        min_xastro_age: 0,
+        proposal_review_period: 0,
+        proposal_tags: vec![],
+        quorum_mode: Default::default(),
     };
 
     #[cfg(not(feature = "testnet"))]
@@ -82,7 +121,7 @@ pub fn instantiate(
 /// Exposes all the execute functions available in the contract.
 ///
 /// ## Execute messages
-/// * **ExecuteMsg::SubmitProposal { title, description, link, messages, ibc_channel }** Submits a new proposal.
+/// * **ExecuteMsg::SubmitProposal { title, description, link, messages, ibc_channel, commit_reveal }** Submits a new proposal.
 ///
 /// * **ExecuteMsg::CheckMessages { messages }** Checks if the messages are correct.
 /// Executes arbitrary messages on behalf of the Assembly contract. Always appends failing message to the end of the list.
@@ -91,9 +130,25 @@ pub fn instantiate(
 ///
 /// * **ExecuteMsg::CastVote { proposal_id, vote }** Cast a vote on a specific proposal.
 ///
+/// * **ExecuteMsg::CastVotes { votes }** Cast votes on multiple proposals skipping the ones which can't be voted on.
+///
 /// * **ExecuteMsg::CastVoteOutpost { voter, voting_power, proposal_id, vote }** Applies a vote on a specific proposal from outpost.
 /// Only emissions controller is allowed to call this endpoint.
 ///
+/// * **ExecuteMsg::CommitVote { proposal_id, commitment }** Commits a hidden vote on a specific commit-reveal proposal.
+///
+/// * **ExecuteMsg::RevealVote { proposal_id, vote, salt }** Reveals a committed vote during the reveal window.
+///
+/// * **ExecuteMsg::CommitVoteOutpost { voter, voting_power, proposal_id, commitment }** Commits a hidden vote from outpost.
+/// Only emissions controller is allowed to call this endpoint.
+///
+/// * **ExecuteMsg::RevealVoteOutpost { voter, proposal_id, vote, salt }** Reveals a committed outpost vote.
+/// Only emissions controller is allowed to call this endpoint.
+///
+/// * **ExecuteMsg::AmendProposal { proposal_id, description, messages }** Amends a proposal during its review window.
+///
+/// * **ExecuteMsg::WithdrawProposal { proposal_id }** Withdraws a proposal during its review window.
+///
 /// * **ExecuteMsg::EndProposal { proposal_id }** Sets the status of an expired/finalized proposal.
 ///
 /// * **ExecuteMsg::ExecuteProposal { proposal_id }** Executes a successful proposal.
@@ -102,6 +157,35 @@ pub fn instantiate(
 ///
 /// * **ExecuteMsg::IBCProposalCompleted { proposal_id, status }** Updates proposal status InProgress -> Executed or Failed.
 /// This endpoint processes callbacks from the ibc controller.
+///
+/// * **ExecuteMsg::RegisterMigrationPlan { steps }** Registers a migration plan and executes its steps sequentially.
+///
+/// * **ExecuteMsg::UpdateParamRegistry { add, remove }** Adds or removes governable parameters.
+///
+/// * **ExecuteMsg::SubmitParamChangeProposal { title, description, link, changes }** Submits a proposal
+/// which changes registered parameters within their bounds.
+///
+/// * **ExecuteMsg::UpdateVoteRelayers { add, remove }** Adds or removes vote relayers.
+///
+/// * **ExecuteMsg::SetRelayPubkey { pubkey }** Registers the public key the sender signs relayed votes with.
+///
+/// * **ExecuteMsg::RelayVote(relayed_vote)** Casts a vote signed by a user.
+/// Only allowlisted relayers are allowed to call this endpoint.
+///
+/// * **ExecuteMsg::GrantRelayFeeAllowance { grantee }** Grants a fee allowance from the Assembly to a user.
+/// Only allowlisted relayers are allowed to call this endpoint.
+///
+/// * **ExecuteMsg::UpdatePausableTargets { add, remove }** Adds or removes contracts paused during critical proposals.
+///
+/// * **ExecuteMsg::ScheduleProposal { open_at, template }** Schedules a follow-up proposal to be opened at a future timestamp.
+///
+/// * **ExecuteMsg::OpenScheduledProposal { id }** Opens a scheduled proposal once its time has come.
+///
+/// * **ExecuteMsg::CancelScheduledProposal { id }** Cancels a scheduled proposal.
+///
+/// * **ExecuteMsg::UpdateConclusionHooks { add, remove }** Adds or removes contracts notified when a matching proposal concludes.
+///
+/// * **ExecuteMsg::SetConclusionHookEnabled { contract, enabled }** Enables or disables a conclusion hook.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -116,44 +200,50 @@ pub fn execute(
             link,
             messages,
             ibc_channel,
+            commit_reveal,
+            tags,
         } => submit_proposal(
             deps,
             env,
             info,
-            title,
-            description,
-            link,
-            messages,
-            ibc_channel,
+            ProposalTemplate {
+                title,
+                description,
+                link,
+                messages,
+                ibc_channel,
+                commit_reveal,
+                tags,
+            },
         ),
         ExecuteMsg::CastVote { proposal_id, vote } => {
             let voter = info.sender.to_string();
             let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
 
-            let voting_power = calc_voting_power(deps.as_ref(), voter.clone(), &proposal)?;
-            ensure!(!voting_power.is_zero(), ContractError::NoVotingPower {});
+            let breakdown = calc_voting_power_breakdown(deps.as_ref(), voter.clone(), &proposal)?;
+            ensure!(
+                !breakdown.total().is_zero(),
+                ContractError::NoVotingPower {}
+            );
 
             cast_vote(
                 deps.storage,
                 env,
                 voter,
-                voting_power,
+                breakdown,
                 proposal_id,
                 proposal,
                 vote,
             )
         }
+        ExecuteMsg::CastVotes { votes } => cast_votes(deps, env, info, votes),
         ExecuteMsg::CastVoteOutpost {
             voter,
             voting_power,
             proposal_id,
             vote,
         } => {
-            let config = CONFIG.load(deps.storage)?;
-            ensure!(
-                Some(info.sender) == config.emissions_controller,
-                ContractError::Unauthorized {}
-            );
+            ensure_emissions_controller(deps.storage, &info.sender)?;
 
             // This endpoint should never fail if called from the emissions controller.
             // Otherwise, an IBC packet will never be acknowledged.
@@ -164,7 +254,10 @@ pub fn execute(
                     deps.storage,
                     env,
                     voter,
-                    voting_power,
+                    VotingPowerBreakdown {
+                        outpost: voting_power,
+                        ..Default::default()
+                    },
                     proposal_id,
                     proposal,
                     vote,
@@ -176,6 +269,105 @@ pub fn execute(
                     .add_attribute("error", err.to_string()))
             })
         }
+        ExecuteMsg::CommitVote {
+            proposal_id,
+            commitment,
+        } => {
+            let voter = info.sender.to_string();
+            let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+
+            let breakdown = calc_voting_power_breakdown(deps.as_ref(), voter.clone(), &proposal)?;
+            ensure!(
+                !breakdown.total().is_zero(),
+                ContractError::NoVotingPower {}
+            );
+
+            commit_vote(
+                deps.storage,
+                env,
+                voter,
+                breakdown,
+                proposal_id,
+                &proposal,
+                commitment,
+            )
+        }
+        ExecuteMsg::RevealVote {
+            proposal_id,
+            vote,
+            salt,
+        } => {
+            let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+
+            reveal_vote(
+                deps.storage,
+                env,
+                info.sender.to_string(),
+                proposal_id,
+                proposal,
+                vote,
+                salt,
+            )
+        }
+        ExecuteMsg::CommitVoteOutpost {
+            voter,
+            voting_power,
+            proposal_id,
+            commitment,
+        } => {
+            ensure_emissions_controller(deps.storage, &info.sender)?;
+
+            // Same as CastVoteOutpost, this endpoint should never fail if called from the emissions controller
+            (|| {
+                let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+
+                commit_vote(
+                    deps.storage,
+                    env,
+                    voter,
+                    VotingPowerBreakdown {
+                        outpost: voting_power,
+                        ..Default::default()
+                    },
+                    proposal_id,
+                    &proposal,
+                    commitment,
+                )
+            })()
+            .or_else(|err| {
+                Ok(Response::new()
+                    .add_attribute("action", "commit_vote")
+                    .add_attribute("error", err.to_string()))
+            })
+        }
+        ExecuteMsg::RevealVoteOutpost {
+            voter,
+            proposal_id,
+            vote,
+            salt,
+        } => {
+            ensure_emissions_controller(deps.storage, &info.sender)?;
+
+            // Same as CastVoteOutpost, this endpoint should never fail if called from the emissions controller
+            (|| {
+                let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+
+                reveal_vote(deps.storage, env, voter, proposal_id, proposal, vote, salt)
+            })()
+            .or_else(|err| {
+                Ok(Response::new()
+                    .add_attribute("action", "reveal_vote")
+                    .add_attribute("error", err.to_string()))
+            })
+        }
+        ExecuteMsg::AmendProposal {
+            proposal_id,
+            description,
+            messages,
+        } => amend_proposal(deps, env, info, proposal_id, description, messages),
+        ExecuteMsg::WithdrawProposal { proposal_id } => {
+            withdraw_proposal(deps, env, info, proposal_id)
+        }
         ExecuteMsg::EndProposal { proposal_id } => end_proposal(deps, env, proposal_id),
         ExecuteMsg::ExecuteProposal { proposal_id } => execute_proposal(deps, env, proposal_id),
         ExecuteMsg::CheckMessages(messages) => check_messages(deps.api, env, messages),
@@ -188,34 +380,57 @@ pub fn execute(
         ExecuteMsg::ExecuteFromMultisig(proposal_messages) => {
             exec_from_multisig(deps.querier, info, env, proposal_messages)
         }
+        ExecuteMsg::RegisterMigrationPlan { steps } => {
+            register_migration_plan(deps, env, info, steps)
+        }
+        ExecuteMsg::UpdateParamRegistry { add, remove } => {
+            update_param_registry(deps, env, info, add, remove)
+        }
+        ExecuteMsg::SubmitParamChangeProposal {
+            title,
+            description,
+            link,
+            changes,
+        } => submit_param_change_proposal(deps, env, info, title, description, link, changes),
+        ExecuteMsg::UpdateVoteRelayers { add, remove } => {
+            update_vote_relayers(deps, env, info, add, remove)
+        }
+        ExecuteMsg::SetRelayPubkey { pubkey } => set_relay_pubkey(deps, info, pubkey),
+        ExecuteMsg::RelayVote(relayed_vote) => relay_vote(deps, env, info, relayed_vote),
+        ExecuteMsg::GrantRelayFeeAllowance { grantee } => {
+            grant_relay_fee_allowance(deps, env, info, grantee)
+        }
+        ExecuteMsg::UpdatePausableTargets { add, remove } => {
+            update_pausable_targets(deps, env, info, add, remove)
+        }
+        ExecuteMsg::ScheduleProposal { open_at, template } => {
+            schedule_proposal(deps, env, info, open_at, template)
+        }
+        ExecuteMsg::OpenScheduledProposal { id } => open_scheduled_proposal(deps, env, id),
+        ExecuteMsg::CancelScheduledProposal { id } => {
+            cancel_scheduled_proposal(deps, env, info, id)
+        }
+        ExecuteMsg::UpdateConclusionHooks { add, remove } => {
+            update_conclusion_hooks(deps, env, info, add, remove)
+        }
+        ExecuteMsg::SetConclusionHookEnabled { contract, enabled } => {
+            set_conclusion_hook_enabled(deps, env, info, contract, enabled)
+        }
     }
 }
 
 /// Submit a brand new proposal and lock some xASTRO as an anti-spam mechanism.
+/// The sender is the proposal submitter and the attached xASTRO is the proposal deposit.
 ///
-/// * **sender** proposal submitter.
-///
-/// * **deposit_amount**  amount of xASTRO to deposit in order to submit the proposal.
-///
-/// * **title** proposal title.
-///
-/// * **description** proposal description.
-///
-/// * **link** proposal link.
-///
-/// * **messages** executable messages (actions to perform if the proposal passes).
-#[allow(clippy::too_many_arguments)]
+/// * **template** proposal contents (title, description, messages, etc.).
 pub fn submit_proposal(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    title: String,
-    description: String,
-    link: Option<String>,
-    messages: Vec<CosmosMsg>,
-    ibc_channel: Option<String>,
+    template: ProposalTemplate,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    validate_proposal_tags(&config, &template.tags)?;
 
     // Ensure that the correct token is sent. This will fail if
     // zero tokens are sent.
@@ -225,6 +440,47 @@ pub fn submit_proposal(
         return Err(ContractError::InsufficientDeposit {});
     }
 
+    // Flash-staked xASTRO can't be used to submit proposals.
+    // The emissions controller submits outpost proposals with the deposit bridged in the same tx,
+    // thus its submissions are exempt.
+    let from_emissions_controller = config.emissions_controller.as_ref() == Some(&info.sender);
+    if config.min_xastro_age > 0 && !from_emissions_controller {
+        let aged_balance = calc_aged_xastro_balance(
+            deps.querier,
+            &config,
+            info.sender.as_str(),
+            env.block.time.seconds(),
+        )?;
+        if aged_balance < config.proposal_required_deposit {
+            return Err(ContractError::InsufficientXastroAge {
+                min_age: config.min_xastro_age,
+            });
+        }
+    }
+
+    open_proposal(deps, &env, &config, info.sender, deposit_amount, template)
+}
+
+/// Opens a new proposal from the template on behalf of the submitter.
+/// The caller is responsible for deposit and tags checks.
+fn open_proposal(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    submitter: Addr,
+    deposit_amount: Uint128,
+    template: ProposalTemplate,
+) -> Result<Response, ContractError> {
+    let ProposalTemplate {
+        title,
+        description,
+        link,
+        messages,
+        ibc_channel,
+        commit_reveal,
+        tags,
+    } = template;
+
     // Update the proposal count
     let count = PROPOSAL_COUNT.update::<_, StdError>(deps.storage, |c| Ok(c + Uint64::one()))?;
 
@@ -237,22 +493,21 @@ pub fn submit_proposal(
         }
     }
 
-    let proposal = Proposal {
+    // Voting opens once the review window is over
+    let review_end_block = (config.proposal_review_period > 0)
+        .then_some(env.block.height + config.proposal_review_period);
+
+    let mut proposal = Proposal {
         proposal_id: count,
-        submitter: info.sender.clone(),
+        submitter: submitter.clone(),
         status: ProposalStatus::Active,
         for_power: Uint128::zero(),
         against_power: Uint128::zero(),
         start_block: env.block.height,
         start_time: env.block.time.seconds(),
-        end_block: env.block.height + config.proposal_voting_period,
-        delayed_end_block: env.block.height
-            + config.proposal_voting_period
-            + config.proposal_effective_delay,
-        expiration_block: env.block.height
-            + config.proposal_voting_period
-            + config.proposal_effective_delay
-            + config.proposal_expiration_period,
+        end_block: 0,
+        delayed_end_block: 0,
+        expiration_block: 0,
         title,
         description,
         link,
@@ -263,26 +518,50 @@ pub fn submit_proposal(
         // this is the last up to date finalized state of token factory tracker contract.
         total_voting_power: calc_total_voting_power_at(
             deps.querier,
-            &config,
+            config,
             env.block.time.seconds() - 1,
         )?,
+        impact_class: Default::default(),
+        reveal_end_block: None,
+        review_end_block,
+        amended: false,
+        tags,
+        adaptive_quorum: calc_adaptive_quorum(deps.storage, config)?,
     };
+    schedule_voting(
+        config,
+        &mut proposal,
+        review_end_block.unwrap_or(env.block.height),
+        commit_reveal,
+    );
 
-    proposal.validate(config.whitelisted_links)?;
+    proposal.validate(config.whitelisted_links.clone())?;
 
     PROPOSALS.save(deps.storage, count.u64(), &proposal)?;
+    ACTIVE_PROPOSALS.save(
+        deps.storage,
+        (proposal.voting_end_block(), count.u64()),
+        &(),
+    )?;
+    for tag in &proposal.tags {
+        PROPOSALS_BY_TAG.save(deps.storage, (tag, count.u64()), &())?;
+    }
 
-    let mut response = Response::new().add_attributes([
+    let pause_msgs = pause_proposal_targets(deps.storage, &proposal)?;
+
+    let mut response = Response::new().add_submessages(pause_msgs).add_attributes([
         attr("action", "submit_proposal"),
-        attr("submitter", info.sender),
+        attr("submitter", submitter),
         attr("proposal_id", count),
-        attr(
-            "proposal_end_height",
-            (env.block.height + config.proposal_voting_period).to_string(),
-        ),
+        attr("proposal_end_height", proposal.end_block.to_string()),
+        attr("impact_class", proposal.impact_class.to_string()),
+        attr("commit_reveal", commit_reveal.to_string()),
     ]);
+    if let Some(review_end_block) = review_end_block {
+        response = response.add_attribute("review_end_height", review_end_block.to_string());
+    }
 
-    if let Some(emissions_controller) = config.emissions_controller {
+    if let Some(emissions_controller) = &config.emissions_controller {
         // Send IBC packets to all outposts to register this proposal.
         let outposts_register_msg = wasm_execute(
             emissions_controller,
@@ -297,11 +576,136 @@ pub fn submit_proposal(
     Ok(response)
 }
 
+/// Classifies the proposal by its messages and sets the voting schedule accordingly.
+/// Voting starts right after **voting_start** block.
+/// Commit-reveal proposals get the reveal window right after the voting period.
+fn schedule_voting(
+    config: &Config,
+    proposal: &mut Proposal,
+    voting_start: u64,
+    commit_reveal: bool,
+) {
+    // High-impact proposals might require longer voting period
+    proposal.impact_class = config.impact_classes.classify(&proposal.messages);
+    let voting_period = config.class_params(proposal.impact_class).min_voting_period;
+
+    proposal.end_block = voting_start + voting_period;
+    proposal.reveal_end_block =
+        commit_reveal.then_some(proposal.end_block + config.proposal_reveal_period);
+    let voting_end_block = proposal.voting_end_block();
+    proposal.delayed_end_block = voting_end_block + config.proposal_effective_delay;
+    proposal.expiration_block =
+        voting_end_block + config.proposal_effective_delay + config.proposal_expiration_period;
+}
+
+/// Loads the proposal and ensures that it is still in its review window
+/// and the sender is the proposal submitter.
+fn load_proposal_in_review(
+    storage: &dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    proposal_id: u64,
+) -> Result<Proposal, ContractError> {
+    let proposal = PROPOSALS.load(storage, proposal_id)?;
+
+    ensure!(
+        *sender == proposal.submitter,
+        ContractError::Unauthorized {}
+    );
+    ensure!(
+        proposal.status == ProposalStatus::Active
+            && proposal.phase(env.block.height) == ProposalPhase::Review,
+        ContractError::ProposalNotInReview {}
+    );
+
+    Ok(proposal)
+}
+
+/// Amends the proposal description and/or messages during its review window.
+/// A proposal can be amended only once. As messages might change the impact class,
+/// the voting schedule is recalculated.
+pub fn amend_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    description: Option<String>,
+    messages: Option<Vec<CosmosMsg>>,
+) -> Result<Response, ContractError> {
+    let mut proposal = load_proposal_in_review(deps.storage, &env, &info.sender, proposal_id)?;
+    ensure!(!proposal.amended, ContractError::ProposalAlreadyAmended {});
+    ensure!(
+        description.is_some() || messages.is_some(),
+        ContractError::EmptyProposalAmendment {}
+    );
+
+    let config = CONFIG.load(deps.storage)?;
+
+    if let Some(description) = description {
+        proposal.description = description;
+    }
+    if let Some(messages) = messages {
+        proposal.messages = messages;
+        let commit_reveal = proposal.reveal_end_block.is_some();
+        // Review window is always set for proposals in the review phase
+        let voting_start = proposal.review_end_block.unwrap_or(env.block.height);
+        ACTIVE_PROPOSALS.remove(deps.storage, (proposal.voting_end_block(), proposal_id));
+        schedule_voting(&config, &mut proposal, voting_start, commit_reveal);
+        ACTIVE_PROPOSALS.save(
+            deps.storage,
+            (proposal.voting_end_block(), proposal_id),
+            &(),
+        )?;
+    }
+    proposal.amended = true;
+
+    proposal.validate(config.whitelisted_links)?;
+
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+    // Amended messages might target other contracts
+    let mut pause_msgs = unpause_proposal_targets(deps.storage, proposal_id)?;
+    pause_msgs.extend(pause_proposal_targets(deps.storage, &proposal)?);
+
+    Ok(Response::new().add_submessages(pause_msgs).add_attributes([
+        attr("action", "amend_proposal"),
+        attr("proposal_id", proposal_id.to_string()),
+        attr("proposal_end_height", proposal.end_block.to_string()),
+        attr("impact_class", proposal.impact_class.to_string()),
+    ]))
+}
+
+/// Withdraws the proposal during its review window and fully refunds the deposit.
+pub fn withdraw_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut proposal = load_proposal_in_review(deps.storage, &env, &info.sender, proposal_id)?;
+    proposal.status = ProposalStatus::Withdrawn;
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+    ACTIVE_PROPOSALS.remove(deps.storage, (proposal.voting_end_block(), proposal_id));
+
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(Response::new()
+        .add_attributes([
+            attr("action", "withdraw_proposal"),
+            attr("proposal_id", proposal_id.to_string()),
+            attr("refund", proposal.deposit_amount),
+        ])
+        .add_messages(refund_deposit_msg(&config, &proposal))
+        .add_submessages(unpause_proposal_targets(deps.storage, proposal_id)?)
+        .add_submessages(notify_outposts_msg(&config, proposal_id)?)
+        .add_submessages(conclusion_hook_msgs(deps.storage, &proposal)?))
+}
+
 /// Cast a vote on a proposal.
 ///
 /// * **voter** is the bech32 address of the voter from any of the supported outposts.
 ///
-/// * **voting_power** is the voting power of the voter.
+/// * **breakdown** is the voting power of the voter split by source.
 ///
 /// * **proposal_id** is the identifier of the proposal.
 ///
@@ -312,7 +716,7 @@ pub fn cast_vote(
     storage: &mut dyn Storage,
     env: Env,
     voter: String,
-    voting_power: Uint128,
+    breakdown: VotingPowerBreakdown,
     proposal_id: u64,
     mut proposal: Proposal,
     vote_option: ProposalVoteOption,
@@ -321,25 +725,25 @@ pub fn cast_vote(
         return Err(ContractError::ProposalNotActive {});
     }
 
-    if env.block.height > proposal.end_block {
-        return Err(ContractError::VotingPeriodEnded {});
-    }
-
-    if PROPOSAL_VOTERS.has(storage, (proposal_id, voter.clone())) {
-        return Err(ContractError::UserAlreadyVoted {});
-    }
-
-    match vote_option {
-        ProposalVoteOption::For => {
-            proposal.for_power = proposal.for_power.checked_add(voting_power)?;
-        }
-        ProposalVoteOption::Against => {
-            proposal.against_power = proposal.against_power.checked_add(voting_power)?;
+    match proposal.phase(env.block.height) {
+        ProposalPhase::Voting => {}
+        ProposalPhase::Review => return Err(ContractError::VotingNotStarted {}),
+        ProposalPhase::Commit => return Err(ContractError::CommitRevealProposal {}),
+        ProposalPhase::Reveal | ProposalPhase::Closed => {
+            return Err(ContractError::VotingPeriodEnded {})
         }
-    };
-    PROPOSAL_VOTERS.save(storage, (proposal_id, voter.clone()), &vote_option)?;
+    }
 
-    PROPOSALS.save(storage, proposal_id, &proposal)?;
+    let voting_power = breakdown.total();
+    apply_vote(
+        storage,
+        &voter,
+        voting_power,
+        &breakdown,
+        proposal_id,
+        &mut proposal,
+        &vote_option,
+    )?;
 
     Ok(Response::new().add_attributes(vec![
         attr("action", "cast_vote"),
@@ -350,38 +754,293 @@ pub fn cast_vote(
     ]))
 }
 
-/// Ends proposal voting period, sets the proposal status by id and returns
-/// xASTRO submitted for the proposal.
-pub fn end_proposal(deps: DepsMut, env: Env, proposal_id: u64) -> Result<Response, ContractError> {
-    let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+/// Cast votes on multiple proposals in a single transaction.
+/// Votes on missing or inactive proposals, proposals which are not in the voting phase
+/// or which the sender has already voted on, as well as votes without voting power are skipped.
+/// Each vote outcome is reported in a separate `batch_vote` event.
+pub fn cast_votes(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    votes: Vec<(u64, ProposalVoteOption)>,
+) -> Result<Response, ContractError> {
+    ensure!(
+        !votes.is_empty() && votes.len() <= MAX_BATCH_VOTES,
+        ContractError::InvalidVoteBatch {
+            max: MAX_BATCH_VOTES
+        }
+    );
+
+    let voter = info.sender.to_string();
+    let mut events = vec![];
+    let mut voted = 0;
+
+    for (proposal_id, vote_option) in votes {
+        let event = Event::new("batch_vote").add_attributes([
+            attr("proposal_id", proposal_id.to_string()),
+            attr("vote", vote_option.to_string()),
+        ]);
+
+        let result = match PROPOSALS.may_load(deps.storage, proposal_id)? {
+            Some(proposal) => {
+                let breakdown =
+                    calc_voting_power_breakdown(deps.as_ref(), voter.clone(), &proposal)?;
+                if breakdown.total().is_zero() {
+                    Err(ContractError::NoVotingPower {})
+                } else {
+                    let voting_power = breakdown.total();
+                    cast_vote(
+                        deps.storage,
+                        env.clone(),
+                        voter.clone(),
+                        breakdown,
+                        proposal_id,
+                        proposal,
+                        vote_option,
+                    )
+                    .map(|_| voting_power)
+                }
+            }
+            None => Err(ContractError::ProposalNotFound {}),
+        };
+
+        let event = match result {
+            Ok(voting_power) => {
+                voted += 1;
+                event.add_attributes([attr("result", "voted"), attr("voting_power", voting_power)])
+            }
+            Err(
+                err @ (ContractError::ProposalNotFound {}
+                | ContractError::ProposalNotActive {}
+                | ContractError::VotingNotStarted {}
+                | ContractError::CommitRevealProposal {}
+                | ContractError::VotingPeriodEnded {}
+                | ContractError::UserAlreadyVoted {}
+                | ContractError::NoVotingPower {}),
+            ) => event.add_attributes([attr("result", "skipped"), attr("reason", err.to_string())]),
+            Err(err) => return Err(err),
+        };
+        events.push(event);
+    }
+
+    Ok(Response::new()
+        .add_attributes([
+            attr("action", "cast_votes"),
+            attr("voter", voter),
+            attr("voted", voted.to_string()),
+            attr("skipped", (events.len() - voted).to_string()),
+        ])
+        .add_events(events))
+}
 
+/// Commit a hidden vote on a commit-reveal proposal.
+/// Voting power is sealed at commit while the vote itself is counted only once revealed.
+/// Commitment can be updated until the voting period ends.
+///
+/// * **voter** is the bech32 address of the voter from any of the supported outposts.
+///
+/// * **breakdown** is the voting power of the voter split by source.
+///
+/// * **commitment** is the hash produced by [`vote_commitment`].
+pub fn commit_vote(
+    storage: &mut dyn Storage,
+    env: Env,
+    voter: String,
+    breakdown: VotingPowerBreakdown,
+    proposal_id: u64,
+    proposal: &Proposal,
+    commitment: Binary,
+) -> Result<Response, ContractError> {
     if proposal.status != ProposalStatus::Active {
         return Err(ContractError::ProposalNotActive {});
     }
 
-    if env.block.height <= proposal.end_block {
-        return Err(ContractError::VotingPeriodNotEnded {});
+    match proposal.phase(env.block.height) {
+        ProposalPhase::Commit => {}
+        ProposalPhase::Review => return Err(ContractError::VotingNotStarted {}),
+        ProposalPhase::Voting => return Err(ContractError::NotCommitRevealProposal {}),
+        ProposalPhase::Reveal | ProposalPhase::Closed => {
+            return Err(ContractError::VotingPeriodEnded {})
+        }
     }
 
-    let config = CONFIG.load(deps.storage)?;
+    ensure!(commitment.len() == 32, ContractError::InvalidCommitment {});
 
-    let for_votes = proposal.for_power;
-    let against_votes = proposal.against_power;
-    let total_votes = for_votes + against_votes;
+    let voting_power = breakdown.total();
+    VOTE_COMMITMENTS.save(
+        storage,
+        (proposal_id, voter.clone()),
+        &VoteCommitment {
+            commitment,
+            voting_power,
+            breakdown,
+        },
+    )?;
 
-    let proposal_quorum =
-        Decimal::checked_from_ratio(total_votes, proposal.total_voting_power).unwrap_or_default();
-    let proposal_threshold =
-        Decimal::checked_from_ratio(for_votes, total_votes).unwrap_or_default();
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "commit_vote"),
+        attr("proposal_id", proposal_id.to_string()),
+        attr("voter", &voter),
+        attr("voting_power", voting_power),
+    ]))
+}
 
-    // Determine the proposal result
-    proposal.status = if proposal_quorum >= config.proposal_required_quorum
-        && proposal_threshold > config.proposal_required_threshold
-    {
-        ProposalStatus::Passed
-    } else {
-        ProposalStatus::Rejected
+/// Reveal a committed vote during the reveal window and count it with the voting power sealed at commit.
+///
+/// * **voter** is the bech32 address of the voter from any of the supported outposts.
+///
+/// * **vote_option** and **salt** must match the commitment.
+pub fn reveal_vote(
+    storage: &mut dyn Storage,
+    env: Env,
+    voter: String,
+    proposal_id: u64,
+    mut proposal: Proposal,
+    vote_option: ProposalVoteOption,
+    salt: String,
+) -> Result<Response, ContractError> {
+    if proposal.status != ProposalStatus::Active {
+        return Err(ContractError::ProposalNotActive {});
+    }
+
+    match proposal.phase(env.block.height) {
+        ProposalPhase::Reveal => {}
+        ProposalPhase::Voting => return Err(ContractError::NotCommitRevealProposal {}),
+        ProposalPhase::Review | ProposalPhase::Commit | ProposalPhase::Closed => {
+            return Err(ContractError::RevealPeriodNotActive {})
+        }
+    }
+
+    let key = (proposal_id, voter.clone());
+    let commitment = VOTE_COMMITMENTS
+        .may_load(storage, key.clone())?
+        .ok_or(ContractError::CommitmentNotFound {})?;
+    ensure!(
+        vote_commitment(proposal_id, &vote_option, &salt) == commitment.commitment,
+        ContractError::InvalidVoteReveal {}
+    );
+    VOTE_COMMITMENTS.remove(storage, key);
+
+    apply_vote(
+        storage,
+        &voter,
+        commitment.voting_power,
+        &commitment.breakdown,
+        proposal_id,
+        &mut proposal,
+        &vote_option,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "reveal_vote"),
+        attr("proposal_id", proposal_id.to_string()),
+        attr("voter", &voter),
+        attr("vote", vote_option.to_string()),
+        attr("voting_power", commitment.voting_power),
+    ]))
+}
+
+/// Adds voter's voting power to the proposal tally, records the vote along with
+/// its voting power breakdown and updates participation statistics.
+fn apply_vote(
+    storage: &mut dyn Storage,
+    voter: &str,
+    voting_power: Uint128,
+    breakdown: &VotingPowerBreakdown,
+    proposal_id: u64,
+    proposal: &mut Proposal,
+    vote_option: &ProposalVoteOption,
+) -> Result<(), ContractError> {
+    if PROPOSAL_VOTERS.has(storage, (proposal_id, voter.to_string())) {
+        return Err(ContractError::UserAlreadyVoted {});
+    }
+
+    match vote_option {
+        ProposalVoteOption::For => {
+            proposal.for_power = proposal.for_power.checked_add(voting_power)?;
+        }
+        ProposalVoteOption::Against => {
+            proposal.against_power = proposal.against_power.checked_add(voting_power)?;
+        }
     };
+    PROPOSAL_VOTERS.save(storage, (proposal_id, voter.to_string()), vote_option)?;
+    VOTING_POWER_BREAKDOWNS.save(storage, (proposal_id, voter.to_string()), breakdown)?;
+
+    PROPOSALS.save(storage, proposal_id, proposal)?;
+
+    let mut voter_stats = VOTER_STATS.may_load(storage, voter)?.unwrap_or_default();
+    let mut stats = GOVERNANCE_STATS.may_load(storage)?.unwrap_or_default();
+    voter_stats.proposals_voted += 1;
+    voter_stats.total_voting_power = voter_stats.total_voting_power.checked_add(voting_power)?;
+    voter_stats.last_voted_proposal = Some(proposal_id);
+    stats.total_votes += 1;
+    match vote_option {
+        ProposalVoteOption::For => {
+            voter_stats.for_votes += 1;
+            stats.for_votes += 1;
+        }
+        ProposalVoteOption::Against => {
+            voter_stats.against_votes += 1;
+            stats.against_votes += 1;
+        }
+    }
+    VOTER_STATS.save(storage, voter, &voter_stats)?;
+    GOVERNANCE_STATS.save(storage, &stats)?;
+
+    Ok(())
+}
+
+/// Ensures that proposal tags belong to the vocabulary, are unique
+/// and don't exceed [`MAX_PROPOSAL_TAGS`].
+fn validate_proposal_tags(config: &Config, tags: &[String]) -> Result<(), ContractError> {
+    ensure!(
+        tags.len() <= MAX_PROPOSAL_TAGS,
+        ContractError::TooManyProposalTags {
+            max: MAX_PROPOSAL_TAGS
+        }
+    );
+
+    for (ind, tag) in tags.iter().enumerate() {
+        ensure!(
+            config.proposal_tags.contains(tag),
+            ContractError::UnknownProposalTag { tag: tag.clone() }
+        );
+        ensure!(
+            !tags[..ind].contains(tag),
+            ContractError::DuplicateProposalTag { tag: tag.clone() }
+        );
+    }
+
+    Ok(())
+}
+
+/// Ensures that the sender is the emissions controller installed in the Assembly.
+fn ensure_emissions_controller(storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+    let config = CONFIG.load(storage)?;
+    ensure!(
+        Some(sender) == config.emissions_controller.as_ref(),
+        ContractError::Unauthorized {}
+    );
+
+    Ok(())
+}
+
+/// Ends proposal voting period, sets the proposal status by id and returns
+/// xASTRO submitted for the proposal.
+pub fn end_proposal(deps: DepsMut, env: Env, proposal_id: u64) -> Result<Response, ContractError> {
+    let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+
+    if proposal.status != ProposalStatus::Active {
+        return Err(ContractError::ProposalNotActive {});
+    }
+
+    if env.block.height <= proposal.voting_end_block() {
+        return Err(ContractError::VotingPeriodNotEnded {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+
+    let refund_msg = conclude_proposal(deps.storage, &config, &mut proposal)?;
 
     PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
 
@@ -391,14 +1050,72 @@ pub fn end_proposal(deps: DepsMut, env: Env, proposal_id: u64) -> Result<Respons
             attr("proposal_id", proposal_id.to_string()),
             attr("proposal_result", proposal.status.to_string()),
         ])
-        .add_message(BankMsg::Send {
-            to_address: proposal.submitter.to_string(),
-            amount: coins(proposal.deposit_amount.into(), config.xastro_denom),
-        });
+        .add_messages(refund_msg)
+        .add_submessages(unpause_proposal_targets(deps.storage, proposal_id)?)
+        .add_submessages(notify_outposts_msg(&config, proposal_id)?)
+        .add_submessages(conclusion_hook_msgs(deps.storage, &proposal)?);
 
     Ok(response)
 }
 
+/// Determines the result of the proposal according to the quorum and threshold settings
+/// and sets the proposal status accordingly.
+/// Records the proposal outcome and turnout in governance statistics.
+/// Returns a message which refunds xASTRO deposited by the proposal submitter if any.
+/// The caller must ensure that the proposal is active and its voting (and reveal) period has ended.
+pub fn conclude_proposal(
+    storage: &mut dyn Storage,
+    config: &Config,
+    proposal: &mut Proposal,
+) -> StdResult<Option<BankMsg>> {
+    let for_votes = proposal.for_power;
+    let against_votes = proposal.against_power;
+    let total_votes = for_votes + against_votes;
+
+    let proposal_quorum =
+        Decimal::checked_from_ratio(total_votes, proposal.total_voting_power).unwrap_or_default();
+    let proposal_threshold =
+        Decimal::checked_from_ratio(for_votes, total_votes).unwrap_or_default();
+
+    // Determine the proposal result
+    let params = config.class_params(proposal.impact_class);
+    proposal.status = if proposal_quorum >= config.required_quorum(proposal)
+        && proposal_threshold > params.required_threshold
+    {
+        ProposalStatus::Passed
+    } else {
+        ProposalStatus::Rejected
+    };
+
+    let mut stats = GOVERNANCE_STATS.may_load(storage)?.unwrap_or_default();
+    stats.concluded_proposals += 1;
+    if proposal.status == ProposalStatus::Passed {
+        stats.passed_proposals += 1;
+    } else {
+        stats.rejected_proposals += 1;
+    }
+    stats.turnout_sum += proposal_quorum;
+    GOVERNANCE_STATS.save(storage, &stats)?;
+    PROPOSAL_TURNOUT.save(storage, proposal.proposal_id.u64(), &proposal_quorum)?;
+    ACTIVE_PROPOSALS.remove(
+        storage,
+        (proposal.voting_end_block(), proposal.proposal_id.u64()),
+    );
+
+    Ok(refund_deposit_msg(config, proposal))
+}
+
+/// Returns a message which refunds the proposal deposit to the submitter.
+/// The caller must ensure that the proposal is concluded or withdrawn.
+/// Scheduled proposals are opened without a deposit, thus there is nothing to refund.
+fn refund_deposit_msg(config: &Config, proposal: &Proposal) -> Option<BankMsg> {
+    let refund = proposal.refunded_deposit();
+    (!refund.is_zero()).then(|| BankMsg::Send {
+        to_address: proposal.submitter.to_string(),
+        amount: coins(refund.into(), &config.xastro_denom),
+    })
+}
+
 /// Executes a successful proposal by id.
 pub fn execute_proposal(
     deps: DepsMut,
@@ -415,6 +1132,7 @@ pub fn execute_proposal(
         return Err(ContractError::ProposalDelayNotEnded {});
     }
 
+    let config = CONFIG.load(deps.storage)?;
     let mut response = Response::new().add_attributes([
         attr("action", "execute_proposal"),
         attr("proposal_id", proposal_id.to_string()),
@@ -424,12 +1142,11 @@ pub fn execute_proposal(
         proposal.status = ProposalStatus::Expired;
     } else if let Some(channel) = &proposal.ibc_channel {
         if !proposal.messages.is_empty() {
-            let config = CONFIG.load(deps.storage)?;
-
             proposal.status = ProposalStatus::InProgress;
             response.messages.push(SubMsg::new(wasm_execute(
                 config
                     .ibc_controller
+                    .as_ref()
                     .ok_or(ContractError::MissingIBCController {})?,
                 &ControllerExecuteMsg::IbcExecuteProposal {
                     channel_id: channel.to_string(),
@@ -450,7 +1167,171 @@ pub fn execute_proposal(
 
     PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
 
-    Ok(response.add_attribute("proposal_status", proposal.status.to_string()))
+    Ok(response
+        .add_submessages(notify_outposts_msg(&config, proposal_id)?)
+        .add_attribute("proposal_status", proposal.status.to_string()))
+}
+
+/// Returns a message which notifies subscribed outposts about the proposal status change
+/// if the Emissions Controller is set.
+/// Notification failures (including running out of gas) never revert the proposal lifecycle.
+pub fn notify_outposts_msg(config: &Config, proposal_id: u64) -> StdResult<Option<SubMsg>> {
+    config
+        .emissions_controller
+        .as_ref()
+        .map(|emissions_controller| {
+            wasm_execute(
+                emissions_controller,
+                &emissions_controller::msg::ExecuteMsg::Custom(HubMsg::NotifyProposalStatus {
+                    proposal_id,
+                }),
+                vec![],
+            )
+            .map(|msg| {
+                SubMsg::reply_on_error(msg, NOTIFY_OUTPOSTS_REPLY_ID)
+                    .with_gas_limit(NOTIFY_OUTPOSTS_GAS_LIMIT)
+            })
+        })
+        .transpose()
+}
+
+/// Returns messages which notify enabled conclusion hooks matching the concluded proposal.
+/// Hook failures never revert the proposal lifecycle.
+pub fn conclusion_hook_msgs(storage: &dyn Storage, proposal: &Proposal) -> StdResult<Vec<SubMsg>> {
+    let hook_msg = to_json_binary(&ConclusionHookMsg::ProposalConcluded {
+        proposal_id: proposal.proposal_id.u64(),
+        status: proposal.status.clone(),
+        tags: proposal.tags.clone(),
+        impact_class: proposal.impact_class,
+    })?;
+
+    CONCLUSION_HOOKS
+        .range(storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((contract, hook)) if hook.enabled && hook.filter.matches(proposal) => {
+                Some(Ok(SubMsg::reply_on_error(
+                    WasmMsg::Execute {
+                        contract_addr: contract.to_string(),
+                        msg: hook_msg.clone(),
+                        funds: vec![],
+                    },
+                    CONCLUSION_HOOK_REPLY_ID,
+                )
+                .with_gas_limit(EXTERNAL_CALL_GAS_LIMIT)))
+            }
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        })
+        .collect()
+}
+
+/// Pauses registered targets of a live critical proposal.
+/// Only proposals executed locally and labeled with [`CRITICAL_PROPOSAL_TAG`] pause their targets.
+/// A target is paused once even if several live proposals target it.
+/// Pause failures (including running out of gas) never revert the proposal submission.
+pub fn pause_proposal_targets(
+    storage: &mut dyn Storage,
+    proposal: &Proposal,
+) -> StdResult<Vec<SubMsg>> {
+    if proposal.ibc_channel.is_some()
+        || !proposal.tags.iter().any(|tag| tag == CRITICAL_PROPOSAL_TAG)
+    {
+        return Ok(vec![]);
+    }
+
+    let proposal_id = proposal.proposal_id.u64();
+    let mut targets: Vec<Addr> = vec![];
+    let mut messages = vec![];
+
+    for msg in &proposal.messages {
+        let contract_addr = match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. })
+            | CosmosMsg::Wasm(WasmMsg::Migrate { contract_addr, .. })
+            | CosmosMsg::Wasm(WasmMsg::UpdateAdmin { contract_addr, .. })
+            | CosmosMsg::Wasm(WasmMsg::ClearAdmin { contract_addr }) => {
+                Addr::unchecked(contract_addr)
+            }
+            _ => continue,
+        };
+        if targets.contains(&contract_addr) {
+            continue;
+        }
+        let target = match PAUSABLE_TARGETS.may_load(storage, &contract_addr)? {
+            Some(target) => target,
+            None => continue,
+        };
+
+        let mut paused = PAUSED_TARGETS
+            .may_load(storage, &contract_addr)?
+            .unwrap_or_else(|| PausedTarget {
+                proposals: vec![],
+                unpause_msg: target.unpause_msg.clone(),
+            });
+        if paused.proposals.is_empty() {
+            messages.push(
+                SubMsg::reply_on_error(
+                    WasmMsg::Execute {
+                        contract_addr: contract_addr.to_string(),
+                        msg: target.pause_msg,
+                        funds: vec![],
+                    },
+                    PAUSABLE_TARGET_REPLY_ID,
+                )
+                .with_gas_limit(EXTERNAL_CALL_GAS_LIMIT),
+            );
+        }
+        paused.proposals.push(proposal_id);
+        PAUSED_TARGETS.save(storage, &contract_addr, &paused)?;
+
+        targets.push(contract_addr);
+    }
+
+    if !targets.is_empty() {
+        PROPOSAL_PAUSED_TARGETS.save(storage, proposal_id, &targets)?;
+    }
+
+    Ok(messages)
+}
+
+/// Releases targets paused on behalf of the concluded proposal.
+/// A target is unpaused once no other live proposal holds it paused.
+/// Unpause failures (including running out of gas) never revert the proposal lifecycle.
+pub fn unpause_proposal_targets(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+) -> StdResult<Vec<SubMsg>> {
+    let mut messages = vec![];
+
+    for contract_addr in PROPOSAL_PAUSED_TARGETS
+        .may_load(storage, proposal_id)?
+        .unwrap_or_default()
+    {
+        let mut paused = match PAUSED_TARGETS.may_load(storage, &contract_addr)? {
+            Some(paused) => paused,
+            None => continue,
+        };
+        paused.proposals.retain(|id| *id != proposal_id);
+
+        if paused.proposals.is_empty() {
+            messages.push(
+                SubMsg::reply_on_error(
+                    WasmMsg::Execute {
+                        contract_addr: contract_addr.to_string(),
+                        msg: paused.unpause_msg,
+                        funds: vec![],
+                    },
+                    PAUSABLE_TARGET_REPLY_ID,
+                )
+                .with_gas_limit(EXTERNAL_CALL_GAS_LIMIT),
+            );
+            PAUSED_TARGETS.remove(storage, &contract_addr);
+        } else {
+            PAUSED_TARGETS.save(storage, &contract_addr, &paused)?;
+        }
+    }
+    PROPOSAL_PAUSED_TARGETS.remove(storage, proposal_id);
+
+    Ok(messages)
 }
 
 /// Checks that proposal messages are correct.
@@ -591,6 +1472,72 @@ pub fn update_config(
         }
     }
 
+    if let Some(include_builder_vp) = updated_config.include_builder_vp {
+        config.include_builder_vp = include_builder_vp;
+        attrs.push(attr(
+            "new_include_builder_vp",
+            include_builder_vp.to_string(),
+        ));
+    }
+
+    if let Some(proposal_reveal_period) = updated_config.proposal_reveal_period {
+        config.proposal_reveal_period = proposal_reveal_period;
+        attrs.push(attr(
+            "new_proposal_reveal_period",
+            proposal_reveal_period.to_string(),
+        ));
+    }
+
+    if let Some(min_xastro_age) = updated_config.min_xastro_age {
+        config.min_xastro_age = min_xastro_age;
+        attrs.push(attr("new_min_xastro_age", min_xastro_age.to_string()));
+    }
+
+    if let Some(proposal_review_period) = updated_config.proposal_review_period {
+        config.proposal_review_period = proposal_review_period;
+        attrs.push(attr(
+            "new_proposal_review_period",
+            proposal_review_period.to_string(),
+        ));
+    }
+
+    if let Some(tags_add) = updated_config.tags_add {
+        validate_tags(&tags_add)?;
+
+        let mut new_tags = vec![];
+        for tag in tags_add {
+            if !config.proposal_tags.contains(&tag) && !new_tags.contains(&tag) {
+                new_tags.push(tag);
+            }
+        }
+
+        if !new_tags.is_empty() {
+            attrs.push(attr("new_proposal_tags", new_tags.join(", ")));
+        }
+
+        config.proposal_tags.append(&mut new_tags);
+    }
+
+    if let Some(tags_remove) = updated_config.tags_remove {
+        config
+            .proposal_tags
+            .retain(|tag| !tags_remove.contains(tag));
+
+        if !tags_remove.is_empty() {
+            attrs.push(attr("removed_proposal_tags", tags_remove.join(", ")));
+        }
+    }
+
+    if let Some(impact_classes) = updated_config.impact_classes {
+        attrs.push(attr("new_impact_classes", to_json_string(&impact_classes)?));
+        config.impact_classes = impact_classes;
+    }
+
+    if let Some(quorum_mode) = updated_config.quorum_mode {
+        attrs.push(attr("new_quorum_mode", to_json_string(&quorum_mode)?));
+        config.quorum_mode = quorum_mode;
+    }
+
     if let Some(vxastro) = updated_config.vxastro {
         let emissions_controller = deps
             .querier
@@ -641,7 +1588,9 @@ fn update_ibc_proposal_status(
             ProposalStatus::Executed {} | ProposalStatus::Failed {} => {
                 proposal.status = new_status;
                 PROPOSALS.save(deps.storage, id, &proposal)?;
-                Ok(Response::new().add_attribute("action", "ibc_proposal_completed"))
+                Ok(Response::new()
+                    .add_submessages(notify_outposts_msg(&config, id)?)
+                    .add_attribute("action", "ibc_proposal_completed"))
             }
             _ => Err(ContractError::InvalidRemoteIbcProposalStatus(
                 new_status.to_string(),
@@ -672,3 +1621,715 @@ pub fn exec_from_multisig(
 
     Ok(Response::new().add_messages(messages))
 }
+
+/// Registers a migration plan and starts its execution.
+/// Steps are executed one by one as submessages. Each step outcome is recorded in the state.
+/// Only the Assembly itself is allowed to call this function (through a successful proposal).
+pub fn register_migration_plan(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    steps: Vec<MigrationStep>,
+) -> Result<Response, ContractError> {
+    ensure!(
+        info.sender == env.contract.address,
+        ContractError::Unauthorized {}
+    );
+    ensure!(!steps.is_empty(), ContractError::EmptyMigrationPlan {});
+
+    for step in &steps {
+        ensure!(
+            deps.api.addr_validate(&step.contract_addr)? != env.contract.address,
+            StdError::generic_err("Assembly can't migrate itself within a migration plan")
+        );
+    }
+
+    let plan_id = MIGRATION_PLAN_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    MIGRATION_PLAN_COUNT.save(deps.storage, &plan_id)?;
+
+    let first_step_msg = migration_step_msg(&steps[0]);
+    let plan = MigrationPlan {
+        plan_id,
+        status: MigrationPlanStatus::InProgress,
+        steps: steps
+            .into_iter()
+            .map(|step| (step, MigrationStepStatus::Pending))
+            .collect(),
+    };
+
+    MIGRATION_PLANS.save(deps.storage, plan_id, &plan)?;
+    ACTIVE_MIGRATION_PLAN.save(deps.storage, &plan_id)?;
+
+    Ok(Response::new()
+        .add_submessage(first_step_msg)
+        .add_attributes([
+            attr("action", "register_migration_plan"),
+            attr("plan_id", plan_id.to_string()),
+            attr("steps", plan.steps.len().to_string()),
+        ]))
+}
+
+/// Adds or removes governable parameters.
+/// Only the Assembly itself is allowed to call this function (through a successful proposal).
+pub fn update_param_registry(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    add: Vec<GovernableParam>,
+    remove: Vec<(String, String)>,
+) -> Result<Response, ContractError> {
+    ensure!(
+        info.sender == env.contract.address,
+        ContractError::Unauthorized {}
+    );
+
+    for (contract, key) in &remove {
+        let contract = deps.api.addr_validate(contract)?.to_string();
+        ensure!(
+            PARAM_REGISTRY.has(deps.storage, (contract.as_str(), key.as_str())),
+            ContractError::ParamNotRegistered {
+                contract,
+                key: key.clone(),
+            }
+        );
+        PARAM_REGISTRY.remove(deps.storage, (contract.as_str(), key.as_str()));
+    }
+
+    let added = add.len();
+    for param in add {
+        let contract = deps.api.addr_validate(&param.contract)?.to_string();
+        ensure!(
+            is_valid_param_key(&param.key)
+                && !param.msg_path.is_empty()
+                && param.msg_path.iter().all(|key| is_valid_param_key(key))
+                && param.kind.has_valid_bounds(),
+            ContractError::InvalidGovernableParam {
+                contract,
+                key: param.key,
+            }
+        );
+
+        let key = param.key.clone();
+        PARAM_REGISTRY.save(
+            deps.storage,
+            (contract.as_str(), key.as_str()),
+            &GovernableParam {
+                contract: contract.clone(),
+                ..param
+            },
+        )?;
+    }
+
+    Ok(Response::new().add_attributes([
+        attr("action", "update_param_registry"),
+        attr("added", added.to_string()),
+        attr("removed", remove.len().to_string()),
+    ]))
+}
+
+/// Adds or removes vote relayers.
+/// Only the Assembly itself is allowed to call this function (through a successful proposal).
+pub fn update_vote_relayers(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    add: Vec<VoteRelayer>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    ensure!(
+        info.sender == env.contract.address,
+        ContractError::Unauthorized {}
+    );
+
+    for relayer in &remove {
+        let relayer = deps.api.addr_validate(relayer)?;
+        VOTE_RELAYERS.remove(deps.storage, &relayer);
+    }
+
+    for relayer in &add {
+        let address = deps.api.addr_validate(&relayer.address)?;
+        VOTE_RELAYERS.save(
+            deps.storage,
+            &address,
+            &VoteRelayerInfo {
+                quota: relayer.quota,
+                usage: Default::default(),
+                fee_grant: relayer.fee_grant.clone(),
+            },
+        )?;
+    }
+
+    Ok(Response::new().add_attributes([
+        attr("action", "update_vote_relayers"),
+        attr("added", add.len().to_string()),
+        attr("removed", remove.len().to_string()),
+    ]))
+}
+
+/// Adds or removes contracts paused while a critical proposal targeting them is live.
+/// Removing a target doesn't unpause it if it is currently paused,
+/// it is unpaused with the stored message once the holding proposals conclude.
+pub fn update_pausable_targets(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    add: Vec<PausableTarget>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    ensure!(
+        info.sender == env.contract.address,
+        ContractError::Unauthorized {}
+    );
+
+    for contract in &remove {
+        let contract = deps.api.addr_validate(contract)?;
+        PAUSABLE_TARGETS.remove(deps.storage, &contract);
+    }
+
+    let added = add.len();
+    for target in add {
+        let contract = deps.api.addr_validate(&target.contract)?;
+        PAUSABLE_TARGETS.save(
+            deps.storage,
+            &contract,
+            &PausableTargetInfo {
+                pause_msg: target.pause_msg,
+                unpause_msg: target.unpause_msg,
+            },
+        )?;
+    }
+
+    Ok(Response::new().add_attributes([
+        attr("action", "update_pausable_targets"),
+        attr("added", added.to_string()),
+        attr("removed", remove.len().to_string()),
+    ]))
+}
+
+/// Adds or removes contracts notified when a matching proposal concludes.
+/// Only the Assembly itself is allowed to call this function.
+pub fn update_conclusion_hooks(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    add: Vec<ConclusionHook>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    ensure!(
+        info.sender == env.contract.address,
+        ContractError::Unauthorized {}
+    );
+
+    for contract in &remove {
+        let contract = deps.api.addr_validate(contract)?;
+        CONCLUSION_HOOKS.remove(deps.storage, &contract);
+    }
+
+    let added = add.len();
+    for hook in add {
+        let contract = deps.api.addr_validate(&hook.contract)?;
+        validate_tags(&hook.filter.tags)?;
+        CONCLUSION_HOOKS.save(
+            deps.storage,
+            &contract,
+            &ConclusionHookInfo {
+                filter: hook.filter,
+                enabled: hook.enabled,
+            },
+        )?;
+    }
+
+    let hooks_count = CONCLUSION_HOOKS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count();
+    ensure!(
+        hooks_count <= MAX_CONCLUSION_HOOKS,
+        ContractError::TooManyConclusionHooks {
+            max: MAX_CONCLUSION_HOOKS
+        }
+    );
+
+    Ok(Response::new().add_attributes([
+        attr("action", "update_conclusion_hooks"),
+        attr("added", added.to_string()),
+        attr("removed", remove.len().to_string()),
+    ]))
+}
+
+/// Enables or disables a registered conclusion hook.
+/// Only the Assembly itself is allowed to call this function.
+pub fn set_conclusion_hook_enabled(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    ensure!(
+        info.sender == env.contract.address,
+        ContractError::Unauthorized {}
+    );
+
+    let contract = deps.api.addr_validate(&contract)?;
+    let mut hook = CONCLUSION_HOOKS
+        .may_load(deps.storage, &contract)?
+        .ok_or_else(|| ContractError::ConclusionHookNotFound(contract.to_string()))?;
+    hook.enabled = enabled;
+    CONCLUSION_HOOKS.save(deps.storage, &contract, &hook)?;
+
+    Ok(Response::new().add_attributes([
+        attr("action", "set_conclusion_hook_enabled"),
+        attr("contract", contract),
+        attr("enabled", enabled.to_string()),
+    ]))
+}
+
+/// Adds a follow-up proposal to the queue. The proposal is opened by anyone
+/// with [`ExecuteMsg::OpenScheduledProposal`] once **open_at** is reached.
+pub fn schedule_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    open_at: u64,
+    template: ProposalTemplate,
+) -> Result<Response, ContractError> {
+    ensure!(
+        info.sender == env.contract.address,
+        ContractError::Unauthorized {}
+    );
+    ensure!(
+        open_at > env.block.time.seconds(),
+        ContractError::InvalidScheduleTime {}
+    );
+
+    let config = CONFIG.load(deps.storage)?;
+    validate_proposal_tags(&config, &template.tags)?;
+
+    let id = SCHEDULED_PROPOSAL_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    SCHEDULED_PROPOSAL_COUNT.save(deps.storage, &id)?;
+    SCHEDULED_PROPOSALS.save(
+        deps.storage,
+        id,
+        &ScheduledProposal {
+            id,
+            open_at,
+            template,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes([
+        attr("action", "schedule_proposal"),
+        attr("scheduled_id", id.to_string()),
+        attr("open_at", open_at.to_string()),
+    ]))
+}
+
+/// Opens a scheduled proposal on behalf of the Assembly without a deposit.
+/// Tags are validated again as the vocabulary might have changed since scheduling.
+pub fn open_scheduled_proposal(
+    deps: DepsMut,
+    env: Env,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let scheduled = SCHEDULED_PROPOSALS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::ScheduledProposalNotFound(id))?;
+    ensure!(
+        env.block.time.seconds() >= scheduled.open_at,
+        ContractError::ScheduledProposalNotReady(scheduled.open_at)
+    );
+    SCHEDULED_PROPOSALS.remove(deps.storage, id);
+
+    let config = CONFIG.load(deps.storage)?;
+    validate_proposal_tags(&config, &scheduled.template.tags)?;
+
+    let submitter = env.contract.address.clone();
+    open_proposal(
+        deps,
+        &env,
+        &config,
+        submitter,
+        Uint128::zero(),
+        scheduled.template,
+    )
+    .map(|response| response.add_attribute("scheduled_id", id.to_string()))
+}
+
+/// Removes a scheduled proposal from the queue.
+pub fn cancel_scheduled_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    ensure!(
+        info.sender == env.contract.address,
+        ContractError::Unauthorized {}
+    );
+    ensure!(
+        SCHEDULED_PROPOSALS.has(deps.storage, id),
+        ContractError::ScheduledProposalNotFound(id)
+    );
+    SCHEDULED_PROPOSALS.remove(deps.storage, id);
+
+    Ok(Response::new().add_attributes([
+        attr("action", "cancel_scheduled_proposal"),
+        attr("scheduled_id", id.to_string()),
+    ]))
+}
+
+/// Registers the secp256k1 public key the sender signs relayed votes with.
+/// Either compressed (33 bytes) or uncompressed (65 bytes) keys are accepted.
+pub fn set_relay_pubkey(
+    deps: DepsMut,
+    info: MessageInfo,
+    pubkey: Binary,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    ensure!(
+        matches!(pubkey.len(), 33 | 65),
+        ContractError::InvalidRelayPubkey {}
+    );
+    RELAY_PUBKEYS.save(deps.storage, &info.sender, &pubkey)?;
+
+    Ok(Response::new().add_attributes([
+        attr("action", "set_relay_pubkey"),
+        attr("user", info.sender),
+    ]))
+}
+
+/// Casts a vote on behalf of a user. The signature must be made by the user's registered public key.
+/// Relayed votes are limited by the relayer quota and [`MAX_USER_RELAYED_VOTES`] per user
+/// within [`RELAY_QUOTA_PERIOD`].
+///
+/// The nonce must match the user's current relay nonce. It is incremented on every relayed vote.
+/// The signature is made over [`RelayVotePayload::digest`].
+pub fn relay_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    RelayedVote {
+        voter,
+        proposal_id,
+        vote,
+        nonce,
+        signature,
+    }: RelayedVote,
+) -> Result<Response, ContractError> {
+    let mut relayer = VOTE_RELAYERS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::Unauthorized {})?;
+    let voter = deps.api.addr_validate(&voter)?;
+    let block_ts = env.block.time.seconds();
+
+    let pubkey = RELAY_PUBKEYS
+        .may_load(deps.storage, &voter)?
+        .ok_or_else(|| ContractError::RelayPubkeyNotSet(voter.to_string()))?;
+    let digest = RelayVotePayload {
+        chain_id: env.block.chain_id.clone(),
+        assembly: env.contract.address.to_string(),
+        voter: voter.to_string(),
+        proposal_id,
+        vote: vote.clone(),
+        nonce,
+    }
+    .digest()?;
+    // Malformed signatures are rejected the same way as mismatching ones
+    let verified = deps
+        .api
+        .secp256k1_verify(&digest, &signature, &pubkey)
+        .unwrap_or_default();
+    ensure!(verified, ContractError::InvalidRelaySignature {});
+
+    let expected = RELAY_NONCES
+        .may_load(deps.storage, &voter)?
+        .unwrap_or_default();
+    ensure!(
+        nonce == expected,
+        ContractError::InvalidRelayNonce { expected }
+    );
+    RELAY_NONCES.save(deps.storage, &voter, &(nonce + 1))?;
+
+    ensure!(
+        relayer.usage.try_consume(block_ts, relayer.quota),
+        ContractError::RelayerQuotaExceeded {}
+    );
+    VOTE_RELAYERS.save(deps.storage, &info.sender, &relayer)?;
+
+    let mut user_usage = USER_RELAY_USAGE
+        .may_load(deps.storage, &voter)?
+        .unwrap_or_default();
+    ensure!(
+        user_usage.try_consume(block_ts, MAX_USER_RELAYED_VOTES),
+        ContractError::UserRelayLimitExceeded(voter.to_string())
+    );
+    USER_RELAY_USAGE.save(deps.storage, &voter, &user_usage)?;
+
+    let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+    let breakdown = calc_voting_power_breakdown(deps.as_ref(), voter.to_string(), &proposal)?;
+    ensure!(
+        !breakdown.total().is_zero(),
+        ContractError::NoVotingPower {}
+    );
+
+    cast_vote(
+        deps.storage,
+        env,
+        voter.to_string(),
+        breakdown,
+        proposal_id,
+        proposal,
+        vote,
+    )
+    .map(|response| {
+        response.add_attributes([
+            attr("relayer", info.sender),
+            attr("nonce", nonce.to_string()),
+        ])
+    })
+}
+
+/// Grants a fee allowance from the Assembly to a user, limited by the relayer's `fee_grant`.
+/// The allowance expires after [`RELAY_QUOTA_PERIOD`]. Each grant consumes the relayer quota.
+pub fn grant_relay_fee_allowance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    grantee: String,
+) -> Result<Response, ContractError> {
+    let mut relayer = VOTE_RELAYERS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::Unauthorized {})?;
+    ensure!(
+        !relayer.fee_grant.is_empty(),
+        ContractError::FeeGrantDisabled {}
+    );
+    let grantee = deps.api.addr_validate(&grantee)?;
+    let block_ts = env.block.time.seconds();
+
+    ensure!(
+        relayer.usage.try_consume(block_ts, relayer.quota),
+        ContractError::RelayerQuotaExceeded {}
+    );
+    VOTE_RELAYERS.save(deps.storage, &info.sender, &relayer)?;
+
+    let grant_msg = fee_allowance_msg(
+        &env.contract.address,
+        &grantee,
+        &relayer.fee_grant,
+        block_ts + RELAY_QUOTA_PERIOD,
+    );
+
+    Ok(Response::new().add_message(grant_msg).add_attributes([
+        attr("action", "grant_relay_fee_allowance"),
+        attr("relayer", info.sender),
+        attr("grantee", grantee),
+    ]))
+}
+
+/// Parameter keys are fields of the target contracts' messages, thus only snake_case keys are allowed.
+fn is_valid_param_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Update message of a target contract rendered from parameter changes.
+/// Parameter values are serialized as plain JSON values, nested messages as JSON objects.
+#[derive(Serialize)]
+#[serde(crate = "cosmwasm_schema::serde", untagged)]
+enum RenderedMsg {
+    U64(u64),
+    Uint128(Uint128),
+    Decimal(Decimal),
+    Bool(bool),
+    Fields(BTreeMap<String, RenderedMsg>),
+}
+
+impl From<&ParamValue> for RenderedMsg {
+    fn from(value: &ParamValue) -> Self {
+        match value {
+            ParamValue::U64(value) => RenderedMsg::U64(*value),
+            ParamValue::Uint128(value) => RenderedMsg::Uint128(*value),
+            ParamValue::Decimal(value) => RenderedMsg::Decimal(*value),
+            ParamValue::Bool(value) => RenderedMsg::Bool(*value),
+        }
+    }
+}
+
+/// Validates parameter changes against the registry and renders them into the target contracts'
+/// update messages. Changes of the same contract and message are batched into a single message.
+/// Then submits a regular proposal with rendered messages.
+pub fn submit_param_change_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    link: Option<String>,
+    changes: Vec<ParamChange>,
+) -> Result<Response, ContractError> {
+    ensure!(!changes.is_empty(), ContractError::EmptyParamChanges {});
+
+    let mut changed = HashSet::new();
+    // (contract, message path, rendered fields)
+    let mut updates: Vec<(String, Vec<String>, BTreeMap<String, RenderedMsg>)> = vec![];
+    for change in &changes {
+        let contract = deps.api.addr_validate(&change.contract)?.to_string();
+        let key = change.key.clone();
+        ensure!(
+            changed.insert((contract.clone(), key.clone())),
+            ContractError::DuplicatedParamChange { contract, key }
+        );
+
+        let Some(param) =
+            PARAM_REGISTRY.may_load(deps.storage, (contract.as_str(), key.as_str()))?
+        else {
+            return Err(ContractError::ParamNotRegistered { contract, key });
+        };
+        match param.kind.is_within_bounds(&change.value) {
+            Some(true) => {}
+            Some(false) => return Err(ContractError::ParamOutOfBounds { contract, key }),
+            None => return Err(ContractError::ParamTypeMismatch { contract, key }),
+        }
+
+        let value = RenderedMsg::from(&change.value);
+        match updates
+            .iter_mut()
+            .find(|(addr, msg_path, _)| *addr == contract && *msg_path == param.msg_path)
+        {
+            Some((_, _, fields)) => {
+                fields.insert(key, value);
+            }
+            None => updates.push((contract, param.msg_path, BTreeMap::from([(key, value)]))),
+        }
+    }
+
+    let messages = updates
+        .into_iter()
+        .map(|(contract_addr, msg_path, fields)| {
+            let msg = msg_path
+                .into_iter()
+                .rev()
+                .fold(RenderedMsg::Fields(fields), |inner, key| {
+                    RenderedMsg::Fields(BTreeMap::from([(key, inner)]))
+                });
+
+            Ok(WasmMsg::Execute {
+                contract_addr,
+                msg: to_json_binary(&msg)?,
+                funds: vec![],
+            }
+            .into())
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    submit_proposal(
+        deps,
+        env,
+        info,
+        ProposalTemplate {
+            title,
+            description,
+            link,
+            messages,
+            ibc_channel: None,
+            commit_reveal: false,
+            tags: vec![],
+        },
+    )
+    .map(|response| response.add_attribute("param_changes", changes.len().to_string()))
+}
+
+fn migration_step_msg(step: &MigrationStep) -> SubMsg {
+    SubMsg::reply_always(
+        WasmMsg::Migrate {
+            contract_addr: step.contract_addr.clone(),
+            new_code_id: step.new_code_id,
+            msg: step.msg.clone(),
+        },
+        MIGRATION_STEP_REPLY_ID,
+    )
+}
+
+/// Processes the outcome of a migration step.
+/// On success, dispatches the next pending step or marks the plan as completed.
+/// On failure, halts the plan and marks all remaining steps as skipped.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg {
+        Reply {
+            id: MIGRATION_STEP_REPLY_ID,
+            result,
+        } => {
+            let plan_id = ACTIVE_MIGRATION_PLAN.load(deps.storage)?;
+            let mut plan = MIGRATION_PLANS.load(deps.storage, plan_id)?;
+
+            let step_ind = plan
+                .steps
+                .iter()
+                .position(|(_, status)| *status == MigrationStepStatus::Pending)
+                .ok_or(ContractError::FailedToParseReply {})?;
+
+            let mut response = Response::new().add_attributes([
+                attr("action", "migration_step"),
+                attr("plan_id", plan_id.to_string()),
+                attr("step", step_ind.to_string()),
+                attr("contract", &plan.steps[step_ind].0.contract_addr),
+            ]);
+
+            match result {
+                SubMsgResult::Ok(_) => {
+                    plan.steps[step_ind].1 = MigrationStepStatus::Succeeded;
+                    response = response.add_attribute("result", "succeeded");
+
+                    if let Some((next_step, _)) = plan.steps.get(step_ind + 1) {
+                        response = response.add_submessage(migration_step_msg(next_step));
+                    } else {
+                        plan.status = MigrationPlanStatus::Completed;
+                        ACTIVE_MIGRATION_PLAN.remove(deps.storage);
+                    }
+                }
+                SubMsgResult::Err(error) => {
+                    response = response.add_attribute("result", format!("failed: {error}"));
+                    plan.steps[step_ind].1 = MigrationStepStatus::Failed { error };
+                    plan.steps
+                        .iter_mut()
+                        .skip(step_ind + 1)
+                        .for_each(|(_, status)| *status = MigrationStepStatus::Skipped);
+                    plan.status = MigrationPlanStatus::Halted;
+                    ACTIVE_MIGRATION_PLAN.remove(deps.storage);
+                }
+            }
+
+            MIGRATION_PLANS.save(deps.storage, plan_id, &plan)?;
+
+            Ok(response.add_attribute("plan_status", format!("{:?}", plan.status)))
+        }
+        Reply {
+            id: NOTIFY_OUTPOSTS_REPLY_ID,
+            result: SubMsgResult::Err(err),
+        } => Ok(Response::new()
+            .add_attributes([attr("action", "notify_outposts_failed"), attr("error", err)])),
+        Reply {
+            id: PAUSABLE_TARGET_REPLY_ID,
+            result: SubMsgResult::Err(err),
+        } => Ok(Response::new().add_attributes([
+            attr("action", "pausable_target_call_failed"),
+            attr("error", err),
+        ])),
+        Reply {
+            id: CONCLUSION_HOOK_REPLY_ID,
+            result: SubMsgResult::Err(err),
+        } => Ok(Response::new()
+            .add_attributes([attr("action", "conclusion_hook_failed"), attr("error", err)])),
+        _ => Err(ContractError::FailedToParseReply {}),
+    }
+}
@@ -23,6 +23,18 @@ pub enum ContractError {
     #[error("Proposal not active!")]
     ProposalNotActive {},
 
+    #[error("Voting has not started yet!")]
+    VotingNotStarted {},
+
+    #[error("Proposal is not in the review window!")]
+    ProposalNotInReview {},
+
+    #[error("Proposal can be amended only once")]
+    ProposalAlreadyAmended {},
+
+    #[error("Amendment must change the description or messages")]
+    EmptyProposalAmendment {},
+
     #[error("Voting period ended!")]
     VotingPeriodEnded {},
 
@@ -38,6 +50,9 @@ pub enum ContractError {
     #[error("Insufficient token deposit!")]
     InsufficientDeposit {},
 
+    #[error("The required deposit amount of xASTRO must be held for at least {min_age} seconds")]
+    InsufficientXastroAge { min_age: u64 },
+
     #[error("Proposal not passed!")]
     ProposalNotPassed {},
 
@@ -47,6 +62,15 @@ pub enum ContractError {
     #[error("Whitelist cannot be empty!")]
     WhitelistEmpty {},
 
+    #[error("Proposal tag {tag} is not in the vocabulary")]
+    UnknownProposalTag { tag: String },
+
+    #[error("Proposal tag {tag} is duplicated")]
+    DuplicateProposalTag { tag: String },
+
+    #[error("Proposal can have at most {max} tags")]
+    TooManyProposalTags { max: usize },
+
     #[error("Messages check passed. Nothing was committed to the blockchain")]
     MessagesCheckPassed {},
 
@@ -70,4 +94,88 @@ pub enum ContractError {
 
     #[error("Failed to migrate contract")]
     MigrationError {},
+
+    #[error("Migration plan must contain at least one step")]
+    EmptyMigrationPlan {},
+
+    #[error("Failed to parse reply")]
+    FailedToParseReply {},
+
+    #[error("Proposal uses commit-reveal voting. Commit a hidden vote instead")]
+    CommitRevealProposal {},
+
+    #[error("Proposal doesn't use commit-reveal voting")]
+    NotCommitRevealProposal {},
+
+    #[error("Vote commitment must be a 32 bytes hash")]
+    InvalidCommitment {},
+
+    #[error("Reveal period is not active!")]
+    RevealPeriodNotActive {},
+
+    #[error("Vote commitment not found")]
+    CommitmentNotFound {},
+
+    #[error("Revealed vote doesn't match the commitment")]
+    InvalidVoteReveal {},
+
+    #[error("Invalid governable parameter {key} of {contract}")]
+    InvalidGovernableParam { contract: String, key: String },
+
+    #[error("Parameter {key} of {contract} is not registered")]
+    ParamNotRegistered { contract: String, key: String },
+
+    #[error("Value type doesn't match the type of parameter {key} of {contract}")]
+    ParamTypeMismatch { contract: String, key: String },
+
+    #[error("Value is out of bounds of parameter {key} of {contract}")]
+    ParamOutOfBounds { contract: String, key: String },
+
+    #[error("Parameter {key} of {contract} is changed more than once")]
+    DuplicatedParamChange { contract: String, key: String },
+
+    #[error("Parameter change proposal must contain at least one change")]
+    EmptyParamChanges {},
+
+    #[error("Invalid relay nonce. Expected: {expected}")]
+    InvalidRelayNonce { expected: u64 },
+
+    #[error("Relayer quota is exhausted for the current period")]
+    RelayerQuotaExceeded {},
+
+    #[error("Relay public key must be a 33 or 65 bytes secp256k1 key")]
+    InvalidRelayPubkey {},
+
+    #[error("{0} hasn't registered a relay public key")]
+    RelayPubkeyNotSet(String),
+
+    #[error("Invalid relayed vote signature")]
+    InvalidRelaySignature {},
+
+    #[error("Relayer isn't allowed to grant fee allowances")]
+    FeeGrantDisabled {},
+
+    #[error("Too many votes relayed on behalf of {0} in the current period")]
+    UserRelayLimitExceeded(String),
+
+    #[error("Proposal not found")]
+    ProposalNotFound {},
+
+    #[error("Vote batch must contain from 1 to {max} votes")]
+    InvalidVoteBatch { max: usize },
+
+    #[error("Scheduled proposal must be opened in the future")]
+    InvalidScheduleTime {},
+
+    #[error("Scheduled proposal {0} not found")]
+    ScheduledProposalNotFound(u64),
+
+    #[error("Scheduled proposal can't be opened before {0}")]
+    ScheduledProposalNotReady(u64),
+
+    #[error("Can't register more than {max} conclusion hooks")]
+    TooManyConclusionHooks { max: usize },
+
+    #[error("Conclusion hook {0} not found")]
+    ConclusionHookNotFound(String),
 }
@@ -1,6 +1,7 @@
 pub mod contract;
 pub mod error;
 pub mod state;
+pub mod sudo;
 
 /// Exclusively to bypass wasmd migration limitation. Assembly doesn't have IBC features.
 /// https://github.com/CosmWasm/wasmd/blob/7165e41cbf14d60a9fef4fb1e04c2c2e5e4e0cf4/x/wasm/keeper/keeper.go#L446
@@ -11,7 +11,7 @@ use astroport_governance::assembly::{Proposal, ProposalStatus};
 
 use crate::contract::{CONTRACT_NAME, CONTRACT_VERSION};
 use crate::error::ContractError;
-use crate::state::PROPOSALS;
+use crate::state::{ACTIVE_PROPOSALS, PROPOSALS};
 
 #[cw_serde]
 pub struct OldProposal {
@@ -68,18 +68,37 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, Contra
                         deposit_amount: old_proposal.deposit_amount,
                         ibc_channel: old_proposal.ibc_channel,
                         total_voting_power: old_proposal.total_voting_power,
+                        impact_class: Default::default(),
+                        reveal_end_block: None,
+                        review_end_block: None,
+                        amended: false,
+                        tags: vec![],
+                        adaptive_quorum: None,
                     };
                     PROPOSALS
                         .save(deps.storage, id, &proposal)
                         .map_err(ContractError::Std)
                 })
             }
-            "3.0.0" => Ok(()),
+            "3.0.0" | "3.0.1" => Ok(()),
             _ => Err(ContractError::MigrationError {}),
         },
         _ => Err(ContractError::MigrationError {}),
     }?;
 
+    // Index active proposals so the sudo endpoint can conclude them once they expire
+    let active_proposals = PROPOSALS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| {
+            item.as_ref()
+                .map(|(_, proposal)| proposal.status == ProposalStatus::Active)
+                .unwrap_or(true)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    for (id, proposal) in active_proposals {
+        ACTIVE_PROPOSALS.save(deps.storage, (proposal.voting_end_block(), id), &())?;
+    }
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::new()
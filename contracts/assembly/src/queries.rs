@@ -1,20 +1,32 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{to_json_binary, Binary, Deps, Env, Order, StdResult};
+use cosmwasm_std::{to_json_binary, Binary, Decimal, Deps, Env, Order, StdResult};
 use cw_storage_plus::Bound;
 
 use astroport_governance::assembly::{
-    ProposalListResponse, ProposalVoterResponse, ProposalVotesResponse, QueryMsg,
+    average_turnout, AdaptiveQuorumResponse, GovernanceStatsResponse, Proposal,
+    ProposalEligibilityResponse, ProposalListResponse, ProposalStatus, ProposalVoterResponse,
+    ProposalVotesResponse, QueryMsg, QuorumMode, SimulateExecutionResponse, VotingPowerBreakdown,
 };
 
-use crate::state::{CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_VOTERS};
-use crate::utils::calc_voting_power;
+use crate::state::{
+    CONCLUSION_HOOKS, CONFIG, GOVERNANCE_STATS, MIGRATION_PLANS, PARAM_REGISTRY, PAUSABLE_TARGETS,
+    PAUSED_TARGETS, PROPOSALS, PROPOSALS_BY_TAG, PROPOSAL_COUNT, PROPOSAL_TURNOUT, PROPOSAL_VOTERS,
+    RELAY_NONCES, RELAY_PUBKEYS, SCHEDULED_PROPOSALS, VOTER_STATS, VOTE_COMMITMENTS, VOTE_RELAYERS,
+    VOTING_POWER_BREAKDOWNS,
+};
+use crate::utils::{
+    calc_adaptive_quorum, calc_aged_xastro_balance, calc_voting_power, latest_turnouts,
+    message_diagnostics,
+};
 
 // Default pagination constants
 const DEFAULT_LIMIT: u32 = 10;
 const MAX_LIMIT: u32 = 30;
 const DEFAULT_VOTERS_LIMIT: u32 = 100;
 const MAX_VOTERS_LIMIT: u32 = 250;
+/// The amount of latest proposal turnouts returned in governance statistics
+const LATEST_TURNOUTS_LIMIT: usize = 10;
 
 /// Expose available contract queries.
 ///
@@ -23,14 +35,55 @@ const MAX_VOTERS_LIMIT: u32 = 250;
 ///
 /// * **QueryMsg::Proposals { start, limit }** Returns a [`ProposalListResponse`] according to the specified input parameters.
 ///
+/// * **QueryMsg::FilteredProposals { tag, status, start_after, limit }** Returns proposals labeled with a specific tag
+/// and/or in a specific status.
+///
 /// * **QueryMsg::Proposal { proposal_id }** Returns a [`Proposal`] according to the specified `proposal_id`.
 ///
+/// * **QueryMsg::ProposalPhase { proposal_id }** Returns the current [`ProposalPhase`] of a specific proposal.
+///
 /// * **QueryMsg::ProposalVotes { proposal_id }** Returns proposal vote counts that are stored in the [`ProposalVotesResponse`] structure.
 ///
+/// * **QueryMsg::UserVote { proposal_id, user }** Returns the vote option cast by a user on a specific proposal if any.
+///
+/// * **QueryMsg::VoteCommitment { proposal_id, user }** Returns the unrevealed vote commitment of a user if any.
+///
+/// * **QueryMsg::VotingPowerBreakdown { proposal_id, voter }** Returns the per-source voting power a voter was counted with if any.
+///
+/// * **QueryMsg::VotingPowerBreakdowns { proposal_id, start_after, limit }** Returns per-source voting power of proposal voters.
+///
 /// * **QueryMsg::UserVotingPower { user, proposal_id }** Returns user voting power for a specific proposal.
 ///
 /// * **QueryMsg::TotalVotingPower { proposal_id }** Returns total voting power for a specific proposal.
 ///
+/// * **QueryMsg::MigrationPlan { plan_id }** Returns a migration plan with the status of each step.
+///
+/// * **QueryMsg::ParamRegistry { start_after, limit }** Returns registered governable parameters.
+///
+/// * **QueryMsg::VoterStats { address }** Returns participation statistics of a specific voter.
+///
+/// * **QueryMsg::GovernanceStats {}** Returns aggregated governance statistics in the [`GovernanceStatsResponse`] structure.
+///
+/// * **QueryMsg::ProposalEligibility { address }** Returns whether an address holds enough aged xASTRO to submit a proposal.
+///
+/// * **QueryMsg::AdaptiveQuorum {}** Returns the general quorum required for a proposal submitted right now.
+///
+/// * **QueryMsg::VoteRelayers {}** Returns allowlisted vote relayers along with their quota usage.
+///
+/// * **QueryMsg::RelayNonce { user }** Returns the nonce the next vote relayed on behalf of a user must be signed with.
+///
+/// * **QueryMsg::RelayPubkey { user }** Returns the public key relayed votes of a user are verified against.
+///
+/// * **QueryMsg::SimulateExecution { proposal_id }** Returns static check results of proposal messages.
+///
+/// * **QueryMsg::PausableTargets {}** Returns contracts registered to be paused during critical proposals.
+///
+/// * **QueryMsg::PausedTargets {}** Returns currently paused contracts along with the proposals holding them paused.
+///
+/// * **QueryMsg::ScheduledProposals { start_after, limit }** Returns the queue of scheduled proposals.
+///
+/// * **QueryMsg::ConclusionHooks {}** Returns contracts notified when a matching proposal concludes.
+///
 /// * **QueryMsg::ProposalVoters {
 ///             proposal_id,
 ///             vote_option,
@@ -38,18 +91,53 @@ const MAX_VOTERS_LIMIT: u32 = 250;
 ///             limit,
 ///         }** Returns a vector of proposal voters according to the specified input parameters.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
         QueryMsg::Proposals { start, limit } => {
             to_json_binary(&query_proposals(deps, start, limit)?)
         }
+        QueryMsg::FilteredProposals {
+            tag,
+            status,
+            start_after,
+            limit,
+        } => to_json_binary(&query_filtered_proposals(
+            deps,
+            tag,
+            status,
+            start_after,
+            limit,
+        )?),
         QueryMsg::Proposal { proposal_id } => {
             to_json_binary(&PROPOSALS.load(deps.storage, proposal_id)?)
         }
+        QueryMsg::ProposalPhase { proposal_id } => {
+            let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+            to_json_binary(&proposal.phase(env.block.height))
+        }
         QueryMsg::ProposalVotes { proposal_id } => {
             to_json_binary(&query_proposal_votes(deps, proposal_id)?)
         }
+        QueryMsg::UserVote { proposal_id, user } => {
+            to_json_binary(&PROPOSAL_VOTERS.may_load(deps.storage, (proposal_id, user))?)
+        }
+        QueryMsg::VoteCommitment { proposal_id, user } => {
+            to_json_binary(&VOTE_COMMITMENTS.may_load(deps.storage, (proposal_id, user))?)
+        }
+        QueryMsg::VotingPowerBreakdown { proposal_id, voter } => {
+            to_json_binary(&VOTING_POWER_BREAKDOWNS.may_load(deps.storage, (proposal_id, voter))?)
+        }
+        QueryMsg::VotingPowerBreakdowns {
+            proposal_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query_voting_power_breakdowns(
+            deps,
+            proposal_id,
+            start_after,
+            limit,
+        )?),
         QueryMsg::UserVotingPower { user, proposal_id } => {
             let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
             to_json_binary(&calc_voting_power(deps, user, &proposal)?)
@@ -58,6 +146,98 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
             to_json_binary(&proposal.total_voting_power)
         }
+        QueryMsg::MigrationPlan { plan_id } => {
+            to_json_binary(&MIGRATION_PLANS.load(deps.storage, plan_id)?)
+        }
+        QueryMsg::ParamRegistry { start_after, limit } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let params = PARAM_REGISTRY
+                .range(
+                    deps.storage,
+                    start_after
+                        .as_ref()
+                        .map(|(contract, key)| Bound::exclusive((contract.as_str(), key.as_str()))),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .map(|item| item.map(|(_, param)| param))
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_json_binary(&params)
+        }
+        QueryMsg::VoterStats { address } => to_json_binary(
+            &VOTER_STATS
+                .may_load(deps.storage, &address)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::GovernanceStats {} => to_json_binary(&query_governance_stats(deps)?),
+        QueryMsg::ProposalEligibility { address } => {
+            let config = CONFIG.load(deps.storage)?;
+            let aged_balance = calc_aged_xastro_balance(
+                deps.querier,
+                &config,
+                &address,
+                env.block.time.seconds(),
+            )?;
+            to_json_binary(&ProposalEligibilityResponse {
+                eligible: aged_balance >= config.proposal_required_deposit,
+                aged_balance,
+                required_deposit: config.proposal_required_deposit,
+                min_xastro_age: config.min_xastro_age,
+            })
+        }
+        QueryMsg::AdaptiveQuorum {} => to_json_binary(&query_adaptive_quorum(deps)?),
+        QueryMsg::VoteRelayers {} => to_json_binary(
+            &VOTE_RELAYERS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?,
+        ),
+        QueryMsg::RelayNonce { user } => {
+            let user = deps.api.addr_validate(&user)?;
+            to_json_binary(
+                &RELAY_NONCES
+                    .may_load(deps.storage, &user)?
+                    .unwrap_or_default(),
+            )
+        }
+        QueryMsg::RelayPubkey { user } => {
+            let user = deps.api.addr_validate(&user)?;
+            to_json_binary(&RELAY_PUBKEYS.may_load(deps.storage, &user)?)
+        }
+        QueryMsg::SimulateExecution { proposal_id } => {
+            to_json_binary(&query_simulate_execution(deps, proposal_id)?)
+        }
+        QueryMsg::PausableTargets {} => to_json_binary(
+            &PAUSABLE_TARGETS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?,
+        ),
+        QueryMsg::ConclusionHooks {} => to_json_binary(
+            &CONCLUSION_HOOKS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?,
+        ),
+        QueryMsg::PausedTargets {} => to_json_binary(
+            &PAUSED_TARGETS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?,
+        ),
+        QueryMsg::ScheduledProposals { start_after, limit } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let scheduled = SCHEDULED_PROPOSALS
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .map(|item| item.map(|(_, scheduled)| scheduled))
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_json_binary(&scheduled)
+        }
         QueryMsg::ProposalVoters {
             proposal_id,
             start_after,
@@ -97,6 +277,43 @@ pub fn query_proposals(
     })
 }
 
+/// Returns proposals labeled with `tag` and in `status` if specified.
+/// Proposals are returned in ascending order of their ids.
+pub fn query_filtered_proposals(
+    deps: Deps,
+    tag: Option<String>,
+    status: Option<ProposalStatus>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Proposal>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let proposals: Box<dyn Iterator<Item = StdResult<Proposal>>> = match &tag {
+        Some(tag) => Box::new(
+            PROPOSALS_BY_TAG
+                .prefix(tag)
+                .keys(deps.storage, start, None, Order::Ascending)
+                .map(|proposal_id| {
+                    proposal_id.and_then(|proposal_id| PROPOSALS.load(deps.storage, proposal_id))
+                }),
+        ),
+        None => Box::new(
+            PROPOSALS
+                .range(deps.storage, start, None, Order::Ascending)
+                .map(|item| item.map(|(_, proposal)| proposal)),
+        ),
+    };
+
+    proposals
+        .filter(|proposal| match (proposal, &status) {
+            (Ok(proposal), Some(status)) => proposal.status == *status,
+            _ => true,
+        })
+        .take(limit)
+        .collect()
+}
+
 /// Returns a proposal's voters
 pub fn query_proposal_voters(
     deps: Deps,
@@ -121,6 +338,62 @@ pub fn query_proposal_voters(
     Ok(voters)
 }
 
+/// Returns per-source voting power breakdowns of proposal voters.
+pub fn query_voting_power_breakdowns(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, VotingPowerBreakdown)>> {
+    let limit = limit.unwrap_or(DEFAULT_VOTERS_LIMIT).min(MAX_VOTERS_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into_bytes()));
+
+    VOTING_POWER_BREAKDOWNS
+        .prefix(proposal_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+/// Returns aggregated governance statistics along with the latest proposal turnouts.
+pub fn query_governance_stats(deps: Deps) -> StdResult<GovernanceStatsResponse> {
+    let stats = GOVERNANCE_STATS.may_load(deps.storage)?.unwrap_or_default();
+    let average_turnout = if stats.concluded_proposals > 0 {
+        stats.turnout_sum / Decimal::from_ratio(stats.concluded_proposals, 1u64)
+    } else {
+        Decimal::zero()
+    };
+    let latest_turnouts = PROPOSAL_TURNOUT
+        .range(deps.storage, None, None, Order::Descending)
+        .take(LATEST_TURNOUTS_LIMIT)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(GovernanceStatsResponse {
+        total_proposals: PROPOSAL_COUNT.load(deps.storage)?.u64(),
+        stats,
+        average_turnout,
+        latest_turnouts,
+    })
+}
+
+/// Returns the general quorum required for a proposal submitted right now
+/// along with the turnout statistics it was derived from.
+pub fn query_adaptive_quorum(deps: Deps) -> StdResult<AdaptiveQuorumResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let turnouts = match &config.quorum_mode {
+        QuorumMode::Static => vec![],
+        QuorumMode::Adaptive(params) => latest_turnouts(deps.storage, params.lookback as usize)?,
+    };
+
+    Ok(AdaptiveQuorumResponse {
+        average_turnout: average_turnout(&turnouts).unwrap_or_default(),
+        sampled_proposals: turnouts.len() as u64,
+        required_quorum: calc_adaptive_quorum(deps.storage, &config)?
+            .unwrap_or(config.proposal_required_quorum),
+        quorum_mode: config.quorum_mode,
+    })
+}
+
 /// Returns proposal votes stored in the [`ProposalVotesResponse`] structure.
 pub fn query_proposal_votes(deps: Deps, proposal_id: u64) -> StdResult<ProposalVotesResponse> {
     let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
@@ -131,3 +404,31 @@ pub fn query_proposal_votes(deps: Deps, proposal_id: u64) -> StdResult<ProposalV
         against_power: proposal.against_power,
     })
 }
+
+/// Runs static checks against proposal messages, so executors can spot proposals
+/// which can't be executed before sending the transaction.
+pub fn query_simulate_execution(
+    deps: Deps,
+    proposal_id: u64,
+) -> StdResult<SimulateExecutionResponse> {
+    let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+    let is_remote = proposal.ibc_channel.is_some();
+
+    let messages: Vec<_> = proposal
+        .messages
+        .iter()
+        .enumerate()
+        .map(|(index, msg)| message_diagnostics(deps.api, index as u32, msg, is_remote))
+        .collect();
+    let executable = proposal.status == ProposalStatus::Passed
+        && messages
+            .iter()
+            .all(|diagnostics| diagnostics.issues.is_empty());
+
+    Ok(SimulateExecutionResponse {
+        proposal_id,
+        status: proposal.status,
+        executable,
+        messages,
+    })
+}
@@ -1,5 +1,9 @@
-use astroport_governance::assembly::{Config, Proposal, ProposalVoteOption};
-use cosmwasm_std::Uint64;
+use astroport_governance::assembly::{
+    ConclusionHookInfo, Config, GovernableParam, GovernanceStats, MigrationPlan,
+    PausableTargetInfo, PausedTarget, Proposal, ProposalVoteOption, RelayUsage, ScheduledProposal,
+    VoteCommitment, VoteRelayerInfo, VoterStats, VotingPowerBreakdown,
+};
+use cosmwasm_std::{Addr, Binary, Decimal, Uint64};
 use cw_storage_plus::{Item, Map};
 
 /// Stores the config for the Assembly contract
@@ -11,6 +15,74 @@ pub const PROPOSAL_COUNT: Item<Uint64> = Item::new("proposal_count");
 /// This is a map that contains information about all proposals
 pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
 
+/// Index of proposals by tag. Contains (tag, proposal id) pairs
+pub const PROPOSALS_BY_TAG: Map<(&str, u64), ()> = Map::new("proposals_by_tag");
+
 /// Contains all the voters and their vote option. A String is used for the address
 /// to account for cross-chain voting
 pub const PROPOSAL_VOTERS: Map<(u64, String), ProposalVoteOption> = Map::new("proposal_votes");
+
+/// Contains unrevealed vote commitments of commit-reveal proposals.
+/// A commitment is removed once the vote is revealed
+pub const VOTE_COMMITMENTS: Map<(u64, String), VoteCommitment> = Map::new("vote_commitments");
+
+/// Contains per-source voting power breakdowns of counted votes. A String is used for the address
+/// to account for cross-chain voting
+pub const VOTING_POWER_BREAKDOWNS: Map<(u64, String), VotingPowerBreakdown> =
+    Map::new("voting_power_breakdowns");
+
+/// Active proposals indexed by (last voting block, proposal id).
+/// Used by the sudo endpoint to conclude expired proposals in batches.
+pub const ACTIVE_PROPOSALS: Map<(u64, u64), ()> = Map::new("active_proposals");
+
+/// Stores the number of registered migration plans
+pub const MIGRATION_PLAN_COUNT: Item<u64> = Item::new("migration_plan_count");
+
+/// Contains all registered migration plans
+pub const MIGRATION_PLANS: Map<u64, MigrationPlan> = Map::new("migration_plans");
+
+/// Id of the migration plan which is being executed in the current transaction
+pub const ACTIVE_MIGRATION_PLAN: Item<u64> = Item::new("active_migration_plan");
+
+/// Registry of governable parameters: (contract address, parameter key) -> parameter
+pub const PARAM_REGISTRY: Map<(&str, &str), GovernableParam> = Map::new("param_registry");
+
+/// Participation statistics of each voter. A String is used for the address
+/// to account for cross-chain voting
+pub const VOTER_STATS: Map<&str, VoterStats> = Map::new("voter_stats");
+
+/// Aggregated governance statistics
+pub const GOVERNANCE_STATS: Item<GovernanceStats> = Item::new("governance_stats");
+
+/// Turnout of each concluded proposal, i.e. the share of the total voting power which voted
+pub const PROPOSAL_TURNOUT: Map<u64, Decimal> = Map::new("proposal_turnout");
+
+/// Allowlisted vote relayers along with their quota usage
+pub const VOTE_RELAYERS: Map<&Addr, VoteRelayerInfo> = Map::new("vote_relayers");
+
+/// The nonce the next vote relayed on behalf of a user must be signed with
+pub const RELAY_NONCES: Map<&Addr, u64> = Map::new("relay_nonces");
+
+/// secp256k1 public keys relayed votes of each user are verified against
+pub const RELAY_PUBKEYS: Map<&Addr, Binary> = Map::new("relay_pubkeys");
+
+/// Number of votes relayed on behalf of each user within the current quota period
+pub const USER_RELAY_USAGE: Map<&Addr, RelayUsage> = Map::new("user_relay_usage");
+
+/// Contracts paused while a critical proposal targeting them is live
+pub const PAUSABLE_TARGETS: Map<&Addr, PausableTargetInfo> = Map::new("pausable_targets");
+
+/// Currently paused contracts along with the proposals holding them paused
+pub const PAUSED_TARGETS: Map<&Addr, PausedTarget> = Map::new("paused_targets");
+
+/// Contracts paused on behalf of a specific proposal
+pub const PROPOSAL_PAUSED_TARGETS: Map<u64, Vec<Addr>> = Map::new("proposal_paused_targets");
+
+/// Stores the number of scheduled proposals. Used to generate scheduled proposal ids
+pub const SCHEDULED_PROPOSAL_COUNT: Item<u64> = Item::new("scheduled_proposal_count");
+
+/// Queue of follow-up proposals which are not opened yet
+pub const SCHEDULED_PROPOSALS: Map<u64, ScheduledProposal> = Map::new("scheduled_proposals");
+
+/// Contracts notified when a matching proposal concludes
+pub const CONCLUSION_HOOKS: Map<&Addr, ConclusionHookInfo> = Map::new("conclusion_hooks");
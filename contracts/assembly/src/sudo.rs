@@ -0,0 +1,110 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{attr, DepsMut, Env, Order, Response, StdResult};
+use cw_storage_plus::Bound;
+
+use astroport_governance::assembly::{ProposalStatus, SudoMsg};
+
+use crate::contract::{
+    conclude_proposal, conclusion_hook_msgs, notify_outposts_msg, unpause_proposal_targets,
+};
+use crate::error::ContractError;
+use crate::state::{ACTIVE_PROPOSALS, CONFIG, PROPOSALS, VOTE_COMMITMENTS};
+
+// Default pagination constants
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+/// Exposes all the sudo endpoints available in the contract.
+///
+/// ## Sudo messages
+/// * **SudoMsg::EndExpiredProposals { limit }** Concludes active proposals whose voting period has ended.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::EndExpiredProposals { limit } => end_expired_proposals(deps, env, limit),
+    }
+}
+
+/// Concludes active proposals whose voting period has ended and refunds submitters' deposits.
+/// The endpoint is idempotent: concluded proposals are skipped.
+/// At most `limit` proposals are concluded in a single call. Proposals are picked from
+/// the index of active proposals ordered by their last voting block, thus proposals
+/// which are still being voted on never block expired ones.
+/// Up to `limit` commitments which were never revealed before their proposals concluded are pruned as well.
+pub fn end_expired_proposals(
+    deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let config = CONFIG.load(deps.storage)?;
+
+    let expired = ACTIVE_PROPOSALS
+        .keys(
+            deps.storage,
+            None,
+            Some(Bound::exclusive((env.block.height, 0))),
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut ended = vec![];
+    let mut messages = vec![];
+    let mut notifications = vec![];
+
+    for (voting_end_block, proposal_id) in expired {
+        let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+
+        if proposal.status == ProposalStatus::Active {
+            messages.extend(conclude_proposal(deps.storage, &config, &mut proposal)?);
+            PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+            notifications.extend(unpause_proposal_targets(deps.storage, proposal_id)?);
+            notifications.extend(notify_outposts_msg(&config, proposal_id)?);
+            notifications.extend(conclusion_hook_msgs(deps.storage, &proposal)?);
+            ended.push(format!("{proposal_id}:{}", proposal.status));
+        }
+
+        // Drop stale index entries
+        ACTIVE_PROPOSALS.remove(deps.storage, (voting_end_block, proposal_id));
+    }
+
+    // Commitments are ordered by proposal id, thus the oldest ones are pruned first
+    let commitments = VOTE_COMMITMENTS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut pruned = 0;
+    let mut concluded = None;
+    for (proposal_id, voter) in commitments {
+        let is_concluded = match concluded {
+            Some((id, is_concluded)) if id == proposal_id => is_concluded,
+            _ => {
+                let status = PROPOSALS.load(deps.storage, proposal_id)?.status;
+                let is_concluded = status != ProposalStatus::Active;
+                concluded = Some((proposal_id, is_concluded));
+                is_concluded
+            }
+        };
+
+        if is_concluded {
+            VOTE_COMMITMENTS.remove(deps.storage, (proposal_id, voter));
+            pruned += 1;
+        }
+    }
+
+    let mut attrs = vec![
+        attr("action", "end_expired_proposals"),
+        attr("ended_count", ended.len().to_string()),
+        attr("pruned_commitments", pruned.to_string()),
+    ];
+    if !ended.is_empty() {
+        attrs.push(attr("ended_proposals", ended.join(",")));
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_submessages(notifications)
+        .add_attributes(attrs))
+}
@@ -13,9 +13,10 @@ use cosmwasm_std::{
 use test_case::test_case;
 
 use astroport_governance::assembly::{
-    Config, ExecuteMsg, Proposal, ProposalStatus, QueryMsg, DELAY_INTERVAL, DEPOSIT_INTERVAL,
-    EXPIRATION_PERIOD_INTERVAL, MINIMUM_PROPOSAL_REQUIRED_QUORUM_PERCENTAGE,
-    MINIMUM_PROPOSAL_REQUIRED_THRESHOLD_PERCENTAGE, VOTING_PERIOD_INTERVAL,
+    Config, ExecuteMsg, Proposal, ProposalStatus, ProposalTemplate, QueryMsg,
+    DEFAULT_REVEAL_PERIOD, DELAY_INTERVAL, DEPOSIT_INTERVAL, EXPIRATION_PERIOD_INTERVAL,
+    MINIMUM_PROPOSAL_REQUIRED_QUORUM_PERCENTAGE, MINIMUM_PROPOSAL_REQUIRED_THRESHOLD_PERCENTAGE,
+    VOTING_PERIOD_INTERVAL,
 };
 
 use crate::contract::{execute, execute_proposal, submit_proposal};
@@ -140,6 +141,13 @@ fn check_proposal_validation(
         )
         .unwrap(),
         whitelisted_links: vec!["https://some.link/".to_string()],
+        include_builder_vp: true,
+        impact_classes: Default::default(),
+        proposal_reveal_period: DEFAULT_REVEAL_PERIOD,
+        min_xastro_age: 0,
+        proposal_review_period: 0,
+        proposal_tags: vec![],
+        quorum_mode: Default::default(),
     };
     CONFIG.save(deps.as_mut().storage, &config).unwrap();
 
@@ -147,11 +155,15 @@ fn check_proposal_validation(
         deps.as_mut(),
         mock_env(),
         mock_info("creator", &funds),
-        title.to_string(),
-        description.to_string(),
-        link.map(|s| s.to_string()),
-        vec![],
-        None,
+        ProposalTemplate {
+            title: title.to_string(),
+            description: description.to_string(),
+            link: link.map(|s| s.to_string()),
+            messages: vec![],
+            ibc_channel: None,
+            commit_reveal: false,
+            tags: vec![],
+        },
     );
 
     if let Some(err_msg) = expected_error {
@@ -192,6 +204,12 @@ fn check_proposal_validation(
                 deposit_amount: funds[0].amount,
                 ibc_channel: None,
                 total_voting_power: Default::default(),
+                impact_class: Default::default(),
+                reveal_end_block: None,
+                review_end_block: None,
+                amended: false,
+                tags: vec![],
+                adaptive_quorum: None,
             }
         );
     }
@@ -224,6 +242,13 @@ fn check_submit_ibc_proposal() {
         )
         .unwrap(),
         whitelisted_links: vec!["https://some.link/".to_string()],
+        include_builder_vp: true,
+        impact_classes: Default::default(),
+        proposal_reveal_period: DEFAULT_REVEAL_PERIOD,
+        min_xastro_age: 0,
+        proposal_review_period: 0,
+        proposal_tags: vec![],
+        quorum_mode: Default::default(),
     };
     CONFIG.save(deps.as_mut().storage, &config).unwrap();
 
@@ -231,11 +256,15 @@ fn check_submit_ibc_proposal() {
         deps.as_mut(),
         mock_env(),
         mock_info("creator", &coins(PROPOSAL_REQUIRED_DEPOSIT, XASTRO_DENOM)),
-        "title".to_string(),
-        "description".to_string(),
-        Some("https://some.link".to_string()),
-        vec![],
-        Some("channel-1".to_string()),
+        ProposalTemplate {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            link: Some("https://some.link".to_string()),
+            messages: vec![],
+            ibc_channel: Some("channel-1".to_string()),
+            commit_reveal: false,
+            tags: vec![],
+        },
     )
     .unwrap_err();
     assert_eq!(err, ContractError::MissingIBCController {});
@@ -248,11 +277,15 @@ fn check_submit_ibc_proposal() {
         deps.as_mut(),
         mock_env(),
         mock_info("creator", &coins(PROPOSAL_REQUIRED_DEPOSIT, XASTRO_DENOM)),
-        "title".to_string(),
-        "description".to_string(),
-        Some("https://some.link/".to_string()),
-        vec![],
-        Some("channel-10".to_string()),
+        ProposalTemplate {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            link: Some("https://some.link/".to_string()),
+            messages: vec![],
+            ibc_channel: Some("channel-10".to_string()),
+            commit_reveal: false,
+            tags: vec![],
+        },
     )
     .unwrap_err();
     assert_eq!(
@@ -265,11 +298,15 @@ fn check_submit_ibc_proposal() {
         deps.as_mut(),
         mock_env(),
         mock_info("creator", &coins(PROPOSAL_REQUIRED_DEPOSIT, XASTRO_DENOM)),
-        "title".to_string(),
-        "description".to_string(),
-        Some("https://some.link/".to_string()),
-        vec![],
-        Some("channel-1".to_string()),
+        ProposalTemplate {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            link: Some("https://some.link/".to_string()),
+            messages: vec![],
+            ibc_channel: Some("channel-1".to_string()),
+            commit_reveal: false,
+            tags: vec![],
+        },
     )
     .unwrap();
 }
@@ -298,6 +335,13 @@ fn check_execute_ibc_proposal() {
         )
         .unwrap(),
         whitelisted_links: vec!["https://some.link/".to_string()],
+        include_builder_vp: true,
+        impact_classes: Default::default(),
+        proposal_reveal_period: DEFAULT_REVEAL_PERIOD,
+        min_xastro_age: 0,
+        proposal_review_period: 0,
+        proposal_tags: vec![],
+        quorum_mode: Default::default(),
     };
     CONFIG.save(deps.as_mut().storage, &config).unwrap();
 
@@ -323,6 +367,12 @@ fn check_execute_ibc_proposal() {
         deposit_amount: Default::default(),
         ibc_channel: Some("channel-1".to_string()),
         total_voting_power: Default::default(),
+        impact_class: Default::default(),
+        reveal_end_block: None,
+        review_end_block: None,
+        amended: false,
+        tags: vec![],
+        adaptive_quorum: None,
     };
 
     // Mocked proposal
@@ -373,6 +423,13 @@ fn check_controller_callback() {
         )
         .unwrap(),
         whitelisted_links: vec!["https://some.link/".to_string()],
+        include_builder_vp: true,
+        impact_classes: Default::default(),
+        proposal_reveal_period: DEFAULT_REVEAL_PERIOD,
+        min_xastro_age: 0,
+        proposal_review_period: 0,
+        proposal_tags: vec![],
+        quorum_mode: Default::default(),
     };
     CONFIG.save(deps.as_mut().storage, &config).unwrap();
 
@@ -399,6 +456,12 @@ fn check_controller_callback() {
         deposit_amount: Default::default(),
         ibc_channel: Some("channel-1".to_string()),
         total_voting_power: Default::default(),
+        impact_class: Default::default(),
+        reveal_end_block: None,
+        review_end_block: None,
+        amended: false,
+        tags: vec![],
+        adaptive_quorum: None,
     };
     PROPOSALS.save(deps.as_mut().storage, 1, &proposal).unwrap();
 
@@ -1,15 +1,19 @@
 use astroport::tokenfactory_tracker;
-use cosmwasm_std::{Deps, QuerierWrapper, StdResult, Uint128};
+use cosmwasm_schema::serde::de::IgnoredAny;
+use cosmwasm_std::{
+    from_json, Addr, Api, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, Order, QuerierWrapper,
+    StdResult, Storage, Uint128, WasmMsg,
+};
 
-use astroport_governance::assembly::Config;
-use astroport_governance::assembly::Proposal;
+use astroport_governance::assembly::{Config, MessageDiagnostics, QuorumMode};
+use astroport_governance::assembly::{Proposal, VotingPowerBreakdown};
 use astroport_governance::builder_unlock::{
     AllocationResponse, QueryMsg as BuilderUnlockQueryMsg, State,
 };
 use astroport_governance::voting_escrow;
 use astroport_governance::voting_escrow::LockInfoResponse;
 
-use crate::state::CONFIG;
+use crate::state::{CONFIG, PROPOSAL_TURNOUT};
 
 /// Calculates an address' voting power at the specified block.
 ///
@@ -17,6 +21,16 @@ use crate::state::CONFIG;
 ///
 /// * **proposal** proposal for which we want to compute the `sender` (voter) voting power.
 pub fn calc_voting_power(deps: Deps, sender: String, proposal: &Proposal) -> StdResult<Uint128> {
+    calc_voting_power_breakdown(deps, sender, proposal).map(|breakdown| breakdown.total())
+}
+
+/// Calculates an address' voting power for the specified proposal split by source.
+/// Outpost voting power is never computed here as it is reported by the emissions controller.
+pub fn calc_voting_power_breakdown(
+    deps: Deps,
+    sender: String,
+    proposal: &Proposal,
+) -> StdResult<VotingPowerBreakdown> {
     let config = CONFIG.load(deps.storage)?;
 
     let xastro_vp: Uint128 = deps.querier.query_wasm_smart(
@@ -28,13 +42,16 @@ pub fn calc_voting_power(deps: Deps, sender: String, proposal: &Proposal) -> Std
         },
     )?;
 
-    let locked_amount: AllocationResponse = deps.querier.query_wasm_smart(
-        config.builder_unlock_addr,
-        &BuilderUnlockQueryMsg::Allocation {
-            account: sender.clone(),
-            timestamp: Some(proposal.start_time - 1),
-        },
-    )?;
+    let builder_vp = if config.include_builder_vp {
+        query_builder_voting_power(
+            deps.querier,
+            &config.builder_unlock_addr,
+            &sender,
+            proposal.start_time - 1,
+        )?
+    } else {
+        Uint128::zero()
+    };
 
     let vxastro_vp = if let Some(vxastro_contract) = &config.vxastro_contract {
         deps.querier
@@ -50,15 +67,68 @@ pub fn calc_voting_power(deps: Deps, sender: String, proposal: &Proposal) -> Std
         Uint128::zero()
     };
 
-    Ok(xastro_vp
-        + (locked_amount.status.amount - locked_amount.status.astro_withdrawn)
-        + vxastro_vp)
+    Ok(VotingPowerBreakdown {
+        xastro: xastro_vp,
+        vxastro: vxastro_vp,
+        builder: builder_vp,
+        outpost: Uint128::zero(),
+    })
+}
+
+/// Returns the amount of ASTRO which an account hasn't withdrawn from the builder unlock contract yet.
+/// Builder unlock contracts which don't support the VotingPowerAt query yet
+/// are queried through the Allocation endpoint. Any other error is propagated.
+fn query_builder_voting_power(
+    querier: QuerierWrapper,
+    builder_unlock_addr: &Addr,
+    account: &str,
+    timestamp: u64,
+) -> StdResult<Uint128> {
+    querier
+        .query_wasm_smart(
+            builder_unlock_addr,
+            &BuilderUnlockQueryMsg::VotingPowerAt {
+                account: account.to_string(),
+                timestamp: Some(timestamp),
+            },
+        )
+        .or_else(|err| {
+            if !err.to_string().contains("unknown variant") {
+                return Err(err);
+            }
+
+            let AllocationResponse { status, .. } = querier.query_wasm_smart(
+                builder_unlock_addr,
+                &BuilderUnlockQueryMsg::Allocation {
+                    account: account.to_string(),
+                    timestamp: Some(timestamp),
+                },
+            )?;
+
+            Ok(status.amount.saturating_sub(status.astro_withdrawn))
+        })
+}
+
+/// Returns the xASTRO balance an address has held for at least `config.min_xastro_age` seconds.
+pub fn calc_aged_xastro_balance(
+    querier: QuerierWrapper,
+    config: &Config,
+    address: &str,
+    block_time: u64,
+) -> StdResult<Uint128> {
+    querier.query_wasm_smart(
+        &config.xastro_denom_tracking,
+        &tokenfactory_tracker::QueryMsg::BalanceAt {
+            address: address.to_string(),
+            timestamp: Some(block_time.saturating_sub(config.min_xastro_age)),
+        },
+    )
 }
 
 /// Calculates the combined total voting power at a specified timestamp (that is relevant for a specific proposal).
 /// Combined voting power includes:
 /// * xASTRO total supply
-/// * ASTRO tokens which are still locked in the builder's unlock contract
+/// * ASTRO tokens which are still locked in the builder's unlock contract (if enabled in config)
 ///
 /// ## Parameters
 /// * **config** contract settings.
@@ -75,6 +145,10 @@ pub fn calc_total_voting_power_at(
         },
     )?;
 
+    if !config.include_builder_vp {
+        return Ok(total);
+    }
+
     // Total amount of ASTRO locked in the initial builder's unlock schedule
     let builder_state: State = querier.query_wasm_smart(
         &config.builder_unlock_addr,
@@ -85,3 +159,193 @@ pub fn calc_total_voting_power_at(
 
     Ok(total + builder_state.remaining_astro_tokens)
 }
+
+/// Returns turnouts of the latest concluded proposals, starting from the most recent one.
+pub fn latest_turnouts(storage: &dyn Storage, limit: usize) -> StdResult<Vec<Decimal>> {
+    PROPOSAL_TURNOUT
+        .range(storage, None, None, Order::Descending)
+        .take(limit)
+        .map(|item| item.map(|(_, turnout)| turnout))
+        .collect()
+}
+
+/// Calculates the general quorum for a proposal submitted right now.
+/// Returns None if the quorum is static.
+pub fn calc_adaptive_quorum(storage: &dyn Storage, config: &Config) -> StdResult<Option<Decimal>> {
+    match &config.quorum_mode {
+        QuorumMode::Static => Ok(None),
+        QuorumMode::Adaptive(params) => {
+            let turnouts = latest_turnouts(storage, params.lookback as usize)?;
+            Ok(Some(
+                params.compute(&turnouts, config.proposal_required_quorum),
+            ))
+        }
+    }
+}
+
+/// Runs static checks against a proposal message.
+/// Address checks are skipped for messages executed on a remote chain via IBC.
+///
+/// * **index** message index in the proposal.
+///
+/// * **is_remote** whether the message is executed on a remote chain.
+pub fn message_diagnostics(
+    api: &dyn Api,
+    index: u32,
+    msg: &CosmosMsg,
+    is_remote: bool,
+) -> MessageDiagnostics {
+    let mut issues = vec![];
+    let check_addr = |field: &str, addr: &str| {
+        (!is_remote && api.addr_validate(addr).is_err())
+            .then(|| format!("Invalid {field} address: {addr}"))
+    };
+
+    let msg_type = match msg {
+        CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+            issues.extend(check_addr("recipient", to_address));
+            issues.extend(check_funds(amount, true));
+            "bank_send"
+        }
+        CosmosMsg::Bank(BankMsg::Burn { amount }) => {
+            issues.extend(check_funds(amount, true));
+            "bank_burn"
+        }
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            msg,
+            funds,
+        }) => {
+            issues.extend(check_addr("contract", contract_addr));
+            issues.extend(check_json(msg));
+            issues.extend(check_funds(funds, false));
+            "wasm_execute"
+        }
+        CosmosMsg::Wasm(WasmMsg::Instantiate {
+            admin,
+            msg,
+            funds,
+            label,
+            ..
+        }) => {
+            if let Some(admin) = admin {
+                issues.extend(check_addr("admin", admin));
+            }
+            if label.is_empty() {
+                issues.push("Label must not be empty".to_string());
+            }
+            issues.extend(check_json(msg));
+            issues.extend(check_funds(funds, false));
+            "wasm_instantiate"
+        }
+        CosmosMsg::Wasm(WasmMsg::Migrate {
+            contract_addr, msg, ..
+        }) => {
+            issues.extend(check_addr("contract", contract_addr));
+            issues.extend(check_json(msg));
+            "wasm_migrate"
+        }
+        CosmosMsg::Wasm(WasmMsg::UpdateAdmin {
+            contract_addr,
+            admin,
+        }) => {
+            issues.extend(check_addr("contract", contract_addr));
+            issues.extend(check_addr("admin", admin));
+            "wasm_update_admin"
+        }
+        CosmosMsg::Wasm(WasmMsg::ClearAdmin { contract_addr }) => {
+            issues.extend(check_addr("contract", contract_addr));
+            "wasm_clear_admin"
+        }
+        CosmosMsg::Bank(_) => "bank",
+        CosmosMsg::Wasm(_) => "wasm",
+        CosmosMsg::Stargate { .. } => "stargate",
+        CosmosMsg::Ibc(_) => "ibc",
+        CosmosMsg::Gov(_) => "gov",
+        _ => "other",
+    };
+
+    MessageDiagnostics {
+        index,
+        msg_type: msg_type.to_string(),
+        issues,
+    }
+}
+
+/// Checks that a wasm message payload is valid JSON.
+fn check_json(msg: &Binary) -> Option<String> {
+    from_json::<IgnoredAny>(msg)
+        .err()
+        .map(|err| format!("Message payload is not valid JSON: {err}"))
+}
+
+/// Checks that coins have non-zero amounts.
+/// Empty coin list is rejected only if at least one coin is required.
+fn check_funds(coins: &[Coin], required: bool) -> Vec<String> {
+    let mut issues: Vec<_> = coins
+        .iter()
+        .filter(|coin| coin.amount.is_zero())
+        .map(|coin| format!("Zero amount of {}", coin.denom))
+        .collect();
+    if required && coins.is_empty() {
+        issues.push("Coins must not be empty".to_string());
+    }
+
+    issues
+}
+
+/// Builds MsgGrantAllowance with a BasicAllowance from **granter** to **grantee**
+/// limited by **spend_limit** and expiring at **expiration** (unix seconds).
+pub fn fee_allowance_msg(
+    granter: &Addr,
+    grantee: &Addr,
+    spend_limit: &[Coin],
+    expiration: u64,
+) -> CosmosMsg {
+    let mut timestamp = vec![];
+    proto_varint(&mut timestamp, 1, expiration);
+
+    let mut allowance = vec![];
+    for coin in spend_limit {
+        let mut encoded_coin = vec![];
+        proto_bytes(&mut encoded_coin, 1, coin.denom.as_bytes());
+        proto_bytes(&mut encoded_coin, 2, coin.amount.to_string().as_bytes());
+        proto_bytes(&mut allowance, 1, &encoded_coin);
+    }
+    proto_bytes(&mut allowance, 2, &timestamp);
+
+    let mut any = vec![];
+    proto_bytes(&mut any, 1, b"/cosmos.feegrant.v1beta1.BasicAllowance");
+    proto_bytes(&mut any, 2, &allowance);
+
+    let mut msg = vec![];
+    proto_bytes(&mut msg, 1, granter.as_bytes());
+    proto_bytes(&mut msg, 2, grantee.as_bytes());
+    proto_bytes(&mut msg, 3, &any);
+
+    CosmosMsg::Stargate {
+        type_url: "/cosmos.feegrant.v1beta1.MsgGrantAllowance".to_string(),
+        value: msg.into(),
+    }
+}
+
+/// Appends a protobuf varint field.
+fn proto_varint(buf: &mut Vec<u8>, field: u64, value: u64) {
+    encode_varint(buf, field << 3);
+    encode_varint(buf, value);
+}
+
+/// Appends a protobuf length-delimited field.
+fn proto_bytes(buf: &mut Vec<u8>, field: u64, value: &[u8]) {
+    encode_varint(buf, (field << 3) | 2);
+    encode_varint(buf, value.len() as u64);
+    buf.extend_from_slice(value);
+}
+
+fn encode_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
@@ -2,19 +2,33 @@ use std::collections::HashMap;
 use std::str::FromStr;
 
 use cosmwasm_std::{
-    coin, coins, wasm_execute, Addr, BankMsg, CosmosMsg, Decimal, Empty, Uint128, WasmMsg,
+    attr, coin, coins, from_json, to_json_binary, wasm_execute, Addr, BankMsg, Binary, CosmosMsg,
+    Decimal, DepsMut, Empty, Env, Event, Response, StdError, StdResult, Uint128, WasmMsg,
 };
 use cw_multi_test::Executor;
+use k256::ecdsa::signature::hazmat::PrehashSigner;
+use k256::ecdsa::{Signature, SigningKey};
 
 use astro_assembly::error::ContractError;
 use astroport_governance::assembly::{
-    Config, ExecuteMsg, InstantiateMsg, ProposalListResponse, ProposalStatus, ProposalVoteOption,
-    ProposalVoterResponse, QueryMsg, UpdateConfig, DELAY_INTERVAL, DEPOSIT_INTERVAL,
-    EXPIRATION_PERIOD_INTERVAL, VOTING_PERIOD_INTERVAL,
+    vote_commitment, AdaptiveQuorumParams, AdaptiveQuorumResponse, ConclusionHook,
+    ConclusionHookFilter, ConclusionHookInfo, Config, ExecuteMsg, GovernableParam, GovernanceStats,
+    ImpactClassParams, ImpactClassesConfig, InstantiateMsg, MessageDiagnostics, MigrationPlan,
+    MigrationPlanStatus, MigrationStep, MigrationStepStatus, ParamChange, ParamKind, ParamValue,
+    PausableTarget, PausableTargetInfo, PausedTarget, ProposalEligibilityResponse,
+    ProposalImpactClass, ProposalListResponse, ProposalPhase, ProposalStatus, ProposalTemplate,
+    ProposalVoteOption, ProposalVoterResponse, QueryMsg, QuorumMode, RelayVotePayload, RelayedVote,
+    ScheduledProposal, SimulateExecutionResponse, UpdateConfig, VoteCommitment, VoteRelayer,
+    VoteRelayerInfo, VoterStats, VotingPowerBreakdown, CRITICAL_PROPOSAL_TAG,
+    DEFAULT_REVEAL_PERIOD, DELAY_INTERVAL, DEPOSIT_INTERVAL, EXPIRATION_PERIOD_INTERVAL,
+    MAX_BATCH_VOTES, MAX_CONCLUSION_HOOKS, MAX_PROPOSAL_TAGS, MAX_QUORUM_LOOKBACK,
+    MAX_REVIEW_PERIOD, MAX_XASTRO_AGE, MINIMUM_PROPOSAL_REQUIRED_QUORUM_PERCENTAGE,
+    RELAY_QUOTA_PERIOD, REVEAL_PERIOD_INTERVAL, VOTING_PERIOD_INTERVAL,
 };
 
 use crate::common::helper::{
-    default_init_msg, noop_contract, Helper, PROPOSAL_DELAY, PROPOSAL_EXPIRATION,
+    conclusion_hook_contract, default_init_msg, migratable_contract, noop_contract,
+    pausable_contract, Helper, PausableMsg, PROPOSAL_DELAY, PROPOSAL_EXPIRATION,
     PROPOSAL_REQUIRED_DEPOSIT, PROPOSAL_VOTING_PERIOD,
 };
 
@@ -469,6 +483,108 @@ fn test_expired_proposal() {
     );
 }
 
+#[test]
+fn test_end_expired_proposals() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+
+    let user = Addr::unchecked("user");
+    helper.get_xastro(&user, 3 * PROPOSAL_REQUIRED_DEPOSIT.u128() + 1000); // initial stake consumes 1000 xASTRO
+
+    helper.next_block(10);
+
+    helper.submit_sample_proposal(&user);
+    helper.submit_sample_proposal(&user);
+    helper.cast_vote(1, &user, ProposalVoteOption::For).unwrap();
+    helper
+        .cast_vote(2, &user, ProposalVoteOption::Against)
+        .unwrap();
+
+    // Nothing to end yet
+    let resp = helper.end_expired_proposals(None).unwrap();
+    assert!(resp.has_event(&Event::new("wasm").add_attribute("ended_count", "0")));
+    assert_eq!(helper.proposal(1).status, ProposalStatus::Active);
+
+    helper.next_block_height(PROPOSAL_VOTING_PERIOD / 2);
+
+    // This proposal will be still active after first two are ended
+    helper.submit_sample_proposal(&user);
+
+    helper.next_block_height(PROPOSAL_VOTING_PERIOD / 2 + 1);
+
+    // Pagination: only the first proposal is checked
+    helper.end_expired_proposals(Some(1)).unwrap();
+    assert_eq!(helper.proposal(1).status, ProposalStatus::Passed);
+    assert_eq!(helper.proposal(2).status, ProposalStatus::Active);
+
+    helper.end_expired_proposals(None).unwrap();
+    assert_eq!(helper.proposal(2).status, ProposalStatus::Rejected);
+    assert_eq!(helper.proposal(3).status, ProposalStatus::Active);
+
+    // Submitter received deposits back for ended proposals
+    assert_eq!(
+        helper.query_balance(&user, &helper.xastro_denom).unwrap(),
+        PROPOSAL_REQUIRED_DEPOSIT * Uint128::new(2)
+    );
+
+    // Ended proposals can't be ended again
+    let err = helper.end_proposal(1).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ProposalNotActive {}
+    );
+
+    // Idempotency: repeated calls don't refund deposits twice
+    helper.end_expired_proposals(None).unwrap();
+    assert_eq!(
+        helper.query_balance(&user, &helper.xastro_denom).unwrap(),
+        PROPOSAL_REQUIRED_DEPOSIT * Uint128::new(2)
+    );
+
+    helper.next_block_height(PROPOSAL_VOTING_PERIOD);
+
+    // Proposal ended manually is skipped by sudo
+    helper.end_proposal(3).unwrap();
+    let resp = helper.end_expired_proposals(None).unwrap();
+    assert!(resp.has_event(&Event::new("wasm").add_attribute("ended_count", "0")));
+    assert_eq!(helper.proposal(3).status, ProposalStatus::Rejected);
+    assert_eq!(
+        helper.query_balance(&user, &helper.xastro_denom).unwrap(),
+        PROPOSAL_REQUIRED_DEPOSIT * Uint128::new(3)
+    );
+}
+
+#[test]
+fn test_end_expired_proposals_behind_active() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+
+    let user = Addr::unchecked("user");
+    helper.get_xastro(&user, 2 * PROPOSAL_REQUIRED_DEPOSIT.u128() + 1000); // initial stake consumes 1000 xASTRO
+
+    helper.next_block(10);
+
+    // Commit-reveal proposal is voted on longer than the regular one submitted after it
+    helper.submit_proposal_with_mode(&user, vec![], true);
+    helper.submit_sample_proposal(&user);
+
+    helper.next_block_height(PROPOSAL_VOTING_PERIOD + 1);
+
+    // Still active proposal with the lower id doesn't block the expired one
+    helper.end_expired_proposals(Some(1)).unwrap();
+    assert_eq!(helper.proposal(1).status, ProposalStatus::Active);
+    assert_eq!(helper.proposal(2).status, ProposalStatus::Rejected);
+
+    helper.next_block_height(DEFAULT_REVEAL_PERIOD);
+
+    let resp = helper.end_expired_proposals(Some(1)).unwrap();
+    assert!(resp.has_event(&Event::new("wasm").add_attribute("ended_proposals", "1:rejected")));
+    assert_eq!(
+        helper.query_balance(&user, &helper.xastro_denom).unwrap(),
+        PROPOSAL_REQUIRED_DEPOSIT * Uint128::new(2)
+    );
+}
+
 #[test]
 fn test_check_messages() {
     let owner = Addr::unchecked("owner");
@@ -625,19 +741,7 @@ fn test_update_config() {
         .execute_contract(
             owner.clone(),
             assembly.clone(),
-            &ExecuteMsg::UpdateConfig(Box::new(UpdateConfig {
-                ibc_controller: None,
-                builder_unlock_addr: None,
-                proposal_voting_period: None,
-                proposal_effective_delay: None,
-                proposal_expiration_period: None,
-                proposal_required_deposit: None,
-                proposal_required_quorum: None,
-                proposal_required_threshold: None,
-                whitelist_remove: None,
-                whitelist_add: None,
-                vxastro: None,
-            })),
+            &ExecuteMsg::UpdateConfig(Box::default()),
             &[],
         )
         .unwrap_err();
@@ -650,6 +754,9 @@ fn test_update_config() {
         ibc_controller: Some("ibc_controller".to_string()),
         builder_unlock_addr: Some("builder_unlock".to_string()),
         proposal_voting_period: Some(*VOTING_PERIOD_INTERVAL.end()),
+        proposal_reveal_period: Some(*REVEAL_PERIOD_INTERVAL.end()),
+        min_xastro_age: Some(86400),
+        proposal_review_period: Some(MAX_REVIEW_PERIOD),
         proposal_effective_delay: Some(*DELAY_INTERVAL.end()),
         proposal_expiration_period: Some(*EXPIRATION_PERIOD_INTERVAL.end()),
         proposal_required_deposit: Some((*DEPOSIT_INTERVAL.end()).into()),
@@ -657,7 +764,8 @@ fn test_update_config() {
         proposal_required_threshold: Some(Decimal::percent(50)),
         whitelist_remove: Some(vec!["https://some.link/".to_string()]),
         whitelist_add: Some(vec!["https://another.link/".to_string()]),
-        vxastro: None,
+        include_builder_vp: Some(false),
+        ..Default::default()
     };
 
     helper
@@ -685,6 +793,9 @@ fn test_update_config() {
         Addr::unchecked("builder_unlock")
     );
     assert_eq!(config.proposal_voting_period, *VOTING_PERIOD_INTERVAL.end());
+    assert_eq!(config.proposal_reveal_period, *REVEAL_PERIOD_INTERVAL.end());
+    assert_eq!(config.min_xastro_age, 86400);
+    assert_eq!(config.proposal_review_period, MAX_REVIEW_PERIOD);
     assert_eq!(config.proposal_effective_delay, *DELAY_INTERVAL.end());
     assert_eq!(
         config.proposal_expiration_period,
@@ -706,6 +817,7 @@ fn test_update_config() {
         config.whitelisted_links,
         vec!["https://another.link/".to_string()]
     );
+    assert!(!config.include_builder_vp);
 }
 
 #[test]
@@ -979,3 +1091,2455 @@ fn test_execute_multisig() {
         )
         .unwrap();
 }
+
+#[test]
+fn test_migration_plan() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+    let assembly = helper.assembly.clone();
+
+    fn migrate_ok(_deps: DepsMut, _env: Env, _msg: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn migrate_err(_deps: DepsMut, _env: Env, _msg: Empty) -> StdResult<Response> {
+        Err(StdError::generic_err("Migration failed"))
+    }
+
+    let old_code = helper.app.store_code(noop_contract());
+    let ok_code = helper.app.store_code(migratable_contract(migrate_ok));
+    let err_code = helper.app.store_code(migratable_contract(migrate_err));
+
+    let targets: Vec<_> = (0..3)
+        .map(|i| {
+            helper
+                .app
+                .instantiate_contract(
+                    old_code,
+                    owner.clone(),
+                    &Empty {},
+                    &[],
+                    format!("target{i}"),
+                    Some(assembly.to_string()),
+                )
+                .unwrap()
+        })
+        .collect();
+
+    let step = |contract: &Addr, new_code_id: u64| MigrationStep {
+        contract_addr: contract.to_string(),
+        new_code_id,
+        msg: to_json_binary(&Empty {}).unwrap(),
+    };
+    let code_id = |helper: &Helper, contract: &Addr| {
+        helper
+            .app
+            .wrap()
+            .query_wasm_contract_info(contract)
+            .unwrap()
+            .code_id
+    };
+
+    // Only the Assembly itself can register a migration plan
+    let err = helper
+        .app
+        .execute_contract(
+            owner.clone(),
+            assembly.clone(),
+            &ExecuteMsg::RegisterMigrationPlan {
+                steps: vec![step(&targets[0], ok_code)],
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    let err = helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::RegisterMigrationPlan { steps: vec![] },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::EmptyMigrationPlan {},
+        err.downcast().unwrap()
+    );
+
+    // All steps succeed
+    helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::RegisterMigrationPlan {
+                steps: vec![step(&targets[0], ok_code), step(&targets[1], ok_code)],
+            },
+            &[],
+        )
+        .unwrap();
+
+    let plan: MigrationPlan = helper
+        .app
+        .wrap()
+        .query_wasm_smart(&assembly, &QueryMsg::MigrationPlan { plan_id: 1 })
+        .unwrap();
+    assert_eq!(plan.status, MigrationPlanStatus::Completed);
+    assert!(plan
+        .steps
+        .iter()
+        .all(|(_, status)| *status == MigrationStepStatus::Succeeded));
+    assert_eq!(code_id(&helper, &targets[0]), ok_code);
+    assert_eq!(code_id(&helper, &targets[1]), ok_code);
+
+    // Second step fails, the third one must be skipped
+    helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::RegisterMigrationPlan {
+                steps: vec![
+                    step(&targets[2], ok_code),
+                    step(&targets[0], err_code),
+                    step(&targets[1], old_code),
+                ],
+            },
+            &[],
+        )
+        .unwrap();
+
+    let plan: MigrationPlan = helper
+        .app
+        .wrap()
+        .query_wasm_smart(&assembly, &QueryMsg::MigrationPlan { plan_id: 2 })
+        .unwrap();
+    assert_eq!(plan.status, MigrationPlanStatus::Halted);
+    assert_eq!(plan.steps[0].1, MigrationStepStatus::Succeeded);
+    assert!(matches!(
+        plan.steps[1].1,
+        MigrationStepStatus::Failed { .. }
+    ));
+    assert_eq!(plan.steps[2].1, MigrationStepStatus::Skipped);
+
+    // Succeeded step is kept while the failed and skipped ones are not applied
+    assert_eq!(code_id(&helper, &targets[2]), ok_code);
+    assert_eq!(code_id(&helper, &targets[0]), ok_code);
+    assert_eq!(code_id(&helper, &targets[1]), ok_code);
+}
+
+#[test]
+fn test_builder_voting_power_flag() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+    let assembly = helper.assembly.clone();
+
+    let builder = Addr::unchecked("builder");
+    helper.create_builder_allocation(&builder, 1000_000000);
+
+    let submitter = Addr::unchecked("submitter");
+    // Initial stake consumes 1000 xASTRO
+    helper.get_xastro(&submitter, PROPOSAL_REQUIRED_DEPOSIT.u128() * 2 + 1000);
+
+    helper.next_block(10);
+    helper.submit_sample_proposal(&submitter);
+
+    assert_eq!(helper.user_vp(&builder, 1).u128(), 1000_000000);
+    let total_vp_with_builder = helper.proposal(1).total_voting_power.u128();
+
+    helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::UpdateConfig(Box::new(UpdateConfig {
+                include_builder_vp: Some(false),
+                ..Default::default()
+            })),
+            &[],
+        )
+        .unwrap();
+
+    helper.next_block(10);
+    helper.submit_sample_proposal(&submitter);
+
+    // Builder allocation doesn't count towards voting power anymore
+    assert_eq!(helper.user_vp(&builder, 2).u128(), 0);
+    assert_eq!(
+        helper.proposal(2).total_voting_power.u128(),
+        total_vp_with_builder - 1000_000000
+    );
+
+    let err = helper
+        .cast_vote(2, &builder, ProposalVoteOption::For)
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NoVotingPower {}
+    );
+}
+
+#[test]
+fn test_voting_power_breakdown() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+
+    let submitter = Addr::unchecked("submitter");
+    helper.get_xastro(&submitter, PROPOSAL_REQUIRED_DEPOSIT.u128() * 2);
+
+    let alice = Addr::unchecked("alice");
+    helper.get_xastro(&alice, 100_000000u128);
+    helper.get_vxastro(&alice, 200_000000u128);
+    helper.create_builder_allocation(&alice, 300_000000);
+
+    let bob = Addr::unchecked("bob");
+    helper.get_xastro(&bob, 50_000000u128);
+
+    helper.next_block(10);
+    helper.submit_sample_proposal(&submitter);
+
+    helper
+        .cast_vote(1, &alice, ProposalVoteOption::For)
+        .unwrap();
+    helper
+        .cast_vote(1, &bob, ProposalVoteOption::Against)
+        .unwrap();
+
+    let alice_breakdown = VotingPowerBreakdown {
+        xastro: 100_000000u128.into(),
+        vxastro: 200_000000u128.into(),
+        builder: 300_000000u128.into(),
+        outpost: Uint128::zero(),
+    };
+    assert_eq!(
+        helper.voting_power_breakdown(1, &alice),
+        Some(alice_breakdown.clone())
+    );
+    assert_eq!(alice_breakdown.total(), helper.proposal(1).for_power);
+
+    let bob_breakdown = VotingPowerBreakdown {
+        xastro: 50_000000u128.into(),
+        ..Default::default()
+    };
+    assert_eq!(
+        helper.voting_power_breakdowns(1, None, None),
+        vec![
+            (alice.to_string(), alice_breakdown),
+            (bob.to_string(), bob_breakdown.clone()),
+        ]
+    );
+    assert_eq!(
+        helper.voting_power_breakdowns(1, Some(&alice), Some(1)),
+        vec![(bob.to_string(), bob_breakdown)]
+    );
+
+    // No breakdown is recorded for those who didn't vote
+    assert_eq!(helper.voting_power_breakdown(1, &submitter), None);
+}
+
+#[test]
+fn test_proposal_tags() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+    let assembly = helper.assembly.clone();
+
+    let submitter = Addr::unchecked("submitter");
+    helper.get_xastro(&submitter, PROPOSAL_REQUIRED_DEPOSIT.u128() * 5);
+    helper.next_block(10);
+
+    let update_tags = |helper: &mut Helper, tags_add: &[&str], tags_remove: &[&str]| {
+        helper.app.execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::UpdateConfig(Box::new(UpdateConfig {
+                tags_add: Some(tags_add.iter().map(|tag| tag.to_string()).collect()),
+                tags_remove: Some(tags_remove.iter().map(|tag| tag.to_string()).collect()),
+                ..Default::default()
+            })),
+            &[],
+        )
+    };
+
+    let err = update_tags(&mut helper, &["Bad Tag"], &[]).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Tag is not properly formatted: Bad Tag. Tag must be at most 32 lowercase alphanumeric characters or dashes."
+    );
+
+    update_tags(&mut helper, &["treasury", "params", "outposts"], &[]).unwrap();
+    let config: Config = helper
+        .app
+        .wrap()
+        .query_wasm_smart(&assembly, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.proposal_tags, vec!["treasury", "params", "outposts"]);
+
+    let err = helper
+        .submit_tagged_proposal(&submitter, &["listing"])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::UnknownProposalTag {
+            tag: "listing".to_string()
+        }
+    );
+    let err = helper
+        .submit_tagged_proposal(&submitter, &["treasury", "treasury"])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::DuplicateProposalTag {
+            tag: "treasury".to_string()
+        }
+    );
+    let err = helper
+        .submit_tagged_proposal(&submitter, &["treasury"; MAX_PROPOSAL_TAGS + 1])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::TooManyProposalTags {
+            max: MAX_PROPOSAL_TAGS
+        }
+    );
+
+    helper
+        .submit_tagged_proposal(&submitter, &["treasury"])
+        .unwrap();
+    helper
+        .submit_tagged_proposal(&submitter, &["params", "treasury"])
+        .unwrap();
+    helper.submit_tagged_proposal(&submitter, &[]).unwrap();
+    assert_eq!(helper.proposal(2).tags, vec!["params", "treasury"]);
+
+    assert_eq!(
+        helper.filtered_proposals(Some("treasury"), None, None),
+        [1, 2]
+    );
+    assert_eq!(helper.filtered_proposals(Some("params"), None, None), [2]);
+    assert_eq!(
+        helper.filtered_proposals(Some("outposts"), None, None),
+        Vec::<u64>::new()
+    );
+    assert_eq!(
+        helper.filtered_proposals(Some("treasury"), None, Some(1)),
+        [2]
+    );
+    assert_eq!(
+        helper.filtered_proposals(None, Some(ProposalStatus::Active), None),
+        [1, 2, 3]
+    );
+
+    helper.next_block_height(PROPOSAL_VOTING_PERIOD + 1);
+    helper.end_proposal(1).unwrap();
+    assert_eq!(helper.proposal(1).status, ProposalStatus::Rejected);
+
+    assert_eq!(
+        helper.filtered_proposals(Some("treasury"), Some(ProposalStatus::Active), None),
+        [2]
+    );
+    assert_eq!(
+        helper.filtered_proposals(Some("treasury"), Some(ProposalStatus::Rejected), None),
+        [1]
+    );
+    assert_eq!(
+        helper.filtered_proposals(None, Some(ProposalStatus::Active), None),
+        [2, 3]
+    );
+
+    // Removed tags can't be used anymore while existing proposals keep them
+    update_tags(&mut helper, &[], &["treasury"]).unwrap();
+    let err = helper
+        .submit_tagged_proposal(&submitter, &["treasury"])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::UnknownProposalTag {
+            tag: "treasury".to_string()
+        }
+    );
+    assert_eq!(
+        helper.filtered_proposals(Some("treasury"), None, None),
+        [1, 2]
+    );
+}
+
+#[test]
+fn test_proposal_impact_classes() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+    let assembly = helper.assembly.clone();
+
+    let strict_params = |min_voting_period| ImpactClassParams {
+        min_voting_period,
+        required_quorum: Decimal::percent(50),
+        required_threshold: Decimal::percent(50),
+    };
+
+    helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::UpdateConfig(Box::new(UpdateConfig {
+                impact_classes: Some(ImpactClassesConfig {
+                    large_spend_threshold: vec![coin(100, "some_coin")],
+                    parameter_change: None,
+                    small_treasury_spend: None,
+                    large_treasury_spend: Some(strict_params(PROPOSAL_VOTING_PERIOD * 2)),
+                    contract_migration: Some(strict_params(PROPOSAL_VOTING_PERIOD * 3)),
+                }),
+                ..Default::default()
+            })),
+            &[],
+        )
+        .unwrap();
+
+    let voter = Addr::unchecked("voter");
+    helper.get_xastro(&voter, PROPOSAL_REQUIRED_DEPOSIT.u128());
+    let submitter = Addr::unchecked("submitter");
+    helper.get_xastro(&submitter, PROPOSAL_REQUIRED_DEPOSIT.u128() * 3);
+
+    helper.next_block(10);
+
+    let spend = |amount| -> CosmosMsg {
+        BankMsg::Send {
+            to_address: "receiver".to_string(),
+            amount: coins(amount, "some_coin"),
+        }
+        .into()
+    };
+    helper.submit_proposal(&submitter, vec![spend(100)]);
+    helper.submit_proposal(&submitter, vec![spend(100), spend(1)]);
+    helper.submit_proposal(
+        &submitter,
+        vec![
+            spend(1),
+            WasmMsg::UpdateAdmin {
+                contract_addr: assembly.to_string(),
+                admin: owner.to_string(),
+            }
+            .into(),
+        ],
+    );
+
+    for (proposal_id, class, voting_period) in [
+        (
+            1,
+            ProposalImpactClass::SmallTreasurySpend,
+            PROPOSAL_VOTING_PERIOD,
+        ),
+        (
+            2,
+            ProposalImpactClass::LargeTreasurySpend,
+            PROPOSAL_VOTING_PERIOD * 2,
+        ),
+        (
+            3,
+            ProposalImpactClass::ContractMigration,
+            PROPOSAL_VOTING_PERIOD * 3,
+        ),
+    ] {
+        let proposal = helper.proposal(proposal_id);
+        assert_eq!(proposal.impact_class, class);
+        assert_eq!(proposal.end_block, proposal.start_block + voting_period);
+
+        helper
+            .cast_vote(proposal_id, &voter, ProposalVoteOption::For)
+            .unwrap();
+    }
+
+    // Small spend proposal can't be ended before the general voting period
+    helper.next_block_height(PROPOSAL_VOTING_PERIOD + 1);
+    helper.end_proposal(1).unwrap();
+    let err = helper.end_proposal(2).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::VotingPeriodNotEnded {}
+    );
+
+    helper.next_block_height(PROPOSAL_VOTING_PERIOD * 2);
+    helper.end_proposal(2).unwrap();
+    helper.end_proposal(3).unwrap();
+
+    // Voter has 25% of total voting power which is enough only for the general quorum
+    assert_eq!(helper.proposal(1).status, ProposalStatus::Passed);
+    assert_eq!(helper.proposal(2).status, ProposalStatus::Rejected);
+    assert_eq!(helper.proposal(3).status, ProposalStatus::Rejected);
+}
+
+#[test]
+fn test_commit_reveal_proposal() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+
+    let submitter = Addr::unchecked("submitter");
+    // Initial stake consumes 1000 xASTRO
+    helper.get_xastro(&submitter, PROPOSAL_REQUIRED_DEPOSIT.u128() + 1000);
+    let user = Addr::unchecked("user");
+    helper.get_xastro(&user, 4 * PROPOSAL_REQUIRED_DEPOSIT.u128());
+    let user2 = Addr::unchecked("user2");
+    helper.get_xastro(&user2, PROPOSAL_REQUIRED_DEPOSIT.u128());
+    let silent_voter = Addr::unchecked("silent_voter");
+    helper.get_xastro(&silent_voter, 2 * PROPOSAL_REQUIRED_DEPOSIT.u128());
+
+    helper.next_block(10);
+
+    let assembly = helper.assembly.clone();
+    helper.mint_coin(&assembly, coin(1, "some_coin"));
+    helper.submit_proposal_with_mode(
+        &submitter,
+        vec![BankMsg::Send {
+            to_address: "receiver".to_string(),
+            amount: coins(1, "some_coin"),
+        }
+        .into()],
+        true,
+    );
+
+    let proposal = helper.proposal(1);
+    assert_eq!(
+        proposal.reveal_end_block,
+        Some(proposal.end_block + DEFAULT_REVEAL_PERIOD)
+    );
+    assert_eq!(
+        proposal.delayed_end_block,
+        proposal.end_block + DEFAULT_REVEAL_PERIOD + PROPOSAL_DELAY
+    );
+
+    // Plain votes are not accepted
+    let err = helper
+        .cast_vote(1, &user, ProposalVoteOption::For)
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::CommitRevealProposal {}
+    );
+
+    let err = helper
+        .commit_vote(1, &user, Binary::from(b"not a hash"))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidCommitment {}
+    );
+
+    let err = helper
+        .commit_vote(
+            1,
+            &Addr::unchecked("stranger"),
+            vote_commitment(1, &ProposalVoteOption::For, "salt"),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NoVotingPower {}
+    );
+
+    // Commitment can be replaced during the commit phase
+    helper
+        .commit_vote(
+            1,
+            &user,
+            vote_commitment(1, &ProposalVoteOption::Against, "user salt"),
+        )
+        .unwrap();
+    helper
+        .commit_vote(
+            1,
+            &user,
+            vote_commitment(1, &ProposalVoteOption::For, "user salt"),
+        )
+        .unwrap();
+    helper
+        .commit_vote(
+            1,
+            &user2,
+            vote_commitment(1, &ProposalVoteOption::Against, "user2 salt"),
+        )
+        .unwrap();
+    helper
+        .commit_vote(
+            1,
+            &silent_voter,
+            vote_commitment(1, &ProposalVoteOption::For, "silent salt"),
+        )
+        .unwrap();
+
+    assert_eq!(
+        helper.vote_commitment(1, &user),
+        Some(VoteCommitment {
+            commitment: vote_commitment(1, &ProposalVoteOption::For, "user salt"),
+            voting_power: (4 * PROPOSAL_REQUIRED_DEPOSIT.u128()).into(),
+            breakdown: VotingPowerBreakdown {
+                xastro: (4 * PROPOSAL_REQUIRED_DEPOSIT.u128()).into(),
+                ..Default::default()
+            },
+        })
+    );
+
+    // Tally stays hidden during the commit phase
+    let proposal = helper.proposal(1);
+    assert_eq!(proposal.for_power, Uint128::zero());
+    assert_eq!(proposal.against_power, Uint128::zero());
+
+    let err = helper
+        .reveal_vote(1, &user, ProposalVoteOption::For, "user salt")
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RevealPeriodNotActive {}
+    );
+
+    helper.next_block_height(PROPOSAL_VOTING_PERIOD + 1);
+
+    let err = helper
+        .commit_vote(
+            1,
+            &user,
+            vote_commitment(1, &ProposalVoteOption::Against, "user salt"),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::VotingPeriodEnded {}
+    );
+
+    // Revealed vote must match the latest commitment
+    for (option, salt) in [
+        (ProposalVoteOption::Against, "user salt"),
+        (ProposalVoteOption::For, "wrong salt"),
+    ] {
+        let err = helper.reveal_vote(1, &user, option, salt).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidVoteReveal {}
+        );
+    }
+
+    helper
+        .reveal_vote(1, &user, ProposalVoteOption::For, "user salt")
+        .unwrap();
+    assert_eq!(helper.vote_commitment(1, &user), None);
+
+    let err = helper
+        .reveal_vote(1, &user, ProposalVoteOption::For, "user salt")
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::CommitmentNotFound {}
+    );
+
+    helper
+        .reveal_vote(1, &user2, ProposalVoteOption::Against, "user2 salt")
+        .unwrap();
+
+    // Proposal can't be ended until the reveal window is over
+    let err = helper.end_proposal(1).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::VotingPeriodNotEnded {}
+    );
+
+    helper.next_block_height(DEFAULT_REVEAL_PERIOD);
+
+    let err = helper
+        .reveal_vote(1, &silent_voter, ProposalVoteOption::For, "silent salt")
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RevealPeriodNotActive {}
+    );
+
+    helper.end_proposal(1).unwrap();
+
+    // Only revealed votes are counted
+    let proposal = helper.proposal(1);
+    assert_eq!(proposal.status, ProposalStatus::Passed);
+    assert_eq!(
+        proposal.for_power.u128(),
+        4 * PROPOSAL_REQUIRED_DEPOSIT.u128()
+    );
+    assert_eq!(
+        proposal.against_power.u128(),
+        PROPOSAL_REQUIRED_DEPOSIT.u128()
+    );
+    assert_eq!(
+        helper
+            .proposal_voters(1)
+            .into_iter()
+            .map(|voter| voter.address)
+            .collect::<Vec<_>>(),
+        vec![user.to_string(), user2.to_string()]
+    );
+
+    // Unrevealed commitments are pruned once the proposal is concluded
+    assert!(helper.vote_commitment(1, &silent_voter).is_some());
+    let resp = helper.end_expired_proposals(None).unwrap();
+    resp.assert_event(&Event::new("wasm").add_attributes([
+        ("action", "end_expired_proposals"),
+        ("ended_count", "0"),
+        ("pruned_commitments", "1"),
+    ]));
+    assert_eq!(helper.vote_commitment(1, &silent_voter), None);
+}
+
+#[test]
+fn test_param_change_proposal() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+    let assembly = helper.assembly.clone();
+
+    let user = Addr::unchecked("user");
+    helper.get_xastro(&user, 2 * PROPOSAL_REQUIRED_DEPOSIT.u128() + 1000);
+    helper.next_block(10);
+
+    let param = |key: &str, kind: ParamKind| GovernableParam {
+        contract: assembly.to_string(),
+        key: key.to_string(),
+        kind,
+        msg_path: vec!["update_config".to_string()],
+    };
+    let quorum = param(
+        "proposal_required_quorum",
+        ParamKind::Decimal {
+            min: Decimal::percent(1),
+            max: Decimal::percent(50),
+        },
+    );
+    let threshold = param(
+        "proposal_required_threshold",
+        ParamKind::Decimal {
+            min: Decimal::percent(33),
+            max: Decimal::percent(100),
+        },
+    );
+    let builder_vp = param("include_builder_vp", ParamKind::Bool);
+
+    // Only the Assembly itself can update the registry
+    let err = helper
+        .app
+        .execute_contract(
+            owner.clone(),
+            assembly.clone(),
+            &ExecuteMsg::UpdateParamRegistry {
+                add: vec![quorum.clone()],
+                remove: vec![],
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    let err = helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::UpdateParamRegistry {
+                add: vec![param("\"quorum\"", ParamKind::Bool)],
+                remove: vec![],
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidGovernableParam {
+            contract: assembly.to_string(),
+            key: "\"quorum\"".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::UpdateParamRegistry {
+                add: vec![param(
+                    "proposal_voting_period",
+                    ParamKind::U64 { min: 10, max: 1 },
+                )],
+                remove: vec![],
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidGovernableParam {
+            contract: assembly.to_string(),
+            key: "proposal_voting_period".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::UpdateParamRegistry {
+                add: vec![quorum.clone(), threshold.clone(), builder_vp.clone()],
+                remove: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+
+    helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::UpdateParamRegistry {
+                add: vec![],
+                remove: vec![(assembly.to_string(), builder_vp.key.clone())],
+            },
+            &[],
+        )
+        .unwrap();
+
+    let registry: Vec<GovernableParam> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &assembly,
+            &QueryMsg::ParamRegistry {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(registry, vec![quorum.clone(), threshold.clone()]);
+
+    let registry: Vec<GovernableParam> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &assembly,
+            &QueryMsg::ParamRegistry {
+                start_after: Some((assembly.to_string(), quorum.key.clone())),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(registry, vec![threshold.clone()]);
+
+    let change = |key: &str, value: ParamValue| ParamChange {
+        contract: assembly.to_string(),
+        key: key.to_string(),
+        value,
+    };
+    let submit = |helper: &mut Helper, changes: Vec<ParamChange>| {
+        helper.app.execute_contract(
+            user.clone(),
+            assembly.clone(),
+            &ExecuteMsg::SubmitParamChangeProposal {
+                title: "Test title".to_string(),
+                description: "Test description".to_string(),
+                link: None,
+                changes,
+            },
+            &coins(PROPOSAL_REQUIRED_DEPOSIT.u128(), &helper.xastro_denom),
+        )
+    };
+
+    let err = submit(&mut helper, vec![]).unwrap_err();
+    assert_eq!(ContractError::EmptyParamChanges {}, err.downcast().unwrap());
+
+    let err = submit(
+        &mut helper,
+        vec![change(&builder_vp.key, ParamValue::Bool(false))],
+    )
+    .unwrap_err();
+    assert_eq!(
+        ContractError::ParamNotRegistered {
+            contract: assembly.to_string(),
+            key: builder_vp.key.clone()
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = submit(
+        &mut helper,
+        vec![change(
+            &quorum.key,
+            ParamValue::Decimal(Decimal::percent(51)),
+        )],
+    )
+    .unwrap_err();
+    assert_eq!(
+        ContractError::ParamOutOfBounds {
+            contract: assembly.to_string(),
+            key: quorum.key.clone()
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = submit(&mut helper, vec![change(&quorum.key, ParamValue::U64(1))]).unwrap_err();
+    assert_eq!(
+        ContractError::ParamTypeMismatch {
+            contract: assembly.to_string(),
+            key: quorum.key.clone()
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = submit(
+        &mut helper,
+        vec![
+            change(&quorum.key, ParamValue::Decimal(Decimal::percent(10))),
+            change(&quorum.key, ParamValue::Decimal(Decimal::percent(20))),
+        ],
+    )
+    .unwrap_err();
+    assert_eq!(
+        ContractError::DuplicatedParamChange {
+            contract: assembly.to_string(),
+            key: quorum.key.clone()
+        },
+        err.downcast().unwrap()
+    );
+
+    submit(
+        &mut helper,
+        vec![
+            change(&quorum.key, ParamValue::Decimal(Decimal::percent(10))),
+            change(&threshold.key, ParamValue::Decimal(Decimal::percent(60))),
+        ],
+    )
+    .unwrap();
+
+    // Both changes are batched into a single update_config message
+    let proposal = helper.proposal(1);
+    let [CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr, msg, ..
+    })] = proposal.messages.as_slice()
+    else {
+        panic!("Unexpected proposal messages: {:?}", proposal.messages);
+    };
+    assert_eq!(contract_addr, assembly.as_str());
+    assert_eq!(
+        from_json::<ExecuteMsg>(msg).unwrap(),
+        ExecuteMsg::UpdateConfig(Box::new(UpdateConfig {
+            proposal_required_quorum: Some(Decimal::percent(10)),
+            proposal_required_threshold: Some(Decimal::percent(60)),
+            ..Default::default()
+        }))
+    );
+
+    helper.next_block(10);
+    helper.cast_vote(1, &user, ProposalVoteOption::For).unwrap();
+    helper.next_block_height(PROPOSAL_VOTING_PERIOD);
+    helper.end_proposal(1).unwrap();
+    helper.next_block_height(PROPOSAL_DELAY);
+    helper.execute_proposal(1).unwrap();
+
+    let config: Config = helper
+        .app
+        .wrap()
+        .query_wasm_smart(&assembly, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.proposal_required_quorum, Decimal::percent(10));
+    assert_eq!(config.proposal_required_threshold, Decimal::percent(60));
+}
+
+#[test]
+fn test_governance_stats() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+
+    let submitter = Addr::unchecked("submitter");
+    helper.get_xastro(&submitter, 2 * PROPOSAL_REQUIRED_DEPOSIT.u128() + 1000); // initial stake consumes 1000 xASTRO
+    let user1 = Addr::unchecked("user1");
+    helper.get_xastro(&user1, 2 * PROPOSAL_REQUIRED_DEPOSIT.u128());
+    let user2 = Addr::unchecked("user2");
+    helper.get_xastro(&user2, PROPOSAL_REQUIRED_DEPOSIT.u128());
+
+    helper.next_block(10);
+
+    // No stats yet
+    assert_eq!(helper.voter_stats(&user1), VoterStats::default());
+    let stats = helper.governance_stats();
+    assert_eq!(stats.total_proposals, 0);
+    assert_eq!(stats.stats, GovernanceStats::default());
+    assert_eq!(stats.average_turnout, Decimal::zero());
+    assert_eq!(stats.latest_turnouts, vec![]);
+
+    helper.submit_sample_proposal(&submitter);
+    helper.submit_sample_proposal(&submitter);
+    helper.next_block(10);
+
+    helper
+        .cast_vote(1, &user1, ProposalVoteOption::For)
+        .unwrap();
+    helper
+        .cast_vote(1, &user2, ProposalVoteOption::Against)
+        .unwrap();
+    helper
+        .cast_vote(2, &user2, ProposalVoteOption::Against)
+        .unwrap();
+
+    assert_eq!(
+        helper.voter_stats(&user1),
+        VoterStats {
+            proposals_voted: 1,
+            for_votes: 1,
+            against_votes: 0,
+            total_voting_power: helper.user_vp(&user1, 1),
+            last_voted_proposal: Some(1),
+        }
+    );
+    assert_eq!(
+        helper.voter_stats(&user2),
+        VoterStats {
+            proposals_voted: 2,
+            for_votes: 0,
+            against_votes: 2,
+            total_voting_power: helper.user_vp(&user2, 1) + helper.user_vp(&user2, 2),
+            last_voted_proposal: Some(2),
+        }
+    );
+
+    // Failed vote doesn't affect stats
+    helper
+        .cast_vote(1, &user1, ProposalVoteOption::Against)
+        .unwrap_err();
+    assert_eq!(helper.voter_stats(&user1).proposals_voted, 1);
+
+    let stats = helper.governance_stats();
+    assert_eq!(stats.total_proposals, 2);
+    assert_eq!(
+        stats.stats,
+        GovernanceStats {
+            total_votes: 3,
+            for_votes: 1,
+            against_votes: 2,
+            ..Default::default()
+        }
+    );
+
+    helper.next_block_height(PROPOSAL_VOTING_PERIOD);
+    helper.end_proposal(1).unwrap();
+    helper.end_expired_proposals(None).unwrap();
+
+    let turnout = |helper: &Helper, proposal_id: u64| {
+        let proposal = helper.proposal(proposal_id);
+        Decimal::from_ratio(
+            proposal.for_power + proposal.against_power,
+            proposal.total_voting_power,
+        )
+    };
+    let turnout1 = turnout(&helper, 1);
+    let turnout2 = turnout(&helper, 2);
+    let passed = [1, 2]
+        .into_iter()
+        .filter(|id| helper.proposal(*id).status == ProposalStatus::Passed)
+        .count() as u64;
+    assert_eq!(helper.proposal(2).status, ProposalStatus::Rejected);
+
+    let stats = helper.governance_stats();
+    assert_eq!(stats.stats.concluded_proposals, 2);
+    assert_eq!(stats.stats.passed_proposals, passed);
+    assert_eq!(stats.stats.rejected_proposals, 2 - passed);
+    assert_eq!(stats.stats.turnout_sum, turnout1 + turnout2);
+    assert_eq!(
+        stats.average_turnout,
+        (turnout1 + turnout2) / Decimal::from_ratio(2u8, 1u8)
+    );
+    assert_eq!(stats.latest_turnouts, vec![(2, turnout2), (1, turnout1)]);
+}
+
+#[test]
+fn test_adaptive_quorum() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+    let assembly = helper.assembly.clone();
+
+    let submitter = Addr::unchecked("submitter");
+    helper.get_xastro(&submitter, 3 * PROPOSAL_REQUIRED_DEPOSIT.u128() + 1000); // initial stake consumes 1000 xASTRO
+    let user1 = Addr::unchecked("user1");
+    helper.get_xastro(&user1, 10 * PROPOSAL_REQUIRED_DEPOSIT.u128());
+    let user2 = Addr::unchecked("user2");
+    helper.get_xastro(&user2, PROPOSAL_REQUIRED_DEPOSIT.u128());
+
+    helper.next_block(10);
+
+    let update_quorum_mode = |helper: &mut Helper, quorum_mode: QuorumMode| {
+        helper.app.execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::UpdateConfig(Box::new(UpdateConfig {
+                quorum_mode: Some(quorum_mode),
+                ..Default::default()
+            })),
+            &[],
+        )
+    };
+
+    let static_quorum = Decimal::from_str(MINIMUM_PROPOSAL_REQUIRED_QUORUM_PERCENTAGE).unwrap();
+    assert_eq!(
+        helper.adaptive_quorum(),
+        AdaptiveQuorumResponse {
+            quorum_mode: QuorumMode::Static,
+            average_turnout: Decimal::zero(),
+            sampled_proposals: 0,
+            required_quorum: static_quorum,
+        }
+    );
+
+    let params = AdaptiveQuorumParams {
+        lookback: 2,
+        turnout_factor: Decimal::percent(50),
+        min_quorum: Decimal::percent(5),
+        max_quorum: Decimal::percent(50),
+    };
+
+    let err = update_quorum_mode(
+        &mut helper,
+        QuorumMode::Adaptive(AdaptiveQuorumParams {
+            lookback: MAX_QUORUM_LOOKBACK + 1,
+            ..params.clone()
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: The adaptive quorum lookback must be between 1 and 30 proposals"
+    );
+
+    let err = update_quorum_mode(
+        &mut helper,
+        QuorumMode::Adaptive(AdaptiveQuorumParams {
+            min_quorum: Decimal::percent(60),
+            ..params.clone()
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: The adaptive quorum bounds must be ordered and lie between 1% and 100%"
+    );
+
+    // No concluded proposals yet, thus the static quorum is clamped by the adaptive bounds
+    update_quorum_mode(&mut helper, QuorumMode::Adaptive(params.clone())).unwrap();
+    assert_eq!(
+        helper.adaptive_quorum(),
+        AdaptiveQuorumResponse {
+            quorum_mode: QuorumMode::Adaptive(params.clone()),
+            average_turnout: Decimal::zero(),
+            sampled_proposals: 0,
+            required_quorum: params.min_quorum,
+        }
+    );
+
+    helper.submit_sample_proposal(&submitter);
+    assert_eq!(helper.proposal(1).adaptive_quorum, Some(params.min_quorum));
+
+    // Proposals submitted in the static mode keep the static quorum
+    update_quorum_mode(&mut helper, QuorumMode::Static).unwrap();
+    helper.submit_sample_proposal(&submitter);
+    assert_eq!(helper.proposal(2).adaptive_quorum, None);
+    update_quorum_mode(&mut helper, QuorumMode::Adaptive(params.clone())).unwrap();
+
+    helper.next_block(10);
+    helper
+        .cast_vote(1, &user1, ProposalVoteOption::For)
+        .unwrap();
+    helper
+        .cast_vote(1, &user2, ProposalVoteOption::For)
+        .unwrap();
+    helper
+        .cast_vote(2, &user2, ProposalVoteOption::For)
+        .unwrap();
+
+    helper.next_block_height(PROPOSAL_VOTING_PERIOD);
+    helper.end_expired_proposals(None).unwrap();
+    assert_eq!(helper.proposal(1).status, ProposalStatus::Passed);
+    assert_eq!(helper.proposal(2).status, ProposalStatus::Passed);
+
+    let turnout = |helper: &Helper, proposal_id: u64| {
+        let proposal = helper.proposal(proposal_id);
+        Decimal::from_ratio(
+            proposal.for_power + proposal.against_power,
+            proposal.total_voting_power,
+        )
+    };
+    let turnout1 = turnout(&helper, 1);
+    let turnout2 = turnout(&helper, 2);
+
+    let average_turnout = (turnout1 + turnout2) / Decimal::from_ratio(2u8, 1u8);
+    let required_quorum =
+        (average_turnout * params.turnout_factor).clamp(params.min_quorum, params.max_quorum);
+    assert_eq!(
+        helper.adaptive_quorum(),
+        AdaptiveQuorumResponse {
+            quorum_mode: QuorumMode::Adaptive(params.clone()),
+            average_turnout,
+            sampled_proposals: 2,
+            required_quorum,
+        }
+    );
+    // Turnout of user2 alone is above the static quorum but below the adaptive one
+    assert!(turnout2 > static_quorum && turnout2 < required_quorum);
+
+    helper.submit_sample_proposal(&submitter);
+    assert_eq!(helper.proposal(3).adaptive_quorum, Some(required_quorum));
+
+    helper.next_block(10);
+    helper
+        .cast_vote(3, &user2, ProposalVoteOption::For)
+        .unwrap();
+    helper.next_block_height(PROPOSAL_VOTING_PERIOD);
+    helper.end_proposal(3).unwrap();
+    assert_eq!(helper.proposal(3).status, ProposalStatus::Rejected);
+
+    // Only the latest concluded proposals are sampled
+    let turnout3 = turnout(&helper, 3);
+    let quorum = helper.adaptive_quorum();
+    assert_eq!(quorum.sampled_proposals, 2);
+    assert_eq!(
+        quorum.average_turnout,
+        (turnout2 + turnout3) / Decimal::from_ratio(2u8, 1u8)
+    );
+    assert_eq!(quorum.required_quorum, params.min_quorum);
+}
+
+#[test]
+fn test_min_xastro_age() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+    let assembly = helper.assembly.clone();
+
+    let min_xastro_age = 86400;
+    let mut update_config = UpdateConfig {
+        min_xastro_age: Some(MAX_XASTRO_AGE + 1),
+        ..Default::default()
+    };
+    let err = helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::UpdateConfig(Box::new(update_config.clone())),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Std(StdError::generic_err(format!(
+            "The minimum xASTRO age cannot be higher than {MAX_XASTRO_AGE} seconds"
+        )))
+    );
+
+    update_config.min_xastro_age = Some(min_xastro_age);
+    helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::UpdateConfig(Box::new(update_config)),
+            &[],
+        )
+        .unwrap();
+
+    let submitter = Addr::unchecked("submitter");
+    helper.get_xastro(&submitter, PROPOSAL_REQUIRED_DEPOSIT.u128() + 1000); // initial stake consumes 1000 xASTRO
+    helper.next_block(10);
+
+    let query_eligibility = |helper: &Helper| -> ProposalEligibilityResponse {
+        helper
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &helper.assembly,
+                &QueryMsg::ProposalEligibility {
+                    address: submitter.to_string(),
+                },
+            )
+            .unwrap()
+    };
+    assert_eq!(
+        query_eligibility(&helper),
+        ProposalEligibilityResponse {
+            eligible: false,
+            aged_balance: Uint128::zero(),
+            required_deposit: PROPOSAL_REQUIRED_DEPOSIT,
+            min_xastro_age,
+        }
+    );
+
+    let submit_msg = ExecuteMsg::SubmitProposal {
+        title: "Test title".to_string(),
+        description: "Test description".to_string(),
+        link: None,
+        messages: vec![],
+        ibc_channel: None,
+        commit_reveal: false,
+        tags: vec![],
+    };
+    let deposit = coins(PROPOSAL_REQUIRED_DEPOSIT.u128(), &helper.xastro_denom);
+
+    // Freshly staked xASTRO can't be used to submit a proposal
+    let err = helper
+        .app
+        .execute_contract(submitter.clone(), assembly.clone(), &submit_msg, &deposit)
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InsufficientXastroAge {
+            min_age: min_xastro_age
+        }
+    );
+
+    helper.next_block(min_xastro_age);
+    assert!(query_eligibility(&helper).eligible);
+
+    helper
+        .app
+        .execute_contract(submitter.clone(), assembly.clone(), &submit_msg, &deposit)
+        .unwrap();
+    assert_eq!(helper.proposal(1).submitter, submitter);
+}
+
+#[test]
+fn test_proposal_review_window() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+    let assembly = helper.assembly.clone();
+
+    let review_period = 1000;
+    helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::UpdateConfig(Box::new(UpdateConfig {
+                proposal_review_period: Some(review_period),
+                ..Default::default()
+            })),
+            &[],
+        )
+        .unwrap();
+
+    let user = Addr::unchecked("user");
+    helper.get_xastro(&user, 2 * PROPOSAL_REQUIRED_DEPOSIT.u128() + 1000); // initial stake consumes 1000 xASTRO
+    helper.next_block(10);
+
+    helper.submit_sample_proposal(&user);
+    helper.submit_sample_proposal(&user);
+
+    let proposal = helper.proposal(1);
+    assert_eq!(
+        proposal.review_end_block,
+        Some(proposal.start_block + review_period)
+    );
+    assert_eq!(
+        proposal.end_block,
+        proposal.start_block + review_period + PROPOSAL_VOTING_PERIOD
+    );
+
+    let query_phase = |helper: &Helper, proposal_id: u64| -> ProposalPhase {
+        helper
+            .app
+            .wrap()
+            .query_wasm_smart(&helper.assembly, &QueryMsg::ProposalPhase { proposal_id })
+            .unwrap()
+    };
+    assert_eq!(query_phase(&helper, 1), ProposalPhase::Review);
+
+    // Voting is not open during the review window
+    let err = helper
+        .cast_vote(1, &user, ProposalVoteOption::For)
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::VotingNotStarted {}
+    );
+
+    let amend_msg = ExecuteMsg::AmendProposal {
+        proposal_id: 1,
+        description: Some("Amended description".to_string()),
+        messages: Some(vec![]),
+    };
+
+    let err = helper
+        .app
+        .execute_contract(
+            Addr::unchecked("stranger"),
+            assembly.clone(),
+            &amend_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    let err = helper
+        .app
+        .execute_contract(
+            user.clone(),
+            assembly.clone(),
+            &ExecuteMsg::AmendProposal {
+                proposal_id: 1,
+                description: None,
+                messages: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::EmptyProposalAmendment {}
+    );
+
+    helper
+        .app
+        .execute_contract(user.clone(), assembly.clone(), &amend_msg, &[])
+        .unwrap();
+    let amended = helper.proposal(1);
+    assert!(amended.amended);
+    assert_eq!(amended.description, "Amended description");
+    assert_eq!(amended.messages, vec![]);
+    assert_eq!(amended.end_block, proposal.end_block);
+
+    // A proposal can be amended only once
+    let err = helper
+        .app
+        .execute_contract(user.clone(), assembly.clone(), &amend_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ProposalAlreadyAmended {}
+    );
+
+    // Withdraw the second proposal with full deposit refund
+    let withdraw_msg = ExecuteMsg::WithdrawProposal { proposal_id: 2 };
+    let err = helper
+        .app
+        .execute_contract(
+            Addr::unchecked("stranger"),
+            assembly.clone(),
+            &withdraw_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    let xastro_denom = helper.xastro_denom.clone();
+    assert_eq!(
+        helper.query_balance(&user, &xastro_denom).unwrap(),
+        Uint128::zero()
+    );
+    helper
+        .app
+        .execute_contract(user.clone(), assembly.clone(), &withdraw_msg, &[])
+        .unwrap();
+    assert_eq!(helper.proposal(2).status, ProposalStatus::Withdrawn);
+    assert_eq!(
+        helper.query_balance(&user, &xastro_denom).unwrap(),
+        PROPOSAL_REQUIRED_DEPOSIT
+    );
+
+    let err = helper
+        .app
+        .execute_contract(user.clone(), assembly.clone(), &withdraw_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ProposalNotInReview {}
+    );
+
+    // After the review window the proposal is immutable and voting opens
+    helper.next_block_height(review_period + 1);
+    assert_eq!(query_phase(&helper, 1), ProposalPhase::Voting);
+
+    let err = helper
+        .app
+        .execute_contract(
+            user.clone(),
+            assembly.clone(),
+            &ExecuteMsg::WithdrawProposal { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ProposalNotInReview {}
+    );
+
+    helper.cast_vote(1, &user, ProposalVoteOption::For).unwrap();
+
+    // Withdrawn proposals can't be voted on
+    let err = helper
+        .cast_vote(2, &user, ProposalVoteOption::For)
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ProposalNotActive {}
+    );
+}
+
+#[test]
+fn test_relayed_votes() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+    let assembly = helper.assembly.clone();
+
+    let submitter = Addr::unchecked("submitter");
+    // Initial stake consumes 1000 xASTRO
+    helper.get_xastro(&submitter, PROPOSAL_REQUIRED_DEPOSIT.u128() * 2 + 1000);
+    let alice = Addr::unchecked("alice");
+    helper.get_xastro(&alice, 100_000000u128);
+    let bob = Addr::unchecked("bob");
+    helper.get_xastro(&bob, 50_000000u128);
+    let relayer = Addr::unchecked("relayer");
+
+    helper.next_block(10);
+    helper.submit_sample_proposal(&submitter);
+    helper.submit_sample_proposal(&submitter);
+
+    let alice_key = SigningKey::from_slice(&[1; 32]).unwrap();
+    let bob_key = SigningKey::from_slice(&[2; 32]).unwrap();
+    for (user, key) in [(&alice, &alice_key), (&bob, &bob_key)] {
+        let pubkey = key.verifying_key().to_encoded_point(true);
+        helper
+            .app
+            .execute_contract(
+                user.clone(),
+                assembly.clone(),
+                &ExecuteMsg::SetRelayPubkey {
+                    pubkey: Binary::from(pubkey.as_bytes()),
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    let sign = |helper: &Helper, key: &SigningKey, voter: &Addr, proposal_id: u64, nonce: u64| {
+        let digest = RelayVotePayload {
+            chain_id: helper.app.block_info().chain_id,
+            assembly: assembly.to_string(),
+            voter: voter.to_string(),
+            proposal_id,
+            vote: ProposalVoteOption::For,
+            nonce,
+        }
+        .digest()
+        .unwrap();
+        let signature: Signature = key.sign_prehash(&digest).unwrap();
+        Binary::from(signature.to_bytes().as_slice())
+    };
+    let relay_vote = |helper: &mut Helper, voter: &Addr, proposal_id: u64, nonce: u64| {
+        let key = if *voter == alice {
+            &alice_key
+        } else {
+            &bob_key
+        };
+        let signature = sign(helper, key, voter, proposal_id, nonce);
+        helper.app.execute_contract(
+            relayer.clone(),
+            assembly.clone(),
+            &ExecuteMsg::RelayVote(RelayedVote {
+                voter: voter.to_string(),
+                proposal_id,
+                vote: ProposalVoteOption::For,
+                nonce,
+                signature,
+            }),
+            &[],
+        )
+    };
+    let relay_nonce = |helper: &Helper, user: &Addr| -> u64 {
+        helper
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &assembly,
+                &QueryMsg::RelayNonce {
+                    user: user.to_string(),
+                },
+            )
+            .unwrap()
+    };
+
+    // Only allowlisted relayers can relay votes
+    let err = relay_vote(&mut helper, &alice, 1, 0).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // Only the Assembly can manage the allowlist
+    let update_msg = ExecuteMsg::UpdateVoteRelayers {
+        add: vec![VoteRelayer {
+            address: relayer.to_string(),
+            quota: 3,
+            fee_grant: coins(1_000000, "untrn"),
+        }],
+        remove: vec![],
+    };
+    let err = helper
+        .app
+        .execute_contract(owner.clone(), assembly.clone(), &update_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    helper
+        .app
+        .execute_contract(assembly.clone(), assembly.clone(), &update_msg, &[])
+        .unwrap();
+
+    let err = relay_vote(&mut helper, &alice, 1, 1).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidRelayNonce { expected: 0 }
+    );
+
+    // Signature must be made by the voter's key over the same payload
+    let signature = sign(&helper, &bob_key, &alice, 1, 0);
+    let err = helper
+        .app
+        .execute_contract(
+            relayer.clone(),
+            assembly.clone(),
+            &ExecuteMsg::RelayVote(RelayedVote {
+                voter: alice.to_string(),
+                proposal_id: 1,
+                vote: ProposalVoteOption::For,
+                nonce: 0,
+                signature,
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidRelaySignature {}
+    );
+    let signature = sign(&helper, &alice_key, &alice, 1, 0);
+    let err = helper
+        .app
+        .execute_contract(
+            relayer.clone(),
+            assembly.clone(),
+            &ExecuteMsg::RelayVote(RelayedVote {
+                voter: alice.to_string(),
+                proposal_id: 1,
+                vote: ProposalVoteOption::Against,
+                nonce: 0,
+                signature,
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidRelaySignature {}
+    );
+
+    // Users without a registered key can't have votes relayed
+    let carol = Addr::unchecked("carol");
+    let err = relay_vote(&mut helper, &carol, 1, 0).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RelayPubkeyNotSet(carol.to_string())
+    );
+
+    // Relayer grants a fee allowance so the user can register the key without gas tokens
+    let resp = helper
+        .app
+        .execute_contract(
+            relayer.clone(),
+            assembly.clone(),
+            &ExecuteMsg::GrantRelayFeeAllowance {
+                grantee: carol.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    assert!(resp.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "grant_relay_fee_allowance")
+            .add_attribute("grantee", &carol)
+    ));
+
+    relay_vote(&mut helper, &alice, 1, 0).unwrap();
+    assert_eq!(helper.proposal(1).for_power.u128(), 100_000000);
+    assert_eq!(relay_nonce(&helper, &alice), 1);
+
+    // The same payload can't be replayed
+    let err = relay_vote(&mut helper, &alice, 2, 0).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidRelayNonce { expected: 1 }
+    );
+
+    relay_vote(&mut helper, &bob, 1, 0).unwrap();
+    assert_eq!(helper.proposal(1).for_power.u128(), 150_000000);
+
+    // Relayer quota is exhausted
+    let err = relay_vote(&mut helper, &alice, 2, 1).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RelayerQuotaExceeded {}
+    );
+    let relayers: Vec<(Addr, VoteRelayerInfo)> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(&assembly, &QueryMsg::VoteRelayers {})
+        .unwrap();
+    assert_eq!(relayers.len(), 1);
+    assert_eq!(relayers[0].0, relayer);
+    assert_eq!(relayers[0].1.usage.relayed, 3);
+
+    // Quota is renewed in the next period
+    helper.next_block(RELAY_QUOTA_PERIOD);
+    relay_vote(&mut helper, &alice, 2, 1).unwrap();
+    assert_eq!(helper.proposal(2).for_power.u128(), 100_000000);
+    assert_eq!(relay_nonce(&helper, &alice), 2);
+
+    // Removed relayers can't relay votes anymore
+    helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::UpdateVoteRelayers {
+                add: vec![],
+                remove: vec![relayer.to_string()],
+            },
+            &[],
+        )
+        .unwrap();
+    let err = relay_vote(&mut helper, &bob, 2, 1).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+}
+
+#[test]
+fn test_simulate_execution() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+    let assembly = helper.assembly.clone();
+
+    let user = Addr::unchecked("user");
+    helper.get_xastro(&user, 3 * PROPOSAL_REQUIRED_DEPOSIT.u128() + 1000);
+    helper.next_block(10);
+
+    helper.submit_proposal(
+        &user,
+        vec![
+            BankMsg::Send {
+                to_address: "receiver".to_string(),
+                amount: coins(1, "uusd"),
+            }
+            .into(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "X".to_string(),
+                msg: Binary::from(b"{invalid".to_vec()),
+                funds: vec![coin(0, "uusd")],
+            }),
+            BankMsg::Burn { amount: vec![] }.into(),
+        ],
+    );
+
+    let resp: SimulateExecutionResponse = helper
+        .app
+        .wrap()
+        .query_wasm_smart(&assembly, &QueryMsg::SimulateExecution { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(resp.status, ProposalStatus::Active);
+    assert!(!resp.executable);
+    assert_eq!(resp.messages.len(), 3);
+    assert_eq!(
+        resp.messages[0],
+        MessageDiagnostics {
+            index: 0,
+            msg_type: "bank_send".to_string(),
+            issues: vec![],
+        }
+    );
+    assert_eq!(resp.messages[1].msg_type, "wasm_execute");
+    assert_eq!(resp.messages[1].issues.len(), 3);
+    assert_eq!(resp.messages[1].issues[0], "Invalid contract address: X");
+    assert!(resp.messages[1].issues[1].starts_with("Message payload is not valid JSON"));
+    assert_eq!(resp.messages[1].issues[2], "Zero amount of uusd");
+    assert_eq!(
+        resp.messages[2],
+        MessageDiagnostics {
+            index: 2,
+            msg_type: "bank_burn".to_string(),
+            issues: vec!["Coins must not be empty".to_string()],
+        }
+    );
+
+    // Clean proposal becomes executable only once it has passed
+    helper.submit_proposal(
+        &user,
+        vec![BankMsg::Send {
+            to_address: "receiver".to_string(),
+            amount: coins(1, "uusd"),
+        }
+        .into()],
+    );
+    helper.cast_vote(2, &user, ProposalVoteOption::For).unwrap();
+
+    let resp: SimulateExecutionResponse = helper
+        .app
+        .wrap()
+        .query_wasm_smart(&assembly, &QueryMsg::SimulateExecution { proposal_id: 2 })
+        .unwrap();
+    assert!(!resp.executable);
+
+    helper.next_block_height(PROPOSAL_VOTING_PERIOD + 1);
+    helper.end_proposal(2).unwrap();
+
+    let resp: SimulateExecutionResponse = helper
+        .app
+        .wrap()
+        .query_wasm_smart(&assembly, &QueryMsg::SimulateExecution { proposal_id: 2 })
+        .unwrap();
+    assert_eq!(resp.status, ProposalStatus::Passed);
+    assert!(resp.executable);
+}
+
+#[test]
+fn test_pausable_targets() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+    let assembly = helper.assembly.clone();
+
+    let user = Addr::unchecked("user");
+    helper.get_xastro(&user, 4 * PROPOSAL_REQUIRED_DEPOSIT.u128() + 1000);
+    helper.next_block(10);
+
+    let pausable_code = helper.app.store_code(pausable_contract());
+    let [target, broken_target] = ["target", "broken_target"].map(|label| {
+        helper
+            .app
+            .instantiate_contract(pausable_code, owner.clone(), &Empty {}, &[], label, None)
+            .unwrap()
+    });
+    let is_paused = |helper: &Helper, contract: &Addr| -> bool {
+        helper
+            .app
+            .wrap()
+            .query_wasm_smart(contract, &Empty {})
+            .unwrap()
+    };
+    let paused_targets = |helper: &Helper| -> Vec<(Addr, PausedTarget)> {
+        helper
+            .app
+            .wrap()
+            .query_wasm_smart(&assembly, &QueryMsg::PausedTargets {})
+            .unwrap()
+    };
+
+    helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::UpdateConfig(Box::new(UpdateConfig {
+                tags_add: Some(vec![CRITICAL_PROPOSAL_TAG.to_string()]),
+                ..Default::default()
+            })),
+            &[],
+        )
+        .unwrap();
+
+    // Only the Assembly can manage the registry
+    let update_msg = ExecuteMsg::UpdatePausableTargets {
+        add: vec![
+            PausableTarget {
+                contract: target.to_string(),
+                pause_msg: to_json_binary(&PausableMsg::Pause {}).unwrap(),
+                unpause_msg: to_json_binary(&PausableMsg::Unpause {}).unwrap(),
+            },
+            PausableTarget {
+                contract: broken_target.to_string(),
+                pause_msg: Binary::from(br#"{"halt":{}}"#.to_vec()),
+                unpause_msg: to_json_binary(&PausableMsg::Unpause {}).unwrap(),
+            },
+        ],
+        remove: vec![],
+    };
+    let err = helper
+        .app
+        .execute_contract(owner.clone(), assembly.clone(), &update_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    helper
+        .app
+        .execute_contract(assembly.clone(), assembly.clone(), &update_msg, &[])
+        .unwrap();
+
+    let targets: Vec<(Addr, PausableTargetInfo)> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(&assembly, &QueryMsg::PausableTargets {})
+        .unwrap();
+    assert_eq!(targets.len(), 2);
+
+    let submit = |helper: &mut Helper, tags: &[&str]| {
+        helper.app.execute_contract(
+            user.clone(),
+            assembly.clone(),
+            &ExecuteMsg::SubmitProposal {
+                title: "Test title".to_string(),
+                description: "Test description".to_string(),
+                link: None,
+                messages: [&target, &broken_target, &target]
+                    .into_iter()
+                    .map(|contract| wasm_execute(contract, &Empty {}, vec![]).unwrap().into())
+                    .collect(),
+                ibc_channel: None,
+                commit_reveal: false,
+                tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            },
+            &coins(PROPOSAL_REQUIRED_DEPOSIT.u128(), &helper.xastro_denom),
+        )
+    };
+
+    // Regular proposals don't pause targets
+    submit(&mut helper, &[]).unwrap();
+    assert!(!is_paused(&helper, &target));
+    assert!(paused_targets(&helper).is_empty());
+
+    // Failing pause calls don't revert the submission
+    let resp = submit(&mut helper, &[CRITICAL_PROPOSAL_TAG]).unwrap();
+    assert!(resp.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.value == "pausable_target_call_failed")));
+    assert!(is_paused(&helper, &target));
+    assert!(!is_paused(&helper, &broken_target));
+
+    // Already paused targets are held by every live critical proposal
+    helper.next_block_height(10);
+    submit(&mut helper, &[CRITICAL_PROPOSAL_TAG]).unwrap();
+    let paused = paused_targets(&helper);
+    assert_eq!(paused.len(), 2);
+    for (_, paused_target) in &paused {
+        assert_eq!(paused_target.proposals, vec![2, 3]);
+        assert_eq!(
+            paused_target.unpause_msg,
+            to_json_binary(&PausableMsg::Unpause {}).unwrap()
+        );
+    }
+
+    helper.next_block_height(PROPOSAL_VOTING_PERIOD);
+    helper.end_proposal(2).unwrap();
+    assert!(is_paused(&helper, &target));
+    let paused = paused_targets(&helper);
+    assert_eq!(paused.len(), 2);
+    assert!(paused
+        .iter()
+        .all(|(_, paused_target)| paused_target.proposals == vec![3]));
+
+    // Targets are unpaused once the last critical proposal concludes
+    helper.next_block_height(10);
+    helper.end_proposal(3).unwrap();
+    assert!(!is_paused(&helper, &target));
+    assert!(paused_targets(&helper).is_empty());
+}
+
+#[test]
+fn test_cast_votes() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+
+    let user = Addr::unchecked("user");
+    helper.get_xastro(&user, 3 * PROPOSAL_REQUIRED_DEPOSIT.u128() + 1000);
+    let other = Addr::unchecked("other");
+    helper.get_xastro(&other, 1000u64);
+    helper.next_block(10);
+
+    helper.submit_sample_proposal(&user);
+    helper.submit_sample_proposal(&user);
+    helper.submit_sample_proposal(&user);
+    helper.cast_vote(3, &user, ProposalVoteOption::For).unwrap();
+
+    let err = helper.cast_votes(&user, vec![]).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidVoteBatch {
+            max: MAX_BATCH_VOTES
+        }
+    );
+    let err = helper
+        .cast_votes(
+            &user,
+            vec![(1, ProposalVoteOption::For); MAX_BATCH_VOTES + 1],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidVoteBatch {
+            max: MAX_BATCH_VOTES
+        }
+    );
+
+    let resp = helper
+        .cast_votes(
+            &user,
+            vec![
+                (1, ProposalVoteOption::For),
+                (2, ProposalVoteOption::Against),
+                (1, ProposalVoteOption::Against),
+                (3, ProposalVoteOption::For),
+                (10, ProposalVoteOption::For),
+            ],
+        )
+        .unwrap();
+    assert!(resp.has_event(&Event::new("wasm").add_attributes([
+        attr("action", "cast_votes"),
+        attr("voted", "2"),
+        attr("skipped", "3"),
+    ])));
+    for (proposal_id, vote, reason) in [
+        ("1", "against", "User already voted!"),
+        ("3", "for", "User already voted!"),
+        ("10", "for", "Proposal not found"),
+    ] {
+        assert!(
+            resp.has_event(&Event::new("wasm-batch_vote").add_attributes([
+                attr("proposal_id", proposal_id),
+                attr("vote", vote),
+                attr("result", "skipped"),
+                attr("reason", reason),
+            ]))
+        );
+    }
+
+    let user_vp = helper.user_vp(&user, 1);
+    assert_eq!(helper.proposal(1).for_power, user_vp);
+    assert_eq!(helper.proposal(2).against_power, helper.user_vp(&user, 2));
+    assert!(
+        resp.has_event(&Event::new("wasm-batch_vote").add_attributes([
+            attr("proposal_id", "1"),
+            attr("vote", "for"),
+            attr("result", "voted"),
+            attr("voting_power", user_vp.to_string()),
+        ]))
+    );
+    assert_eq!(helper.voter_stats(&user).proposals_voted, 3);
+
+    // Votes on ended proposals are skipped
+    helper.next_block_height(PROPOSAL_VOTING_PERIOD + 1);
+    let resp = helper
+        .cast_votes(&other, vec![(1, ProposalVoteOption::For)])
+        .unwrap();
+    assert!(
+        resp.has_event(&Event::new("wasm-batch_vote").add_attributes([
+            attr("result", "skipped"),
+            attr("reason", "Voting period ended!"),
+        ]))
+    );
+    assert_eq!(helper.proposal(1).for_power, user_vp);
+}
+
+#[test]
+fn test_scheduled_proposals() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+    let assembly = helper.assembly.clone();
+
+    let template = ProposalTemplate {
+        title: "Follow-up title".to_string(),
+        description: "Follow-up description".to_string(),
+        link: None,
+        messages: vec![],
+        ibc_channel: None,
+        commit_reveal: false,
+        tags: vec![],
+    };
+    let now = helper.app.block_info().time.seconds();
+    let open_at = now + 86400;
+
+    // Only the Assembly itself can schedule proposals
+    let err = helper
+        .app
+        .execute_contract(
+            Addr::unchecked("random"),
+            assembly.clone(),
+            &ExecuteMsg::ScheduleProposal {
+                open_at,
+                template: template.clone(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    let err = helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::ScheduleProposal {
+                open_at: now,
+                template: template.clone(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidScheduleTime {}
+    );
+
+    let resp = helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::ScheduleProposal {
+                open_at,
+                template: template.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+    assert!(resp.has_event(&Event::new("wasm").add_attributes([
+        attr("action", "schedule_proposal"),
+        attr("scheduled_id", "1"),
+        attr("open_at", open_at.to_string()),
+    ])));
+
+    let scheduled: Vec<ScheduledProposal> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &assembly,
+            &QueryMsg::ScheduledProposals {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        scheduled,
+        vec![ScheduledProposal {
+            id: 1,
+            open_at,
+            template: template.clone(),
+        }]
+    );
+
+    // Anyone can open the proposal but only when its time comes
+    let open_msg = ExecuteMsg::OpenScheduledProposal { id: 1 };
+    let err = helper
+        .app
+        .execute_contract(Addr::unchecked("random"), assembly.clone(), &open_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ScheduledProposalNotReady(open_at)
+    );
+
+    helper.next_block(86400);
+
+    let resp = helper
+        .app
+        .execute_contract(Addr::unchecked("random"), assembly.clone(), &open_msg, &[])
+        .unwrap();
+    assert!(resp.has_event(&Event::new("wasm").add_attributes([
+        attr("action", "submit_proposal"),
+        attr("submitter", assembly.as_str()),
+        attr("proposal_id", "1"),
+    ])));
+    assert!(resp.has_event(&Event::new("wasm").add_attribute("scheduled_id", "1")));
+
+    let proposal = helper.proposal(1);
+    assert_eq!(proposal.submitter, assembly);
+    assert_eq!(proposal.deposit_amount, Uint128::zero());
+    assert_eq!(proposal.title, template.title);
+
+    let scheduled: Vec<ScheduledProposal> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &assembly,
+            &QueryMsg::ScheduledProposals {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(scheduled.is_empty());
+
+    let err = helper
+        .app
+        .execute_contract(Addr::unchecked("random"), assembly.clone(), &open_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ScheduledProposalNotFound(1)
+    );
+
+    // Proposal without a deposit is concluded without a refund
+    helper.next_block_height(PROPOSAL_VOTING_PERIOD + 1);
+    helper.end_proposal(1).unwrap();
+    assert_eq!(helper.proposal(1).status, ProposalStatus::Rejected);
+
+    // Cancel a scheduled proposal
+    let open_at = helper.app.block_info().time.seconds() + 86400;
+    helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::ScheduleProposal { open_at, template },
+            &[],
+        )
+        .unwrap();
+
+    let cancel_msg = ExecuteMsg::CancelScheduledProposal { id: 2 };
+    let err = helper
+        .app
+        .execute_contract(
+            Addr::unchecked("random"),
+            assembly.clone(),
+            &cancel_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    let resp = helper
+        .app
+        .execute_contract(assembly.clone(), assembly.clone(), &cancel_msg, &[])
+        .unwrap();
+    assert!(resp.has_event(&Event::new("wasm").add_attributes([
+        attr("action", "cancel_scheduled_proposal"),
+        attr("scheduled_id", "2"),
+    ])));
+
+    let err = helper
+        .app
+        .execute_contract(
+            Addr::unchecked("random"),
+            assembly.clone(),
+            &ExecuteMsg::OpenScheduledProposal { id: 2 },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ScheduledProposalNotFound(2)
+    );
+}
+
+#[test]
+fn test_conclusion_hooks() {
+    let owner = Addr::unchecked("owner");
+    let mut helper = Helper::new(&owner).unwrap();
+    let assembly = helper.assembly.clone();
+
+    let user = Addr::unchecked("user");
+    helper.get_xastro(&user, 2 * PROPOSAL_REQUIRED_DEPOSIT.u128() + 1000);
+    helper.next_block(10);
+
+    let hook_code = helper.app.store_code(conclusion_hook_contract());
+    let [hook, migrations_hook, disabled_hook] =
+        ["hook", "migrations_hook", "disabled_hook"].map(|label| {
+            helper
+                .app
+                .instantiate_contract(hook_code, owner.clone(), &Empty {}, &[], label, None)
+                .unwrap()
+        });
+    // Doesn't understand hook notifications
+    let pausable_code = helper.app.store_code(pausable_contract());
+    let broken_hook = helper
+        .app
+        .instantiate_contract(pausable_code, owner.clone(), &Empty {}, &[], "broken", None)
+        .unwrap();
+    let concluded = |helper: &Helper, contract: &Addr| -> Vec<u64> {
+        helper
+            .app
+            .wrap()
+            .query_wasm_smart(contract, &Empty {})
+            .unwrap()
+    };
+
+    let hooks = vec![
+        ConclusionHook {
+            contract: hook.to_string(),
+            filter: ConclusionHookFilter::default(),
+            enabled: true,
+        },
+        ConclusionHook {
+            contract: migrations_hook.to_string(),
+            filter: ConclusionHookFilter {
+                tags: vec![],
+                impact_classes: vec![ProposalImpactClass::ContractMigration],
+            },
+            enabled: true,
+        },
+        ConclusionHook {
+            contract: disabled_hook.to_string(),
+            filter: ConclusionHookFilter::default(),
+            enabled: false,
+        },
+        ConclusionHook {
+            contract: broken_hook.to_string(),
+            filter: ConclusionHookFilter::default(),
+            enabled: true,
+        },
+    ];
+    let update_msg = ExecuteMsg::UpdateConclusionHooks {
+        add: hooks.clone(),
+        remove: vec![],
+    };
+
+    // Only the Assembly can manage hooks
+    for sender in [&user, &owner] {
+        let err = helper
+            .app
+            .execute_contract(sender.clone(), assembly.clone(), &update_msg, &[])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {}
+        );
+    }
+
+    let too_many = (0..=MAX_CONCLUSION_HOOKS)
+        .map(|i| ConclusionHook {
+            contract: format!("hook{i}"),
+            filter: ConclusionHookFilter::default(),
+            enabled: true,
+        })
+        .collect();
+    let err = helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::UpdateConclusionHooks {
+                add: too_many,
+                remove: vec![],
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::TooManyConclusionHooks {
+            max: MAX_CONCLUSION_HOOKS
+        }
+    );
+
+    helper
+        .app
+        .execute_contract(assembly.clone(), assembly.clone(), &update_msg, &[])
+        .unwrap();
+    let registered: Vec<(Addr, ConclusionHookInfo)> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(&assembly, &QueryMsg::ConclusionHooks {})
+        .unwrap();
+    assert_eq!(registered.len(), 4);
+
+    helper.submit_proposal(&user, vec![]);
+    helper.next_block_height(PROPOSAL_VOTING_PERIOD + 1);
+    let resp = helper.end_proposal(1).unwrap();
+
+    // Broken hook doesn't block the proposal conclusion
+    assert!(resp.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.value == "conclusion_hook_failed")));
+    assert_eq!(helper.proposal(1).status, ProposalStatus::Rejected);
+    assert_eq!(concluded(&helper, &hook), vec![1]);
+    assert!(concluded(&helper, &migrations_hook).is_empty());
+    assert!(concluded(&helper, &disabled_hook).is_empty());
+
+    // Toggle hooks
+    let set_enabled = |contract: &Addr, enabled: bool| ExecuteMsg::SetConclusionHookEnabled {
+        contract: contract.to_string(),
+        enabled,
+    };
+    let err = helper
+        .app
+        .execute_contract(
+            user.clone(),
+            assembly.clone(),
+            &set_enabled(&disabled_hook, true),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    let err = helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &set_enabled(&Addr::unchecked("unknown"), true),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ConclusionHookNotFound("unknown".to_string())
+    );
+    for (contract, enabled) in [(&disabled_hook, true), (&hook, false)] {
+        helper
+            .app
+            .execute_contract(
+                assembly.clone(),
+                assembly.clone(),
+                &set_enabled(contract, enabled),
+                &[],
+            )
+            .unwrap();
+    }
+    helper
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &ExecuteMsg::UpdateConclusionHooks {
+                add: vec![],
+                remove: vec![broken_hook.to_string()],
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Proposals ended by the end blocker notify hooks as well
+    helper.submit_proposal(&user, vec![]);
+    helper.next_block_height(PROPOSAL_VOTING_PERIOD + 1);
+    let resp = helper.end_expired_proposals(None).unwrap();
+    assert!(!resp.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.value == "conclusion_hook_failed")));
+    assert_eq!(concluded(&helper, &hook), vec![1]);
+    assert_eq!(concluded(&helper, &disabled_hook), vec![2]);
+}
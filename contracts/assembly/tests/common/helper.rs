@@ -3,10 +3,12 @@
 use anyhow::Result as AnyResult;
 use astroport::staking;
 use astroport::token::Logo;
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::testing::MockApi;
 use cosmwasm_std::{
-    coin, coins, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env,
-    GovMsg, IbcMsg, IbcQuery, MemoryStorage, MessageInfo, Response, StdResult, Uint128, WasmMsg,
+    coin, coins, from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps,
+    DepsMut, Empty, Env, GovMsg, IbcMsg, IbcQuery, MemoryStorage, MessageInfo, Response, StdResult,
+    Uint128, WasmMsg,
 };
 use cw_multi_test::{
     App, AppResponse, BankKeeper, BasicAppBuilder, Contract, ContractWrapper, DistributionKeeper,
@@ -14,10 +16,12 @@ use cw_multi_test::{
 };
 
 use astroport_governance::assembly::{
-    ExecuteMsg, InstantiateMsg, Proposal, ProposalVoteOption, ProposalVoterResponse,
-    ProposalVotesResponse, QueryMsg, UpdateConfig, DELAY_INTERVAL, DEPOSIT_INTERVAL,
-    EXPIRATION_PERIOD_INTERVAL, MINIMUM_PROPOSAL_REQUIRED_QUORUM_PERCENTAGE,
-    MINIMUM_PROPOSAL_REQUIRED_THRESHOLD_PERCENTAGE, VOTING_PERIOD_INTERVAL,
+    AdaptiveQuorumResponse, ConclusionHookMsg, ExecuteMsg, GovernanceStatsResponse, InstantiateMsg,
+    Proposal, ProposalStatus, ProposalVoteOption, ProposalVoterResponse, ProposalVotesResponse,
+    QueryMsg, SudoMsg, UpdateConfig, VoteCommitment, VoterStats, VotingPowerBreakdown,
+    DELAY_INTERVAL, DEPOSIT_INTERVAL, EXPIRATION_PERIOD_INTERVAL,
+    MINIMUM_PROPOSAL_REQUIRED_QUORUM_PERCENTAGE, MINIMUM_PROPOSAL_REQUIRED_THRESHOLD_PERCENTAGE,
+    VOTING_PERIOD_INTERVAL,
 };
 use astroport_governance::builder_unlock::{CreateAllocationParams, Schedule};
 use astroport_governance::voting_escrow::UpdateMarketingInfo;
@@ -50,19 +54,26 @@ fn tracker_contract() -> Box<dyn Contract<Empty>> {
 }
 
 fn assembly_contract() -> Box<dyn Contract<Empty>> {
-    Box::new(ContractWrapper::new_with_empty(
-        astro_assembly::contract::execute,
-        astro_assembly::contract::instantiate,
-        astro_assembly::queries::query,
-    ))
+    Box::new(
+        ContractWrapper::new_with_empty(
+            astro_assembly::contract::execute,
+            astro_assembly::contract::instantiate,
+            astro_assembly::queries::query,
+        )
+        .with_sudo_empty(astro_assembly::sudo::sudo)
+        .with_reply_empty(astro_assembly::contract::reply),
+    )
 }
 
 fn vxastro_contract() -> Box<dyn Contract<Empty>> {
-    Box::new(ContractWrapper::new_with_empty(
-        astroport_voting_escrow::contract::execute,
-        astroport_voting_escrow::contract::instantiate,
-        astroport_voting_escrow::contract::query,
-    ))
+    Box::new(
+        ContractWrapper::new_with_empty(
+            astroport_voting_escrow::contract::execute,
+            astroport_voting_escrow::contract::instantiate,
+            astroport_voting_escrow::contract::query,
+        )
+        .with_reply_empty(astroport_voting_escrow::contract::reply),
+    )
 }
 
 fn builder_contract() -> Box<dyn Contract<Empty>> {
@@ -94,6 +105,107 @@ pub fn noop_contract() -> Box<dyn Contract<Empty>> {
     ))
 }
 
+#[cw_serde]
+pub enum PausableMsg {
+    Pause {},
+    Unpause {},
+}
+
+/// Mocks a contract which can be paused by governance. Queries return whether it is paused.
+pub fn pausable_contract() -> Box<dyn Contract<Empty>> {
+    fn instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: PausableMsg,
+    ) -> StdResult<Response> {
+        match msg {
+            PausableMsg::Pause {} => deps.storage.set(b"paused", b"true"),
+            PausableMsg::Unpause {} => deps.storage.remove(b"paused"),
+        }
+        Ok(Response::new())
+    }
+
+    fn query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        to_json_binary(&deps.storage.get(b"paused").is_some())
+    }
+
+    Box::new(ContractWrapper::new_with_empty(execute, instantiate, query))
+}
+
+/// Records ids of concluded proposals it was notified about
+pub fn conclusion_hook_contract() -> Box<dyn Contract<Empty>> {
+    fn instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: ConclusionHookMsg,
+    ) -> StdResult<Response> {
+        let ConclusionHookMsg::ProposalConcluded { proposal_id, .. } = msg;
+        let mut concluded: Vec<u64> = deps
+            .storage
+            .get(b"concluded")
+            .map(from_json)
+            .transpose()?
+            .unwrap_or_default();
+        concluded.push(proposal_id);
+        deps.storage.set(b"concluded", &to_json_binary(&concluded)?);
+        Ok(Response::new())
+    }
+
+    fn query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        let concluded: Vec<u64> = deps
+            .storage
+            .get(b"concluded")
+            .map(from_json)
+            .transpose()?
+            .unwrap_or_default();
+        to_json_binary(&concluded)
+    }
+
+    Box::new(ContractWrapper::new_with_empty(execute, instantiate, query))
+}
+
+pub fn migratable_contract(
+    migrate: fn(DepsMut, Env, Empty) -> StdResult<Response>,
+) -> Box<dyn Contract<Empty>> {
+    fn noop_execute(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn noop_query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        Ok(Default::default())
+    }
+
+    Box::new(
+        ContractWrapper::new_with_empty(noop_execute, noop_execute, noop_query)
+            .with_migrate_empty(migrate),
+    )
+}
+
 fn mock_emissions_controller() -> Box<dyn Contract<Empty>> {
     fn instantiate(
         _deps: DepsMut,
@@ -272,17 +384,8 @@ impl Helper {
             assembly.clone(),
             assembly.clone(),
             &ExecuteMsg::UpdateConfig(Box::new(UpdateConfig {
-                ibc_controller: None,
-                builder_unlock_addr: None,
-                proposal_voting_period: None,
-                proposal_effective_delay: None,
-                proposal_expiration_period: None,
-                proposal_required_deposit: None,
-                proposal_required_quorum: None,
-                proposal_required_threshold: None,
-                whitelist_remove: None,
-                whitelist_add: None,
                 vxastro: Some(vxastro.to_string()),
+                ..Default::default()
             })),
             &[],
         )
@@ -370,6 +473,7 @@ impl Helper {
                                 duration: 10,
                                 ..Default::default()
                             },
+                            schedule_template: None,
                         },
                     )],
                 },
@@ -379,6 +483,15 @@ impl Helper {
     }
 
     pub fn submit_proposal(&mut self, submitter: &Addr, messages: Vec<CosmosMsg>) {
+        self.submit_proposal_with_mode(submitter, messages, false)
+    }
+
+    pub fn submit_proposal_with_mode(
+        &mut self,
+        submitter: &Addr,
+        messages: Vec<CosmosMsg>,
+        commit_reveal: bool,
+    ) {
         self.app
             .execute_contract(
                 submitter.clone(),
@@ -389,12 +502,35 @@ impl Helper {
                     link: None,
                     messages,
                     ibc_channel: None,
+                    commit_reveal,
+                    tags: vec![],
                 },
                 &coins(PROPOSAL_REQUIRED_DEPOSIT.u128(), &self.xastro_denom),
             )
             .unwrap();
     }
 
+    pub fn submit_tagged_proposal(
+        &mut self,
+        submitter: &Addr,
+        tags: &[&str],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            submitter.clone(),
+            self.assembly.clone(),
+            &ExecuteMsg::SubmitProposal {
+                title: "Test title".to_string(),
+                description: "Test description".to_string(),
+                link: None,
+                messages: vec![],
+                ibc_channel: None,
+                commit_reveal: false,
+                tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            },
+            &coins(PROPOSAL_REQUIRED_DEPOSIT.u128(), &self.xastro_denom),
+        )
+    }
+
     pub fn submit_sample_proposal(&mut self, submitter: &Addr) {
         let assembly = self.assembly.clone();
         self.mint_coin(&assembly, coin(1, "some_coin"));
@@ -417,6 +553,13 @@ impl Helper {
         )
     }
 
+    pub fn end_expired_proposals(&mut self, limit: Option<u32>) -> AnyResult<AppResponse> {
+        self.app.wasm_sudo(
+            self.assembly.clone(),
+            &SudoMsg::EndExpiredProposals { limit },
+        )
+    }
+
     pub fn execute_proposal(&mut self, proposal_id: u64) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             Addr::unchecked("permissionless"),
@@ -466,6 +609,29 @@ impl Helper {
         )
     }
 
+    pub fn filtered_proposals(
+        &self,
+        tag: Option<&str>,
+        status: Option<ProposalStatus>,
+        start_after: Option<u64>,
+    ) -> Vec<u64> {
+        self.app
+            .wrap()
+            .query_wasm_smart::<Vec<Proposal>>(
+                &self.assembly,
+                &QueryMsg::FilteredProposals {
+                    tag: tag.map(|tag| tag.to_string()),
+                    status,
+                    start_after,
+                    limit: None,
+                },
+            )
+            .unwrap()
+            .into_iter()
+            .map(|proposal| proposal.proposal_id.u64())
+            .collect()
+    }
+
     pub fn proposal(&self, proposal_id: u64) -> Proposal {
         self.app
             .wrap()
@@ -480,6 +646,32 @@ impl Helper {
             .unwrap()
     }
 
+    pub fn voter_stats(&self, address: &Addr) -> VoterStats {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.assembly,
+                &QueryMsg::VoterStats {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn governance_stats(&self) -> GovernanceStatsResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.assembly, &QueryMsg::GovernanceStats {})
+            .unwrap()
+    }
+
+    pub fn adaptive_quorum(&self) -> AdaptiveQuorumResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.assembly, &QueryMsg::AdaptiveQuorum {})
+            .unwrap()
+    }
+
     pub fn proposal_voters(&self, proposal_id: u64) -> Vec<ProposalVoterResponse> {
         self.app
             .wrap()
@@ -511,6 +703,104 @@ impl Helper {
         )
     }
 
+    pub fn cast_votes(
+        &mut self,
+        sender: &Addr,
+        votes: Vec<(u64, ProposalVoteOption)>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.assembly.clone(),
+            &ExecuteMsg::CastVotes { votes },
+            &[],
+        )
+    }
+
+    pub fn commit_vote(
+        &mut self,
+        proposal_id: u64,
+        sender: &Addr,
+        commitment: Binary,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.assembly.clone(),
+            &ExecuteMsg::CommitVote {
+                proposal_id,
+                commitment,
+            },
+            &[],
+        )
+    }
+
+    pub fn reveal_vote(
+        &mut self,
+        proposal_id: u64,
+        sender: &Addr,
+        option: ProposalVoteOption,
+        salt: &str,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.assembly.clone(),
+            &ExecuteMsg::RevealVote {
+                proposal_id,
+                vote: option,
+                salt: salt.to_string(),
+            },
+            &[],
+        )
+    }
+
+    pub fn voting_power_breakdown(
+        &self,
+        proposal_id: u64,
+        voter: &Addr,
+    ) -> Option<VotingPowerBreakdown> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.assembly,
+                &QueryMsg::VotingPowerBreakdown {
+                    proposal_id,
+                    voter: voter.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn voting_power_breakdowns(
+        &self,
+        proposal_id: u64,
+        start_after: Option<&Addr>,
+        limit: Option<u32>,
+    ) -> Vec<(String, VotingPowerBreakdown)> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.assembly,
+                &QueryMsg::VotingPowerBreakdowns {
+                    proposal_id,
+                    start_after: start_after.map(Addr::to_string),
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn vote_commitment(&self, proposal_id: u64, user: &Addr) -> Option<VoteCommitment> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.assembly,
+                &QueryMsg::VoteCommitment {
+                    proposal_id,
+                    user: user.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
     pub fn mint_coin(&mut self, to: &Addr, coin: Coin) {
         // .init_balance() erases previous balance thus I use such hack and create intermediate "denom admin"
         let denom_admin = Addr::unchecked(format!("{}_admin", &coin.denom));
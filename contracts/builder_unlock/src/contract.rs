@@ -4,20 +4,30 @@ use astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_ow
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, coins, ensure, BankMsg, DepsMut, Env, MessageInfo, Response, StdError, Uint128,
+    attr, coins, ensure, BankMsg, Binary, Decimal, DepsMut, Env, MessageInfo, Response, StdError,
+    Uint128,
 };
 use cw2::set_contract_version;
 use cw_utils::{may_pay, must_pay};
 
-use astroport_governance::builder_unlock::{Config, CreateAllocationParams, Schedule};
+use astroport_governance::assembly;
+use astroport_governance::builder_unlock::{
+    Config, CreateAllocationParams, EpochRebate, FallbackReceiver, MerkleDrop, Schedule,
+    ScheduleTemplate, FALLBACK_RECEIVER_TIMELOCK, MIN_FALLBACK_INACTIVITY_PERIOD,
+};
 use astroport_governance::builder_unlock::{ExecuteMsg, InstantiateMsg};
 
 use crate::error::ContractError;
-use crate::state::{Allocation, CONFIG, OWNERSHIP_PROPOSAL, PARAMS, STATE};
+use crate::merkle::{leaf_hash, verify_proof};
+use crate::state::{
+    claim_rebates, get_epoch_start, is_claimed, set_claimed, Allocation, CONFIG, EPOCH_REBATES,
+    FALLBACK_RECEIVERS, MERKLE_DROPS, MERKLE_DROP_COUNT, OWNERSHIP_PROPOSAL, PARAMS,
+    REBATE_PARTICIPANTS, REBATE_POOL, SCHEDULE_TEMPLATES, SCHEDULE_TEMPLATE_COUNT, STATE,
+};
 
 // Version and name used for contract migration.
-const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Creates a new contract with the specified parameters in the `msg` variable.
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -35,6 +45,9 @@ pub fn instantiate(
             owner: deps.api.addr_validate(&msg.owner)?,
             astro_denom: msg.astro_denom,
             max_allocations_amount: msg.max_allocations_amount,
+            advance_ltv: Decimal::zero(),
+            assembly: None,
+            rebate_per_epoch: Uint128::zero(),
         },
     )?;
 
@@ -73,6 +86,28 @@ pub fn instantiate(
 /// * **ExecuteMsg::ClaimOwnership** Claims contract ownership.
 ///
 /// * **ExecuteMsg::UpdateConfig** Update contract configuration.
+///
+/// * **ExecuteMsg::RegisterMerkleDrop** Register a merkle drop of allocations.
+///
+/// * **ExecuteMsg::ClaimMerkleAllocation** Create an allocation from a merkle drop.
+///
+/// * **ExecuteMsg::ExpireMerkleDrop** Return unclaimed ASTRO of an expired merkle drop.
+///
+/// * **ExecuteMsg::SetFallbackReceiver** Set a fallback receiver for the sender's allocation.
+///
+/// * **ExecuteMsg::RemoveFallbackReceiver** Remove the fallback receiver of the sender's allocation.
+///
+/// * **ExecuteMsg::ClaimFallback** Claim the allocation of an inactive account as its fallback receiver.
+///
+/// * **ExecuteMsg::TakeAdvance** Advance ASTRO which unlocks during the next 30 days.
+///
+/// * **ExecuteMsg::FundRebatePool** Top up the voting rebate pool.
+///
+/// * **ExecuteMsg::RecordProposalVote** Record the sender's vote in an Assembly proposal for the voting rebate.
+///
+/// * **ExecuteMsg::CreateScheduleTemplate** Create a named unlock schedule template.
+///
+/// * **ExecuteMsg::UpdateScheduleTemplate** Update an unlock schedule template.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -142,10 +177,50 @@ pub fn execute(
         }
         ExecuteMsg::UpdateConfig {
             new_max_allocations_amount,
-        } => update_config(deps, info, new_max_allocations_amount),
+            new_advance_ltv,
+            new_assembly,
+            new_rebate_per_epoch,
+        } => update_config(
+            deps,
+            info,
+            new_max_allocations_amount,
+            new_advance_ltv,
+            new_assembly,
+            new_rebate_per_epoch,
+        ),
         ExecuteMsg::UpdateUnlockSchedules {
             new_unlock_schedules,
         } => update_unlock_schedules(deps, env, info, new_unlock_schedules),
+        ExecuteMsg::RegisterMerkleDrop {
+            merkle_root,
+            expiration,
+        } => execute_register_merkle_drop(deps, env, info, merkle_root, expiration),
+        ExecuteMsg::ClaimMerkleAllocation {
+            drop_id,
+            index,
+            params,
+            proof,
+        } => execute_claim_merkle_allocation(deps, env, info, drop_id, index, params, proof),
+        ExecuteMsg::ExpireMerkleDrop { drop_id } => execute_expire_merkle_drop(deps, env, drop_id),
+        ExecuteMsg::SetFallbackReceiver {
+            receiver,
+            inactivity_period,
+        } => execute_set_fallback_receiver(deps, env, info, receiver, inactivity_period),
+        ExecuteMsg::RemoveFallbackReceiver {} => execute_remove_fallback_receiver(deps, env, info),
+        ExecuteMsg::ClaimFallback { account } => execute_claim_fallback(deps, env, info, account),
+        ExecuteMsg::TakeAdvance { amount } => execute_take_advance(deps, env, info, amount),
+        ExecuteMsg::FundRebatePool {} => execute_fund_rebate_pool(deps, info),
+        ExecuteMsg::RecordProposalVote { proposal_id } => {
+            execute_record_proposal_vote(deps, env, info, proposal_id)
+        }
+        ExecuteMsg::CreateScheduleTemplate { name, schedule } => {
+            execute_create_schedule_template(deps, info, name, schedule)
+        }
+        ExecuteMsg::UpdateScheduleTemplate {
+            template_id,
+            new_name,
+            new_schedule,
+        } => execute_update_schedule_template(deps, info, template_id, new_name, new_schedule),
     }
 }
 
@@ -204,7 +279,7 @@ pub fn execute_create_allocations(
     Ok(Response::default())
 }
 
-/// Allow allocation recipients to withdraw unlocked ASTRO.
+/// Allow allocation recipients to withdraw unlocked ASTRO along with voting rebates of finished epochs.
 pub fn execute_withdraw(
     deps: DepsMut,
     env: Env,
@@ -213,9 +288,20 @@ pub fn execute_withdraw(
     let block_ts = env.block.time.seconds();
     let mut allocation = Allocation::must_load(deps.storage, block_ts, &info.sender)?;
 
-    let astro_to_withdraw = allocation.withdraw_and_update()?;
+    let rebates = claim_rebates(deps.storage, &info.sender, block_ts)?;
+    let astro_to_withdraw = match allocation.withdraw_and_update() {
+        // Rebates can be claimed even if nothing has unlocked since the last withdrawal
+        Err(ContractError::NoUnlockedAstro {}) if !rebates.is_zero() => Uint128::zero(),
+        result => result?,
+    };
     allocation.save(deps.storage)?;
 
+    // Any withdrawal restarts the inactivity period of the fallback receiver
+    if let Some(mut fallback) = FALLBACK_RECEIVERS.may_load(deps.storage, &info.sender)? {
+        fallback.last_activity = block_ts;
+        FALLBACK_RECEIVERS.save(deps.storage, &info.sender, &fallback)?;
+    }
+
     let mut state = STATE.load(deps.storage)?;
     state.remaining_astro_tokens -= astro_to_withdraw;
 
@@ -224,14 +310,19 @@ pub fn execute_withdraw(
     let bank_msg = BankMsg::Send {
         to_address: info.sender.to_string(),
         amount: coins(
-            astro_to_withdraw.u128(),
+            (astro_to_withdraw + rebates).u128(),
             CONFIG.load(deps.storage)?.astro_denom,
         ),
     };
 
-    Ok(Response::new()
+    let mut response = Response::new()
         .add_message(bank_msg)
-        .add_attribute("astro_withdrawn", astro_to_withdraw))
+        .add_attribute("astro_withdrawn", astro_to_withdraw);
+    if !rebates.is_zero() {
+        response = response.add_attribute("rebates_claimed", rebates);
+    }
+
+    Ok(response)
 }
 
 /// Allows the current allocation receiver to propose a new receiver.
@@ -293,6 +384,7 @@ pub fn execute_claim_receiver(
 
         let new_allocation = allocation.claim_allocation(deps.storage, &info.sender)?;
         new_allocation.save(deps.storage)?;
+        FALLBACK_RECEIVERS.remove(deps.storage, &prev_receiver_addr);
     } else {
         return Err(ContractError::ProposedReceiverMismatch {});
     }
@@ -440,6 +532,9 @@ pub fn update_config(
     deps: DepsMut,
     info: MessageInfo,
     new_max_allocations_amount: Uint128,
+    new_advance_ltv: Option<Decimal>,
+    new_assembly: Option<String>,
+    new_rebate_per_epoch: Option<Uint128>,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
 
@@ -455,11 +550,35 @@ pub fn update_config(
     }
 
     config.max_allocations_amount = new_max_allocations_amount;
+
+    let mut attrs = vec![
+        attr("action", "update_config"),
+        attr("new_max_allocations_amount", new_max_allocations_amount),
+    ];
+
+    if let Some(new_advance_ltv) = new_advance_ltv {
+        ensure!(
+            new_advance_ltv <= Decimal::one(),
+            StdError::generic_err("Advance LTV must not exceed 1")
+        );
+
+        config.advance_ltv = new_advance_ltv;
+        attrs.push(attr("new_advance_ltv", new_advance_ltv.to_string()));
+    }
+
+    if let Some(new_assembly) = new_assembly {
+        config.assembly = Some(deps.api.addr_validate(&new_assembly)?);
+        attrs.push(attr("new_assembly", new_assembly));
+    }
+
+    if let Some(new_rebate_per_epoch) = new_rebate_per_epoch {
+        config.rebate_per_epoch = new_rebate_per_epoch;
+        attrs.push(attr("new_rebate_per_epoch", new_rebate_per_epoch));
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
-    Ok(Response::new()
-        .add_attribute("action", "update_config")
-        .add_attribute("new_max_allocations_amount", new_max_allocations_amount))
+    Ok(Response::new().add_attributes(attrs))
 }
 
 /// Updates builder unlock schedules for specified accounts.
@@ -484,3 +603,515 @@ pub fn update_unlock_schedules(
 
     Ok(Response::new().add_attribute("action", "update_unlock_schedules"))
 }
+
+/// Registers a merkle drop of allocations. The ASTRO sent along with the call is reserved
+/// for the drop and becomes allocated as recipients claim their allocations.
+///
+/// * **merkle_root** root of the merkle tree with allocations.
+///
+/// * **expiration** timestamp after which allocations can't be claimed anymore.
+pub fn execute_register_merkle_drop(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    merkle_root: Binary,
+    expiration: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    ensure!(info.sender == config.owner, ContractError::Unauthorized {});
+    ensure!(merkle_root.len() == 32, ContractError::InvalidMerkleRoot {});
+
+    let block_ts = env.block.time.seconds();
+    ensure!(
+        expiration > block_ts,
+        ContractError::InvalidMerkleDropExpiration {}
+    );
+
+    let deposit_amount = must_pay(&info, &config.astro_denom)?;
+
+    let mut state = STATE.load(deps.storage)?;
+    state.total_astro_deposited += deposit_amount;
+
+    ensure!(
+        state.total_astro_deposited <= config.max_allocations_amount,
+        ContractError::TotalAllocationExceedsAmount(config.max_allocations_amount)
+    );
+
+    STATE.save(deps.storage, &state, block_ts)?;
+
+    let drop_id = MERKLE_DROP_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    MERKLE_DROP_COUNT.save(deps.storage, &drop_id)?;
+    MERKLE_DROPS.save(
+        deps.storage,
+        drop_id,
+        &MerkleDrop {
+            merkle_root: merkle_root.clone(),
+            total_amount: deposit_amount,
+            claimed_amount: Uint128::zero(),
+            expiration,
+            expired: false,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "register_merkle_drop"),
+        attr("drop_id", drop_id.to_string()),
+        attr("merkle_root", merkle_root.to_base64()),
+        attr("total_amount", deposit_amount),
+        attr("expiration", expiration.to_string()),
+    ]))
+}
+
+/// Creates an allocation for the message sender from a merkle drop.
+///
+/// * **drop_id** merkle drop id.
+///
+/// * **index** leaf index in the merkle tree.
+///
+/// * **params** allocation parameters.
+///
+/// * **proof** merkle proof of the leaf.
+pub fn execute_claim_merkle_allocation(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    drop_id: u64,
+    index: u64,
+    params: CreateAllocationParams,
+    proof: Vec<Binary>,
+) -> Result<Response, ContractError> {
+    let mut merkle_drop = MERKLE_DROPS
+        .may_load(deps.storage, drop_id)?
+        .ok_or(ContractError::MerkleDropNotFound { drop_id })?;
+
+    let block_ts = env.block.time.seconds();
+    ensure!(
+        !merkle_drop.expired && block_ts <= merkle_drop.expiration,
+        ContractError::MerkleDropExpired { drop_id }
+    );
+    ensure!(
+        !is_claimed(deps.storage, drop_id, index)?,
+        ContractError::MerkleAllocationClaimed { drop_id, index }
+    );
+
+    let leaf = leaf_hash(index, &info.sender, &params)?;
+    ensure!(
+        verify_proof(&merkle_drop.merkle_root, leaf, &proof),
+        ContractError::InvalidMerkleProof {}
+    );
+
+    merkle_drop.claimed_amount += params.amount;
+    ensure!(
+        merkle_drop.claimed_amount <= merkle_drop.total_amount,
+        ContractError::MerkleDropExhausted { drop_id }
+    );
+
+    let amount = params.amount;
+    let allocation = Allocation::new_allocation(deps.storage, block_ts, &info.sender, params)?;
+    allocation.save(deps.storage)?;
+
+    set_claimed(deps.storage, drop_id, index)?;
+    MERKLE_DROPS.save(deps.storage, drop_id, &merkle_drop)?;
+
+    let mut state = STATE.load(deps.storage)?;
+    state.remaining_astro_tokens += amount;
+    STATE.save(deps.storage, &state, block_ts)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "claim_merkle_allocation"),
+        attr("drop_id", drop_id.to_string()),
+        attr("index", index.to_string()),
+        attr("receiver", info.sender),
+        attr("amount", amount),
+    ]))
+}
+
+/// Returns unclaimed ASTRO of an expired merkle drop back to unallocated tokens.
+/// Anyone can call this endpoint once the drop has expired.
+///
+/// * **drop_id** merkle drop id.
+pub fn execute_expire_merkle_drop(
+    deps: DepsMut,
+    env: Env,
+    drop_id: u64,
+) -> Result<Response, ContractError> {
+    let mut merkle_drop = MERKLE_DROPS
+        .may_load(deps.storage, drop_id)?
+        .ok_or(ContractError::MerkleDropNotFound { drop_id })?;
+
+    ensure!(
+        !merkle_drop.expired,
+        ContractError::MerkleDropExpired { drop_id }
+    );
+
+    let block_ts = env.block.time.seconds();
+    ensure!(
+        block_ts > merkle_drop.expiration,
+        ContractError::MerkleDropNotExpired {
+            drop_id,
+            expiration: merkle_drop.expiration
+        }
+    );
+
+    let unclaimed = merkle_drop
+        .total_amount
+        .checked_sub(merkle_drop.claimed_amount)?;
+    merkle_drop.expired = true;
+    MERKLE_DROPS.save(deps.storage, drop_id, &merkle_drop)?;
+
+    let mut state = STATE.load(deps.storage)?;
+    state.unallocated_astro_tokens += unclaimed;
+    STATE.save(deps.storage, &state, block_ts)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "expire_merkle_drop"),
+        attr("drop_id", drop_id.to_string()),
+        attr("unclaimed_amount", unclaimed),
+    ]))
+}
+
+/// Sets a fallback receiver for the sender's allocation. Replaces the previous fallback receiver if any.
+/// The fallback receiver becomes effective after [`FALLBACK_RECEIVER_TIMELOCK`].
+///
+/// * **receiver** address which can claim the allocation once the sender becomes inactive.
+///
+/// * **inactivity_period** period without withdrawals (in seconds) after which the allocation can be claimed.
+pub fn execute_set_fallback_receiver(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receiver: String,
+    inactivity_period: u64,
+) -> Result<Response, ContractError> {
+    let block_ts = env.block.time.seconds();
+    // Ensure the sender has an allocation
+    Allocation::must_load(deps.storage, block_ts, &info.sender)?;
+
+    let receiver = deps.api.addr_validate(&receiver)?;
+    ensure!(
+        receiver != info.sender,
+        ContractError::InvalidFallbackReceiver {}
+    );
+    ensure!(
+        inactivity_period >= MIN_FALLBACK_INACTIVITY_PERIOD,
+        ContractError::InvalidInactivityPeriod {
+            min: MIN_FALLBACK_INACTIVITY_PERIOD
+        }
+    );
+
+    let fallback = FallbackReceiver {
+        receiver,
+        inactivity_period,
+        effective_from: block_ts + FALLBACK_RECEIVER_TIMELOCK,
+        last_activity: block_ts,
+    };
+    FALLBACK_RECEIVERS.save(deps.storage, &info.sender, &fallback)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_fallback_receiver"),
+        attr("account", info.sender),
+        attr("fallback_receiver", fallback.receiver),
+        attr("inactivity_period", inactivity_period.to_string()),
+        attr("effective_from", fallback.effective_from.to_string()),
+    ]))
+}
+
+/// Removes the fallback receiver of the sender's allocation.
+pub fn execute_remove_fallback_receiver(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    Allocation::must_load(deps.storage, env.block.time.seconds(), &info.sender)?;
+
+    let fallback = FALLBACK_RECEIVERS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::FallbackReceiverNotSet {})?;
+    FALLBACK_RECEIVERS.remove(deps.storage, &info.sender);
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "remove_fallback_receiver"),
+        attr("account", info.sender),
+        attr("fallback_receiver", fallback.receiver),
+    ]))
+}
+
+/// Allows a fallback receiver to claim the allocation of an account which didn't withdraw
+/// during the configured inactivity period.
+///
+/// * **account** the inactive allocation holder.
+pub fn execute_claim_fallback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    account: String,
+) -> Result<Response, ContractError> {
+    let account = deps.api.addr_validate(&account)?;
+    let block_ts = env.block.time.seconds();
+    let allocation = Allocation::must_load(deps.storage, block_ts, &account)?;
+
+    let fallback = FALLBACK_RECEIVERS
+        .may_load(deps.storage, &account)?
+        .ok_or(ContractError::FallbackReceiverNotSet {})?;
+    ensure!(
+        fallback.receiver == info.sender,
+        ContractError::Unauthorized {}
+    );
+
+    let claimable_from = fallback.claimable_from();
+    ensure!(
+        block_ts >= claimable_from,
+        ContractError::FallbackNotClaimable { claimable_from }
+    );
+    ensure!(
+        !PARAMS.has(deps.storage, &info.sender),
+        ContractError::AllocationExists {
+            user: info.sender.to_string()
+        }
+    );
+
+    FALLBACK_RECEIVERS.remove(deps.storage, &account);
+    let new_allocation = allocation.claim_allocation(deps.storage, &info.sender)?;
+    new_allocation.save(deps.storage)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "claim_fallback"),
+        attr("prev_receiver", account),
+        attr("receiver", info.sender),
+        attr("last_activity", fallback.last_activity.to_string()),
+    ]))
+}
+
+/// Advances ASTRO which unlocks during the next 30 days to the sender.
+/// The advance is bounded by the configured LTV and is repaid automatically from future unlocks.
+///
+/// * **amount** amount of ASTRO to advance.
+pub fn execute_take_advance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let block_ts = env.block.time.seconds();
+    let mut allocation = Allocation::must_load(deps.storage, block_ts, &info.sender)?;
+
+    let advanced = allocation.take_advance(amount, config.advance_ltv)?;
+    allocation.save(deps.storage)?;
+
+    // Taking an advance restarts the inactivity period of the fallback receiver
+    if let Some(mut fallback) = FALLBACK_RECEIVERS.may_load(deps.storage, &info.sender)? {
+        fallback.last_activity = block_ts;
+        FALLBACK_RECEIVERS.save(deps.storage, &info.sender, &fallback)?;
+    }
+
+    let mut state = STATE.load(deps.storage)?;
+    state.remaining_astro_tokens = state.remaining_astro_tokens.checked_sub(amount)?;
+    STATE.save(deps.storage, &state, block_ts)?;
+
+    let bank_msg = BankMsg::Send {
+        to_address: info.sender.to_string(),
+        amount: coins(amount.u128(), config.astro_denom),
+    };
+
+    Ok(Response::new().add_message(bank_msg).add_attributes([
+        attr("action", "take_advance"),
+        attr("receiver", info.sender),
+        attr("amount", amount),
+        attr("advanced", advanced),
+    ]))
+}
+
+/// Tops up the voting rebate pool. Only the contract owner can fund the pool.
+pub fn execute_fund_rebate_pool(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(info.sender == config.owner, ContractError::Unauthorized {});
+
+    let amount = must_pay(&info, &config.astro_denom)?;
+    let rebate_pool = REBATE_POOL.may_load(deps.storage)?.unwrap_or_default() + amount;
+    REBATE_POOL.save(deps.storage, &rebate_pool)?;
+
+    Ok(Response::new().add_attributes([
+        attr("action", "fund_rebate_pool"),
+        attr("amount", amount),
+        attr("rebate_pool", rebate_pool),
+    ]))
+}
+
+/// Records the sender's vote in an Assembly proposal submitted in the current epoch.
+/// The first recorded vote of an epoch reserves the epoch rebate from the rebate pool.
+pub fn execute_record_proposal_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let assembly = config
+        .assembly
+        .filter(|_| !config.rebate_per_epoch.is_zero())
+        .ok_or(ContractError::RebatesDisabled {})?;
+
+    let block_ts = env.block.time.seconds();
+    let allocation = Allocation::must_load(deps.storage, block_ts, &info.sender)?;
+    ensure!(
+        allocation.status.amount > allocation.status.astro_withdrawn,
+        ContractError::InactiveAllocation {}
+    );
+
+    let epoch_start = get_epoch_start(block_ts);
+    let proposal = deps.querier.query_wasm_smart::<assembly::Proposal>(
+        &assembly,
+        &assembly::QueryMsg::Proposal { proposal_id },
+    )?;
+    ensure!(
+        get_epoch_start(proposal.start_time) == epoch_start,
+        ContractError::ProposalNotInCurrentEpoch { proposal_id }
+    );
+
+    let vote = deps
+        .querier
+        .query_wasm_smart::<Option<assembly::ProposalVoteOption>>(
+            &assembly,
+            &assembly::QueryMsg::UserVote {
+                proposal_id,
+                user: info.sender.to_string(),
+            },
+        )?;
+    ensure!(
+        vote.is_some(),
+        ContractError::ProposalNotVoted { proposal_id }
+    );
+
+    let key = (&info.sender, epoch_start);
+    ensure!(
+        !REBATE_PARTICIPANTS.has(deps.storage, key),
+        ContractError::RebateAlreadyRecorded { epoch_start }
+    );
+    REBATE_PARTICIPANTS.save(deps.storage, key, &())?;
+
+    let mut rebate = match EPOCH_REBATES.may_load(deps.storage, epoch_start)? {
+        Some(rebate) => rebate,
+        None => {
+            let rebate_pool = REBATE_POOL.may_load(deps.storage)?.unwrap_or_default();
+            let amount = rebate_pool.min(config.rebate_per_epoch);
+            ensure!(!amount.is_zero(), ContractError::RebatePoolEmpty {});
+            REBATE_POOL.save(deps.storage, &(rebate_pool - amount))?;
+
+            EpochRebate {
+                amount,
+                participants: 0,
+            }
+        }
+    };
+    rebate.participants += 1;
+    EPOCH_REBATES.save(deps.storage, epoch_start, &rebate)?;
+
+    Ok(Response::new().add_attributes([
+        attr("action", "record_proposal_vote"),
+        attr("account", info.sender),
+        attr("proposal_id", proposal_id.to_string()),
+        attr("epoch_start", epoch_start.to_string()),
+        attr("participants", rebate.participants.to_string()),
+    ]))
+}
+
+/// Validates an unlock schedule of a template. Milestone schedules are validated by their milestones.
+fn validate_template_schedule(schedule: &Schedule) -> Result<(), ContractError> {
+    if schedule.milestones.is_some() {
+        schedule.validate_milestones()?;
+    } else {
+        ensure!(
+            schedule.cliff < schedule.duration,
+            ContractError::InvalidSchedule {}
+        );
+    }
+
+    Ok(())
+}
+
+/// Creates a named unlock schedule template. Only the contract owner can create templates.
+///
+/// * **name** template name.
+///
+/// * **schedule** unlock schedule applied to allocations referencing the template.
+pub fn execute_create_schedule_template(
+    deps: DepsMut,
+    info: MessageInfo,
+    name: String,
+    schedule: Schedule,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(info.sender == config.owner, ContractError::Unauthorized {});
+    validate_template_schedule(&schedule)?;
+
+    let template_id = SCHEDULE_TEMPLATE_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    SCHEDULE_TEMPLATE_COUNT.save(deps.storage, &template_id)?;
+    SCHEDULE_TEMPLATES.save(
+        deps.storage,
+        template_id,
+        &ScheduleTemplate {
+            name: name.clone(),
+            schedule,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes([
+        attr("action", "create_schedule_template"),
+        attr("template_id", template_id.to_string()),
+        attr("name", name),
+    ]))
+}
+
+/// Updates an unlock schedule template. Only the contract owner can update templates.
+/// Allocations which were already created from the template keep their schedule.
+///
+/// * **template_id** schedule template id.
+///
+/// * **new_name** new template name.
+///
+/// * **new_schedule** new unlock schedule.
+pub fn execute_update_schedule_template(
+    deps: DepsMut,
+    info: MessageInfo,
+    template_id: u64,
+    new_name: Option<String>,
+    new_schedule: Option<Schedule>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(info.sender == config.owner, ContractError::Unauthorized {});
+
+    let mut template = SCHEDULE_TEMPLATES
+        .may_load(deps.storage, template_id)?
+        .ok_or(ContractError::ScheduleTemplateNotFound { template_id })?;
+
+    let mut attrs = vec![
+        attr("action", "update_schedule_template"),
+        attr("template_id", template_id.to_string()),
+    ];
+
+    if let Some(new_name) = new_name {
+        attrs.push(attr("new_name", &new_name));
+        template.name = new_name;
+    }
+
+    if let Some(new_schedule) = new_schedule {
+        validate_template_schedule(&new_schedule)?;
+        template.schedule = new_schedule;
+        attrs.push(attr("schedule_updated", "true"));
+    }
+
+    SCHEDULE_TEMPLATES.save(deps.storage, template_id, &template)?;
+
+    Ok(Response::new().add_attributes(attrs))
+}
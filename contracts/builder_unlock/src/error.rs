@@ -62,4 +62,76 @@ pub enum ContractError {
 
     #[error("{address} doesn't have allocation")]
     NoAllocation { address: String },
+
+    #[error("Merkle root must be a 32 bytes sha256 hash")]
+    InvalidMerkleRoot {},
+
+    #[error("Merkle drop expiration must be in the future")]
+    InvalidMerkleDropExpiration {},
+
+    #[error("Merkle drop {drop_id} not found")]
+    MerkleDropNotFound { drop_id: u64 },
+
+    #[error("Merkle drop {drop_id} expired")]
+    MerkleDropExpired { drop_id: u64 },
+
+    #[error("Merkle drop {drop_id} can be expired only after {expiration}")]
+    MerkleDropNotExpired { drop_id: u64, expiration: u64 },
+
+    #[error("Invalid merkle proof")]
+    InvalidMerkleProof {},
+
+    #[error("Allocation {index} of merkle drop {drop_id} already claimed")]
+    MerkleAllocationClaimed { drop_id: u64, index: u64 },
+
+    #[error("Merkle drop {drop_id} doesn't have enough ASTRO left for this allocation")]
+    MerkleDropExhausted { drop_id: u64 },
+
+    #[error("Fallback receiver not set")]
+    FallbackReceiverNotSet {},
+
+    #[error("Fallback receiver must differ from the allocation holder")]
+    InvalidFallbackReceiver {},
+
+    #[error("Inactivity period must be at least {min} seconds")]
+    InvalidInactivityPeriod { min: u64 },
+
+    #[error("Allocation can be claimed by the fallback receiver only after {claimable_from}")]
+    FallbackNotClaimable { claimable_from: u64 },
+
+    #[error("Advance amount must be greater than zero and not exceed {available}")]
+    InvalidAdvanceAmount { available: Uint128 },
+
+    #[error("Voting rebates are disabled")]
+    RebatesDisabled {},
+
+    #[error("Rebate pool is empty")]
+    RebatePoolEmpty {},
+
+    #[error("Proposal {proposal_id} was not submitted in the current epoch")]
+    ProposalNotInCurrentEpoch { proposal_id: u64 },
+
+    #[error("No vote found in proposal {proposal_id}")]
+    ProposalNotVoted { proposal_id: u64 },
+
+    #[error("Vote for epoch {epoch_start} is already recorded")]
+    RebateAlreadyRecorded { epoch_start: u64 },
+
+    #[error("Allocation is fully withdrawn")]
+    InactiveAllocation {},
+
+    #[error("Too many accounts in a single query. Max: {max}")]
+    TooManyAccounts { max: u32 },
+
+    #[error("Schedule template {template_id} not found")]
+    ScheduleTemplateNotFound { template_id: u64 },
+
+    #[error("Either an unlock schedule or a schedule template must be specified. Account: {user}")]
+    AmbiguousSchedule { user: String },
+
+    #[error("Schedule cliff must be less than the duration")]
+    InvalidSchedule {},
+
+    #[error("Failed to migrate contract")]
+    MigrationError {},
 }
@@ -1,4 +1,6 @@
 pub mod contract;
 pub mod error;
+pub mod merkle;
+pub mod migration;
 pub mod query;
 pub mod state;
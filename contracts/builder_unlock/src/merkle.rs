@@ -0,0 +1,35 @@
+use cosmwasm_std::{to_json_vec, Addr, Binary, StdResult};
+use sha2::{Digest, Sha256};
+
+use astroport_governance::builder_unlock::CreateAllocationParams;
+
+/// Computes the merkle tree leaf for an allocation.
+/// The leaf is sha256 hash of JSON serialized (index, address, params) tuple.
+pub fn leaf_hash(
+    index: u64,
+    address: &Addr,
+    params: &CreateAllocationParams,
+) -> StdResult<[u8; 32]> {
+    let leaf = to_json_vec(&(index, address, params))?;
+    Ok(Sha256::digest(leaf).into())
+}
+
+/// Hashes a pair of nodes. Nodes are sorted before hashing,
+/// thus the proof doesn't need to specify the side of each sibling.
+pub fn hash_pair(a: &[u8], b: &[u8]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    Sha256::new()
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+/// Checks that the leaf belongs to the merkle tree with the given root.
+pub fn verify_proof(root: &Binary, leaf: [u8; 32], proof: &[Binary]) -> bool {
+    let computed_root = proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_pair(&node, sibling));
+
+    computed_root.as_slice() == root.as_slice()
+}
@@ -0,0 +1,44 @@
+#![cfg(not(tarpaulin_include))]
+
+use cosmwasm_std::{DepsMut, Empty, Env, Response, Uint128};
+use cw2::{get_contract_version, set_contract_version};
+
+use crate::contract::{CONTRACT_NAME, CONTRACT_VERSION};
+use crate::error::ContractError;
+use crate::state::{CONFIG, MERKLE_DROP_COUNT, REBATE_POOL, SCHEDULE_TEMPLATE_COUNT};
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
+    let contract_version = get_contract_version(deps.storage)?;
+
+    match contract_version.contract.as_ref() {
+        CONTRACT_NAME => match contract_version.version.as_ref() {
+            "3.0.0" => Ok(()),
+            _ => Err(ContractError::MigrationError {}),
+        },
+        _ => Err(ContractError::MigrationError {}),
+    }?;
+
+    // Persist new config fields with their default values
+    let config = CONFIG.load(deps.storage)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    // Initialize counters and the voting rebate pool
+    if MERKLE_DROP_COUNT.may_load(deps.storage)?.is_none() {
+        MERKLE_DROP_COUNT.save(deps.storage, &0)?;
+    }
+    if SCHEDULE_TEMPLATE_COUNT.may_load(deps.storage)?.is_none() {
+        SCHEDULE_TEMPLATE_COUNT.save(deps.storage, &0)?;
+    }
+    if REBATE_POOL.may_load(deps.storage)?.is_none() {
+        REBATE_POOL.save(deps.storage, &Uint128::zero())?;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
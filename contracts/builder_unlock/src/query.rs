@@ -1,15 +1,21 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{to_json_binary, Addr, Binary, Deps, Env, Order, StdError, StdResult, Uint128};
+use cosmwasm_std::{
+    ensure, to_json_binary, Addr, Binary, Deps, Env, Order, StdError, StdResult, Uint128,
+};
 use cw_storage_plus::Bound;
 
 use astroport_governance::builder_unlock::{
-    AllocationParams, AllocationResponse, QueryMsg, SimulateWithdrawResponse, State,
+    AdvanceResponse, AllocationParams, AllocationResponse, AllocationStatusFilter,
+    FallbackReceiver, QueryMsg, ScheduleTemplate, SimulateWithdrawResponse, State,
 };
 use astroport_governance::{DEFAULT_LIMIT, MAX_LIMIT};
 
 use crate::error::ContractError;
-use crate::state::{Allocation, CONFIG, PARAMS, STATE, STATUS};
+use crate::state::{
+    compute_rebates, is_claimed, Allocation, CONFIG, EPOCH_REBATES, FALLBACK_RECEIVERS,
+    MERKLE_DROPS, PARAMS, SCHEDULE_TEMPLATES, STATE, STATUS,
+};
 
 /// Expose available contract queries.
 ///
@@ -23,6 +29,32 @@ use crate::state::{Allocation, CONFIG, PARAMS, STATE, STATUS};
 /// * **QueryMsg::UnlockedTokens {}** Return the amount of unlocked ASTRO for a specific account.
 ///
 /// * **QueryMsg::SimulateWithdraw {}** Return the result of a withdrawal simulation.
+///
+/// * **QueryMsg::SimulateWithdrawBatch {}** Return results of withdrawal simulations for several accounts.
+///
+/// * **QueryMsg::AllocationsByStatus {}** Return allocations which are currently in a specific status.
+///
+/// * **QueryMsg::VotingPowerAt {}** Return the amount of ASTRO which hasn't been withdrawn from an allocation yet.
+///
+/// * **QueryMsg::MerkleDrop {}** Return the merkle drop info.
+///
+/// * **QueryMsg::IsClaimed {}** Return whether the merkle drop leaf has been already claimed.
+///
+/// * **QueryMsg::FallbackReceiver {}** Return the fallback receiver configured for an allocation.
+///
+/// * **QueryMsg::FallbackReceivers {}** Return all configured fallback receivers.
+///
+/// * **QueryMsg::Advance {}** Return the outstanding advance of an allocation.
+///
+/// * **QueryMsg::EpochRebate {}** Return the voting rebate of an epoch.
+///
+/// * **QueryMsg::Rebates {}** Return voting rebates of an allocation holder.
+///
+/// * **QueryMsg::ScheduleTemplate {}** Return a schedule template.
+///
+/// * **QueryMsg::ScheduleTemplates {}** Return all schedule templates.
+///
+/// * **QueryMsg::TemplateAllocations {}** Return accounts whose unlock schedule follows a schedule template.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -39,9 +71,73 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             &query_simulate_withdraw(deps, env, account, timestamp)
                 .map_err(|err| StdError::generic_err(err.to_string()))?,
         ),
+        QueryMsg::SimulateWithdrawBatch {
+            accounts,
+            timestamp,
+        } => to_json_binary(
+            &query_simulate_withdraw_batch(deps, env, accounts, timestamp)
+                .map_err(|err| StdError::generic_err(err.to_string()))?,
+        ),
+        QueryMsg::AllocationsByStatus {
+            status,
+            start_after,
+            limit,
+        } => to_json_binary(&query_allocations_by_status(
+            deps,
+            status,
+            start_after,
+            limit,
+        )?),
         QueryMsg::Allocations { start_after, limit } => {
             to_json_binary(&query_allocations(deps, start_after, limit)?)
         }
+        QueryMsg::VotingPowerAt { account, timestamp } => {
+            to_json_binary(&query_voting_power_at(deps, account, timestamp)?)
+        }
+        QueryMsg::MerkleDrop { drop_id } => {
+            to_json_binary(&MERKLE_DROPS.load(deps.storage, drop_id)?)
+        }
+        QueryMsg::IsClaimed { drop_id, index } => {
+            to_json_binary(&is_claimed(deps.storage, drop_id, index)?)
+        }
+        QueryMsg::FallbackReceiver { account } => {
+            let account = deps.api.addr_validate(&account)?;
+            to_json_binary(&FALLBACK_RECEIVERS.may_load(deps.storage, &account)?)
+        }
+        QueryMsg::FallbackReceivers { start_after, limit } => {
+            to_json_binary(&query_fallback_receivers(deps, start_after, limit)?)
+        }
+        QueryMsg::Advance { account } => to_json_binary(
+            &query_advance(deps, env, account)
+                .map_err(|err| StdError::generic_err(err.to_string()))?,
+        ),
+        QueryMsg::EpochRebate { epoch_start } => {
+            to_json_binary(&EPOCH_REBATES.may_load(deps.storage, epoch_start)?)
+        }
+        QueryMsg::Rebates { account } => {
+            let account = deps.api.addr_validate(&account)?;
+            to_json_binary(&compute_rebates(
+                deps.storage,
+                &account,
+                env.block.time.seconds(),
+            )?)
+        }
+        QueryMsg::ScheduleTemplate { template_id } => {
+            to_json_binary(&SCHEDULE_TEMPLATES.load(deps.storage, template_id)?)
+        }
+        QueryMsg::ScheduleTemplates { start_after, limit } => {
+            to_json_binary(&query_schedule_templates(deps, start_after, limit)?)
+        }
+        QueryMsg::TemplateAllocations {
+            template_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query_template_allocations(
+            deps,
+            template_id,
+            start_after,
+            limit,
+        )?),
     }
 }
 
@@ -87,6 +183,25 @@ pub fn query_allocation(
     Ok(AllocationResponse { params, status })
 }
 
+/// Return the amount of ASTRO which hasn't been withdrawn from the contract by a specific account yet.
+/// Unlocked but not yet withdrawn tokens count as well. Outstanding advances are excluded.
+///
+/// * **account** account whose voting power we query.
+///
+/// * **timestamp** timestamp at which we query the voting power. Optional.
+pub fn query_voting_power_at(
+    deps: Deps,
+    account: String,
+    timestamp: Option<u64>,
+) -> StdResult<Uint128> {
+    let AllocationResponse { status, .. } = query_allocation(deps, account, timestamp)?;
+
+    Ok(status
+        .amount
+        .saturating_sub(status.astro_withdrawn)
+        .saturating_sub(status.advanced))
+}
+
 /// Return information about a specific allocation.
 ///
 /// * **start_after** account from which to start querying.
@@ -145,3 +260,169 @@ pub fn query_simulate_withdraw(
 
     Ok(allocation.compute_withdraw_amount(timestamp))
 }
+
+/// Simulate token withdrawals for several accounts.
+///
+/// * **accounts** accounts for which we simulate withdrawals.
+///
+/// * **timestamp** timestamp where we assume the accounts would withdraw.
+pub fn query_simulate_withdraw_batch(
+    deps: Deps,
+    env: Env,
+    accounts: Vec<String>,
+    timestamp: Option<u64>,
+) -> Result<Vec<(String, SimulateWithdrawResponse)>, ContractError> {
+    ensure!(
+        accounts.len() <= MAX_LIMIT as usize,
+        ContractError::TooManyAccounts { max: MAX_LIMIT }
+    );
+
+    accounts
+        .into_iter()
+        .map(|account| {
+            let response = query_simulate_withdraw(deps, env.clone(), account.clone(), timestamp)?;
+            Ok((account, response))
+        })
+        .collect()
+}
+
+/// Return allocations which are currently in a specific status.
+///
+/// * **status** status allocations must be in.
+///
+/// * **start_after** account from which to start querying.
+///
+/// * **limit** max amount of entries to return.
+pub fn query_allocations_by_status(
+    deps: Deps,
+    status: AllocationStatusFilter,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, AllocationResponse)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let default_start;
+
+    let start = if let Some(start_after) = start_after {
+        default_start = deps.api.addr_validate(&start_after)?;
+        Some(Bound::exclusive(&default_start))
+    } else {
+        None
+    };
+
+    PARAMS
+        .range(deps.storage, start, None, Order::Ascending)
+        .map(|item| {
+            let (account, params) = item?;
+            let status = STATUS.may_load(deps.storage, &account)?.unwrap_or_default();
+            Ok((account, AllocationResponse { params, status }))
+        })
+        .filter(|item| match item {
+            Ok((_, allocation)) => status.matches(allocation),
+            Err(_) => true,
+        })
+        .take(limit)
+        .collect()
+}
+
+/// Return configured fallback receivers.
+///
+/// * **start_after** account from which to start querying.
+///
+/// * **limit** max amount of entries to return.
+pub fn query_fallback_receivers(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, FallbackReceiver)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+
+    FALLBACK_RECEIVERS
+        .range(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect()
+}
+
+/// Return the outstanding advance of an allocation and how much ASTRO can still be advanced.
+///
+/// * **account** account whose advance we query.
+pub fn query_advance(
+    deps: Deps,
+    env: Env,
+    account: String,
+) -> Result<AdvanceResponse, ContractError> {
+    let receiver = deps.api.addr_validate(&account)?;
+    let block_ts = env.block.time.seconds();
+    let allocation = Allocation::must_load(deps.storage, block_ts, &receiver)?;
+    let advance_ltv = CONFIG.load(deps.storage)?.advance_ltv;
+
+    Ok(AdvanceResponse {
+        outstanding: allocation.compute_outstanding_advance(block_ts),
+        available: allocation.compute_advance_limit(block_ts, advance_ltv),
+    })
+}
+
+/// Return schedule templates.
+///
+/// * **start_after** template id from which to start querying.
+///
+/// * **limit** max amount of entries to return.
+pub fn query_schedule_templates(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, ScheduleTemplate)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    SCHEDULE_TEMPLATES
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect()
+}
+
+/// Return accounts whose unlock schedule follows a schedule template.
+///
+/// * **template_id** schedule template id.
+///
+/// * **start_after** account from which to start querying.
+///
+/// * **limit** max amount of entries to return.
+pub fn query_template_allocations(
+    deps: Deps,
+    template_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Addr>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+
+    PARAMS
+        .range(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .filter_map(|item| match item {
+            Ok((account, params)) => {
+                (params.schedule_template == Some(template_id)).then_some(Ok(account))
+            }
+            Err(err) => Some(Err(err)),
+        })
+        .take(limit)
+        .collect()
+}
@@ -1,12 +1,14 @@
 use astroport::common::OwnershipProposal;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{ensure, Addr, StdResult, Storage, Uint128};
+use cosmwasm_std::{ensure, Addr, Decimal, Order, StdResult, Storage, Uint128};
 use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
 
 use astroport_governance::builder_unlock::{
-    AllocationParams, AllocationStatus, Config, CreateAllocationParams, Schedule,
-    SimulateWithdrawResponse, State,
+    AllocationParams, AllocationStatus, Config, CreateAllocationParams, EpochRebate,
+    FallbackReceiver, MerkleDrop, RebatesResponse, Schedule, ScheduleTemplate,
+    SimulateWithdrawResponse, State, UnlockMilestone, ADVANCE_WINDOW,
 };
+use astroport_governance::emissions_controller::consts::{EPOCHS_START, EPOCH_LENGTH};
 
 use crate::error::ContractError;
 
@@ -30,6 +32,128 @@ pub const STATUS: SnapshotMap<&Addr, AllocationStatus> = SnapshotMap::new(
 );
 /// Contains a proposal to change contract ownership
 pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
+/// Number of registered merkle drops. Used to generate merkle drop ids
+pub const MERKLE_DROP_COUNT: Item<u64> = Item::new("merkle_drop_count");
+/// Registered merkle drops by their id
+pub const MERKLE_DROPS: Map<u64, MerkleDrop> = Map::new("merkle_drops");
+/// Claimed leaves bitmap. (drop id, word index) -> 64 bits word
+pub const MERKLE_CLAIMED: Map<(u64, u64), u64> = Map::new("merkle_claimed");
+/// Fallback receivers for each allocation holder which configured one
+pub const FALLBACK_RECEIVERS: Map<&Addr, FallbackReceiver> = Map::new("fallback_receivers");
+/// ASTRO available for voting rebates which is not reserved for any epoch yet
+pub const REBATE_POOL: Item<Uint128> = Item::new("rebate_pool");
+/// Voting rebates by epoch start
+pub const EPOCH_REBATES: Map<u64, EpochRebate> = Map::new("epoch_rebates");
+/// Participation snapshots. (allocation holder, epoch start) -> (). Removed once the rebate is paid out
+pub const REBATE_PARTICIPANTS: Map<(&Addr, u64), ()> = Map::new("rebate_participants");
+/// Number of created schedule templates. Used to generate schedule template ids
+pub const SCHEDULE_TEMPLATE_COUNT: Item<u64> = Item::new("schedule_template_count");
+/// Schedule templates by their id
+pub const SCHEDULE_TEMPLATES: Map<u64, ScheduleTemplate> = Map::new("schedule_templates");
+
+/// Normalize the timestamp to the beginning of its emissions epoch.
+pub fn get_epoch_start(timestamp: u64) -> u64 {
+    let rem = timestamp % EPOCHS_START;
+    if rem % EPOCH_LENGTH == 0 {
+        // Hit at the beginning of the current epoch
+        timestamp
+    } else {
+        // Hit somewhere in the middle
+        EPOCHS_START + rem / EPOCH_LENGTH * EPOCH_LENGTH
+    }
+}
+
+/// Returns voting rebates of the account.
+/// Rebates of an epoch become claimable once the epoch is over.
+pub fn compute_rebates(
+    storage: &dyn Storage,
+    account: &Addr,
+    block_ts: u64,
+) -> StdResult<RebatesResponse> {
+    let epochs = REBATE_PARTICIPANTS
+        .prefix(account)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut claimable = Uint128::zero();
+    for epoch_start in &epochs {
+        if epoch_start + EPOCH_LENGTH <= block_ts {
+            let rebate = EPOCH_REBATES.load(storage, *epoch_start)?;
+            claimable += rebate.amount.multiply_ratio(1u8, rebate.participants);
+        }
+    }
+
+    Ok(RebatesResponse { claimable, epochs })
+}
+
+/// Removes participation snapshots of finished epochs and returns the claimable rebates.
+pub fn claim_rebates(
+    storage: &mut dyn Storage,
+    account: &Addr,
+    block_ts: u64,
+) -> StdResult<Uint128> {
+    let RebatesResponse { claimable, epochs } = compute_rebates(storage, account, block_ts)?;
+    for epoch_start in epochs {
+        if epoch_start + EPOCH_LENGTH <= block_ts {
+            REBATE_PARTICIPANTS.remove(storage, (account, epoch_start));
+        }
+    }
+
+    Ok(claimable)
+}
+
+/// Returns the storage key and the bit mask of the leaf in the claimed bitmap
+fn claimed_bit(drop_id: u64, index: u64) -> ((u64, u64), u64) {
+    ((drop_id, index / 64), 1 << (index % 64))
+}
+
+pub fn is_claimed(storage: &dyn Storage, drop_id: u64, index: u64) -> StdResult<bool> {
+    let (key, mask) = claimed_bit(drop_id, index);
+    let word = MERKLE_CLAIMED.may_load(storage, key)?.unwrap_or_default();
+    Ok(word & mask != 0)
+}
+
+pub fn set_claimed(storage: &mut dyn Storage, drop_id: u64, index: u64) -> StdResult<()> {
+    let (key, mask) = claimed_bit(drop_id, index);
+    MERKLE_CLAIMED
+        .update(storage, key, |word| -> StdResult<_> {
+            Ok(word.unwrap_or_default() | mask)
+        })
+        .map(|_| ())
+}
+
+/// Computes number of tokens unlocked by milestones at a given timestamp.
+/// Tokens unlock linearly between consecutive milestones.
+fn compute_milestone_unlocked_amount(
+    start_time: u64,
+    milestones: &[UnlockMilestone],
+    timestamp: u64,
+    total_amount: Uint128,
+) -> Uint128 {
+    if timestamp < start_time {
+        return Uint128::zero();
+    }
+
+    let elapsed = timestamp - start_time;
+    // The last reached milestone
+    match milestones.iter().rposition(|m| m.offset <= elapsed) {
+        Some(ind) => {
+            let reached = &milestones[ind];
+            let unlocked_at_milestone = total_amount * reached.percent;
+
+            match milestones.get(ind + 1) {
+                Some(next) => {
+                    let unlocked_at_next = total_amount * next.percent;
+                    unlocked_at_milestone
+                        + (unlocked_at_next - unlocked_at_milestone)
+                            .multiply_ratio(elapsed - reached.offset, next.offset - reached.offset)
+                }
+                None => total_amount,
+            }
+        }
+        None => Uint128::zero(),
+    }
+}
 
 #[cw_serde]
 pub struct Allocation {
@@ -82,17 +206,40 @@ impl Allocation {
             }
         );
 
+        let params = match params.schedule_template {
+            Some(template_id) => {
+                ensure!(
+                    params.unlock_schedule == Schedule::default(),
+                    ContractError::AmbiguousSchedule {
+                        user: user.to_string()
+                    }
+                );
+
+                let template = SCHEDULE_TEMPLATES
+                    .may_load(storage, template_id)?
+                    .ok_or(ContractError::ScheduleTemplateNotFound { template_id })?;
+
+                CreateAllocationParams {
+                    unlock_schedule: template.schedule,
+                    ..params
+                }
+            }
+            None => params,
+        };
+
         params.validate(user.as_str())?;
 
         Ok(Self {
             params: AllocationParams {
                 unlock_schedule: params.unlock_schedule,
                 proposed_receiver: None,
+                schedule_template: params.schedule_template,
             },
             status: AllocationStatus {
                 amount: params.amount,
                 astro_withdrawn: Default::default(),
                 unlocked_amount_checkpoint: Default::default(),
+                advanced: Default::default(),
             },
             user: user.clone(),
             block_ts,
@@ -105,6 +252,8 @@ impl Allocation {
             ContractError::WithdrawErrorWhenProposedReceiver {}
         );
 
+        self.repay_advance();
+
         let SimulateWithdrawResponse { astro_to_withdraw } =
             self.compute_withdraw_amount(self.block_ts);
 
@@ -118,6 +267,57 @@ impl Allocation {
         Ok(astro_to_withdraw)
     }
 
+    /// Advances ASTRO which unlocks during the next [`ADVANCE_WINDOW`].
+    /// Returns the new outstanding advance.
+    pub fn take_advance(
+        &mut self,
+        amount: Uint128,
+        advance_ltv: Decimal,
+    ) -> Result<Uint128, ContractError> {
+        ensure!(
+            self.params.proposed_receiver.is_none(),
+            ContractError::WithdrawErrorWhenProposedReceiver {}
+        );
+
+        self.repay_advance();
+
+        let available = self.compute_advance_limit(self.block_ts, advance_ltv);
+        ensure!(
+            !amount.is_zero() && amount <= available,
+            ContractError::InvalidAdvanceAmount { available }
+        );
+
+        self.status.advanced += amount;
+
+        Ok(self.status.advanced)
+    }
+
+    /// Repays the outstanding advance with tokens which are already unlocked.
+    /// Repaid tokens are accounted as withdrawn since they were sent out with the advance.
+    fn repay_advance(&mut self) {
+        let unlocked = self.compute_unlocked_amount(self.block_ts) - self.status.astro_withdrawn;
+        let repaid = unlocked.min(self.status.advanced);
+
+        self.status.astro_withdrawn += repaid;
+        self.status.advanced -= repaid;
+    }
+
+    /// Computes the advance which is not covered by unlocked tokens yet
+    pub fn compute_outstanding_advance(&self, timestamp: u64) -> Uint128 {
+        let unlocked = self.compute_unlocked_amount(timestamp) - self.status.astro_withdrawn;
+        self.status.advanced.saturating_sub(unlocked)
+    }
+
+    /// Computes max amount of ASTRO which can be advanced at a given timestamp.
+    /// The limit is `advance_ltv` of tokens unlocking during the next [`ADVANCE_WINDOW`]
+    /// minus the outstanding advance.
+    pub fn compute_advance_limit(&self, timestamp: u64, advance_ltv: Decimal) -> Uint128 {
+        let upcoming = self.compute_unlocked_amount(timestamp + ADVANCE_WINDOW)
+            - self.compute_unlocked_amount(timestamp);
+
+        (upcoming * advance_ltv).saturating_sub(self.compute_outstanding_advance(timestamp))
+    }
+
     pub fn propose_new_receiver(
         &mut self,
         storage: &dyn Storage,
@@ -177,6 +377,17 @@ impl Allocation {
             self.status.amount,
         );
 
+        if let Some(milestones) = &schedule.milestones {
+            let unlocked_amount = compute_milestone_unlocked_amount(
+                schedule.start_time,
+                milestones,
+                timestamp,
+                total_amount,
+            );
+
+            return unlocked_amount.max(unlock_checkpoint);
+        }
+
         // Tokens haven't begun unlocking
         if timestamp < schedule.start_time + schedule.cliff {
             unlock_checkpoint
@@ -213,14 +424,18 @@ impl Allocation {
         let astro_unlocked = self.compute_unlocked_amount(timestamp);
 
         // Withdrawal amount is unlocked amount minus the amount already withdrawn
+        // and the advance which is repaid from unlocked tokens first
         SimulateWithdrawResponse {
-            astro_to_withdraw: astro_unlocked - self.status.astro_withdrawn,
+            astro_to_withdraw: (astro_unlocked - self.status.astro_withdrawn)
+                .saturating_sub(self.status.advanced),
         }
     }
 
     pub fn decrease_allocation(&mut self, amount: Uint128) -> Result<(), ContractError> {
         let unlocked_amount = self.compute_unlocked_amount(self.block_ts);
-        let locked_amount = self.status.amount - unlocked_amount;
+        // Locked tokens must still cover the outstanding advance
+        let locked_amount = (self.status.amount - unlocked_amount)
+            .saturating_sub(self.compute_outstanding_advance(self.block_ts));
 
         ensure!(
             locked_amount >= amount,
@@ -1,15 +1,24 @@
 use std::time::SystemTime;
 
-use cosmwasm_std::{coin, coins, Addr, Decimal, StdResult, Timestamp, Uint128};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    coin, coins, to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo,
+    Response, StdResult, Timestamp, Uint128, Uint64,
+};
 use cw_multi_test::{App, BasicApp, ContractWrapper, Executor};
 use cw_utils::PaymentError;
 
+use astroport_governance::assembly;
 use astroport_governance::builder_unlock::{
-    AllocationParams, AllocationResponse, Config, ExecuteMsg, InstantiateMsg, QueryMsg,
-    SimulateWithdrawResponse,
+    AdvanceResponse, AllocationParams, AllocationResponse, AllocationStatusFilter, Config,
+    EpochRebate, ExecuteMsg, FallbackReceiver, InstantiateMsg, MerkleDrop, QueryMsg,
+    RebatesResponse, ScheduleTemplate, SimulateWithdrawResponse, UnlockMilestone,
+    FALLBACK_RECEIVER_TIMELOCK, MIN_FALLBACK_INACTIVITY_PERIOD,
 };
 use astroport_governance::builder_unlock::{CreateAllocationParams, Schedule, State};
+use astroport_governance::emissions_controller::consts::{EPOCHS_START, EPOCH_LENGTH};
 use builder_unlock::error::ContractError;
+use builder_unlock::merkle::{hash_pair, leaf_hash};
 
 pub const ASTRO_DENOM: &str = "factory/assembly/ASTRO";
 
@@ -185,7 +194,9 @@ fn test_create_allocations() {
                 cliff: 0u64,
                 duration: 31536000u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
     allocations.push((
@@ -197,7 +208,9 @@ fn test_create_allocations() {
                 cliff: 7776000u64,
                 duration: 31536000u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
     allocations.push((
@@ -209,7 +222,9 @@ fn test_create_allocations() {
                 cliff: 7776000u64,
                 duration: 31536000u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
 
@@ -313,6 +328,7 @@ fn test_create_allocations() {
             cliff: 0u64,
             duration: 31536000u64,
             percent_at_cliff: None,
+            milestones: None,
         }
     );
 
@@ -336,6 +352,7 @@ fn test_create_allocations() {
             cliff: 7776000u64,
             duration: 31536000u64,
             percent_at_cliff: None,
+            milestones: None,
         }
     );
 
@@ -359,6 +376,7 @@ fn test_create_allocations() {
             cliff: 7776000u64,
             duration: 31536000u64,
             percent_at_cliff: None,
+            milestones: None,
         }
     );
 
@@ -396,7 +414,9 @@ fn test_withdraw() {
                 cliff: 0u64,
                 duration: 31536000u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
     allocations.push((
@@ -408,7 +428,9 @@ fn test_withdraw() {
                 cliff: 7776000u64,
                 duration: 31536000u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
     allocations.push((
@@ -420,7 +442,9 @@ fn test_withdraw() {
                 cliff: 7776000u64,
                 duration: 31536000u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
 
@@ -512,6 +536,19 @@ fn test_withdraw() {
     assert_eq!(alloc_resp.status.amount, Uint128::from(5_000_000_000000u64));
     assert_eq!(alloc_resp.status.astro_withdrawn, Uint128::from(158548u64));
 
+    // Withdrawn ASTRO doesn't count towards voting power
+    let voting_power: Uint128 = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::VotingPowerAt {
+                account: "investor_1".to_string(),
+                timestamp: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(voting_power, Uint128::from(4_999_999_841452u64));
+
     let astro_bal_after = app.wrap().query_balance("investor_1", ASTRO_DENOM).unwrap();
 
     assert_eq!(
@@ -734,7 +771,9 @@ fn test_propose_new_receiver() {
                 cliff: 0u64,
                 duration: 31536000u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
     allocations.push((
@@ -746,7 +785,9 @@ fn test_propose_new_receiver() {
                 cliff: 7776000u64,
                 duration: 31536000u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
     allocations.push((
@@ -758,7 +799,9 @@ fn test_propose_new_receiver() {
                 cliff: 7776000u64,
                 duration: 31536000u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
 
@@ -867,7 +910,9 @@ fn test_drop_new_receiver() {
                 cliff: 0u64,
                 duration: 31536000u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
     allocations.push((
@@ -879,7 +924,9 @@ fn test_drop_new_receiver() {
                 cliff: 7776000u64,
                 duration: 31536000u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
     allocations.push((
@@ -891,7 +938,9 @@ fn test_drop_new_receiver() {
                 cliff: 7776000u64,
                 duration: 31536000u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
 
@@ -999,7 +1048,9 @@ fn test_claim_receiver() {
                 cliff: 0u64,
                 duration: 31536000u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
     allocations.push((
@@ -1011,7 +1062,9 @@ fn test_claim_receiver() {
                 cliff: 7776000u64,
                 duration: 31536000u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
     allocations.push((
@@ -1023,7 +1076,9 @@ fn test_claim_receiver() {
                 cliff: 7776000u64,
                 duration: 31536000u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
 
@@ -1134,8 +1189,10 @@ fn test_claim_receiver() {
                 cliff: 0u64,
                 duration: 0u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
             proposed_receiver: None,
+            schedule_template: None,
         },
         alloc_resp_after.params
     );
@@ -1158,8 +1215,10 @@ fn test_claim_receiver() {
                 cliff: alloc_resp_before.params.unlock_schedule.cliff,
                 duration: alloc_resp_before.params.unlock_schedule.duration,
                 percent_at_cliff: None,
+                milestones: None,
             },
             proposed_receiver: None,
+            schedule_template: None,
         },
         alloc_resp_after.params
     );
@@ -1213,7 +1272,9 @@ fn test_increase_and_decrease_allocation() {
                 cliff: 300u64,
                 duration: 1_534_700u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     )];
 
@@ -1427,7 +1488,9 @@ fn test_updates_schedules() {
                 cliff: 0u64,
                 duration: 31536000u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
     allocations.push((
@@ -1439,7 +1502,9 @@ fn test_updates_schedules() {
                 cliff: 7776000u64,
                 duration: 31536000u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
     allocations.push((
@@ -1451,7 +1516,9 @@ fn test_updates_schedules() {
                 cliff: 7776000u64,
                 duration: 31536000u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
 
@@ -1492,6 +1559,7 @@ fn test_updates_schedules() {
             cliff: 0u64,
             duration: 31536000u64,
             percent_at_cliff: None,
+            milestones: None,
         },
     )
     .unwrap();
@@ -1508,6 +1576,7 @@ fn test_updates_schedules() {
             cliff: 7776000u64,
             duration: 31536000u64,
             percent_at_cliff: None,
+            milestones: None,
         },
     )
     .unwrap();
@@ -1524,6 +1593,7 @@ fn test_updates_schedules() {
             cliff: 7776000u64,
             duration: 31536000u64,
             percent_at_cliff: None,
+            milestones: None,
         },
     )
     .unwrap();
@@ -1541,6 +1611,7 @@ fn test_updates_schedules() {
                         cliff: 123u64,
                         duration: 123u64,
                         percent_at_cliff: None,
+                        milestones: None,
                     },
                 )],
             },
@@ -1565,6 +1636,7 @@ fn test_updates_schedules() {
                             cliff: 123u64,
                             duration: 123u64,
                             percent_at_cliff: None,
+                            milestones: None,
                         },
                     ),
                     (
@@ -1574,6 +1646,7 @@ fn test_updates_schedules() {
                             cliff: 123u64,
                             duration: 123u64,
                             percent_at_cliff: None,
+                            milestones: None,
                         },
                     ),
                 ],
@@ -1598,6 +1671,7 @@ fn test_updates_schedules() {
                         cliff: 8776000u64,
                         duration: 31536001u64,
                         percent_at_cliff: None,
+                        milestones: None,
                     },
                 ),
                 (
@@ -1607,6 +1681,7 @@ fn test_updates_schedules() {
                         cliff: 8776000u64,
                         duration: 31536001u64,
                         percent_at_cliff: None,
+                        milestones: None,
                     },
                 ),
             ],
@@ -1627,6 +1702,7 @@ fn test_updates_schedules() {
             cliff: 8776000u64,
             duration: 31536001u64,
             percent_at_cliff: None,
+            milestones: None,
         },
     )
     .unwrap();
@@ -1643,6 +1719,7 @@ fn test_updates_schedules() {
             cliff: 8776000u64,
             duration: 31536001u64,
             percent_at_cliff: None,
+            milestones: None,
         },
     )
     .unwrap();
@@ -1668,8 +1745,10 @@ fn test_updates_schedules() {
                     cliff: 8776000u64,
                     duration: 31536001u64,
                     percent_at_cliff: None,
+                    milestones: None,
                 },
                 proposed_receiver: None,
+                schedule_template: None,
             },
         ),
         (
@@ -1680,8 +1759,10 @@ fn test_updates_schedules() {
                     cliff: 0,
                     duration: 31536000,
                     percent_at_cliff: None,
+                    milestones: None,
                 },
                 proposed_receiver: None,
+                schedule_template: None,
             },
         ),
         (
@@ -1692,8 +1773,10 @@ fn test_updates_schedules() {
                     cliff: 8776000u64,
                     duration: 31536001u64,
                     percent_at_cliff: None,
+                    milestones: None,
                 },
                 proposed_receiver: None,
+                schedule_template: None,
             },
         ),
     ];
@@ -1718,8 +1801,10 @@ fn test_updates_schedules() {
                 cliff: 8776000u64,
                 duration: 31536001u64,
                 percent_at_cliff: None,
+                milestones: None,
             },
             proposed_receiver: None,
+            schedule_template: None,
         },
     )];
     assert_eq!(comparing_values, resp);
@@ -1784,7 +1869,9 @@ fn test_create_allocations_with_custom_cliff() {
                 cliff: day * 365,        // 1 year
                 duration: 3 * day * 365, // 3 years
                 percent_at_cliff: None,
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
     allocations.push((
@@ -1796,7 +1883,9 @@ fn test_create_allocations_with_custom_cliff() {
                 cliff: 6 * day * 30,                                   // 6 months
                 duration: 3 * day * 365,                               // 3 years
                 percent_at_cliff: Some(Decimal::from_ratio(1u8, 6u8)), // one sixth
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
     allocations.push((
@@ -1808,7 +1897,9 @@ fn test_create_allocations_with_custom_cliff() {
                 cliff: 6 * day * 30,                          // 6 months
                 duration: 3 * day * 365,                      // 3 years
                 percent_at_cliff: Some(Decimal::percent(20)), // 20% at cliff
+                milestones: None,
             },
+            schedule_template: None,
         },
     ));
 
@@ -2006,3 +2097,1775 @@ impl AppExtension for App {
         });
     }
 }
+
+#[test]
+fn test_merkle_drop() {
+    let mut app = mock_app();
+    let (unlock_instance, _) = init_contracts(&mut app);
+    let now_ts = app.block_info().time.seconds();
+
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+    let carol = Addr::unchecked("carol");
+    let allocations = [&alice, &bob, &carol]
+        .into_iter()
+        .enumerate()
+        .map(|(i, user)| {
+            let params = CreateAllocationParams {
+                amount: Uint128::new(100_000000 * (i as u128 + 1)),
+                unlock_schedule: Schedule {
+                    start_time: now_ts,
+                    cliff: 0,
+                    duration: 1000,
+                    percent_at_cliff: None,
+                    milestones: None,
+                },
+                schedule_template: None,
+            };
+            (user.clone(), params)
+        })
+        .collect::<Vec<_>>();
+
+    // Merkle tree with 3 leaves: root = hash(hash(leaf0, leaf1), leaf2)
+    let leaves = allocations
+        .iter()
+        .enumerate()
+        .map(|(i, (user, params))| leaf_hash(i as u64, user, params).unwrap())
+        .collect::<Vec<_>>();
+    let node01 = hash_pair(&leaves[0], &leaves[1]);
+    let merkle_root = Binary::from(hash_pair(&node01, &leaves[2]));
+    let proofs = [
+        vec![Binary::from(leaves[1]), Binary::from(leaves[2])],
+        vec![Binary::from(leaves[0]), Binary::from(leaves[2])],
+        vec![Binary::from(node01)],
+    ];
+
+    let total_amount = Uint128::new(600_000000);
+    let expiration = now_ts + 1000;
+
+    let err = app
+        .execute_contract(
+            alice.clone(),
+            unlock_instance.clone(),
+            &ExecuteMsg::RegisterMerkleDrop {
+                merkle_root: merkle_root.clone(),
+                expiration,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            unlock_instance.clone(),
+            &ExecuteMsg::RegisterMerkleDrop {
+                merkle_root: Binary::from(vec![1u8; 31]),
+                expiration,
+            },
+            &coins(total_amount.u128(), ASTRO_DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidMerkleRoot {}
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::RegisterMerkleDrop {
+            merkle_root: merkle_root.clone(),
+            expiration,
+        },
+        &coins(total_amount.u128(), ASTRO_DENOM),
+    )
+    .unwrap();
+
+    let claim_msg = |index: usize, proof: Vec<Binary>| ExecuteMsg::ClaimMerkleAllocation {
+        drop_id: 1,
+        index: index as u64,
+        params: allocations[index].1.clone(),
+        proof,
+    };
+
+    // Invalid proof
+    let err = app
+        .execute_contract(
+            alice.clone(),
+            unlock_instance.clone(),
+            &claim_msg(0, proofs[1].clone()),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidMerkleProof {}
+    );
+
+    // Bob can't claim Alice's allocation
+    let err = app
+        .execute_contract(
+            bob.clone(),
+            unlock_instance.clone(),
+            &claim_msg(0, proofs[0].clone()),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidMerkleProof {}
+    );
+
+    for (i, user) in [&alice, &bob].into_iter().enumerate() {
+        app.execute_contract(
+            user.clone(),
+            unlock_instance.clone(),
+            &claim_msg(i, proofs[i].clone()),
+            &[],
+        )
+        .unwrap();
+        check_alloc_amount(&mut app, &unlock_instance, user, allocations[i].1.amount);
+    }
+
+    let is_claimed: bool = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::IsClaimed {
+                drop_id: 1,
+                index: 0,
+            },
+        )
+        .unwrap();
+    assert!(is_claimed);
+
+    let err = app
+        .execute_contract(
+            alice.clone(),
+            unlock_instance.clone(),
+            &claim_msg(0, proofs[0].clone()),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MerkleAllocationClaimed {
+            drop_id: 1,
+            index: 0
+        }
+    );
+
+    let err = app
+        .execute_contract(
+            carol.clone(),
+            unlock_instance.clone(),
+            &ExecuteMsg::ExpireMerkleDrop { drop_id: 1 },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MerkleDropNotExpired {
+            drop_id: 1,
+            expiration
+        }
+    );
+
+    app.update_block(|block| block.time = Timestamp::from_seconds(expiration + 1));
+
+    // Carol didn't claim in time
+    let err = app
+        .execute_contract(
+            carol.clone(),
+            unlock_instance.clone(),
+            &claim_msg(2, proofs[2].clone()),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MerkleDropExpired { drop_id: 1 }
+    );
+
+    app.execute_contract(
+        carol.clone(),
+        unlock_instance.clone(),
+        &ExecuteMsg::ExpireMerkleDrop { drop_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let merkle_drop: MerkleDrop = app
+        .wrap()
+        .query_wasm_smart(&unlock_instance, &QueryMsg::MerkleDrop { drop_id: 1 })
+        .unwrap();
+    assert_eq!(
+        merkle_drop,
+        MerkleDrop {
+            merkle_root,
+            total_amount,
+            claimed_amount: Uint128::new(300_000000),
+            expiration,
+            expired: true,
+        }
+    );
+
+    // Unclaimed ASTRO returned to unallocated tokens
+    let state: State = app
+        .wrap()
+        .query_wasm_smart(&unlock_instance, &QueryMsg::State { timestamp: None })
+        .unwrap();
+    assert_eq!(
+        state,
+        State {
+            total_astro_deposited: total_amount,
+            remaining_astro_tokens: Uint128::new(300_000000),
+            unallocated_astro_tokens: Uint128::new(300_000000),
+        }
+    );
+
+    let err = app
+        .execute_contract(
+            carol.clone(),
+            unlock_instance.clone(),
+            &ExecuteMsg::ExpireMerkleDrop { drop_id: 1 },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MerkleDropExpired { drop_id: 1 }
+    );
+}
+
+#[test]
+fn test_fallback_receiver() {
+    let mut app = mock_app();
+    let (unlock_instance, _) = init_contracts(&mut app);
+    let now_ts = app.block_info().time.seconds();
+
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+    let carol = Addr::unchecked("carol");
+
+    let schedule = Schedule {
+        start_time: now_ts,
+        cliff: 0,
+        duration: 4 * MIN_FALLBACK_INACTIVITY_PERIOD,
+        percent_at_cliff: None,
+        milestones: None,
+    };
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::CreateAllocations {
+            allocations: vec![(
+                alice.to_string(),
+                CreateAllocationParams {
+                    amount: Uint128::new(400_000000),
+                    unlock_schedule: schedule.clone(),
+                    schedule_template: None,
+                },
+            )],
+        },
+        &coins(400_000000, ASTRO_DENOM),
+    )
+    .unwrap();
+
+    let set_msg = |receiver: &Addr, inactivity_period: u64| ExecuteMsg::SetFallbackReceiver {
+        receiver: receiver.to_string(),
+        inactivity_period,
+    };
+    let query_fallback = |app: &App, account: &Addr| -> Option<FallbackReceiver> {
+        app.wrap()
+            .query_wasm_smart(
+                &unlock_instance,
+                &QueryMsg::FallbackReceiver {
+                    account: account.to_string(),
+                },
+            )
+            .unwrap()
+    };
+
+    let err = app
+        .execute_contract(
+            bob.clone(),
+            unlock_instance.clone(),
+            &set_msg(&carol, MIN_FALLBACK_INACTIVITY_PERIOD),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NoAllocation {
+            address: bob.to_string()
+        }
+    );
+
+    let err = app
+        .execute_contract(
+            alice.clone(),
+            unlock_instance.clone(),
+            &set_msg(&alice, MIN_FALLBACK_INACTIVITY_PERIOD),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidFallbackReceiver {}
+    );
+
+    let err = app
+        .execute_contract(
+            alice.clone(),
+            unlock_instance.clone(),
+            &set_msg(&bob, MIN_FALLBACK_INACTIVITY_PERIOD - 1),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidInactivityPeriod {
+            min: MIN_FALLBACK_INACTIVITY_PERIOD
+        }
+    );
+
+    let err = app
+        .execute_contract(
+            alice.clone(),
+            unlock_instance.clone(),
+            &ExecuteMsg::RemoveFallbackReceiver {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::FallbackReceiverNotSet {}
+    );
+
+    // Set and replace the fallback receiver
+    app.execute_contract(
+        alice.clone(),
+        unlock_instance.clone(),
+        &set_msg(&carol, MIN_FALLBACK_INACTIVITY_PERIOD),
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        alice.clone(),
+        unlock_instance.clone(),
+        &set_msg(&bob, MIN_FALLBACK_INACTIVITY_PERIOD),
+        &[],
+    )
+    .unwrap();
+
+    let fallback = FallbackReceiver {
+        receiver: bob.clone(),
+        inactivity_period: MIN_FALLBACK_INACTIVITY_PERIOD,
+        effective_from: now_ts + FALLBACK_RECEIVER_TIMELOCK,
+        last_activity: now_ts,
+    };
+    assert_eq!(query_fallback(&app, &alice), Some(fallback.clone()));
+    let fallbacks: Vec<(Addr, FallbackReceiver)> = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::FallbackReceivers {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(fallbacks, vec![(alice.clone(), fallback.clone())]);
+
+    let err = app
+        .execute_contract(
+            bob.clone(),
+            unlock_instance.clone(),
+            &ExecuteMsg::ClaimFallback {
+                account: alice.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::FallbackNotClaimable {
+            claimable_from: now_ts + MIN_FALLBACK_INACTIVITY_PERIOD
+        }
+    );
+
+    // Withdrawal restarts the inactivity period
+    app.update_block(|b| b.time = b.time.plus_seconds(MIN_FALLBACK_INACTIVITY_PERIOD / 2));
+    app.execute_contract(
+        alice.clone(),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw {},
+        &[],
+    )
+    .unwrap();
+    let withdraw_ts = app.block_info().time.seconds();
+
+    app.update_block(|b| b.time = b.time.plus_seconds(MIN_FALLBACK_INACTIVITY_PERIOD / 2));
+    let err = app
+        .execute_contract(
+            bob.clone(),
+            unlock_instance.clone(),
+            &ExecuteMsg::ClaimFallback {
+                account: alice.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::FallbackNotClaimable {
+            claimable_from: withdraw_ts + MIN_FALLBACK_INACTIVITY_PERIOD
+        }
+    );
+
+    app.update_block(|b| {
+        b.time = Timestamp::from_seconds(withdraw_ts + MIN_FALLBACK_INACTIVITY_PERIOD)
+    });
+
+    // Only the fallback receiver can claim the allocation
+    let err = app
+        .execute_contract(
+            carol.clone(),
+            unlock_instance.clone(),
+            &ExecuteMsg::ClaimFallback {
+                account: alice.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    let alice_allocation: AllocationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::Allocation {
+                account: alice.to_string(),
+                timestamp: None,
+            },
+        )
+        .unwrap();
+
+    app.execute_contract(
+        bob.clone(),
+        unlock_instance.clone(),
+        &ExecuteMsg::ClaimFallback {
+            account: alice.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // The allocation is moved to the fallback receiver
+    check_allocation(
+        &mut app,
+        &unlock_instance,
+        bob.to_string(),
+        alice_allocation.status.amount,
+        alice_allocation.status.astro_withdrawn,
+        schedule,
+    )
+    .unwrap();
+    check_alloc_amount(&mut app, &unlock_instance, &alice, Uint128::zero());
+    assert_eq!(query_fallback(&app, &alice), None);
+
+    app.execute_contract(
+        bob.clone(),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw {},
+        &[],
+    )
+    .unwrap();
+    assert!(query_bal(&mut app, &bob) > 0);
+}
+
+#[test]
+fn test_advances() {
+    let mut app = mock_app();
+    let (unlock_instance, _) = init_contracts(&mut app);
+    let now_ts = app.block_info().time.seconds();
+    let day = 86400;
+
+    let alice = Addr::unchecked("alice");
+
+    // 1 ASTRO unlocks every day
+    let schedule = Schedule {
+        start_time: now_ts,
+        cliff: 0,
+        duration: 300 * day,
+        percent_at_cliff: None,
+        milestones: None,
+    };
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::CreateAllocations {
+            allocations: vec![(
+                alice.to_string(),
+                CreateAllocationParams {
+                    amount: Uint128::new(300_000000),
+                    unlock_schedule: schedule.clone(),
+                    schedule_template: None,
+                },
+            )],
+        },
+        &coins(300_000000, ASTRO_DENOM),
+    )
+    .unwrap();
+
+    let advance_msg = |amount: u128| ExecuteMsg::TakeAdvance {
+        amount: Uint128::new(amount),
+    };
+    let query_advance = |app: &App| -> AdvanceResponse {
+        app.wrap()
+            .query_wasm_smart(
+                &unlock_instance,
+                &QueryMsg::Advance {
+                    account: alice.to_string(),
+                },
+            )
+            .unwrap()
+    };
+
+    // Advances are disabled by default
+    let err = app
+        .execute_contract(alice.clone(), unlock_instance.clone(), &advance_msg(1), &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidAdvanceAmount {
+            available: Uint128::zero()
+        }
+    );
+
+    let update_config_msg = |ltv: Decimal| ExecuteMsg::UpdateConfig {
+        new_max_allocations_amount: Uint128::new(300_000_000_000_000u128),
+        new_advance_ltv: Some(ltv),
+        new_assembly: None,
+        new_rebate_per_epoch: None,
+    };
+    let err = app
+        .execute_contract(
+            alice.clone(),
+            unlock_instance.clone(),
+            &update_config_msg(Decimal::percent(50)),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            unlock_instance.clone(),
+            &update_config_msg(Decimal::percent(150)),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Advance LTV must not exceed 1"
+    );
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &update_config_msg(Decimal::percent(50)),
+        &[],
+    )
+    .unwrap();
+
+    // Half of 30 ASTRO unlocking during the next 30 days can be advanced
+    assert_eq!(
+        query_advance(&app),
+        AdvanceResponse {
+            outstanding: Uint128::zero(),
+            available: Uint128::new(15_000000)
+        }
+    );
+
+    let err = app
+        .execute_contract(
+            alice.clone(),
+            unlock_instance.clone(),
+            &advance_msg(15_000001),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidAdvanceAmount {
+            available: Uint128::new(15_000000)
+        }
+    );
+
+    app.execute_contract(
+        alice.clone(),
+        unlock_instance.clone(),
+        &advance_msg(10_000000),
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_bal(&mut app, &alice), 10_000000);
+    assert_eq!(
+        query_advance(&app),
+        AdvanceResponse {
+            outstanding: Uint128::new(10_000000),
+            available: Uint128::new(5_000000)
+        }
+    );
+
+    // Advanced tokens are not counted as voting power anymore
+    let voting_power: Uint128 = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::VotingPowerAt {
+                account: alice.to_string(),
+                timestamp: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(voting_power.u128(), 290_000000);
+    let state: State = app
+        .wrap()
+        .query_wasm_smart(&unlock_instance, &QueryMsg::State { timestamp: None })
+        .unwrap();
+    assert_eq!(state.remaining_astro_tokens.u128(), 290_000000);
+
+    // Unlocked tokens repay the advance first
+    app.update_block(|b| b.time = b.time.plus_seconds(5 * day));
+    assert_eq!(
+        query_advance(&app),
+        AdvanceResponse {
+            outstanding: Uint128::new(5_000000),
+            available: Uint128::new(10_000000)
+        }
+    );
+    let err = app
+        .execute_contract(
+            alice.clone(),
+            unlock_instance.clone(),
+            &ExecuteMsg::Withdraw {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NoUnlockedAstro {}
+    );
+
+    app.update_block(|b| b.time = b.time.plus_seconds(10 * day));
+    let simulate: SimulateWithdrawResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::SimulateWithdraw {
+                account: alice.to_string(),
+                timestamp: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(simulate.astro_to_withdraw.u128(), 5_000000);
+
+    app.execute_contract(
+        alice.clone(),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_bal(&mut app, &alice), 15_000000);
+    check_allocation(
+        &mut app,
+        &unlock_instance,
+        alice.to_string(),
+        Uint128::new(300_000000),
+        Uint128::new(15_000000),
+        schedule,
+    )
+    .unwrap();
+    assert_eq!(
+        query_advance(&app),
+        AdvanceResponse {
+            outstanding: Uint128::zero(),
+            available: Uint128::new(15_000000)
+        }
+    );
+
+    // Locked tokens backing the advance can't be decreased
+    app.execute_contract(
+        alice.clone(),
+        unlock_instance.clone(),
+        &advance_msg(15_000000),
+        &[],
+    )
+    .unwrap();
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            unlock_instance.clone(),
+            &ExecuteMsg::DecreaseAllocation {
+                receiver: alice.to_string(),
+                amount: Uint128::new(280_000000),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InsufficientLockedAmount {
+            locked_amount: Uint128::new(270_000000)
+        }
+    );
+}
+
+#[cw_serde]
+enum MockAssemblyMsg {
+    SubmitProposal { proposal_id: u64 },
+    CastVote { proposal_id: u64, user: String },
+}
+
+/// Minimal Assembly which only keeps proposal start times and votes
+fn mock_assembly_contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
+    Box::new(ContractWrapper::new_with_empty(
+        |deps: DepsMut, env: Env, _: MessageInfo, msg: MockAssemblyMsg| -> StdResult<Response> {
+            match msg {
+                MockAssemblyMsg::SubmitProposal { proposal_id } => deps.storage.set(
+                    &proposal_id.to_be_bytes(),
+                    &env.block.time.seconds().to_be_bytes(),
+                ),
+                MockAssemblyMsg::CastVote { proposal_id, user } => deps
+                    .storage
+                    .set(format!("{proposal_id}:{user}").as_bytes(), &[1]),
+            }
+            Ok(Response::new())
+        },
+        |_: DepsMut, _: Env, _: MessageInfo, _: Empty| -> StdResult<Response> {
+            Ok(Response::new())
+        },
+        |deps: Deps, _: Env, msg: assembly::QueryMsg| -> StdResult<Binary> {
+            match msg {
+                assembly::QueryMsg::Proposal { proposal_id } => {
+                    let start_time = deps.storage.get(&proposal_id.to_be_bytes()).unwrap();
+                    to_json_binary(&assembly::Proposal {
+                        proposal_id: Uint64::new(proposal_id),
+                        submitter: Addr::unchecked("submitter"),
+                        status: assembly::ProposalStatus::Active,
+                        for_power: Uint128::zero(),
+                        against_power: Uint128::zero(),
+                        start_block: 0,
+                        start_time: u64::from_be_bytes(start_time.try_into().unwrap()),
+                        end_block: 0,
+                        delayed_end_block: 0,
+                        expiration_block: 0,
+                        title: "Title".to_string(),
+                        description: "Description".to_string(),
+                        link: None,
+                        messages: vec![],
+                        deposit_amount: Uint128::zero(),
+                        ibc_channel: None,
+                        total_voting_power: Uint128::zero(),
+                        impact_class: Default::default(),
+                        reveal_end_block: None,
+                        review_end_block: None,
+                        amended: false,
+                        tags: vec![],
+                        adaptive_quorum: None,
+                    })
+                }
+                assembly::QueryMsg::UserVote { proposal_id, user } => to_json_binary(
+                    &deps
+                        .storage
+                        .get(format!("{proposal_id}:{user}").as_bytes())
+                        .map(|_| assembly::ProposalVoteOption::For),
+                ),
+                _ => unimplemented!(),
+            }
+        },
+    ))
+}
+
+#[test]
+fn test_voting_rebates() {
+    let mut app = mock_app();
+    let (unlock_instance, _) = init_contracts(&mut app);
+    app.update_block(|block| block.time = Timestamp::from_seconds(EPOCHS_START + 100));
+    let now_ts = app.block_info().time.seconds();
+    let day = 86400;
+
+    let assembly_code_id = app.store_code(mock_assembly_contract());
+    let assembly = app
+        .instantiate_contract(
+            assembly_code_id,
+            Addr::unchecked(OWNER),
+            &Empty {},
+            &[],
+            "assembly",
+            None,
+        )
+        .unwrap();
+
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+
+    let schedule = Schedule {
+        start_time: now_ts,
+        cliff: 0,
+        duration: 300 * day,
+        percent_at_cliff: None,
+        milestones: None,
+    };
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::CreateAllocations {
+            allocations: vec![
+                (
+                    alice.to_string(),
+                    CreateAllocationParams {
+                        amount: Uint128::new(300_000000),
+                        unlock_schedule: schedule.clone(),
+                        schedule_template: None,
+                    },
+                ),
+                (
+                    bob.to_string(),
+                    CreateAllocationParams {
+                        amount: Uint128::new(300_000000),
+                        unlock_schedule: Schedule {
+                            cliff: 100 * day,
+                            ..schedule
+                        },
+                        schedule_template: None,
+                    },
+                ),
+            ],
+        },
+        &coins(600_000000, ASTRO_DENOM),
+    )
+    .unwrap();
+
+    let submit_proposal = |app: &mut App, proposal_id: u64| {
+        app.execute_contract(
+            Addr::unchecked("submitter"),
+            assembly.clone(),
+            &MockAssemblyMsg::SubmitProposal { proposal_id },
+            &[],
+        )
+        .unwrap();
+    };
+    let cast_vote = |app: &mut App, proposal_id: u64, user: &Addr| {
+        app.execute_contract(
+            user.clone(),
+            assembly.clone(),
+            &MockAssemblyMsg::CastVote {
+                proposal_id,
+                user: user.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let record_vote = |app: &mut App, proposal_id: u64, user: &Addr| {
+        app.execute_contract(
+            user.clone(),
+            unlock_instance.clone(),
+            &ExecuteMsg::RecordProposalVote { proposal_id },
+            &[],
+        )
+    };
+    let query_rebates = |app: &App, user: &Addr| -> RebatesResponse {
+        app.wrap()
+            .query_wasm_smart(
+                &unlock_instance,
+                &QueryMsg::Rebates {
+                    account: user.to_string(),
+                },
+            )
+            .unwrap()
+    };
+
+    submit_proposal(&mut app, 1);
+    cast_vote(&mut app, 1, &alice);
+
+    // Rebates are disabled by default
+    let err = record_vote(&mut app, 1, &alice).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RebatesDisabled {}
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_max_allocations_amount: Uint128::new(300_000_000_000_000u128),
+            new_advance_ltv: None,
+            new_assembly: Some(assembly.to_string()),
+            new_rebate_per_epoch: Some(Uint128::new(10_000000)),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Rebate pool is empty
+    let err = record_vote(&mut app, 1, &alice).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RebatePoolEmpty {}
+    );
+
+    // Only owner can fund the rebate pool
+    mint_some_astro(&mut app, Uint128::new(15_000000), alice.to_string());
+    let err = app
+        .execute_contract(
+            alice.clone(),
+            unlock_instance.clone(),
+            &ExecuteMsg::FundRebatePool {},
+            &coins(15_000000, ASTRO_DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::FundRebatePool {},
+        &coins(15_000000, ASTRO_DENOM),
+    )
+    .unwrap();
+
+    record_vote(&mut app, 1, &alice).unwrap();
+    let err = record_vote(&mut app, 1, &alice).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RebateAlreadyRecorded {
+            epoch_start: EPOCHS_START
+        }
+    );
+
+    // Bob didn't vote yet
+    let err = record_vote(&mut app, 1, &bob).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ProposalNotVoted { proposal_id: 1 }
+    );
+    cast_vote(&mut app, 1, &bob);
+    record_vote(&mut app, 1, &bob).unwrap();
+
+    // Bob is in the cliff period and the epoch rebate is not claimable yet
+    let err = app
+        .execute_contract(
+            bob.clone(),
+            unlock_instance.clone(),
+            &ExecuteMsg::Withdraw {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NoUnlockedAstro {}
+    );
+
+    let epoch_rebate: Option<EpochRebate> = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::EpochRebate {
+                epoch_start: EPOCHS_START,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        epoch_rebate,
+        Some(EpochRebate {
+            amount: Uint128::new(10_000000),
+            participants: 2,
+        })
+    );
+
+    // Rebates are not claimable until the epoch is over
+    assert_eq!(
+        query_rebates(&app, &alice),
+        RebatesResponse {
+            claimable: Uint128::zero(),
+            epochs: vec![EPOCHS_START],
+        }
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(EPOCH_LENGTH));
+
+    // Votes in proposals from previous epochs are not accepted
+    let err = record_vote(&mut app, 1, &alice).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ProposalNotInCurrentEpoch { proposal_id: 1 }
+    );
+
+    assert_eq!(
+        query_rebates(&app, &alice).claimable,
+        Uint128::new(5_000000)
+    );
+    let unlocked: Uint128 = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::UnlockedTokens {
+                account: alice.to_string(),
+            },
+        )
+        .unwrap();
+    let alice_bal_before = query_bal(&mut app, &alice);
+    app.execute_contract(
+        alice.clone(),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_bal(&mut app, &alice),
+        alice_bal_before + unlocked.u128() + 5_000000
+    );
+    assert_eq!(
+        query_rebates(&app, &alice),
+        RebatesResponse {
+            claimable: Uint128::zero(),
+            epochs: vec![],
+        }
+    );
+
+    // The next epoch gets the rest of the pool
+    submit_proposal(&mut app, 2);
+    cast_vote(&mut app, 2, &alice);
+    record_vote(&mut app, 2, &alice).unwrap();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(EPOCH_LENGTH));
+
+    submit_proposal(&mut app, 3);
+    cast_vote(&mut app, 3, &bob);
+    let err = record_vote(&mut app, 3, &bob).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RebatePoolEmpty {}
+    );
+
+    // 1 ASTRO unlocks every day
+    let unlocked_per_epoch = 300_000000 * EPOCH_LENGTH as u128 / (300 * day) as u128;
+
+    let alice_bal_before = query_bal(&mut app, &alice);
+    app.execute_contract(
+        alice.clone(),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_bal(&mut app, &alice),
+        alice_bal_before + unlocked_per_epoch + 5_000000
+    );
+
+    // Bob's allocation is still in the cliff period, however, rebates are claimable
+    let bob_bal_before = query_bal(&mut app, &bob);
+    app.execute_contract(
+        bob.clone(),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_bal(&mut app, &bob), bob_bal_before + 5_000000);
+}
+
+#[test]
+fn test_batch_queries() {
+    let mut app = mock_app();
+    let (unlock_instance, _) = init_contracts(&mut app);
+
+    let schedule = Schedule {
+        start_time: 1642402274u64,
+        cliff: 0u64,
+        duration: 31536000u64,
+        percent_at_cliff: None,
+        milestones: None,
+    };
+    let allocations = ["alice", "bob", "carol"]
+        .into_iter()
+        .map(|account| {
+            (
+                account.to_string(),
+                CreateAllocationParams {
+                    amount: Uint128::from(5_000_000_000000u64),
+                    unlock_schedule: schedule.clone(),
+                    schedule_template: None,
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::CreateAllocations { allocations },
+        &coins(15_000_000_000000, ASTRO_DENOM),
+    )
+    .unwrap();
+
+    // Move to the middle of the unlock schedule
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(schedule.start_time + schedule.duration / 2)
+    });
+
+    let accounts = vec!["alice".to_string(), "bob".to_string()];
+    let batch: Vec<(String, SimulateWithdrawResponse)> = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::SimulateWithdrawBatch {
+                accounts: accounts.clone(),
+                timestamp: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(batch.len(), 2);
+    for (account, (batch_account, batch_resp)) in accounts.iter().zip(batch) {
+        let resp: SimulateWithdrawResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &unlock_instance,
+                &QueryMsg::SimulateWithdraw {
+                    account: account.clone(),
+                    timestamp: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(account, &batch_account);
+        assert_eq!(resp, batch_resp);
+        assert_eq!(resp.astro_to_withdraw, Uint128::new(2_500_000_000000));
+    }
+
+    // Batch fails if any account doesn't have an allocation
+    app.wrap()
+        .query_wasm_smart::<Vec<(String, SimulateWithdrawResponse)>>(
+            &unlock_instance,
+            &QueryMsg::SimulateWithdrawBatch {
+                accounts: vec!["alice".to_string(), "random".to_string()],
+                timestamp: None,
+            },
+        )
+        .unwrap_err();
+
+    let err = app
+        .wrap()
+        .query_wasm_smart::<Vec<(String, SimulateWithdrawResponse)>>(
+            &unlock_instance,
+            &QueryMsg::SimulateWithdrawBatch {
+                accounts: vec!["alice".to_string(); 101],
+                timestamp: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Querier contract error: Generic error: Too many accounts in a single query. Max: 100"
+    );
+
+    // Alice withdraws everything after the schedule ends and Bob proposes a new receiver
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(schedule.start_time + schedule.duration)
+    });
+
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw {},
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("bob"),
+        unlock_instance.clone(),
+        &ExecuteMsg::ProposeNewReceiver {
+            new_receiver: "bob_new".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let query_by_status =
+        |status: AllocationStatusFilter, start_after: Option<&str>, limit: Option<u32>| {
+            app.wrap()
+                .query_wasm_smart::<Vec<(Addr, AllocationResponse)>>(
+                    &unlock_instance,
+                    &QueryMsg::AllocationsByStatus {
+                        status,
+                        start_after: start_after.map(ToString::to_string),
+                        limit,
+                    },
+                )
+                .unwrap()
+                .into_iter()
+                .map(|(account, _)| account.to_string())
+                .collect::<Vec<_>>()
+        };
+
+    assert_eq!(
+        query_by_status(AllocationStatusFilter::Active, None, None),
+        ["bob", "carol"]
+    );
+    assert_eq!(
+        query_by_status(AllocationStatusFilter::Active, None, Some(1)),
+        ["bob"]
+    );
+    assert_eq!(
+        query_by_status(AllocationStatusFilter::Active, Some("bob"), Some(1)),
+        ["carol"]
+    );
+    assert_eq!(
+        query_by_status(AllocationStatusFilter::FullyWithdrawn, None, None),
+        ["alice"]
+    );
+    assert_eq!(
+        query_by_status(AllocationStatusFilter::ProposedTransfer, None, None),
+        ["bob"]
+    );
+    assert!(
+        query_by_status(AllocationStatusFilter::ProposedTransfer, Some("bob"), None).is_empty()
+    );
+}
+
+#[test]
+fn test_schedule_templates() {
+    let mut app = mock_app();
+    let (unlock_instance, _) = init_contracts(&mut app);
+
+    let schedule = Schedule {
+        start_time: 1642402274u64,
+        cliff: 7776000u64,
+        duration: 31536000u64,
+        percent_at_cliff: None,
+        milestones: None,
+    };
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("random"),
+            unlock_instance.clone(),
+            &ExecuteMsg::CreateScheduleTemplate {
+                name: "team".to_string(),
+                schedule: schedule.clone(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            unlock_instance.clone(),
+            &ExecuteMsg::CreateScheduleTemplate {
+                name: "team".to_string(),
+                schedule: Schedule {
+                    cliff: schedule.duration,
+                    ..schedule.clone()
+                },
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidSchedule {}
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::CreateScheduleTemplate {
+            name: "team".to_string(),
+            schedule: schedule.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let template: ScheduleTemplate = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::ScheduleTemplate { template_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(
+        template,
+        ScheduleTemplate {
+            name: "team".to_string(),
+            schedule: schedule.clone(),
+        }
+    );
+
+    let templated = |template_id: u64| CreateAllocationParams {
+        amount: Uint128::from(5_000_000_000000u64),
+        unlock_schedule: Schedule::default(),
+        schedule_template: Some(template_id),
+    };
+
+    // Unknown template
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            unlock_instance.clone(),
+            &ExecuteMsg::CreateAllocations {
+                allocations: vec![("alice".to_string(), templated(2))],
+            },
+            &coins(5_000_000_000000, ASTRO_DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ScheduleTemplateNotFound { template_id: 2 }
+    );
+
+    // Inline schedule and template can't be specified at the same time
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            unlock_instance.clone(),
+            &ExecuteMsg::CreateAllocations {
+                allocations: vec![(
+                    "alice".to_string(),
+                    CreateAllocationParams {
+                        unlock_schedule: schedule.clone(),
+                        ..templated(1)
+                    },
+                )],
+            },
+            &coins(5_000_000_000000, ASTRO_DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::AmbiguousSchedule {
+            user: "alice".to_string()
+        }
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::CreateAllocations {
+            allocations: vec![
+                ("alice".to_string(), templated(1)),
+                ("bob".to_string(), templated(1)),
+                (
+                    "carol".to_string(),
+                    CreateAllocationParams {
+                        amount: Uint128::from(5_000_000_000000u64),
+                        unlock_schedule: schedule.clone(),
+                        schedule_template: None,
+                    },
+                ),
+            ],
+        },
+        &coins(15_000_000_000000, ASTRO_DENOM),
+    )
+    .unwrap();
+
+    let alloc: AllocationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::Allocation {
+                account: "alice".to_string(),
+                timestamp: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        alloc.params,
+        AllocationParams {
+            unlock_schedule: schedule.clone(),
+            proposed_receiver: None,
+            schedule_template: Some(1),
+        }
+    );
+
+    let query_template_allocations = |app: &App| {
+        app.wrap()
+            .query_wasm_smart::<Vec<Addr>>(
+                &unlock_instance,
+                &QueryMsg::TemplateAllocations {
+                    template_id: 1,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+    };
+    assert_eq!(
+        query_template_allocations(&app),
+        [Addr::unchecked("alice"), Addr::unchecked("bob")]
+    );
+
+    // Template update doesn't affect existing allocations
+    let new_schedule = Schedule {
+        duration: schedule.duration * 2,
+        ..schedule.clone()
+    };
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::UpdateScheduleTemplate {
+            template_id: 1,
+            new_name: Some("team v2".to_string()),
+            new_schedule: Some(new_schedule.clone()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let templates: Vec<(u64, ScheduleTemplate)> = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::ScheduleTemplates {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        templates,
+        [(
+            1,
+            ScheduleTemplate {
+                name: "team v2".to_string(),
+                schedule: new_schedule.clone(),
+            }
+        )]
+    );
+
+    let alloc: AllocationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::Allocation {
+                account: "bob".to_string(),
+                timestamp: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(alloc.params.unlock_schedule, schedule);
+
+    // Individually updated schedule no longer follows the template
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::UpdateUnlockSchedules {
+            new_unlock_schedules: vec![("bob".to_string(), new_schedule.clone())],
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_template_allocations(&app), [Addr::unchecked("alice")]);
+
+    // Template link moves along with the allocation to a new receiver
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        unlock_instance.clone(),
+        &ExecuteMsg::ProposeNewReceiver {
+            new_receiver: "alice_new".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("alice_new"),
+        unlock_instance.clone(),
+        &ExecuteMsg::ClaimReceiver {
+            prev_receiver: "alice".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_template_allocations(&app),
+        [Addr::unchecked("alice_new")]
+    );
+}
+
+#[test]
+fn test_milestone_schedules() {
+    let mut app = mock_app();
+    let (unlock_instance, _) = init_contracts(&mut app);
+
+    let day = 86400u64;
+    let start_time = app.block_info().time.seconds();
+    let milestone = |offset: u64, percent: u64| UnlockMilestone {
+        offset,
+        percent: Decimal::percent(percent),
+    };
+
+    // 10% at start, 20% after 6 months, the rest linearly during the next 18 months
+    let schedule = Schedule {
+        start_time,
+        cliff: 0,
+        duration: 0,
+        percent_at_cliff: None,
+        milestones: Some(vec![
+            milestone(0, 10),
+            milestone(180 * day, 10),
+            milestone(180 * day, 20),
+            milestone(720 * day, 100),
+        ]),
+    };
+
+    let create_allocation = |app: &mut App, account: &str, schedule: Schedule| {
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            unlock_instance.clone(),
+            &ExecuteMsg::CreateAllocations {
+                allocations: vec![(
+                    account.to_string(),
+                    CreateAllocationParams {
+                        amount: Uint128::from(1_000_000_000000u64),
+                        unlock_schedule: schedule,
+                        schedule_template: None,
+                    },
+                )],
+            },
+            &coins(1_000_000_000000, ASTRO_DENOM),
+        )
+    };
+
+    let invalid_schedules = [
+        (
+            Schedule {
+                milestones: Some(vec![]),
+                ..schedule.clone()
+            },
+            "Generic error: Number of unlock milestones must be within [1, 32]",
+        ),
+        (
+            Schedule {
+                milestones: Some(vec![milestone(day, 50), milestone(0, 100)]),
+                ..schedule.clone()
+            },
+            "Generic error: Unlock milestones must be ordered by offset and cumulative percent",
+        ),
+        (
+            Schedule {
+                milestones: Some(vec![milestone(0, 50), milestone(day, 40)]),
+                ..schedule.clone()
+            },
+            "Generic error: Unlock milestones must be ordered by offset and cumulative percent",
+        ),
+        (
+            Schedule {
+                milestones: Some(vec![milestone(0, 50), milestone(day, 90)]),
+                ..schedule.clone()
+            },
+            "Generic error: The last unlock milestone must unlock 100% of tokens",
+        ),
+        (
+            Schedule {
+                duration: day,
+                ..schedule.clone()
+            },
+            "Generic error: Cliff, duration and percent at cliff must be omitted if unlock milestones are set",
+        ),
+    ];
+    for (invalid_schedule, expected_err) in invalid_schedules {
+        let err = create_allocation(&mut app, "alice", invalid_schedule).unwrap_err();
+        assert_eq!(err.root_cause().to_string(), expected_err);
+    }
+
+    create_allocation(&mut app, "alice", schedule.clone()).unwrap();
+
+    let simulate_withdraw = |app: &App, timestamp: u64| {
+        app.wrap()
+            .query_wasm_smart::<SimulateWithdrawResponse>(
+                &unlock_instance,
+                &QueryMsg::SimulateWithdraw {
+                    account: "alice".to_string(),
+                    timestamp: Some(timestamp),
+                },
+            )
+            .unwrap()
+            .astro_to_withdraw
+            .u128()
+    };
+
+    assert_eq!(simulate_withdraw(&app, start_time), 100_000_000000);
+    assert_eq!(
+        simulate_withdraw(&app, start_time + 90 * day),
+        100_000_000000
+    );
+    assert_eq!(
+        simulate_withdraw(&app, start_time + 180 * day),
+        200_000_000000
+    );
+    assert_eq!(
+        simulate_withdraw(&app, start_time + 450 * day),
+        600_000_000000
+    );
+    assert_eq!(
+        simulate_withdraw(&app, start_time + 720 * day),
+        1_000_000_000000
+    );
+    assert_eq!(
+        simulate_withdraw(&app, start_time + 1000 * day),
+        1_000_000_000000
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(180 * day));
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        unlock_instance.clone(),
+        &ExecuteMsg::Withdraw {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_bal(&mut app, &Addr::unchecked("alice")),
+        200_000_000000
+    );
+
+    // The new schedule can't end earlier than the old one
+    let linear_schedule = Schedule {
+        start_time,
+        cliff: 0,
+        duration: 360 * day,
+        percent_at_cliff: None,
+        milestones: None,
+    };
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            unlock_instance.clone(),
+            &ExecuteMsg::UpdateUnlockSchedules {
+                new_unlock_schedules: vec![("alice".to_string(), linear_schedule.clone())],
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: The new schedule must not start or end earlier than the old one. Account error: alice"
+    );
+
+    // Switch to the linear unlock which ends in 2 years after the start
+    let linear_schedule = Schedule {
+        duration: 720 * day,
+        ..linear_schedule
+    };
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::UpdateUnlockSchedules {
+            new_unlock_schedules: vec![("alice".to_string(), linear_schedule.clone())],
+        },
+        &[],
+    )
+    .unwrap();
+    // Linear unlock is 25% after 6 months
+    assert_eq!(
+        simulate_withdraw(&app, start_time + 180 * day),
+        50_000_000000
+    );
+    // Withdrawn tokens are deducted from the linearly unlocked amount
+    assert_eq!(
+        simulate_withdraw(&app, start_time + 540 * day),
+        550_000_000000
+    );
+
+    // Milestone schedules can be used in templates as well
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            unlock_instance.clone(),
+            &ExecuteMsg::CreateScheduleTemplate {
+                name: "seed".to_string(),
+                schedule: Schedule {
+                    milestones: Some(vec![milestone(0, 50)]),
+                    ..schedule.clone()
+                },
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: The last unlock milestone must unlock 100% of tokens"
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::CreateScheduleTemplate {
+            name: "seed".to_string(),
+            schedule: schedule.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        unlock_instance.clone(),
+        &ExecuteMsg::CreateAllocations {
+            allocations: vec![(
+                "bob".to_string(),
+                CreateAllocationParams {
+                    amount: Uint128::from(1_000_000_000000u64),
+                    unlock_schedule: Schedule::default(),
+                    schedule_template: Some(1),
+                },
+            )],
+        },
+        &coins(1_000_000_000000, ASTRO_DENOM),
+    )
+    .unwrap();
+
+    let alloc: AllocationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &unlock_instance,
+            &QueryMsg::Allocation {
+                account: "bob".to_string(),
+                timestamp: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(alloc.params.unlock_schedule, schedule);
+    check_unlock_amount(
+        &mut app,
+        &unlock_instance,
+        &Addr::unchecked("bob"),
+        Uint128::new(200_000_000000),
+    );
+}
@@ -1,8 +1,10 @@
-use cosmwasm_std::{CheckedFromRatioError, Coin, StdError};
+use cosmwasm_std::{CheckedFromRatioError, Coin, Decimal, StdError};
 use cw_utils::{ParseReplyError, PaymentError};
 use neutron_sdk::NeutronError;
 use thiserror::Error;
 
+use astroport_governance::emissions_controller::msg::AckErrorCode;
+
 /// This enum describes contract errors
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
@@ -75,6 +77,12 @@ pub enum ContractError {
     #[error("Can't set zero emissions for astro pool")]
     ZeroAstroEmissions {},
 
+    #[error("Can't set zero emissions cap for astro pools")]
+    ZeroAstroEmissionsCap {},
+
+    #[error("Astro pool {0} is specified more than once")]
+    DuplicatedAstroPool(String),
+
     #[error("Failed to migrate contract")]
     MigrationError {},
 
@@ -84,6 +92,131 @@ pub enum ContractError {
     #[error("Outpost {prefix} is jailed. Only vxASTRO unlocks are available")]
     JailedOutpost { prefix: String },
 
+    #[error("Outpost {0} has no IBC channels to migrate")]
+    OutpostChannelsNotSet(String),
+
+    #[error("No new channels to migrate to")]
+    NoChannelsToMigrate {},
+
+    #[error("Channel {0} is already used or has been migrated")]
+    ChannelAlreadyUsed(String),
+
+    #[error("Packet retry {0} is not pending")]
+    PacketRetryNotPending(u64),
+
+    #[error("Packet retry {id} is available after {next_retry_at}")]
+    PacketRetryTooEarly { id: u64, next_retry_at: u64 },
+
     #[error("Pool {0} is blacklisted")]
     PoolIsBlacklisted(String),
+
+    #[error("Pool {0} has been migrated to another LP token")]
+    PoolMigrated(String),
+
+    #[error("Votes of the previous pool migration haven't been moved yet")]
+    PoolMigrationInProgress {},
+
+    #[error("No pool migration in progress")]
+    NoPoolMigrationInProgress {},
+
+    #[error("Vote weight for pool {pool} exceeds the limit of {max_weight}")]
+    PoolWeightExceedsLimit { pool: String, max_weight: Decimal },
+
+    #[error("{voter} has already voted on proposal {proposal_id} from {origin}")]
+    DuplicateVote {
+        proposal_id: u64,
+        voter: String,
+        origin: String,
+    },
+
+    #[error("Supply query for outpost {0} is already registered")]
+    SupplyQueryExists(String),
+
+    #[error("Supply query for outpost {0} is not registered")]
+    SupplyQueryNotFound(String),
+
+    #[error("No new interchain query result for outpost {0}")]
+    NoNewSupplyResult(String),
+
+    #[error("User can't be their own vote manager")]
+    InvalidVoteManager {},
+
+    #[error("Outpost {prefix} is not permitted to relay {action}")]
+    ActionNotPermitted { prefix: String, action: String },
+
+    #[error("Invalid outpost permissions bitmask {0}")]
+    InvalidOutpostPermissions(u8),
+
+    #[error("Whitelist auction is disabled")]
+    WhitelistAuctionDisabled {},
+
+    #[error("Whitelist is full. Bid for a whitelist slot instead")]
+    WhitelistFull {},
+
+    #[error("Whitelist is not full. Whitelist the pool directly")]
+    WhitelistNotFull {},
+
+    #[error("Bid must be at least {0}")]
+    BidTooLow(Coin),
+
+    #[error("No vote found for pool {0}")]
+    NoVoteForPool(String),
+
+    #[error("IBC timeout must be within [{min}, {max}] seconds")]
+    InvalidIbcTimeout { min: u64, max: u64 },
+
+    #[error("Pools per packet must be greater than zero")]
+    InvalidPoolsPerPacket {},
+
+    #[error("Emissions to outpost {0} are not paused")]
+    OutpostEmissionsNotPaused(String),
+
+    #[error("Voting power snapshot {snapshot_time} doesn't match proposal {proposal_id} snapshot {expected}")]
+    InvalidVotingPowerSnapshot {
+        proposal_id: u64,
+        snapshot_time: u64,
+        expected: u64,
+    },
+
+    #[error("Voting power snapshot time is missing for proposal {0}")]
+    MissingVotingPowerSnapshot(u64),
+
+    #[error("Proposal deposit must be at least {0}")]
+    InsufficientProposalDeposit(Coin),
+
+    #[error("Hub can't receive {0} message")]
+    InvalidOutpostPacket(String),
+
+    #[error("Outpost submission {channel}/{sequence} not found")]
+    OutpostSubmissionNotFound { channel: String, sequence: u64 },
+
+    #[error("Outpost submission {channel}/{sequence} doesn't await the deposit")]
+    OutpostSubmissionNotAwaitingDeposit { channel: String, sequence: u64 },
+
+    #[error("Proposal deposit must be exactly {0}")]
+    ProposalDepositMismatch(Coin),
+
+    #[error("Deposit of outpost submission {channel}/{sequence} can't be refunded yet or its refund is in progress")]
+    OutpostDepositNotRefundable { channel: String, sequence: u64 },
+
+    #[error("Proposal {0} is still active")]
+    ProposalNotConcluded(u64),
+}
+
+impl ContractError {
+    /// Error code sent to outposts in IBC error acknowledgements
+    pub fn ack_code(&self) -> AckErrorCode {
+        match self {
+            ContractError::VoteCooldown(_) => AckErrorCode::VoteCooldown,
+            ContractError::JailedOutpost { .. } => AckErrorCode::JailedOutpost,
+            ContractError::ActionNotPermitted { .. } => AckErrorCode::ActionNotPermitted,
+            ContractError::PoolIsNotWhitelisted(_)
+            | ContractError::PoolIsBlacklisted(_)
+            | ContractError::InvalidTotalWeight {}
+            | ContractError::DuplicatedVotes {}
+            | ContractError::PoolWeightExceedsLimit { .. }
+            | ContractError::DuplicateVote { .. } => AckErrorCode::InvalidVotes,
+            _ => AckErrorCode::Unknown,
+        }
+    }
 }
@@ -6,35 +6,55 @@ use astroport::incentives;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, ensure, ensure_eq, to_json_binary, wasm_execute, BankMsg, Coin, CosmosMsg, Decimal,
-    DepsMut, Env, Fraction, IbcMsg, IbcTimeout, MessageInfo, Order, Response, StdError, StdResult,
-    Storage, Uint128,
+    attr, coin, coins, ensure, ensure_eq, from_json, to_json_binary, wasm_execute, Addr, BankMsg,
+    CosmosMsg, Decimal, Deps, DepsMut, Env, Event, Fraction, IbcMsg, IbcTimeout, MessageInfo,
+    Order, Response, StdError, StdResult, Storage, SubMsg, SubMsgResult, Uint128,
 };
+use cw_storage_plus::Bound;
 use cw_utils::{must_pay, nonpayable};
 use itertools::Itertools;
 use neutron_sdk::bindings::msg::NeutronMsg;
 use neutron_sdk::bindings::query::NeutronQuery;
+use neutron_sdk::interchain_queries::queries::get_raw_interchain_query_result;
+use neutron_sdk::interchain_queries::v045::register_queries::new_register_wasm_contract_store_query_msg;
 
-use astroport_governance::emissions_controller::consts::{EPOCH_LENGTH, IBC_TIMEOUT};
+use astroport_governance::emissions_controller::consts::{
+    EPOCH_LENGTH, IBC_TIMEOUT_LIMITS, MAX_PAGE_LIMIT, OUTPOST_PERMIT_ALL, POOL_MIGRATION_BATCH,
+    USER_VOTES_HISTORY_EPOCHS, VXASTRO_TOTAL_POWER_KEY,
+};
 use astroport_governance::emissions_controller::hub::{
-    AstroPoolConfig, HubMsg, InputOutpostParams, OutpostInfo, OutpostParams, OutpostStatus,
-    TuneInfo, UserInfo, VotedPoolInfo,
+    AstroPoolConfig, ChunkedDelivery, Config, DeliveryStatus, HubMsg, InputOutpostParams,
+    OutpostExecution, OutpostExecutionStatus, OutpostInfo, OutpostParams, OutpostProposal,
+    OutpostStatus, OutpostSubmission, OutpostSubmissionStatus, PacketRetryStatus, PoolMigration,
+    PoolTvl, ProposalNotification, SupplyCheck, TuneInfo, UpdateConfigMsg, UserEpochVotes,
+    UserInfo, VotedPoolInfo, WhitelistBid,
 };
 use astroport_governance::emissions_controller::msg::{ExecuteMsg, VxAstroIbcMsg};
-use astroport_governance::emissions_controller::utils::{check_lp_token, get_voting_power};
+use astroport_governance::emissions_controller::utils::{
+    check_lp_token, get_total_voting_power, get_voting_power,
+};
 use astroport_governance::utils::{
     check_contract_supports_channel, determine_ics20_escrow_address,
 };
 use astroport_governance::{assembly, voting_escrow};
 
 use crate::error::ContractError;
+use crate::instantiate::{REGISTER_SUPPLY_QUERY_REPLY_ID, SUBMIT_OUTPOST_PROPOSAL_REPLY_ID};
 use crate::state::{
-    get_active_outposts, CONFIG, OUTPOSTS, OWNERSHIP_PROPOSAL, POOLS_BLACKLIST, POOLS_WHITELIST,
-    TUNE_INFO, USER_INFO, VOTED_POOLS,
+    get_active_outposts, get_all_outposts, CHANNEL_MIGRATIONS, CONFIG, LEGACY_ESCROWS, OUTPOSTS,
+    OUTPOST_EXECUTIONS, OUTPOST_EXECUTIONS_COUNT, OUTPOST_FAILURES, OUTPOST_PROPOSALS,
+    OUTPOST_SUBMISSIONS, OWNERSHIP_PROPOSAL, PACKET_RETRIES, PACKET_RETRY_IDS,
+    PENDING_POOL_MIGRATION, PENDING_SUBMISSION, PENDING_SUPPLY_QUERY, POOLS_BLACKLIST,
+    POOLS_WHITELIST, POOL_MIGRATIONS, POOL_TVLS, PROPOSAL_NOTIFICATIONS, PROPOSAL_SUBSCRIBERS,
+    SUPPLY_CHECKS, TUNE_INFO, USER_INFO, USER_VOTES_HISTORY, VOTED_POOLS, VOTER_ORIGINS,
+    VOTE_MANAGERS, WHITELIST_BIDS,
 };
 use crate::utils::{
-    build_emission_ibc_msg, get_epoch_start, get_outpost_prefix, jail_outpost, min_ntrn_ibc_fee,
-    raw_emissions_to_schedules, simulate_tune, validate_outpost_prefix, TuneResult,
+    apply_pending_migration, build_deposit_refund_msg, build_emission_ibc_msgs, get_epoch_start,
+    get_outpost_prefix, hub_packet_type, is_whitelist_full, jail_outpost, migrate_user_votes,
+    min_ntrn_ibc_fee, pool_eligible_from, raw_emissions_to_schedules, record_packets,
+    simulate_tune, track_outpost_failures, validate_outpost_prefix, validate_votes, TuneResult,
+    ICS20_DEPOSIT_REFUND_PACKET, ICS20_EMISSIONS_PACKET,
 };
 
 /// Exposes all the execute functions available in the contract.
@@ -46,19 +66,45 @@ pub fn execute(
     msg: ExecuteMsg<HubMsg>,
 ) -> Result<Response<NeutronMsg>, ContractError> {
     match msg {
-        ExecuteMsg::Vote { votes } => {
+        ExecuteMsg::Vote {
+            votes,
+            on_behalf_of,
+        } => {
             nonpayable(&info)?;
             let votes_map: HashMap<_, _> = votes.iter().cloned().collect();
             ensure!(
                 votes.len() == votes_map.len(),
                 ContractError::DuplicatedVotes {}
             );
+
+            let (voter, voted_by) = match on_behalf_of {
+                Some(user) => {
+                    let user = deps.api.addr_validate(&user)?;
+                    let manager = VOTE_MANAGERS.may_load(deps.storage, user.as_str())?;
+                    ensure!(
+                        manager.as_ref() == Some(&info.sender),
+                        ContractError::Unauthorized {}
+                    );
+                    (user, manager)
+                }
+                None => (info.sender, None),
+            };
+
             let deps = deps.into_empty();
             let config = CONFIG.load(deps.storage)?;
-            let voting_power = get_voting_power(deps.querier, &config.vxastro, &info.sender, None)?;
+            let voting_power = get_voting_power(deps.querier, &config.vxastro, &voter, None)?;
             ensure!(!voting_power.is_zero(), ContractError::ZeroVotingPower {});
+            let total_voting_power = get_total_voting_power(deps.querier, &config.vxastro, None)?;
 
-            handle_vote(deps, env, info.sender.as_str(), voting_power, votes_map)
+            handle_vote(
+                deps,
+                env,
+                voter.as_str(),
+                voting_power,
+                total_voting_power,
+                votes_map,
+                voted_by,
+            )
         }
         ExecuteMsg::UpdateUserVotes { user, is_unlock } => {
             let config = CONFIG.load(deps.storage)?;
@@ -70,22 +116,28 @@ pub fn execute(
             let deps = deps.into_empty();
 
             let voting_power = get_voting_power(deps.querier, &config.vxastro, &voter, None)?;
-            handle_update_user(deps.storage, env, voter.as_str(), voting_power).and_then(
-                |response| {
-                    if is_unlock {
-                        let confirm_unlock_msg = wasm_execute(
-                            config.vxastro,
-                            &voting_escrow::ExecuteMsg::ConfirmUnlock {
-                                user: voter.to_string(),
-                            },
-                            vec![],
-                        )?;
-                        Ok(response.add_message(confirm_unlock_msg))
-                    } else {
-                        Ok(response)
-                    }
-                },
+            let total_voting_power = get_total_voting_power(deps.querier, &config.vxastro, None)?;
+            handle_update_user(
+                deps.storage,
+                env,
+                voter.as_str(),
+                voting_power,
+                total_voting_power,
             )
+            .and_then(|response| {
+                if is_unlock {
+                    let confirm_unlock_msg = wasm_execute(
+                        config.vxastro,
+                        &voting_escrow::ExecuteMsg::ConfirmUnlock {
+                            user: voter.to_string(),
+                        },
+                        vec![],
+                    )?;
+                    Ok(response.add_message(confirm_unlock_msg))
+                } else {
+                    Ok(response)
+                }
+            })
         }
         ExecuteMsg::RefreshUserVotes {} => {
             nonpayable(&info)?;
@@ -95,7 +147,18 @@ pub fn execute(
             let voting_power = get_voting_power(deps.querier, &config.vxastro, &info.sender, None)?;
 
             ensure!(!voting_power.is_zero(), ContractError::ZeroVotingPower {});
-            handle_update_user(deps.storage, env, info.sender.as_str(), voting_power)
+            let total_voting_power = get_total_voting_power(deps.querier, &config.vxastro, None)?;
+            handle_update_user(
+                deps.storage,
+                env,
+                info.sender.as_str(),
+                voting_power,
+                total_voting_power,
+            )
+        }
+        ExecuteMsg::RetractVote { pool } => {
+            nonpayable(&info)?;
+            handle_retract_vote(deps.storage, env, info.sender.as_str(), pool)
         }
         ExecuteMsg::ProposeNewOwner {
             new_owner,
@@ -136,14 +199,18 @@ pub fn execute(
         }
         ExecuteMsg::Custom(hub_msg) => match hub_msg {
             HubMsg::WhitelistPool { lp_token: pool } => whitelist_pool(deps, env, info, pool),
+            HubMsg::BidWhitelistSlot { lp_token: pool } => bid_whitelist_slot(deps, info, pool),
             HubMsg::UpdateBlacklist { add, remove } => {
                 update_blacklist(deps, info, env, add, remove)
             }
+            HubMsg::MigratePool { old_lp, new_lp } => migrate_pool(deps, info, env, old_lp, new_lp),
+            HubMsg::MigratePoolVotes { limit } => migrate_pool_votes(deps, info, env, limit),
             HubMsg::UpdateOutpost {
                 prefix,
                 astro_denom,
                 outpost_params,
                 astro_pool_config,
+                permissions,
             } => update_outpost(
                 deps,
                 env,
@@ -152,27 +219,62 @@ pub fn execute(
                 astro_denom,
                 outpost_params,
                 astro_pool_config,
+                permissions,
             ),
             HubMsg::JailOutpost { prefix } => jail_outpost_endpoint(deps, env, info, prefix),
-            HubMsg::UnjailOutpost { prefix } => unjail_outpost(deps, info, prefix),
-            HubMsg::TunePools {} => tune_pools(deps, env),
-            HubMsg::RetryFailedOutposts {} => retry_failed_outposts(deps, info, env),
-            HubMsg::UpdateConfig {
-                pools_per_outpost,
-                whitelisting_fee,
-                fee_receiver,
-                emissions_multiple,
-                max_astro,
-            } => update_config(
+            HubMsg::MigrateOutpostChannel {
+                prefix,
+                new_voting_channel,
+                new_ics20_channel,
+            } => migrate_outpost_channel(
                 deps,
+                env,
                 info,
-                pools_per_outpost,
-                whitelisting_fee,
-                fee_receiver,
-                emissions_multiple,
-                max_astro,
+                prefix,
+                new_voting_channel,
+                new_ics20_channel,
             ),
+            HubMsg::UnjailOutpost { prefix } => unjail_outpost(deps, info, prefix),
+            HubMsg::ResumeOutpostEmissions { prefix } => {
+                resume_outpost_emissions(deps, info, prefix)
+            }
+            HubMsg::TunePools {} => tune_pools(deps, env, info),
+            HubMsg::RetryFailedOutposts {} => retry_failed_outposts(deps, info, env),
+            HubMsg::UpdateConfig(update) => update_config(deps, info, *update),
             HubMsg::RegisterProposal { proposal_id } => register_proposal(deps, env, proposal_id),
+            HubMsg::RegisterSupplyQuery {
+                prefix,
+                connection_id,
+                vxastro,
+                update_period,
+            } => register_supply_query(deps, info, prefix, connection_id, vxastro, update_period),
+            HubMsg::RemoveSupplyQuery { prefix } => remove_supply_query(deps, info, prefix),
+            HubMsg::ReconcileOutpostSupply { prefix } => {
+                nonpayable(&info)?;
+                reconcile_outpost_supply(deps, env, prefix)
+            }
+            HubMsg::ExecuteOnOutpost {
+                outpost_prefix,
+                msgs,
+            } => execute_on_outpost(deps, env, info, outpost_prefix, msgs),
+            HubMsg::SetVoteManager { manager } => set_vote_manager(deps, info, manager),
+            HubMsg::SetProposalSubscription { prefix, enabled } => {
+                set_proposal_subscription(deps, info, prefix, enabled)
+            }
+            HubMsg::NotifyProposalStatus { proposal_id } => {
+                notify_proposal_status(deps, env, proposal_id)
+            }
+            HubMsg::ReportPoolTvls { tvls } => report_pool_tvls(deps, env, info, tvls),
+            HubMsg::FundOutpostProposal { channel, sequence } => {
+                fund_outpost_proposal(deps, env, info, channel, sequence)
+            }
+            HubMsg::RefundOutpostDeposit { channel, sequence } => {
+                refund_outpost_deposit(deps, env, info, channel, sequence)
+            }
+            HubMsg::ClearVoterOrigins { proposal_id, limit } => {
+                clear_voter_origins(deps, proposal_id, limit)
+            }
+            HubMsg::RetryPacket { id } => retry_packet(deps, env, info, id),
         },
     }
 }
@@ -193,12 +295,204 @@ pub fn whitelist_pool(
         amount == config.whitelisting_fee.amount,
         ContractError::IncorrectWhitelistFee(config.whitelisting_fee)
     );
+    ensure!(
+        !(config.whitelist_auction && is_whitelist_full(deps.storage, &config)?),
+        ContractError::WhitelistFull {}
+    );
+
+    add_pool_to_whitelist(deps, &env, &config, &pool)?;
+
+    let send_fee_msg = BankMsg::Send {
+        to_address: config.fee_receiver.to_string(),
+        amount: info.funds,
+    };
+
+    Ok(Response::default()
+        .add_message(send_fee_msg)
+        .add_attributes([attr("action", "whitelist_pool"), attr("pool", &pool)]))
+}
+
+/// Permissionless endpoint to bid for a whitelist slot.
+/// Available only if the whitelist auction is enabled and the whitelist is full.
+/// Bids are escrowed in the contract until the next tune.
+pub fn bid_whitelist_slot(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    pool: String,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let deps = deps.into_empty();
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        config.whitelist_auction,
+        ContractError::WhitelistAuctionDisabled {}
+    );
+    ensure!(
+        is_whitelist_full(deps.storage, &config)?,
+        ContractError::WhitelistNotFull {}
+    );
+    let amount = must_pay(&info, &config.whitelisting_fee.denom)?;
+
+    validate_whitelist_candidate(deps.as_ref(), &config, &pool)?;
+    ensure!(
+        !POOLS_WHITELIST.load(deps.storage)?.contains(&pool),
+        ContractError::PoolAlreadyWhitelisted(pool)
+    );
+
+    let mut response = Response::default();
+    // The bid must exceed the current bid for the same pool. Outbid bidder is refunded
+    let min_bid = match WHITELIST_BIDS.may_load(deps.storage, &pool)? {
+        Some(prev_bid) => {
+            response = response.add_message(BankMsg::Send {
+                to_address: prev_bid.bidder.to_string(),
+                amount: coins(prev_bid.amount.u128(), &config.whitelisting_fee.denom),
+            });
+            prev_bid.amount + Uint128::one()
+        }
+        None => config.whitelisting_fee.amount,
+    };
+    ensure!(
+        amount >= min_bid,
+        ContractError::BidTooLow(coin(min_bid.u128(), &config.whitelisting_fee.denom))
+    );
+
+    WHITELIST_BIDS.save(
+        deps.storage,
+        &pool,
+        &WhitelistBid {
+            bidder: info.sender.clone(),
+            amount,
+        },
+    )?;
+
+    Ok(response.add_attributes([
+        attr("action", "bid_whitelist_slot"),
+        attr("pool", &pool),
+        attr("bidder", info.sender),
+        attr("amount", amount),
+    ]))
+}
+
+/// Settles pending whitelist bids at the epoch boundary.
+/// Highest bids take free whitelist slots first,
+/// then each of them displaces the lowest voted incumbent which is not in the cooldown period.
+/// Winning bids are sent to the fee receiver, losing bids are refunded.
+fn settle_whitelist_bids(
+    mut deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    epoch_start: u64,
+) -> Result<(Vec<BankMsg>, Vec<Event>), ContractError> {
+    let mut bids = WHITELIST_BIDS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    if bids.is_empty() {
+        return Ok((vec![], vec![]));
+    }
+    WHITELIST_BIDS.clear(deps.storage);
+    bids.sort_by(|(_, a), (_, b)| b.amount.cmp(&a.amount));
+
+    let block_ts = env.block.time.seconds();
+    let whitelist = POOLS_WHITELIST.load(deps.storage)?;
+    let total_pool_limit =
+        config.pools_per_outpost as usize * get_active_outposts(deps.storage)?.len();
+    let mut free_slots = total_pool_limit.saturating_sub(whitelist.len());
+
+    // Incumbents are sorted in descending order of voting power, thus the lowest voted is the last one.
+    // Ties are broken by LP token in lexicographic order as in the tune.
+    let mut incumbents = whitelist
+        .into_iter()
+        .map(|pool| Ok::<_, StdError>((VOTED_POOLS.may_load(deps.storage, &pool)?, pool)))
+        .filter_ok(|(pool_info, _)| {
+            // Pools in the cooldown period can't be displaced
+            !pool_info.as_ref().is_some_and(|info| {
+                config.new_pool_cooldown_epochs > 0
+                    && pool_eligible_from(info.init_ts, config.new_pool_cooldown_epochs)
+                        > epoch_start
+            })
+        })
+        .map_ok(|(pool_info, pool)| {
+            let pool_vp = pool_info.map(|info| info.voting_power).unwrap_or_default();
+            (pool_vp, pool)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    incumbents
+        .sort_by(|(vp_a, pool_a), (vp_b, pool_b)| vp_b.cmp(vp_a).then_with(|| pool_a.cmp(pool_b)));
+
+    let mut messages = vec![];
+    let mut events = vec![];
+    let mut collected = Uint128::zero();
+    for (pool, bid) in bids {
+        let mut event = Event::new("whitelist_bid").add_attributes([
+            attr("pool", &pool),
+            attr("bidder", &bid.bidder),
+            attr("amount", bid.amount),
+        ]);
+
+        // The pool might have been blacklisted or whitelisted since the bid
+        let is_valid = config.whitelist_auction
+            && validate_whitelist_candidate(deps.as_ref(), config, &pool).is_ok()
+            && !POOLS_WHITELIST.load(deps.storage)?.contains(&pool);
+        let slot = if !is_valid {
+            None
+        } else if free_slots > 0 {
+            free_slots -= 1;
+            Some(None)
+        } else {
+            incumbents.pop().map(|(_, displaced)| Some(displaced))
+        };
+
+        match slot {
+            Some(displaced) => {
+                if let Some(displaced) = displaced {
+                    POOLS_WHITELIST.update::<_, StdError>(deps.storage, |mut pools| {
+                        pools.retain(|pool| pool != &displaced);
+                        Ok(pools)
+                    })?;
+                    VOTED_POOLS.remove(deps.storage, &displaced, block_ts)?;
+                    event = event.add_attribute("displaced_pool", displaced);
+                }
+                add_pool_to_whitelist(deps.branch(), env, config, &pool)?;
+                collected += bid.amount;
+                events.push(event.add_attribute("outcome", "won"));
+            }
+            None => {
+                messages.push(BankMsg::Send {
+                    to_address: bid.bidder.to_string(),
+                    amount: coins(bid.amount.u128(), &config.whitelisting_fee.denom),
+                });
+                events.push(event.add_attribute("outcome", "refunded"));
+            }
+        }
+    }
+
+    if !collected.is_zero() {
+        messages.push(BankMsg::Send {
+            to_address: config.fee_receiver.to_string(),
+            amount: coins(collected.u128(), &config.whitelisting_fee.denom),
+        });
+    }
+
+    Ok((messages, events))
+}
+
+/// Checks that the pool can be whitelisted.
+fn validate_whitelist_candidate(
+    deps: Deps,
+    config: &Config,
+    pool: &str,
+) -> Result<(), ContractError> {
+    let pool = pool.to_string();
 
     // Ensure that LP token is not blacklisted
     ensure!(
         !POOLS_BLACKLIST.has(deps.storage, &pool),
         ContractError::PoolIsBlacklisted(pool.clone())
     );
+    // Votes for migrated LP tokens are redirected to the new ones
+    ensure!(
+        !POOL_MIGRATIONS.has(deps.storage, &pool),
+        ContractError::PoolMigrated(pool.clone())
+    );
 
     // Perform basic LP token validation. Ensure the outpost exists.
     let outposts = get_active_outposts(deps.storage)?;
@@ -218,12 +512,26 @@ pub fn whitelist_pool(
             outpost_info
                 .astro_pool_config
                 .as_ref()
-                .map(|conf| conf.astro_pool != pool)
+                .map(|conf| !conf.contains(&pool))
                 .unwrap_or(true)
         }),
         ContractError::IsAstroPool {}
     );
 
+    Ok(())
+}
+
+/// Validates the pool and adds it to the whitelist.
+/// Whitelisting fee must be handled by the caller.
+pub fn add_pool_to_whitelist(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    pool: &str,
+) -> Result<(), ContractError> {
+    validate_whitelist_candidate(deps.as_ref(), config, pool)?;
+    let pool = pool.to_string();
+
     POOLS_WHITELIST.update(deps.storage, |v| {
         let mut pools: HashSet<_> = v.into_iter().collect();
         if !pools.insert(pool.clone()) {
@@ -243,14 +551,7 @@ pub fn whitelist_pool(
         env.block.time.seconds(),
     )?;
 
-    let send_fee_msg = BankMsg::Send {
-        to_address: config.fee_receiver.to_string(),
-        amount: info.funds,
-    };
-
-    Ok(Response::default()
-        .add_message(send_fee_msg)
-        .add_attributes([attr("action", "whitelist_pool"), attr("pool", &pool)]))
+    Ok(())
 }
 
 pub fn update_blacklist(
@@ -310,8 +611,156 @@ pub fn update_blacklist(
     Ok(Response::default().add_attributes(attrs))
 }
 
+/// Permissioned endpoint to move pool whitelist status and votes to a new LP token.
+/// The new LP token must belong to the same outpost.
+/// User votes are moved to the new LP token in batches of [`POOL_MIGRATION_BATCH`] voters.
+/// If there are more voters, the remaining votes must be moved with MigratePoolVotes
+/// before another pool can be migrated.
+pub fn migrate_pool(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    env: Env,
+    old_lp: String,
+    new_lp: String,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    nonpayable(&info)?;
+    let deps = deps.into_empty();
+    let config = CONFIG.load(deps.storage)?;
+
+    ensure!(
+        info.sender == config.owner || info.sender == config.assembly,
+        ContractError::Unauthorized {}
+    );
+    ensure!(
+        !PENDING_POOL_MIGRATION.exists(deps.storage),
+        ContractError::PoolMigrationInProgress {}
+    );
+
+    let mut whitelist = POOLS_WHITELIST.load(deps.storage)?;
+    let old_lp_ind = whitelist
+        .iter()
+        .position(|pool| *pool == old_lp)
+        .ok_or_else(|| ContractError::PoolIsNotWhitelisted(old_lp.clone()))?;
+    ensure!(
+        !whitelist.contains(&new_lp),
+        ContractError::PoolAlreadyWhitelisted(new_lp.clone())
+    );
+    ensure!(
+        !POOLS_BLACKLIST.has(deps.storage, &new_lp),
+        ContractError::PoolIsBlacklisted(new_lp.clone())
+    );
+    ensure!(
+        !POOL_MIGRATIONS.has(deps.storage, &new_lp),
+        ContractError::PoolMigrated(new_lp.clone())
+    );
+
+    // Perform basic LP token validation. Ensure the new LP token belongs to the same outpost.
+    let outposts = get_active_outposts(deps.storage)?;
+    let prefix = get_outpost_prefix(&old_lp, &outposts)
+        .ok_or_else(|| ContractError::NoOutpostForPool(old_lp.clone()))?;
+    validate_outpost_prefix(&new_lp, &prefix)?;
+    if outposts[&prefix].params.is_none() {
+        // Validate LP token on the Hub
+        determine_asset_info(&new_lp, deps.api)
+            .and_then(|maybe_lp| check_lp_token(deps.querier, &config.factory, &maybe_lp))?
+    }
+
+    // Astro pools receive flat emissions hence we don't allow people to vote for them
+    ensure!(
+        outposts.values().all(|outpost_info| {
+            outpost_info
+                .astro_pool_config
+                .as_ref()
+                .map(|conf| !conf.contains(&new_lp))
+                .unwrap_or(true)
+        }),
+        ContractError::IsAstroPool {}
+    );
+
+    whitelist[old_lp_ind] = new_lp.clone();
+    POOLS_WHITELIST.save(deps.storage, &whitelist)?;
+
+    // Whitelisting time is kept, thus users' votes remain applied to the new LP token
+    let block_ts = env.block.time.seconds();
+    let pool_info = VOTED_POOLS.load(deps.storage, &old_lp)?;
+    VOTED_POOLS.remove(deps.storage, &old_lp, block_ts)?;
+    VOTED_POOLS.save(deps.storage, &new_lp, &pool_info, block_ts)?;
+    POOL_MIGRATIONS.save(deps.storage, &old_lp, &new_lp)?;
+
+    let last_voter = migrate_user_votes(
+        deps.storage,
+        block_ts,
+        &old_lp,
+        &new_lp,
+        None,
+        POOL_MIGRATION_BATCH as usize,
+    )?;
+    let completed = last_voter.is_none();
+    if let Some(last_voter) = last_voter {
+        PENDING_POOL_MIGRATION.save(
+            deps.storage,
+            &PoolMigration {
+                old_lp: old_lp.clone(),
+                new_lp: new_lp.clone(),
+                last_voter,
+            },
+        )?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "migrate_pool")
+        .add_event(Event::new("pool_migrated").add_attributes([
+            attr("old_lp", old_lp),
+            attr("new_lp", new_lp),
+            attr("voting_power", pool_info.voting_power),
+            attr("completed", completed.to_string()),
+        ])))
+}
+
+/// Permissionless endpoint to move the remaining user votes of the pool migration in progress.
+/// Processes up to **limit** voters, capped by [`POOL_MIGRATION_BATCH`].
+pub fn migrate_pool_votes(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    nonpayable(&info)?;
+    let mut migration = PENDING_POOL_MIGRATION
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPoolMigrationInProgress {})?;
+    let limit = limit
+        .unwrap_or(POOL_MIGRATION_BATCH)
+        .clamp(1, POOL_MIGRATION_BATCH);
+
+    let last_voter = migrate_user_votes(
+        deps.storage,
+        env.block.time.seconds(),
+        &migration.old_lp,
+        &migration.new_lp,
+        Some(&migration.last_voter),
+        limit as usize,
+    )?;
+    let completed = last_voter.is_none();
+    match last_voter {
+        Some(last_voter) => {
+            migration.last_voter = last_voter;
+            PENDING_POOL_MIGRATION.save(deps.storage, &migration)?;
+        }
+        None => PENDING_POOL_MIGRATION.remove(deps.storage),
+    }
+
+    Ok(Response::default().add_attributes([
+        attr("action", "migrate_pool_votes"),
+        attr("old_lp", migration.old_lp),
+        attr("new_lp", migration.new_lp),
+        attr("completed", completed.to_string()),
+    ]))
+}
+
 /// Permissioned endpoint to add or update outpost.
 /// Performs several simple checks to cut off possible human errors.
+#[allow(clippy::too_many_arguments)]
 pub fn update_outpost(
     deps: DepsMut<NeutronQuery>,
     env: Env,
@@ -320,6 +769,7 @@ pub fn update_outpost(
     astro_denom: String,
     outpost_params: Option<InputOutpostParams>,
     astro_pool_config: Option<AstroPoolConfig>,
+    permissions: Option<u8>,
 ) -> Result<Response<NeutronMsg>, ContractError> {
     nonpayable(&info)?;
     let deps = deps.into_empty();
@@ -328,21 +778,42 @@ pub fn update_outpost(
     ensure!(info.sender == config.owner, ContractError::Unauthorized {});
 
     validate_native_denom(&astro_denom)?;
+    if let Some(permissions) = permissions {
+        ensure!(
+            permissions & !OUTPOST_PERMIT_ALL == 0,
+            ContractError::InvalidOutpostPermissions(permissions)
+        );
+    }
     if let Some(conf) = &astro_pool_config {
-        validate_outpost_prefix(&conf.astro_pool, &prefix)?;
         ensure!(
-            !conf.constant_emissions.is_zero(),
-            ContractError::ZeroAstroEmissions {}
+            conf.emissions_cap != Some(Uint128::zero()),
+            ContractError::ZeroAstroEmissionsCap {}
         );
 
-        // Remove this pool from whitelist
+        let astro_pools = conf.pools();
+        let mut seen = HashSet::new();
+        for (astro_pool, constant_emissions) in &astro_pools {
+            validate_outpost_prefix(astro_pool, &prefix)?;
+            ensure!(
+                !constant_emissions.is_zero(),
+                ContractError::ZeroAstroEmissions {}
+            );
+            ensure!(
+                seen.insert(astro_pool),
+                ContractError::DuplicatedAstroPool(astro_pool.clone())
+            );
+        }
+
+        // Remove these pools from whitelist
         POOLS_WHITELIST.update::<_, StdError>(deps.storage, |mut pools| {
-            pools.retain(|pool| pool != &conf.astro_pool);
+            pools.retain(|pool| !conf.contains(pool));
             Ok(pools)
         })?;
 
         // And remove from votable pools
-        VOTED_POOLS.remove(deps.storage, &conf.astro_pool, env.block.time.seconds())?;
+        for (astro_pool, _) in &astro_pools {
+            VOTED_POOLS.remove(deps.storage, astro_pool, env.block.time.seconds())?;
+        }
     }
 
     if let Some(params) = &outpost_params {
@@ -360,10 +831,25 @@ pub fn update_outpost(
             params.ics20_channel.starts_with("channel-"),
             ContractError::InvalidOutpostIcs20Channel {}
         );
+        if let Some(ibc_timeout) = params.ibc_timeout {
+            ensure!(
+                IBC_TIMEOUT_LIMITS.contains(&ibc_timeout),
+                ContractError::InvalidIbcTimeout {
+                    min: *IBC_TIMEOUT_LIMITS.start(),
+                    max: *IBC_TIMEOUT_LIMITS.end(),
+                }
+            );
+        }
+        ensure!(
+            params.pools_per_packet != Some(0),
+            ContractError::InvalidPoolsPerPacket {}
+        );
     } else {
         if let Some(conf) = &astro_pool_config {
-            let maybe_lp_token = determine_asset_info(&conf.astro_pool, deps.api)?;
-            check_lp_token(deps.querier, &config.factory, &maybe_lp_token)?;
+            for (astro_pool, _) in conf.pools() {
+                let maybe_lp_token = determine_asset_info(&astro_pool, deps.api)?;
+                check_lp_token(deps.querier, &config.factory, &maybe_lp_token)?;
+            }
         }
         ensure!(
             astro_denom == config.astro_denom,
@@ -375,7 +861,7 @@ pub fn update_outpost(
         Some(OutpostInfo { jailed: true, .. }) => Err(ContractError::JailedOutpost {
             prefix: prefix.clone(),
         }),
-        _ => {
+        outpost => {
             let params = outpost_params
                 .map(|params| -> StdResult<_> {
                     Ok(OutpostParams {
@@ -387,6 +873,9 @@ pub fn update_outpost(
                             &params.ics20_channel,
                         )?,
                         ics20_channel: params.ics20_channel,
+                        ibc_timeout: params.ibc_timeout,
+                        relayer_fee: params.relayer_fee,
+                        pools_per_packet: params.pools_per_packet,
                     })
                 })
                 .transpose()?;
@@ -396,6 +885,9 @@ pub fn update_outpost(
                 astro_denom,
                 astro_pool_config,
                 jailed: false,
+                permissions: permissions
+                    .or(outpost.map(|outpost| outpost.permissions))
+                    .unwrap_or(OUTPOST_PERMIT_ALL),
             })
         }
     })?;
@@ -403,6 +895,106 @@ pub fn update_outpost(
     Ok(Response::default().add_attributes([("action", "update_outpost"), ("prefix", &prefix)]))
 }
 
+/// Moves an outpost to new IBC channels.
+/// Old channels are recorded in [`CHANNEL_MIGRATIONS`] so that in-flight packets are still attributed
+/// to the outpost. The old ics20 escrow address is kept in [`LEGACY_ESCROWS`].
+pub fn migrate_outpost_channel(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    prefix: String,
+    new_voting_channel: Option<String>,
+    new_ics20_channel: Option<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    nonpayable(&info)?;
+    let deps = deps.into_empty();
+    let config = CONFIG.load(deps.storage)?;
+
+    ensure!(
+        info.sender == config.owner || info.sender == config.assembly,
+        ContractError::Unauthorized {}
+    );
+    ensure!(
+        new_voting_channel.is_some() || new_ics20_channel.is_some(),
+        ContractError::NoChannelsToMigrate {}
+    );
+
+    let mut outpost = OUTPOSTS.may_load(deps.storage, &prefix)?.ok_or_else(|| {
+        ContractError::OutpostNotFound {
+            prefix: prefix.clone(),
+        }
+    })?;
+    ensure!(
+        !outpost.jailed,
+        ContractError::JailedOutpost {
+            prefix: prefix.clone()
+        }
+    );
+    let mut params = outpost
+        .params
+        .clone()
+        .ok_or_else(|| ContractError::OutpostChannelsNotSet(prefix.clone()))?;
+
+    let used_channels = get_all_outposts(deps.storage)?
+        .into_values()
+        .filter_map(|outpost| outpost.params)
+        .flat_map(|params| [params.voting_channel, params.ics20_channel])
+        .collect::<HashSet<_>>();
+    for channel in new_voting_channel.iter().chain(&new_ics20_channel) {
+        ensure!(
+            !used_channels.contains(channel) && !CHANNEL_MIGRATIONS.has(deps.storage, channel),
+            ContractError::ChannelAlreadyUsed(channel.clone())
+        );
+    }
+
+    let mut attrs = vec![
+        attr("action", "migrate_outpost_channel"),
+        attr("prefix", &prefix),
+    ];
+
+    if let Some(new_voting_channel) = new_voting_channel {
+        check_contract_supports_channel(
+            deps.as_ref().querier,
+            &env.contract.address,
+            &new_voting_channel,
+        )?;
+
+        CHANNEL_MIGRATIONS.save(deps.storage, &params.voting_channel, &new_voting_channel)?;
+        attrs.extend([
+            attr("old_voting_channel", &params.voting_channel),
+            attr("new_voting_channel", &new_voting_channel),
+        ]);
+        params.voting_channel = new_voting_channel;
+    }
+
+    if let Some(new_ics20_channel) = new_ics20_channel {
+        ensure!(
+            new_ics20_channel.starts_with("channel-"),
+            ContractError::InvalidOutpostIcs20Channel {}
+        );
+
+        CHANNEL_MIGRATIONS.save(deps.storage, &params.ics20_channel, &new_ics20_channel)?;
+        let mut legacy_escrows = LEGACY_ESCROWS
+            .may_load(deps.storage, &prefix)?
+            .unwrap_or_default();
+        legacy_escrows.push(params.escrow_address.clone());
+        LEGACY_ESCROWS.save(deps.storage, &prefix, &legacy_escrows)?;
+
+        attrs.extend([
+            attr("old_ics20_channel", &params.ics20_channel),
+            attr("new_ics20_channel", &new_ics20_channel),
+        ]);
+        params.escrow_address =
+            determine_ics20_escrow_address(deps.api, "transfer", &new_ics20_channel)?;
+        params.ics20_channel = new_ics20_channel;
+    }
+
+    outpost.params = Some(params);
+    OUTPOSTS.save(deps.storage, &prefix, &outpost)?;
+
+    Ok(Response::default().add_attributes(attrs))
+}
+
 /// Jails outpost as well as removes all whitelisted
 /// and being voted pools related to this outpost.
 pub fn jail_outpost_endpoint(
@@ -445,6 +1037,28 @@ pub fn unjail_outpost(
     Ok(Response::default().add_attributes([("action", "unjail_outpost"), ("prefix", &prefix)]))
 }
 
+/// Resumes emissions to an outpost paused after consecutive failed deliveries.
+/// The outpost receives emissions starting from the next tune.
+/// Only contract owner can call this function.
+pub fn resume_outpost_emissions(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    prefix: String,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(info.sender == config.owner, ContractError::Unauthorized {});
+
+    OUTPOST_FAILURES
+        .may_load(deps.storage, &prefix)?
+        .filter(|failures| failures.emissions_paused)
+        .ok_or_else(|| ContractError::OutpostEmissionsNotPaused(prefix.clone()))?;
+    OUTPOST_FAILURES.remove(deps.storage, &prefix);
+
+    Ok(Response::default()
+        .add_attributes([("action", "resume_outpost_emissions"), ("prefix", &prefix)]))
+}
+
 /// This permissionless endpoint retries failed emission IBC messages.
 pub fn retry_failed_outposts(
     deps: DepsMut<NeutronQuery>,
@@ -473,20 +1087,47 @@ pub fn retry_failed_outposts(
                         &outpost_info.astro_denom,
                         &config.astro_denom,
                     );
+                    let chunked_delivery = tune_info.outpost_chunk_statuses.get_mut(outpost);
+                    // Chunks must be rebuilt exactly as they were sent during the tune
+                    let pools_per_packet = chunked_delivery
+                        .as_ref()
+                        .map(|delivery| delivery.pools_per_packet);
                     // Ignoring this outpost if it failed to serialize IbcHook msg for some reason
-                    let msg =
-                        build_emission_ibc_msg(&env, params, &ibc_fee, astro_funds, &schedules)
-                            .ok()?;
+                    let msgs = build_emission_ibc_msgs(
+                        &env,
+                        params,
+                        &ibc_fee,
+                        astro_funds,
+                        &schedules,
+                        pools_per_packet,
+                        tune_info.tune_ts,
+                    )
+                    .ok()?;
+
+                    // Only failed packets are resent
+                    let msgs = match chunked_delivery {
+                        Some(delivery) => msgs
+                            .into_iter()
+                            .zip(delivery.statuses.iter_mut())
+                            .filter(|(_, status)| **status == OutpostStatus::Failed)
+                            .map(|(msg, status)| {
+                                *status = OutpostStatus::InProgress;
+                                msg
+                            })
+                            .collect(),
+                        None => msgs,
+                    };
 
                     *status = OutpostStatus::InProgress;
                     attrs.push(attr("outpost", outpost));
 
-                    Some(msg)
+                    Some(msgs)
                 } else {
                     None
                 }
             })
         })
+        .flatten()
         .collect_vec();
 
     ensure!(
@@ -495,6 +1136,12 @@ pub fn retry_failed_outposts(
     );
 
     TUNE_INFO.save(deps.storage, &tune_info, env.block.time.seconds())?;
+    record_packets(
+        deps.storage,
+        "sent",
+        ICS20_EMISSIONS_PACKET,
+        retry_msgs.len(),
+    )?;
 
     Ok(Response::new()
         .add_messages(retry_msgs)
@@ -511,15 +1158,20 @@ pub fn retry_failed_outposts(
 ///
 /// * **voter** is a voter address.
 /// * **voting_power** is a user's voting power reported from the outpost.
+/// * **total_voting_power** is the total vxASTRO supply where the voting power comes from.
+/// It caps the voting power applied to pools if max_voter_share is set.
 /// * **votes** is a map LP token -> percentage of user's voting power to direct to this pool.
 pub fn handle_vote(
     deps: DepsMut,
     env: Env,
     voter: &str,
     voting_power: Uint128,
+    total_voting_power: Uint128,
     votes: HashMap<String, Decimal>,
+    voted_by: Option<Addr>,
 ) -> Result<Response<NeutronMsg>, ContractError> {
-    let user_info = USER_INFO.may_load(deps.storage, voter)?.unwrap_or_default();
+    let mut user_info = USER_INFO.may_load(deps.storage, voter)?.unwrap_or_default();
+    apply_pending_migration(deps.storage, &mut user_info.votes)?;
     let block_ts = env.block.time.seconds();
 
     let epoch_start = get_epoch_start(block_ts);
@@ -529,21 +1181,11 @@ pub fn handle_vote(
         ContractError::VoteCooldown(epoch_start + EPOCH_LENGTH)
     );
 
-    let mut total_weight = Decimal::zero();
-    let whitelist: HashSet<_> = POOLS_WHITELIST.load(deps.storage)?.into_iter().collect();
-    for (pool, weight) in &votes {
-        ensure!(
-            whitelist.contains(pool),
-            ContractError::PoolIsNotWhitelisted(pool.clone())
-        );
-
-        total_weight += weight;
-
-        ensure!(
-            total_weight <= Decimal::one(),
-            ContractError::InvalidTotalWeight {}
-        );
-    }
+    let config = CONFIG.load(deps.storage)?;
+    let whitelist = validate_votes(deps.storage, &config, &votes)?;
+    let raw_voting_power = voting_power;
+    let (voting_power, excess_voting_power) =
+        config.split_voting_power(raw_voting_power, total_voting_power);
 
     // Cancel previous user votes. Filter non-whitelisted pools.
     let mut cache = user_info
@@ -590,6 +1232,23 @@ pub fn handle_vote(
         VOTED_POOLS.save(deps.storage, &pool, &pool_info, block_ts)
     })?;
 
+    record_votes_history(
+        deps.storage,
+        voter,
+        epoch_start,
+        &UserEpochVotes {
+            epoch_start,
+            vote_ts: block_ts,
+            voting_power,
+            votes: votes
+                .iter()
+                .map(|(pool, weight)| (pool.clone(), *weight))
+                .sorted()
+                .collect(),
+            voted_by: voted_by.clone(),
+        },
+    )?;
+
     USER_INFO.save(
         deps.storage,
         voter,
@@ -597,24 +1256,120 @@ pub fn handle_vote(
             vote_ts: block_ts,
             voting_power,
             votes,
+            voted_by: voted_by.clone(),
+            excess_voting_power,
         },
         block_ts,
     )?;
 
-    Ok(Response::default()
-        .add_attributes([attr("action", "vote"), attr("voting_power", voting_power)]))
+    let mut response = Response::default()
+        .add_attributes([attr("action", "vote"), attr("voting_power", voting_power)]);
+    if !excess_voting_power.is_zero() {
+        response = response.add_attributes([
+            attr("raw_voting_power", raw_voting_power),
+            attr("excess_voting_power", excess_voting_power),
+        ]);
+    }
+    if let Some(manager) = voted_by {
+        response = response.add_attributes([attr("voter", voter), attr("vote_manager", manager)]);
+    }
+
+    Ok(response)
+}
+
+/// Removes the user's vote from a single pool.
+/// Voting power dedicated to the pool becomes unallocated.
+/// The vote timestamp is kept, thus the vote cooldown isn't affected.
+pub fn handle_retract_vote(
+    store: &mut dyn Storage,
+    env: Env,
+    voter: &str,
+    pool: String,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let mut user_info = USER_INFO.may_load(store, voter)?.unwrap_or_default();
+    apply_pending_migration(store, &mut user_info.votes)?;
+    let weight = user_info
+        .votes
+        .remove(&pool)
+        .ok_or_else(|| ContractError::NoVoteForPool(pool.clone()))?;
+    let block_ts = env.block.time.seconds();
+
+    // Votes for non-whitelisted pools don't contribute to pool voting power
+    let whitelist = POOLS_WHITELIST.load(store)?;
+    let mut retracted_vp = Uint128::zero();
+    if whitelist.contains(&pool) {
+        let pool_info = VOTED_POOLS.load(store, &pool)?;
+        // Subtract the vote from pool voting power if pool wasn't reset to 0
+        if pool_info.init_ts <= user_info.vote_ts {
+            retracted_vp = user_info
+                .voting_power
+                .multiply_ratio(weight.numerator(), weight.denominator());
+        }
+        VOTED_POOLS.save(store, &pool, &pool_info.with_sub_vp(retracted_vp), block_ts)?;
+    }
+
+    // Keep the history of the current epoch in sync if the user voted in this epoch
+    let epoch_start = get_epoch_start(block_ts);
+    if let Some(mut epoch_votes) = USER_VOTES_HISTORY.may_load(store, (voter, epoch_start))? {
+        epoch_votes
+            .votes
+            .retain(|(voted_pool, _)| voted_pool != &pool);
+        USER_VOTES_HISTORY.save(store, (voter, epoch_start), &epoch_votes)?;
+    }
+
+    USER_INFO.save(store, voter, &user_info, block_ts)?;
+
+    Ok(Response::default().add_attributes([
+        attr("action", "retract_vote"),
+        attr("voter", voter),
+        attr("pool", pool),
+        attr("retracted_voting_power", retracted_vp),
+    ]))
+}
+
+/// Saves the user's votes for the epoch and drops entries older than [`USER_VOTES_HISTORY_EPOCHS`].
+fn record_votes_history(
+    storage: &mut dyn Storage,
+    voter: &str,
+    epoch_start: u64,
+    epoch_votes: &UserEpochVotes,
+) -> StdResult<()> {
+    USER_VOTES_HISTORY.save(storage, (voter, epoch_start), epoch_votes)?;
+
+    let retention_start =
+        epoch_start.saturating_sub((USER_VOTES_HISTORY_EPOCHS - 1) * EPOCH_LENGTH);
+    let outdated: Vec<_> = USER_VOTES_HISTORY
+        .prefix(voter)
+        .keys(
+            storage,
+            None,
+            Some(Bound::exclusive(retention_start)),
+            Order::Ascending,
+        )
+        .collect::<StdResult<_>>()?;
+    for epoch in outdated {
+        USER_VOTES_HISTORY.remove(storage, (voter, epoch));
+    }
+
+    Ok(())
 }
 
 /// This function updates existing user's voting power contribution in pool votes.
 /// Is used to reflect user's vxASTRO balance changes in the emissions controller contract.
+/// The new voting power is capped by max_voter_share of **total_voting_power**.
 pub fn handle_update_user(
     store: &mut dyn Storage,
     env: Env,
     voter: &str,
     new_voting_power: Uint128,
+    total_voting_power: Uint128,
 ) -> Result<Response<NeutronMsg>, ContractError> {
-    if let Some(user_info) = USER_INFO.may_load(store, voter)? {
+    if let Some(mut user_info) = USER_INFO.may_load(store, voter)? {
+        apply_pending_migration(store, &mut user_info.votes)?;
         let block_ts = env.block.time.seconds();
+        let (new_voting_power, excess_voting_power) = CONFIG
+            .load(store)?
+            .split_voting_power(new_voting_power, total_voting_power);
 
         let whitelist: HashSet<_> = POOLS_WHITELIST.load(store)?.into_iter().collect();
         user_info
@@ -644,6 +1399,7 @@ pub fn handle_update_user(
             voter,
             &UserInfo {
                 voting_power: new_voting_power,
+                excess_voting_power,
                 ..user_info
             },
             block_ts,
@@ -654,6 +1410,7 @@ pub fn handle_update_user(
             attr("voter", voter),
             attr("old_voting_power", user_info.voting_power),
             attr("new_voting_power", new_voting_power),
+            attr("excess_voting_power", excess_voting_power),
         ]))
     } else {
         Ok(Response::default())
@@ -670,6 +1427,7 @@ pub fn handle_update_user(
 pub fn tune_pools(
     deps: DepsMut<NeutronQuery>,
     env: Env,
+    info: MessageInfo,
 ) -> Result<Response<NeutronMsg>, ContractError> {
     let tune_info = TUNE_INFO.load(deps.storage)?;
     let block_ts = env.block.time.seconds();
@@ -681,25 +1439,45 @@ pub fn tune_pools(
 
     let config = CONFIG.load(deps.storage)?;
     let ibc_fee = min_ntrn_ibc_fee(deps.as_ref())?;
-    let deps = deps.into_empty();
+    let mut deps = deps.into_empty();
+    let epoch_start = get_epoch_start(block_ts);
+
+    // Outposts which failed to receive emissions for too long are paused before tuning
+    let failure_events = track_outpost_failures(deps.storage, &tune_info, &config)?;
+
+    // Whitelist bids are settled before tuning, thus displaced pools don't receive emissions
+    let (auction_msgs, auction_events) =
+        settle_whitelist_bids(deps.branch(), &env, &config, epoch_start)?;
 
     let voted_pools = VOTED_POOLS
         .keys(deps.storage, None, None, Order::Ascending)
         .collect::<StdResult<HashSet<_>>>()?;
     let outposts = get_active_outposts(deps.storage)?;
-    let epoch_start = get_epoch_start(block_ts);
 
     let TuneResult {
         candidates,
+        cooldown_pools,
         new_emissions_state,
         next_pools_grouped,
-    } = simulate_tune(deps.as_ref(), &voted_pools, &outposts, epoch_start, &config)?;
+        skipped_pools,
+        tvl_weighted,
+        keeper_reward,
+        ..
+    } = simulate_tune(
+        deps.as_ref(),
+        &voted_pools,
+        &outposts,
+        epoch_start,
+        block_ts,
+        &config,
+    )?;
 
     let total_pool_limit = config.pools_per_outpost as usize * outposts.len();
 
     // If candidates list size is more than the total pool number limit,
     // we need to whitelist all candidates
     // and those which have more than the threshold voting power.
+    // Pools in the cooldown period always keep their place in the whitelist.
     // Otherwise, keep the current whitelist.
     if candidates.len() > total_pool_limit {
         let total_vp = candidates
@@ -718,6 +1496,7 @@ pub fn tune_pools(
             })
             .chain(candidates.iter().take(total_pool_limit))
             .map(|(_, (pool, _))| (*pool).clone())
+            .chain(cooldown_pools)
             .collect();
 
         // Remove all non-whitelisted pools
@@ -728,8 +1507,13 @@ pub fn tune_pools(
         POOLS_WHITELIST.save(deps.storage, &new_whitelist.into_iter().collect())?;
     }
 
-    let mut attrs = vec![attr("action", "tune_pools")];
+    let mut attrs = vec![
+        attr("action", "tune_pools"),
+        attr("tvl_weighted", tvl_weighted.to_string()),
+    ];
     let mut outpost_emissions_statuses = HashMap::new();
+    let mut outpost_chunk_statuses = HashMap::new();
+    let mut ics20_packets = 0;
     let setup_pools_msgs = next_pools_grouped
         .iter()
         .map(|(prefix, raw_schedules)| {
@@ -742,12 +1526,34 @@ pub fn tune_pools(
                 &config.astro_denom,
             );
 
-            let msg = if let Some(params) = &outpost_info.params {
+            let msgs = if let Some(params) = &outpost_info.params {
                 outpost_emissions_statuses.insert(prefix.clone(), OutpostStatus::InProgress);
-                build_emission_ibc_msg(&env, params, &ibc_fee, astro_funds, &schedules)?
+                let msgs = build_emission_ibc_msgs(
+                    &env,
+                    params,
+                    &ibc_fee,
+                    astro_funds,
+                    &schedules,
+                    params.pools_per_packet,
+                    epoch_start,
+                )?;
+                ics20_packets += msgs.len();
+                if msgs.len() > 1 {
+                    outpost_chunk_statuses.insert(
+                        prefix.clone(),
+                        ChunkedDelivery {
+                            pools_per_packet: params.pools_per_packet.unwrap_or_default(),
+                            statuses: vec![OutpostStatus::InProgress; msgs.len()],
+                        },
+                    );
+                }
+                msgs
             } else {
                 let incentives_msg = incentives::ExecuteMsg::IncentivizeMany(schedules);
-                wasm_execute(&config.incentives_addr, &incentives_msg, vec![astro_funds])?.into()
+                vec![
+                    wasm_execute(&config.incentives_addr, &incentives_msg, vec![astro_funds])?
+                        .into(),
+                ]
             };
 
             attrs.push(attr("outpost", prefix));
@@ -756,25 +1562,62 @@ pub fn tune_pools(
                 serde_json::to_string(&raw_schedules)
                     .map_err(|err| StdError::generic_err(err.to_string()))?,
             ));
+            if msgs.len() > 1 {
+                attrs.push(attr("packets", msgs.len().to_string()));
+            }
 
-            Ok(msg)
+            Ok(msgs)
         })
+        .flatten_ok()
         .collect::<StdResult<Vec<CosmosMsg<NeutronMsg>>>>()?;
 
+    // Emit an explicit event for each pool excluded from the tune
+    let events = skipped_pools
+        .iter()
+        .map(|skipped| {
+            Event::new("skipped_pool").add_attributes([
+                attr("pool", &skipped.pool),
+                attr("reason", skipped.reason.to_string()),
+            ])
+        })
+        .collect_vec();
+
+    // Reward the keeper who executed tuning out of the epoch emissions
+    let mut response = Response::new();
+    if !keeper_reward.is_zero() {
+        response = response
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(keeper_reward.u128(), &config.astro_denom),
+            })
+            .add_event(Event::new("tune_incentive").add_attributes([
+                attr("keeper", info.sender),
+                attr("amount", keeper_reward.to_string()),
+            ]));
+    }
+
+    record_packets(deps.storage, "sent", ICS20_EMISSIONS_PACKET, ics20_packets)?;
+
     TUNE_INFO.save(
         deps.storage,
         &TuneInfo {
             tune_ts: epoch_start,
             pools_grouped: next_pools_grouped,
             outpost_emissions_statuses,
+            outpost_chunk_statuses,
             emissions_state: new_emissions_state,
+            skipped_pools,
         },
         block_ts,
     )?;
 
-    Ok(Response::new()
+    Ok(response
+        .add_messages(auction_msgs)
         .add_messages(setup_pools_msgs)
-        .add_attributes(attrs))
+        .add_attributes(attrs)
+        .add_events(auction_events)
+        .add_events(failure_events)
+        .add_events(events))
 }
 
 /// Permissioned to the contract owner.
@@ -782,11 +1625,7 @@ pub fn tune_pools(
 pub fn update_config(
     deps: DepsMut<NeutronQuery>,
     info: MessageInfo,
-    pools_limit: Option<u64>,
-    whitelisting_fee: Option<Coin>,
-    fee_receiver: Option<String>,
-    emissions_multiple: Option<Decimal>,
-    max_astro: Option<Uint128>,
+    update: UpdateConfigMsg,
 ) -> Result<Response<NeutronMsg>, ContractError> {
     nonpayable(&info)?;
     let mut config = CONFIG.load(deps.storage)?;
@@ -795,22 +1634,22 @@ pub fn update_config(
 
     let mut attrs = vec![attr("action", "update_config")];
 
-    if let Some(pools_limit) = pools_limit {
+    if let Some(pools_limit) = update.pools_per_outpost {
         attrs.push(attr("new_pools_limit", pools_limit.to_string()));
         config.pools_per_outpost = pools_limit;
     }
 
-    if let Some(whitelisting_fee) = whitelisting_fee {
+    if let Some(whitelisting_fee) = update.whitelisting_fee {
         attrs.push(attr("new_whitelisting_fee", whitelisting_fee.to_string()));
         config.whitelisting_fee = whitelisting_fee;
     }
 
-    if let Some(fee_receiver) = fee_receiver {
+    if let Some(fee_receiver) = update.fee_receiver {
         attrs.push(attr("new_fee_receiver", &fee_receiver));
         config.fee_receiver = deps.api.addr_validate(&fee_receiver)?;
     }
 
-    if let Some(emissions_multiple) = emissions_multiple {
+    if let Some(emissions_multiple) = update.emissions_multiple {
         attrs.push(attr(
             "new_emissions_multiple",
             emissions_multiple.to_string(),
@@ -818,11 +1657,67 @@ pub fn update_config(
         config.emissions_multiple = emissions_multiple;
     }
 
-    if let Some(max_astro) = max_astro {
+    if let Some(max_astro) = update.max_astro {
         attrs.push(attr("new_max_astro", max_astro.to_string()));
         config.max_astro = max_astro;
     }
 
+    if let Some(new_pool_cooldown_epochs) = update.new_pool_cooldown_epochs {
+        attrs.push(attr(
+            "new_pool_cooldown_epochs",
+            new_pool_cooldown_epochs.to_string(),
+        ));
+        config.new_pool_cooldown_epochs = new_pool_cooldown_epochs;
+    }
+
+    if let Some(max_weight_per_pool) = update.max_weight_per_pool {
+        attrs.push(attr(
+            "new_max_weight_per_pool",
+            max_weight_per_pool.to_string(),
+        ));
+        config.max_weight_per_pool = Some(max_weight_per_pool);
+    }
+
+    if let Some(tune_incentive) = update.tune_incentive {
+        attrs.push(attr("new_tune_incentive", tune_incentive.to_string()));
+        config.tune_incentive = tune_incentive;
+    }
+
+    if let Some(tvl_oracle) = update.tvl_oracle {
+        attrs.push(attr("new_tvl_oracle", &tvl_oracle));
+        config.tvl_oracle = Some(deps.api.addr_validate(&tvl_oracle)?);
+    }
+
+    if let Some(tvl_weight) = update.tvl_weight {
+        attrs.push(attr("new_tvl_weight", tvl_weight.to_string()));
+        config.tvl_weight = tvl_weight;
+    }
+
+    if let Some(tvl_max_age) = update.tvl_max_age {
+        attrs.push(attr("new_tvl_max_age", tvl_max_age.to_string()));
+        config.tvl_max_age = tvl_max_age;
+    }
+
+    if let Some(whitelist_auction) = update.whitelist_auction {
+        attrs.push(attr("new_whitelist_auction", whitelist_auction.to_string()));
+        config.whitelist_auction = whitelist_auction;
+    }
+
+    if let Some(min_pool_emissions) = update.min_pool_emissions {
+        attrs.push(attr("new_min_pool_emissions", min_pool_emissions));
+        config.min_pool_emissions = min_pool_emissions;
+    }
+
+    if let Some(max_failed_epochs) = update.max_failed_epochs {
+        attrs.push(attr("new_max_failed_epochs", max_failed_epochs.to_string()));
+        config.max_failed_epochs = max_failed_epochs;
+    }
+
+    if let Some(max_voter_share) = update.max_voter_share {
+        attrs.push(attr("new_max_voter_share", max_voter_share.to_string()));
+        config.max_voter_share = Some(max_voter_share);
+    }
+
     config.validate()?;
 
     CONFIG.save(deps.storage, &config)?;
@@ -860,8 +1755,6 @@ pub fn register_proposal(
         proposal_id,
         start_time: proposal.start_time,
     })?;
-    let timeout = IbcTimeout::from(env.block.time.plus_seconds(IBC_TIMEOUT));
-
     let mut attrs = vec![("action", "register_proposal")];
 
     let ibc_messages: Vec<CosmosMsg<NeutronMsg>> = outposts
@@ -872,14 +1765,836 @@ pub fn register_proposal(
                 IbcMsg::SendPacket {
                     channel_id: params.voting_channel.clone(),
                     data: data.clone(),
-                    timeout: timeout.clone(),
+                    timeout: IbcTimeout::from(env.block.time.plus_seconds(params.packet_timeout())),
                 }
                 .into()
             })
         })
         .collect();
+    record_packets(
+        deps.storage,
+        "sent",
+        "register_proposal",
+        ibc_messages.len(),
+    )?;
 
     Ok(Response::new()
         .add_messages(ibc_messages)
         .add_attributes(attrs))
 }
+
+/// Registers an interchain query which reads the total voting power from the outpost vxASTRO state.
+/// Query id is saved in the reply.
+/// Only contract owner can call this function.
+pub fn register_supply_query(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    prefix: String,
+    connection_id: String,
+    vxastro: String,
+    update_period: u64,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(info.sender == config.owner, ContractError::Unauthorized {});
+
+    // Only remote outposts report voting power
+    OUTPOSTS
+        .may_load(deps.storage, &prefix)?
+        .and_then(|outpost| outpost.params)
+        .ok_or_else(|| ContractError::OutpostNotFound {
+            prefix: prefix.clone(),
+        })?;
+    validate_outpost_prefix(&vxastro, &prefix)?;
+    ensure!(
+        !SUPPLY_CHECKS.has(deps.storage, &prefix),
+        ContractError::SupplyQueryExists(prefix)
+    );
+
+    let register_msg = new_register_wasm_contract_store_query_msg(
+        connection_id.clone(),
+        vxastro.clone(),
+        VXASTRO_TOTAL_POWER_KEY,
+        update_period,
+    )?;
+
+    SUPPLY_CHECKS.save(
+        deps.storage,
+        &prefix,
+        &SupplyCheck {
+            query_id: 0,
+            vxastro: vxastro.clone(),
+            reported_supply: Uint128::zero(),
+            reported_at: 0,
+            verified_supply: Uint128::zero(),
+            verified_height: 0,
+            mismatch: false,
+        },
+    )?;
+    PENDING_SUPPLY_QUERY.save(deps.storage, &prefix)?;
+
+    Ok(Response::default()
+        .add_submessage(SubMsg::reply_on_success(
+            register_msg,
+            REGISTER_SUPPLY_QUERY_REPLY_ID,
+        ))
+        .add_attributes([
+            attr("action", "register_supply_query"),
+            attr("prefix", prefix),
+            attr("connection_id", connection_id),
+            attr("vxastro", vxastro),
+        ]))
+}
+
+/// Removes the supply query of an outpost along with its verification state.
+/// Only contract owner can call this function.
+pub fn remove_supply_query(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    prefix: String,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(info.sender == config.owner, ContractError::Unauthorized {});
+
+    let supply_check = SUPPLY_CHECKS
+        .may_load(deps.storage, &prefix)?
+        .ok_or_else(|| ContractError::SupplyQueryNotFound(prefix.clone()))?;
+    SUPPLY_CHECKS.remove(deps.storage, &prefix);
+
+    Ok(Response::default()
+        .add_message(NeutronMsg::remove_interchain_query(supply_check.query_id))
+        .add_attributes([
+            attr("action", "remove_supply_query"),
+            attr("prefix", prefix),
+            attr("query_id", supply_check.query_id.to_string()),
+        ]))
+}
+
+/// Compares the latest total voting power reported by the outpost with the latest interchain
+/// query result. The outpost is flagged if the reported voting power exceeds the verified one.
+/// Flagging doesn't jail the outpost; the decision is left to the contract owner.
+pub fn reconcile_outpost_supply(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    prefix: String,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let mut supply_check = SUPPLY_CHECKS
+        .may_load(deps.storage, &prefix)?
+        .filter(|supply_check| supply_check.query_id != 0)
+        .ok_or_else(|| ContractError::SupplyQueryNotFound(prefix.clone()))?;
+
+    let query_result = get_raw_interchain_query_result(deps.as_ref(), supply_check.query_id)?;
+    ensure!(
+        query_result.result.height > supply_check.verified_height,
+        ContractError::NoNewSupplyResult(prefix)
+    );
+
+    // Empty value means vxASTRO has never been locked on the outpost
+    supply_check.verified_supply = match query_result.result.kv_results.first() {
+        Some(kv) if !kv.value.is_empty() => from_json(&kv.value)?,
+        _ => Uint128::zero(),
+    };
+    supply_check.verified_height = query_result.result.height;
+    supply_check.mismatch = supply_check.reported_supply > supply_check.verified_supply;
+    SUPPLY_CHECKS.save(deps.storage, &prefix, &supply_check)?;
+
+    let mut response = Response::default().add_attributes([
+        attr("action", "reconcile_outpost_supply"),
+        attr("prefix", &prefix),
+        attr("reported_supply", supply_check.reported_supply),
+        attr("verified_supply", supply_check.verified_supply),
+        attr("verified_height", supply_check.verified_height.to_string()),
+    ]);
+    if supply_check.mismatch {
+        response = response.add_event(
+            Event::new("outpost_supply_mismatch")
+                .add_attribute("prefix", &prefix)
+                .add_attribute("reported_supply", supply_check.reported_supply)
+                .add_attribute("verified_supply", supply_check.verified_supply)
+                .add_attribute("time", env.block.time.seconds().to_string()),
+        );
+    }
+
+    Ok(response)
+}
+
+/// Authorizes a vote manager who can cast emissions votes on behalf of the sender.
+/// Passing None revokes the current vote manager.
+pub fn set_vote_manager(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    manager: Option<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    nonpayable(&info)?;
+
+    let manager = match manager {
+        Some(manager) => {
+            let manager = deps.api.addr_validate(&manager)?;
+            ensure!(manager != info.sender, ContractError::InvalidVoteManager {});
+            VOTE_MANAGERS.save(deps.storage, info.sender.as_str(), &manager)?;
+            manager.to_string()
+        }
+        None => {
+            VOTE_MANAGERS.remove(deps.storage, info.sender.as_str());
+            "none".to_string()
+        }
+    };
+
+    Ok(Response::default().add_attributes([
+        attr("action", "set_vote_manager"),
+        attr("user", info.sender),
+        attr("manager", manager),
+    ]))
+}
+
+/// Forwards messages approved by the Hub governance to an outpost.
+/// The outpost executes them only if they come from the Hub voting channel.
+/// Only Assembly can call this function.
+pub fn execute_on_outpost(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    prefix: String,
+    msgs: Vec<CosmosMsg>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        info.sender == config.assembly,
+        ContractError::Unauthorized {}
+    );
+    ensure!(
+        !msgs.is_empty(),
+        StdError::generic_err("Messages list can't be empty")
+    );
+
+    let params = OUTPOSTS
+        .may_load(deps.storage, &prefix)?
+        .and_then(|outpost| outpost.params)
+        .ok_or_else(|| ContractError::OutpostNotFound {
+            prefix: prefix.clone(),
+        })?;
+
+    let execution_id = OUTPOST_EXECUTIONS_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    OUTPOST_EXECUTIONS_COUNT.save(deps.storage, &execution_id)?;
+
+    let timeout = IbcTimeout::from(env.block.time.plus_seconds(params.packet_timeout()));
+    let ibc_msg = IbcMsg::SendPacket {
+        channel_id: params.voting_channel,
+        data: to_json_binary(&VxAstroIbcMsg::ExecuteMsgs {
+            execution_id,
+            msgs: msgs.clone(),
+        })?,
+        timeout,
+    };
+
+    OUTPOST_EXECUTIONS.save(
+        deps.storage,
+        execution_id,
+        &OutpostExecution {
+            prefix: prefix.clone(),
+            msgs,
+            status: OutpostExecutionStatus::Pending,
+            created_at: env.block.time.seconds(),
+        },
+    )?;
+    record_packets(deps.storage, "sent", "execute_msgs", 1)?;
+
+    Ok(Response::default().add_message(ibc_msg).add_attributes([
+        attr("action", "execute_on_outpost"),
+        attr("outpost", prefix),
+        attr("execution_id", execution_id.to_string()),
+    ]))
+}
+
+/// Subscribes or unsubscribes an outpost from proposal lifecycle notifications.
+/// Only contract owner can call this function.
+pub fn set_proposal_subscription(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    prefix: String,
+    enabled: bool,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(info.sender == config.owner, ContractError::Unauthorized {});
+
+    if enabled {
+        // Only remote outposts can receive notifications
+        OUTPOSTS
+            .may_load(deps.storage, &prefix)?
+            .and_then(|outpost| outpost.params)
+            .ok_or_else(|| ContractError::OutpostNotFound {
+                prefix: prefix.clone(),
+            })?;
+        PROPOSAL_SUBSCRIBERS.save(deps.storage, &prefix, &())?;
+    } else {
+        PROPOSAL_SUBSCRIBERS.remove(deps.storage, &prefix);
+    }
+
+    Ok(Response::default().add_attributes([
+        attr("action", "set_proposal_subscription"),
+        attr("outpost", prefix),
+        attr("enabled", enabled.to_string()),
+    ]))
+}
+
+/// Sends the current proposal status to all subscribed active outposts.
+/// Outposts which have already been notified about the current status are skipped
+/// unless the previous delivery failed.
+/// Once a proposal submitted from an active outpost is concluded, its deposit refund is sent as well.
+pub fn notify_proposal_status(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    proposal_id: u64,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let proposal = deps.querier.query_wasm_smart::<assembly::Proposal>(
+        &config.assembly,
+        &assembly::QueryMsg::Proposal { proposal_id },
+    )?;
+
+    let outposts = get_active_outposts(deps.storage)?;
+    let subscribers = PROPOSAL_SUBSCRIBERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let data = to_json_binary(&VxAstroIbcMsg::ProposalStatusUpdate {
+        proposal_id,
+        status: proposal.status.clone(),
+    })?;
+    let mut attrs = vec![
+        attr("action", "notify_proposal_status"),
+        attr("proposal_id", proposal_id.to_string()),
+        attr("status", proposal.status.to_string()),
+    ];
+    let mut ibc_messages: Vec<CosmosMsg<NeutronMsg>> = vec![];
+
+    let subscribed_outposts = subscribers.iter().filter_map(|prefix| {
+        outposts
+            .get(prefix)
+            .and_then(|outpost| outpost.params.as_ref())
+            .map(|params| (prefix, params))
+    });
+
+    for (prefix, params) in subscribed_outposts {
+        let already_notified = PROPOSAL_NOTIFICATIONS
+            .may_load(deps.storage, (proposal_id, prefix.as_str()))?
+            .map(|notification| {
+                notification.status == proposal.status
+                    && !matches!(notification.delivery, DeliveryStatus::Failed { .. })
+            })
+            .unwrap_or(false);
+        if already_notified {
+            continue;
+        }
+
+        PROPOSAL_NOTIFICATIONS.save(
+            deps.storage,
+            (proposal_id, prefix.as_str()),
+            &ProposalNotification {
+                status: proposal.status.clone(),
+                delivery: DeliveryStatus::Pending,
+            },
+        )?;
+
+        ibc_messages.push(
+            IbcMsg::SendPacket {
+                channel_id: params.voting_channel.clone(),
+                data: data.clone(),
+                timeout: IbcTimeout::from(env.block.time.plus_seconds(params.packet_timeout())),
+            }
+            .into(),
+        );
+        attrs.push(attr("outpost", prefix));
+    }
+    record_packets(
+        deps.storage,
+        "sent",
+        "proposal_status_update",
+        ibc_messages.len(),
+    )?;
+
+    if proposal.status != assembly::ProposalStatus::Active {
+        let cleared = remove_voter_origins(deps.storage, proposal_id, MAX_PAGE_LIMIT)?;
+        if cleared > 0 {
+            attrs.push(attr("cleared_voter_origins", cleared.to_string()));
+        }
+
+        // Assembly has refunded the deposit of a proposal submitted from an active outpost
+        let refunded = proposal.refunded_deposit();
+        let outpost_proposal = OUTPOST_PROPOSALS
+            .may_load(deps.storage, proposal_id)?
+            .filter(|outpost_proposal| {
+                !refunded.is_zero()
+                    && outposts
+                        .get(&outpost_proposal.prefix)
+                        .is_some_and(|outpost| outpost.params.is_some())
+            });
+        if let Some(outpost_proposal) = outpost_proposal {
+            let key = (outpost_proposal.channel.as_str(), outpost_proposal.sequence);
+            let submission = OUTPOST_SUBMISSIONS.load(deps.storage, key)?;
+            if submission.refund.is_none() {
+                attrs.push(attr("refund_deposit", &outpost_proposal.prefix));
+                ibc_messages.push(deposit_refund_msg(
+                    deps,
+                    &env,
+                    &outpost_proposal.channel,
+                    outpost_proposal.sequence,
+                    submission,
+                    refunded,
+                )?);
+            }
+        }
+    }
+
+    Ok(Response::default()
+        .add_messages(ibc_messages)
+        .add_attributes(attrs))
+}
+
+/// Permissionless endpoint which removes outpost vote origins of a concluded proposal.
+pub fn clear_voter_origins(
+    deps: DepsMut<NeutronQuery>,
+    proposal_id: u64,
+    limit: Option<u8>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let proposal = deps.querier.query_wasm_smart::<assembly::Proposal>(
+        &config.assembly,
+        &assembly::QueryMsg::Proposal { proposal_id },
+    )?;
+    ensure!(
+        proposal.status != assembly::ProposalStatus::Active,
+        ContractError::ProposalNotConcluded(proposal_id)
+    );
+
+    let cleared = remove_voter_origins(deps.storage, proposal_id, limit.unwrap_or(MAX_PAGE_LIMIT))?;
+
+    Ok(Response::default().add_attributes([
+        attr("action", "clear_voter_origins"),
+        attr("proposal_id", proposal_id.to_string()),
+        attr("cleared", cleared.to_string()),
+    ]))
+}
+
+/// Removes up to `limit` outpost vote origins of the proposal. Returns the number of removed entries.
+fn remove_voter_origins(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    limit: u8,
+) -> StdResult<usize> {
+    let voters = VOTER_ORIGINS
+        .prefix(proposal_id)
+        .keys(storage, None, None, Order::Ascending)
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+    for voter in &voters {
+        VOTER_ORIGINS.remove(storage, (proposal_id, voter));
+    }
+
+    Ok(voters.len())
+}
+
+/// Escrows the deposit of an outpost submission and submits the proposal to the Assembly.
+/// This endpoint is meant to be executed by the outpost via IBC hook of the ics20 transfer
+/// carrying the deposit. If it fails, the transfer bounces back to the outpost.
+pub fn fund_outpost_proposal(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    channel: String,
+    sequence: u64,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let submission = OUTPOST_SUBMISSIONS
+        .may_load(deps.storage, (&channel, sequence))?
+        .ok_or_else(|| ContractError::OutpostSubmissionNotFound {
+            channel: channel.clone(),
+            sequence,
+        })?;
+    ensure!(
+        submission.status == OutpostSubmissionStatus::AwaitingDeposit
+            && env.block.time.seconds() < submission.deadline,
+        ContractError::OutpostSubmissionNotAwaitingDeposit {
+            channel: channel.clone(),
+            sequence,
+        }
+    );
+    let amount = must_pay(&info, &config.xastro_denom)?;
+    ensure!(
+        amount == submission.deposit,
+        ContractError::ProposalDepositMismatch(coin(
+            submission.deposit.u128(),
+            &config.xastro_denom
+        ))
+    );
+
+    PENDING_SUBMISSION.save(deps.storage, &(channel.clone(), sequence))?;
+
+    let proposal = submission.proposal;
+    let submit_msg = wasm_execute(
+        config.assembly,
+        &assembly::ExecuteMsg::SubmitProposal {
+            title: proposal.title,
+            description: proposal.description,
+            link: proposal.link,
+            messages: proposal.messages,
+            ibc_channel: proposal.ibc_channel,
+            commit_reveal: proposal.commit_reveal,
+            tags: proposal.tags,
+        },
+        info.funds,
+    )?;
+
+    Ok(Response::default()
+        .add_submessage(SubMsg::reply_always(
+            submit_msg,
+            SUBMIT_OUTPOST_PROPOSAL_REPLY_ID,
+        ))
+        .add_attributes([
+            attr("action", "fund_outpost_proposal"),
+            attr("channel", channel),
+            attr("sequence", sequence.to_string()),
+            attr("submitter", submission.submitter),
+        ]))
+}
+
+/// Saves the outcome of the Assembly submission.
+/// The id of the submitted proposal is taken from the Assembly response.
+/// If the Assembly rejects the proposal, the escrowed deposit is refunded to the submitter over ics20.
+pub fn process_outpost_submission_reply(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    result: SubMsgResult,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let (channel, sequence) = PENDING_SUBMISSION.load(deps.storage)?;
+    PENDING_SUBMISSION.remove(deps.storage);
+    let mut submission = OUTPOST_SUBMISSIONS.load(deps.storage, (&channel, sequence))?;
+
+    match result {
+        SubMsgResult::Ok(response) => {
+            let config = CONFIG.load(deps.storage)?;
+            let proposal_id = response
+                .events
+                .iter()
+                .filter(|event| {
+                    event.ty == "wasm"
+                        && event.attributes.iter().any(|attr| {
+                            attr.key == "_contract_address" && attr.value == config.assembly
+                        })
+                })
+                .flat_map(|event| &event.attributes)
+                .find(|attr| attr.key == "proposal_id")
+                .ok_or(ContractError::FailedToParseReply {})?
+                .value
+                .parse::<u64>()
+                .map_err(|_| ContractError::FailedToParseReply {})?;
+
+            submission.status = OutpostSubmissionStatus::Submitted { proposal_id };
+            OUTPOST_SUBMISSIONS.save(deps.storage, (&channel, sequence), &submission)?;
+            OUTPOST_PROPOSALS.save(
+                deps.storage,
+                proposal_id,
+                &OutpostProposal {
+                    prefix: submission.prefix.clone(),
+                    channel: channel.clone(),
+                    sequence,
+                    submitter: submission.submitter,
+                    deposit: submission.deposit,
+                },
+            )?;
+
+            Ok(Response::default().add_attributes([
+                attr("action", "submit_outpost_proposal"),
+                attr("proposal_id", proposal_id.to_string()),
+                attr("outpost", submission.prefix),
+            ]))
+        }
+        SubMsgResult::Err(reason) => {
+            submission.status = OutpostSubmissionStatus::Rejected {
+                reason: reason.clone(),
+            };
+            let deposit = submission.deposit;
+            let refund_msg =
+                deposit_refund_msg(deps, &env, &channel, sequence, submission, deposit)?;
+
+            Ok(Response::default().add_message(refund_msg).add_attributes([
+                attr("action", "reject_outpost_proposal"),
+                attr("channel", channel),
+                attr("sequence", sequence.to_string()),
+                attr("reason", reason),
+            ]))
+        }
+    }
+}
+
+/// Refunds the deposit of an outpost submission.
+/// Deposits of submitted and rejected proposals are refunded over ics20 once the Assembly has refunded them.
+/// If the deposit transfer hasn't reached the Hub before the deadline,
+/// the outpost is told to return the deposit bounced back to it.
+/// Refunds are sent automatically, failed refunds can be retried here.
+pub fn refund_outpost_deposit(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    channel: String,
+    sequence: u64,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    nonpayable(&info)?;
+    let mut submission = OUTPOST_SUBMISSIONS
+        .may_load(deps.storage, (&channel, sequence))?
+        .ok_or_else(|| ContractError::OutpostSubmissionNotFound {
+            channel: channel.clone(),
+            sequence,
+        })?;
+    let not_refundable = || ContractError::OutpostDepositNotRefundable {
+        channel: channel.clone(),
+        sequence,
+    };
+    ensure!(submission.can_refund(), not_refundable());
+
+    let refund_msg = match submission.status.clone() {
+        OutpostSubmissionStatus::AwaitingDeposit | OutpostSubmissionStatus::Expired => {
+            ensure!(
+                env.block.time.seconds() >= submission.deadline,
+                not_refundable()
+            );
+            submission.status = OutpostSubmissionStatus::Expired;
+            outpost_refund_msg(deps.storage, &env, &channel, sequence, submission)?
+        }
+        OutpostSubmissionStatus::Rejected { .. } => {
+            let deposit = submission.deposit;
+            deposit_refund_msg(deps, &env, &channel, sequence, submission, deposit)?
+        }
+        OutpostSubmissionStatus::Submitted { proposal_id } => {
+            let config = CONFIG.load(deps.storage)?;
+            let proposal = deps.querier.query_wasm_smart::<assembly::Proposal>(
+                &config.assembly,
+                &assembly::QueryMsg::Proposal { proposal_id },
+            )?;
+            let refunded = proposal.refunded_deposit();
+            ensure!(!refunded.is_zero(), not_refundable());
+            deposit_refund_msg(deps, &env, &channel, sequence, submission, refunded)?
+        }
+    };
+
+    Ok(Response::default().add_message(refund_msg).add_attributes([
+        attr("action", "refund_outpost_deposit"),
+        attr("channel", channel),
+        attr("sequence", sequence.to_string()),
+    ]))
+}
+
+/// Marks the deposit refund as pending and prepares the ics20 transfer to the outpost submitter.
+fn deposit_refund_msg(
+    deps: DepsMut<NeutronQuery>,
+    env: &Env,
+    channel: &str,
+    sequence: u64,
+    mut submission: OutpostSubmission,
+    amount: Uint128,
+) -> Result<CosmosMsg<NeutronMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let params = OUTPOSTS
+        .may_load(deps.storage, &submission.prefix)?
+        .and_then(|outpost| outpost.params)
+        .ok_or_else(|| ContractError::OutpostNotFound {
+            prefix: submission.prefix.clone(),
+        })?;
+    let ibc_fee = min_ntrn_ibc_fee(deps.as_ref())?;
+
+    let msg = build_deposit_refund_msg(
+        env,
+        &params,
+        &ibc_fee,
+        coin(amount.u128(), config.xastro_denom),
+        &submission.submitter,
+        channel,
+        sequence,
+    )?;
+
+    submission.refund = Some(DeliveryStatus::Pending);
+    OUTPOST_SUBMISSIONS.save(deps.storage, (channel, sequence), &submission)?;
+    record_packets(deps.storage, "sent", ICS20_DEPOSIT_REFUND_PACKET, 1)?;
+
+    Ok(msg)
+}
+
+/// Marks the deposit refund as pending and prepares the IBC packet
+/// which tells the outpost to return the deposit whose transfer hasn't reached the Hub.
+fn outpost_refund_msg(
+    storage: &mut dyn Storage,
+    env: &Env,
+    channel: &str,
+    sequence: u64,
+    mut submission: OutpostSubmission,
+) -> Result<CosmosMsg<NeutronMsg>, ContractError> {
+    let params = OUTPOSTS
+        .may_load(storage, &submission.prefix)?
+        .and_then(|outpost| outpost.params)
+        .ok_or_else(|| ContractError::OutpostNotFound {
+            prefix: submission.prefix.clone(),
+        })?;
+
+    let data = to_json_binary(&VxAstroIbcMsg::RefundProposalDeposit {
+        sequence,
+        submitter: submission.submitter.clone(),
+        deposit: submission.deposit,
+    })?;
+    submission.refund = Some(DeliveryStatus::Pending);
+    OUTPOST_SUBMISSIONS.save(storage, (channel, sequence), &submission)?;
+    record_packets(storage, "sent", "refund_proposal_deposit", 1)?;
+
+    Ok(IbcMsg::SendPacket {
+        timeout: IbcTimeout::from(env.block.time.plus_seconds(params.packet_timeout())),
+        channel_id: params.voting_channel,
+        data,
+    }
+    .into())
+}
+
+/// Resends a timed out packet to the outpost once its backoff window has passed.
+/// Packets superseded since the timeout are expired instead.
+pub fn retry_packet(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    nonpayable(&info)?;
+    let mut retry = PACKET_RETRIES.load(deps.storage, id)?;
+    ensure!(
+        retry.status == PacketRetryStatus::Pending,
+        ContractError::PacketRetryNotPending(id)
+    );
+    ensure!(
+        env.block.time.seconds() >= retry.next_retry_at,
+        ContractError::PacketRetryTooEarly {
+            id,
+            next_retry_at: retry.next_retry_at
+        }
+    );
+
+    let outpost_not_found = || ContractError::OutpostNotFound {
+        prefix: retry.prefix.clone(),
+    };
+    let outpost = OUTPOSTS
+        .may_load(deps.storage, &retry.prefix)?
+        .ok_or_else(outpost_not_found)?;
+    ensure!(
+        !outpost.jailed,
+        ContractError::JailedOutpost {
+            prefix: retry.prefix.clone()
+        }
+    );
+    let params = outpost.params.ok_or_else(outpost_not_found)?;
+
+    // Tracked state is marked as pending again unless a newer packet has been sent since the timeout
+    let superseded = match from_json(&retry.data)? {
+        VxAstroIbcMsg::ExecuteMsgs { execution_id, .. } => {
+            let mut execution = OUTPOST_EXECUTIONS.load(deps.storage, execution_id)?;
+            let failed = matches!(execution.status, OutpostExecutionStatus::Failed { .. });
+            if failed {
+                execution.status = OutpostExecutionStatus::Pending;
+                OUTPOST_EXECUTIONS.save(deps.storage, execution_id, &execution)?;
+            }
+            !failed
+        }
+        VxAstroIbcMsg::ProposalStatusUpdate {
+            proposal_id,
+            status,
+        } => {
+            let key = (proposal_id, retry.prefix.as_str());
+            match PROPOSAL_NOTIFICATIONS.may_load(deps.storage, key)? {
+                Some(mut notification)
+                    if notification.status == status
+                        && matches!(notification.delivery, DeliveryStatus::Failed { .. }) =>
+                {
+                    notification.delivery = DeliveryStatus::Pending;
+                    PROPOSAL_NOTIFICATIONS.save(deps.storage, key, &notification)?;
+                    false
+                }
+                _ => true,
+            }
+        }
+        VxAstroIbcMsg::RefundProposalDeposit { sequence, .. } => {
+            let channel = params.voting_channel.as_str();
+            match OUTPOST_SUBMISSIONS.may_load(deps.storage, (channel, sequence))? {
+                Some(mut submission)
+                    if matches!(submission.refund, Some(DeliveryStatus::Failed { .. })) =>
+                {
+                    submission.refund = Some(DeliveryStatus::Pending);
+                    OUTPOST_SUBMISSIONS.save(deps.storage, (channel, sequence), &submission)?;
+                    false
+                }
+                _ => true,
+            }
+        }
+        _ => true,
+    };
+
+    let mut response = Response::default().add_attributes([
+        attr("action", "retry_packet"),
+        attr("id", id.to_string()),
+        attr("outpost", &retry.prefix),
+    ]);
+
+    if superseded {
+        PACKET_RETRY_IDS.remove(deps.storage, (retry.prefix.as_str(), retry.data.as_slice()));
+        retry.status = PacketRetryStatus::Expired;
+        response = response.add_attribute("status", "superseded");
+    } else {
+        if let Some(packet_type) = hub_packet_type(&from_json(&retry.data)?) {
+            record_packets(deps.storage, "sent", packet_type, 1)?;
+        }
+        retry.attempts += 1;
+        retry.status = PacketRetryStatus::InFlight;
+        response = response
+            .add_message(IbcMsg::SendPacket {
+                channel_id: params.voting_channel.clone(),
+                data: retry.data.clone(),
+                timeout: IbcTimeout::from(env.block.time.plus_seconds(params.packet_timeout())),
+            })
+            .add_attribute("attempt", retry.attempts.to_string());
+    }
+    PACKET_RETRIES.save(deps.storage, id, &retry)?;
+
+    Ok(response)
+}
+
+/// Stores pool TVLs reported by the TVL oracle.
+/// Only the TVL oracle can call this endpoint.
+pub fn report_pool_tvls(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    tvls: Vec<(String, Uint128)>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        config.tvl_oracle == Some(info.sender),
+        ContractError::Unauthorized {}
+    );
+
+    let updated_at = env.block.time.seconds();
+    for (pool, tvl) in &tvls {
+        POOL_TVLS.save(
+            deps.storage,
+            pool,
+            &PoolTvl {
+                tvl: *tvl,
+                updated_at,
+            },
+        )?;
+    }
+
+    Ok(Response::default().add_attributes([
+        attr("action", "report_pool_tvls"),
+        attr("pools", tvls.len().to_string()),
+    ]))
+}
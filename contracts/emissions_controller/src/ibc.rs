@@ -1,23 +1,39 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure, from_json, wasm_execute, Deps, DepsMut, Env, Ibc3ChannelOpenResponse, IbcBasicResponse,
-    IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg, IbcPacketAckMsg,
-    IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse, Never, StdError, StdResult,
-    Uint128,
+    attr, coin, ensure, from_json, to_json_binary, wasm_execute, Addr, Attribute, Deps, DepsMut,
+    Env, Ibc3ChannelOpenResponse, IbcBasicResponse, IbcChannelCloseMsg, IbcChannelConnectMsg,
+    IbcChannelOpenMsg, IbcPacket, IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg,
+    IbcReceiveResponse, Never, StdError, StdResult, Storage, Uint128,
 };
 
 use astroport_governance::assembly;
-use astroport_governance::emissions_controller::consts::{IBC_APP_VERSION, IBC_ORDERING};
-use astroport_governance::emissions_controller::hub::OutpostInfo;
+use astroport_governance::assembly::{ProposalPhase, ProposalStatus};
+use astroport_governance::emissions_controller::consts::{
+    IBC_APP_VERSION, IBC_ORDERING, IBC_TIMEOUT, MAX_PACKET_ATTEMPTS, OUTPOST_PERMIT_EMISSIONS_VOTE,
+    OUTPOST_PERMIT_GOVERNANCE_VOTE, OUTPOST_PERMIT_LOCK_UPDATE, OUTPOST_PERMIT_SUBMIT_PROPOSAL,
+    OUTPOST_PERMIT_WHITELIST_POOL, PACKET_RETRY_BACKOFF,
+};
+use astroport_governance::emissions_controller::hub::{
+    DeliveryStatus, OutpostExecution, OutpostExecutionStatus, OutpostInfo, OutpostParams,
+    OutpostSubmission, OutpostSubmissionStatus, PacketRetry, PacketRetryStatus,
+};
 use astroport_governance::emissions_controller::msg::{
-    ack_fail, ack_ok, IbcAckResult, VxAstroIbcMsg,
+    ack_fail_with_code, ack_ok, ack_ok_with_data, AckError, IbcAckResult, VxAstroIbcMsg,
 };
+use astroport_governance::emissions_controller::outpost::ProposalTally;
 
 use crate::error::ContractError;
-use crate::execute::{handle_update_user, handle_vote};
-use crate::state::{get_all_outposts, CONFIG};
-use crate::utils::jail_outpost;
+use crate::execute::{add_pool_to_whitelist, handle_update_user, handle_vote};
+use crate::state::{
+    get_all_outposts, CONFIG, FLOW_STATS, LAST_REPORTED_SUPPLY, LEGACY_ESCROWS, OUTPOST_EXECUTIONS,
+    OUTPOST_SUBMISSIONS, PACKET_RETRIES, PACKET_RETRIES_COUNT, PACKET_RETRY_IDS,
+    PROPOSAL_NOTIFICATIONS, SUPPLY_CHECKS, VOTER_ORIGINS,
+};
+use crate::utils::{
+    get_epoch_start, hub_packet_type, increment_metric, is_whitelist_full, jail_outpost,
+    record_packets, resolve_channel, validate_outpost_prefix,
+};
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn ibc_channel_open(
@@ -78,7 +94,7 @@ pub fn ibc_packet_receive(
     do_packet_receive(deps, env, msg).or_else(|err| {
         Ok(IbcReceiveResponse::new()
             .add_attribute("action", "ibc_packet_receive")
-            .set_ack(ack_fail(err)))
+            .set_ack(ack_fail_with_code(err.ack_code(), err)))
     })
 }
 
@@ -86,6 +102,7 @@ pub fn ibc_packet_receive(
 /// bridged over (held in escrow).
 fn is_outpost_valid(
     deps: Deps,
+    prefix: &str,
     outpost: &OutpostInfo,
     ibc_msg: &VxAstroIbcMsg,
 ) -> Result<bool, ContractError> {
@@ -98,10 +115,19 @@ fn is_outpost_valid(
 
     let xastro_denom = CONFIG.load(deps.storage)?.xastro_denom;
 
-    let escrow_balance = deps
-        .querier
-        .query_balance(escrow_address, xastro_denom)?
-        .amount;
+    // xASTRO bridged over migrated ics20 channels is still backing outpost voting power
+    let legacy_escrows = LEGACY_ESCROWS
+        .may_load(deps.storage, prefix)?
+        .unwrap_or_default();
+    let escrow_balance = legacy_escrows
+        .into_iter()
+        .chain([escrow_address])
+        .map(|escrow| {
+            deps.querier
+                .query_balance(escrow, &xastro_denom)
+                .map(|coin| coin.amount)
+        })
+        .sum::<StdResult<Uint128>>()?;
 
     match ibc_msg {
         VxAstroIbcMsg::EmissionsVote {
@@ -112,11 +138,145 @@ fn is_outpost_valid(
         }
         | VxAstroIbcMsg::GovernanceVote {
             total_voting_power, ..
+        }
+        | VxAstroIbcMsg::GovernanceVoteCommit {
+            total_voting_power, ..
+        }
+        | VxAstroIbcMsg::GovernanceVoteReveal {
+            total_voting_power, ..
         } => Ok(*total_voting_power <= escrow_balance),
+        // Whitelisting, tally requests and proposal submissions don't depend on voting power
+        VxAstroIbcMsg::WhitelistPool { .. }
+        | VxAstroIbcMsg::ProposalTallyRequest { .. }
+        | VxAstroIbcMsg::SubmitProposal { .. } => Ok(true),
         VxAstroIbcMsg::RegisterProposal { .. } => {
             unreachable!("Hub can't receive RegisterProposal message")
         }
+        VxAstroIbcMsg::ExecuteMsgs { .. } => Err(ContractError::InvalidOutpostPacket(
+            "ExecuteMsgs".to_string(),
+        )),
+        VxAstroIbcMsg::ProposalStatusUpdate { .. } => Err(ContractError::InvalidOutpostPacket(
+            "ProposalStatusUpdate".to_string(),
+        )),
+        VxAstroIbcMsg::RefundProposalDeposit { .. } => Err(ContractError::InvalidOutpostPacket(
+            "RefundProposalDeposit".to_string(),
+        )),
+    }
+}
+
+/// Returns the outpost permission required to relay the IBC message along with the action name.
+/// None means the message is always accepted.
+/// Messages which can only be sent by the Hub are rejected.
+fn required_permission(
+    ibc_msg: &VxAstroIbcMsg,
+) -> Result<Option<(u8, &'static str)>, ContractError> {
+    let permission = match ibc_msg {
+        VxAstroIbcMsg::EmissionsVote { .. } => {
+            Some((OUTPOST_PERMIT_EMISSIONS_VOTE, "emissions_vote"))
+        }
+        // vxASTRO unlocks are always accepted same as in jailed outposts
+        VxAstroIbcMsg::UpdateUserVotes {
+            is_unlock: true, ..
+        } => None,
+        VxAstroIbcMsg::UpdateUserVotes { .. } => Some((OUTPOST_PERMIT_LOCK_UPDATE, "lock_update")),
+        VxAstroIbcMsg::GovernanceVote { .. }
+        | VxAstroIbcMsg::GovernanceVoteCommit { .. }
+        | VxAstroIbcMsg::GovernanceVoteReveal { .. } => {
+            Some((OUTPOST_PERMIT_GOVERNANCE_VOTE, "governance_vote"))
+        }
+        VxAstroIbcMsg::WhitelistPool { .. } => {
+            Some((OUTPOST_PERMIT_WHITELIST_POOL, "whitelist_pool"))
+        }
+        VxAstroIbcMsg::SubmitProposal { .. } => {
+            Some((OUTPOST_PERMIT_SUBMIT_PROPOSAL, "submit_proposal"))
+        }
+        // Tally requests are read-only
+        VxAstroIbcMsg::ProposalTallyRequest { .. } => None,
+        VxAstroIbcMsg::RegisterProposal { .. } => {
+            return Err(ContractError::InvalidOutpostPacket(
+                "RegisterProposal".to_string(),
+            ))
+        }
+        VxAstroIbcMsg::ExecuteMsgs { .. } => {
+            return Err(ContractError::InvalidOutpostPacket(
+                "ExecuteMsgs".to_string(),
+            ))
+        }
+        VxAstroIbcMsg::ProposalStatusUpdate { .. } => {
+            return Err(ContractError::InvalidOutpostPacket(
+                "ProposalStatusUpdate".to_string(),
+            ))
+        }
+        VxAstroIbcMsg::RefundProposalDeposit { .. } => {
+            return Err(ContractError::InvalidOutpostPacket(
+                "RefundProposalDeposit".to_string(),
+            ))
+        }
+    };
+
+    Ok(permission)
+}
+
+/// Saves the total voting power reported by the outpost
+/// if its supply is verified via interchain queries.
+/// Changes of the reported voting power are accumulated into the outpost flow of the current epoch.
+fn record_reported_supply(
+    storage: &mut dyn Storage,
+    env: &Env,
+    prefix: &str,
+    ibc_msg: &VxAstroIbcMsg,
+) -> StdResult<()> {
+    let total_voting_power = match ibc_msg {
+        VxAstroIbcMsg::EmissionsVote {
+            total_voting_power, ..
+        }
+        | VxAstroIbcMsg::UpdateUserVotes {
+            total_voting_power, ..
+        }
+        | VxAstroIbcMsg::GovernanceVote {
+            total_voting_power, ..
+        }
+        | VxAstroIbcMsg::GovernanceVoteCommit {
+            total_voting_power, ..
+        }
+        | VxAstroIbcMsg::GovernanceVoteReveal {
+            total_voting_power, ..
+        } => *total_voting_power,
+        VxAstroIbcMsg::WhitelistPool { .. }
+        | VxAstroIbcMsg::ProposalTallyRequest { .. }
+        | VxAstroIbcMsg::SubmitProposal { .. }
+        | VxAstroIbcMsg::RegisterProposal { .. }
+        | VxAstroIbcMsg::ExecuteMsgs { .. }
+        | VxAstroIbcMsg::ProposalStatusUpdate { .. }
+        | VxAstroIbcMsg::RefundProposalDeposit { .. } => return Ok(()),
+    };
+
+    if let Some(mut supply_check) = SUPPLY_CHECKS.may_load(storage, prefix)? {
+        supply_check.reported_supply = total_voting_power;
+        supply_check.reported_at = env.block.time.seconds();
+        SUPPLY_CHECKS.save(storage, prefix, &supply_check)?;
     }
+
+    // The first report only sets the baseline
+    if let Some(last_supply) = LAST_REPORTED_SUPPLY.may_load(storage, prefix)? {
+        if last_supply != total_voting_power {
+            let key = (get_epoch_start(env.block.time.seconds()), prefix);
+            let mut flow = FLOW_STATS.may_load(storage, key)?.unwrap_or_default();
+            if total_voting_power > last_supply {
+                let staked = total_voting_power - last_supply;
+                flow.staked += staked;
+                increment_metric(storage, "total_staked", staked)?;
+            } else {
+                let unstaked = last_supply - total_voting_power;
+                flow.unstaked += unstaked;
+                increment_metric(storage, "total_unstaked", unstaked)?;
+            }
+            FLOW_STATS.save(storage, key, &flow)?;
+        }
+    }
+    LAST_REPORTED_SUPPLY.save(storage, prefix, &total_voting_power)?;
+
+    Ok(())
 }
 
 pub fn do_packet_receive(
@@ -151,19 +311,34 @@ pub fn do_packet_receive(
                 voter,
                 is_unlock: true,
                 ..
-            } => handle_update_user(deps.storage, env, voter.as_str(), Uint128::zero()).map(
-                |orig_response| {
-                    IbcReceiveResponse::new()
-                        .add_attributes(orig_response.attributes)
-                        .set_ack(ack_ok())
-                },
-            ),
+            } => handle_update_user(
+                deps.storage,
+                env,
+                voter.as_str(),
+                Uint128::zero(),
+                Uint128::zero(),
+            )
+            .map(|orig_response| {
+                IbcReceiveResponse::new()
+                    .add_attributes(orig_response.attributes)
+                    .set_ack(ack_ok())
+            }),
             _ => Err(ContractError::JailedOutpost { prefix }),
         }
     } else {
+        if let Some((permission, action)) = required_permission(&ibc_msg)? {
+            ensure!(
+                outpost.permissions & permission != 0,
+                ContractError::ActionNotPermitted {
+                    prefix,
+                    action: action.to_string(),
+                }
+            );
+        }
+
         // Check for possible malicious xASTRO minting behavior on the outpost.
         // Jail this outpost in case of total vxASTRO exceeds the total xASTRO bridged over.
-        if !is_outpost_valid(deps.as_ref(), &outpost, &ibc_msg)? {
+        if !is_outpost_valid(deps.as_ref(), &prefix, &outpost, &ibc_msg)? {
             jail_outpost(deps.storage, &prefix, env)?;
 
             return Ok(IbcReceiveResponse::default()
@@ -171,13 +346,24 @@ pub fn do_packet_receive(
                 .add_attributes([("action", "jail_outpost"), ("prefix", &prefix)]));
         }
 
+        record_reported_supply(deps.storage, &env, &prefix, &ibc_msg)?;
+
         match ibc_msg {
             VxAstroIbcMsg::EmissionsVote {
                 voter,
                 voting_power,
+                total_voting_power,
                 votes,
-                ..
-            } => handle_vote(deps, env, &voter, voting_power, votes).map(|orig_response| {
+            } => handle_vote(
+                deps,
+                env,
+                &voter,
+                voting_power,
+                total_voting_power,
+                votes,
+                None,
+            )
+            .map(|orig_response| {
                 IbcReceiveResponse::new()
                     .add_attributes(orig_response.attributes)
                     .set_ack(ack_ok())
@@ -185,22 +371,40 @@ pub fn do_packet_receive(
             VxAstroIbcMsg::UpdateUserVotes {
                 voter,
                 voting_power,
+                total_voting_power,
                 ..
-            } => handle_update_user(deps.storage, env, voter.as_str(), voting_power).map(
-                |orig_response| {
-                    IbcReceiveResponse::new()
-                        .add_attributes(orig_response.attributes)
-                        .set_ack(ack_ok())
-                },
-            ),
+            } => handle_update_user(
+                deps.storage,
+                env,
+                voter.as_str(),
+                voting_power,
+                total_voting_power,
+            )
+            .map(|orig_response| {
+                IbcReceiveResponse::new()
+                    .add_attributes(orig_response.attributes)
+                    .set_ack(ack_ok())
+            }),
             VxAstroIbcMsg::GovernanceVote {
                 voter,
                 voting_power,
                 proposal_id,
                 vote,
+                snapshot_time,
                 ..
             } => {
                 let config = CONFIG.load(deps.storage)?;
+                check_vp_snapshot(deps.as_ref(), &config.assembly, proposal_id, snapshot_time)?;
+                register_vote_origin(
+                    deps,
+                    &env,
+                    &config.assembly,
+                    &msg.packet.dest.channel_id,
+                    proposal_id,
+                    &voter,
+                    false,
+                )?;
+
                 let cast_vote_msg = wasm_execute(
                     config.assembly,
                     &assembly::ExecuteMsg::CastVoteOutpost {
@@ -216,36 +420,502 @@ pub fn do_packet_receive(
                     .add_message(cast_vote_msg)
                     .set_ack(ack_ok()))
             }
+            VxAstroIbcMsg::GovernanceVoteCommit {
+                voter,
+                voting_power,
+                proposal_id,
+                commitment,
+                snapshot_time,
+                ..
+            } => {
+                let config = CONFIG.load(deps.storage)?;
+                check_vp_snapshot(deps.as_ref(), &config.assembly, proposal_id, snapshot_time)?;
+                register_vote_origin(
+                    deps,
+                    &env,
+                    &config.assembly,
+                    &msg.packet.dest.channel_id,
+                    proposal_id,
+                    &voter,
+                    true,
+                )?;
+
+                let commit_vote_msg = wasm_execute(
+                    config.assembly,
+                    &assembly::ExecuteMsg::CommitVoteOutpost {
+                        voter,
+                        voting_power,
+                        proposal_id,
+                        commitment,
+                    },
+                    vec![],
+                )?;
+
+                Ok(IbcReceiveResponse::new()
+                    .add_message(commit_vote_msg)
+                    .set_ack(ack_ok()))
+            }
+            VxAstroIbcMsg::GovernanceVoteReveal {
+                voter,
+                proposal_id,
+                vote,
+                salt,
+                ..
+            } => {
+                let config = CONFIG.load(deps.storage)?;
+
+                // Only the channel which relayed the commitment is allowed to reveal it
+                if let Some(origin) = VOTER_ORIGINS.may_load(deps.storage, (proposal_id, &voter))? {
+                    ensure!(
+                        resolve_channel(deps.storage, &origin)? == msg.packet.dest.channel_id,
+                        ContractError::DuplicateVote {
+                            proposal_id,
+                            voter,
+                            origin,
+                        }
+                    );
+                }
+
+                let reveal_vote_msg = wasm_execute(
+                    config.assembly,
+                    &assembly::ExecuteMsg::RevealVoteOutpost {
+                        voter,
+                        proposal_id,
+                        vote,
+                        salt,
+                    },
+                    vec![],
+                )?;
+
+                Ok(IbcReceiveResponse::new()
+                    .add_message(reveal_vote_msg)
+                    .set_ack(ack_ok()))
+            }
+            VxAstroIbcMsg::WhitelistPool { user, pool, fee } => {
+                let config = CONFIG.load(deps.storage)?;
+                // The fee is escrowed on the outpost and forwarded to the fee receiver
+                // only after successful acknowledgment
+                ensure!(
+                    fee == config.whitelisting_fee.amount,
+                    ContractError::IncorrectWhitelistFee(config.whitelisting_fee)
+                );
+                // Outpost can whitelist only its own pools
+                validate_outpost_prefix(&pool, &prefix)?;
+                ensure!(
+                    !(config.whitelist_auction && is_whitelist_full(deps.storage, &config)?),
+                    ContractError::WhitelistFull {}
+                );
+
+                add_pool_to_whitelist(deps, &env, &config, &pool)?;
+
+                Ok(IbcReceiveResponse::new()
+                    .add_attributes([
+                        ("action", "whitelist_pool"),
+                        ("pool", pool.as_str()),
+                        ("user", user.as_str()),
+                        ("outpost", prefix.as_str()),
+                    ])
+                    .set_ack(ack_ok_with_data(to_json_binary(&config.fee_receiver)?)))
+            }
+            VxAstroIbcMsg::ProposalTallyRequest { proposal_id } => {
+                let config = CONFIG.load(deps.storage)?;
+                let tally =
+                    query_proposal_tally(deps.as_ref(), &env, &config.assembly, proposal_id)?;
+
+                Ok(IbcReceiveResponse::new()
+                    .add_attributes([
+                        ("action", "proposal_tally_request"),
+                        ("proposal_id", proposal_id.to_string().as_str()),
+                        ("outpost", prefix.as_str()),
+                    ])
+                    .set_ack(ack_ok_with_data(to_json_binary(&tally)?)))
+            }
+            VxAstroIbcMsg::SubmitProposal {
+                submitter,
+                proposal,
+                deposit,
+            } => {
+                let config = CONFIG.load(deps.storage)?;
+                let assembly_config: assembly::Config = deps
+                    .querier
+                    .query_wasm_smart(&config.assembly, &assembly::QueryMsg::Config {})?;
+                ensure!(
+                    deposit >= assembly_config.proposal_required_deposit,
+                    ContractError::InsufficientProposalDeposit(coin(
+                        assembly_config.proposal_required_deposit.u128(),
+                        &config.xastro_denom
+                    ))
+                );
+
+                // The outpost transfers the deposit over ICS20 only after this packet is acknowledged.
+                // ICS20 timeouts are checked against the Hub time, hence the transfer
+                // which hasn't arrived before the deadline can't arrive anymore.
+                let packet_timeout = outpost
+                    .params
+                    .as_ref()
+                    .map_or(IBC_TIMEOUT, OutpostParams::packet_timeout);
+                let deadline = env.block.time.seconds() + 2 * packet_timeout;
+                let channel = msg.packet.dest.channel_id;
+                let sequence = msg.packet.sequence;
+                OUTPOST_SUBMISSIONS.save(
+                    deps.storage,
+                    (&channel, sequence),
+                    &OutpostSubmission {
+                        prefix: prefix.clone(),
+                        submitter: submitter.clone(),
+                        proposal,
+                        deposit,
+                        deadline,
+                        status: OutpostSubmissionStatus::AwaitingDeposit,
+                        refund: None,
+                    },
+                )?;
+
+                Ok(IbcReceiveResponse::new()
+                    .add_attributes([
+                        ("action", "outpost_submission"),
+                        ("channel", channel.as_str()),
+                        ("sequence", sequence.to_string().as_str()),
+                        ("submitter", submitter.as_str()),
+                        ("outpost", prefix.as_str()),
+                    ])
+                    .set_ack(ack_ok_with_data(to_json_binary(&deadline)?)))
+            }
             VxAstroIbcMsg::RegisterProposal { .. } => {
                 unreachable!("Hub can't receive RegisterProposal message")
             }
+            VxAstroIbcMsg::ExecuteMsgs { .. } => Err(ContractError::InvalidOutpostPacket(
+                "ExecuteMsgs".to_string(),
+            )),
+            VxAstroIbcMsg::ProposalStatusUpdate { .. } => Err(ContractError::InvalidOutpostPacket(
+                "ProposalStatusUpdate".to_string(),
+            )),
+            VxAstroIbcMsg::RefundProposalDeposit { .. } => Err(
+                ContractError::InvalidOutpostPacket("RefundProposalDeposit".to_string()),
+            ),
         }
     }
 }
 
-#[cfg(not(tarpaulin_include))]
+/// Takes the live tally of the Assembly proposal.
+fn query_proposal_tally(
+    deps: Deps,
+    env: &Env,
+    assembly_addr: &Addr,
+    proposal_id: u64,
+) -> StdResult<ProposalTally> {
+    let proposal: assembly::Proposal = deps
+        .querier
+        .query_wasm_smart(assembly_addr, &assembly::QueryMsg::Proposal { proposal_id })?;
+    let voting_end_block = proposal.voting_end_block();
+
+    Ok(ProposalTally {
+        proposal_id,
+        status: proposal.status,
+        for_power: proposal.for_power,
+        against_power: proposal.against_power,
+        total_voting_power: proposal.total_voting_power,
+        voting_end_block,
+        blocks_remaining: voting_end_block.saturating_sub(env.block.height),
+        updated_at: env.block.time.seconds(),
+    })
+}
+
+/// Ensures the voting power reported by an outpost was taken 1 second before the proposal started.
+/// Packets without the snapshot time are rejected as their voting power can't be verified.
+fn check_vp_snapshot(
+    deps: Deps,
+    assembly_addr: &Addr,
+    proposal_id: u64,
+    snapshot_time: Option<u64>,
+) -> Result<(), ContractError> {
+    let snapshot_time =
+        snapshot_time.ok_or(ContractError::MissingVotingPowerSnapshot(proposal_id))?;
+    let proposal: assembly::Proposal = deps
+        .querier
+        .query_wasm_smart(assembly_addr, &assembly::QueryMsg::Proposal { proposal_id })?;
+    let expected = proposal.start_time - 1;
+    ensure!(
+        snapshot_time == expected,
+        ContractError::InvalidVotingPowerSnapshot {
+            proposal_id,
+            snapshot_time,
+            expected,
+        }
+    );
+
+    Ok(())
+}
+
+/// Outpost voters are identified by their outpost address and the channel their vote was relayed from.
+/// The first channel which relayed a vote wins; subsequent votes relayed from other channels are rejected.
+/// Outpost addresses are never converted to Hub addresses as the same account bytes might belong to
+/// different owners across chains.
+/// If `allow_recommit` is set, the channel which has already relayed a hidden vote
+/// is allowed to replace its commitment.
+/// Records the vote origin only if Assembly is going to accept the vote.
+fn register_vote_origin(
+    deps: DepsMut,
+    env: &Env,
+    assembly_addr: &Addr,
+    channel: &str,
+    proposal_id: u64,
+    voter: &str,
+    allow_recommit: bool,
+) -> Result<(), ContractError> {
+    if let Some(origin) = VOTER_ORIGINS.may_load(deps.storage, (proposal_id, voter))? {
+        ensure!(
+            allow_recommit && resolve_channel(deps.storage, &origin)? == channel,
+            ContractError::DuplicateVote {
+                proposal_id,
+                voter: voter.to_string(),
+                origin,
+            }
+        );
+
+        return Ok(());
+    }
+
+    let expected_phase = if allow_recommit {
+        ProposalPhase::Commit
+    } else {
+        ProposalPhase::Voting
+    };
+    let is_open = deps
+        .querier
+        .query_wasm_smart::<assembly::Proposal>(
+            assembly_addr,
+            &assembly::QueryMsg::Proposal { proposal_id },
+        )
+        .map(|proposal| {
+            proposal.status == ProposalStatus::Active
+                && proposal.phase(env.block.height) == expected_phase
+        })
+        .unwrap_or_default();
+    if is_open {
+        VOTER_ORIGINS.save(deps.storage, (proposal_id, voter), &channel.to_string())?;
+    }
+
+    Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn ibc_packet_ack(
-    _deps: DepsMut,
+    deps: DepsMut,
     _env: Env,
     msg: IbcPacketAckMsg,
 ) -> StdResult<IbcBasicResponse> {
     match from_json(msg.acknowledgement.data)? {
         IbcAckResult::Ok(_) => {
+            process_packet_result(deps.storage, &msg.original_packet, Ok(()))?;
+            finish_packet_retry(deps.storage, &msg.original_packet, Ok(()))?;
+            record_packet_result(deps.storage, &msg.original_packet, "acked")?;
             Ok(IbcBasicResponse::default().add_attribute("action", "ibc_packet_ack"))
         }
-        IbcAckResult::Error(err) => Ok(IbcBasicResponse::default().add_attribute("error", err)),
+        IbcAckResult::Error(err) => {
+            let err = AckError::parse(&err);
+            process_packet_result(deps.storage, &msg.original_packet, Err(err.message.clone()))?;
+            finish_packet_retry(deps.storage, &msg.original_packet, Err(err.message.clone()))?;
+            record_packet_result(deps.storage, &msg.original_packet, "failed")?;
+            Ok(IbcBasicResponse::default()
+                .add_attribute("error", err.message)
+                .add_attribute("error_code", format!("{:?}", err.code)))
+        }
     }
 }
 
-#[cfg(not(tarpaulin_include))]
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn ibc_packet_timeout(
-    _deps: DepsMut,
-    _env: Env,
-    _msg: IbcPacketTimeoutMsg,
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketTimeoutMsg,
 ) -> StdResult<IbcBasicResponse> {
-    Ok(IbcBasicResponse::default().add_attribute("action", "ibc_packet_timeout"))
+    process_packet_result(
+        deps.storage,
+        &msg.packet,
+        Err("IBC packet timeout".to_string()),
+    )?;
+    let retry_attrs = schedule_packet_retry(deps.storage, &env, &msg.packet)?;
+    record_packet_result(deps.storage, &msg.packet, "timed_out")?;
+    Ok(IbcBasicResponse::default()
+        .add_attribute("action", "ibc_packet_timeout")
+        .add_attributes(retry_attrs))
+}
+
+/// Counts the outcome of a packet sent by the Hub in packet metrics.
+fn record_packet_result(
+    storage: &mut dyn Storage,
+    packet: &IbcPacket,
+    event: &str,
+) -> StdResult<()> {
+    let msg = from_json(&packet.data).ok();
+    if let Some(packet_type) = msg.as_ref().and_then(hub_packet_type) {
+        record_packets(storage, event, packet_type, 1)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the prefix of the outpost whose voting channel the packet was sent over.
+fn get_outpost_by_voting_channel(
+    storage: &dyn Storage,
+    channel: &str,
+) -> StdResult<Option<String>> {
+    let channel = resolve_channel(storage, channel)?;
+    let prefix = get_all_outposts(storage)?
+        .into_iter()
+        .find_map(|(prefix, outpost)| {
+            outpost
+                .params
+                .filter(|params| params.voting_channel == channel)
+                .map(|_| prefix)
+        });
+
+    Ok(prefix)
+}
+
+/// Returns the outpost prefix of a packet which can be resent as is after a timeout,
+/// i.e., messages forwarded to an outpost, proposal status notifications and deposit refunds.
+fn get_retryable_packet_outpost(
+    storage: &dyn Storage,
+    packet: &IbcPacket,
+) -> StdResult<Option<String>> {
+    match from_json(&packet.data) {
+        Ok(
+            VxAstroIbcMsg::ExecuteMsgs { .. }
+            | VxAstroIbcMsg::ProposalStatusUpdate { .. }
+            | VxAstroIbcMsg::RefundProposalDeposit { .. },
+        ) => get_outpost_by_voting_channel(storage, &packet.src.channel_id),
+        _ => Ok(None),
+    }
+}
+
+/// Registers a timed out packet for a retry.
+/// The waiting time before the retry doubles with every timeout of the same packet.
+/// Packets which have been sent MAX_PACKET_ATTEMPTS times are expired.
+fn schedule_packet_retry(
+    storage: &mut dyn Storage,
+    env: &Env,
+    packet: &IbcPacket,
+) -> StdResult<Vec<Attribute>> {
+    let Some(prefix) = get_retryable_packet_outpost(storage, packet)? else {
+        return Ok(vec![]);
+    };
+
+    let key = (prefix.as_str(), packet.data.as_slice());
+    let (id, mut retry) = match PACKET_RETRY_IDS.may_load(storage, key)? {
+        Some(id) => (id, PACKET_RETRIES.load(storage, id)?),
+        None => {
+            let id = PACKET_RETRIES_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+            PACKET_RETRIES_COUNT.save(storage, &id)?;
+            PACKET_RETRY_IDS.save(storage, key, &id)?;
+
+            let retry = PacketRetry {
+                prefix: prefix.clone(),
+                data: packet.data.clone(),
+                attempts: 1,
+                next_retry_at: 0,
+                status: PacketRetryStatus::Pending,
+            };
+            (id, retry)
+        }
+    };
+
+    let attr_key = if retry.attempts >= MAX_PACKET_ATTEMPTS {
+        retry.status = PacketRetryStatus::Expired;
+        PACKET_RETRY_IDS.remove(storage, key);
+        "packet_retry_expired"
+    } else {
+        retry.status = PacketRetryStatus::Pending;
+        retry.next_retry_at =
+            env.block.time.seconds() + PACKET_RETRY_BACKOFF * 2u64.pow(retry.attempts as u32 - 1);
+        "packet_retry_scheduled"
+    };
+    PACKET_RETRIES.save(storage, id, &retry)?;
+
+    Ok(vec![attr(attr_key, id.to_string())])
+}
+
+/// Saves the outcome of a resent packet. The outcome is final, no more retries are possible.
+fn finish_packet_retry(
+    storage: &mut dyn Storage,
+    packet: &IbcPacket,
+    result: Result<(), String>,
+) -> StdResult<()> {
+    let Some(prefix) = get_retryable_packet_outpost(storage, packet)? else {
+        return Ok(());
+    };
+
+    let key = (prefix.as_str(), packet.data.as_slice());
+    if let Some(id) = PACKET_RETRY_IDS.may_load(storage, key)? {
+        let mut retry = PACKET_RETRIES.load(storage, id)?;
+        retry.status = match result {
+            Ok(()) => PacketRetryStatus::Delivered,
+            Err(reason) => PacketRetryStatus::Failed { reason },
+        };
+        PACKET_RETRIES.save(storage, id, &retry)?;
+        PACKET_RETRY_IDS.remove(storage, key);
+    }
+
+    Ok(())
+}
+
+/// Updates the state of packets whose outcome is tracked by the Hub,
+/// i.e., messages forwarded to an outpost, proposal status notifications and deposit refunds.
+/// Other packets sent by the Hub don't require any processing.
+fn process_packet_result(
+    storage: &mut dyn Storage,
+    packet: &IbcPacket,
+    result: Result<(), String>,
+) -> StdResult<()> {
+    match from_json(&packet.data) {
+        Ok(VxAstroIbcMsg::ExecuteMsgs { execution_id, .. }) => {
+            let status = match result {
+                Ok(()) => OutpostExecutionStatus::Executed,
+                Err(reason) => OutpostExecutionStatus::Failed { reason },
+            };
+            OUTPOST_EXECUTIONS.update(storage, execution_id, |execution| {
+                execution
+                    .map(|execution| OutpostExecution {
+                        status,
+                        ..execution
+                    })
+                    .ok_or_else(|| StdError::not_found("OutpostExecution"))
+            })?;
+        }
+        Ok(VxAstroIbcMsg::ProposalStatusUpdate {
+            proposal_id,
+            status,
+        }) => {
+            // Notifications are sent over the outpost voting channel
+            if let Some(prefix) = get_outpost_by_voting_channel(storage, &packet.src.channel_id)? {
+                let key = (proposal_id, prefix.as_str());
+                if let Some(mut notification) = PROPOSAL_NOTIFICATIONS.may_load(storage, key)? {
+                    // Ignore outdated packets if a newer status has been sent since then
+                    if notification.status == status {
+                        notification.delivery = match result {
+                            Ok(()) => DeliveryStatus::Delivered,
+                            Err(reason) => DeliveryStatus::Failed { reason },
+                        };
+                        PROPOSAL_NOTIFICATIONS.save(storage, key, &notification)?;
+                    }
+                }
+            }
+        }
+        Ok(VxAstroIbcMsg::RefundProposalDeposit { sequence, .. }) => {
+            let key = (packet.src.channel_id.as_str(), sequence);
+            if let Some(mut submission) = OUTPOST_SUBMISSIONS.may_load(storage, key)? {
+                submission.refund = Some(match result {
+                    Ok(()) => DeliveryStatus::Delivered,
+                    Err(reason) => DeliveryStatus::Failed { reason },
+                });
+                OUTPOST_SUBMISSIONS.save(storage, key, &submission)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
 }
 
 #[cfg(not(tarpaulin_include))]
@@ -265,23 +935,28 @@ mod unit_tests {
 
     use cosmwasm_std::testing::{mock_dependencies, mock_env, MockQuerier, MockStorage};
     use cosmwasm_std::{
-        attr, coins, to_json_binary, Addr, Decimal, IbcChannel, IbcEndpoint, IbcOrder, IbcPacket,
-        IbcTimeout, OwnedDeps, Timestamp,
+        attr, coins, to_json_binary, to_json_string, Addr, Decimal, IbcChannel, IbcEndpoint,
+        IbcOrder, IbcPacket, IbcTimeout, OwnedDeps, Timestamp,
     };
     use cw_multi_test::MockApiBech32;
     use neutron_sdk::bindings::query::NeutronQuery;
 
     use astroport_governance::assembly::ProposalVoteOption;
+    use astroport_governance::emissions_controller::consts::OUTPOST_PERMIT_ALL;
     use astroport_governance::emissions_controller::hub::{
         Config, OutpostInfo, OutpostParams, VotedPoolInfo,
     };
-    use astroport_governance::emissions_controller::msg::IbcAckResult;
+    use astroport_governance::emissions_controller::msg::{AckErrorCode, IbcAckResult};
     use astroport_governance::utils::determine_ics20_escrow_address;
 
     use crate::state::{OUTPOSTS, POOLS_WHITELIST, VOTED_POOLS};
 
     use super::*;
 
+    fn ack_error(code: AckErrorCode, message: impl std::fmt::Display) -> IbcAckResult {
+        IbcAckResult::Error(to_json_string(&AckError::new(code, message)).unwrap())
+    }
+
     pub fn mock_custom_dependencies(
     ) -> OwnedDeps<MockStorage, MockApiBech32, MockQuerier, NeutronQuery> {
         OwnedDeps {
@@ -429,6 +1104,16 @@ mod unit_tests {
                     whitelist_threshold: Default::default(),
                     emissions_multiple: Default::default(),
                     max_astro: Default::default(),
+                    new_pool_cooldown_epochs: 0,
+                    max_weight_per_pool: None,
+                    tune_incentive: Default::default(),
+                    tvl_oracle: None,
+                    tvl_weight: Default::default(),
+                    tvl_max_age: 0,
+                    whitelist_auction: false,
+                    min_pool_emissions: Default::default(),
+                    max_failed_epochs: 0,
+                    max_voter_share: None,
                 },
             )
             .unwrap();
@@ -459,8 +1144,9 @@ mod unit_tests {
         let ack_err: IbcAckResult = from_json(resp.acknowledgement).unwrap();
         assert_eq!(
             ack_err,
-            IbcAckResult::Error(
-                "Generic error: Unknown outpost with channel-2 voting channel".to_string()
+            ack_error(
+                AckErrorCode::Unknown,
+                "Generic error: Unknown outpost with channel-2 voting channel"
             )
         );
 
@@ -478,10 +1164,14 @@ mod unit_tests {
                         voting_channel: "channel-2".to_string(),
                         ics20_channel: "".to_string(),
                         escrow_address: escrow_address.clone(),
+                        ibc_timeout: None,
+                        relayer_fee: None,
+                        pools_per_packet: None,
                     }),
                     astro_denom: "".to_string(),
                     astro_pool_config: None,
                     jailed: false,
+                    permissions: OUTPOST_PERMIT_ALL,
                 },
             )
             .unwrap();
@@ -514,7 +1204,10 @@ mod unit_tests {
         let ack_err: IbcAckResult = from_json(resp.acknowledgement).unwrap();
         assert_eq!(
             ack_err,
-            IbcAckResult::Error("Next time you can change your vote is at 1725235200".to_string())
+            ack_error(
+                AckErrorCode::VoteCooldown,
+                "Next time you can change your vote is at 1725235200"
+            )
         );
 
         // Voting from random channel is not possible
@@ -536,8 +1229,9 @@ mod unit_tests {
         let ack_err: IbcAckResult = from_json(resp.acknowledgement).unwrap();
         assert_eq!(
             ack_err,
-            IbcAckResult::Error(
-                "Generic error: Unknown outpost with channel-3 voting channel".to_string()
+            ack_error(
+                AckErrorCode::Unknown,
+                "Generic error: Unknown outpost with channel-3 voting channel"
             )
         );
 
@@ -597,6 +1291,57 @@ mod unit_tests {
         let ack_err: IbcAckResult = from_json(resp.acknowledgement).unwrap();
         assert_eq!(ack_err, IbcAckResult::Ok(b"ok".into()));
 
+        // Messages which can only be sent by the Hub are rejected with an error ack
+        for (hub_msg, name) in [
+            (
+                VxAstroIbcMsg::ExecuteMsgs {
+                    execution_id: 1,
+                    msgs: vec![],
+                },
+                "ExecuteMsgs",
+            ),
+            (
+                VxAstroIbcMsg::ProposalStatusUpdate {
+                    proposal_id: 1,
+                    status: ProposalStatus::Passed,
+                },
+                "ProposalStatusUpdate",
+            ),
+            (
+                VxAstroIbcMsg::RefundProposalDeposit {
+                    sequence: 1,
+                    submitter: "user".to_string(),
+                    deposit: 1000u128.into(),
+                },
+                "RefundProposalDeposit",
+            ),
+        ] {
+            let packet = IbcPacket::new(
+                to_json_binary(&hub_msg).unwrap(),
+                IbcEndpoint {
+                    port_id: "".to_string(),
+                    channel_id: "".to_string(),
+                },
+                IbcEndpoint {
+                    port_id: "".to_string(),
+                    channel_id: "channel-2".to_string(),
+                },
+                1,
+                IbcTimeout::with_timestamp(Timestamp::from_seconds(100)),
+            );
+            let ibc_msg = IbcPacketReceiveMsg::new(packet, Addr::unchecked("doesnt matter"));
+            let resp =
+                ibc_packet_receive(deps.as_mut().into_empty(), env.clone(), ibc_msg).unwrap();
+            let ack_err: IbcAckResult = from_json(resp.acknowledgement).unwrap();
+            assert_eq!(
+                ack_err,
+                ack_error(
+                    AckErrorCode::Unknown,
+                    format!("Hub can't receive {name} message")
+                )
+            );
+        }
+
         // Emulate outpost total voting power at 150_000 xASTRO
         let voting_msg = VxAstroIbcMsg::EmissionsVote {
             voter: "osmo1voter3".to_string(),
@@ -647,10 +1392,14 @@ mod unit_tests {
                         voting_channel: "channel-2".to_string(),
                         ics20_channel: "".to_string(),
                         escrow_address: Addr::unchecked("".to_string()),
+                        ibc_timeout: None,
+                        relayer_fee: None,
+                        pools_per_packet: None,
                     }),
                     astro_denom: "".to_string(),
                     astro_pool_config: None,
                     jailed: true,
+                    permissions: OUTPOST_PERMIT_ALL,
                 },
             )
             .unwrap();
@@ -672,6 +1421,28 @@ mod unit_tests {
                     total_voting_power: Default::default(),
                     proposal_id: 1,
                     vote: ProposalVoteOption::For,
+                    snapshot_time: None,
+                },
+                true,
+            ),
+            (
+                VxAstroIbcMsg::GovernanceVoteCommit {
+                    voter: "osmo1voter".to_string(),
+                    voting_power: 1000u128.into(),
+                    total_voting_power: Default::default(),
+                    proposal_id: 1,
+                    commitment: assembly::vote_commitment(1, &ProposalVoteOption::For, "salt"),
+                    snapshot_time: None,
+                },
+                true,
+            ),
+            (
+                VxAstroIbcMsg::GovernanceVoteReveal {
+                    voter: "osmo1voter".to_string(),
+                    total_voting_power: Default::default(),
+                    proposal_id: 1,
+                    vote: ProposalVoteOption::For,
+                    salt: "salt".to_string(),
                 },
                 true,
             ),
@@ -715,11 +1486,11 @@ mod unit_tests {
             if is_error {
                 assert_eq!(
                     ack_err,
-                    IbcAckResult::Error(
+                    ack_error(
+                        AckErrorCode::JailedOutpost,
                         ContractError::JailedOutpost {
                             prefix: "osmo".to_string()
                         }
-                        .to_string()
                     )
                 );
             } else {
@@ -2,12 +2,12 @@ use astroport::asset::validate_native_denom;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure, to_json_binary, Addr, DepsMut, Env, MessageInfo, Reply, Response, StdError, SubMsg,
-    SubMsgResponse, SubMsgResult, Uint128, WasmMsg,
+    ensure, from_json, to_json_binary, Addr, Decimal, DepsMut, Env, MessageInfo, Reply, Response,
+    StdError, SubMsg, SubMsgResponse, SubMsgResult, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
 use cw_utils::parse_instantiate_response_data;
-use neutron_sdk::bindings::msg::NeutronMsg;
+use neutron_sdk::bindings::msg::{MsgRegisterInterchainQueryResponse, NeutronMsg};
 use neutron_sdk::bindings::query::NeutronQuery;
 
 use astroport_governance::emissions_controller::hub::{Config, TuneInfo};
@@ -16,7 +16,8 @@ use astroport_governance::emissions_controller::utils::query_incentives_addr;
 use astroport_governance::voting_escrow;
 
 use crate::error::ContractError;
-use crate::state::{CONFIG, POOLS_WHITELIST, TUNE_INFO};
+use crate::execute::process_outpost_submission_reply;
+use crate::state::{CONFIG, PENDING_SUPPLY_QUERY, POOLS_WHITELIST, SUPPLY_CHECKS, TUNE_INFO};
 use crate::utils::{get_epoch_start, get_xastro_rate_and_share};
 
 /// Contract name that is used for migration.
@@ -25,6 +26,10 @@ pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 /// ID for the vxastro contract instantiate reply
 pub const INSTANTIATE_VXASTRO_REPLY_ID: u64 = 1;
+/// ID for the outpost supply interchain query registration reply
+pub const REGISTER_SUPPLY_QUERY_REPLY_ID: u64 = 2;
+/// ID for the Assembly proposal submission reply of an outpost submission
+pub const SUBMIT_OUTPOST_PROPOSAL_REPLY_ID: u64 = 3;
 
 /// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -66,6 +71,16 @@ pub fn instantiate(
         whitelist_threshold: msg.whitelist_threshold,
         emissions_multiple: msg.emissions_multiple,
         max_astro: msg.max_astro,
+        new_pool_cooldown_epochs: 0,
+        max_weight_per_pool: None,
+        tune_incentive: Uint128::zero(),
+        tvl_oracle: None,
+        tvl_weight: Decimal::zero(),
+        tvl_max_age: 0,
+        whitelist_auction: false,
+        min_pool_emissions: Uint128::zero(),
+        max_failed_epochs: 0,
+        max_voter_share: None,
         staking,
         xastro_denom: msg.xastro_denom.clone(),
     };
@@ -83,12 +98,14 @@ pub fn instantiate(
             tune_ts: get_epoch_start(env.block.time.seconds()),
             pools_grouped: Default::default(),
             outpost_emissions_statuses: Default::default(),
+            outpost_chunk_statuses: Default::default(),
             emissions_state: EmissionsState {
                 xastro_rate,
                 collected_astro: msg.collected_astro,
                 ema: msg.ema,
                 emissions_amount: Uint128::zero(),
             },
+            skipped_pools: vec![],
         },
         env.block.time.seconds(),
     )?;
@@ -117,7 +134,11 @@ pub fn instantiate(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    msg: Reply,
+) -> Result<Response<NeutronMsg>, ContractError> {
     match msg {
         Reply {
             id: INSTANTIATE_VXASTRO_REPLY_ID,
@@ -140,6 +161,33 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 
             Ok(Response::new().add_attribute("vxastro", vxastro_contract))
         }
+        Reply {
+            id: REGISTER_SUPPLY_QUERY_REPLY_ID,
+            result:
+                SubMsgResult::Ok(SubMsgResponse {
+                    data: Some(data), ..
+                }),
+        } => {
+            let query_id = from_json::<MsgRegisterInterchainQueryResponse>(&data)?.id;
+            let prefix = PENDING_SUPPLY_QUERY.load(deps.storage)?;
+            PENDING_SUPPLY_QUERY.remove(deps.storage);
+
+            SUPPLY_CHECKS.update::<_, StdError>(deps.storage, &prefix, |supply_check| {
+                let mut supply_check = supply_check
+                    .ok_or_else(|| StdError::generic_err("Supply check must be initialized"))?;
+                supply_check.query_id = query_id;
+                Ok(supply_check)
+            })?;
+
+            Ok(Response::new()
+                .add_attribute("action", "supply_query_registered")
+                .add_attribute("prefix", prefix)
+                .add_attribute("query_id", query_id.to_string()))
+        }
+        Reply {
+            id: SUBMIT_OUTPOST_PROPOSAL_REPLY_ID,
+            result,
+        } => process_outpost_submission_reply(deps, env, result),
         _ => Err(ContractError::FailedToParseReply {}),
     }
 }
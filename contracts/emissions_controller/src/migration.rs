@@ -1,10 +1,11 @@
 #![cfg(not(tarpaulin_include))]
 
-use cosmwasm_std::{DepsMut, Empty, Env, Response};
+use cosmwasm_std::{DepsMut, Empty, Env, Order, Response, StdResult};
 use cw2::{get_contract_version, set_contract_version};
 
 use crate::error::ContractError;
 use crate::instantiate::{CONTRACT_NAME, CONTRACT_VERSION};
+use crate::state::{CONFIG, OUTPOSTS};
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
@@ -12,12 +13,23 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, Contra
 
     match contract_version.contract.as_ref() {
         CONTRACT_NAME => match contract_version.version.as_ref() {
-            "1.1.0" | "1.1.1" => Ok(()),
+            "1.1.0" | "1.1.1" | "1.2.0" => Ok(()),
             _ => Err(ContractError::MigrationError {}),
         },
         _ => Err(ContractError::MigrationError {}),
     }?;
 
+    // Persist new config and outpost fields with their default values
+    let config = CONFIG.load(deps.storage)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    let outposts = OUTPOSTS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (prefix, outpost) in outposts {
+        OUTPOSTS.save(deps.storage, &prefix, &outpost)?;
+    }
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::new()
@@ -1,23 +1,32 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{to_json_binary, Binary, Deps, Env, Order, StdError, StdResult};
+use cosmwasm_std::{
+    ensure, to_json_binary, Binary, Deps, Env, Fraction, Order, StdError, StdResult, Uint128,
+};
 use cw_storage_plus::Bound;
 use itertools::Itertools;
 use neutron_sdk::bindings::query::NeutronQuery;
 
 use astroport_governance::emissions_controller::consts::MAX_PAGE_LIMIT;
 use astroport_governance::emissions_controller::hub::{
-    QueryMsg, SimulateTuneResponse, UserInfoResponse,
+    FlowStatsResponse, PoolStatusResponse, QueryMsg, SimulateTuneResponse, SimulateVoteResponse,
+    UserInfoResponse,
 };
+use astroport_governance::emissions_controller::utils::{get_total_voting_power, get_voting_power};
 
 use crate::error::ContractError;
 use crate::state::{
-    get_active_outposts, get_all_outposts, CONFIG, POOLS_BLACKLIST, POOLS_WHITELIST, TUNE_INFO,
-    USER_INFO, VOTED_POOLS,
+    get_active_outposts, get_all_outposts, CONFIG, FLOW_STATS, METRICS, OUTPOST_EXECUTIONS,
+    OUTPOST_FAILURES, OUTPOST_PROPOSALS, OUTPOST_SUBMISSIONS, PACKET_RETRIES,
+    PENDING_POOL_MIGRATION, POOLS_BLACKLIST, POOLS_WHITELIST, POOL_MIGRATIONS, POOL_TVLS,
+    PROPOSAL_NOTIFICATIONS, PROPOSAL_SUBSCRIBERS, SUPPLY_CHECKS, TUNE_INFO, USER_INFO,
+    USER_VOTES_HISTORY, VOTED_POOLS, VOTER_ORIGINS, VOTE_MANAGERS, WHITELIST_BIDS,
+};
+use crate::utils::{
+    failed_emissions_amount, get_epoch_start, pool_eligible_from, simulate_tune, validate_votes,
 };
-use crate::utils::simulate_tune;
 
 /// Expose available contract queries.
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -32,23 +41,34 @@ pub fn query(deps: Deps<NeutronQuery>, env: Env, msg: QueryMsg) -> Result<Binary
             }?
             .unwrap_or_default();
 
-            let applied_votes = user_info
-                .votes
-                .iter()
-                .filter_map(|(pool, weight)| {
+            // Votes for migrated pools are applied to the LP token which existed at that time
+            let applied_pool = |pool: &str| -> StdResult<Option<String>> {
+                let mut candidate = Some(pool.to_string());
+                while let Some(pool) = candidate {
                     let data = if timestamp == block_time {
-                        VOTED_POOLS.may_load(deps.storage, pool)
+                        VOTED_POOLS.may_load(deps.storage, &pool)
                     } else {
-                        VOTED_POOLS.may_load_at_height(deps.storage, pool, timestamp)
-                    };
+                        VOTED_POOLS.may_load_at_height(deps.storage, &pool, timestamp)
+                    }?;
 
                     match data {
-                        Ok(Some(pool_info)) if pool_info.init_ts <= user_info.vote_ts => {
-                            Some(Ok((pool.clone(), *weight)))
+                        Some(pool_info) => {
+                            return Ok((pool_info.init_ts <= user_info.vote_ts).then_some(pool))
                         }
-                        Err(err) => Some(Err(err)),
-                        _ => None,
+                        None => candidate = POOL_MIGRATIONS.may_load(deps.storage, &pool)?,
                     }
+                }
+
+                Ok(None)
+            };
+
+            let applied_votes = user_info
+                .votes
+                .iter()
+                .filter_map(|(pool, weight)| {
+                    applied_pool(pool)
+                        .transpose()
+                        .map(|maybe_pool| maybe_pool.map(|pool| (pool, *weight)))
                 })
                 .try_collect()?;
 
@@ -57,6 +77,9 @@ pub fn query(deps: Deps<NeutronQuery>, env: Env, msg: QueryMsg) -> Result<Binary
                 voting_power: user_info.voting_power,
                 votes: user_info.votes,
                 applied_votes,
+                voted_by: user_info.voted_by,
+                vote_manager: VOTE_MANAGERS.may_load(deps.storage, &user)?,
+                excess_voting_power: user_info.excess_voting_power,
             };
 
             Ok(to_json_binary(&response)?)
@@ -157,11 +180,207 @@ pub fn query(deps: Deps<NeutronQuery>, env: Env, msg: QueryMsg) -> Result<Binary
                 &voted_pools,
                 &outposts,
                 env.block.time.seconds(),
+                env.block.time.seconds(),
                 &config,
             )?;
             Ok(to_json_binary(&SimulateTuneResponse {
                 new_emissions_state: tune_result.new_emissions_state,
                 next_pools_grouped: tune_result.next_pools_grouped,
+                skipped_pools: tune_result.skipped_pools,
+                tvl_weighted: tune_result.tvl_weighted,
+                pool_weights: tune_result.pool_weights,
+            })?)
+        }
+        QueryMsg::PoolStatus { pool } => {
+            let config = CONFIG.load(deps.storage)?;
+            let pool_info = VOTED_POOLS.may_load(deps.storage, &pool)?;
+
+            let response = PoolStatusResponse {
+                is_whitelisted: POOLS_WHITELIST.load(deps.storage)?.contains(&pool),
+                is_blacklisted: POOLS_BLACKLIST.has(deps.storage, &pool),
+                whitelisted_ts: pool_info.as_ref().map(|info| info.init_ts),
+                eligible_from: pool_info
+                    .as_ref()
+                    .map(|info| pool_eligible_from(info.init_ts, config.new_pool_cooldown_epochs)),
+                voting_power: pool_info.map(|info| info.voting_power).unwrap_or_default(),
+            };
+
+            Ok(to_json_binary(&response)?)
+        }
+        QueryMsg::VoterOrigins {
+            proposal_id,
+            limit,
+            start_after,
+        } => {
+            let limit = limit.unwrap_or(MAX_PAGE_LIMIT) as usize;
+            let start_after = start_after.as_ref().map(|s| Bound::exclusive(s.as_str()));
+            let voters = VOTER_ORIGINS
+                .prefix(proposal_id)
+                .range(deps.storage, start_after, None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            Ok(to_json_binary(&voters)?)
+        }
+        QueryMsg::UserVotesHistory {
+            user,
+            limit,
+            start_after,
+        } => {
+            let limit = limit.unwrap_or(MAX_PAGE_LIMIT) as usize;
+            let history = USER_VOTES_HISTORY
+                .prefix(&user)
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .map(|item| item.map(|(_, epoch_votes)| epoch_votes))
+                .collect::<StdResult<Vec<_>>>()?;
+
+            Ok(to_json_binary(&history)?)
+        }
+        QueryMsg::OutpostExecution { execution_id } => Ok(to_json_binary(
+            &OUTPOST_EXECUTIONS.load(deps.storage, execution_id)?,
+        )?),
+        QueryMsg::ProposalSubscribers {} => {
+            let subscribers = PROPOSAL_SUBSCRIBERS
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            Ok(to_json_binary(&subscribers)?)
+        }
+        QueryMsg::ProposalNotifications { proposal_id } => {
+            let notifications = PROPOSAL_NOTIFICATIONS
+                .prefix(proposal_id)
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            Ok(to_json_binary(&notifications)?)
+        }
+        QueryMsg::PoolTvl { pool } => {
+            Ok(to_json_binary(&POOL_TVLS.may_load(deps.storage, &pool)?)?)
+        }
+        QueryMsg::WhitelistBids {} => {
+            let bids = WHITELIST_BIDS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            Ok(to_json_binary(&bids)?)
+        }
+        QueryMsg::OutpostFailures {} => {
+            let failures = OUTPOST_FAILURES
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            Ok(to_json_binary(&failures)?)
+        }
+        QueryMsg::SupplyChecks {} => {
+            let supply_checks = SUPPLY_CHECKS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            Ok(to_json_binary(&supply_checks)?)
+        }
+        QueryMsg::SimulateVote { voter, votes } => {
+            let deps = deps.into_empty();
+            let votes_map: HashMap<_, _> = votes.iter().cloned().collect();
+            ensure!(
+                votes.len() == votes_map.len(),
+                ContractError::DuplicatedVotes {}
+            );
+
+            let config = CONFIG.load(deps.storage)?;
+            validate_votes(deps.storage, &config, &votes_map)?;
+
+            let voting_power = get_voting_power(deps.querier, &config.vxastro, voter, None)?;
+            let total_voting_power = get_total_voting_power(deps.querier, &config.vxastro, None)?;
+            let (effective_voting_power, excess_voting_power) =
+                config.split_voting_power(voting_power, total_voting_power);
+
+            let pools = votes
+                .into_iter()
+                .map(|(pool, weight)| {
+                    let pool_vp = effective_voting_power
+                        .multiply_ratio(weight.numerator(), weight.denominator());
+                    (pool, pool_vp)
+                })
+                .collect_vec();
+
+            Ok(to_json_binary(&SimulateVoteResponse {
+                voting_power,
+                effective_voting_power,
+                excess_voting_power,
+                pools,
+            })?)
+        }
+        QueryMsg::OutpostProposal { proposal_id } => Ok(to_json_binary(
+            &OUTPOST_PROPOSALS.may_load(deps.storage, proposal_id)?,
+        )?),
+        QueryMsg::OutpostSubmission { channel, sequence } => Ok(to_json_binary(
+            &OUTPOST_SUBMISSIONS.may_load(deps.storage, (&channel, sequence))?,
+        )?),
+        QueryMsg::PendingPoolMigration {} => Ok(to_json_binary(
+            &PENDING_POOL_MIGRATION.may_load(deps.storage)?,
+        )?),
+        QueryMsg::Metrics {} => {
+            let mut metrics = METRICS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            let outposts = get_all_outposts(deps.storage)?;
+            let jailed_outposts = outposts.values().filter(|outpost| outpost.jailed).count();
+            let failed_emissions = TUNE_INFO
+                .may_load(deps.storage)?
+                .map(|tune_info| failed_emissions_amount(&tune_info))
+                .unwrap_or_default();
+            metrics.extend([
+                (
+                    "outposts".to_string(),
+                    Uint128::from(outposts.len() as u128),
+                ),
+                (
+                    "jailed_outposts".to_string(),
+                    Uint128::from(jailed_outposts as u128),
+                ),
+                ("failed_emissions_astro".to_string(), failed_emissions),
+            ]);
+            metrics.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            Ok(to_json_binary(&metrics)?)
+        }
+        QueryMsg::PacketRetry { id } => {
+            Ok(to_json_binary(&PACKET_RETRIES.load(deps.storage, id)?)?)
+        }
+        QueryMsg::PacketRetries { limit, start_after } => {
+            let limit = limit.unwrap_or(MAX_PAGE_LIMIT) as usize;
+            let retries = PACKET_RETRIES
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            Ok(to_json_binary(&retries)?)
+        }
+        QueryMsg::FlowStats { epoch_start } => {
+            let epoch_start = get_epoch_start(epoch_start);
+            let outposts = FLOW_STATS
+                .prefix(epoch_start)
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            let (total_staked, total_unstaked) = outposts.iter().fold(
+                (Uint128::zero(), Uint128::zero()),
+                |(staked, unstaked), (_, flow)| (staked + flow.staked, unstaked + flow.unstaked),
+            );
+
+            Ok(to_json_binary(&FlowStatsResponse {
+                epoch_start,
+                total_staked,
+                total_unstaked,
+                outposts,
             })?)
         }
     }
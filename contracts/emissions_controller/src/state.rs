@@ -1,10 +1,12 @@
 use astroport::common::OwnershipProposal;
-use cosmwasm_std::{Order, StdResult, Storage};
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
 use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
 use std::collections::HashMap;
 
 use astroport_governance::emissions_controller::hub::{
-    Config, OutpostInfo, TuneInfo, UserInfo, VotedPoolInfo,
+    Config, OutpostExecution, OutpostFailures, OutpostFlow, OutpostInfo, OutpostProposal,
+    OutpostSubmission, PacketRetry, PoolMigration, PoolTvl, ProposalNotification, SupplyCheck,
+    TuneInfo, UserEpochVotes, UserInfo, VotedPoolInfo, WhitelistBid,
 };
 
 /// Stores config at the given key.
@@ -23,6 +25,9 @@ pub const USER_INFO: SnapshotMap<&str, UserInfo> = SnapshotMap::new(
     "user_info__changelog",
     Strategy::EveryBlock,
 );
+/// User's emissions votes per epoch: (user, epoch start) -> votes.
+/// Only the last USER_VOTES_HISTORY_EPOCHS epochs are kept.
+pub const USER_VOTES_HISTORY: Map<(&str, u64), UserEpochVotes> = Map::new("user_votes_history");
 /// Historical pools voting power and the time when they were whitelisted.
 pub const VOTED_POOLS: SnapshotMap<&str, VotedPoolInfo> = SnapshotMap::new(
     "voted_pools",
@@ -37,6 +42,62 @@ pub const TUNE_INFO: SnapshotItem<TuneInfo> = SnapshotItem::new(
     "tune_info__changelog",
     Strategy::EveryBlock,
 );
+/// Migrated pools: old LP token -> new LP token.
+/// Historical votes for old LP tokens are matched against pools following this map.
+pub const POOL_MIGRATIONS: Map<&str, String> = Map::new("pool_migrations");
+/// Pool migration in progress. Votes of users after the last processed voter still reference the old LP token
+pub const PENDING_POOL_MIGRATION: Item<PoolMigration> = Item::new("pending_pool_migration");
+/// Outpost governance voters per proposal: (proposal_id, outpost voter) -> voting channel the vote was relayed from.
+/// Used to prevent the same voter from voting on a proposal from more than one channel.
+pub const VOTER_ORIGINS: Map<(u64, &str), String> = Map::new("voter_origins");
+/// Interchain query based supply verification state per outpost prefix.
+pub const SUPPLY_CHECKS: Map<&str, SupplyCheck> = Map::new("supply_checks");
+/// Outpost prefix whose supply query registration awaits the reply from Neutron.
+pub const PENDING_SUPPLY_QUERY: Item<String> = Item::new("pending_supply_query");
+/// Messages forwarded by the Hub governance to outposts. Key: execution id
+pub const OUTPOST_EXECUTIONS: Map<u64, OutpostExecution> = Map::new("outpost_executions");
+/// Latest outpost execution id
+pub const OUTPOST_EXECUTIONS_COUNT: Item<u64> = Item::new("outpost_executions_count");
+/// Vote managers authorized to vote on behalf of users. Key: user, value: manager
+pub const VOTE_MANAGERS: Map<&str, Addr> = Map::new("vote_managers");
+/// Outposts subscribed to proposal lifecycle notifications
+pub const PROPOSAL_SUBSCRIBERS: Map<&str, ()> = Map::new("proposal_subscribers");
+/// The last proposal status notification sent to an outpost: (proposal_id, outpost prefix) -> notification
+pub const PROPOSAL_NOTIFICATIONS: Map<(u64, &str), ProposalNotification> =
+    Map::new("proposal_notifications");
+/// The latest TVL reported by the oracle for each pool
+pub const POOL_TVLS: Map<&str, PoolTvl> = Map::new("pool_tvls");
+/// Pending bids for whitelist slots. Pool -> bid. Cleared during every tune
+pub const WHITELIST_BIDS: Map<&str, WhitelistBid> = Map::new("whitelist_bids");
+/// Failed emissions deliveries per outpost prefix.
+/// Outposts without failures since the latest successful delivery have no entry
+pub const OUTPOST_FAILURES: Map<&str, OutpostFailures> = Map::new("outpost_failures");
+/// Assembly proposals submitted on behalf of outpost users. Key: proposal id
+pub const OUTPOST_PROPOSALS: Map<u64, OutpostProposal> = Map::new("outpost_proposals");
+/// Proposal submissions relayed from outposts: (Hub voting channel, packet sequence) -> submission
+pub const OUTPOST_SUBMISSIONS: Map<(&str, u64), OutpostSubmission> =
+    Map::new("outpost_submissions");
+/// Submission whose proposal is being submitted to the Assembly.
+/// Used to pass the submission key to the reply.
+pub const PENDING_SUBMISSION: Item<(String, u64)> = Item::new("pending_submission");
+/// Migrated Hub channels: old channel -> new channel.
+/// Packets in flight over old channels are matched against outposts following this map.
+pub const CHANNEL_MIGRATIONS: Map<&str, String> = Map::new("channel_migrations");
+/// ICS20 escrow addresses of migrated ics20 channels per outpost prefix.
+/// xASTRO bridged over old channels stays escrowed there.
+pub const LEGACY_ESCROWS: Map<&str, Vec<Addr>> = Map::new("legacy_escrows");
+/// The latest total voting power reported by each outpost in IBC messages
+pub const LAST_REPORTED_SUPPLY: Map<&str, Uint128> = Map::new("last_reported_supply");
+/// vxASTRO supply flows reported by outposts: (epoch start, outpost prefix) -> flow
+pub const FLOW_STATS: Map<(u64, &str), OutpostFlow> = Map::new("flow_stats");
+/// Timed out packets sent over outpost voting channels. Key: retry id
+pub const PACKET_RETRIES: Map<u64, PacketRetry> = Map::new("packet_retries");
+/// Latest packet retry id
+pub const PACKET_RETRIES_COUNT: Item<u64> = Item::new("packet_retries_count");
+/// Unfinished packet retries: (outpost prefix, packet data) -> retry id
+pub const PACKET_RETRY_IDS: Map<(&str, &[u8]), u64> = Map::new("packet_retry_ids");
+/// Contract health counters exposed via the Metrics query
+pub const METRICS: Map<&str, Uint128> = Map::new("metrics");
 
 pub fn get_all_outposts(storage: &dyn Storage) -> StdResult<HashMap<String, OutpostInfo>> {
     OUTPOSTS
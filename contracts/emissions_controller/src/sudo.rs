@@ -3,55 +3,109 @@ use cosmwasm_std::entry_point;
 use cosmwasm_std::{ensure, DepsMut, Env, Response, StdError, StdResult, Storage};
 use neutron_sdk::sudo::msg::{RequestPacket, TransferSudoMsg};
 
-use astroport_governance::emissions_controller::hub::OutpostStatus;
+use astroport_governance::emissions_controller::hub::{DeliveryStatus, OutpostStatus};
 
-use crate::state::TUNE_INFO;
-use crate::utils::get_outpost_from_hub_channel;
+use crate::state::{OUTPOST_SUBMISSIONS, TUNE_INFO};
+use crate::utils::{
+    get_outpost_from_hub_channel, get_packet_deposit_refund, get_packet_schedule_chunk,
+    ics20_packet_type, record_packets,
+};
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn sudo(deps: DepsMut, env: Env, msg: TransferSudoMsg) -> StdResult<Response> {
     match msg {
         TransferSudoMsg::Response { request, .. } => {
+            record_packets(deps.storage, "acked", ics20_packet_type(&request), 1)?;
             process_ibc_reply(deps.storage, env, request, false)
         }
-        TransferSudoMsg::Error { request, .. } | TransferSudoMsg::Timeout { request } => {
+        TransferSudoMsg::Error { request, .. } => {
+            record_packets(deps.storage, "failed", ics20_packet_type(&request), 1)?;
+            process_ibc_reply(deps.storage, env, request, true)
+        }
+        TransferSudoMsg::Timeout { request } => {
+            record_packets(deps.storage, "timed_out", ics20_packet_type(&request), 1)?;
             process_ibc_reply(deps.storage, env, request, true)
         }
     }
 }
 
 /// Process outcome of an ics20 IBC packet with IBC hook.
+/// Outcomes of deposit refunds are saved to the outpost submission.
 /// If a packet was successful, it marks the outpost as done.
 /// If a packet failed or timed out, it marks the outpost as failed, so it can be retried.
+/// For schedules delivered in multiple packets, the outpost status is updated
+/// once all packets are acknowledged or failed.
 pub fn process_ibc_reply(
     storage: &mut dyn Storage,
     env: Env,
     packet: RequestPacket,
     failed: bool,
 ) -> StdResult<Response> {
+    if let Some((channel, sequence)) = packet.data.as_ref().and_then(get_packet_deposit_refund) {
+        return process_deposit_refund_reply(storage, channel, sequence, failed);
+    }
+
     let source_channel = packet
         .source_channel
         .ok_or_else(|| StdError::generic_err("Missing source_channel in IBC ack packet"))?;
     let outpost =
         get_outpost_from_hub_channel(storage, source_channel, |params| &params.ics20_channel)?;
 
+    let chunk = packet.data.as_ref().and_then(get_packet_schedule_chunk);
+    let new_status = if failed {
+        OutpostStatus::Failed
+    } else {
+        OutpostStatus::Done
+    };
+
     let mut tune_info = TUNE_INFO.load(storage)?;
-    tune_info
-        .outpost_emissions_statuses
-        .get_mut(&outpost)
-        .ok_or_else(|| StdError::generic_err("Outpost status for {outpost} not found"))
-        .and_then(|status| {
+    match (&chunk, tune_info.outpost_chunk_statuses.get_mut(&outpost)) {
+        (Some(chunk), Some(delivery)) => {
+            ensure!(
+                chunk.epoch == tune_info.tune_ts,
+                StdError::generic_err(format!(
+                    "Chunk {} for outpost {outpost} belongs to stale epoch {}",
+                    chunk.index, chunk.epoch
+                ))
+            );
+            let status = delivery
+                .statuses
+                .get_mut(chunk.index as usize)
+                .ok_or_else(|| {
+                    StdError::generic_err(format!(
+                        "Chunk {} status for outpost {outpost} not found",
+                        chunk.index
+                    ))
+                })?;
             ensure!(
                 *status == OutpostStatus::InProgress,
-                StdError::generic_err(format!("Outpost {outpost} is not in progress"))
+                StdError::generic_err(format!(
+                    "Chunk {} for outpost {outpost} is not in progress",
+                    chunk.index
+                ))
             );
-            *status = if failed {
-                OutpostStatus::Failed
-            } else {
-                OutpostStatus::Done
-            };
-            Ok(())
-        })?;
+            *status = new_status;
+
+            let combined_status = delivery.status();
+            tune_info
+                .outpost_emissions_statuses
+                .insert(outpost.clone(), combined_status);
+        }
+        _ => {
+            tune_info
+                .outpost_emissions_statuses
+                .get_mut(&outpost)
+                .ok_or_else(|| StdError::generic_err("Outpost status for {outpost} not found"))
+                .and_then(|status| {
+                    ensure!(
+                        *status == OutpostStatus::InProgress,
+                        StdError::generic_err(format!("Outpost {outpost} is not in progress"))
+                    );
+                    *status = new_status;
+                    Ok(())
+                })?;
+        }
+    }
     TUNE_INFO.save(storage, &tune_info, env.block.time.seconds())?;
 
     let mut attrs = if failed {
@@ -60,6 +114,40 @@ pub fn process_ibc_reply(
         vec![("action", "ibc_transfer_ack")]
     };
     attrs.push(("outpost", &outpost));
+    let chunk_attr = chunk.map(|chunk| format!("{}/{}", chunk.index + 1, chunk.total));
+    if let Some(chunk_attr) = &chunk_attr {
+        attrs.push(("chunk", chunk_attr));
+    }
 
     Ok(Response::default().add_attributes(attrs))
 }
+
+/// Saves the delivery status of an ics20 deposit refund. Failed refunds can be retried.
+fn process_deposit_refund_reply(
+    storage: &mut dyn Storage,
+    channel: String,
+    sequence: u64,
+    failed: bool,
+) -> StdResult<Response> {
+    let mut submission = OUTPOST_SUBMISSIONS.load(storage, (&channel, sequence))?;
+    submission.refund = Some(if failed {
+        DeliveryStatus::Failed {
+            reason: "ics20 transfer failed".to_string(),
+        }
+    } else {
+        DeliveryStatus::Delivered
+    });
+    OUTPOST_SUBMISSIONS.save(storage, (&channel, sequence), &submission)?;
+
+    let action = if failed {
+        "deposit_refund_failed"
+    } else {
+        "deposit_refund_ack"
+    };
+
+    Ok(Response::default().add_attributes([
+        ("action", action),
+        ("channel", channel.as_str()),
+        ("sequence", sequence.to_string().as_str()),
+    ]))
+}
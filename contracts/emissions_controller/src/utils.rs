@@ -1,31 +1,38 @@
 use std::collections::{HashMap, HashSet};
 
-use astroport::asset::{determine_asset_info, Asset};
+use astroport::asset::{determine_asset_info, Asset, AssetInfo};
 use astroport::common::LP_SUBDENOM;
-use astroport::incentives::{IncentivesSchedule, InputSchedule};
+use astroport::incentives;
+use astroport::incentives::{
+    IncentivesSchedule, InputSchedule, PoolInfoResponse, RewardType, MAX_REWARD_TOKENS,
+};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_schema::serde::Serialize;
 use cosmwasm_std::{
-    coin, Coin, CosmosMsg, Decimal, Deps, Env, Order, QuerierWrapper, StdError, StdResult, Storage,
-    Uint128,
+    attr, coin, ensure, Binary, Coin, CosmosMsg, Decimal, Deps, Env, Event, Order, QuerierWrapper,
+    StdError, StdResult, Storage, Uint128,
 };
+use cw_storage_plus::Bound;
 use itertools::Itertools;
 use neutron_sdk::bindings::msg::{IbcFee, NeutronMsg};
 use neutron_sdk::bindings::query::NeutronQuery;
 use neutron_sdk::query::min_ibc_fee::query_min_ibc_fee;
-use neutron_sdk::sudo::msg::RequestPacketTimeoutHeight;
+use neutron_sdk::sudo::msg::{RequestPacket, RequestPacketTimeoutHeight};
 
-use astroport_governance::emissions_controller::consts::{
-    EPOCHS_START, EPOCH_LENGTH, FEE_DENOM, IBC_TIMEOUT,
-};
+use astroport_governance::emissions_controller::consts::{EPOCHS_START, EPOCH_LENGTH, FEE_DENOM};
 use astroport_governance::emissions_controller::hub::{
-    Config, EmissionsState, OutpostInfo, OutpostParams,
+    Config, EmissionsState, OutpostInfo, OutpostParams, OutpostStatus, PoolWeight, SkipReason,
+    SkippedPool, TuneInfo,
 };
-use astroport_governance::emissions_controller::outpost::OutpostMsg;
+use astroport_governance::emissions_controller::msg::VxAstroIbcMsg;
+use astroport_governance::emissions_controller::outpost::{OutpostMsg, ScheduleChunk};
 use astroport_governance::emissions_controller::utils::check_lp_token;
 
 use crate::error::ContractError;
-use crate::state::{get_active_outposts, OUTPOSTS, POOLS_WHITELIST, TUNE_INFO, VOTED_POOLS};
+use crate::state::{
+    get_active_outposts, CHANNEL_MIGRATIONS, METRICS, OUTPOSTS, OUTPOST_FAILURES,
+    PENDING_POOL_MIGRATION, POOLS_WHITELIST, POOL_TVLS, TUNE_INFO, USER_INFO, VOTED_POOLS,
+};
 
 /// Determine outpost prefix from address or tokenfactory denom.
 pub fn determine_outpost_prefix(value: &str) -> Option<String> {
@@ -75,12 +82,174 @@ pub fn validate_outpost_prefix(value: &str, prefix: &str) -> Result<(), Contract
         .map(|_| ())
 }
 
+/// Moves the user's vote from the old LP token of the pool migration in progress to the new one.
+/// Moved votes take precedence over stale votes for the same LP token.
+pub fn apply_pending_migration(
+    storage: &dyn Storage,
+    votes: &mut HashMap<String, Decimal>,
+) -> StdResult<()> {
+    if let Some(migration) = PENDING_POOL_MIGRATION.may_load(storage)? {
+        if let Some(weight) = votes.remove(&migration.old_lp) {
+            votes.insert(migration.new_lp, weight);
+        }
+    }
+
+    Ok(())
+}
+
+/// Moves votes of up to **limit** users following **start_after** from the old LP token to the new one.
+/// Returns the last processed user if there might be more users left.
+pub fn migrate_user_votes(
+    storage: &mut dyn Storage,
+    block_ts: u64,
+    old_lp: &str,
+    new_lp: &str,
+    start_after: Option<&str>,
+    limit: usize,
+) -> StdResult<Option<String>> {
+    let users = USER_INFO
+        .range(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    let last_user = (users.len() == limit)
+        .then(|| users.last().map(|(user, _)| user.clone()))
+        .flatten();
+
+    for (user, mut user_info) in users {
+        if let Some(weight) = user_info.votes.remove(old_lp) {
+            user_info.votes.insert(new_lp.to_string(), weight);
+            USER_INFO.save(storage, &user, &user_info, block_ts)?;
+        }
+    }
+
+    Ok(last_user)
+}
+
+/// Checks that all voted pools are whitelisted, pool weights are within max_weight_per_pool
+/// and the total weight doesn't exceed 1. Returns the current pools whitelist.
+pub fn validate_votes(
+    storage: &dyn Storage,
+    config: &Config,
+    votes: &HashMap<String, Decimal>,
+) -> Result<HashSet<String>, ContractError> {
+    let mut total_weight = Decimal::zero();
+    let whitelist: HashSet<_> = POOLS_WHITELIST.load(storage)?.into_iter().collect();
+    for (pool, weight) in votes {
+        ensure!(
+            whitelist.contains(pool),
+            ContractError::PoolIsNotWhitelisted(pool.clone())
+        );
+
+        if let Some(max_weight) = config.max_weight_per_pool {
+            ensure!(
+                *weight <= max_weight,
+                ContractError::PoolWeightExceedsLimit {
+                    pool: pool.clone(),
+                    max_weight
+                }
+            );
+        }
+
+        total_weight += weight;
+
+        ensure!(
+            total_weight <= Decimal::one(),
+            ContractError::InvalidTotalWeight {}
+        );
+    }
+
+    Ok(whitelist)
+}
+
+/// Packet type used in metrics of ics20 emissions transfers
+pub const ICS20_EMISSIONS_PACKET: &str = "ics20_emissions";
+/// Metrics type of ics20 packets refunding proposal deposits to outpost submitters
+pub const ICS20_DEPOSIT_REFUND_PACKET: &str = "ics20_deposit_refund";
+
+/// Increases a contract health counter exposed via the Metrics query.
+pub fn increment_metric(storage: &mut dyn Storage, key: &str, amount: Uint128) -> StdResult<()> {
+    if !amount.is_zero() {
+        let value = METRICS.may_load(storage, key)?.unwrap_or_default();
+        METRICS.save(storage, key, &(value + amount))?;
+    }
+
+    Ok(())
+}
+
+/// Counts packets of the given type. Event is one of sent, acked, failed or timed_out.
+pub fn record_packets(
+    storage: &mut dyn Storage,
+    event: &str,
+    packet_type: &str,
+    count: usize,
+) -> StdResult<()> {
+    increment_metric(
+        storage,
+        &format!("packets_{event}_{packet_type}"),
+        Uint128::from(count as u128),
+    )
+}
+
+/// Returns the metrics type of packets sent by the Hub over outpost voting channels.
+pub fn hub_packet_type(msg: &VxAstroIbcMsg) -> Option<&'static str> {
+    match msg {
+        VxAstroIbcMsg::RegisterProposal { .. } => Some("register_proposal"),
+        VxAstroIbcMsg::ExecuteMsgs { .. } => Some("execute_msgs"),
+        VxAstroIbcMsg::ProposalStatusUpdate { .. } => Some("proposal_status_update"),
+        VxAstroIbcMsg::RefundProposalDeposit { .. } => Some("refund_proposal_deposit"),
+        _ => None,
+    }
+}
+
+/// Returns ASTRO of the latest tune whose delivery to outposts failed.
+/// These funds stay on the contract until failed outposts are retried.
+pub fn failed_emissions_amount(tune_info: &TuneInfo) -> Uint128 {
+    tune_info
+        .outpost_emissions_statuses
+        .iter()
+        .filter(|(_, status)| **status == OutpostStatus::Failed)
+        .filter_map(|(prefix, _)| {
+            let pools = tune_info.pools_grouped.get(prefix)?;
+            let amount: Uint128 = match tune_info.outpost_chunk_statuses.get(prefix) {
+                // Only failed packets of the schedule are retried
+                Some(delivery) => pools
+                    .chunks(delivery.pools_per_packet.max(1) as usize)
+                    .zip(&delivery.statuses)
+                    .filter(|(_, status)| **status == OutpostStatus::Failed)
+                    .flat_map(|(chunk, _)| chunk.iter().map(|(_, amount)| *amount))
+                    .sum(),
+                None => pools.iter().map(|(_, amount)| *amount).sum(),
+            };
+
+            Some(amount)
+        })
+        .sum()
+}
+
+/// Returns the channel which replaced the given one following channel migrations.
+/// Channels which have never been migrated are returned as is.
+pub fn resolve_channel(storage: &dyn Storage, channel: &str) -> StdResult<String> {
+    let mut channel = channel.to_string();
+    while let Some(new_channel) = CHANNEL_MIGRATIONS.may_load(storage, &channel)? {
+        channel = new_channel;
+    }
+
+    Ok(channel)
+}
+
 /// Helper function to get outpost prefix from an IBC channel.
+/// Migrated channels are resolved to the current outpost channels.
 pub fn get_outpost_from_hub_channel(
     store: &dyn Storage,
     source_channel: String,
     get_channel_closure: impl Fn(&OutpostParams) -> &String,
 ) -> StdResult<String> {
+    let source_channel = resolve_channel(store, &source_channel)?;
     get_active_outposts(store)?
         .into_iter()
         .find_map(|(outpost_prefix, outpost)| {
@@ -132,20 +301,70 @@ pub fn min_ntrn_ibc_fee(deps: Deps<NeutronQuery>) -> Result<IbcFee, ContractErro
     })
 }
 
+/// Compose ics20 messages with IBC hook memo for outpost emissions controller.
+/// If the number of pools exceeds **pools_per_packet**, the schedule is split into chunks
+/// delivered in separate packets. Each chunk carries its own ASTRO funds.
+/// Messages are returned in the chunk index order.
+///
+/// * **epoch** is the epoch start the schedule belongs to.
+pub fn build_emission_ibc_msgs(
+    env: &Env,
+    params: &OutpostParams,
+    ibc_fee: &IbcFee,
+    astro_funds: Coin,
+    schedules: &[(String, InputSchedule)],
+    pools_per_packet: Option<u32>,
+    epoch: u64,
+) -> StdResult<Vec<CosmosMsg<NeutronMsg>>> {
+    let chunk_size = pools_per_packet.map_or(schedules.len(), |limit| limit as usize);
+    if schedules.len() <= chunk_size {
+        return build_emission_ibc_msg(env, params, ibc_fee, astro_funds, schedules, None)
+            .map(|msg| vec![msg]);
+    }
+
+    let chunks = schedules.chunks(chunk_size).collect_vec();
+    let total = chunks.len() as u32;
+    chunks
+        .into_iter()
+        .enumerate()
+        .map(|(index, chunk)| {
+            let amount: Uint128 = chunk
+                .iter()
+                .map(|(_, schedule)| schedule.reward.amount)
+                .sum();
+            build_emission_ibc_msg(
+                env,
+                params,
+                ibc_fee,
+                coin(amount.u128(), &astro_funds.denom),
+                chunk,
+                Some(ScheduleChunk {
+                    epoch,
+                    index: index as u32,
+                    total,
+                }),
+            )
+        })
+        .collect()
+}
+
 /// Compose ics20 message with IBC hook memo for outpost emissions controller.
-pub fn build_emission_ibc_msg(
+fn build_emission_ibc_msg(
     env: &Env,
     params: &OutpostParams,
     ibc_fee: &IbcFee,
     astro_funds: Coin,
     schedules: &[(String, InputSchedule)],
+    chunk: Option<ScheduleChunk>,
 ) -> StdResult<CosmosMsg<NeutronMsg>> {
     let outpost_controller_msg =
         astroport_governance::emissions_controller::msg::ExecuteMsg::Custom(
             OutpostMsg::SetEmissions {
                 schedules: schedules.to_vec(),
+                chunk,
             },
         );
+
     Ok(NeutronMsg::IbcTransfer {
         source_port: "transfer".to_string(),
         source_channel: params.ics20_channel.clone(),
@@ -156,13 +375,117 @@ pub fn build_emission_ibc_msg(
             revision_number: None,
             revision_height: None,
         },
-        timeout_timestamp: env.block.time.plus_seconds(IBC_TIMEOUT).nanos(),
+        timeout_timestamp: env.block.time.plus_seconds(params.packet_timeout()).nanos(),
         memo: IbcHookMemo::build(&params.emissions_controller, outpost_controller_msg)?,
-        fee: ibc_fee.clone(),
+        fee: outpost_ibc_fee(params, ibc_fee),
+    }
+    .into())
+}
+
+/// Memo of ics20 packets refunding the deposit of an outpost submission.
+/// `channel` and `sequence` identify the SubmitProposal packet received by the Hub.
+#[cw_serde]
+pub enum DepositRefundMemo {
+    DepositRefund { channel: String, sequence: u64 },
+}
+
+/// Compose ics20 message which refunds the xASTRO deposit to the outpost submitter.
+pub fn build_deposit_refund_msg(
+    env: &Env,
+    params: &OutpostParams,
+    ibc_fee: &IbcFee,
+    deposit: Coin,
+    submitter: &str,
+    channel: &str,
+    sequence: u64,
+) -> StdResult<CosmosMsg<NeutronMsg>> {
+    let memo = serde_json::to_string(&DepositRefundMemo::DepositRefund {
+        channel: channel.to_string(),
+        sequence,
+    })
+    .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    Ok(NeutronMsg::IbcTransfer {
+        source_port: "transfer".to_string(),
+        source_channel: params.ics20_channel.clone(),
+        token: deposit,
+        sender: env.contract.address.to_string(),
+        receiver: submitter.to_string(),
+        timeout_height: RequestPacketTimeoutHeight {
+            revision_number: None,
+            revision_height: None,
+        },
+        timeout_timestamp: env.block.time.plus_seconds(params.packet_timeout()).nanos(),
+        memo,
+        fee: outpost_ibc_fee(params, ibc_fee),
     }
     .into())
 }
 
+/// Extracts the submission key from the memo of an ics20 packet refunding a proposal deposit.
+/// Returns None for other ics20 packets.
+pub fn get_packet_deposit_refund(packet_data: &Binary) -> Option<(String, u64)> {
+    let data: serde_json::Value = serde_json::from_slice(packet_data).ok()?;
+    let memo = data.get("memo")?.as_str()?;
+    match serde_json::from_str(memo).ok()? {
+        DepositRefundMemo::DepositRefund { channel, sequence } => Some((channel, sequence)),
+    }
+}
+
+/// Returns the metrics type of an ics20 packet sent by the Hub.
+pub fn ics20_packet_type(packet: &RequestPacket) -> &'static str {
+    match packet.data.as_ref().and_then(get_packet_deposit_refund) {
+        Some(_) => ICS20_DEPOSIT_REFUND_PACKET,
+        None => ICS20_EMISSIONS_PACKET,
+    }
+}
+
+/// Extracts the schedule chunk from the IBC hook memo of an ics20 packet sent to an outpost.
+/// Returns None if the schedule was delivered in a single packet.
+pub fn get_packet_schedule_chunk(packet_data: &Binary) -> Option<ScheduleChunk> {
+    let data: serde_json::Value = serde_json::from_slice(packet_data).ok()?;
+    let memo = data.get("memo")?.as_str()?;
+    match serde_json::from_str(memo).ok()? {
+        IbcHookMemo::Wasm {
+            msg:
+                astroport_governance::emissions_controller::msg::ExecuteMsg::Custom(
+                    OutpostMsg::SetEmissions { chunk, .. },
+                ),
+            ..
+        } => chunk,
+        _ => None,
+    }
+}
+
+/// Returns the IBC fee for ics20 transfers to an outpost.
+fn outpost_ibc_fee(params: &OutpostParams, ibc_fee: &IbcFee) -> IbcFee {
+    match params.relayer_fee {
+        Some(relayer_fee) => IbcFee {
+            recv_fee: ibc_fee.recv_fee.clone(),
+            ack_fee: with_relayer_fee(&ibc_fee.ack_fee, relayer_fee),
+            timeout_fee: with_relayer_fee(&ibc_fee.timeout_fee, relayer_fee),
+        },
+        None => ibc_fee.clone(),
+    }
+}
+
+/// Raises the NTRN fee up to the relayer fee configured for an outpost.
+/// The minimal fee is kept if it is higher.
+fn with_relayer_fee(min_fee: &[Coin], relayer_fee: Uint128) -> Vec<Coin> {
+    let min_amount = min_fee
+        .iter()
+        .find(|coin| coin.denom == FEE_DENOM)
+        .map(|coin| coin.amount)
+        .unwrap_or_default();
+
+    min_fee
+        .iter()
+        .filter(|coin| coin.denom != FEE_DENOM)
+        .cloned()
+        .chain([coin(min_amount.max(relayer_fee).u128(), FEE_DENOM)])
+        .collect()
+}
+
 /// This function converts schedule pairs (lp_token, ASTRO amount)
 /// into the incentives contract executable message.
 /// It also calculates total ASTRO funds required for the emissions.
@@ -259,37 +582,77 @@ pub fn astro_emissions_curve(
 pub struct TuneResult {
     /// All candidates with their voting power and outpost prefix.
     pub candidates: Vec<(String, (String, Uint128))>,
+    /// Pools which are still in the cooldown period after whitelisting.
+    /// They keep their place in the whitelist but don't receive emissions.
+    pub cooldown_pools: Vec<String>,
     /// Dynammic emissions curve state
     pub new_emissions_state: EmissionsState,
     /// Next pools grouped by outpost prefix.
     pub next_pools_grouped: HashMap<String, Vec<(String, Uint128)>>,
+    /// Hub pools excluded from the tune.
+    pub skipped_pools: Vec<SkippedPool>,
+    /// Whether emissions are weighted by pool TVL in addition to votes.
+    pub tvl_weighted: bool,
+    /// Emissions weights of selected pools.
+    pub pool_weights: Vec<PoolWeight>,
+    /// ASTRO paid to the keeper out of the epoch emissions.
+    pub keeper_reward: Uint128,
+}
+
+/// Returns whether the whitelist contains as many pools as can receive ASTRO emissions.
+pub fn is_whitelist_full(storage: &dyn Storage, config: &Config) -> StdResult<bool> {
+    let total_pool_limit = config.pools_per_outpost as usize * get_active_outposts(storage)?.len();
+    Ok(POOLS_WHITELIST.load(storage)?.len() >= total_pool_limit)
+}
+
+/// Returns the epoch start from which a pool whitelisted at `init_ts` is eligible for emissions.
+/// The pool must wait for `cooldown_epochs` full epochs following the epoch it was whitelisted in.
+pub fn pool_eligible_from(init_ts: u64, cooldown_epochs: u64) -> u64 {
+    get_epoch_start(init_ts) + (cooldown_epochs + 1) * EPOCH_LENGTH
 }
 
 /// Simulate the next tune outcome based on the voting power distribution at given timestamp.
 /// In actual tuning context (function tune_pools) timestamp must match current epoch start.
+/// `block_ts` is used to check the state of rewards in the incentives contract.
 pub fn simulate_tune(
     deps: Deps,
     voted_pools: &HashSet<String>,
     outposts: &HashMap<String, OutpostInfo>,
     timestamp: u64,
+    block_ts: u64,
     config: &Config,
 ) -> Result<TuneResult, ContractError> {
+    let mut cooldown_pools = vec![];
     // Determine outpost prefix and filter out non-outpost pools.
     let mut candidates = voted_pools
         .iter()
         .filter_map(|pool| get_outpost_prefix(pool, outposts).map(|prefix| (prefix, pool.clone())))
         .map(|(prefix, pool)| {
-            let pool_vp = VOTED_POOLS
-                .may_load_at_height(deps.storage, &pool, timestamp)?
-                .map(|info| info.voting_power)
-                .unwrap_or_default();
-            Ok((prefix, (pool, pool_vp)))
+            let pool_info = VOTED_POOLS.may_load_at_height(deps.storage, &pool, timestamp)?;
+            Ok::<_, StdError>((prefix, pool, pool_info))
+        })
+        .filter_map_ok(|(prefix, pool, pool_info)| {
+            // Newly whitelisted pools don't receive emissions until their cooldown is over.
+            // A pool without a record at this timestamp was whitelisted after it.
+            if config.new_pool_cooldown_epochs > 0 {
+                let eligible = pool_info.as_ref().is_some_and(|info| {
+                    pool_eligible_from(info.init_ts, config.new_pool_cooldown_epochs) <= timestamp
+                });
+                if !eligible {
+                    cooldown_pools.push(pool);
+                    return None;
+                }
+            }
+
+            let pool_vp = pool_info.map(|info| info.voting_power).unwrap_or_default();
+            Some((prefix, (pool, pool_vp)))
         })
         .collect::<StdResult<Vec<_>>>()?;
 
-    candidates.sort_by(
-        |(_, (_, a)), (_, (_, b))| b.cmp(a), // Sort in descending order
-    );
+    // Sort in descending order of voting power.
+    // Ties are broken by LP token in lexicographic order, thus the cut-off is deterministic.
+    candidates
+        .sort_by(|(_, (pool_a, a)), (_, (pool_b, b))| b.cmp(a).then_with(|| pool_a.cmp(pool_b)));
 
     let total_pool_limit = config.pools_per_outpost as usize * outposts.len();
 
@@ -297,34 +660,81 @@ pub fn simulate_tune(
 
     let new_emissions_state = astro_emissions_curve(deps, tune_info.emissions_state, config)?;
 
-    // Total voting power of all selected pools
-    let total_selected_vp = candidates
+    // Selected pools along with their fresh TVL reports
+    let mut selected = candidates
         .iter()
         .take(total_pool_limit)
-        .fold(Uint128::zero(), |acc, (_, (_, vp))| acc + vp);
-    // Calculate each pool's ASTRO emissions
-    let mut next_pools = candidates
+        .map(|candidate| {
+            let (_, (pool, _)) = candidate;
+            let tvl = if config.tvl_weight.is_zero() {
+                None
+            } else {
+                POOL_TVLS
+                    .may_load(deps.storage, pool)?
+                    .and_then(|pool_tvl| {
+                        (pool_tvl.updated_at + config.tvl_max_age >= block_ts)
+                            .then_some(pool_tvl.tvl)
+                    })
+            };
+            Ok((candidate, tvl))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // The keeper incentive is paid out of the epoch emissions
+    let keeper_reward = config
+        .tune_incentive
+        .min(new_emissions_state.emissions_amount);
+    let pools_emissions = new_emissions_state.emissions_amount - keeper_reward;
+
+    // Pools which would receive less than min_pool_emissions are dropped one by one
+    // starting from the smallest amount, so the remaining pools absorb their share.
+    let mut skipped_pools = vec![];
+    let (tvl_weighted, pool_weights, amounts) = loop {
+        let (tvl_weighted, pool_weights, amounts) =
+            distribute_emissions(&selected, pools_emissions, config);
+
+        // Iterating in reverse, thus on a tie the pool ranked lower in candidates is dropped first
+        let dust_pool = amounts
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, amount)| **amount < config.min_pool_emissions)
+            .min_by_key(|(_, amount)| **amount)
+            .map(|(ind, _)| ind);
+
+        match dust_pool {
+            Some(ind) => {
+                let ((_, (pool, _)), _) = selected.remove(ind);
+                skipped_pools.push(SkippedPool {
+                    pool: pool.clone(),
+                    reason: SkipReason::BelowMinEmissions,
+                });
+            }
+            None => break (tvl_weighted, pool_weights, amounts),
+        }
+    };
+
+    // Emissions to outposts paused after consecutive failed deliveries stay on the emissions controller
+    let paused_outposts = OUTPOST_FAILURES
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map_ok(|(prefix, failures)| failures.emissions_paused.then_some(prefix))
+        .collect::<StdResult<HashSet<_>>>()?;
+
+    let mut next_pools = selected
         .iter()
-        .take(total_pool_limit)
-        .map(|(prefix, (pool, pool_vp))| {
-            let astro_for_pool = new_emissions_state
-                .emissions_amount
-                .multiply_ratio(*pool_vp, total_selected_vp);
-            (prefix.clone(), ((*pool).clone(), astro_for_pool))
+        .zip(amounts)
+        .map(|(((prefix, (pool, _)), _), astro_for_pool)| {
+            (prefix.clone(), (pool.clone(), astro_for_pool))
         })
         .collect_vec();
 
     // Add astro pools for each registered outpost
-    next_pools.extend(outposts.iter().filter_map(|(prefix, outpost)| {
-        outpost.astro_pool_config.as_ref().map(|astro_pool_config| {
-            (
-                prefix.clone(),
-                (
-                    astro_pool_config.astro_pool.clone(),
-                    astro_pool_config.constant_emissions,
-                ),
-            )
-        })
+    next_pools.extend(outposts.iter().flat_map(|(prefix, outpost)| {
+        outpost
+            .astro_pool_config
+            .iter()
+            .flat_map(|astro_pool_config| astro_pool_config.capped_pools())
+            .map(move |astro_pool| (prefix.clone(), astro_pool))
     }));
 
     let next_pools_grouped: HashMap<_, _> = next_pools
@@ -333,17 +743,40 @@ pub fn simulate_tune(
         .into_group_map()
         .into_iter()
         .filter_map(|(prefix, pools)| {
-            if outposts.get(&prefix).unwrap().params.is_none() {
-                // Ensure on the Hub that all LP tokens are valid.
-                // Otherwise, keep ASTRO directed to invalid pools on the emissions controller.
+            if paused_outposts.contains(&prefix) {
+                skipped_pools.extend(pools.into_iter().map(|(pool, _)| SkippedPool {
+                    pool,
+                    reason: SkipReason::OutpostPaused,
+                }));
+                None
+            } else if outposts.get(&prefix).unwrap().params.is_none() {
+                // Ensure on the Hub that all LP tokens are valid
+                // and the incentives contract is able to accept ASTRO rewards.
+                // Otherwise, keep ASTRO directed to skipped pools on the emissions controller.
                 let pools = pools
                     .into_iter()
                     .filter(|(pool, _)| {
-                        determine_asset_info(pool, deps.api)
+                        let reason = if determine_asset_info(pool, deps.api)
                             .and_then(|maybe_lp| {
                                 check_lp_token(deps.querier, &config.factory, &maybe_lp)
                             })
-                            .is_ok()
+                            .is_err()
+                        {
+                            Some(SkipReason::InvalidLpToken)
+                        } else if !has_reward_slot(deps.querier, config, pool, block_ts) {
+                            Some(SkipReason::RewardLimitReached)
+                        } else {
+                            None
+                        };
+
+                        if let Some(reason) = reason {
+                            skipped_pools.push(SkippedPool {
+                                pool: pool.clone(),
+                                reason,
+                            });
+                        }
+
+                        reason.is_none()
                     })
                     .collect_vec();
                 if !pools.is_empty() {
@@ -356,14 +789,174 @@ pub fn simulate_tune(
             }
         })
         .collect();
+    skipped_pools.sort_by(|a, b| a.pool.cmp(&b.pool));
 
     Ok(TuneResult {
         candidates,
+        cooldown_pools,
         new_emissions_state,
         next_pools_grouped,
+        skipped_pools,
+        tvl_weighted,
+        pool_weights,
+        keeper_reward,
     })
 }
 
+/// Selected pool vote with the pool TVL if it was reported
+type SelectedPool<'a> = (&'a (String, (String, Uint128)), Option<Uint128>);
+
+/// Splits ASTRO emissions between selected pools.
+/// Emissions are weighted by TVL only if all selected pools have fresh TVL reports.
+/// Otherwise, fall back to pure vote weighting.
+/// Returns whether emissions are TVL weighted, pool weights and each pool's ASTRO emissions.
+fn distribute_emissions(
+    selected: &[SelectedPool],
+    emissions_amount: Uint128,
+    config: &Config,
+) -> (bool, Vec<PoolWeight>, Vec<Uint128>) {
+    // Total voting power of all selected pools
+    let total_selected_vp = selected
+        .iter()
+        .fold(Uint128::zero(), |acc, ((_, (_, vp)), _)| acc + vp);
+
+    let pool_tvls = selected
+        .iter()
+        .map(|(_, tvl)| *tvl)
+        .collect::<Option<Vec<_>>>()
+        .filter(|tvls| !tvls.iter().sum::<Uint128>().is_zero());
+    let total_selected_tvl: Uint128 = pool_tvls.iter().flatten().sum();
+
+    let pool_weights = selected
+        .iter()
+        .enumerate()
+        .map(|(ind, ((_, (pool, pool_vp)), _))| {
+            let vote_weight =
+                Decimal::checked_from_ratio(*pool_vp, total_selected_vp).unwrap_or_default();
+            let (tvl_weight, weight) = match &pool_tvls {
+                Some(tvls) => {
+                    let tvl_weight = Decimal::from_ratio(tvls[ind], total_selected_tvl);
+                    let weight = vote_weight * (Decimal::one() - config.tvl_weight)
+                        + tvl_weight * config.tvl_weight;
+                    (tvl_weight, weight)
+                }
+                None => (Decimal::zero(), vote_weight),
+            };
+
+            PoolWeight {
+                pool: pool.clone(),
+                vote_weight,
+                tvl_weight,
+                weight,
+            }
+        })
+        .collect_vec();
+
+    // Calculate each pool's ASTRO emissions
+    let amounts = selected
+        .iter()
+        .zip(&pool_weights)
+        .map(|(((_, (_, pool_vp)), _), pool_weight)| {
+            if pool_tvls.is_some() {
+                emissions_amount * pool_weight.weight
+            } else {
+                emissions_amount
+                    .checked_multiply_ratio(*pool_vp, total_selected_vp)
+                    .unwrap_or_default()
+            }
+        })
+        .collect();
+
+    (pool_tvls.is_some(), pool_weights, amounts)
+}
+
+/// Checks whether the incentives contract is able to accept ASTRO rewards for the pool.
+/// The incentives contract limits the number of active external rewards per pool.
+/// A new ASTRO schedule extends the existing ASTRO reward, thus it doesn't need a new slot.
+/// Pools unknown to the incentives contract have all slots available.
+pub fn has_reward_slot(
+    querier: QuerierWrapper,
+    config: &Config,
+    lp_token: &str,
+    block_ts: u64,
+) -> bool {
+    let Ok(pool_info) = querier.query_wasm_smart::<PoolInfoResponse>(
+        &config.incentives_addr,
+        &incentives::QueryMsg::PoolInfo {
+            lp_token: lp_token.to_string(),
+        },
+    ) else {
+        return true;
+    };
+
+    let astro = AssetInfo::native(&config.astro_denom);
+    let mut active_rewards = 0;
+    for reward in &pool_info.rewards {
+        if let RewardType::Ext {
+            info,
+            next_update_ts,
+        } = &reward.reward
+        {
+            if *info == astro {
+                return true;
+            }
+            if *next_update_ts > block_ts {
+                active_rewards += 1;
+            }
+        }
+    }
+
+    active_rewards < MAX_REWARD_TOKENS as usize
+}
+
+/// Updates consecutive failed epochs counters according to emissions delivery statuses
+/// of the previous epoch. An epoch counts as failed if its delivery is still failed at the next tune.
+/// Emissions to outposts which reached [`Config::max_failed_epochs`] are paused.
+/// Returns alert events for newly paused outposts.
+pub fn track_outpost_failures(
+    storage: &mut dyn Storage,
+    tune_info: &TuneInfo,
+    config: &Config,
+) -> StdResult<Vec<Event>> {
+    let mut events = vec![];
+
+    for (prefix, status) in tune_info
+        .outpost_emissions_statuses
+        .iter()
+        .sorted_by_key(|(prefix, _)| *prefix)
+    {
+        match status {
+            OutpostStatus::Failed => {
+                let mut failures = OUTPOST_FAILURES
+                    .may_load(storage, prefix)?
+                    .unwrap_or_default();
+                failures.consecutive_failed_epochs += 1;
+                failures.last_failed_epoch = tune_info.tune_ts;
+
+                if config.max_failed_epochs > 0
+                    && failures.consecutive_failed_epochs >= config.max_failed_epochs
+                    && !failures.emissions_paused
+                {
+                    failures.emissions_paused = true;
+                    events.push(Event::new("outpost_emissions_paused").add_attributes([
+                        attr("prefix", prefix),
+                        attr(
+                            "failed_epochs",
+                            failures.consecutive_failed_epochs.to_string(),
+                        ),
+                    ]));
+                }
+
+                OUTPOST_FAILURES.save(storage, prefix, &failures)?;
+            }
+            OutpostStatus::Done => OUTPOST_FAILURES.remove(storage, prefix),
+            OutpostStatus::InProgress => {}
+        }
+    }
+
+    Ok(events)
+}
+
 /// Jails outpost as well as removes all whitelisted
 /// and being voted pools related to this outpost.
 pub fn jail_outpost(
@@ -405,6 +998,8 @@ pub fn jail_outpost(
 
 #[cfg(test)]
 mod unit_tests {
+    use astroport_governance::emissions_controller::hub::ChunkedDelivery;
+
     use super::*;
 
     #[test]
@@ -455,4 +1050,106 @@ mod unit_tests {
             official_launch_date + EPOCH_LENGTH
         );
     }
+
+    #[test]
+    fn test_with_relayer_fee() {
+        let min_fee = vec![coin(1000, FEE_DENOM)];
+        assert_eq!(
+            with_relayer_fee(&min_fee, Uint128::new(5000)),
+            vec![coin(5000, FEE_DENOM)]
+        );
+        // Minimal fee is kept if the relayer fee is lower
+        assert_eq!(
+            with_relayer_fee(&min_fee, Uint128::new(500)),
+            vec![coin(1000, FEE_DENOM)]
+        );
+        assert_eq!(
+            with_relayer_fee(&[], Uint128::new(500)),
+            vec![coin(500, FEE_DENOM)]
+        );
+    }
+
+    #[test]
+    fn test_get_packet_schedule_chunk() {
+        let packet_data = |chunk: Option<ScheduleChunk>| {
+            let memo = IbcHookMemo::build(
+                "osmo1controller",
+                astroport_governance::emissions_controller::msg::ExecuteMsg::Custom(
+                    OutpostMsg::SetEmissions {
+                        schedules: vec![],
+                        chunk,
+                    },
+                ),
+            )
+            .unwrap();
+            Binary::from(
+                serde_json::json!({
+                    "amount": "100",
+                    "denom": "astro",
+                    "memo": memo,
+                    "receiver": "osmo1controller",
+                    "sender": "neutron1controller",
+                })
+                .to_string()
+                .into_bytes(),
+            )
+        };
+
+        let chunk = ScheduleChunk {
+            epoch: EPOCHS_START,
+            index: 1,
+            total: 2,
+        };
+        assert_eq!(
+            get_packet_schedule_chunk(&packet_data(Some(chunk.clone()))),
+            Some(chunk)
+        );
+        assert_eq!(get_packet_schedule_chunk(&packet_data(None)), None);
+        assert_eq!(get_packet_schedule_chunk(&Binary::from(b"{}")), None);
+    }
+
+    #[test]
+    fn test_failed_emissions_amount() {
+        let pools = |amounts: &[u128]| {
+            amounts
+                .iter()
+                .enumerate()
+                .map(|(i, amount)| (format!("pool{i}"), Uint128::new(*amount)))
+                .collect_vec()
+        };
+        let tune_info = TuneInfo {
+            tune_ts: EPOCHS_START,
+            pools_grouped: HashMap::from([
+                ("osmo".to_string(), pools(&[100, 200])),
+                ("terra".to_string(), pools(&[10, 20, 30, 40, 50])),
+                ("inj".to_string(), pools(&[1000])),
+            ]),
+            outpost_emissions_statuses: HashMap::from([
+                ("osmo".to_string(), OutpostStatus::Failed),
+                ("terra".to_string(), OutpostStatus::Failed),
+                ("inj".to_string(), OutpostStatus::Done),
+            ]),
+            outpost_chunk_statuses: HashMap::from([(
+                "terra".to_string(),
+                ChunkedDelivery {
+                    pools_per_packet: 2,
+                    statuses: vec![
+                        OutpostStatus::Done,
+                        OutpostStatus::Failed,
+                        OutpostStatus::Failed,
+                    ],
+                },
+            )]),
+            emissions_state: EmissionsState {
+                xastro_rate: Decimal::one(),
+                collected_astro: Uint128::zero(),
+                ema: Uint128::zero(),
+                emissions_amount: Uint128::zero(),
+            },
+            skipped_pools: vec![],
+        };
+
+        // All osmo pools and the last two terra chunks
+        assert_eq!(failed_emissions_amount(&tune_info).u128(), 300 + 120);
+    }
 }
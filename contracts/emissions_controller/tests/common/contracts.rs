@@ -3,16 +3,20 @@ use std::fmt::Debug;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_schema::schemars::JsonSchema;
 use cosmwasm_std::{
-    Binary, CustomMsg, CustomQuery, DepsMut, Empty, Env, IbcPacketReceiveMsg, MessageInfo,
-    Response, StdResult,
+    Binary, CustomMsg, CustomQuery, Deps, DepsMut, Empty, Env, IbcPacketAckMsg,
+    IbcPacketReceiveMsg, IbcPacketTimeoutMsg, MessageInfo, Response, StdError, StdResult,
 };
 use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Item;
 use neutron_sdk::bindings::msg::NeutronMsg;
 use neutron_sdk::bindings::query::NeutronQuery;
 use neutron_sdk::sudo::msg::RequestPacket;
 
-use astroport_emissions_controller::ibc::ibc_packet_receive;
+use astroport_emissions_controller::ibc::{ibc_packet_ack, ibc_packet_receive, ibc_packet_timeout};
 use astroport_emissions_controller::sudo::process_ibc_reply;
+use astroport_governance::voting_escrow::SubscriberMsg;
+
+const SUBSCRIBER_FAIL: Item<bool> = Item::new("fail");
 
 pub fn token_contract<T, C>() -> Box<dyn Contract<T, C>>
 where
@@ -46,11 +50,51 @@ where
     T: CustomMsg + Clone + Debug + PartialEq + JsonSchema + 'static,
     C: CustomQuery + for<'de> cosmwasm_schema::serde::Deserialize<'de> + 'static,
 {
-    Box::new(ContractWrapper::new_with_empty(
-        astroport_voting_escrow::contract::execute,
-        astroport_voting_escrow::contract::instantiate,
-        astroport_voting_escrow::contract::query,
-    ))
+    Box::new(
+        ContractWrapper::new_with_empty(
+            astroport_voting_escrow::contract::execute,
+            astroport_voting_escrow::contract::instantiate,
+            astroport_voting_escrow::contract::query,
+        )
+        .with_reply_empty(astroport_voting_escrow::contract::reply),
+    )
+}
+
+/// Mocked vxASTRO lock changes subscriber.
+/// Fails on all notifications if `fail` is set during instantiation.
+pub fn mock_subscriber_contract<T, C>() -> Box<dyn Contract<T, C>>
+where
+    T: CustomMsg + Clone + Debug + PartialEq + JsonSchema + 'static,
+    C: CustomQuery + for<'de> cosmwasm_schema::serde::Deserialize<'de> + 'static,
+{
+    fn instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        fail: bool,
+    ) -> StdResult<Response> {
+        SUBSCRIBER_FAIL.save(deps.storage, &fail)?;
+        Ok(Response::default())
+    }
+    fn execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: SubscriberMsg,
+    ) -> StdResult<Response> {
+        if SUBSCRIBER_FAIL.load(deps.storage)? {
+            return Err(StdError::generic_err("Subscriber failure"));
+        }
+
+        let SubscriberMsg::LockChanged { user, action, .. } = msg;
+        Ok(Response::default()
+            .add_attributes([("subscriber_action", action), ("subscriber_user", user)]))
+    }
+    fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        unimplemented!()
+    }
+
+    Box::new(ContractWrapper::new_with_empty(execute, instantiate, query))
 }
 
 pub fn incentives_contract<T, C>() -> Box<dyn Contract<T, C>>
@@ -95,6 +139,8 @@ pub enum TestSudoMsg {
         request: RequestPacket,
     },
     IbcRecv(IbcPacketReceiveMsg),
+    IbcAck(IbcPacketAckMsg),
+    IbcTimeout(IbcPacketTimeoutMsg),
 }
 
 fn emissions_controller_sudo(deps: DepsMut, env: Env, msg: TestSudoMsg) -> StdResult<Response> {
@@ -109,8 +155,13 @@ fn emissions_controller_sudo(deps: DepsMut, env: Env, msg: TestSudoMsg) -> StdRe
             let ibc_response = ibc_packet_receive(deps, env, packet).unwrap();
             Ok(Response::default()
                 .add_attributes(ibc_response.attributes)
-                .add_submessages(ibc_response.messages))
+                .add_submessages(ibc_response.messages)
+                .set_data(ibc_response.acknowledgement))
         }
+        TestSudoMsg::IbcAck(ack) => ibc_packet_ack(deps, env, ack)
+            .map(|resp| Response::default().add_attributes(resp.attributes)),
+        TestSudoMsg::IbcTimeout(timeout) => ibc_packet_timeout(deps, env, timeout)
+            .map(|resp| Response::default().add_attributes(resp.attributes)),
     }
 }
 
@@ -122,7 +173,7 @@ pub fn emissions_controller() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
             astroport_emissions_controller::query::query,
         )
         .with_sudo_empty(emissions_controller_sudo)
-        .with_reply_empty(astroport_emissions_controller::instantiate::reply),
+        .with_reply(astroport_emissions_controller::instantiate::reply),
     )
 }
 
@@ -163,11 +214,14 @@ where
     T: CustomMsg + Clone + Debug + PartialEq + JsonSchema + 'static,
     C: CustomQuery + for<'de> cosmwasm_schema::serde::Deserialize<'de> + 'static,
 {
-    Box::new(ContractWrapper::new_with_empty(
-        astro_assembly::contract::execute,
-        astro_assembly::contract::instantiate,
-        astro_assembly::queries::query,
-    ))
+    Box::new(
+        ContractWrapper::new_with_empty(
+            astro_assembly::contract::execute,
+            astro_assembly::contract::instantiate,
+            astro_assembly::queries::query,
+        )
+        .with_reply_empty(astro_assembly::contract::reply),
+    )
 }
 
 pub fn builder_unlock_contract<T, C>() -> Box<dyn Contract<T, C>>
@@ -1,11 +1,14 @@
+use std::collections::HashMap;
+
 use astroport::asset::{AssetInfo, PairInfo};
 use astroport::factory::{PairConfig, PairType};
 use astroport::incentives::RewardInfo;
 use astroport::token::Logo;
 use astroport::{factory, incentives, staking};
 use cosmwasm_std::{
-    coin, coins, from_json, to_json_binary, Addr, BlockInfo, Coin, Decimal, Empty, IbcEndpoint,
-    IbcPacket, IbcPacketReceiveMsg, MemoryStorage, StdResult, Timestamp, Uint128,
+    coin, coins, from_json, to_json_binary, Addr, BlockInfo, Coin, Decimal, Empty,
+    IbcAcknowledgement, IbcEndpoint, IbcPacket, IbcPacketAckMsg, IbcPacketReceiveMsg,
+    IbcPacketTimeoutMsg, MemoryStorage, StdResult, Timestamp, Uint128,
 };
 use cw_multi_test::error::AnyResult;
 use cw_multi_test::{
@@ -24,16 +27,18 @@ use astroport_governance::assembly::{
 };
 use astroport_governance::emissions_controller::consts::EPOCHS_START;
 use astroport_governance::emissions_controller::hub::{
-    EmissionsState, HubInstantiateMsg, HubMsg, InputOutpostParams, OutpostInfo,
-    SimulateTuneResponse, TuneInfo, UserInfoResponse, VotedPoolInfo,
+    EmissionsState, FlowStatsResponse, HubInstantiateMsg, HubMsg, InputOutpostParams,
+    OutpostExecution, OutpostFailures, OutpostInfo, PacketRetry, PoolStatusResponse,
+    ProposalNotification, SimulateTuneResponse, SimulateVoteResponse, SupplyCheck, TuneInfo,
+    UserInfoResponse, VotedPoolInfo, WhitelistBid,
 };
-use astroport_governance::emissions_controller::msg::VxAstroIbcMsg;
+use astroport_governance::emissions_controller::msg::{IbcAckResult, VxAstroIbcMsg};
 use astroport_governance::voting_escrow::UpdateMarketingInfo;
 use astroport_governance::{assembly, emissions_controller, voting_escrow};
 
 use crate::common::contracts::*;
 use crate::common::ibc_module::IbcMockModule;
-use crate::common::neutron_module::MockNeutronModule;
+use crate::common::neutron_module::{set_icq_result, MockNeutronModule};
 use crate::common::stargate::StargateModule;
 
 pub const PROPOSAL_REQUIRED_DEPOSIT: Uint128 = Uint128::new(*DEPOSIT_INTERVAL.start());
@@ -70,6 +75,23 @@ fn mock_ntrn_app() -> NeutronApp {
         .build(no_init)
 }
 
+/// Builds an IBC packet sent from the Hub over the voting channel.
+fn mock_ibc_packet(ibc_msg: &VxAstroIbcMsg) -> IbcPacket {
+    IbcPacket::new(
+        to_json_binary(ibc_msg).unwrap(),
+        IbcEndpoint {
+            port_id: "".to_string(),
+            channel_id: "channel-1".to_string(),
+        },
+        IbcEndpoint {
+            port_id: "".to_string(),
+            channel_id: "".to_string(),
+        },
+        0,
+        Timestamp::from_seconds(0).into(),
+    )
+}
+
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub struct ControllerHelper {
@@ -294,17 +316,8 @@ impl ControllerHelper {
             assembly.clone(),
             assembly.clone(),
             &ExecuteMsg::UpdateConfig(Box::new(UpdateConfig {
-                ibc_controller: None,
-                builder_unlock_addr: None,
-                proposal_voting_period: None,
-                proposal_effective_delay: None,
-                proposal_expiration_period: None,
-                proposal_required_deposit: None,
-                proposal_required_quorum: None,
-                proposal_required_threshold: None,
-                whitelist_remove: None,
-                whitelist_add: None,
                 vxastro: Some(vxastro.to_string()),
+                ..Default::default()
             })),
             &[],
         )
@@ -362,6 +375,26 @@ impl ControllerHelper {
         )
     }
 
+    pub fn lock_for(
+        &mut self,
+        user: &Addr,
+        receiver: &Addr,
+        amount: u128,
+    ) -> AnyResult<AppResponse> {
+        let data = self.enter_staking(user, amount)?.data.unwrap();
+        let mint_amount = from_json::<staking::StakingResponse>(&data)
+            .unwrap()
+            .xastro_amount;
+        self.app.execute_contract(
+            user.clone(),
+            self.vxastro.clone(),
+            &voting_escrow::ExecuteMsg::Lock {
+                receiver: Some(receiver.to_string()),
+            },
+            &coins(mint_amount.u128(), &self.xastro),
+        )
+    }
+
     pub fn withdraw(&mut self, user: &Addr) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             user.clone(),
@@ -391,6 +424,25 @@ impl ControllerHelper {
         )
     }
 
+    pub fn slash_lock(
+        &mut self,
+        sender: &Addr,
+        user: &Addr,
+        amount: u128,
+        recipient: &Addr,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.vxastro.clone(),
+            &voting_escrow::ExecuteMsg::SlashLock {
+                user: user.to_string(),
+                amount: amount.into(),
+                recipient: recipient.to_string(),
+            },
+            &[],
+        )
+    }
+
     pub fn set_privileged_list(
         &mut self,
         sender: &Addr,
@@ -404,6 +456,226 @@ impl ControllerHelper {
         )
     }
 
+    pub fn set_unlock_period(
+        &mut self,
+        sender: &Addr,
+        unlock_period: u64,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.vxastro.clone(),
+            &voting_escrow::ExecuteMsg::SetUnlockPeriod { unlock_period },
+            &[],
+        )
+    }
+
+    pub fn set_exit_queue_limit(&mut self, sender: &Addr, limit: u32) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.vxastro.clone(),
+            &voting_escrow::ExecuteMsg::SetExitQueueLimit { limit },
+            &[],
+        )
+    }
+
+    pub fn process_exit_queue(
+        &mut self,
+        sender: &Addr,
+        limit: Option<u32>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.vxastro.clone(),
+            &voting_escrow::ExecuteMsg::ProcessExitQueue { limit },
+            &[],
+        )
+    }
+
+    pub fn query_exit_queue(&self) -> StdResult<voting_escrow::ExitQueueResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.vxastro, &voting_escrow::QueryMsg::ExitQueue {})
+    }
+
+    pub fn query_exit_queue_position(
+        &self,
+        user: &Addr,
+    ) -> StdResult<Vec<voting_escrow::QueuedWithdrawalResponse>> {
+        self.app.wrap().query_wasm_smart(
+            &self.vxastro,
+            &voting_escrow::QueryMsg::ExitQueuePosition {
+                user: user.to_string(),
+            },
+        )
+    }
+
+    pub fn set_pause_mask(&mut self, sender: &Addr, pause_mask: u8) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.vxastro.clone(),
+            &voting_escrow::ExecuteMsg::SetPauseMask { pause_mask },
+            &[],
+        )
+    }
+
+    pub fn set_deposit_fee(
+        &mut self,
+        sender: &Addr,
+        fee_bps: u16,
+        fee_receiver: Option<&Addr>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.vxastro.clone(),
+            &voting_escrow::ExecuteMsg::SetDepositFee {
+                fee_bps,
+                fee_receiver: fee_receiver.map(Addr::to_string),
+            },
+            &[],
+        )
+    }
+
+    pub fn set_fee_exemptions(
+        &mut self,
+        sender: &Addr,
+        add: &[&Addr],
+        remove: &[&Addr],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.vxastro.clone(),
+            &voting_escrow::ExecuteMsg::SetFeeExemptions {
+                add: add.iter().map(|addr| addr.to_string()).collect(),
+                remove: remove.iter().map(|addr| addr.to_string()).collect(),
+            },
+            &[],
+        )
+    }
+
+    pub fn forward_fees(&mut self, sender: &Addr) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.vxastro.clone(),
+            &voting_escrow::ExecuteMsg::ForwardFees {},
+            &[],
+        )
+    }
+
+    pub fn set_insurance_fund(
+        &mut self,
+        sender: &Addr,
+        contribution_bps: u16,
+        fund: Option<&Addr>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.vxastro.clone(),
+            &voting_escrow::ExecuteMsg::SetInsuranceFund {
+                contribution_bps,
+                fund: fund.map(Addr::to_string),
+            },
+            &[],
+        )
+    }
+
+    pub fn set_insurance_exemptions(
+        &mut self,
+        sender: &Addr,
+        add: &[&Addr],
+        remove: &[&Addr],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.vxastro.clone(),
+            &voting_escrow::ExecuteMsg::SetInsuranceExemptions {
+                add: add.iter().map(|addr| addr.to_string()).collect(),
+                remove: remove.iter().map(|addr| addr.to_string()).collect(),
+            },
+            &[],
+        )
+    }
+
+    pub fn query_insurance_contributions(&self, user: &Addr) -> StdResult<Uint128> {
+        self.app.wrap().query_wasm_smart(
+            &self.vxastro,
+            &voting_escrow::QueryMsg::InsuranceContributions {
+                user: user.to_string(),
+            },
+        )
+    }
+
+    pub fn query_total_insurance_contributions(&self) -> StdResult<Uint128> {
+        self.app.wrap().query_wasm_smart(
+            &self.vxastro,
+            &voting_escrow::QueryMsg::TotalInsuranceContributions {},
+        )
+    }
+
+    pub fn query_vxastro_accrued_fees(&self) -> StdResult<Uint128> {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.vxastro, &voting_escrow::QueryMsg::AccruedFees {})
+    }
+
+    pub fn set_subscriber(
+        &mut self,
+        sender: &Addr,
+        contract: &Addr,
+        enabled: bool,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.vxastro.clone(),
+            &voting_escrow::ExecuteMsg::SetSubscriber {
+                contract: contract.to_string(),
+                enabled,
+            },
+            &[],
+        )
+    }
+
+    pub fn make_permanent(&mut self, user: &Addr) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            user.clone(),
+            self.vxastro.clone(),
+            &voting_escrow::ExecuteMsg::MakePermanent {},
+            &[],
+        )
+    }
+
+    pub fn unlock_permanent(&mut self, user: &Addr) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            user.clone(),
+            self.vxastro.clone(),
+            &voting_escrow::ExecuteMsg::UnlockPermanent {},
+            &[],
+        )
+    }
+
+    pub fn set_permanent_boost(&mut self, sender: &Addr, boost_bps: u16) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.vxastro.clone(),
+            &voting_escrow::ExecuteMsg::SetPermanentBoost { boost_bps },
+            &[],
+        )
+    }
+
+    pub fn refresh_permanent_votes(
+        &mut self,
+        sender: &Addr,
+        users: &[&Addr],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.vxastro.clone(),
+            &voting_escrow::ExecuteMsg::RefreshPermanentVotes {
+                users: users.iter().map(|user| user.to_string()).collect(),
+            },
+            &[],
+        )
+    }
+
     pub fn relock(&mut self, user: &Addr) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             user.clone(),
@@ -480,6 +752,24 @@ impl ControllerHelper {
             self.emission_controller.clone(),
             &emissions_controller::msg::ExecuteMsg::<Empty>::Vote {
                 votes: votes.to_vec(),
+                on_behalf_of: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn vote_on_behalf(
+        &mut self,
+        manager: &Addr,
+        user: &Addr,
+        votes: &[(String, Decimal)],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            manager.clone(),
+            self.emission_controller.clone(),
+            &emissions_controller::msg::ExecuteMsg::<Empty>::Vote {
+                votes: votes.to_vec(),
+                on_behalf_of: Some(user.to_string()),
             },
             &[],
         )
@@ -501,6 +791,29 @@ impl ControllerHelper {
         )
     }
 
+    pub fn bid_whitelist_slot(
+        &mut self,
+        user: &Addr,
+        pool: impl Into<String>,
+        bid: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            user.clone(),
+            self.emission_controller.clone(),
+            &emissions_controller::msg::ExecuteMsg::Custom(HubMsg::BidWhitelistSlot {
+                lp_token: pool.into(),
+            }),
+            bid,
+        )
+    }
+
+    pub fn query_whitelist_bids(&self) -> StdResult<Vec<(String, WhitelistBid)>> {
+        self.app.wrap().query_wasm_smart(
+            &self.emission_controller,
+            &emissions_controller::hub::QueryMsg::WhitelistBids {},
+        )
+    }
+
     pub fn update_blacklist(
         &mut self,
         user: &Addr,
@@ -515,6 +828,36 @@ impl ControllerHelper {
         )
     }
 
+    pub fn migrate_pool(
+        &mut self,
+        sender: &Addr,
+        old_lp: &str,
+        new_lp: &str,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.emission_controller.clone(),
+            &emissions_controller::msg::ExecuteMsg::Custom(HubMsg::MigratePool {
+                old_lp: old_lp.to_string(),
+                new_lp: new_lp.to_string(),
+            }),
+            &[],
+        )
+    }
+
+    pub fn migrate_pool_votes(
+        &mut self,
+        sender: &Addr,
+        limit: Option<u32>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.emission_controller.clone(),
+            &emissions_controller::msg::ExecuteMsg::Custom(HubMsg::MigratePoolVotes { limit }),
+            &[],
+        )
+    }
+
     pub fn add_outpost(&mut self, prefix: &str, outpost: OutpostInfo) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             self.owner.clone(),
@@ -526,8 +869,12 @@ impl ControllerHelper {
                     emissions_controller: info.emissions_controller,
                     voting_channel: info.voting_channel,
                     ics20_channel: info.ics20_channel,
+                    ibc_timeout: info.ibc_timeout,
+                    relayer_fee: info.relayer_fee,
+                    pools_per_packet: info.pools_per_packet,
                 }),
                 astro_pool_config: outpost.astro_pool_config,
+                permissions: Some(outpost.permissions),
             }),
             &[],
         )
@@ -543,6 +890,15 @@ impl ControllerHelper {
         )
     }
 
+    pub fn query_pool_status(&self, pool: &str) -> StdResult<PoolStatusResponse> {
+        self.app.wrap().query_wasm_smart(
+            &self.emission_controller,
+            &emissions_controller::hub::QueryMsg::PoolStatus {
+                pool: pool.to_string(),
+            },
+        )
+    }
+
     pub fn query_current_emissions(&self) -> StdResult<EmissionsState> {
         self.query_tune_info(None).map(|x| x.emissions_state)
     }
@@ -562,6 +918,20 @@ impl ControllerHelper {
             })
     }
 
+    pub fn query_simulate_vote(
+        &self,
+        voter: &Addr,
+        votes: &[(String, Decimal)],
+    ) -> StdResult<SimulateVoteResponse> {
+        self.app.wrap().query_wasm_smart(
+            &self.emission_controller,
+            &emissions_controller::hub::QueryMsg::SimulateVote {
+                voter: voter.to_string(),
+                votes: votes.to_vec(),
+            },
+        )
+    }
+
     pub fn query_pool_vp(&self, pool: &str, timestamp: Option<u64>) -> StdResult<Uint128> {
         self.query_voted_pool(pool, timestamp)
             .map(|x| x.voting_power)
@@ -620,6 +990,98 @@ impl ControllerHelper {
         )
     }
 
+    pub fn query_supply_checks(&self) -> StdResult<Vec<(String, SupplyCheck)>> {
+        self.app.wrap().query_wasm_smart(
+            &self.emission_controller,
+            &emissions_controller::hub::QueryMsg::SupplyChecks {},
+        )
+    }
+
+    pub fn query_flow_stats(&self, epoch_start: u64) -> StdResult<FlowStatsResponse> {
+        self.app.wrap().query_wasm_smart(
+            &self.emission_controller,
+            &emissions_controller::hub::QueryMsg::FlowStats { epoch_start },
+        )
+    }
+
+    pub fn query_outpost_failures(&self) -> StdResult<Vec<(String, OutpostFailures)>> {
+        self.app.wrap().query_wasm_smart(
+            &self.emission_controller,
+            &emissions_controller::hub::QueryMsg::OutpostFailures {},
+        )
+    }
+
+    pub fn query_metrics(&self) -> StdResult<HashMap<String, Uint128>> {
+        self.app
+            .wrap()
+            .query_wasm_smart::<Vec<(String, Uint128)>>(
+                &self.emission_controller,
+                &emissions_controller::hub::QueryMsg::Metrics {},
+            )
+            .map(|metrics| metrics.into_iter().collect())
+    }
+
+    pub fn query_packet_retry(&self, id: u64) -> StdResult<PacketRetry> {
+        self.app.wrap().query_wasm_smart(
+            &self.emission_controller,
+            &emissions_controller::hub::QueryMsg::PacketRetry { id },
+        )
+    }
+
+    pub fn query_outpost_execution(&self, execution_id: u64) -> StdResult<OutpostExecution> {
+        self.app.wrap().query_wasm_smart(
+            &self.emission_controller,
+            &emissions_controller::hub::QueryMsg::OutpostExecution { execution_id },
+        )
+    }
+
+    pub fn query_proposal_notifications(
+        &self,
+        proposal_id: u64,
+    ) -> StdResult<Vec<(String, ProposalNotification)>> {
+        self.app.wrap().query_wasm_smart(
+            &self.emission_controller,
+            &emissions_controller::hub::QueryMsg::ProposalNotifications { proposal_id },
+        )
+    }
+
+    pub fn execute_hub_msg(&mut self, sender: &Addr, msg: HubMsg) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.emission_controller.clone(),
+            &emissions_controller::msg::ExecuteMsg::Custom(msg),
+            &[],
+        )
+    }
+
+    pub fn set_icq_result(&mut self, query_id: u64, height: u64, value: u128) {
+        self.app
+            .init_modules(|_, _, storage| set_icq_result(storage, query_id, height, value.into()))
+    }
+
+    pub fn query_voter_origins(&self, proposal_id: u64) -> StdResult<Vec<(String, String)>> {
+        self.app.wrap().query_wasm_smart(
+            &self.emission_controller,
+            &emissions_controller::hub::QueryMsg::VoterOrigins {
+                proposal_id,
+                limit: None,
+                start_after: None,
+            },
+        )
+    }
+
+    pub fn query_vxastro_config(&self) -> StdResult<voting_escrow::Config> {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.vxastro, &voting_escrow::QueryMsg::Config {})
+    }
+
+    pub fn query_vxastro_paused(&self) -> StdResult<voting_escrow::PausedResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.vxastro, &voting_escrow::QueryMsg::Paused {})
+    }
+
     pub fn tune(&mut self, sender: &Addr) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             sender.clone(),
@@ -638,6 +1100,17 @@ impl ControllerHelper {
         )
     }
 
+    pub fn retract_vote(&mut self, user: &Addr, pool: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            user.clone(),
+            self.emission_controller.clone(),
+            &emissions_controller::msg::ExecuteMsg::<Empty>::RetractVote {
+                pool: pool.to_string(),
+            },
+            &[],
+        )
+    }
+
     pub fn retry_failed_outposts(&mut self, sender: &Addr) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             sender.clone(),
@@ -674,6 +1147,8 @@ impl ControllerHelper {
                 link: None,
                 messages: vec![],
                 ibc_channel: None,
+                commit_reveal: false,
+                tags: vec![],
             },
             &deposit,
         )
@@ -691,6 +1166,23 @@ impl ControllerHelper {
     }
 
     pub fn mock_packet_receive(&mut self, ibc_msg: VxAstroIbcMsg) -> AnyResult<AppResponse> {
+        self.mock_packet_receive_on("channel-1", ibc_msg)
+    }
+
+    pub fn mock_packet_receive_on(
+        &mut self,
+        channel: &str,
+        ibc_msg: VxAstroIbcMsg,
+    ) -> AnyResult<AppResponse> {
+        self.mock_packet_receive_seq(channel, 0, ibc_msg)
+    }
+
+    pub fn mock_packet_receive_seq(
+        &mut self,
+        channel: &str,
+        sequence: u64,
+        ibc_msg: VxAstroIbcMsg,
+    ) -> AnyResult<AppResponse> {
         let packet = IbcPacketReceiveMsg::new(
             IbcPacket::new(
                 to_json_binary(&ibc_msg).unwrap(),
@@ -700,9 +1192,9 @@ impl ControllerHelper {
                 },
                 IbcEndpoint {
                     port_id: "".to_string(),
-                    channel_id: "channel-1".to_string(),
+                    channel_id: channel.to_string(),
                 },
-                0,
+                sequence,
                 Timestamp::from_seconds(0).into(),
             ),
             Addr::unchecked("relayer"),
@@ -713,6 +1205,31 @@ impl ControllerHelper {
         )
     }
 
+    pub fn mock_ibc_ack(
+        &mut self,
+        ibc_msg: VxAstroIbcMsg,
+        ack_result: IbcAckResult,
+    ) -> AnyResult<AppResponse> {
+        let packet = IbcPacketAckMsg::new(
+            IbcAcknowledgement::encode_json(&ack_result).unwrap(),
+            mock_ibc_packet(&ibc_msg),
+            Addr::unchecked("relayer"),
+        );
+        self.app.wasm_sudo(
+            self.emission_controller.clone(),
+            &TestSudoMsg::IbcAck(packet),
+        )
+    }
+
+    pub fn mock_ibc_timeout(&mut self, ibc_msg: VxAstroIbcMsg) -> AnyResult<AppResponse> {
+        let packet =
+            IbcPacketTimeoutMsg::new(mock_ibc_packet(&ibc_msg), Addr::unchecked("relayer"));
+        self.app.wasm_sudo(
+            self.emission_controller.clone(),
+            &TestSudoMsg::IbcTimeout(packet),
+        )
+    }
+
     pub fn reset_astro_reward(&mut self, lp_token: &str) -> AnyResult<AppResponse> {
         // Mocking LP provide and depositing to incentives contract
         // NOTE:
@@ -728,6 +1245,23 @@ impl ControllerHelper {
         )
     }
 
+    pub fn incentivize(&mut self, lp_token: &str, reward: Coin) -> AnyResult<AppResponse> {
+        self.mint_tokens(&self.owner.clone(), &[reward.clone()])
+            .unwrap();
+        self.app.execute_contract(
+            self.owner.clone(),
+            self.incentives.clone(),
+            &incentives::ExecuteMsg::Incentivize {
+                lp_token: lp_token.to_string(),
+                schedule: incentives::InputSchedule {
+                    reward: reward.clone().into(),
+                    duration_periods: 2,
+                },
+            },
+            &[reward],
+        )
+    }
+
     pub fn query_rewards(&self, pool: impl Into<String>) -> StdResult<Vec<RewardInfo>> {
         self.app
             .wrap()
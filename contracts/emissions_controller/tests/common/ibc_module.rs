@@ -6,6 +6,9 @@ use cosmwasm_std::{
 use cw_multi_test::error::{anyhow, AnyResult};
 use cw_multi_test::{AppResponse, CosmosRouter, Ibc, Module};
 
+/// Wasm<>wasm channels the emissions controller is connected to
+const OPEN_CHANNELS: [&str; 3] = ["channel-1", "channel-3", "channel-5"];
+
 pub struct IbcMockModule;
 
 impl Ibc for IbcMockModule {}
@@ -40,12 +43,14 @@ impl Module for IbcMockModule {
         request: Self::QueryT,
     ) -> AnyResult<Binary> {
         match &request {
-            IbcQuery::Channel { channel_id, .. } if channel_id == "channel-1" => {
+            IbcQuery::Channel { channel_id, .. }
+                if OPEN_CHANNELS.contains(&channel_id.as_str()) =>
+            {
                 to_json_binary(&ChannelResponse {
                     channel: Some(IbcChannel::new(
                         IbcEndpoint {
                             port_id: "".to_string(),
-                            channel_id: "channel-1".to_string(),
+                            channel_id: channel_id.clone(),
                         },
                         IbcEndpoint {
                             port_id: "".to_string(),
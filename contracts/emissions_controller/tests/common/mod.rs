@@ -1,4 +1,4 @@
-mod contracts;
+pub mod contracts;
 pub mod helper;
 mod ibc_module;
 mod neutron_module;
@@ -1,13 +1,34 @@
 use astroport_governance::emissions_controller::consts::FEE_DENOM;
 use cosmwasm_schema::serde::de::DeserializeOwned;
 use cosmwasm_std::{
-    coins, to_json_binary, Addr, Api, BankMsg, Binary, BlockInfo, CustomMsg, CustomQuery, Empty,
-    Querier, Storage,
+    coins, from_json, to_json_binary, Addr, Api, BankMsg, Binary, BlockInfo, CustomMsg,
+    CustomQuery, Empty, Querier, Storage, Uint128,
 };
 use cw_multi_test::error::{anyhow, AnyResult};
 use cw_multi_test::{AppResponse, CosmosRouter, MockApiBech32, Module};
-use neutron_sdk::bindings::msg::{IbcFee, NeutronMsg};
-use neutron_sdk::bindings::query::NeutronQuery;
+use neutron_sdk::bindings::msg::{IbcFee, MsgRegisterInterchainQueryResponse, NeutronMsg};
+use neutron_sdk::bindings::query::{NeutronQuery, QueryRegisteredQueryResultResponse};
+use neutron_sdk::bindings::types::{InterchainQueryResult, StorageValue};
+
+const ICQ_COUNT_KEY: &[u8] = b"mock_icq_count";
+
+fn icq_result_key(query_id: u64) -> Vec<u8> {
+    format!("mock_icq_result_{query_id}").into_bytes()
+}
+
+/// Mocks a KV interchain query result which holds a single value
+pub fn set_icq_result(storage: &mut dyn Storage, query_id: u64, height: u64, value: Uint128) {
+    let result = InterchainQueryResult {
+        kv_results: vec![StorageValue {
+            storage_prefix: "wasm".to_string(),
+            key: Binary::default(),
+            value: to_json_binary(&value).unwrap(),
+        }],
+        height,
+        revision: 1,
+    };
+    storage.set(&icq_result_key(query_id), &to_json_binary(&result).unwrap());
+}
 use neutron_sdk::query::min_ibc_fee::MinIbcFeeResponse;
 
 pub struct MockNeutronModule {
@@ -41,6 +62,21 @@ impl Module for MockNeutronModule {
         QueryC: CustomQuery + DeserializeOwned + 'static,
     {
         match msg {
+            NeutronMsg::RegisterInterchainQuery { .. } => {
+                let query_id = storage
+                    .get(ICQ_COUNT_KEY)
+                    .map(|v| from_json::<u64>(v).unwrap())
+                    .unwrap_or_default()
+                    + 1;
+                storage.set(ICQ_COUNT_KEY, &to_json_binary(&query_id)?);
+
+                return Ok(AppResponse {
+                    events: vec![],
+                    data: Some(to_json_binary(&MsgRegisterInterchainQueryResponse {
+                        id: query_id,
+                    })?),
+                });
+            }
             NeutronMsg::IbcTransfer { token, .. } => {
                 router.execute(
                     api,
@@ -63,7 +99,7 @@ impl Module for MockNeutronModule {
     fn query(
         &self,
         _api: &dyn Api,
-        _storage: &dyn Storage,
+        storage: &dyn Storage,
         _querier: &dyn Querier,
         _block: &BlockInfo,
         request: Self::QueryT,
@@ -77,6 +113,15 @@ impl Module for MockNeutronModule {
                 },
             })
             .map_err(Into::into),
+            NeutronQuery::InterchainQueryResult { query_id } => storage
+                .get(&icq_result_key(*query_id))
+                .ok_or_else(|| anyhow!("No result for interchain query {query_id}"))
+                .and_then(|result| {
+                    to_json_binary(&QueryRegisteredQueryResultResponse {
+                        result: from_json(result)?,
+                    })
+                    .map_err(Into::into)
+                }),
             _ => Err(anyhow!("Unknown query: {request:?}")),
         }
     }
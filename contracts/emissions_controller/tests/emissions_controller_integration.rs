@@ -1,27 +1,43 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 
+use astroport::incentives::MAX_REWARD_TOKENS;
 use astroport::{asset::AssetInfo, common::LP_SUBDENOM, incentives::RewardType};
-use cosmwasm_std::{coin, coins, Addr, Decimal, Decimal256, Empty, Event, Uint128};
-use cw_multi_test::Executor;
+use cosmwasm_std::{
+    attr, coin, coins, from_json, to_json_binary, to_json_string, Addr, BankMsg, CosmosMsg,
+    Decimal, Decimal256, Empty, Event, Uint128,
+};
+use cw_multi_test::{AppResponse, Executor};
 use cw_utils::PaymentError;
 use itertools::Itertools;
 use neutron_sdk::sudo::msg::{RequestPacket, TransferSudoMsg};
 
 use astroport_emissions_controller::error::ContractError;
 use astroport_emissions_controller::utils::get_epoch_start;
-use astroport_governance::assembly::{ProposalVoteOption, ProposalVoterResponse};
-use astroport_governance::emissions_controller::consts::{DAY, EPOCH_LENGTH};
+use astroport_governance::assembly::{
+    ProposalStatus, ProposalTemplate, ProposalVoteOption, ProposalVoterResponse,
+};
+use astroport_governance::emissions_controller::consts::{
+    DAY, EPOCH_LENGTH, IBC_TIMEOUT, MAX_PACKET_ATTEMPTS, MAX_PAGE_LIMIT, OUTPOST_PERMIT_ALL,
+    OUTPOST_PERMIT_WHITELIST_POOL, PACKET_RETRY_BACKOFF, USER_VOTES_HISTORY_EPOCHS,
+};
 use astroport_governance::emissions_controller::hub::{
-    AstroPoolConfig, EmissionsState, HubMsg, OutpostInfo, OutpostParams, OutpostStatus, TuneInfo,
-    UserInfoResponse, VotedPoolInfo,
+    AstroPoolConfig, AstroPoolEmissions, ChunkedDelivery, DeliveryStatus, EmissionsState,
+    FlowStatsResponse, HubMsg, OutpostExecutionStatus, OutpostFailures, OutpostFlow, OutpostInfo,
+    OutpostParams, OutpostProposal, OutpostStatus, OutpostSubmission, OutpostSubmissionStatus,
+    PacketRetryStatus, PoolStatusResponse, PoolTvl, PoolWeight, ProposalNotification,
+    SimulateVoteResponse, SkipReason, SkippedPool, TuneInfo, UpdateConfigMsg, UserEpochVotes,
+    UserInfoResponse, VotedPoolInfo, WhitelistBid,
+};
+use astroport_governance::emissions_controller::msg::{
+    AckError, AckErrorCode, ExecuteMsg, IbcAckResult, VxAstroIbcMsg,
 };
-use astroport_governance::emissions_controller::msg::{ExecuteMsg, VxAstroIbcMsg};
+use astroport_governance::emissions_controller::outpost::ProposalTally;
 use astroport_governance::utils::determine_ics20_escrow_address;
 use astroport_governance::{assembly, emissions_controller, voting_escrow};
-use astroport_voting_escrow::state::UNLOCK_PERIOD;
 
-use crate::common::helper::{ControllerHelper, PROPOSAL_VOTING_PERIOD};
+use crate::common::contracts::mock_subscriber_contract;
+use crate::common::helper::{ControllerHelper, PROPOSAL_REQUIRED_DEPOSIT, PROPOSAL_VOTING_PERIOD};
 
 mod common;
 
@@ -49,6 +65,7 @@ pub fn voting_test() {
         params: None,
         astro_pool_config: None,
         jailed: false,
+        permissions: OUTPOST_PERMIT_ALL,
     };
     helper.add_outpost("neutron", neutron).unwrap();
 
@@ -174,8 +191,11 @@ fn test_whitelist_blacklist() {
         astro_pool_config: Some(AstroPoolConfig {
             astro_pool: astro_pool.clone(),
             constant_emissions: Uint128::one(),
+            extra_pools: vec![],
+            emissions_cap: None,
         }),
         jailed: false,
+        permissions: OUTPOST_PERMIT_ALL,
     };
     helper.add_outpost("neutron", neutron).unwrap();
 
@@ -381,8 +401,11 @@ fn test_outpost_management() {
         astro_pool_config: Some(AstroPoolConfig {
             astro_pool: "wasm1pool".to_string(),
             constant_emissions: Uint128::one(),
+            extra_pools: vec![],
+            emissions_cap: None,
         }),
         jailed: false,
+        permissions: OUTPOST_PERMIT_ALL,
     };
 
     let err = helper
@@ -395,6 +418,7 @@ fn test_outpost_management() {
                 astro_denom: neutron.astro_denom.clone(),
                 outpost_params: None,
                 astro_pool_config: neutron.astro_pool_config.clone(),
+                permissions: None,
             }),
             &[],
         )
@@ -455,9 +479,13 @@ fn test_outpost_management() {
             voting_channel: "channel-1".to_string(),
             ics20_channel: "channel-2".to_string(),
             escrow_address: osmo_escrow_address,
+            ibc_timeout: None,
+            relayer_fee: None,
+            pools_per_packet: None,
         }),
         astro_pool_config: None,
         jailed: false,
+        permissions: OUTPOST_PERMIT_ALL,
     };
 
     let err = helper.add_outpost("osmo", osmosis.clone()).unwrap_err();
@@ -495,6 +523,19 @@ fn test_outpost_management() {
     );
 
     osmosis.params.as_mut().unwrap().emissions_controller = "osmo1controller".to_string();
+    osmosis.params.as_mut().unwrap().ibc_timeout = Some(60);
+
+    let err = helper.add_outpost("osmo", osmosis.clone()).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidIbcTimeout {
+            min: 300,
+            max: DAY * 7
+        }
+    );
+
+    osmosis.params.as_mut().unwrap().ibc_timeout = Some(DAY);
+    osmosis.params.as_mut().unwrap().relayer_fee = Some(Uint128::new(10_000));
     helper.add_outpost("osmo", osmosis.clone()).unwrap();
 
     let mut outposts = helper
@@ -557,6 +598,8 @@ fn test_outpost_management() {
     osmosis.astro_pool_config = Some(AstroPoolConfig {
         astro_pool: osmosis_astro_pool,
         constant_emissions: Uint128::from(100000u128),
+        extra_pools: vec![],
+        emissions_cap: None,
     });
     helper.add_outpost("osmo", osmosis.clone()).unwrap();
 
@@ -674,6 +717,7 @@ fn test_outpost_management() {
                 astro_denom: neutron.astro_denom.clone(),
                 outpost_params: None,
                 astro_pool_config: None,
+                permissions: None,
             }),
             &[],
         )
@@ -785,8 +829,11 @@ fn test_tune_only_hub() {
         astro_pool_config: Some(AstroPoolConfig {
             astro_pool: astro_pool.clone(),
             constant_emissions: 1_000_000_000u128.into(),
+            extra_pools: vec![],
+            emissions_cap: None,
         }),
         jailed: false,
+        permissions: OUTPOST_PERMIT_ALL,
     };
     helper.add_outpost("neutron", neutron.clone()).unwrap();
 
@@ -949,12 +996,14 @@ fn test_tune_only_hub() {
             .collect(),
         )]),
         outpost_emissions_statuses: Default::default(),
+        outpost_chunk_statuses: Default::default(),
         emissions_state: EmissionsState {
             xastro_rate: Decimal::from_str("499501.4995004995004995").unwrap(),
             collected_astro: 499999999999u128.into(),
             ema: 366666666664u128.into(),
             emissions_amount: 293333333331u128.into(),
         },
+        skipped_pools: vec![],
     };
     assert_eq!(tune_info, expected_tune_info);
 
@@ -978,65 +1027,105 @@ fn test_tune_only_hub() {
             .collect(),
         )]),
         outpost_emissions_statuses: Default::default(),
+        outpost_chunk_statuses: Default::default(),
         emissions_state: EmissionsState {
             xastro_rate: Decimal::one(),
             collected_astro: 0u128.into(),
             ema: 99999999999u128.into(),
             emissions_amount: 267200000000u128.into(),
         },
+        skipped_pools: vec![],
     };
     assert_eq!(tune_info, expected_tune_info);
 }
 
 #[test]
-fn test_tune_outpost() {
+fn test_multiple_astro_pools() {
     let mut helper = ControllerHelper::new();
     let owner = helper.owner.clone();
 
-    let lp_token1 = "factory/osmo1pool1/astroport/share";
-    let lp_token2 = "factory/osmo1pool2/astroport/share";
-    let astro_pool = "factory/osmo1astropool/astroport/share";
+    let lp_token = helper.create_pair("token1", "token2");
+    let astro_usdc_pool = helper
+        .create_pair(helper.astro.clone().as_str(), "uusdc")
+        .to_string();
+    let astro_atom_pool = helper
+        .create_pair(helper.astro.clone().as_str(), "uatom")
+        .to_string();
 
-    let osmosis = OutpostInfo {
-        astro_denom: "ibc/6569E05DEE32B339D9286A52BE33DFCEFC97267F23EF9CFDE0C055140967A9A5"
-            .to_string(),
-        params: Some(OutpostParams {
-            emissions_controller: "osmo1emissionscontroller".to_string(),
-            voting_channel: "channel-1".to_string(),
-            ics20_channel: "channel-2".to_string(),
-            escrow_address: Addr::unchecked(""),
-        }),
+    let mut neutron = OutpostInfo {
+        astro_denom: helper.astro.clone(),
+        params: None,
         astro_pool_config: Some(AstroPoolConfig {
-            astro_pool: astro_pool.to_string(),
+            astro_pool: astro_usdc_pool.clone(),
             constant_emissions: 1_000_000_000u128.into(),
+            extra_pools: vec![AstroPoolEmissions {
+                pool: "wasm1pool".to_string(),
+                constant_emissions: 500_000_000u128.into(),
+            }],
+            emissions_cap: None,
         }),
         jailed: false,
+        permissions: OUTPOST_PERMIT_ALL,
     };
-    helper.add_outpost("osmo", osmosis.clone()).unwrap();
 
+    let err = helper.add_outpost("neutron", neutron.clone()).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidOutpostPrefix("wasm1pool".to_string())
+    );
+
+    let astro_pool_config = neutron.astro_pool_config.as_mut().unwrap();
+    astro_pool_config.extra_pools[0].pool = astro_usdc_pool.clone();
+    let err = helper.add_outpost("neutron", neutron.clone()).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::DuplicatedAstroPool(astro_usdc_pool.clone())
+    );
+
+    let astro_pool_config = neutron.astro_pool_config.as_mut().unwrap();
+    astro_pool_config.extra_pools[0].pool = astro_atom_pool.clone();
+    astro_pool_config.extra_pools[0].constant_emissions = Uint128::zero();
+    let err = helper.add_outpost("neutron", neutron.clone()).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ZeroAstroEmissions {}
+    );
+
+    let astro_pool_config = neutron.astro_pool_config.as_mut().unwrap();
+    astro_pool_config.extra_pools[0].constant_emissions = 500_000_000u128.into();
+    astro_pool_config.emissions_cap = Some(Uint128::zero());
+    let err = helper.add_outpost("neutron", neutron.clone()).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ZeroAstroEmissionsCap {}
+    );
+
+    neutron.astro_pool_config.as_mut().unwrap().emissions_cap = None;
+    helper.add_outpost("neutron", neutron.clone()).unwrap();
+
+    // Neither of ASTRO pools can be whitelisted
+    let user = helper.app.api().addr_make("user");
     let whitelist_fee = helper.whitelisting_fee.clone();
-    for pool in [lp_token1, lp_token2] {
-        helper
-            .mint_tokens(&owner, &[whitelist_fee.clone()])
-            .unwrap();
-        helper
-            .whitelist(&owner, pool, &[whitelist_fee.clone()])
-            .unwrap();
+    helper.mint_tokens(&user, &[whitelist_fee.clone()]).unwrap();
+    for pool in [&astro_usdc_pool, &astro_atom_pool] {
+        let err = helper
+            .whitelist(&user, pool, &[whitelist_fee.clone()])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::IsAstroPool {}
+        );
     }
 
-    let user = helper.app.api().addr_make("user");
+    helper
+        .whitelist(&user, &lp_token, &[whitelist_fee.clone()])
+        .unwrap();
     helper.lock(&user, 1000).unwrap();
-
     helper
-        .vote(
-            &user,
-            &[
-                (lp_token1.to_string(), Decimal::percent(50)),
-                (lp_token2.to_string(), Decimal::percent(50)),
-            ],
-        )
+        .vote(&user, &[(lp_token.to_string(), Decimal::one())])
         .unwrap();
 
+    helper.timetravel(EPOCH_LENGTH);
     helper
         .mint_tokens(
             &helper.emission_controller.clone(),
@@ -1044,471 +1133,5213 @@ fn test_tune_outpost() {
         )
         .unwrap();
 
-    helper.timetravel(EPOCH_LENGTH);
-    helper.tune(&owner).unwrap();
-
-    let epoch_start = get_epoch_start(helper.app.block_info().time.seconds());
-    let mut tune_info = helper.query_tune_info(None).unwrap();
-    tune_info
-        .pools_grouped
-        .iter_mut()
-        .for_each(|(_, pools)| pools.sort());
-    let expected_tune_info = TuneInfo {
-        tune_ts: epoch_start,
-        pools_grouped: HashMap::from([(
-            "osmo".to_string(),
-            vec![
-                (lp_token1.to_string(), Uint128::new(133600000000)),
-                (lp_token2.to_string(), Uint128::new(133600000000)),
-                (astro_pool.to_string(), Uint128::new(1000000000)),
-            ]
-            .into_iter()
-            .sorted()
-            .collect(),
-        )]),
-        outpost_emissions_statuses: HashMap::from([(
-            "osmo".to_string(),
-            OutpostStatus::InProgress,
-        )]),
-        emissions_state: EmissionsState {
-            xastro_rate: Decimal::one(),
-            collected_astro: 0u128.into(),
-            ema: 99999999999u128.into(),
-            emissions_amount: 267200000000u128.into(),
-        },
+    // Each ASTRO pool receives its own flat emissions
+    let astro_pools = |helper: &ControllerHelper| {
+        helper.query_simulate_tune().unwrap().next_pools_grouped["neutron"]
+            .iter()
+            .filter(|(pool, _)| pool != &lp_token.to_string())
+            .cloned()
+            .collect_vec()
     };
-    assert_eq!(tune_info, expected_tune_info);
+    assert_eq!(
+        astro_pools(&helper),
+        vec![
+            (astro_usdc_pool.clone(), Uint128::new(1_000_000_000)),
+            (astro_atom_pool.clone(), Uint128::new(500_000_000)),
+        ]
+        .into_iter()
+        .sorted()
+        .collect_vec()
+    );
 
-    // Try to retry outposts which are being in progress
-    let err = helper.retry_failed_outposts(&owner).unwrap_err();
+    // The aggregate cap scales ASTRO pools emissions down proportionally
+    neutron.astro_pool_config.as_mut().unwrap().emissions_cap = Some(900_000_000u128.into());
+    helper.add_outpost("neutron", neutron.clone()).unwrap();
     assert_eq!(
-        err.downcast::<ContractError>().unwrap(),
-        ContractError::NoFailedOutpostsToRetry {}
+        astro_pools(&helper),
+        vec![
+            (astro_usdc_pool.clone(), Uint128::new(600_000_000)),
+            (astro_atom_pool.clone(), Uint128::new(300_000_000)),
+        ]
+        .into_iter()
+        .sorted()
+        .collect_vec()
     );
 
-    // Mock ics20 IBC timeout
+    helper.tune(&owner).unwrap();
+
+    let epoch_start = get_epoch_start(helper.app.block_info().time.seconds());
+    for (pool, amount) in [
+        (&astro_usdc_pool, 600_000_000u128),
+        (&astro_atom_pool, 300_000_000),
+    ] {
+        let rewards = helper.query_rewards(pool).unwrap();
+        assert_eq!(rewards.len(), 1);
+        assert_eq!(rewards[0].rps, Decimal256::from_ratio(amount, EPOCH_LENGTH));
+        assert_eq!(
+            rewards[0].reward,
+            RewardType::Ext {
+                info: AssetInfo::native(&helper.astro),
+                next_update_ts: epoch_start + EPOCH_LENGTH
+            }
+        );
+    }
+}
+
+#[test]
+fn test_new_pool_cooldown() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+
     helper
         .app
-        .wasm_sudo(
+        .execute_contract(
+            owner.clone(),
             helper.emission_controller.clone(),
-            &TransferSudoMsg::Timeout {
-                request: RequestPacket {
-                    sequence: None,
-                    source_port: None,
-                    source_channel: Some("channel-2".to_string()),
-                    destination_port: None,
-                    destination_channel: None,
-                    data: None,
-                    timeout_height: None,
-                    timeout_timestamp: None,
-                },
-            },
+            &ExecuteMsg::Custom(HubMsg::UpdateConfig(Box::new(UpdateConfigMsg {
+                new_pool_cooldown_epochs: Some(1),
+                ..Default::default()
+            }))),
+            &[],
         )
         .unwrap();
 
-    // Try to mock ics20 message failure right after timeout even tho this must be impossible
-    let err = helper
-        .app
-        .wasm_sudo(
-            helper.emission_controller.clone(),
-            &TransferSudoMsg::Error {
-                request: RequestPacket {
-                    sequence: None,
-                    source_port: None,
-                    source_channel: Some("channel-2".to_string()),
-                    destination_port: None,
-                    destination_channel: None,
-                    data: None,
-                    timeout_height: None,
-                    timeout_timestamp: None,
-                },
-                details: "".to_string(),
-            },
-        )
-        .unwrap_err();
+    let lp_token1 = helper.create_pair("token1", "token2");
+    let lp_token2 = helper.create_pair("token1", "token3");
+
+    let neutron = OutpostInfo {
+        astro_denom: helper.astro.clone(),
+        params: None,
+        astro_pool_config: None,
+        jailed: false,
+        permissions: OUTPOST_PERMIT_ALL,
+    };
+    helper.add_outpost("neutron", neutron).unwrap();
+
+    let user = helper.app.api().addr_make("user");
+    let whitelist_fee = helper.whitelisting_fee.clone();
+    helper.mint_tokens(&user, &[whitelist_fee.clone()]).unwrap();
+    helper
+        .whitelist(&user, &lp_token1, &[whitelist_fee.clone()])
+        .unwrap();
+
+    let epoch_start = get_epoch_start(helper.app.block_info().time.seconds());
+    let pool_status = helper.query_pool_status(&lp_token1).unwrap();
     assert_eq!(
-        err.root_cause().to_string(),
-        "Generic error: Outpost osmo is not in progress"
+        pool_status,
+        PoolStatusResponse {
+            is_whitelisted: true,
+            is_blacklisted: false,
+            whitelisted_ts: Some(helper.app.block_info().time.seconds()),
+            eligible_from: Some(epoch_start + 2 * EPOCH_LENGTH),
+            voting_power: Uint128::zero(),
+        }
     );
 
-    // Retry failed outposts and mock IBC acknowledgment packet
-    helper.retry_failed_outposts(&owner).unwrap();
+    helper.lock(&user, 1000).unwrap();
     helper
-        .app
-        .wasm_sudo(
-            helper.emission_controller.clone(),
-            &TransferSudoMsg::Response {
-                request: RequestPacket {
-                    sequence: None,
-                    source_port: None,
-                    source_channel: Some("channel-2".to_string()),
-                    destination_port: None,
-                    destination_channel: None,
-                    data: None,
-                    timeout_height: None,
-                    timeout_timestamp: None,
-                },
-                data: Default::default(),
-            },
+        .vote(&user, &[(lp_token1.to_string(), Decimal::one())])
+        .unwrap();
+
+    helper
+        .mint_tokens(
+            &helper.emission_controller.clone(),
+            &coins(50_000_000_000_000, helper.astro.clone()),
         )
         .unwrap();
 
-    helper.timetravel(10000);
+    helper.timetravel(EPOCH_LENGTH);
 
-    let mut tune_info = helper.query_tune_info(None).unwrap();
-    tune_info
+    // Pool is still in the cooldown period. It doesn't receive emissions
+    let sim_tune_result = helper.query_simulate_tune().unwrap();
+    assert!(sim_tune_result.next_pools_grouped.is_empty());
+    helper.tune(&owner).unwrap();
+    assert!(helper
+        .query_tune_info(None)
+        .unwrap()
         .pools_grouped
-        .iter_mut()
-        .for_each(|(_, pools)| pools.sort());
-    let expected_tune_info = TuneInfo {
-        tune_ts: epoch_start,
-        pools_grouped: HashMap::from([(
-            "osmo".to_string(),
-            vec![
-                (lp_token1.to_string(), Uint128::new(133600000000)),
-                (lp_token2.to_string(), Uint128::new(133600000000)),
-                (astro_pool.to_string(), Uint128::new(1000000000)),
-            ]
-            .into_iter()
-            .sorted()
-            .collect(),
-        )]),
-        outpost_emissions_statuses: HashMap::from([("osmo".to_string(), OutpostStatus::Done)]),
-        emissions_state: EmissionsState {
-            xastro_rate: Decimal::one(),
-            collected_astro: 0u128.into(),
-            ema: 99999999999u128.into(),
-            emissions_amount: 267200000000u128.into(),
-        },
-    };
-    assert_eq!(tune_info, expected_tune_info);
+        .is_empty());
 
-    // Confirm there is no outposts to retry
+    // However, it keeps its votes and place in the whitelist
+    assert_eq!(helper.query_whitelist().unwrap(), vec![lp_token1.clone()]);
+    assert_eq!(
+        helper.query_pool_status(&lp_token1).unwrap().voting_power,
+        Uint128::new(1000)
+    );
+
+    // Whitelist one more pool. Voting for it is allowed right away
+    helper.mint_tokens(&user, &[whitelist_fee.clone()]).unwrap();
+    helper
+        .whitelist(&user, &lp_token2, &[whitelist_fee.clone()])
+        .unwrap();
+    helper.timetravel(1);
+    helper
+        .vote(
+            &user,
+            &[
+                (lp_token1.to_string(), Decimal::percent(50)),
+                (lp_token2.to_string(), Decimal::percent(50)),
+            ],
+        )
+        .unwrap();
+
+    helper.timetravel(EPOCH_LENGTH);
+    helper.tune(&owner).unwrap();
+
+    // The first pool passed the cooldown, the second pool didn't
+    let tune_info = helper.query_tune_info(None).unwrap();
+    let neutron_pools = tune_info.pools_grouped.get("neutron").unwrap();
+    assert_eq!(neutron_pools.len(), 1);
+    assert_eq!(neutron_pools[0].0, lp_token1);
+
+    helper.timetravel(EPOCH_LENGTH);
+    helper.tune(&owner).unwrap();
+
+    // Now both pools receive emissions
+    let tune_info = helper.query_tune_info(None).unwrap();
+    assert_eq!(tune_info.pools_grouped.get("neutron").unwrap().len(), 2);
+}
+
+#[test]
+fn test_max_weight_per_pool() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+
+    helper
+        .app
+        .execute_contract(
+            owner.clone(),
+            helper.emission_controller.clone(),
+            &ExecuteMsg::Custom(HubMsg::UpdateConfig(Box::new(UpdateConfigMsg {
+                max_weight_per_pool: Some(Decimal::percent(50)),
+                ..Default::default()
+            }))),
+            &[],
+        )
+        .unwrap();
+
+    let lp_token1 = helper.create_pair("token1", "token2");
+    let lp_token2 = helper.create_pair("token1", "token3");
+
+    let neutron = OutpostInfo {
+        astro_denom: helper.astro.clone(),
+        params: None,
+        astro_pool_config: None,
+        jailed: false,
+        permissions: OUTPOST_PERMIT_ALL,
+    };
+    helper.add_outpost("neutron", neutron).unwrap();
+
+    let user = helper.app.api().addr_make("user");
+
+    let whitelist_fee = helper.whitelisting_fee.clone();
+    for pool in &[lp_token1.clone(), lp_token2.clone()] {
+        helper.mint_tokens(&user, &[whitelist_fee.clone()]).unwrap();
+        helper
+            .whitelist(&user, pool, &[whitelist_fee.clone()])
+            .unwrap();
+    }
+
+    helper.lock(&user, 1000).unwrap();
+
+    let err = helper
+        .vote(&user, &[(lp_token1.to_string(), Decimal::percent(60))])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PoolWeightExceedsLimit {
+            pool: lp_token1.to_string(),
+            max_weight: Decimal::percent(50)
+        }
+    );
+
+    helper
+        .vote(
+            &user,
+            &[
+                (lp_token1.to_string(), Decimal::percent(50)),
+                (lp_token2.to_string(), Decimal::percent(50)),
+            ],
+        )
+        .unwrap();
+
+    assert_eq!(
+        helper.query_config().unwrap().max_weight_per_pool,
+        Some(Decimal::percent(50))
+    );
+}
+
+#[test]
+fn test_user_votes_history() {
+    let mut helper = ControllerHelper::new();
+
+    let lp_token1 = helper.create_pair("token1", "token2");
+    let lp_token2 = helper.create_pair("token1", "token3");
+
+    let neutron = OutpostInfo {
+        astro_denom: helper.astro.clone(),
+        params: None,
+        astro_pool_config: None,
+        jailed: false,
+        permissions: OUTPOST_PERMIT_ALL,
+    };
+    helper.add_outpost("neutron", neutron).unwrap();
+
+    let user = helper.app.api().addr_make("user");
+
+    let whitelist_fee = helper.whitelisting_fee.clone();
+    for pool in &[lp_token1.clone(), lp_token2.clone()] {
+        helper.mint_tokens(&user, &[whitelist_fee.clone()]).unwrap();
+        helper
+            .whitelist(&user, pool, &[whitelist_fee.clone()])
+            .unwrap();
+    }
+
+    helper.lock(&user, 1000).unwrap();
+
+    let query_history = |helper: &ControllerHelper, start_after: Option<u64>, limit: Option<u8>| {
+        helper
+            .app
+            .wrap()
+            .query_wasm_smart::<Vec<UserEpochVotes>>(
+                &helper.emission_controller,
+                &emissions_controller::hub::QueryMsg::UserVotesHistory {
+                    user: user.to_string(),
+                    limit,
+                    start_after,
+                },
+            )
+            .unwrap()
+    };
+    assert_eq!(query_history(&helper, None, None), vec![]);
+
+    let first_epoch = get_epoch_start(helper.app.block_info().time.seconds());
+    let epochs = USER_VOTES_HISTORY_EPOCHS + 2;
+    for i in 0..epochs {
+        let pool = if i % 2 == 0 { &lp_token1 } else { &lp_token2 };
+        helper
+            .vote(&user, &[(pool.to_string(), Decimal::one())])
+            .unwrap();
+        helper.timetravel(EPOCH_LENGTH);
+    }
+
+    // Only the latest epochs are kept
+    let history = query_history(&helper, None, Some(MAX_PAGE_LIMIT));
+    assert_eq!(history.len() as u64, USER_VOTES_HISTORY_EPOCHS);
+    assert_eq!(history[0].epoch_start, first_epoch + 2 * EPOCH_LENGTH);
+    assert_eq!(
+        history.last().unwrap(),
+        &UserEpochVotes {
+            epoch_start: first_epoch + (epochs - 1) * EPOCH_LENGTH,
+            vote_ts: history.last().unwrap().vote_ts,
+            voting_power: 1000u128.into(),
+            votes: vec![(lp_token2.to_string(), Decimal::one())],
+            voted_by: None,
+        }
+    );
+
+    // Pagination
+    let page = query_history(&helper, Some(history[0].epoch_start), Some(2));
+    assert_eq!(page, history[1..3].to_vec());
+}
+
+#[test]
+fn test_retract_vote() {
+    let mut helper = ControllerHelper::new();
+
+    let lp_token1 = helper.create_pair("token1", "token2");
+    let lp_token2 = helper.create_pair("token1", "token3");
+
+    let neutron = OutpostInfo {
+        astro_denom: helper.astro.clone(),
+        params: None,
+        astro_pool_config: None,
+        jailed: false,
+        permissions: OUTPOST_PERMIT_ALL,
+    };
+    helper.add_outpost("neutron", neutron).unwrap();
+
+    let user = helper.app.api().addr_make("user");
+
+    let whitelist_fee = helper.whitelisting_fee.clone();
+    for pool in &[lp_token1.clone(), lp_token2.clone()] {
+        helper.mint_tokens(&user, &[whitelist_fee.clone()]).unwrap();
+        helper
+            .whitelist(&user, pool, &[whitelist_fee.clone()])
+            .unwrap();
+    }
+
+    helper.lock(&user, 1000).unwrap();
+
+    let err = helper.retract_vote(&user, &lp_token1).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NoVoteForPool(lp_token1.clone())
+    );
+
+    helper
+        .vote(
+            &user,
+            &[
+                (lp_token1.to_string(), Decimal::percent(40)),
+                (lp_token2.to_string(), Decimal::percent(60)),
+            ],
+        )
+        .unwrap();
+    let vote_ts = helper.user_info(&user, None).unwrap().vote_ts;
+
+    helper.timetravel(1);
+    helper.retract_vote(&user, &lp_token1).unwrap();
+
+    assert_eq!(
+        helper.query_pool_vp(&lp_token1, None).unwrap(),
+        Uint128::zero()
+    );
+    assert_eq!(
+        helper.query_pool_vp(&lp_token2, None).unwrap(),
+        Uint128::new(600)
+    );
+
+    let user_info = helper.user_info(&user, None).unwrap();
+    assert_eq!(user_info.vote_ts, vote_ts);
+    assert_eq!(
+        user_info.votes,
+        HashMap::from([(lp_token2.clone(), Decimal::percent(60))])
+    );
+    assert_eq!(user_info.applied_votes, user_info.votes);
+
+    let history = helper
+        .app
+        .wrap()
+        .query_wasm_smart::<Vec<UserEpochVotes>>(
+            &helper.emission_controller,
+            &emissions_controller::hub::QueryMsg::UserVotesHistory {
+                user: user.to_string(),
+                limit: None,
+                start_after: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        history[0].votes,
+        vec![(lp_token2.clone(), Decimal::percent(60))]
+    );
+
+    // Retraction doesn't lift the vote cooldown
+    let err = helper
+        .vote(&user, &[(lp_token1.to_string(), Decimal::one())])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::VoteCooldown(get_epoch_start(vote_ts) + EPOCH_LENGTH)
+    );
+
+    // Voting power changes are applied only to the remaining votes
+    helper.lock(&user, 1000).unwrap();
+    assert_eq!(
+        helper.query_pool_vp(&lp_token1, None).unwrap(),
+        Uint128::zero()
+    );
+    assert_eq!(
+        helper.query_pool_vp(&lp_token2, None).unwrap(),
+        Uint128::new(1200)
+    );
+
+    let err = helper.retract_vote(&user, &lp_token1).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NoVoteForPool(lp_token1.clone())
+    );
+}
+
+#[test]
+fn test_tune_incentive() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+    let astro = helper.astro.clone();
+
+    helper
+        .app
+        .execute_contract(
+            owner.clone(),
+            helper.emission_controller.clone(),
+            &ExecuteMsg::Custom(HubMsg::UpdateConfig(Box::new(UpdateConfigMsg {
+                tune_incentive: Some(10_000000u128.into()),
+                ..Default::default()
+            }))),
+            &[],
+        )
+        .unwrap();
+
+    let lp_token = helper.create_pair("token1", "token2");
+    let neutron = OutpostInfo {
+        astro_denom: astro.clone(),
+        params: None,
+        astro_pool_config: None,
+        jailed: false,
+        permissions: OUTPOST_PERMIT_ALL,
+    };
+    helper.add_outpost("neutron", neutron).unwrap();
+
+    let user = helper.app.api().addr_make("user");
+    let whitelist_fee = helper.whitelisting_fee.clone();
+    helper.mint_tokens(&user, &[whitelist_fee.clone()]).unwrap();
+    helper
+        .whitelist(&user, &lp_token, &[whitelist_fee.clone()])
+        .unwrap();
+    helper.lock(&user, 1000).unwrap();
+    helper
+        .vote(&user, &[(lp_token.to_string(), Decimal::one())])
+        .unwrap();
+
+    helper.timetravel(EPOCH_LENGTH);
+    helper
+        .mint_tokens(
+            &helper.emission_controller.clone(),
+            &coins(50_000_000_000_000, &astro),
+        )
+        .unwrap();
+
+    let keeper = helper.app.api().addr_make("keeper");
+    let resp = helper.tune(&keeper).unwrap();
+    assert!(resp.has_event(
+        &Event::new("wasm-tune_incentive")
+            .add_attribute("keeper", keeper.as_str())
+            .add_attribute("amount", "10000000")
+    ));
+    let balance = helper.app.wrap().query_balance(&keeper, &astro).unwrap();
+    assert_eq!(balance.amount.u128(), 10_000000);
+
+    // The incentive is deducted from the epoch emissions
+    let emissions_amount = helper
+        .query_tune_info(None)
+        .unwrap()
+        .emissions_state
+        .emissions_amount;
+    let incentivized = helper
+        .app
+        .wrap()
+        .query_balance(&helper.incentives, &astro)
+        .unwrap();
+    assert_eq!(
+        incentivized.amount,
+        emissions_amount - Uint128::new(10_000000)
+    );
+
+    // Keeper is not paid if tuning is not executed
+    helper.tune(&keeper).unwrap_err();
+    let balance = helper.app.wrap().query_balance(&keeper, &astro).unwrap();
+    assert_eq!(balance.amount.u128(), 10_000000);
+}
+
+#[test]
+fn test_tvl_weighted_emissions() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+    let tvl_oracle = helper.app.api().addr_make("tvl_oracle");
+
+    let lp_token1 = helper.create_pair("token1", "token2");
+    let lp_token2 = helper.create_pair("token1", "token3");
+    let neutron = OutpostInfo {
+        astro_denom: helper.astro.clone(),
+        params: None,
+        astro_pool_config: None,
+        jailed: false,
+        permissions: OUTPOST_PERMIT_ALL,
+    };
+    helper.add_outpost("neutron", neutron).unwrap();
+
+    let user = helper.app.api().addr_make("user");
+    let whitelist_fee = helper.whitelisting_fee.clone();
+    for pool in [&lp_token1, &lp_token2] {
+        helper.mint_tokens(&user, &[whitelist_fee.clone()]).unwrap();
+        helper
+            .whitelist(&user, pool, &[whitelist_fee.clone()])
+            .unwrap();
+    }
+    helper.lock(&user, 1000).unwrap();
+    helper
+        .vote(
+            &user,
+            &[
+                (lp_token1.clone(), Decimal::percent(75)),
+                (lp_token2.clone(), Decimal::percent(25)),
+            ],
+        )
+        .unwrap();
+
+    let update_config_msg = |tvl_weight: Decimal| {
+        ExecuteMsg::Custom(HubMsg::UpdateConfig(Box::new(UpdateConfigMsg {
+            tvl_oracle: Some(tvl_oracle.to_string()),
+            tvl_weight: Some(tvl_weight),
+            tvl_max_age: Some(DAY),
+            ..Default::default()
+        })))
+    };
+    let err = helper
+        .app
+        .execute_contract(
+            owner.clone(),
+            helper.emission_controller.clone(),
+            &update_config_msg(Decimal::percent(101)),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: tvl_weight must be within [0, 1]"
+    );
+    helper
+        .app
+        .execute_contract(
+            owner.clone(),
+            helper.emission_controller.clone(),
+            &update_config_msg(Decimal::percent(50)),
+            &[],
+        )
+        .unwrap();
+
+    helper.timetravel(EPOCH_LENGTH);
+    helper
+        .mint_tokens(
+            &helper.staking.clone(),
+            &coins(500_000_000_000, helper.astro.clone()),
+        )
+        .unwrap();
+    helper
+        .mint_tokens(
+            &helper.emission_controller.clone(),
+            &coins(50_000_000_000_000, helper.astro.clone()),
+        )
+        .unwrap();
+
+    let report_msg = ExecuteMsg::Custom(HubMsg::ReportPoolTvls {
+        tvls: vec![
+            (lp_token1.clone(), 100u128.into()),
+            (lp_token2.clone(), 300u128.into()),
+        ],
+    });
+    let err = helper
+        .app
+        .execute_contract(
+            user.clone(),
+            helper.emission_controller.clone(),
+            &report_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // No TVL reports yet. Emissions are weighted by votes only
+    let sim_tune_result = helper.query_simulate_tune().unwrap();
+    assert!(!sim_tune_result.tvl_weighted);
+    assert_eq!(
+        sim_tune_result.pool_weights,
+        vec![
+            PoolWeight {
+                pool: lp_token1.clone(),
+                vote_weight: Decimal::percent(75),
+                tvl_weight: Decimal::zero(),
+                weight: Decimal::percent(75),
+            },
+            PoolWeight {
+                pool: lp_token2.clone(),
+                vote_weight: Decimal::percent(25),
+                tvl_weight: Decimal::zero(),
+                weight: Decimal::percent(25),
+            }
+        ]
+    );
+
+    helper
+        .app
+        .execute_contract(
+            tvl_oracle.clone(),
+            helper.emission_controller.clone(),
+            &report_msg,
+            &[],
+        )
+        .unwrap();
+    let pool_tvl: Option<PoolTvl> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &helper.emission_controller,
+            &emissions_controller::hub::QueryMsg::PoolTvl {
+                pool: lp_token2.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        pool_tvl,
+        Some(PoolTvl {
+            tvl: 300u128.into(),
+            updated_at: helper.app.block_info().time.seconds()
+        })
+    );
+
+    // Votes and TVL are blended equally
+    let sim_tune_result = helper.query_simulate_tune().unwrap();
+    assert!(sim_tune_result.tvl_weighted);
+    assert_eq!(
+        sim_tune_result.pool_weights,
+        vec![
+            PoolWeight {
+                pool: lp_token1.clone(),
+                vote_weight: Decimal::percent(75),
+                tvl_weight: Decimal::percent(25),
+                weight: Decimal::percent(50),
+            },
+            PoolWeight {
+                pool: lp_token2.clone(),
+                vote_weight: Decimal::percent(25),
+                tvl_weight: Decimal::percent(75),
+                weight: Decimal::percent(50),
+            }
+        ]
+    );
+    let astro_per_pool =
+        sim_tune_result.new_emissions_state.emissions_amount * Decimal::percent(50);
+    let mut expected_pools = vec![
+        (lp_token1.clone(), astro_per_pool),
+        (lp_token2.clone(), astro_per_pool),
+    ];
+    expected_pools.sort();
+    assert_eq!(
+        sim_tune_result.next_pools_grouped,
+        HashMap::from([("neutron".to_string(), expected_pools)])
+    );
+
+    let resp = helper.tune(&owner).unwrap();
+    resp.assert_event(
+        &Event::new("wasm").add_attributes([("action", "tune_pools"), ("tvl_weighted", "true")]),
+    );
+
+    // Stale TVL reports are ignored
+    helper.timetravel(EPOCH_LENGTH);
+    let sim_tune_result = helper.query_simulate_tune().unwrap();
+    assert!(!sim_tune_result.tvl_weighted);
+    assert_eq!(sim_tune_result.pool_weights[0].weight, Decimal::percent(75));
+}
+
+#[test]
+fn test_tune_outpost() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+
+    let lp_token1 = "factory/osmo1pool1/astroport/share";
+    let lp_token2 = "factory/osmo1pool2/astroport/share";
+    let astro_pool = "factory/osmo1astropool/astroport/share";
+
+    let osmosis = OutpostInfo {
+        astro_denom: "ibc/6569E05DEE32B339D9286A52BE33DFCEFC97267F23EF9CFDE0C055140967A9A5"
+            .to_string(),
+        params: Some(OutpostParams {
+            emissions_controller: "osmo1emissionscontroller".to_string(),
+            voting_channel: "channel-1".to_string(),
+            ics20_channel: "channel-2".to_string(),
+            escrow_address: Addr::unchecked(""),
+            ibc_timeout: None,
+            relayer_fee: None,
+            pools_per_packet: None,
+        }),
+        astro_pool_config: Some(AstroPoolConfig {
+            astro_pool: astro_pool.to_string(),
+            constant_emissions: 1_000_000_000u128.into(),
+            extra_pools: vec![],
+            emissions_cap: None,
+        }),
+        jailed: false,
+        permissions: OUTPOST_PERMIT_ALL,
+    };
+    helper.add_outpost("osmo", osmosis.clone()).unwrap();
+
+    let whitelist_fee = helper.whitelisting_fee.clone();
+    for pool in [lp_token1, lp_token2] {
+        helper
+            .mint_tokens(&owner, &[whitelist_fee.clone()])
+            .unwrap();
+        helper
+            .whitelist(&owner, pool, &[whitelist_fee.clone()])
+            .unwrap();
+    }
+
+    let user = helper.app.api().addr_make("user");
+    helper.lock(&user, 1000).unwrap();
+
+    helper
+        .vote(
+            &user,
+            &[
+                (lp_token1.to_string(), Decimal::percent(50)),
+                (lp_token2.to_string(), Decimal::percent(50)),
+            ],
+        )
+        .unwrap();
+
+    helper
+        .mint_tokens(
+            &helper.emission_controller.clone(),
+            &coins(50_000_000_000_000, helper.astro.clone()),
+        )
+        .unwrap();
+
+    helper.timetravel(EPOCH_LENGTH);
+    helper.tune(&owner).unwrap();
+
+    let epoch_start = get_epoch_start(helper.app.block_info().time.seconds());
+    let mut tune_info = helper.query_tune_info(None).unwrap();
+    tune_info
+        .pools_grouped
+        .iter_mut()
+        .for_each(|(_, pools)| pools.sort());
+    let expected_tune_info = TuneInfo {
+        tune_ts: epoch_start,
+        pools_grouped: HashMap::from([(
+            "osmo".to_string(),
+            vec![
+                (lp_token1.to_string(), Uint128::new(133600000000)),
+                (lp_token2.to_string(), Uint128::new(133600000000)),
+                (astro_pool.to_string(), Uint128::new(1000000000)),
+            ]
+            .into_iter()
+            .sorted()
+            .collect(),
+        )]),
+        outpost_emissions_statuses: HashMap::from([(
+            "osmo".to_string(),
+            OutpostStatus::InProgress,
+        )]),
+        outpost_chunk_statuses: Default::default(),
+        emissions_state: EmissionsState {
+            xastro_rate: Decimal::one(),
+            collected_astro: 0u128.into(),
+            ema: 99999999999u128.into(),
+            emissions_amount: 267200000000u128.into(),
+        },
+        skipped_pools: vec![],
+    };
+    assert_eq!(tune_info, expected_tune_info);
+
+    // Try to retry outposts which are being in progress
+    let err = helper.retry_failed_outposts(&owner).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NoFailedOutpostsToRetry {}
+    );
+
+    // Mock ics20 IBC timeout
+    helper
+        .app
+        .wasm_sudo(
+            helper.emission_controller.clone(),
+            &TransferSudoMsg::Timeout {
+                request: RequestPacket {
+                    sequence: None,
+                    source_port: None,
+                    source_channel: Some("channel-2".to_string()),
+                    destination_port: None,
+                    destination_channel: None,
+                    data: None,
+                    timeout_height: None,
+                    timeout_timestamp: None,
+                },
+            },
+        )
+        .unwrap();
+
+    // Try to mock ics20 message failure right after timeout even tho this must be impossible
+    let err = helper
+        .app
+        .wasm_sudo(
+            helper.emission_controller.clone(),
+            &TransferSudoMsg::Error {
+                request: RequestPacket {
+                    sequence: None,
+                    source_port: None,
+                    source_channel: Some("channel-2".to_string()),
+                    destination_port: None,
+                    destination_channel: None,
+                    data: None,
+                    timeout_height: None,
+                    timeout_timestamp: None,
+                },
+                details: "".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Outpost osmo is not in progress"
+    );
+
+    // Retry failed outposts and mock IBC acknowledgment packet
+    helper.retry_failed_outposts(&owner).unwrap();
+    helper
+        .app
+        .wasm_sudo(
+            helper.emission_controller.clone(),
+            &TransferSudoMsg::Response {
+                request: RequestPacket {
+                    sequence: None,
+                    source_port: None,
+                    source_channel: Some("channel-2".to_string()),
+                    destination_port: None,
+                    destination_channel: None,
+                    data: None,
+                    timeout_height: None,
+                    timeout_timestamp: None,
+                },
+                data: Default::default(),
+            },
+        )
+        .unwrap();
+
+    helper.timetravel(10000);
+
+    let mut tune_info = helper.query_tune_info(None).unwrap();
+    tune_info
+        .pools_grouped
+        .iter_mut()
+        .for_each(|(_, pools)| pools.sort());
+    let expected_tune_info = TuneInfo {
+        tune_ts: epoch_start,
+        pools_grouped: HashMap::from([(
+            "osmo".to_string(),
+            vec![
+                (lp_token1.to_string(), Uint128::new(133600000000)),
+                (lp_token2.to_string(), Uint128::new(133600000000)),
+                (astro_pool.to_string(), Uint128::new(1000000000)),
+            ]
+            .into_iter()
+            .sorted()
+            .collect(),
+        )]),
+        outpost_emissions_statuses: HashMap::from([("osmo".to_string(), OutpostStatus::Done)]),
+        outpost_chunk_statuses: Default::default(),
+        emissions_state: EmissionsState {
+            xastro_rate: Decimal::one(),
+            collected_astro: 0u128.into(),
+            ema: 99999999999u128.into(),
+            emissions_amount: 267200000000u128.into(),
+        },
+        skipped_pools: vec![],
+    };
+    assert_eq!(tune_info, expected_tune_info);
+
+    // Confirm there is no outposts to retry
     let err = helper.retry_failed_outposts(&owner).unwrap_err();
     assert_eq!(
         err.downcast::<ContractError>().unwrap(),
-        ContractError::NoFailedOutpostsToRetry {}
+        ContractError::NoFailedOutpostsToRetry {}
+    );
+}
+
+#[test]
+fn test_tune_outpost_chunked() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+
+    let lp_token1 = "factory/osmo1pool1/astroport/share";
+    let lp_token2 = "factory/osmo1pool2/astroport/share";
+    let astro_pool = "factory/osmo1astropool/astroport/share";
+
+    let mut osmosis = OutpostInfo {
+        astro_denom: "ibc/6569E05DEE32B339D9286A52BE33DFCEFC97267F23EF9CFDE0C055140967A9A5"
+            .to_string(),
+        params: Some(OutpostParams {
+            emissions_controller: "osmo1emissionscontroller".to_string(),
+            voting_channel: "channel-1".to_string(),
+            ics20_channel: "channel-2".to_string(),
+            escrow_address: Addr::unchecked(""),
+            ibc_timeout: None,
+            relayer_fee: None,
+            pools_per_packet: Some(0),
+        }),
+        astro_pool_config: Some(AstroPoolConfig {
+            astro_pool: astro_pool.to_string(),
+            constant_emissions: 1_000_000_000u128.into(),
+            extra_pools: vec![],
+            emissions_cap: None,
+        }),
+        jailed: false,
+        permissions: OUTPOST_PERMIT_ALL,
+    };
+    let err = helper.add_outpost("osmo", osmosis.clone()).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidPoolsPerPacket {}
+    );
+
+    osmosis.params.as_mut().unwrap().pools_per_packet = Some(2);
+    helper.add_outpost("osmo", osmosis).unwrap();
+
+    let whitelist_fee = helper.whitelisting_fee.clone();
+    for pool in [lp_token1, lp_token2] {
+        helper
+            .mint_tokens(&owner, &[whitelist_fee.clone()])
+            .unwrap();
+        helper
+            .whitelist(&owner, pool, &[whitelist_fee.clone()])
+            .unwrap();
+    }
+
+    let user = helper.app.api().addr_make("user");
+    helper.lock(&user, 1000).unwrap();
+    helper
+        .vote(
+            &user,
+            &[
+                (lp_token1.to_string(), Decimal::percent(50)),
+                (lp_token2.to_string(), Decimal::percent(50)),
+            ],
+        )
+        .unwrap();
+
+    helper
+        .mint_tokens(
+            &helper.emission_controller.clone(),
+            &coins(50_000_000_000_000, helper.astro.clone()),
+        )
+        .unwrap();
+
+    helper.timetravel(EPOCH_LENGTH);
+    // 3 pools are split into 2 packets
+    let resp = helper.tune(&owner).unwrap();
+    assert!(resp.has_event(&Event::new("wasm").add_attribute("packets", "2")));
+
+    let epoch_start = get_epoch_start(helper.app.block_info().time.seconds());
+    let tune_info = helper.query_tune_info(None).unwrap();
+    assert_eq!(
+        tune_info.outpost_chunk_statuses,
+        HashMap::from([(
+            "osmo".to_string(),
+            ChunkedDelivery {
+                pools_per_packet: 2,
+                statuses: vec![OutpostStatus::InProgress; 2],
+            }
+        )])
+    );
+
+    let packet = |index: u32| {
+        let memo = serde_json::json!({
+            "wasm": {
+                "contract": "osmo1emissionscontroller",
+                "msg": {
+                    "custom": {
+                        "set_emissions": {
+                            "schedules": [],
+                            "chunk": { "epoch": epoch_start, "index": index, "total": 2 }
+                        }
+                    }
+                }
+            }
+        });
+        RequestPacket {
+            sequence: None,
+            source_port: None,
+            source_channel: Some("channel-2".to_string()),
+            destination_port: None,
+            destination_channel: None,
+            data: Some(to_json_binary(&serde_json::json!({ "memo": memo.to_string() })).unwrap()),
+            timeout_height: None,
+            timeout_timestamp: None,
+        }
+    };
+    let outpost_statuses = |helper: &ControllerHelper| {
+        let tune_info = helper.query_tune_info(None).unwrap();
+        (
+            tune_info.outpost_emissions_statuses["osmo"],
+            tune_info.outpost_chunk_statuses["osmo"].statuses.clone(),
+        )
+    };
+
+    // The first packet is acknowledged, the second one timed out
+    helper
+        .app
+        .wasm_sudo(
+            helper.emission_controller.clone(),
+            &TransferSudoMsg::Response {
+                request: packet(0),
+                data: Default::default(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        outpost_statuses(&helper),
+        (
+            OutpostStatus::InProgress,
+            vec![OutpostStatus::Done, OutpostStatus::InProgress]
+        )
+    );
+    helper
+        .app
+        .wasm_sudo(
+            helper.emission_controller.clone(),
+            &TransferSudoMsg::Timeout { request: packet(1) },
+        )
+        .unwrap();
+    assert_eq!(
+        outpost_statuses(&helper),
+        (
+            OutpostStatus::Failed,
+            vec![OutpostStatus::Done, OutpostStatus::Failed]
+        )
+    );
+
+    // Only the failed packet is resent
+    helper.retry_failed_outposts(&owner).unwrap();
+    assert_eq!(
+        outpost_statuses(&helper),
+        (
+            OutpostStatus::InProgress,
+            vec![OutpostStatus::Done, OutpostStatus::InProgress]
+        )
+    );
+
+    let err = helper
+        .app
+        .wasm_sudo(
+            helper.emission_controller.clone(),
+            &TransferSudoMsg::Response {
+                request: packet(0),
+                data: Default::default(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Chunk 0 for outpost osmo is not in progress"
+    );
+
+    helper
+        .app
+        .wasm_sudo(
+            helper.emission_controller.clone(),
+            &TransferSudoMsg::Response {
+                request: packet(1),
+                data: Default::default(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        outpost_statuses(&helper),
+        (
+            OutpostStatus::Done,
+            vec![OutpostStatus::Done, OutpostStatus::Done]
+        )
+    );
+}
+
+#[test]
+fn test_lock_unlock_vxastro() {
+    let mut helper = ControllerHelper::new();
+
+    // Ensure nobody but vxASTRO can call UpdateUserVotes endpoint
+    let err = helper
+        .app
+        .execute_contract(
+            helper.app.api().addr_make("random"),
+            helper.emission_controller.clone(),
+            &ExecuteMsg::<Empty>::UpdateUserVotes {
+                user: "user".to_string(),
+                is_unlock: false,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    helper
+        .app
+        .execute_contract(
+            helper.vxastro.clone(),
+            helper.emission_controller.clone(),
+            &ExecuteMsg::<Empty>::UpdateUserVotes {
+                user: helper.app.api().addr_make("random").to_string(),
+                is_unlock: false,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let owner = helper.owner.clone();
+    helper
+        .mint_tokens(&owner, &[coin(1000_000000, helper.astro.clone())])
+        .unwrap();
+    let whitelisting_fee = helper.whitelisting_fee.clone();
+
+    helper
+        .add_outpost(
+            "neutron",
+            OutpostInfo {
+                astro_denom: helper.astro.clone(),
+                params: None,
+                astro_pool_config: None,
+                jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
+            },
+        )
+        .unwrap();
+
+    let pool1 = helper.create_pair("token1", "token2");
+    helper
+        .whitelist(&owner, &pool1, &[whitelisting_fee.clone()])
+        .unwrap();
+    let pool2 = helper.create_pair("token1", "token3");
+    helper
+        .whitelist(&owner, &pool2, &[whitelisting_fee.clone()])
+        .unwrap();
+
+    let alice = helper.app.api().addr_make("alice");
+    helper.lock(&alice, 1_000000).unwrap();
+
+    let bob = helper.app.api().addr_make("bob");
+    helper.lock(&bob, 1_000000).unwrap();
+
+    let voting_block_ts = helper.app.block_info().time.seconds();
+    // Alice and Bob vote 50:50 for two existing pools
+    for user in [&alice, &bob] {
+        helper
+            .vote(
+                user,
+                &[
+                    (pool1.to_string(), Decimal::percent(50)),
+                    (pool2.to_string(), Decimal::percent(50)),
+                ],
+            )
+            .unwrap();
+
+        let user_info = helper.user_info(user, None).unwrap();
+        let user_info_historical = helper.user_info(user, Some(voting_block_ts)).unwrap();
+
+        assert_eq!(user_info, user_info_historical);
+        assert_eq!(
+            user_info,
+            UserInfoResponse {
+                vote_ts: voting_block_ts,
+                voting_power: 1_000000u128.into(),
+                votes: HashMap::from([
+                    (pool1.to_string(), Decimal::percent(50)),
+                    (pool2.to_string(), Decimal::percent(50)),
+                ]),
+                applied_votes: HashMap::from([
+                    (pool1.to_string(), Decimal::percent(50)),
+                    (pool2.to_string(), Decimal::percent(50)),
+                ]),
+                voted_by: None,
+                vote_manager: None,
+                excess_voting_power: Uint128::zero(),
+            }
+        );
+    }
+
+    // Assert pools voting power
+    for pool in [&pool1, &pool2] {
+        let pool_vp = helper.query_pool_vp(pool.as_str(), None).unwrap();
+        assert_eq!(pool_vp.u128(), 1_000000);
+    }
+
+    helper.timetravel(3 * DAY);
+
+    // Alice locks more astro
+    helper.lock(&alice, 1_000000).unwrap();
+
+    // Ensure pool voting power is updated
+    for pool in [&pool1, &pool2] {
+        let pool_vp = helper.query_pool_vp(pool.as_str(), None).unwrap();
+        assert_eq!(pool_vp.u128(), 1_500000);
+    }
+
+    // Bob starts unlocking
+    helper.unlock(&bob).unwrap();
+
+    // Ensure pool voting power is updated
+    for pool in [&pool1, &pool2] {
+        let pool_vp = helper.query_pool_vp(pool.as_str(), None).unwrap();
+        assert_eq!(pool_vp.u128(), 1_000000);
+    }
+
+    helper.timetravel(2 * DAY);
+
+    // Bob relocks
+    helper.relock(&bob).unwrap();
+
+    // Ensure pool voting power is updated
+    for pool in [&pool1, &pool2] {
+        let pool_vp = helper.query_pool_vp(pool.as_str(), None).unwrap();
+        assert_eq!(pool_vp.u128(), 1_500000);
+    }
+
+    // Check historical queries
+    for user in [&alice, &bob] {
+        // Contract state is finalized at the end of the voting block,
+        // thus we are querying the next block
+        let user_info = helper.user_info(user, Some(voting_block_ts + 1)).unwrap();
+
+        assert_eq!(
+            user_info,
+            UserInfoResponse {
+                vote_ts: voting_block_ts,
+                voting_power: 1_000000u128.into(),
+                votes: HashMap::from([
+                    (pool1.to_string(), Decimal::percent(50)),
+                    (pool2.to_string(), Decimal::percent(50)),
+                ]),
+                applied_votes: HashMap::from([
+                    (pool1.to_string(), Decimal::percent(50)),
+                    (pool2.to_string(), Decimal::percent(50)),
+                ]),
+                voted_by: None,
+                vote_manager: None,
+                excess_voting_power: Uint128::zero(),
+            }
+        );
+    }
+
+    let voted_pools = helper.query_pools_vp(Some(5)).unwrap();
+    let mut expected_pools = vec![
+        (pool1.to_string(), 1_500000u128.into()),
+        (pool2.to_string(), 1_500000u128.into()),
+    ];
+    expected_pools.sort();
+    assert_eq!(voted_pools, expected_pools);
+
+    // Unlock and withdraw
+    helper.unlock(&alice).unwrap();
+    helper.unlock(&bob).unwrap();
+
+    let unlock_period = helper.query_vxastro_config().unwrap().unlock_period;
+    helper.timetravel(unlock_period);
+
+    helper.withdraw(&alice).unwrap();
+    helper.withdraw(&bob).unwrap();
+
+    let alice_balance = helper
+        .app
+        .wrap()
+        .query_balance(alice, &helper.xastro)
+        .unwrap();
+    let bob_balance = helper
+        .app
+        .wrap()
+        .query_balance(bob, &helper.xastro)
+        .unwrap();
+    assert_eq!(alice_balance, coin(2_000000, &helper.xastro));
+    assert_eq!(bob_balance, coin(1_000000, &helper.xastro));
+}
+
+#[test]
+fn test_instant_unlock_vxastro() {
+    let mut helper = ControllerHelper::new();
+
+    let owner = helper.owner.clone();
+    helper
+        .mint_tokens(&owner, &[coin(1000_000000, helper.astro.clone())])
+        .unwrap();
+    let whitelisting_fee = helper.whitelisting_fee.clone();
+
+    helper
+        .add_outpost(
+            "neutron",
+            OutpostInfo {
+                astro_denom: helper.astro.clone(),
+                params: None,
+                astro_pool_config: None,
+                jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
+            },
+        )
+        .unwrap();
+
+    let pool1 = helper.create_pair("token1", "token2");
+    helper
+        .whitelist(&owner, &pool1, &[whitelisting_fee.clone()])
+        .unwrap();
+    let pool2 = helper.create_pair("token1", "token3");
+    helper
+        .whitelist(&owner, &pool2, &[whitelisting_fee.clone()])
+        .unwrap();
+
+    let alice = helper.app.api().addr_make("alice");
+    helper.lock(&alice, 10_000000).unwrap();
+
+    helper
+        .vote(
+            &alice,
+            &[
+                (pool1.to_string(), Decimal::percent(50)),
+                (pool2.to_string(), Decimal::percent(50)),
+            ],
+        )
+        .unwrap();
+
+    // Assert pools voting power
+    for pool in [&pool1, &pool2] {
+        let pool_vp = helper.query_pool_vp(pool.as_str(), None).unwrap();
+        assert_eq!(pool_vp.u128(), 5_000000);
+    }
+
+    // Ensure random user can't instantly unlock
+    let random = helper.app.api().addr_make("random");
+    let err = helper.instant_unlock(&random, 100).unwrap_err();
+    assert_eq!(
+        err.downcast::<astroport_voting_escrow::error::ContractError>()
+            .unwrap(),
+        astroport_voting_escrow::error::ContractError::Unauthorized {}
+    );
+
+    let err = helper
+        .set_privileged_list(&random, vec![alice.to_string()])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<astroport_voting_escrow::error::ContractError>()
+            .unwrap(),
+        astroport_voting_escrow::error::ContractError::Unauthorized {}
+    );
+
+    // Add Alice to the privileged list
+    helper
+        .set_privileged_list(&owner, vec![alice.to_string()])
+        .unwrap();
+
+    // Ensure alice is added
+    let privileged_list: Vec<Addr> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(&helper.vxastro, &voting_escrow::QueryMsg::PrivilegedList {})
+        .unwrap();
+    assert_eq!(privileged_list, vec![alice.clone()]);
+
+    // Alice instantly unlocks
+    helper.instant_unlock(&alice, 2_000000).unwrap();
+
+    let xastro_bal = helper
+        .app
+        .wrap()
+        .query_balance(&alice, &helper.xastro)
+        .unwrap();
+    assert_eq!(xastro_bal.amount.u128(), 2_000000);
+
+    // Assert pools voting power is reduced
+    for pool in [&pool1, &pool2] {
+        let pool_vp = helper.query_pool_vp(pool.as_str(), None).unwrap();
+        assert_eq!(pool_vp.u128(), 4_000000);
+    }
+
+    // Total voting power must be 8
+    let total_vp = helper.total_vp(None).unwrap();
+    assert_eq!(total_vp.u128(), 8_000000);
+
+    let lock_info: voting_escrow::LockInfoResponse = helper
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &helper.vxastro,
+            &voting_escrow::QueryMsg::LockInfo {
+                user: alice.to_string(),
+                timestamp: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        lock_info,
+        voting_escrow::LockInfoResponse {
+            amount: 8_000000u128.into(),
+            unlock_status: None,
+            permanent: false,
+        }
+    );
+}
+
+#[test]
+fn test_slash_vxastro_lock() {
+    let mut helper = ControllerHelper::new();
+
+    let owner = helper.owner.clone();
+    helper
+        .mint_tokens(&owner, &[coin(1000_000000, helper.astro.clone())])
+        .unwrap();
+    let whitelisting_fee = helper.whitelisting_fee.clone();
+
+    helper
+        .add_outpost(
+            "neutron",
+            OutpostInfo {
+                astro_denom: helper.astro.clone(),
+                params: None,
+                astro_pool_config: None,
+                jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
+            },
+        )
+        .unwrap();
+
+    let pool1 = helper.create_pair("token1", "token2");
+    helper
+        .whitelist(&owner, &pool1, &[whitelisting_fee.clone()])
+        .unwrap();
+    let pool2 = helper.create_pair("token1", "token3");
+    helper
+        .whitelist(&owner, &pool2, &[whitelisting_fee.clone()])
+        .unwrap();
+
+    let alice = helper.app.api().addr_make("alice");
+    helper.lock(&alice, 10_000000).unwrap();
+    helper
+        .vote(
+            &alice,
+            &[
+                (pool1.to_string(), Decimal::percent(50)),
+                (pool2.to_string(), Decimal::percent(50)),
+            ],
+        )
+        .unwrap();
+
+    let assembly = helper.assembly.clone();
+    let treasury = helper.app.api().addr_make("treasury");
+
+    // Only the Assembly can slash
+    let err = helper
+        .slash_lock(&owner, &alice, 4_000000, &treasury)
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<astroport_voting_escrow::error::ContractError>()
+            .unwrap(),
+        astroport_voting_escrow::error::ContractError::Unauthorized {}
+    );
+
+    let err = helper
+        .slash_lock(&assembly, &alice, 0, &treasury)
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<astroport_voting_escrow::error::ContractError>()
+            .unwrap(),
+        astroport_voting_escrow::error::ContractError::ZeroSlashAmount {}
+    );
+
+    let resp = helper
+        .slash_lock(&assembly, &alice, 4_000000, &treasury)
+        .unwrap();
+    assert!(resp.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "slash_lock")
+            .add_attribute("user", alice.to_string())
+            .add_attribute("recipient", treasury.to_string())
+            .add_attribute("slashed_amount", "4000000")
+            .add_attribute("new_lock_amount", "6000000")
+    ));
+
+    let treasury_bal = helper
+        .app
+        .wrap()
+        .query_balance(&treasury, &helper.xastro)
+        .unwrap();
+    assert_eq!(treasury_bal.amount.u128(), 4_000000);
+
+    // Votes are adjusted in the emissions controller
+    for pool in [&pool1, &pool2] {
+        let pool_vp = helper.query_pool_vp(pool.as_str(), None).unwrap();
+        assert_eq!(pool_vp.u128(), 3_000000);
+    }
+    assert_eq!(helper.total_vp(None).unwrap().u128(), 6_000000);
+
+    let err = helper
+        .slash_lock(&assembly, &alice, 7_000000, &treasury)
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<astroport_voting_escrow::error::ContractError>()
+            .unwrap(),
+        astroport_voting_escrow::error::ContractError::SlashAmountExceedsLock(6_000000u128.into())
+    );
+
+    // Slash an unlocking position completely
+    helper.unlock(&alice).unwrap();
+    assert_eq!(helper.total_vp(None).unwrap().u128(), 0);
+
+    helper
+        .slash_lock(&assembly, &alice, 6_000000, &treasury)
+        .unwrap();
+    assert_eq!(helper.total_vp(None).unwrap().u128(), 0);
+
+    let lock_info: voting_escrow::LockInfoResponse = helper
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &helper.vxastro,
+            &voting_escrow::QueryMsg::LockInfo {
+                user: alice.to_string(),
+                timestamp: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        lock_info,
+        voting_escrow::LockInfoResponse {
+            amount: Uint128::zero(),
+            unlock_status: None,
+            permanent: false,
+        }
+    );
+
+    let treasury_bal = helper
+        .app
+        .wrap()
+        .query_balance(&treasury, &helper.xastro)
+        .unwrap();
+    assert_eq!(treasury_bal.amount.u128(), 10_000000);
+}
+
+#[test]
+fn test_some_epochs() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+    let whitelisting_fee = helper.whitelisting_fee.clone();
+
+    helper
+        .add_outpost(
+            "osmo",
+            OutpostInfo {
+                astro_denom: "ibc/C4CFF46FD6DE35CA4CF4CE031E643C8FDC9BA4B99AE598E9B0ED98FE3A2319F9"
+                    .to_string(),
+                params: Some(OutpostParams {
+                    emissions_controller: "osmo1controller".to_string(),
+                    voting_channel: "channel-1".to_string(),
+                    ics20_channel: "channel-2".to_string(),
+                    escrow_address: Addr::unchecked(""),
+                    ibc_timeout: None,
+                    relayer_fee: None,
+                    pools_per_packet: None,
+                }),
+                astro_pool_config: None,
+                jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
+            },
+        )
+        .unwrap();
+    let pool1 = "osmo1pool1";
+    let pool2 = "osmo1pool2";
+    helper
+        .mint_tokens(&owner, &coins(100000000, helper.astro.clone()))
+        .unwrap();
+    helper
+        .whitelist(&owner, pool1, &[whitelisting_fee.clone()])
+        .unwrap();
+    helper
+        .whitelist(&owner, pool2, &[whitelisting_fee.clone()])
+        .unwrap();
+
+    let user1 = helper.app.api().addr_make("user1");
+    helper.lock(&user1, 1_000000).unwrap();
+    let user2 = helper.app.api().addr_make("user2");
+    helper.lock(&user2, 1_000000).unwrap();
+
+    helper
+        .vote(&user1, &[(pool1.to_string(), Decimal::one())])
+        .unwrap();
+    helper
+        .vote(&user2, &[(pool2.to_string(), Decimal::one())])
+        .unwrap();
+
+    // Preparing controller balance for tuning
+    helper
+        .mint_tokens(
+            &helper.emission_controller.clone(),
+            &coins(100_000_000_000_000, helper.astro.clone()),
+        )
+        .unwrap();
+
+    helper.timetravel(EPOCH_LENGTH);
+
+    helper.tune(&user1).unwrap();
+    let voted_pools = helper.query_pools_vp(None).unwrap();
+    assert_eq!(
+        voted_pools,
+        [
+            (pool1.to_string(), 1_000000u128.into()),
+            (pool2.to_string(), 1_000000u128.into()),
+        ]
+    );
+
+    helper.unlock(&user1).unwrap();
+
+    helper.timetravel(EPOCH_LENGTH);
+    helper.tune(&user1).unwrap();
+
+    // User1 unlocked, user2 still keeps his votes
+    let voted_pools = helper.query_pools_vp(None).unwrap();
+    let expected_pools = vec![
+        (pool1.to_string(), 0u128.into()),
+        (pool2.to_string(), 1_000000u128.into()),
+    ];
+    assert_eq!(voted_pools, expected_pools);
+
+    helper.relock(&user1).unwrap();
+    helper.unlock(&user2).unwrap();
+
+    helper.timetravel(EPOCH_LENGTH);
+    helper.tune(&user1).unwrap();
+
+    // User1 relocked, user2 unlocked
+    let voted_pools = helper.query_pools_vp(None).unwrap();
+    assert_eq!(
+        voted_pools,
+        [
+            (pool1.to_string(), 1_000000u128.into()),
+            (pool2.to_string(), 0u128.into()),
+        ]
+    );
+
+    // Allow only 1 pool for tuning
+    helper
+        .app
+        .execute_contract(
+            owner.clone(),
+            helper.emission_controller.clone(),
+            &ExecuteMsg::Custom(HubMsg::UpdateConfig(Box::new(UpdateConfigMsg {
+                pools_per_outpost: Some(1),
+                ..Default::default()
+            }))),
+            &[],
+        )
+        .unwrap();
+
+    helper.timetravel(EPOCH_LENGTH);
+    helper.tune(&user1).unwrap();
+
+    // pool2 was removed from votable pools
+    let voted_pools = helper.query_pools_vp(None).unwrap();
+    assert_eq!(voted_pools, [(pool1.to_string(), 1_000000u128.into())]);
+
+    // And from whitelist
+    let whitelist = helper.query_whitelist().unwrap();
+    assert_eq!(whitelist, vec![pool1.to_string()]);
+
+    // If user2 relocks his votes won't be restored as pool2 must be whitelisted again
+    helper.relock(&user2).unwrap();
+    let voted_pools = helper.query_pools_vp(None).unwrap();
+    assert_eq!(voted_pools, [(pool1.to_string(), 1_000000u128.into())]);
+
+    // Whitelist pool2 again
+    helper
+        .whitelist(&owner, pool2, &[whitelisting_fee.clone()])
+        .unwrap();
+
+    // Ensure that user2 votes are not applied
+    let user2_info = helper.user_info(&user2, None).unwrap();
+    assert_eq!(user2_info.applied_votes, HashMap::new());
+
+    // User2 must refresh his votes
+    helper.refresh_user_votes(&user2).unwrap();
+
+    // His contribution must be restored
+    let voted_pools = helper.query_pools_vp(None).unwrap();
+    assert_eq!(
+        voted_pools,
+        [
+            (pool1.to_string(), 1_000000u128.into()),
+            (pool2.to_string(), 1_000000u128.into()),
+        ]
+    );
+}
+
+#[test]
+fn test_interchain_governance() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+
+    // No proposal yet
+    let err = helper.register_proposal(1).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Generic error: Querier contract error: type: astroport_governance::assembly::Proposal; key: [00, 09, 70, 72, 6F, 70, 6F, 73, 61, 6C, 73, 00, 00, 00, 00, 00, 00, 00, 01] not found");
+
+    helper.submit_proposal(&owner).unwrap();
+
+    // No outposts yet but it shouldn't fail transaction
+    let resp = helper.register_proposal(1).unwrap();
+    assert!(
+        !resp.has_event(
+            &Event::new("wasm")
+                .add_attributes([("action", "register_proposal"), ("outpost", "osmo")])
+        ),
+        "Controller tried to register outpost {:?}",
+        resp.events
+    );
+
+    // Add outpost
+    helper
+        .add_outpost(
+            "osmo",
+            OutpostInfo {
+                astro_denom: "ibc/C4CFF46FD6DE35CA4CF4CE031E643C8FDC9BA4B99AE598E9B0ED98FE3A2319F9"
+                    .to_string(),
+                params: Some(OutpostParams {
+                    emissions_controller: "osmo1controller".to_string(),
+                    voting_channel: "channel-1".to_string(),
+                    ics20_channel: "channel-2".to_string(),
+                    escrow_address: Addr::unchecked(""),
+                    ibc_timeout: None,
+                    relayer_fee: None,
+                    pools_per_packet: None,
+                }),
+                astro_pool_config: None,
+                jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
+            },
+        )
+        .unwrap();
+
+    // Submit 2nd proposal. Ensure it registers a proposal on osmosis
+    let resp = helper.submit_proposal(&owner).unwrap();
+    resp.assert_event(
+        &Event::new("wasm").add_attributes([("action", "register_proposal"), ("outpost", "osmo")]),
+    );
+
+    // Now we can register 1st proposal
+    helper.register_proposal(1).unwrap();
+    resp.assert_event(
+        &Event::new("wasm").add_attributes([("action", "register_proposal"), ("outpost", "osmo")]),
+    );
+
+    // Timeout both proposals
+    helper.blocktravel(PROPOSAL_VOTING_PERIOD + 1);
+
+    let err = helper.register_proposal(1).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Proposal is not active"
+    );
+
+    let proposal_start_time = |helper: &ControllerHelper, proposal_id: u64| {
+        helper
+            .app
+            .wrap()
+            .query_wasm_smart::<assembly::Proposal>(
+                helper.assembly.clone(),
+                &assembly::QueryMsg::Proposal { proposal_id },
+            )
+            .unwrap()
+            .start_time
+    };
+
+    // Emulate outpost vote after a voting period is over.
+    // It shouldn't fail but must not register a vote
+    let resp = helper
+        .mock_packet_receive(VxAstroIbcMsg::GovernanceVote {
+            voter: "osmo1voter".to_string(),
+            voting_power: Default::default(),
+            total_voting_power: Default::default(),
+            proposal_id: 1,
+            vote: ProposalVoteOption::For,
+            snapshot_time: Some(proposal_start_time(&helper, 1) - 1),
+        })
+        .unwrap();
+    resp.assert_event(
+        &Event::new("wasm")
+            .add_attributes([("action", "cast_vote"), ("error", "Voting period ended!")]),
+    );
+
+    // Submit 3rd proposal
+    helper.submit_proposal(&owner).unwrap();
+    let start_time = proposal_start_time(&helper, 3);
+    let governance_vote = |snapshot_time: Option<u64>| VxAstroIbcMsg::GovernanceVote {
+        voter: "osmo1voter".to_string(),
+        voting_power: 1_000000u128.into(),
+        total_voting_power: Default::default(),
+        proposal_id: 3,
+        vote: ProposalVoteOption::For,
+        snapshot_time,
+    };
+
+    // Voting power without the snapshot time can't be verified
+    let resp = helper.mock_packet_receive(governance_vote(None)).unwrap();
+    let IbcAckResult::Error(err) = from_json(resp.data.unwrap()).unwrap() else {
+        panic!("Expected error acknowledgement");
+    };
+    assert_eq!(
+        AckError::parse(&err).message,
+        ContractError::MissingVotingPowerSnapshot(3).to_string()
+    );
+
+    // Voting power taken at a wrong timestamp is rejected
+    let resp = helper
+        .mock_packet_receive(governance_vote(Some(start_time)))
+        .unwrap();
+    let IbcAckResult::Error(err) = from_json(resp.data.unwrap()).unwrap() else {
+        panic!("Expected error acknowledgement");
+    };
+    assert_eq!(
+        AckError::parse(&err).message,
+        ContractError::InvalidVotingPowerSnapshot {
+            proposal_id: 3,
+            snapshot_time: start_time,
+            expected: start_time - 1,
+        }
+        .to_string()
+    );
+
+    // Emulate vote from osmosis
+    let resp = helper
+        .mock_packet_receive(governance_vote(Some(start_time - 1)))
+        .unwrap();
+
+    resp.assert_event(&Event::new("wasm").add_attributes([
+        ("action", "cast_vote"),
+        ("proposal_id", "3"),
+        ("voter", "osmo1voter"),
+        ("vote", "for"),
+        ("voting_power", "1000000"),
+    ]));
+
+    // Ensure voter has been reflected in assembly
+    let proposal = helper
+        .app
+        .wrap()
+        .query_wasm_smart::<assembly::Proposal>(
+            helper.assembly.clone(),
+            &assembly::QueryMsg::Proposal { proposal_id: 3 },
+        )
+        .unwrap();
+    assert_eq!(proposal.for_power.u128(), 1_000000);
+
+    let voters = helper
+        .app
+        .wrap()
+        .query_wasm_smart::<Vec<ProposalVoterResponse>>(
+            helper.assembly.clone(),
+            &assembly::QueryMsg::ProposalVoters {
+                proposal_id: 3,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        voters,
+        vec![ProposalVoterResponse {
+            address: "osmo1voter".to_string(),
+            vote_option: ProposalVoteOption::For,
+        }]
+    );
+
+    // Outposts can fetch the live tally
+    let resp = helper
+        .mock_packet_receive(VxAstroIbcMsg::ProposalTallyRequest { proposal_id: 3 })
+        .unwrap();
+    let IbcAckResult::Ok(data) = from_json(resp.data.as_ref().unwrap()).unwrap() else {
+        panic!("Unexpected ack: {:?}", resp.data)
+    };
+    let tally: ProposalTally = from_json(data).unwrap();
+    assert_eq!(
+        tally,
+        ProposalTally {
+            proposal_id: 3,
+            status: ProposalStatus::Active,
+            for_power: proposal.for_power,
+            against_power: proposal.against_power,
+            total_voting_power: proposal.total_voting_power,
+            voting_end_block: proposal.end_block,
+            blocks_remaining: proposal.end_block - helper.app.block_info().height,
+            updated_at: helper.app.block_info().time.seconds(),
+        }
+    );
+}
+
+#[test]
+fn test_governance_votes_deduplication() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+
+    for (prefix, voting_channel) in [("osmo", "channel-1"), ("terra", "channel-3")] {
+        helper
+            .add_outpost(
+                prefix,
+                OutpostInfo {
+                    astro_denom: format!("ibc/{prefix:0>64}"),
+                    params: Some(OutpostParams {
+                        emissions_controller: format!("{prefix}1controller"),
+                        voting_channel: voting_channel.to_string(),
+                        ics20_channel: "channel-2".to_string(),
+                        escrow_address: Addr::unchecked(""),
+                        ibc_timeout: None,
+                        relayer_fee: None,
+                        pools_per_packet: None,
+                    }),
+                    astro_pool_config: None,
+                    jailed: false,
+                    permissions: OUTPOST_PERMIT_ALL,
+                },
+            )
+            .unwrap();
+    }
+
+    let user1 = helper.app.api().addr_make("user1");
+    helper.lock(&user1, 1_000000).unwrap();
+    helper.timetravel(10);
+    helper.submit_proposal(&owner).unwrap();
+    let snapshot_time = helper
+        .app
+        .wrap()
+        .query_wasm_smart::<assembly::Proposal>(
+            helper.assembly.clone(),
+            &assembly::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap()
+        .start_time
+        - 1;
+
+    let ack_error = |resp: &AppResponse| -> Option<AckError> {
+        match from_json(resp.data.as_ref().unwrap()).unwrap() {
+            IbcAckResult::Ok(_) => None,
+            IbcAckResult::Error(err) => Some(AckError::parse(&err)),
+        }
+    };
+    let governance_vote = |voter: &str| VxAstroIbcMsg::GovernanceVote {
+        voter: voter.to_string(),
+        voting_power: 2_000000u128.into(),
+        total_voting_power: Default::default(),
+        proposal_id: 1,
+        vote: ProposalVoteOption::Against,
+        snapshot_time: Some(snapshot_time),
+    };
+
+    // User1 votes natively on the Hub
+    helper
+        .app
+        .execute_contract(
+            user1.clone(),
+            helper.assembly.clone(),
+            &assembly::ExecuteMsg::CastVote {
+                proposal_id: 1,
+                vote: ProposalVoteOption::For,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Outpost voters are never matched against Hub accounts
+    let resp = helper
+        .mock_packet_receive(governance_vote("osmo1voter"))
+        .unwrap();
+    assert!(ack_error(&resp).is_none());
+    resp.assert_event(&Event::new("wasm").add_attributes([
+        ("action", "cast_vote"),
+        ("proposal_id", "1"),
+        ("voter", "osmo1voter"),
+        ("vote", "against"),
+    ]));
+
+    // The same voter relayed from another channel is rejected
+    let resp = helper
+        .mock_packet_receive_on("channel-3", governance_vote("osmo1voter"))
+        .unwrap();
+    let err = ack_error(&resp).unwrap();
+    assert_eq!(err.code, AckErrorCode::InvalidVotes);
+    assert_eq!(
+        err.message,
+        ContractError::DuplicateVote {
+            proposal_id: 1,
+            voter: "osmo1voter".to_string(),
+            origin: "channel-1".to_string(),
+        }
+        .to_string()
+    );
+
+    let resp = helper
+        .mock_packet_receive_on("channel-3", governance_vote("terra1voter"))
+        .unwrap();
+    assert!(ack_error(&resp).is_none());
+
+    // Each voter was counted only once
+    let proposal = helper
+        .app
+        .wrap()
+        .query_wasm_smart::<assembly::Proposal>(
+            helper.assembly.clone(),
+            &assembly::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.for_power.u128(), 1_000000);
+    assert_eq!(proposal.against_power.u128(), 4_000000);
+
+    assert_eq!(
+        helper.query_voter_origins(1).unwrap(),
+        vec![
+            ("osmo1voter".to_string(), "channel-1".to_string()),
+            ("terra1voter".to_string(), "channel-3".to_string()),
+        ]
+    );
+
+    // Vote origins can't be cleared while the proposal is active
+    let random = helper.app.api().addr_make("random");
+    let clear_msg = HubMsg::ClearVoterOrigins {
+        proposal_id: 1,
+        limit: None,
+    };
+    let err = helper
+        .execute_hub_msg(&random, clear_msg.clone())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ProposalNotConcluded(1)
+    );
+
+    // Vote origins are removed once the proposal is concluded
+    helper.blocktravel(PROPOSAL_VOTING_PERIOD + 1);
+    let resp = helper
+        .app
+        .execute_contract(
+            random.clone(),
+            helper.assembly.clone(),
+            &assembly::ExecuteMsg::EndProposal { proposal_id: 1 },
+            &[],
+        )
+        .unwrap();
+    resp.assert_event(&Event::new("wasm").add_attributes([
+        ("action", "notify_proposal_status"),
+        ("cleared_voter_origins", "2"),
+    ]));
+    assert!(helper.query_voter_origins(1).unwrap().is_empty());
+
+    let resp = helper.execute_hub_msg(&random, clear_msg).unwrap();
+    resp.assert_event(
+        &Event::new("wasm").add_attributes([("action", "clear_voter_origins"), ("cleared", "0")]),
+    );
+}
+
+#[test]
+fn test_change_ownership() {
+    let mut helper = ControllerHelper::new();
+
+    let new_owner = helper.app.api().addr_make("new_owner");
+
+    // New owner
+    let msg = ExecuteMsg::<Empty>::ProposeNewOwner {
+        new_owner: new_owner.to_string(),
+        expires_in: 100, // seconds
+    };
+
+    // Unauthorized check
+    let err = helper
+        .app
+        .execute_contract(
+            helper.app.api().addr_make("not_owner"),
+            helper.emission_controller.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
+
+    // Claim before proposal
+    let err = helper
+        .app
+        .execute_contract(
+            new_owner.clone(),
+            helper.emission_controller.clone(),
+            &ExecuteMsg::<Empty>::ClaimOwnership {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Ownership proposal not found"
+    );
+
+    // Propose a new owner
+    helper
+        .app
+        .execute_contract(
+            helper.owner.clone(),
+            helper.emission_controller.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    // Claim from invalid addr
+    let err = helper
+        .app
+        .execute_contract(
+            helper.app.api().addr_make("invalid_addr"),
+            helper.emission_controller.clone(),
+            &ExecuteMsg::<Empty>::ClaimOwnership {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
+
+    // Drop the ownership proposal
+    helper
+        .app
+        .execute_contract(
+            helper.owner.clone(),
+            helper.emission_controller.clone(),
+            &ExecuteMsg::<Empty>::DropOwnershipProposal {},
+            &[],
+        )
+        .unwrap();
+
+    // Claim ownership
+    let err = helper
+        .app
+        .execute_contract(
+            new_owner.clone(),
+            helper.emission_controller.clone(),
+            &ExecuteMsg::<Empty>::ClaimOwnership {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Ownership proposal not found"
+    );
+
+    // Propose a new owner again
+    helper
+        .app
+        .execute_contract(
+            helper.owner.clone(),
+            helper.emission_controller.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+    helper
+        .app
+        .execute_contract(
+            new_owner.clone(),
+            helper.emission_controller.clone(),
+            &ExecuteMsg::<Empty>::ClaimOwnership {},
+            &[],
+        )
+        .unwrap();
+
+    assert_eq!(helper.query_config().unwrap().owner.to_string(), new_owner)
+}
+
+#[test]
+fn test_update_config() {
+    let mut helper = ControllerHelper::new();
+
+    let fee_receiver = helper.app.api().addr_make("fee_receiver");
+    let tvl_oracle = helper.app.api().addr_make("tvl_oracle");
+    let msg = ExecuteMsg::Custom(HubMsg::UpdateConfig(Box::new(UpdateConfigMsg {
+        pools_per_outpost: Some(8),
+        whitelisting_fee: Some(coin(100, "astro")),
+        fee_receiver: Some(fee_receiver.to_string()),
+        emissions_multiple: Some(Decimal::percent(90)),
+        max_astro: Some(1_000_000u128.into()),
+        new_pool_cooldown_epochs: Some(2),
+        max_weight_per_pool: Some(Decimal::percent(50)),
+        tune_incentive: Some(1_000u128.into()),
+        tvl_oracle: Some(tvl_oracle.to_string()),
+        tvl_weight: Some(Decimal::percent(20)),
+        tvl_max_age: Some(DAY),
+        whitelist_auction: Some(true),
+        min_pool_emissions: Some(10_000u128.into()),
+        max_failed_epochs: Some(3),
+        max_voter_share: Some(Decimal::percent(20)),
+    })));
+
+    let err = helper
+        .app
+        .execute_contract(
+            helper.app.api().addr_make("random"),
+            helper.emission_controller.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    helper
+        .app
+        .execute_contract(
+            helper.owner.clone(),
+            helper.emission_controller.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    let config = helper.query_config().unwrap();
+
+    assert_eq!(
+        config,
+        emissions_controller::hub::Config {
+            owner: helper.owner.clone(),
+            assembly: helper.assembly.clone(),
+            vxastro: helper.vxastro.clone(),
+            factory: helper.factory.clone(),
+            astro_denom: helper.astro.clone(),
+            xastro_denom: helper.xastro.clone(),
+            staking: helper.staking.clone(),
+            incentives_addr: helper.incentives.clone(),
+            pools_per_outpost: 8,
+            whitelisting_fee: coin(100, "astro"),
+            fee_receiver,
+            whitelist_threshold: Decimal::percent(1),
+            emissions_multiple: Decimal::percent(90),
+            max_astro: 1_000_000u128.into(),
+            new_pool_cooldown_epochs: 2,
+            max_weight_per_pool: Some(Decimal::percent(50)),
+            tune_incentive: 1_000u128.into(),
+            tvl_oracle: Some(tvl_oracle),
+            tvl_weight: Decimal::percent(20),
+            tvl_max_age: DAY,
+            whitelist_auction: true,
+            min_pool_emissions: 10_000u128.into(),
+            max_failed_epochs: 3,
+            max_voter_share: Some(Decimal::percent(20)),
+        }
+    );
+}
+
+#[test]
+fn test_whitelist_auction() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+    let whitelisting_fee = helper.whitelisting_fee.clone();
+    let fee_receiver = helper.query_config().unwrap().fee_receiver;
+
+    helper
+        .add_outpost(
+            "neutron",
+            OutpostInfo {
+                astro_denom: helper.astro.clone(),
+                params: None,
+                astro_pool_config: None,
+                jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
+            },
+        )
+        .unwrap();
+
+    let pool1 = helper.create_pair("token1", "token2");
+    let pool2 = helper.create_pair("token1", "token3");
+    let pool3 = helper.create_pair("token1", "token4");
+
+    let alice = helper.app.api().addr_make("alice");
+    let bob = helper.app.api().addr_make("bob");
+    let carol = helper.app.api().addr_make("carol");
+    for user in [&owner, &alice, &bob, &carol] {
+        helper
+            .mint_tokens(user, &[coin(1000_000000, helper.astro.clone())])
+            .unwrap();
+    }
+
+    let bid = |amount: u128| coins(amount, &whitelisting_fee.denom);
+
+    let err = helper
+        .bid_whitelist_slot(&alice, &pool2, &bid(whitelisting_fee.amount.u128()))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::WhitelistAuctionDisabled {}
+    );
+
+    // Only one pool can receive emissions
+    helper
+        .app
+        .execute_contract(
+            owner.clone(),
+            helper.emission_controller.clone(),
+            &ExecuteMsg::Custom(HubMsg::UpdateConfig(Box::new(UpdateConfigMsg {
+                pools_per_outpost: Some(1),
+                whitelist_auction: Some(true),
+                ..Default::default()
+            }))),
+            &[],
+        )
+        .unwrap();
+
+    let err = helper
+        .bid_whitelist_slot(&alice, &pool2, &bid(whitelisting_fee.amount.u128()))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::WhitelistNotFull {}
+    );
+
+    helper
+        .whitelist(&owner, &pool1, &[whitelisting_fee.clone()])
+        .unwrap();
+    let err = helper
+        .whitelist(&owner, &pool2, &[whitelisting_fee.clone()])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::WhitelistFull {}
+    );
+
+    let err = helper
+        .bid_whitelist_slot(&alice, &pool1, &bid(whitelisting_fee.amount.u128()))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PoolAlreadyWhitelisted(pool1.clone())
+    );
+
+    let err = helper
+        .bid_whitelist_slot(&alice, &pool2, &bid(whitelisting_fee.amount.u128() - 1))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::BidTooLow(whitelisting_fee.clone())
+    );
+
+    helper
+        .bid_whitelist_slot(&alice, &pool2, &bid(whitelisting_fee.amount.u128()))
+        .unwrap();
+
+    // Bob outbids Alice
+    let err = helper
+        .bid_whitelist_slot(&bob, &pool2, &bid(whitelisting_fee.amount.u128()))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::BidTooLow(coin(
+            whitelisting_fee.amount.u128() + 1,
+            &whitelisting_fee.denom
+        ))
+    );
+    let bob_bid = whitelisting_fee.amount.u128() * 2;
+    helper
+        .bid_whitelist_slot(&bob, &pool2, &bid(bob_bid))
+        .unwrap();
+    let alice_bal = helper
+        .app
+        .wrap()
+        .query_balance(&alice, &whitelisting_fee.denom)
+        .unwrap();
+    assert_eq!(alice_bal.amount.u128(), 1000_000000);
+
+    helper
+        .bid_whitelist_slot(&carol, &pool3, &bid(whitelisting_fee.amount.u128()))
+        .unwrap();
+    // Bids are ordered by LP token denom
+    let mut expected_bids = vec![
+        (
+            pool2.to_string(),
+            WhitelistBid {
+                bidder: bob.clone(),
+                amount: bob_bid.into(),
+            },
+        ),
+        (
+            pool3.to_string(),
+            WhitelistBid {
+                bidder: carol.clone(),
+                amount: whitelisting_fee.amount,
+            },
+        ),
+    ];
+    expected_bids.sort_by(|(a, _), (b, _)| a.cmp(b));
+    assert_eq!(helper.query_whitelist_bids().unwrap(), expected_bids);
+
+    let user = helper.app.api().addr_make("user");
+    helper.lock(&user, 1_000000).unwrap();
+    helper
+        .vote(&user, &[(pool1.to_string(), Decimal::one())])
+        .unwrap();
+
+    let fee_receiver_bal_before = helper
+        .app
+        .wrap()
+        .query_balance(&fee_receiver, &whitelisting_fee.denom)
+        .unwrap()
+        .amount;
+
+    helper.timetravel(EPOCH_LENGTH);
+    helper
+        .mint_tokens(
+            &helper.emission_controller.clone(),
+            &coins(50_000_000_000_000, helper.astro.clone()),
+        )
+        .unwrap();
+    let resp = helper.tune(&owner).unwrap();
+
+    // The highest bid displaces the only incumbent. There is no slot left for Carol
+    assert!(
+        resp.has_event(&Event::new("wasm-whitelist_bid").add_attributes([
+            attr("pool", &pool2),
+            attr("bidder", bob.as_str()),
+            attr("displaced_pool", &pool1),
+            attr("outcome", "won"),
+        ]))
+    );
+    assert!(
+        resp.has_event(&Event::new("wasm-whitelist_bid").add_attributes([
+            attr("pool", &pool3),
+            attr("bidder", carol.as_str()),
+            attr("outcome", "refunded"),
+        ]))
+    );
+
+    assert_eq!(helper.query_whitelist().unwrap(), vec![pool2.to_string()]);
+    assert_eq!(helper.query_whitelist_bids().unwrap(), vec![]);
+    helper.query_voted_pool(&pool1, None).unwrap_err();
+
+    let carol_bal = helper
+        .app
+        .wrap()
+        .query_balance(&carol, &whitelisting_fee.denom)
+        .unwrap();
+    assert_eq!(carol_bal.amount.u128(), 1000_000000);
+    let fee_receiver_bal = helper
+        .app
+        .wrap()
+        .query_balance(&fee_receiver, &whitelisting_fee.denom)
+        .unwrap()
+        .amount;
+    assert_eq!((fee_receiver_bal - fee_receiver_bal_before).u128(), bob_bid);
+}
+
+#[test]
+fn test_set_unlock_period() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+
+    let alice = helper.app.api().addr_make("alice");
+    let bob = helper.app.api().addr_make("bob");
+    helper.lock(&alice, 10_000000).unwrap();
+    helper.lock(&bob, 10_000000).unwrap();
+
+    let config = helper.query_vxastro_config().unwrap();
+    assert_eq!(config.unlock_period, voting_escrow::DEFAULT_UNLOCK_PERIOD);
+
+    // Alice starts unlocking with the default period
+    helper.unlock(&alice).unwrap();
+    let alice_end = helper.app.block_info().time.seconds() + config.unlock_period;
+
+    let random = helper.app.api().addr_make("random");
+    let err = helper.set_unlock_period(&random, 7 * DAY).unwrap_err();
+    assert_eq!(
+        err.downcast::<astroport_voting_escrow::error::ContractError>()
+            .unwrap(),
+        astroport_voting_escrow::error::ContractError::Unauthorized {}
+    );
+
+    let (min, max) = (
+        *voting_escrow::UNLOCK_PERIOD_LIMITS.start(),
+        *voting_escrow::UNLOCK_PERIOD_LIMITS.end(),
+    );
+    for invalid_period in [min - 1, max + 1] {
+        let err = helper
+            .set_unlock_period(&owner, invalid_period)
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<astroport_voting_escrow::error::ContractError>()
+                .unwrap(),
+            astroport_voting_escrow::error::ContractError::InvalidUnlockPeriod { min, max }
+        );
+    }
+
+    helper.set_unlock_period(&owner, 7 * DAY).unwrap();
+    let unlock_period = helper.query_vxastro_config().unwrap().unlock_period;
+    assert_eq!(unlock_period, 7 * DAY);
+
+    // Bob unlocks with the new period
+    helper.unlock(&bob).unwrap();
+    helper.timetravel(unlock_period);
+    helper.withdraw(&bob).unwrap();
+
+    // Alice's unlock keeps its original end time
+    let err = helper.withdraw(&alice).unwrap_err();
+    assert_eq!(
+        err.downcast::<astroport_voting_escrow::error::ContractError>()
+            .unwrap(),
+        astroport_voting_escrow::error::ContractError::UnlockPeriodNotExpired(alice_end)
+    );
+
+    helper.timetravel(alice_end - helper.app.block_info().time.seconds());
+    helper.withdraw(&alice).unwrap();
+}
+
+#[test]
+fn test_vxastro_pause_mask() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+
+    let alice = helper.app.api().addr_make("alice");
+    let bob = helper.app.api().addr_make("bob");
+    helper.lock(&alice, 10_000000).unwrap();
+    helper.lock(&bob, 10_000000).unwrap();
+    helper.unlock(&bob).unwrap();
+
+    let paused = |err: anyhow::Error, operation: &str| {
+        assert_eq!(
+            err.downcast::<astroport_voting_escrow::error::ContractError>()
+                .unwrap(),
+            astroport_voting_escrow::error::ContractError::Paused(operation.to_string())
+        );
+    };
+
+    let random = helper.app.api().addr_make("random");
+    let err = helper
+        .set_pause_mask(&random, voting_escrow::PAUSE_ALL)
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<astroport_voting_escrow::error::ContractError>()
+            .unwrap(),
+        astroport_voting_escrow::error::ContractError::Unauthorized {}
+    );
+
+    let err = helper
+        .set_pause_mask(&owner, voting_escrow::PAUSE_ALL + 1)
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<astroport_voting_escrow::error::ContractError>()
+            .unwrap(),
+        astroport_voting_escrow::error::ContractError::InvalidPauseMask(
+            voting_escrow::PAUSE_ALL + 1
+        )
+    );
+
+    // Pause deposits while keeping withdrawals open
+    helper
+        .set_pause_mask(
+            &owner,
+            voting_escrow::PAUSE_NEW_LOCKS | voting_escrow::PAUSE_DEPOSITS,
+        )
+        .unwrap();
+    assert_eq!(
+        helper.query_vxastro_paused().unwrap(),
+        voting_escrow::PausedResponse {
+            pause_mask: voting_escrow::PAUSE_NEW_LOCKS | voting_escrow::PAUSE_DEPOSITS,
+            new_locks: true,
+            deposits: true,
+            withdrawals: false,
+        }
+    );
+
+    let carol = helper.app.api().addr_make("carol");
+    paused(helper.lock(&carol, 10_000000).unwrap_err(), "New locks");
+    paused(helper.lock(&alice, 10_000000).unwrap_err(), "Deposits");
+    paused(helper.relock(&bob).unwrap_err(), "Deposits");
+
+    // Unlocking and withdrawals are still available
+    helper.unlock(&alice).unwrap();
+    helper.timetravel(voting_escrow::DEFAULT_UNLOCK_PERIOD);
+    helper.withdraw(&bob).unwrap();
+
+    // Pause withdrawals only
+    helper
+        .set_pause_mask(&owner, voting_escrow::PAUSE_WITHDRAWALS)
+        .unwrap();
+    paused(helper.withdraw(&alice).unwrap_err(), "Withdrawals");
+    helper.lock(&carol, 10_000000).unwrap();
+
+    helper.set_pause_mask(&owner, 0).unwrap();
+    assert_eq!(helper.query_vxastro_paused().unwrap().pause_mask, 0);
+    helper.withdraw(&alice).unwrap();
+}
+
+#[test]
+fn test_vxastro_exit_queue() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+
+    let vxastro_err = |err: anyhow::Error| {
+        err.downcast::<astroport_voting_escrow::error::ContractError>()
+            .unwrap()
+    };
+
+    let alice = helper.app.api().addr_make("alice");
+    let bob = helper.app.api().addr_make("bob");
+    let carol = helper.app.api().addr_make("carol");
+    let mut amounts = vec![];
+    for user in [&alice, &bob, &carol] {
+        helper.lock(user, 10_000000).unwrap();
+        amounts.push(helper.user_vp(user, None).unwrap());
+        helper.unlock(user).unwrap();
+    }
+    helper.timetravel(voting_escrow::DEFAULT_UNLOCK_PERIOD);
+
+    let random = helper.app.api().addr_make("random");
+    let err = helper.set_exit_queue_limit(&random, 2).unwrap_err();
+    assert_eq!(
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::Unauthorized {}
+    );
+    let err = helper
+        .set_exit_queue_limit(&owner, voting_escrow::MAX_EXIT_QUEUE_LIMIT + 1)
+        .unwrap_err();
+    assert_eq!(
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::InvalidExitQueueLimit(
+            voting_escrow::MAX_EXIT_QUEUE_LIMIT
+        )
+    );
+
+    helper.set_exit_queue_limit(&owner, 2).unwrap();
+    assert_eq!(helper.query_vxastro_config().unwrap().exit_queue_limit, 2);
+
+    // Withdrawals are queued instead of being paid out
+    for user in [&alice, &bob, &carol] {
+        helper.withdraw(user).unwrap();
+    }
+    let xastro_balance = |helper: &ControllerHelper, user: &Addr| {
+        helper
+            .app
+            .wrap()
+            .query_balance(user, &helper.xastro)
+            .unwrap()
+            .amount
+    };
+    assert_eq!(xastro_balance(&helper, &alice), Uint128::zero());
+
+    let total_amount: Uint128 = amounts.iter().sum();
+    assert_eq!(
+        helper.query_exit_queue().unwrap(),
+        voting_escrow::ExitQueueResponse {
+            limit: 2,
+            length: 3,
+            total_amount,
+            processed_in_block: 0,
+        }
+    );
+    let carol_queue = helper.query_exit_queue_position(&carol).unwrap();
+    assert_eq!(carol_queue.len(), 1);
+    assert_eq!(carol_queue[0].amount, amounts[2]);
+    assert_eq!(carol_queue[0].position, 2);
+    assert_eq!(carol_queue[0].eta_blocks, 2);
+
+    // The queue can't be disabled until it is drained
+    let err = helper.set_exit_queue_limit(&owner, 0).unwrap_err();
+    assert_eq!(
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::ExitQueueNotEmpty {}
+    );
+
+    // Paused withdrawals pause the queue processing as well
+    helper
+        .set_pause_mask(&owner, voting_escrow::PAUSE_WITHDRAWALS)
+        .unwrap();
+    let err = helper.process_exit_queue(&random, None).unwrap_err();
+    assert_eq!(
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::Paused("Withdrawals".to_string())
+    );
+    helper.set_pause_mask(&owner, 0).unwrap();
+
+    // Anyone can process the queue in FIFO order up to the per block limit
+    helper.process_exit_queue(&random, Some(1)).unwrap();
+    assert_eq!(xastro_balance(&helper, &alice), amounts[0]);
+    helper.process_exit_queue(&random, None).unwrap();
+    assert_eq!(xastro_balance(&helper, &bob), amounts[1]);
+    assert_eq!(xastro_balance(&helper, &carol), Uint128::zero());
+
+    let err = helper.process_exit_queue(&random, None).unwrap_err();
+    assert_eq!(
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::ExitQueueBlockLimitReached(2)
+    );
+    assert_eq!(helper.query_exit_queue().unwrap().processed_in_block, 2);
+    assert_eq!(
+        helper.query_exit_queue_position(&carol).unwrap()[0].position,
+        0
+    );
+
+    helper.blocktravel(1);
+    helper.process_exit_queue(&random, None).unwrap();
+    assert_eq!(xastro_balance(&helper, &carol), amounts[2]);
+    assert!(helper.query_exit_queue_position(&carol).unwrap().is_empty());
+    assert_eq!(
+        helper.query_exit_queue().unwrap(),
+        voting_escrow::ExitQueueResponse {
+            limit: 2,
+            length: 0,
+            total_amount: Uint128::zero(),
+            processed_in_block: 1,
+        }
+    );
+
+    let err = helper.process_exit_queue(&random, None).unwrap_err();
+    assert_eq!(
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::ExitQueueEmpty {}
+    );
+
+    // Withdrawals are paid out immediately once the queue is disabled
+    helper.set_exit_queue_limit(&owner, 0).unwrap();
+    helper.lock(&alice, 10_000000).unwrap();
+    let amount = helper.user_vp(&alice, None).unwrap();
+    helper.unlock(&alice).unwrap();
+    helper.timetravel(voting_escrow::DEFAULT_UNLOCK_PERIOD);
+    helper.withdraw(&alice).unwrap();
+    assert_eq!(xastro_balance(&helper, &alice), amounts[0] + amount);
+}
+
+#[test]
+fn test_vxastro_deposit_fee() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+    let fee_distributor = helper.app.api().addr_make("fee_distributor");
+
+    let vxastro_err = |err: anyhow::Error| {
+        err.downcast::<astroport_voting_escrow::error::ContractError>()
+            .unwrap()
+    };
+
+    let random = helper.app.api().addr_make("random");
+    let err = helper
+        .set_deposit_fee(&random, 100, Some(&fee_distributor))
+        .unwrap_err();
+    assert_eq!(
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::Unauthorized {}
+    );
+
+    let err = helper
+        .set_deposit_fee(&owner, voting_escrow::MAX_DEPOSIT_FEE_BPS + 1, None)
+        .unwrap_err();
+    assert_eq!(
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::InvalidDepositFee(
+            voting_escrow::MAX_DEPOSIT_FEE_BPS
+        )
+    );
+
+    let err = helper.set_deposit_fee(&owner, 100, None).unwrap_err();
+    assert_eq!(
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::FeeReceiverNotSet {}
+    );
+
+    // 1% deposit fee
+    helper
+        .set_deposit_fee(&owner, 100, Some(&fee_distributor))
+        .unwrap();
+    let config = helper.query_vxastro_config().unwrap();
+    assert_eq!(config.deposit_fee_bps, 100);
+    assert_eq!(config.fee_receiver, Some(fee_distributor.clone()));
+
+    let alice = helper.app.api().addr_make("alice");
+    let resp = helper.lock(&alice, 10_000000).unwrap();
+    assert!(resp.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "lock")
+            .add_attribute("receiver", alice.to_string())
+            .add_attribute("deposit_amount", "10000000")
+            .add_attribute("deposit_fee", "100000")
+            .add_attribute("new_lock_amount", "9900000")
+    ));
+    // Deposits to existing positions are charged as well
+    helper.lock(&alice, 10_000000).unwrap();
+    assert_eq!(helper.user_vp(&alice, None).unwrap().u128(), 19_800000);
+    assert_eq!(helper.total_vp(None).unwrap().u128(), 19_800000);
+    assert_eq!(helper.query_vxastro_accrued_fees().unwrap().u128(), 200000);
+
+    // Exempted protocol contracts lock without fees
+    let protocol = helper.app.api().addr_make("protocol");
+    helper
+        .set_fee_exemptions(&owner, &[&protocol], &[])
+        .unwrap();
+    let exemptions: Vec<Addr> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(&helper.vxastro, &voting_escrow::QueryMsg::FeeExemptions {})
+        .unwrap();
+    assert_eq!(exemptions, vec![protocol.clone()]);
+
+    helper.lock(&protocol, 10_000000).unwrap();
+    assert_eq!(helper.user_vp(&protocol, None).unwrap().u128(), 10_000000);
+    assert_eq!(helper.query_vxastro_accrued_fees().unwrap().u128(), 200000);
+
+    // Exemptions apply to the lock receiver, not to the sender
+    helper.lock_for(&alice, &protocol, 10_000000).unwrap();
+    assert_eq!(helper.user_vp(&protocol, None).unwrap().u128(), 20_000000);
+    assert_eq!(helper.query_vxastro_accrued_fees().unwrap().u128(), 200000);
+    helper.lock_for(&protocol, &alice, 10_000000).unwrap();
+    assert_eq!(helper.user_vp(&alice, None).unwrap().u128(), 29_700000);
+    assert_eq!(helper.query_vxastro_accrued_fees().unwrap().u128(), 300000);
+
+    // Anyone can forward accrued fees
+    let resp = helper.forward_fees(&random).unwrap();
+    assert!(resp.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "forward_fees")
+            .add_attribute("fee_receiver", fee_distributor.to_string())
+            .add_attribute("amount", "300000")
+    ));
+    let fee_distributor_bal = helper
+        .app
+        .wrap()
+        .query_balance(&fee_distributor, &helper.xastro)
+        .unwrap();
+    assert_eq!(fee_distributor_bal.amount.u128(), 300000);
+    assert_eq!(
+        helper.query_vxastro_accrued_fees().unwrap(),
+        Uint128::zero()
+    );
+
+    let err = helper.forward_fees(&random).unwrap_err();
+    assert_eq!(
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::NoFeesToForward {}
+    );
+
+    // Removed exemptions and disabled fees
+    helper
+        .set_fee_exemptions(&owner, &[], &[&protocol])
+        .unwrap();
+    helper.lock(&protocol, 10_000000).unwrap();
+    assert_eq!(helper.user_vp(&protocol, None).unwrap().u128(), 29_900000);
+
+    helper.set_deposit_fee(&owner, 0, None).unwrap();
+    helper.lock(&protocol, 10_000000).unwrap();
+    assert_eq!(helper.user_vp(&protocol, None).unwrap().u128(), 39_900000);
+    assert_eq!(helper.query_vxastro_accrued_fees().unwrap().u128(), 100000);
+
+    // Withdrawals return only locked xASTRO
+    helper.unlock(&alice).unwrap();
+    helper.timetravel(voting_escrow::DEFAULT_UNLOCK_PERIOD);
+    helper.withdraw(&alice).unwrap();
+    let alice_bal = helper
+        .app
+        .wrap()
+        .query_balance(&alice, &helper.xastro)
+        .unwrap();
+    assert_eq!(alice_bal.amount.u128(), 29_700000);
+}
+
+#[test]
+fn test_vxastro_insurance_fund() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+    let insurance_fund = helper.app.api().addr_make("insurance_fund");
+    let fee_distributor = helper.app.api().addr_make("fee_distributor");
+
+    let vxastro_err = |err: anyhow::Error| {
+        err.downcast::<astroport_voting_escrow::error::ContractError>()
+            .unwrap()
+    };
+
+    let random = helper.app.api().addr_make("random");
+    let err = helper
+        .set_insurance_fund(&random, 100, Some(&insurance_fund))
+        .unwrap_err();
+    assert_eq!(
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::Unauthorized {}
+    );
+
+    let err = helper
+        .set_insurance_fund(&owner, voting_escrow::MAX_INSURANCE_FUND_BPS + 1, None)
+        .unwrap_err();
+    assert_eq!(
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::InvalidInsuranceContribution(
+            voting_escrow::MAX_INSURANCE_FUND_BPS
+        )
+    );
+
+    let err = helper.set_insurance_fund(&owner, 100, None).unwrap_err();
+    assert_eq!(
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::InsuranceFundNotSet {}
+    );
+
+    // 2% insurance fund contribution alongside 1% deposit fee
+    helper
+        .set_insurance_fund(&owner, 200, Some(&insurance_fund))
+        .unwrap();
+    helper
+        .set_deposit_fee(&owner, 100, Some(&fee_distributor))
+        .unwrap();
+    let config = helper.query_vxastro_config().unwrap();
+    assert_eq!(config.insurance_fund_bps, 200);
+    assert_eq!(config.insurance_fund, Some(insurance_fund.clone()));
+
+    let alice = helper.app.api().addr_make("alice");
+    let resp = helper.lock(&alice, 10_000000).unwrap();
+    assert!(resp.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "lock")
+            .add_attribute("receiver", alice.to_string())
+            .add_attribute("deposit_amount", "10000000")
+            .add_attribute("deposit_fee", "100000")
+            .add_attribute("insurance_contribution", "200000")
+            .add_attribute("new_lock_amount", "9700000")
+    ));
+    // Deposits to existing positions contribute as well
+    helper.lock(&alice, 10_000000).unwrap();
+    assert_eq!(helper.user_vp(&alice, None).unwrap().u128(), 19_400000);
+    assert_eq!(
+        helper.query_insurance_contributions(&alice).unwrap().u128(),
+        400000
+    );
+
+    // Contributions are sent to the fund right away
+    let fund_bal = helper
+        .app
+        .wrap()
+        .query_balance(&insurance_fund, &helper.xastro)
+        .unwrap();
+    assert_eq!(fund_bal.amount.u128(), 400000);
+    assert_eq!(helper.query_vxastro_accrued_fees().unwrap().u128(), 200000);
+
+    // Exempted addresses don't contribute, but still pay the deposit fee
+    let protocol = helper.app.api().addr_make("protocol");
+    helper
+        .set_insurance_exemptions(&owner, &[&protocol], &[])
+        .unwrap();
+    let exemptions: Vec<Addr> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &helper.vxastro,
+            &voting_escrow::QueryMsg::InsuranceExemptions {},
+        )
+        .unwrap();
+    assert_eq!(exemptions, vec![protocol.clone()]);
+
+    helper.lock(&protocol, 10_000000).unwrap();
+    assert_eq!(helper.user_vp(&protocol, None).unwrap().u128(), 9_900000);
+    assert_eq!(
+        helper.query_insurance_contributions(&protocol).unwrap(),
+        Uint128::zero()
+    );
+
+    // Exemptions apply to the lock receiver, not to the sender
+    helper.lock_for(&alice, &protocol, 10_000000).unwrap();
+    assert_eq!(helper.user_vp(&protocol, None).unwrap().u128(), 19_800000);
+    assert_eq!(
+        helper.query_insurance_contributions(&protocol).unwrap(),
+        Uint128::zero()
+    );
+    helper.lock_for(&protocol, &alice, 10_000000).unwrap();
+    assert_eq!(helper.user_vp(&alice, None).unwrap().u128(), 29_100000);
+    assert_eq!(
+        helper.query_insurance_contributions(&alice).unwrap().u128(),
+        600000
+    );
+
+    // Removed exemptions and disabled contributions
+    helper
+        .set_insurance_exemptions(&owner, &[], &[&protocol])
+        .unwrap();
+    helper.lock(&protocol, 10_000000).unwrap();
+    assert_eq!(helper.user_vp(&protocol, None).unwrap().u128(), 29_500000);
+
+    helper.set_insurance_fund(&owner, 0, None).unwrap();
+    let resp = helper.lock(&protocol, 10_000000).unwrap();
+    assert!(resp.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "lock")
+            .add_attribute("insurance_contribution", "0")
+    ));
+    assert_eq!(helper.user_vp(&protocol, None).unwrap().u128(), 39_400000);
+
+    // Lifetime contributions are kept per user
+    assert_eq!(
+        helper
+            .query_insurance_contributions(&protocol)
+            .unwrap()
+            .u128(),
+        200000
+    );
+    assert_eq!(
+        helper.query_total_insurance_contributions().unwrap().u128(),
+        800000
+    );
+    let fund_bal = helper
+        .app
+        .wrap()
+        .query_balance(&insurance_fund, &helper.xastro)
+        .unwrap();
+    assert_eq!(fund_bal.amount.u128(), 800000);
+}
+
+#[test]
+fn test_tune_skips_pools_without_reward_slots() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+
+    let lp_token1 = helper.create_pair("token1", "token2");
+    let lp_token2 = helper.create_pair("token1", "token3");
+
+    helper
+        .add_outpost(
+            "neutron",
+            OutpostInfo {
+                astro_denom: helper.astro.clone(),
+                params: None,
+                astro_pool_config: None,
+                jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
+            },
+        )
+        .unwrap();
+
+    let user = helper.app.api().addr_make("user");
+    let whitelist_fee = helper.whitelisting_fee.clone();
+    for pool in [&lp_token1, &lp_token2] {
+        helper.mint_tokens(&user, &[whitelist_fee.clone()]).unwrap();
+        helper
+            .whitelist(&user, pool, &[whitelist_fee.clone()])
+            .unwrap();
+    }
+
+    helper.lock(&user, 1000).unwrap();
+    helper
+        .vote(
+            &user,
+            &[
+                (lp_token1.to_string(), Decimal::percent(50)),
+                (lp_token2.to_string(), Decimal::percent(50)),
+            ],
+        )
+        .unwrap();
+
+    // Occupy all external reward slots of the second pool
+    for i in 0..MAX_REWARD_TOKENS {
+        helper
+            .incentivize(&lp_token2, coin(1_000_000_000, format!("reward{i}")))
+            .unwrap();
+    }
+
+    helper.timetravel(EPOCH_LENGTH);
+    helper
+        .mint_tokens(
+            &helper.emission_controller.clone(),
+            &coins(50_000_000_000_000, helper.astro.clone()),
+        )
+        .unwrap();
+
+    let expected_skipped = vec![SkippedPool {
+        pool: lp_token2.clone(),
+        reason: SkipReason::RewardLimitReached,
+    }];
+
+    let sim_tune_result = helper.query_simulate_tune().unwrap();
+    assert_eq!(sim_tune_result.skipped_pools, expected_skipped);
+
+    let resp = helper.tune(&owner).unwrap();
+    assert!(
+        resp.has_event(&Event::new("wasm-skipped_pool").add_attributes([
+            ("pool", lp_token2.as_str()),
+            ("reason", "reward_limit_reached")
+        ]))
+    );
+
+    let tune_info = helper.query_tune_info(None).unwrap();
+    assert_eq!(tune_info.skipped_pools, expected_skipped);
+    let hub_pools = tune_info.pools_grouped.get("neutron").unwrap();
+    assert_eq!(hub_pools.len(), 1);
+    assert_eq!(hub_pools[0].0, lp_token1);
+
+    // The first pool received ASTRO emissions while the second one didn't
+    let rewards = helper.query_rewards(&lp_token1).unwrap();
+    assert_eq!(rewards.len(), 1);
+    let rewards = helper.query_rewards(&lp_token2).unwrap();
+    assert!(rewards.iter().all(|reward| !matches!(
+        &reward.reward,
+        RewardType::Ext { info, .. } if *info == AssetInfo::native(&helper.astro)
+    )));
+}
+
+#[test]
+fn test_min_pool_emissions_and_tie_breaking() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+
+    let lp_token1 = helper.create_pair("token1", "token2");
+    let lp_token2 = helper.create_pair("token1", "token3");
+    let lp_token3 = helper.create_pair("token1", "token4");
+
+    helper
+        .add_outpost(
+            "neutron",
+            OutpostInfo {
+                astro_denom: helper.astro.clone(),
+                params: None,
+                astro_pool_config: None,
+                jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
+            },
+        )
+        .unwrap();
+
+    let user = helper.app.api().addr_make("user");
+    let whitelist_fee = helper.whitelisting_fee.clone();
+    for pool in [&lp_token1, &lp_token2, &lp_token3] {
+        helper.mint_tokens(&user, &[whitelist_fee.clone()]).unwrap();
+        helper
+            .whitelist(&user, pool, &[whitelist_fee.clone()])
+            .unwrap();
+    }
+
+    helper.lock(&user, 1000).unwrap();
+    helper
+        .vote(
+            &user,
+            &[
+                (lp_token1.to_string(), Decimal::percent(45)),
+                (lp_token2.to_string(), Decimal::percent(45)),
+                (lp_token3.to_string(), Decimal::percent(10)),
+            ],
+        )
+        .unwrap();
+
+    helper.timetravel(EPOCH_LENGTH);
+    helper
+        .mint_tokens(
+            &helper.emission_controller.clone(),
+            &coins(50_000_000_000_000, helper.astro.clone()),
+        )
+        .unwrap();
+
+    let update_config_msg = |pools_per_outpost: Option<u64>,
+                             min_pool_emissions: Option<Uint128>| {
+        HubMsg::UpdateConfig(Box::new(UpdateConfigMsg {
+            pools_per_outpost,
+            min_pool_emissions,
+            ..Default::default()
+        }))
+    };
+
+    // Pools with equal votes at the cut-off are ranked by LP token in lexicographic order
+    helper
+        .execute_hub_msg(&owner, update_config_msg(Some(1), None))
+        .unwrap();
+    let sim_tune_result = helper.query_simulate_tune().unwrap();
+    let selected = sim_tune_result
+        .pool_weights
+        .iter()
+        .map(|pool_weight| pool_weight.pool.clone())
+        .collect_vec();
+    assert_eq!(selected, vec![lp_token1.clone().min(lp_token2.clone())]);
+
+    helper
+        .execute_hub_msg(&owner, update_config_msg(Some(5), None))
+        .unwrap();
+    let sim_tune_result = helper.query_simulate_tune().unwrap();
+    let pools = sim_tune_result.next_pools_grouped.get("neutron").unwrap();
+    assert_eq!(pools.len(), 3);
+    let dust_amount = pools
+        .iter()
+        .find(|(pool, _)| *pool == lp_token3)
+        .map(|(_, amount)| *amount)
+        .unwrap();
+
+    let err = helper
+        .execute_hub_msg(
+            &owner,
+            update_config_msg(None, Some(helper.query_config().unwrap().max_astro)),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: min_pool_emissions must be lower than max_astro"
+    );
+
+    helper
+        .execute_hub_msg(
+            &owner,
+            update_config_msg(None, Some(dust_amount + Uint128::one())),
+        )
+        .unwrap();
+
+    let expected_skipped = vec![SkippedPool {
+        pool: lp_token3.clone(),
+        reason: SkipReason::BelowMinEmissions,
+    }];
+
+    // The third pool is excluded and its share is redistributed between the remaining pools
+    let sim_tune_result = helper.query_simulate_tune().unwrap();
+    assert_eq!(sim_tune_result.skipped_pools, expected_skipped);
+    let emissions_amount = sim_tune_result.new_emissions_state.emissions_amount;
+    let pools = sim_tune_result.next_pools_grouped.get("neutron").unwrap();
+    assert_eq!(pools.len(), 2);
+    for (pool, amount) in pools {
+        assert_ne!(*pool, lp_token3);
+        assert_eq!(*amount, emissions_amount.multiply_ratio(1u8, 2u8));
+    }
+
+    let resp = helper.tune(&owner).unwrap();
+    assert!(
+        resp.has_event(&Event::new("wasm-skipped_pool").add_attributes([
+            ("pool", lp_token3.as_str()),
+            ("reason", "below_min_emissions")
+        ]))
+    );
+
+    let tune_info = helper.query_tune_info(None).unwrap();
+    assert_eq!(tune_info.skipped_pools, expected_skipped);
+    assert_eq!(tune_info.pools_grouped.get("neutron").unwrap().len(), 2);
+}
+
+#[test]
+fn test_vxastro_subscribers() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+
+    let subscriber_code_id = helper.app.store_code(mock_subscriber_contract());
+    let [subscriber, failing_subscriber] = [false, true].map(|fail| {
+        helper
+            .app
+            .instantiate_contract(subscriber_code_id, owner.clone(), &fail, &[], "label", None)
+            .unwrap()
+    });
+
+    let random = helper.app.api().addr_make("random");
+    let err = helper
+        .set_subscriber(&random, &subscriber, true)
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<astroport_voting_escrow::error::ContractError>()
+            .unwrap(),
+        astroport_voting_escrow::error::ContractError::Unauthorized {}
+    );
+
+    helper.set_subscriber(&owner, &subscriber, true).unwrap();
+    helper
+        .set_subscriber(&owner, &failing_subscriber, true)
+        .unwrap();
+
+    let subscribers: Vec<(Addr, bool)> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(&helper.vxastro, &voting_escrow::QueryMsg::Subscribers {})
+        .unwrap();
+    let mut expected = vec![
+        (subscriber.clone(), true),
+        (failing_subscriber.clone(), true),
+    ];
+    expected.sort();
+    assert_eq!(subscribers, expected);
+
+    // Failing subscriber doesn't revert the lock
+    let alice = helper.app.api().addr_make("alice");
+    let resp = helper.lock(&alice, 1000).unwrap();
+    assert!(resp.has_event(&Event::new("wasm").add_attributes([
+        ("subscriber_action", "lock"),
+        ("subscriber_user", alice.as_str())
+    ])));
+    assert!(resp
+        .has_event(&Event::new("wasm").add_attribute("action", "subscriber_notification_failed")));
+    assert_eq!(helper.user_vp(&alice, None).unwrap().u128(), 1000);
+
+    // Disabled subscribers don't receive notifications
+    helper.set_subscriber(&owner, &subscriber, false).unwrap();
+    helper
+        .set_subscriber(&owner, &failing_subscriber, false)
+        .unwrap();
+    let resp = helper.unlock(&alice).unwrap();
+    assert!(!resp.has_event(&Event::new("wasm").add_attribute("subscriber_action", "unlock")));
+    assert!(!resp
+        .has_event(&Event::new("wasm").add_attribute("action", "subscriber_notification_failed")));
+
+    helper.set_subscriber(&owner, &subscriber, true).unwrap();
+    let resp = helper.relock(&alice).unwrap();
+    assert!(resp.has_event(&Event::new("wasm").add_attributes([
+        ("subscriber_action", "relock"),
+        ("subscriber_user", alice.as_str())
+    ])));
+
+    helper
+        .app
+        .execute_contract(
+            owner.clone(),
+            helper.vxastro.clone(),
+            &voting_escrow::ExecuteMsg::RemoveSubscriber {
+                contract: subscriber.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    let subscribers: Vec<(Addr, bool)> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(&helper.vxastro, &voting_escrow::QueryMsg::Subscribers {})
+        .unwrap();
+    assert_eq!(subscribers, vec![(failing_subscriber, false)]);
+}
+
+#[test]
+fn test_pool_migration() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+
+    let old_lp = helper.create_pair("token1", "token2");
+    let new_lp = helper.create_pair("token1", "token3");
+    let other_lp = helper.create_pair("token2", "token3");
+
+    let neutron = OutpostInfo {
+        astro_denom: helper.astro.clone(),
+        params: None,
+        astro_pool_config: None,
+        jailed: false,
+        permissions: OUTPOST_PERMIT_ALL,
+    };
+    helper.add_outpost("neutron", neutron).unwrap();
+
+    let user1 = helper.app.api().addr_make("user1");
+    let user2 = helper.app.api().addr_make("user2");
+
+    let whitelist_fee = helper.whitelisting_fee.clone();
+    for pool in [&old_lp, &other_lp] {
+        helper
+            .mint_tokens(&user1, &[whitelist_fee.clone()])
+            .unwrap();
+        helper
+            .whitelist(&user1, pool, &[whitelist_fee.clone()])
+            .unwrap();
+    }
+
+    helper.lock(&user1, 1000).unwrap();
+    helper.lock(&user2, 2000).unwrap();
+    helper
+        .vote(&user1, &[(old_lp.clone(), Decimal::one())])
+        .unwrap();
+    helper
+        .vote(
+            &user2,
+            &[
+                (old_lp.clone(), Decimal::percent(50)),
+                (other_lp.clone(), Decimal::percent(50)),
+            ],
+        )
+        .unwrap();
+    assert_eq!(helper.query_pool_vp(&old_lp, None).unwrap().u128(), 2000);
+
+    let err = helper.migrate_pool(&user1, &old_lp, &new_lp).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    let err = helper.migrate_pool(&owner, &new_lp, &old_lp).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PoolIsNotWhitelisted(new_lp.clone())
+    );
+
+    let err = helper.migrate_pool(&owner, &old_lp, &other_lp).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PoolAlreadyWhitelisted(other_lp.clone())
+    );
+
+    let err = helper
+        .migrate_pool(&owner, &old_lp, "osmo1pool")
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidOutpostPrefix("osmo1pool".to_string())
+    );
+
+    // Assembly is also allowed to migrate pools
+    let assembly = helper.assembly.clone();
+    let resp = helper.migrate_pool(&assembly, &old_lp, &new_lp).unwrap();
+    assert!(resp.has_event(
+        &Event::new("wasm-pool_migrated")
+            .add_attribute("old_lp", &old_lp)
+            .add_attribute("new_lp", &new_lp)
+            .add_attribute("voting_power", "2000")
+            .add_attribute("completed", "true")
+    ));
+
+    // All votes fit into a single batch
+    let err = helper.migrate_pool_votes(&user1, None).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NoPoolMigrationInProgress {}
+    );
+
+    let whitelist = helper.query_whitelist().unwrap();
+    assert!(!whitelist.contains(&old_lp));
+    assert!(whitelist.contains(&new_lp));
+    helper.query_pool_vp(&old_lp, None).unwrap_err();
+    assert_eq!(helper.query_pool_vp(&new_lp, None).unwrap().u128(), 2000);
+
+    // User votes are moved to the new LP token
+    let user_info = helper.user_info(&user1, None).unwrap();
+    assert_eq!(
+        user_info.votes,
+        HashMap::from([(new_lp.clone(), Decimal::one())])
+    );
+    assert_eq!(
+        user_info.applied_votes,
+        HashMap::from([(new_lp.clone(), Decimal::one())])
+    );
+    let user_info = helper.user_info(&user2, None).unwrap();
+    assert_eq!(
+        user_info.votes,
+        HashMap::from([
+            (new_lp.clone(), Decimal::percent(50)),
+            (other_lp.clone(), Decimal::percent(50))
+        ])
+    );
+
+    // Migrated LP token can't be whitelisted again
+    helper
+        .mint_tokens(&user1, &[whitelist_fee.clone()])
+        .unwrap();
+    let err = helper
+        .whitelist(&user1, &old_lp, &[whitelist_fee.clone()])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PoolMigrated(old_lp.clone())
+    );
+    let err = helper.migrate_pool(&owner, &new_lp, &old_lp).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PoolMigrated(old_lp.clone())
+    );
+
+    // Voting power updates are applied to the new LP token
+    helper.lock(&user1, 1000).unwrap();
+    assert_eq!(helper.query_pool_vp(&new_lp, None).unwrap().u128(), 3000);
+    let user_info = helper.user_info(&user1, None).unwrap();
+    assert_eq!(
+        user_info.votes,
+        HashMap::from([(new_lp.clone(), Decimal::one())])
+    );
+
+    // Revoting cancels previous votes for the new LP token
+    helper.timetravel(EPOCH_LENGTH);
+    helper
+        .vote(&user2, &[(other_lp.clone(), Decimal::one())])
+        .unwrap();
+    assert_eq!(helper.query_pool_vp(&new_lp, None).unwrap().u128(), 2000);
+    assert_eq!(helper.query_pool_vp(&other_lp, None).unwrap().u128(), 2000);
+}
+
+#[test]
+fn test_outpost_whitelist_pool() {
+    let mut helper = ControllerHelper::new();
+
+    for (prefix, voting_channel) in [("osmo", "channel-1"), ("terra", "channel-3")] {
+        helper
+            .add_outpost(
+                prefix,
+                OutpostInfo {
+                    astro_denom:
+                        "ibc/C4CFF46FD6DE35CA4CF4CE031E643C8FDC9BA4B99AE598E9B0ED98FE3A2319F9"
+                            .to_string(),
+                    params: Some(OutpostParams {
+                        emissions_controller: format!("{prefix}1controller"),
+                        voting_channel: voting_channel.to_string(),
+                        ics20_channel: "channel-2".to_string(),
+                        escrow_address: Addr::unchecked(""),
+                        ibc_timeout: None,
+                        relayer_fee: None,
+                        pools_per_packet: None,
+                    }),
+                    astro_pool_config: None,
+                    jailed: false,
+                    permissions: OUTPOST_PERMIT_ALL,
+                },
+            )
+            .unwrap();
+    }
+
+    let osmo_pool = format!("factory/osmo1pool/{}", LP_SUBDENOM);
+    let whitelist_packet = |pool: &str, fee: u128| VxAstroIbcMsg::WhitelistPool {
+        user: "osmo1user".to_string(),
+        pool: pool.to_string(),
+        fee: fee.into(),
+    };
+    let parse_ack =
+        |resp: AppResponse| -> IbcAckResult { from_json(resp.data.as_ref().unwrap()).unwrap() };
+
+    // Fee must match the whitelisting fee
+    let fee = helper.whitelisting_fee.amount.u128();
+    let resp = helper
+        .mock_packet_receive(whitelist_packet(&osmo_pool, fee - 1))
+        .unwrap();
+    assert!(matches!(parse_ack(resp), IbcAckResult::Error(_)));
+
+    // Outpost can't whitelist pools of other outposts
+    let terra_pool = format!("factory/terra1pool/{}", LP_SUBDENOM);
+    let resp = helper
+        .mock_packet_receive(whitelist_packet(&terra_pool, fee))
+        .unwrap();
+    assert!(matches!(parse_ack(resp), IbcAckResult::Error(_)));
+    assert_eq!(helper.query_whitelist().unwrap(), Vec::<String>::new());
+
+    // Successful whitelisting acknowledges with the fee receiver address
+    let resp = helper
+        .mock_packet_receive(whitelist_packet(&osmo_pool, fee))
+        .unwrap();
+    let fee_receiver = helper.query_config().unwrap().fee_receiver;
+    assert_eq!(
+        parse_ack(resp.clone()),
+        IbcAckResult::Ok(to_json_binary(&fee_receiver).unwrap())
+    );
+    resp.assert_event(&Event::new("wasm").add_attributes([
+        ("action", "whitelist_pool"),
+        ("pool", osmo_pool.as_str()),
+        ("outpost", "osmo"),
+    ]));
+    assert_eq!(helper.query_whitelist().unwrap(), vec![osmo_pool.clone()]);
+
+    // Pool can't be whitelisted twice
+    let resp = helper
+        .mock_packet_receive(whitelist_packet(&osmo_pool, fee))
+        .unwrap();
+    assert!(matches!(parse_ack(resp), IbcAckResult::Error(_)));
+}
+
+#[test]
+fn test_max_voter_share() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+
+    let escrow_address =
+        determine_ics20_escrow_address(helper.app.api(), "transfer", "channel-2").unwrap();
+    helper
+        .add_outpost(
+            "osmo",
+            OutpostInfo {
+                astro_denom: "ibc/C4CFF46FD6DE35CA4CF4CE031E643C8FDC9BA4B99AE598E9B0ED98FE3A2319F9"
+                    .to_string(),
+                params: Some(OutpostParams {
+                    emissions_controller: "osmo1controller".to_string(),
+                    voting_channel: "channel-1".to_string(),
+                    ics20_channel: "channel-2".to_string(),
+                    escrow_address: escrow_address.clone(),
+                    ibc_timeout: None,
+                    relayer_fee: None,
+                    pools_per_packet: None,
+                }),
+                astro_pool_config: None,
+                jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
+            },
+        )
+        .unwrap();
+    let xastro = helper.xastro.clone();
+    helper
+        .mint_tokens(&escrow_address, &coins(1000, xastro))
+        .unwrap();
+    helper
+        .add_outpost(
+            "neutron",
+            OutpostInfo {
+                astro_denom: helper.astro.clone(),
+                params: None,
+                astro_pool_config: None,
+                jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
+            },
+        )
+        .unwrap();
+
+    let update_config = |max_voter_share| {
+        HubMsg::UpdateConfig(Box::new(UpdateConfigMsg {
+            max_voter_share: Some(max_voter_share),
+            ..Default::default()
+        }))
+    };
+    let err = helper
+        .execute_hub_msg(&owner, update_config(Decimal::percent(101)))
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: max_voter_share must be within (0, 1]"
+    );
+    helper
+        .execute_hub_msg(&owner, update_config(Decimal::percent(20)))
+        .unwrap();
+
+    let pool = helper.create_pair("token1", "token2");
+    let whale = helper.app.api().addr_make("whale");
+    let user = helper.app.api().addr_make("user");
+    let whitelist_fee = helper.whitelisting_fee.clone();
+    helper.mint_tokens(&user, &[whitelist_fee.clone()]).unwrap();
+    helper.whitelist(&user, &pool, &[whitelist_fee]).unwrap();
+
+    helper.lock(&whale, 9_000000).unwrap();
+    helper.lock(&user, 1_000000).unwrap();
+
+    let whale_vp = helper.user_vp(&whale, None).unwrap();
+    let user_vp = helper.user_vp(&user, None).unwrap();
+    let cap = helper.total_vp(None).unwrap() * Decimal::percent(20);
+    assert!(whale_vp > cap && user_vp < cap);
+
+    // Simulation reports the capped voting power
+    let votes = [(pool.clone(), Decimal::one())];
+    let simulation = helper.query_simulate_vote(&whale, &votes).unwrap();
+    assert_eq!(
+        simulation,
+        SimulateVoteResponse {
+            voting_power: whale_vp,
+            effective_voting_power: cap,
+            excess_voting_power: whale_vp - cap,
+            pools: vec![(pool.clone(), cap)],
+        }
+    );
+    let simulation = helper.query_simulate_vote(&user, &votes).unwrap();
+    assert_eq!(simulation.effective_voting_power, user_vp);
+    assert_eq!(simulation.excess_voting_power, Uint128::zero());
+
+    let resp = helper.vote(&whale, &votes).unwrap();
+    resp.assert_event(&Event::new("wasm").add_attributes([
+        attr("action", "vote"),
+        attr("voting_power", cap),
+        attr("raw_voting_power", whale_vp),
+        attr("excess_voting_power", whale_vp - cap),
+    ]));
+    helper.vote(&user, &votes).unwrap();
+
+    let whale_info = helper.user_info(&whale, None).unwrap();
+    assert_eq!(whale_info.voting_power, cap);
+    assert_eq!(whale_info.excess_voting_power, whale_vp - cap);
+    assert_eq!(helper.query_pool_vp(&pool, None).unwrap(), cap + user_vp);
+
+    // Outpost votes are capped relative to the outpost supply
+    let resp = helper
+        .mock_packet_receive(VxAstroIbcMsg::EmissionsVote {
+            voter: "osmo1voter".to_string(),
+            voting_power: 500u128.into(),
+            total_voting_power: 1000u128.into(),
+            votes: HashMap::from([(pool.clone(), Decimal::one())]),
+        })
+        .unwrap();
+    assert_eq!(
+        from_json::<IbcAckResult>(resp.data.unwrap()).unwrap(),
+        IbcAckResult::Ok(b"ok".into())
+    );
+    assert_eq!(
+        helper.query_pool_vp(&pool, None).unwrap(),
+        cap + user_vp + Uint128::new(200)
+    );
+
+    // Voting power updates are capped as well
+    helper
+        .mock_packet_receive(VxAstroIbcMsg::UpdateUserVotes {
+            voter: "osmo1voter".to_string(),
+            voting_power: 100u128.into(),
+            total_voting_power: 1000u128.into(),
+            is_unlock: false,
+        })
+        .unwrap();
+    assert_eq!(
+        helper.query_pool_vp(&pool, None).unwrap(),
+        cap + user_vp + Uint128::new(100)
+    );
+    let osmo_info = helper
+        .user_info(&Addr::unchecked("osmo1voter"), None)
+        .unwrap();
+    assert_eq!(osmo_info.excess_voting_power, Uint128::zero());
+}
+
+#[test]
+fn test_outpost_permissions() {
+    let mut helper = ControllerHelper::new();
+
+    let osmo = OutpostInfo {
+        astro_denom: "ibc/C4CFF46FD6DE35CA4CF4CE031E643C8FDC9BA4B99AE598E9B0ED98FE3A2319F9"
+            .to_string(),
+        params: Some(OutpostParams {
+            emissions_controller: "osmo1controller".to_string(),
+            voting_channel: "channel-1".to_string(),
+            ics20_channel: "channel-2".to_string(),
+            escrow_address: Addr::unchecked(""),
+            ibc_timeout: None,
+            relayer_fee: None,
+            pools_per_packet: None,
+        }),
+        astro_pool_config: None,
+        jailed: false,
+        permissions: 1 << 5,
+    };
+    let err = helper.add_outpost("osmo", osmo.clone()).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidOutpostPermissions(1 << 5)
+    );
+
+    // Osmosis is only allowed to whitelist pools
+    helper
+        .add_outpost(
+            "osmo",
+            OutpostInfo {
+                permissions: OUTPOST_PERMIT_WHITELIST_POOL,
+                ..osmo
+            },
+        )
+        .unwrap();
+
+    let ack_error = |resp: &AppResponse| -> Option<AckError> {
+        match from_json(resp.data.as_ref().unwrap()).unwrap() {
+            IbcAckResult::Ok(_) => None,
+            IbcAckResult::Error(err) => Some(AckError::parse(&err)),
+        }
+    };
+
+    let resp = helper
+        .mock_packet_receive(VxAstroIbcMsg::EmissionsVote {
+            voter: "osmo1voter".to_string(),
+            voting_power: 1_000000u128.into(),
+            total_voting_power: Default::default(),
+            votes: HashMap::from([(format!("factory/osmo1pool/{}", LP_SUBDENOM), Decimal::one())]),
+        })
+        .unwrap();
+    let err = ack_error(&resp).unwrap();
+    assert_eq!(err.code, AckErrorCode::ActionNotPermitted);
+    assert_eq!(
+        err.message,
+        ContractError::ActionNotPermitted {
+            prefix: "osmo".to_string(),
+            action: "emissions_vote".to_string(),
+        }
+        .to_string()
+    );
+
+    let update_user_votes = |is_unlock: bool| VxAstroIbcMsg::UpdateUserVotes {
+        voter: "osmo1voter".to_string(),
+        voting_power: Default::default(),
+        total_voting_power: Default::default(),
+        is_unlock,
+    };
+    let resp = helper
+        .mock_packet_receive(update_user_votes(false))
+        .unwrap();
+    assert_eq!(
+        ack_error(&resp).unwrap().code,
+        AckErrorCode::ActionNotPermitted
+    );
+
+    // vxASTRO unlocks are always accepted
+    let resp = helper.mock_packet_receive(update_user_votes(true)).unwrap();
+    assert!(ack_error(&resp).is_none());
+
+    let osmo_pool = format!("factory/osmo1pool/{}", LP_SUBDENOM);
+    let resp = helper
+        .mock_packet_receive(VxAstroIbcMsg::WhitelistPool {
+            user: "osmo1user".to_string(),
+            pool: osmo_pool.clone(),
+            fee: helper.whitelisting_fee.amount,
+        })
+        .unwrap();
+    assert!(ack_error(&resp).is_none());
+    assert_eq!(helper.query_whitelist().unwrap(), vec![osmo_pool]);
+}
+
+#[test]
+fn test_migrate_outpost_channel() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+
+    let osmo = OutpostInfo {
+        astro_denom: "ibc/C4CFF46FD6DE35CA4CF4CE031E643C8FDC9BA4B99AE598E9B0ED98FE3A2319F9"
+            .to_string(),
+        params: Some(OutpostParams {
+            emissions_controller: "osmo1controller".to_string(),
+            voting_channel: "channel-1".to_string(),
+            ics20_channel: "channel-2".to_string(),
+            escrow_address: Addr::unchecked(""),
+            ibc_timeout: None,
+            relayer_fee: None,
+            pools_per_packet: None,
+        }),
+        astro_pool_config: None,
+        jailed: false,
+        permissions: OUTPOST_PERMIT_ALL,
+    };
+    helper.add_outpost("osmo", osmo).unwrap();
+
+    let migrate_msg =
+        |voting_channel: Option<&str>, ics20_channel: Option<&str>| HubMsg::MigrateOutpostChannel {
+            prefix: "osmo".to_string(),
+            new_voting_channel: voting_channel.map(ToString::to_string),
+            new_ics20_channel: ics20_channel.map(ToString::to_string),
+        };
+
+    let random = helper.app.api().addr_make("random");
+    let err = helper
+        .execute_hub_msg(&random, migrate_msg(Some("channel-5"), None))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    let err = helper
+        .execute_hub_msg(&owner, migrate_msg(None, None))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NoChannelsToMigrate {}
+    );
+
+    let err = helper
+        .execute_hub_msg(&owner, migrate_msg(Some("channel-1"), None))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ChannelAlreadyUsed("channel-1".to_string())
+    );
+
+    let err = helper
+        .execute_hub_msg(&owner, migrate_msg(Some("channel-200"), None))
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: The contract does not have channel channel-200"
+    );
+
+    let resp = helper
+        .execute_hub_msg(&owner, migrate_msg(Some("channel-5"), Some("channel-6")))
+        .unwrap();
+    resp.assert_event(&Event::new("wasm").add_attributes([
+        ("action", "migrate_outpost_channel"),
+        ("prefix", "osmo"),
+        ("old_voting_channel", "channel-1"),
+        ("new_voting_channel", "channel-5"),
+        ("old_ics20_channel", "channel-2"),
+        ("new_ics20_channel", "channel-6"),
+    ]));
+
+    let params = helper
+        .app
+        .wrap()
+        .query_wasm_smart::<Vec<(String, OutpostInfo)>>(
+            helper.emission_controller.clone(),
+            &emissions_controller::hub::QueryMsg::ListOutposts {},
+        )
+        .unwrap()
+        .into_iter()
+        .find(|(prefix, _)| prefix == "osmo")
+        .and_then(|(_, outpost)| outpost.params)
+        .unwrap();
+    assert_eq!(params.voting_channel, "channel-5");
+    assert_eq!(params.ics20_channel, "channel-6");
+    assert_eq!(
+        params.escrow_address,
+        determine_ics20_escrow_address(helper.app.api(), "transfer", "channel-6").unwrap()
+    );
+
+    // Old channels can't be used again
+    let err = helper
+        .execute_hub_msg(&owner, migrate_msg(Some("channel-1"), None))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ChannelAlreadyUsed("channel-1".to_string())
+    );
+
+    let ack_error = |resp: &AppResponse| -> Option<AckError> {
+        match from_json(resp.data.as_ref().unwrap()).unwrap() {
+            IbcAckResult::Ok(_) => None,
+            IbcAckResult::Error(err) => Some(AckError::parse(&err)),
+        }
+    };
+    let update_user_votes = VxAstroIbcMsg::UpdateUserVotes {
+        voter: "osmo1voter".to_string(),
+        voting_power: 1_000000u128.into(),
+        total_voting_power: 1_000000u128.into(),
+        is_unlock: false,
+    };
+
+    // Packets over the old voting channel are rejected
+    let resp = helper
+        .mock_packet_receive(update_user_votes.clone())
+        .unwrap();
+    assert_eq!(
+        ack_error(&resp).unwrap().message,
+        "Generic error: Unknown outpost with channel-1 voting channel"
+    );
+
+    // xASTRO escrowed for the old ics20 channel keeps backing outpost voting power
+    let old_escrow =
+        determine_ics20_escrow_address(helper.app.api(), "transfer", "channel-2").unwrap();
+    helper
+        .mint_tokens(&old_escrow, &[coin(1_000000, &helper.xastro)])
+        .unwrap();
+    let resp = helper
+        .mock_packet_receive_on("channel-5", update_user_votes)
+        .unwrap();
+    assert!(ack_error(&resp).is_none());
+    assert!(!resp.has_event(&Event::new("wasm").add_attribute("action", "jail_outpost")));
+}
+
+#[test]
+fn test_outpost_supply_verification() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
+
+    let escrow_address =
+        determine_ics20_escrow_address(helper.app.api(), "transfer", "channel-2").unwrap();
+    helper
+        .add_outpost(
+            "osmo",
+            OutpostInfo {
+                astro_denom: "ibc/C4CFF46FD6DE35CA4CF4CE031E643C8FDC9BA4B99AE598E9B0ED98FE3A2319F9"
+                    .to_string(),
+                params: Some(OutpostParams {
+                    emissions_controller: "osmo1controller".to_string(),
+                    voting_channel: "channel-1".to_string(),
+                    ics20_channel: "channel-2".to_string(),
+                    escrow_address: escrow_address.clone(),
+                    ibc_timeout: None,
+                    relayer_fee: None,
+                    pools_per_packet: None,
+                }),
+                astro_pool_config: None,
+                jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
+            },
+        )
+        .unwrap();
+    let xastro = helper.xastro.clone();
+    helper
+        .mint_tokens(&escrow_address, &coins(1000, xastro))
+        .unwrap();
+
+    let register_msg = |prefix: &str| HubMsg::RegisterSupplyQuery {
+        prefix: prefix.to_string(),
+        connection_id: "connection-0".to_string(),
+        vxastro: "osmo18leaw2lzc6x70qxfnlmg8scashvsnk94vtjpdv43j5wlkgzcw3rsdfwr93".to_string(),
+        update_period: 10,
+    };
+
+    let random = helper.app.api().addr_make("random");
+    let err = helper
+        .execute_hub_msg(&random, register_msg("osmo"))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    let err = helper
+        .execute_hub_msg(&owner, register_msg("terra"))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::OutpostNotFound {
+            prefix: "terra".to_string()
+        }
+    );
+
+    helper
+        .execute_hub_msg(&owner, register_msg("osmo"))
+        .unwrap();
+    let supply_checks = helper.query_supply_checks().unwrap();
+    assert_eq!(supply_checks.len(), 1);
+    assert_eq!(supply_checks[0].0, "osmo");
+    assert_eq!(supply_checks[0].1.query_id, 1);
+
+    let err = helper
+        .execute_hub_msg(&owner, register_msg("osmo"))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::SupplyQueryExists("osmo".to_string())
+    );
+
+    // Outpost reports its total voting power with every vote update
+    helper
+        .mock_packet_receive(VxAstroIbcMsg::UpdateUserVotes {
+            voter: "osmo1voter".to_string(),
+            voting_power: 1000u128.into(),
+            total_voting_power: 1000u128.into(),
+            is_unlock: false,
+        })
+        .unwrap();
+    let supply_check = &helper.query_supply_checks().unwrap()[0].1;
+    assert_eq!(supply_check.reported_supply.u128(), 1000);
+
+    let reconcile_msg = HubMsg::ReconcileOutpostSupply {
+        prefix: "osmo".to_string(),
+    };
+
+    // Verified supply matches the reported one
+    helper.set_icq_result(1, 5, 1000);
+    helper
+        .execute_hub_msg(&random, reconcile_msg.clone())
+        .unwrap();
+    let supply_check = &helper.query_supply_checks().unwrap()[0].1;
+    assert_eq!(supply_check.verified_supply.u128(), 1000);
+    assert_eq!(supply_check.verified_height, 5);
+    assert!(!supply_check.mismatch);
+
+    // The same result can't be reconciled twice
+    let err = helper
+        .execute_hub_msg(&random, reconcile_msg.clone())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NoNewSupplyResult("osmo".to_string())
+    );
+
+    // Outpost reports more voting power than it actually has
+    helper.set_icq_result(1, 6, 800);
+    let resp = helper
+        .execute_hub_msg(&random, reconcile_msg.clone())
+        .unwrap();
+    resp.assert_event(&Event::new("wasm-outpost_supply_mismatch").add_attributes([
+        ("prefix", "osmo"),
+        ("reported_supply", "1000"),
+        ("verified_supply", "800"),
+    ]));
+    let supply_check = &helper.query_supply_checks().unwrap()[0].1;
+    assert!(supply_check.mismatch);
+
+    // Flagging doesn't jail the outpost
+    let outposts: Vec<(String, OutpostInfo)> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &helper.emission_controller,
+            &emissions_controller::hub::QueryMsg::ListOutposts {},
+        )
+        .unwrap();
+    assert!(!outposts.iter().any(|(_, outpost)| outpost.jailed));
+
+    helper
+        .execute_hub_msg(
+            &owner,
+            HubMsg::RemoveSupplyQuery {
+                prefix: "osmo".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(helper.query_supply_checks().unwrap(), vec![]);
+
+    let err = helper.execute_hub_msg(&random, reconcile_msg).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::SupplyQueryNotFound("osmo".to_string())
+    );
+}
+
+#[test]
+fn test_vote_managers() {
+    let mut helper = ControllerHelper::new();
+
+    let lp_token1 = helper.create_pair("token1", "token2");
+    let lp_token2 = helper.create_pair("token1", "token3");
+
+    let neutron = OutpostInfo {
+        astro_denom: helper.astro.clone(),
+        params: None,
+        astro_pool_config: None,
+        jailed: false,
+        permissions: OUTPOST_PERMIT_ALL,
+    };
+    helper.add_outpost("neutron", neutron).unwrap();
+
+    let user = helper.app.api().addr_make("user");
+    let manager = helper.app.api().addr_make("manager");
+
+    let whitelist_fee = helper.whitelisting_fee.clone();
+    for pool in &[lp_token1.clone(), lp_token2.clone()] {
+        helper.mint_tokens(&user, &[whitelist_fee.clone()]).unwrap();
+        helper
+            .whitelist(&user, pool, &[whitelist_fee.clone()])
+            .unwrap();
+    }
+
+    helper.lock(&user, 1000).unwrap();
+    let votes = vec![(lp_token1.clone(), Decimal::one())];
+
+    // Manager is not authorized yet
+    let err = helper.vote_on_behalf(&manager, &user, &votes).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    let err = helper
+        .execute_hub_msg(
+            &user,
+            HubMsg::SetVoteManager {
+                manager: Some(user.to_string()),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidVoteManager {}
+    );
+
+    helper
+        .execute_hub_msg(
+            &user,
+            HubMsg::SetVoteManager {
+                manager: Some(manager.to_string()),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        helper.user_info(&user, None).unwrap().vote_manager,
+        Some(manager.clone())
+    );
+
+    // Manager votes with the full voting power of the user
+    helper.vote_on_behalf(&manager, &user, &votes).unwrap();
+    let user_info = helper.user_info(&user, None).unwrap();
+    assert_eq!(user_info.voting_power.u128(), 1000);
+    assert_eq!(user_info.voted_by, Some(manager.clone()));
+    assert_eq!(
+        helper
+            .query_pool_vp(lp_token1.as_str(), None)
+            .unwrap()
+            .u128(),
+        1000
+    );
+
+    // Manager itself has no voting power
+    let err = helper.vote(&manager, &votes).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ZeroVotingPower {}
+    );
+
+    // Managed votes share the same cooldown with the user votes
+    let err = helper
+        .vote(&user, &[(lp_token2.clone(), Decimal::one())])
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::VoteCooldown(_)
+    ));
+
+    helper.timetravel(EPOCH_LENGTH);
+
+    // User can still vote themselves
+    helper
+        .vote(&user, &[(lp_token2.clone(), Decimal::one())])
+        .unwrap();
+    let user_info = helper.user_info(&user, None).unwrap();
+    assert_eq!(user_info.voted_by, None);
+    assert_eq!(
+        helper
+            .query_pool_vp(lp_token1.as_str(), None)
+            .unwrap()
+            .u128(),
+        0
+    );
+    assert_eq!(
+        helper
+            .query_pool_vp(lp_token2.as_str(), None)
+            .unwrap()
+            .u128(),
+        1000
+    );
+
+    helper
+        .execute_hub_msg(&user, HubMsg::SetVoteManager { manager: None })
+        .unwrap();
+    assert_eq!(helper.user_info(&user, None).unwrap().vote_manager, None);
+
+    helper.timetravel(EPOCH_LENGTH);
+
+    let err = helper.vote_on_behalf(&manager, &user, &votes).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+}
+
+#[test]
+fn test_execute_on_outpost() {
+    let mut helper = ControllerHelper::new();
+    let assembly = helper.assembly.clone();
+
+    helper
+        .add_outpost(
+            "osmo",
+            OutpostInfo {
+                astro_denom: "ibc/C4CFF46FD6DE35CA4CF4CE031E643C8FDC9BA4B99AE598E9B0ED98FE3A2319F9"
+                    .to_string(),
+                params: Some(OutpostParams {
+                    emissions_controller: "osmo1controller".to_string(),
+                    voting_channel: "channel-1".to_string(),
+                    ics20_channel: "channel-2".to_string(),
+                    escrow_address: Addr::unchecked(""),
+                    ibc_timeout: None,
+                    relayer_fee: None,
+                    pools_per_packet: None,
+                }),
+                astro_pool_config: None,
+                jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
+            },
+        )
+        .unwrap();
+
+    let msgs: Vec<CosmosMsg> = vec![BankMsg::Send {
+        to_address: "osmo1recipient".to_string(),
+        amount: coins(1000, "uosmo"),
+    }
+    .into()];
+    let execute_msg = |prefix: &str, msgs: &[CosmosMsg]| HubMsg::ExecuteOnOutpost {
+        outpost_prefix: prefix.to_string(),
+        msgs: msgs.to_vec(),
+    };
+
+    // Only Assembly can forward messages to outposts
+    let random = helper.app.api().addr_make("random");
+    let err = helper
+        .execute_hub_msg(&random, execute_msg("osmo", &msgs))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    let err = helper
+        .execute_hub_msg(&assembly, execute_msg("osmo", &[]))
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Messages list can't be empty"
+    );
+
+    let err = helper
+        .execute_hub_msg(&assembly, execute_msg("unknown", &msgs))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::OutpostNotFound {
+            prefix: "unknown".to_string()
+        }
+    );
+
+    // Successful execution
+    helper
+        .execute_hub_msg(&assembly, execute_msg("osmo", &msgs))
+        .unwrap();
+    let execution = helper.query_outpost_execution(1).unwrap();
+    assert_eq!(execution.prefix, "osmo");
+    assert_eq!(execution.msgs, msgs);
+    assert_eq!(execution.status, OutpostExecutionStatus::Pending);
+
+    helper
+        .mock_ibc_ack(
+            VxAstroIbcMsg::ExecuteMsgs {
+                execution_id: 1,
+                msgs: msgs.clone(),
+            },
+            IbcAckResult::Ok(b"ok".into()),
+        )
+        .unwrap();
+    assert_eq!(
+        helper.query_outpost_execution(1).unwrap().status,
+        OutpostExecutionStatus::Executed
+    );
+
+    // Failed execution on the outpost
+    helper
+        .execute_hub_msg(&assembly, execute_msg("osmo", &msgs))
+        .unwrap();
+    let ack_err = AckError::new(AckErrorCode::Unknown, "insufficient funds");
+    helper
+        .mock_ibc_ack(
+            VxAstroIbcMsg::ExecuteMsgs {
+                execution_id: 2,
+                msgs: msgs.clone(),
+            },
+            IbcAckResult::Error(to_json_string(&ack_err).unwrap()),
+        )
+        .unwrap();
+    assert_eq!(
+        helper.query_outpost_execution(2).unwrap().status,
+        OutpostExecutionStatus::Failed {
+            reason: "insufficient funds".to_string()
+        }
+    );
+
+    // Timed out packet
+    helper
+        .execute_hub_msg(&assembly, execute_msg("osmo", &msgs))
+        .unwrap();
+    helper
+        .mock_ibc_timeout(VxAstroIbcMsg::ExecuteMsgs {
+            execution_id: 3,
+            msgs,
+        })
+        .unwrap();
+    assert_eq!(
+        helper.query_outpost_execution(3).unwrap().status,
+        OutpostExecutionStatus::Failed {
+            reason: "IBC packet timeout".to_string()
+        }
     );
 }
 
-#[test]
-fn test_lock_unlock_vxastro() {
-    let mut helper = ControllerHelper::new();
+#[test]
+fn test_packet_retries() {
+    let mut helper = ControllerHelper::new();
+    let assembly = helper.assembly.clone();
+
+    helper
+        .add_outpost(
+            "osmo",
+            OutpostInfo {
+                astro_denom: "ibc/C4CFF46FD6DE35CA4CF4CE031E643C8FDC9BA4B99AE598E9B0ED98FE3A2319F9"
+                    .to_string(),
+                params: Some(OutpostParams {
+                    emissions_controller: "osmo1controller".to_string(),
+                    voting_channel: "channel-1".to_string(),
+                    ics20_channel: "channel-2".to_string(),
+                    escrow_address: Addr::unchecked(""),
+                    ibc_timeout: None,
+                    relayer_fee: None,
+                    pools_per_packet: None,
+                }),
+                astro_pool_config: None,
+                jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
+            },
+        )
+        .unwrap();
+
+    let msgs: Vec<CosmosMsg> = vec![BankMsg::Send {
+        to_address: "osmo1recipient".to_string(),
+        amount: coins(1000, "uosmo"),
+    }
+    .into()];
+    let execute_msg = HubMsg::ExecuteOnOutpost {
+        outpost_prefix: "osmo".to_string(),
+        msgs: msgs.clone(),
+    };
+    let packet = |execution_id: u64| VxAstroIbcMsg::ExecuteMsgs {
+        execution_id,
+        msgs: msgs.clone(),
+    };
+
+    helper
+        .execute_hub_msg(&assembly, execute_msg.clone())
+        .unwrap();
+    let resp = helper.mock_ibc_timeout(packet(1)).unwrap();
+    resp.assert_event(&Event::new("wasm").add_attribute("packet_retry_scheduled", "1"));
+
+    let block_ts = helper.app.block_info().time.seconds();
+    let retry = helper.query_packet_retry(1).unwrap();
+    assert_eq!(retry.prefix, "osmo");
+    assert_eq!(retry.data, to_json_binary(&packet(1)).unwrap());
+    assert_eq!(retry.attempts, 1);
+    assert_eq!(retry.next_retry_at, block_ts + PACKET_RETRY_BACKOFF);
+    assert_eq!(retry.status, PacketRetryStatus::Pending);
+
+    // Anyone can retry, but only after the backoff window
+    let random = helper.app.api().addr_make("random");
+    let err = helper
+        .execute_hub_msg(&random, HubMsg::RetryPacket { id: 1 })
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PacketRetryTooEarly {
+            id: 1,
+            next_retry_at: block_ts + PACKET_RETRY_BACKOFF
+        }
+    );
+
+    // Every timeout doubles the backoff window until the packet expires
+    for attempt in 2..=MAX_PACKET_ATTEMPTS {
+        let backoff = PACKET_RETRY_BACKOFF * 2u64.pow(attempt as u32 - 2);
+        helper.timetravel(backoff);
+        let resp = helper
+            .execute_hub_msg(&random, HubMsg::RetryPacket { id: 1 })
+            .unwrap();
+        resp.assert_event(&Event::new("wasm").add_attribute("attempt", attempt.to_string()));
+        assert_eq!(
+            helper.query_outpost_execution(1).unwrap().status,
+            OutpostExecutionStatus::Pending
+        );
+        assert_eq!(
+            helper.query_packet_retry(1).unwrap().status,
+            PacketRetryStatus::InFlight
+        );
+
+        let err = helper
+            .execute_hub_msg(&random, HubMsg::RetryPacket { id: 1 })
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::PacketRetryNotPending(1)
+        );
 
-    // Ensure nobody but vxASTRO can call UpdateUserVotes endpoint
+        helper.mock_ibc_timeout(packet(1)).unwrap();
+        let retry = helper.query_packet_retry(1).unwrap();
+        assert_eq!(retry.attempts, attempt);
+        if attempt < MAX_PACKET_ATTEMPTS {
+            assert_eq!(
+                retry.next_retry_at,
+                helper.app.block_info().time.seconds() + backoff * 2
+            );
+        }
+    }
+
+    let retry = helper.query_packet_retry(1).unwrap();
+    assert_eq!(retry.status, PacketRetryStatus::Expired);
+    assert_eq!(
+        helper.query_outpost_execution(1).unwrap().status,
+        OutpostExecutionStatus::Failed {
+            reason: "IBC packet timeout".to_string()
+        }
+    );
+    helper.timetravel(EPOCH_LENGTH);
     let err = helper
-        .app
-        .execute_contract(
-            helper.app.api().addr_make("random"),
-            helper.emission_controller.clone(),
-            &ExecuteMsg::<Empty>::UpdateUserVotes {
-                user: "user".to_string(),
-                is_unlock: false,
-            },
-            &[],
-        )
+        .execute_hub_msg(&random, HubMsg::RetryPacket { id: 1 })
         .unwrap_err();
     assert_eq!(
         err.downcast::<ContractError>().unwrap(),
-        ContractError::Unauthorized {}
+        ContractError::PacketRetryNotPending(1)
     );
+
+    // Delivered retry
+    helper.execute_hub_msg(&assembly, execute_msg).unwrap();
+    let resp = helper.mock_ibc_timeout(packet(2)).unwrap();
+    resp.assert_event(&Event::new("wasm").add_attribute("packet_retry_scheduled", "2"));
+    helper.timetravel(PACKET_RETRY_BACKOFF);
     helper
-        .app
-        .execute_contract(
-            helper.vxastro.clone(),
-            helper.emission_controller.clone(),
-            &ExecuteMsg::<Empty>::UpdateUserVotes {
-                user: helper.app.api().addr_make("random").to_string(),
-                is_unlock: false,
-            },
-            &[],
-        )
+        .execute_hub_msg(&random, HubMsg::RetryPacket { id: 2 })
+        .unwrap();
+    helper
+        .mock_ibc_ack(packet(2), IbcAckResult::Ok(b"ok".into()))
         .unwrap();
+    assert_eq!(
+        helper.query_packet_retry(2).unwrap().status,
+        PacketRetryStatus::Delivered
+    );
+    assert_eq!(
+        helper.query_outpost_execution(2).unwrap().status,
+        OutpostExecutionStatus::Executed
+    );
 
-    let owner = helper.owner.clone();
+    // Packets without tracked state aren't retried
     helper
-        .mint_tokens(&owner, &[coin(1000_000000, helper.astro.clone())])
+        .mock_ibc_timeout(VxAstroIbcMsg::RegisterProposal {
+            proposal_id: 1,
+            start_time: 0,
+        })
         .unwrap();
-    let whitelisting_fee = helper.whitelisting_fee.clone();
+    helper.query_packet_retry(3).unwrap_err();
+}
+
+#[test]
+fn test_metrics() {
+    let mut helper = ControllerHelper::new();
+    let assembly = helper.assembly.clone();
 
+    let escrow_address =
+        determine_ics20_escrow_address(helper.app.api(), "transfer", "channel-2").unwrap();
     helper
         .add_outpost(
-            "neutron",
+            "osmo",
             OutpostInfo {
-                astro_denom: helper.astro.clone(),
-                params: None,
+                astro_denom: "ibc/C4CFF46FD6DE35CA4CF4CE031E643C8FDC9BA4B99AE598E9B0ED98FE3A2319F9"
+                    .to_string(),
+                params: Some(OutpostParams {
+                    emissions_controller: "osmo1controller".to_string(),
+                    voting_channel: "channel-1".to_string(),
+                    ics20_channel: "channel-2".to_string(),
+                    escrow_address: escrow_address.clone(),
+                    ibc_timeout: None,
+                    relayer_fee: None,
+                    pools_per_packet: None,
+                }),
                 astro_pool_config: None,
                 jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
             },
         )
         .unwrap();
-
-    let pool1 = helper.create_pair("token1", "token2");
-    helper
-        .whitelist(&owner, &pool1, &[whitelisting_fee.clone()])
-        .unwrap();
-    let pool2 = helper.create_pair("token1", "token3");
+    let xastro = helper.xastro.clone();
     helper
-        .whitelist(&owner, &pool2, &[whitelisting_fee.clone()])
+        .mint_tokens(&escrow_address, &coins(10_000000, xastro))
         .unwrap();
 
-    let alice = helper.app.api().addr_make("alice");
-    helper.lock(&alice, 1_000000).unwrap();
-
-    let bob = helper.app.api().addr_make("bob");
-    helper.lock(&bob, 1_000000).unwrap();
+    let metrics = helper.query_metrics().unwrap();
+    assert_eq!(
+        metrics,
+        HashMap::from([
+            ("outposts".to_string(), Uint128::one()),
+            ("jailed_outposts".to_string(), Uint128::zero()),
+            ("failed_emissions_astro".to_string(), Uint128::zero()),
+        ])
+    );
 
-    let voting_block_ts = helper.app.block_info().time.seconds();
-    // Alice and Bob vote 50:50 for two existing pools
-    for user in [&alice, &bob] {
+    let msgs: Vec<CosmosMsg> = vec![BankMsg::Send {
+        to_address: "osmo1recipient".to_string(),
+        amount: coins(1000, "uosmo"),
+    }
+    .into()];
+    for _ in 0..2 {
         helper
-            .vote(
-                user,
-                &[
-                    (pool1.to_string(), Decimal::percent(50)),
-                    (pool2.to_string(), Decimal::percent(50)),
-                ],
+            .execute_hub_msg(
+                &assembly,
+                HubMsg::ExecuteOnOutpost {
+                    outpost_prefix: "osmo".to_string(),
+                    msgs: msgs.clone(),
+                },
             )
             .unwrap();
-
-        let user_info = helper.user_info(user, None).unwrap();
-        let user_info_historical = helper.user_info(user, Some(voting_block_ts)).unwrap();
-
-        assert_eq!(user_info, user_info_historical);
-        assert_eq!(
-            user_info,
-            UserInfoResponse {
-                vote_ts: voting_block_ts,
-                voting_power: 1_000000u128.into(),
-                votes: HashMap::from([
-                    (pool1.to_string(), Decimal::percent(50)),
-                    (pool2.to_string(), Decimal::percent(50)),
-                ]),
-                applied_votes: HashMap::from([
-                    (pool1.to_string(), Decimal::percent(50)),
-                    (pool2.to_string(), Decimal::percent(50)),
-                ])
-            }
-        );
     }
+    helper
+        .mock_ibc_ack(
+            VxAstroIbcMsg::ExecuteMsgs {
+                execution_id: 1,
+                msgs: msgs.clone(),
+            },
+            IbcAckResult::Ok(b"ok".into()),
+        )
+        .unwrap();
+    helper
+        .mock_ibc_timeout(VxAstroIbcMsg::ExecuteMsgs {
+            execution_id: 2,
+            msgs,
+        })
+        .unwrap();
 
-    // Assert pools voting power
-    for pool in [&pool1, &pool2] {
-        let pool_vp = helper.query_pool_vp(pool.as_str(), None).unwrap();
-        assert_eq!(pool_vp.u128(), 1_000000);
+    for total_voting_power in [1_000000u128, 3_000000, 2_500000] {
+        helper
+            .mock_packet_receive(VxAstroIbcMsg::UpdateUserVotes {
+                voter: "osmo1voter".to_string(),
+                voting_power: 1_000000u128.into(),
+                total_voting_power: total_voting_power.into(),
+                is_unlock: false,
+            })
+            .unwrap();
     }
 
-    helper.timetravel(3 * DAY);
+    let metrics = helper.query_metrics().unwrap();
+    assert_eq!(metrics["packets_sent_execute_msgs"].u128(), 2);
+    assert_eq!(metrics["packets_acked_execute_msgs"].u128(), 1);
+    assert_eq!(metrics["packets_timed_out_execute_msgs"].u128(), 1);
+    assert!(!metrics.contains_key("packets_failed_execute_msgs"));
+    assert_eq!(metrics["total_staked"].u128(), 2_000000);
+    assert_eq!(metrics["total_unstaked"].u128(), 500000);
+    assert_eq!(metrics["outposts"].u128(), 1);
+}
 
-    // Alice locks more astro
-    helper.lock(&alice, 1_000000).unwrap();
+#[test]
+fn test_proposal_notifications() {
+    let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
 
-    // Ensure pool voting power is updated
-    for pool in [&pool1, &pool2] {
-        let pool_vp = helper.query_pool_vp(pool.as_str(), None).unwrap();
-        assert_eq!(pool_vp.u128(), 1_500000);
-    }
+    helper
+        .add_outpost(
+            "osmo",
+            OutpostInfo {
+                astro_denom: "ibc/C4CFF46FD6DE35CA4CF4CE031E643C8FDC9BA4B99AE598E9B0ED98FE3A2319F9"
+                    .to_string(),
+                params: Some(OutpostParams {
+                    emissions_controller: "osmo1controller".to_string(),
+                    voting_channel: "channel-1".to_string(),
+                    ics20_channel: "channel-2".to_string(),
+                    escrow_address: Addr::unchecked(""),
+                    ibc_timeout: None,
+                    relayer_fee: None,
+                    pools_per_packet: None,
+                }),
+                astro_pool_config: None,
+                jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
+            },
+        )
+        .unwrap();
 
-    // Bob starts unlocking
-    helper.unlock(&bob).unwrap();
+    let subscribe_msg = |prefix: &str| HubMsg::SetProposalSubscription {
+        prefix: prefix.to_string(),
+        enabled: true,
+    };
 
-    // Ensure pool voting power is updated
-    for pool in [&pool1, &pool2] {
-        let pool_vp = helper.query_pool_vp(pool.as_str(), None).unwrap();
-        assert_eq!(pool_vp.u128(), 1_000000);
-    }
+    let random = helper.app.api().addr_make("random");
+    let err = helper
+        .execute_hub_msg(&random, subscribe_msg("osmo"))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
 
-    helper.timetravel(2 * DAY);
+    let err = helper
+        .execute_hub_msg(&owner, subscribe_msg("unknown"))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::OutpostNotFound {
+            prefix: "unknown".to_string()
+        }
+    );
 
-    // Bob relocks
-    helper.relock(&bob).unwrap();
+    helper
+        .execute_hub_msg(&owner, subscribe_msg("osmo"))
+        .unwrap();
+    let subscribers: Vec<String> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &helper.emission_controller,
+            &emissions_controller::hub::QueryMsg::ProposalSubscribers {},
+        )
+        .unwrap();
+    assert_eq!(subscribers, vec!["osmo".to_string()]);
 
-    // Ensure pool voting power is updated
-    for pool in [&pool1, &pool2] {
-        let pool_vp = helper.query_pool_vp(pool.as_str(), None).unwrap();
-        assert_eq!(pool_vp.u128(), 1_500000);
-    }
+    helper.submit_proposal(&owner).unwrap();
+    assert_eq!(helper.query_proposal_notifications(1).unwrap(), vec![]);
 
-    // Check historical queries
-    for user in [&alice, &bob] {
-        // Contract state is finalized at the end of the voting block,
-        // thus we are querying the next block
-        let user_info = helper.user_info(user, Some(voting_block_ts + 1)).unwrap();
+    // Assembly notifies outposts once the proposal is concluded
+    helper.blocktravel(PROPOSAL_VOTING_PERIOD + 1);
+    let resp = helper
+        .app
+        .execute_contract(
+            random.clone(),
+            helper.assembly.clone(),
+            &assembly::ExecuteMsg::EndProposal { proposal_id: 1 },
+            &[],
+        )
+        .unwrap();
+    resp.assert_event(&Event::new("wasm").add_attributes([
+        ("action", "notify_proposal_status"),
+        ("proposal_id", "1"),
+        ("status", "rejected"),
+        ("outpost", "osmo"),
+    ]));
 
-        assert_eq!(
-            user_info,
-            UserInfoResponse {
-                vote_ts: voting_block_ts,
-                voting_power: 1_000000u128.into(),
-                votes: HashMap::from([
-                    (pool1.to_string(), Decimal::percent(50)),
-                    (pool2.to_string(), Decimal::percent(50)),
-                ]),
-                applied_votes: HashMap::from([
-                    (pool1.to_string(), Decimal::percent(50)),
-                    (pool2.to_string(), Decimal::percent(50)),
-                ])
-            }
-        );
-    }
+    let pending = ProposalNotification {
+        status: ProposalStatus::Rejected,
+        delivery: DeliveryStatus::Pending,
+    };
+    assert_eq!(
+        helper.query_proposal_notifications(1).unwrap(),
+        vec![("osmo".to_string(), pending.clone())]
+    );
 
-    let voted_pools = helper.query_pools_vp(Some(5)).unwrap();
-    let mut expected_pools = vec![
-        (pool1.to_string(), 1_500000u128.into()),
-        (pool2.to_string(), 1_500000u128.into()),
-    ];
-    expected_pools.sort();
-    assert_eq!(voted_pools, expected_pools);
+    // Outposts which have already been notified are skipped
+    let notify_msg = HubMsg::NotifyProposalStatus { proposal_id: 1 };
+    let resp = helper.execute_hub_msg(&random, notify_msg.clone()).unwrap();
+    assert!(!resp.has_event(&Event::new("wasm").add_attribute("outpost", "osmo")));
 
-    // Unlock and withdraw
-    helper.unlock(&alice).unwrap();
-    helper.unlock(&bob).unwrap();
+    let packet = VxAstroIbcMsg::ProposalStatusUpdate {
+        proposal_id: 1,
+        status: ProposalStatus::Rejected,
+    };
+    helper.mock_ibc_timeout(packet.clone()).unwrap();
+    assert_eq!(
+        helper.query_proposal_notifications(1).unwrap(),
+        vec![(
+            "osmo".to_string(),
+            ProposalNotification {
+                status: ProposalStatus::Rejected,
+                delivery: DeliveryStatus::Failed {
+                    reason: "IBC packet timeout".to_string()
+                },
+            }
+        )]
+    );
 
-    helper.timetravel(UNLOCK_PERIOD);
+    // Failed deliveries can be retried
+    let resp = helper.execute_hub_msg(&random, notify_msg).unwrap();
+    resp.assert_event(&Event::new("wasm").add_attribute("outpost", "osmo"));
+    assert_eq!(
+        helper.query_proposal_notifications(1).unwrap(),
+        vec![("osmo".to_string(), pending)]
+    );
 
-    helper.withdraw(&alice).unwrap();
-    helper.withdraw(&bob).unwrap();
+    helper
+        .mock_ibc_ack(packet, IbcAckResult::Ok(b"ok".into()))
+        .unwrap();
+    assert_eq!(
+        helper.query_proposal_notifications(1).unwrap(),
+        vec![(
+            "osmo".to_string(),
+            ProposalNotification {
+                status: ProposalStatus::Rejected,
+                delivery: DeliveryStatus::Delivered,
+            }
+        )]
+    );
 
-    let alice_balance = helper
-        .app
-        .wrap()
-        .query_balance(alice, &helper.xastro)
+    // Unsubscribed outposts don't receive notifications
+    helper
+        .execute_hub_msg(
+            &owner,
+            HubMsg::SetProposalSubscription {
+                prefix: "osmo".to_string(),
+                enabled: false,
+            },
+        )
         .unwrap();
-    let bob_balance = helper
+    helper.submit_proposal(&owner).unwrap();
+    helper.blocktravel(PROPOSAL_VOTING_PERIOD + 1);
+    helper
         .app
-        .wrap()
-        .query_balance(bob, &helper.xastro)
+        .execute_contract(
+            random,
+            helper.assembly.clone(),
+            &assembly::ExecuteMsg::EndProposal { proposal_id: 2 },
+            &[],
+        )
         .unwrap();
-    assert_eq!(alice_balance, coin(2_000000, &helper.xastro));
-    assert_eq!(bob_balance, coin(1_000000, &helper.xastro));
+    assert_eq!(helper.query_proposal_notifications(2).unwrap(), vec![]);
 }
 
 #[test]
-fn test_instant_unlock_vxastro() {
+fn test_outpost_proposals() {
     let mut helper = ControllerHelper::new();
-
-    let owner = helper.owner.clone();
-    helper
-        .mint_tokens(&owner, &[coin(1000_000000, helper.astro.clone())])
-        .unwrap();
-    let whitelisting_fee = helper.whitelisting_fee.clone();
+    let xastro = helper.xastro.clone();
 
     helper
         .add_outpost(
-            "neutron",
+            "osmo",
             OutpostInfo {
-                astro_denom: helper.astro.clone(),
-                params: None,
+                astro_denom: "ibc/C4CFF46FD6DE35CA4CF4CE031E643C8FDC9BA4B99AE598E9B0ED98FE3A2319F9"
+                    .to_string(),
+                params: Some(OutpostParams {
+                    emissions_controller: "osmo1controller".to_string(),
+                    voting_channel: "channel-1".to_string(),
+                    ics20_channel: "channel-2".to_string(),
+                    escrow_address: Addr::unchecked(""),
+                    ibc_timeout: None,
+                    relayer_fee: None,
+                    pools_per_packet: None,
+                }),
                 astro_pool_config: None,
                 jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
             },
         )
         .unwrap();
 
-    let pool1 = helper.create_pair("token1", "token2");
-    helper
-        .whitelist(&owner, &pool1, &[whitelisting_fee.clone()])
+    let submit_packet = |title: &str, deposit: Uint128| VxAstroIbcMsg::SubmitProposal {
+        submitter: "osmo1submitter".to_string(),
+        proposal: ProposalTemplate {
+            title: title.to_string(),
+            description: "Submitted from Osmosis".to_string(),
+            link: None,
+            messages: vec![],
+            ibc_channel: None,
+            commit_reveal: false,
+            tags: vec![],
+        },
+        deposit,
+    };
+    let ack_error = |resp: &AppResponse| -> Option<AckError> {
+        match from_json(resp.data.as_ref().unwrap()).unwrap() {
+            IbcAckResult::Ok(_) => None,
+            IbcAckResult::Error(err) => Some(AckError::parse(&err)),
+        }
+    };
+    let query_submission = |helper: &ControllerHelper, sequence: u64| -> OutpostSubmission {
+        helper
+            .app
+            .wrap()
+            .query_wasm_smart::<Option<OutpostSubmission>>(
+                &helper.emission_controller,
+                &emissions_controller::hub::QueryMsg::OutpostSubmission {
+                    channel: "channel-1".to_string(),
+                    sequence,
+                },
+            )
+            .unwrap()
+            .unwrap()
+    };
+    let refund_request = |sequence: u64| RequestPacket {
+        sequence: Some(1),
+        source_port: Some("transfer".to_string()),
+        source_channel: Some("channel-2".to_string()),
+        destination_port: None,
+        destination_channel: None,
+        data: Some(
+            to_json_binary(&serde_json::json!({
+                "memo": format!(
+                    r#"{{"deposit_refund":{{"channel":"channel-1","sequence":{sequence}}}}}"#
+                ),
+            }))
+            .unwrap(),
+        ),
+        timeout_height: None,
+        timeout_timestamp: None,
+    };
+
+    // Deposit must cover the Assembly required deposit
+    let resp = helper
+        .mock_packet_receive(submit_packet(
+            "Outpost proposal",
+            PROPOSAL_REQUIRED_DEPOSIT - Uint128::one(),
+        ))
         .unwrap();
-    let pool2 = helper.create_pair("token1", "token3");
+    assert_eq!(
+        ack_error(&resp).unwrap().message,
+        ContractError::InsufficientProposalDeposit(coin(PROPOSAL_REQUIRED_DEPOSIT.u128(), &xastro))
+            .to_string()
+    );
+
+    // The Hub accepts the submission and awaits the deposit transfer
+    let resp = helper
+        .mock_packet_receive_seq(
+            "channel-1",
+            1,
+            submit_packet("Outpost proposal", PROPOSAL_REQUIRED_DEPOSIT),
+        )
+        .unwrap();
+    let deadline = helper.app.block_info().time.seconds() + 2 * IBC_TIMEOUT;
+    assert_eq!(
+        from_json::<IbcAckResult>(resp.data.as_ref().unwrap()).unwrap(),
+        IbcAckResult::Ok(to_json_binary(&deadline).unwrap())
+    );
+    resp.assert_event(&Event::new("wasm").add_attributes([
+        ("action", "outpost_submission"),
+        ("channel", "channel-1"),
+        ("sequence", "1"),
+        ("submitter", "osmo1submitter"),
+        ("outpost", "osmo"),
+    ]));
+    let submission = query_submission(&helper, 1);
+    assert_eq!(submission.deadline, deadline);
+    assert_eq!(submission.status, OutpostSubmissionStatus::AwaitingDeposit);
+    assert_eq!(submission.refund, None);
+
+    // IBC hook of the ics20 transfer must carry the exact deposit
+    let hook_sender = helper.app.api().addr_make("ibc_hook_sender");
     helper
-        .whitelist(&owner, &pool2, &[whitelisting_fee.clone()])
+        .mint_tokens(
+            &hook_sender,
+            &coins(PROPOSAL_REQUIRED_DEPOSIT.u128() * 3, &xastro),
+        )
         .unwrap();
+    let fund_msg = emissions_controller::msg::ExecuteMsg::Custom(HubMsg::FundOutpostProposal {
+        channel: "channel-1".to_string(),
+        sequence: 1,
+    });
+    let emissions_controller = helper.emission_controller.clone();
+    let err = helper
+        .app
+        .execute_contract(
+            hook_sender.clone(),
+            emissions_controller.clone(),
+            &fund_msg,
+            &coins(PROPOSAL_REQUIRED_DEPOSIT.u128() - 1, &xastro),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ProposalDepositMismatch(coin(PROPOSAL_REQUIRED_DEPOSIT.u128(), &xastro))
+    );
 
-    let alice = helper.app.api().addr_make("alice");
-    helper.lock(&alice, 10_000000).unwrap();
+    let resp = helper
+        .app
+        .execute_contract(
+            hook_sender.clone(),
+            emissions_controller.clone(),
+            &fund_msg,
+            &coins(PROPOSAL_REQUIRED_DEPOSIT.u128(), &xastro),
+        )
+        .unwrap();
+    resp.assert_event(&Event::new("wasm").add_attributes([
+        ("action", "submit_outpost_proposal"),
+        ("proposal_id", "1"),
+        ("outpost", "osmo"),
+    ]));
 
-    helper
-        .vote(
-            &alice,
-            &[
-                (pool1.to_string(), Decimal::percent(50)),
-                (pool2.to_string(), Decimal::percent(50)),
-            ],
+    let proposal: assembly::Proposal = helper
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &helper.assembly,
+            &assembly::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.title, "Outpost proposal");
+    assert_eq!(proposal.submitter, emissions_controller);
+    assert_eq!(proposal.deposit_amount, PROPOSAL_REQUIRED_DEPOSIT);
+    let outpost_proposal: Option<OutpostProposal> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &emissions_controller,
+            &emissions_controller::hub::QueryMsg::OutpostProposal { proposal_id: 1 },
         )
         .unwrap();
+    assert_eq!(
+        outpost_proposal,
+        Some(OutpostProposal {
+            prefix: "osmo".to_string(),
+            channel: "channel-1".to_string(),
+            sequence: 1,
+            submitter: "osmo1submitter".to_string(),
+            deposit: PROPOSAL_REQUIRED_DEPOSIT,
+        })
+    );
+    assert_eq!(
+        query_submission(&helper, 1).status,
+        OutpostSubmissionStatus::Submitted { proposal_id: 1 }
+    );
 
-    // Assert pools voting power
-    for pool in [&pool1, &pool2] {
-        let pool_vp = helper.query_pool_vp(pool.as_str(), None).unwrap();
-        assert_eq!(pool_vp.u128(), 5_000000);
-    }
+    // The submission can't be funded twice
+    let err = helper
+        .app
+        .execute_contract(
+            hook_sender.clone(),
+            emissions_controller.clone(),
+            &fund_msg,
+            &coins(PROPOSAL_REQUIRED_DEPOSIT.u128(), &xastro),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::OutpostSubmissionNotAwaitingDeposit {
+            channel: "channel-1".to_string(),
+            sequence: 1,
+        }
+    );
 
-    // Ensure random user can't instantly unlock
+    // Deposit can't be refunded while the Assembly holds it
     let random = helper.app.api().addr_make("random");
-    let err = helper.instant_unlock(&random, 100).unwrap_err();
+    let refund_msg = HubMsg::RefundOutpostDeposit {
+        channel: "channel-1".to_string(),
+        sequence: 1,
+    };
+    let err = helper
+        .execute_hub_msg(&random, refund_msg.clone())
+        .unwrap_err();
     assert_eq!(
-        err.downcast::<astroport_voting_escrow::error::ContractError>()
-            .unwrap(),
-        astroport_voting_escrow::error::ContractError::Unauthorized {}
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::OutpostDepositNotRefundable {
+            channel: "channel-1".to_string(),
+            sequence: 1,
+        }
     );
 
+    // Assembly refunds the deposit and the Hub refunds it to the submitter over ics20
+    helper.blocktravel(PROPOSAL_VOTING_PERIOD + 1);
+    let resp = helper
+        .app
+        .execute_contract(
+            random.clone(),
+            helper.assembly.clone(),
+            &assembly::ExecuteMsg::EndProposal { proposal_id: 1 },
+            &[],
+        )
+        .unwrap();
+    resp.assert_event(&Event::new("wasm").add_attributes([
+        ("action", "notify_proposal_status"),
+        ("refund_deposit", "osmo"),
+    ]));
+    let balance = helper
+        .app
+        .wrap()
+        .query_balance(&emissions_controller, &xastro)
+        .unwrap();
+    assert_eq!(balance.amount, Uint128::zero());
+    assert_eq!(
+        query_submission(&helper, 1).refund,
+        Some(DeliveryStatus::Pending)
+    );
+
+    // In-flight refund can't be resent
     let err = helper
-        .set_privileged_list(&random, vec![alice.to_string()])
+        .execute_hub_msg(&random, refund_msg.clone())
         .unwrap_err();
     assert_eq!(
-        err.downcast::<astroport_voting_escrow::error::ContractError>()
-            .unwrap(),
-        astroport_voting_escrow::error::ContractError::Unauthorized {}
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::OutpostDepositNotRefundable {
+            channel: "channel-1".to_string(),
+            sequence: 1,
+        }
     );
 
-    // Add Alice to the privileged list
     helper
-        .set_privileged_list(&owner, vec![alice.to_string()])
+        .app
+        .wasm_sudo(
+            emissions_controller.clone(),
+            &TransferSudoMsg::Timeout {
+                request: refund_request(1),
+            },
+        )
         .unwrap();
+    assert_eq!(
+        query_submission(&helper, 1).refund,
+        Some(DeliveryStatus::Failed {
+            reason: "ics20 transfer failed".to_string()
+        })
+    );
 
-    // Ensure alice is added
-    let privileged_list: Vec<Addr> = helper
+    // Failed refunds can be retried. Mocking the timed out transfer returned to the Hub
+    helper
+        .mint_tokens(
+            &emissions_controller,
+            &coins(PROPOSAL_REQUIRED_DEPOSIT.u128(), &xastro),
+        )
+        .unwrap();
+    let resp = helper.execute_hub_msg(&random, refund_msg.clone()).unwrap();
+    resp.assert_event(&Event::new("wasm").add_attributes([
+        ("action", "refund_outpost_deposit"),
+        ("channel", "channel-1"),
+        ("sequence", "1"),
+    ]));
+    helper
         .app
-        .wrap()
-        .query_wasm_smart(&helper.vxastro, &voting_escrow::QueryMsg::PrivilegedList {})
+        .wasm_sudo(
+            emissions_controller.clone(),
+            &TransferSudoMsg::Response {
+                request: refund_request(1),
+                data: Default::default(),
+            },
+        )
         .unwrap();
-    assert_eq!(privileged_list, vec![alice.clone()]);
+    assert_eq!(
+        query_submission(&helper, 1).refund,
+        Some(DeliveryStatus::Delivered)
+    );
+    let metrics = helper.query_metrics().unwrap();
+    assert_eq!(
+        metrics["packets_sent_ics20_deposit_refund"],
+        Uint128::new(2)
+    );
 
-    // Alice instantly unlocks
-    helper.instant_unlock(&alice, 2_000000).unwrap();
+    let err = helper
+        .execute_hub_msg(&random, refund_msg.clone())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::OutpostDepositNotRefundable {
+            channel: "channel-1".to_string(),
+            sequence: 1,
+        }
+    );
 
-    let xastro_bal = helper
+    // Deposit of the proposal rejected by the Assembly is refunded right away
+    helper
+        .mock_packet_receive_seq(
+            "channel-1",
+            2,
+            submit_packet("No", PROPOSAL_REQUIRED_DEPOSIT),
+        )
+        .unwrap();
+    let resp = helper
+        .app
+        .execute_contract(
+            hook_sender.clone(),
+            emissions_controller.clone(),
+            &emissions_controller::msg::ExecuteMsg::Custom(HubMsg::FundOutpostProposal {
+                channel: "channel-1".to_string(),
+                sequence: 2,
+            }),
+            &coins(PROPOSAL_REQUIRED_DEPOSIT.u128(), &xastro),
+        )
+        .unwrap();
+    resp.assert_event(&Event::new("wasm").add_attributes([
+        ("action", "reject_outpost_proposal"),
+        ("channel", "channel-1"),
+        ("sequence", "2"),
+    ]));
+    let submission = query_submission(&helper, 2);
+    assert!(matches!(
+        submission.status,
+        OutpostSubmissionStatus::Rejected { .. }
+    ));
+    assert_eq!(submission.refund, Some(DeliveryStatus::Pending));
+    let balance = helper
         .app
         .wrap()
-        .query_balance(&alice, &helper.xastro)
+        .query_balance(&emissions_controller, &xastro)
         .unwrap();
-    assert_eq!(xastro_bal.amount.u128(), 2_000000);
-
-    // Assert pools voting power is reduced
-    for pool in [&pool1, &pool2] {
-        let pool_vp = helper.query_pool_vp(pool.as_str(), None).unwrap();
-        assert_eq!(pool_vp.u128(), 4_000000);
-    }
+    assert_eq!(balance.amount, Uint128::zero());
 
-    // Total voting power must be 8
-    let total_vp = helper.total_vp(None).unwrap();
-    assert_eq!(total_vp.u128(), 8_000000);
+    // The outpost returns the deposit whose transfer hasn't reached the Hub before the deadline
+    let resp = helper
+        .mock_packet_receive_seq(
+            "channel-1",
+            3,
+            submit_packet("Outpost proposal", PROPOSAL_REQUIRED_DEPOSIT),
+        )
+        .unwrap();
+    let deadline: u64 = match from_json(resp.data.as_ref().unwrap()).unwrap() {
+        IbcAckResult::Ok(data) => from_json(data).unwrap(),
+        IbcAckResult::Error(err) => panic!("Unexpected error: {err}"),
+    };
+    let refund_msg = HubMsg::RefundOutpostDeposit {
+        channel: "channel-1".to_string(),
+        sequence: 3,
+    };
+    let err = helper
+        .execute_hub_msg(&random, refund_msg.clone())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::OutpostDepositNotRefundable {
+            channel: "channel-1".to_string(),
+            sequence: 3,
+        }
+    );
 
-    let lock_info: voting_escrow::LockInfoResponse = helper
+    helper.timetravel(deadline - helper.app.block_info().time.seconds());
+    let err = helper
         .app
-        .wrap()
-        .query_wasm_smart(
-            &helper.vxastro,
-            &voting_escrow::QueryMsg::LockInfo {
-                user: alice.to_string(),
-                timestamp: None,
-            },
+        .execute_contract(
+            hook_sender.clone(),
+            emissions_controller.clone(),
+            &emissions_controller::msg::ExecuteMsg::Custom(HubMsg::FundOutpostProposal {
+                channel: "channel-1".to_string(),
+                sequence: 3,
+            }),
+            &coins(PROPOSAL_REQUIRED_DEPOSIT.u128(), &xastro),
         )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::OutpostSubmissionNotAwaitingDeposit {
+            channel: "channel-1".to_string(),
+            sequence: 3,
+        }
+    );
+
+    helper.execute_hub_msg(&random, refund_msg).unwrap();
+    let submission = query_submission(&helper, 3);
+    assert_eq!(submission.status, OutpostSubmissionStatus::Expired);
+    assert_eq!(submission.refund, Some(DeliveryStatus::Pending));
+
+    let packet = VxAstroIbcMsg::RefundProposalDeposit {
+        sequence: 3,
+        submitter: "osmo1submitter".to_string(),
+        deposit: PROPOSAL_REQUIRED_DEPOSIT,
+    };
+    helper
+        .mock_ibc_ack(packet, IbcAckResult::Ok(b"ok".into()))
         .unwrap();
     assert_eq!(
-        lock_info,
-        voting_escrow::LockInfoResponse {
-            amount: 8_000000u128.into(),
-            unlock_status: None
+        query_submission(&helper, 3).refund,
+        Some(DeliveryStatus::Delivered)
+    );
+
+    // Unknown submissions can't be funded
+    let err = helper
+        .app
+        .execute_contract(
+            hook_sender,
+            emissions_controller,
+            &emissions_controller::msg::ExecuteMsg::Custom(HubMsg::FundOutpostProposal {
+                channel: "channel-1".to_string(),
+                sequence: 4,
+            }),
+            &coins(PROPOSAL_REQUIRED_DEPOSIT.u128(), &xastro),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::OutpostSubmissionNotFound {
+            channel: "channel-1".to_string(),
+            sequence: 4,
         }
     );
 }
 
 #[test]
-fn test_some_epochs() {
+fn test_outpost_proposals_with_min_xastro_age() {
     let mut helper = ControllerHelper::new();
-    let owner = helper.owner.clone();
-    let whitelisting_fee = helper.whitelisting_fee.clone();
+    let xastro = helper.xastro.clone();
 
     helper
         .add_outpost(
@@ -1521,440 +6352,543 @@ fn test_some_epochs() {
                     voting_channel: "channel-1".to_string(),
                     ics20_channel: "channel-2".to_string(),
                     escrow_address: Addr::unchecked(""),
+                    ibc_timeout: None,
+                    relayer_fee: None,
+                    pools_per_packet: None,
                 }),
                 astro_pool_config: None,
                 jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
             },
         )
         .unwrap();
-    let pool1 = "osmo1pool1";
-    let pool2 = "osmo1pool2";
-    helper
-        .mint_tokens(&owner, &coins(100000000, helper.astro.clone()))
-        .unwrap();
-    helper
-        .whitelist(&owner, pool1, &[whitelisting_fee.clone()])
-        .unwrap();
+
+    let assembly = helper.assembly.clone();
     helper
-        .whitelist(&owner, pool2, &[whitelisting_fee.clone()])
+        .app
+        .execute_contract(
+            assembly.clone(),
+            assembly.clone(),
+            &assembly::ExecuteMsg::UpdateConfig(Box::new(assembly::UpdateConfig {
+                min_xastro_age: Some(DAY),
+                ..Default::default()
+            })),
+            &[],
+        )
         .unwrap();
 
-    let user1 = helper.app.api().addr_make("user1");
-    helper.lock(&user1, 1_000000).unwrap();
-    let user2 = helper.app.api().addr_make("user2");
-    helper.lock(&user2, 1_000000).unwrap();
+    // Flash-staked xASTRO can't be used to submit proposals on the Hub
+    let user = helper.app.api().addr_make("user");
+    let err = helper.submit_proposal(&user).unwrap_err();
+    assert_eq!(
+        err.downcast::<astro_assembly::error::ContractError>()
+            .unwrap(),
+        astro_assembly::error::ContractError::InsufficientXastroAge { min_age: DAY }
+    );
 
+    // Outpost deposits arrive over ics20 in the same tx the proposal is submitted in
     helper
-        .vote(&user1, &[(pool1.to_string(), Decimal::one())])
-        .unwrap();
-    helper
-        .vote(&user2, &[(pool2.to_string(), Decimal::one())])
+        .mock_packet_receive_seq(
+            "channel-1",
+            1,
+            VxAstroIbcMsg::SubmitProposal {
+                submitter: "osmo1submitter".to_string(),
+                proposal: ProposalTemplate {
+                    title: "Outpost proposal".to_string(),
+                    description: "Submitted from Osmosis".to_string(),
+                    link: None,
+                    messages: vec![],
+                    ibc_channel: None,
+                    commit_reveal: false,
+                    tags: vec![],
+                },
+                deposit: PROPOSAL_REQUIRED_DEPOSIT,
+            },
+        )
         .unwrap();
-
-    // Preparing controller balance for tuning
+    let hook_sender = helper.app.api().addr_make("ibc_hook_sender");
     helper
         .mint_tokens(
-            &helper.emission_controller.clone(),
-            &coins(100_000_000_000_000, helper.astro.clone()),
+            &hook_sender,
+            &coins(PROPOSAL_REQUIRED_DEPOSIT.u128(), &xastro),
         )
         .unwrap();
-
-    helper.timetravel(EPOCH_LENGTH);
-
-    helper.tune(&user1).unwrap();
-    let voted_pools = helper.query_pools_vp(None).unwrap();
-    assert_eq!(
-        voted_pools,
-        [
-            (pool1.to_string(), 1_000000u128.into()),
-            (pool2.to_string(), 1_000000u128.into()),
-        ]
-    );
-
-    helper.unlock(&user1).unwrap();
-
-    helper.timetravel(EPOCH_LENGTH);
-    helper.tune(&user1).unwrap();
-
-    // User1 unlocked, user2 still keeps his votes
-    let voted_pools = helper.query_pools_vp(None).unwrap();
-    let expected_pools = vec![
-        (pool1.to_string(), 0u128.into()),
-        (pool2.to_string(), 1_000000u128.into()),
-    ];
-    assert_eq!(voted_pools, expected_pools);
-
-    helper.relock(&user1).unwrap();
-    helper.unlock(&user2).unwrap();
-
-    helper.timetravel(EPOCH_LENGTH);
-    helper.tune(&user1).unwrap();
-
-    // User1 relocked, user2 unlocked
-    let voted_pools = helper.query_pools_vp(None).unwrap();
-    assert_eq!(
-        voted_pools,
-        [
-            (pool1.to_string(), 1_000000u128.into()),
-            (pool2.to_string(), 0u128.into()),
-        ]
-    );
-
-    // Allow only 1 pool for tuning
-    helper
+    let emissions_controller = helper.emission_controller.clone();
+    let resp = helper
         .app
         .execute_contract(
-            owner.clone(),
-            helper.emission_controller.clone(),
-            &ExecuteMsg::Custom(HubMsg::UpdateConfig {
-                pools_per_outpost: Some(1),
-                whitelisting_fee: None,
-                fee_receiver: None,
-                emissions_multiple: None,
-                max_astro: None,
+            hook_sender,
+            emissions_controller.clone(),
+            &emissions_controller::msg::ExecuteMsg::Custom(HubMsg::FundOutpostProposal {
+                channel: "channel-1".to_string(),
+                sequence: 1,
             }),
-            &[],
+            &coins(PROPOSAL_REQUIRED_DEPOSIT.u128(), &xastro),
         )
         .unwrap();
+    resp.assert_event(&Event::new("wasm").add_attributes([
+        ("action", "submit_outpost_proposal"),
+        ("proposal_id", "1"),
+        ("outpost", "osmo"),
+    ]));
 
-    helper.timetravel(EPOCH_LENGTH);
-    helper.tune(&user1).unwrap();
-
-    // pool2 was removed from votable pools
-    let voted_pools = helper.query_pools_vp(None).unwrap();
-    assert_eq!(voted_pools, [(pool1.to_string(), 1_000000u128.into())]);
-
-    // And from whitelist
-    let whitelist = helper.query_whitelist().unwrap();
-    assert_eq!(whitelist, vec![pool1.to_string()]);
-
-    // If user2 relocks his votes won't be restored as pool2 must be whitelisted again
-    helper.relock(&user2).unwrap();
-    let voted_pools = helper.query_pools_vp(None).unwrap();
-    assert_eq!(voted_pools, [(pool1.to_string(), 1_000000u128.into())]);
-
-    // Whitelist pool2 again
-    helper
-        .whitelist(&owner, pool2, &[whitelisting_fee.clone()])
+    let proposal: assembly::Proposal = helper
+        .app
+        .wrap()
+        .query_wasm_smart(&assembly, &assembly::QueryMsg::Proposal { proposal_id: 1 })
         .unwrap();
-
-    // Ensure that user2 votes are not applied
-    let user2_info = helper.user_info(&user2, None).unwrap();
-    assert_eq!(user2_info.applied_votes, HashMap::new());
-
-    // User2 must refresh his votes
-    helper.refresh_user_votes(&user2).unwrap();
-
-    // His contribution must be restored
-    let voted_pools = helper.query_pools_vp(None).unwrap();
-    assert_eq!(
-        voted_pools,
-        [
-            (pool1.to_string(), 1_000000u128.into()),
-            (pool2.to_string(), 1_000000u128.into()),
-        ]
-    );
+    assert_eq!(proposal.submitter, emissions_controller);
 }
 
 #[test]
-fn test_interchain_governance() {
+fn test_vxastro_permanent_locks() {
     let mut helper = ControllerHelper::new();
     let owner = helper.owner.clone();
 
-    // No proposal yet
-    let err = helper.register_proposal(1).unwrap_err();
-    assert_eq!(err.root_cause().to_string(), "Generic error: Querier contract error: type: astroport_governance::assembly::Proposal; key: [00, 09, 70, 72, 6F, 70, 6F, 73, 61, 6C, 73, 00, 00, 00, 00, 00, 00, 00, 01] not found");
-
-    helper.submit_proposal(&owner).unwrap();
-
-    // No outposts yet but it shouldn't fail transaction
-    let resp = helper.register_proposal(1).unwrap();
-    assert!(
-        !resp.has_event(
-            &Event::new("wasm")
-                .add_attributes([("action", "register_proposal"), ("outpost", "osmo")])
-        ),
-        "Controller tried to register outpost {:?}",
-        resp.events
-    );
+    let vxastro_err = |err: anyhow::Error| {
+        err.downcast::<astroport_voting_escrow::error::ContractError>()
+            .unwrap()
+    };
 
-    // Add outpost
+    helper
+        .mint_tokens(&owner, &[coin(1000_000000, helper.astro.clone())])
+        .unwrap();
+    let whitelisting_fee = helper.whitelisting_fee.clone();
     helper
         .add_outpost(
-            "osmo",
+            "neutron",
             OutpostInfo {
-                astro_denom: "ibc/C4CFF46FD6DE35CA4CF4CE031E643C8FDC9BA4B99AE598E9B0ED98FE3A2319F9"
-                    .to_string(),
-                params: Some(OutpostParams {
-                    emissions_controller: "osmo1controller".to_string(),
-                    voting_channel: "channel-1".to_string(),
-                    ics20_channel: "channel-2".to_string(),
-                    escrow_address: Addr::unchecked(""),
-                }),
+                astro_denom: helper.astro.clone(),
+                params: None,
                 astro_pool_config: None,
                 jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
             },
         )
         .unwrap();
+    let pool = helper.create_pair("token1", "token2");
+    helper
+        .whitelist(&owner, &pool, &[whitelisting_fee])
+        .unwrap();
 
-    // Submit 2nd proposal. Ensure it registers a proposal on osmosis
-    let resp = helper.submit_proposal(&owner).unwrap();
-    resp.assert_event(
-        &Event::new("wasm").add_attributes([("action", "register_proposal"), ("outpost", "osmo")]),
-    );
+    let alice = helper.app.api().addr_make("alice");
+    helper.lock(&alice, 1_000000).unwrap();
+    let bob = helper.app.api().addr_make("bob");
+    helper.lock(&bob, 1_000000).unwrap();
+    for user in [&alice, &bob] {
+        helper
+            .vote(user, &[(pool.to_string(), Decimal::one())])
+            .unwrap();
+    }
 
-    // Now we can register 1st proposal
-    helper.register_proposal(1).unwrap();
-    resp.assert_event(
-        &Event::new("wasm").add_attributes([("action", "register_proposal"), ("outpost", "osmo")]),
+    let random = helper.app.api().addr_make("random");
+    let err = helper.set_permanent_boost(&random, 5000).unwrap_err();
+    assert_eq!(
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::Unauthorized {}
+    );
+    let err = helper
+        .set_permanent_boost(&owner, voting_escrow::MAX_PERMANENT_BOOST_BPS + 1)
+        .unwrap_err();
+    assert_eq!(
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::InvalidPermanentBoost(
+            voting_escrow::MAX_PERMANENT_BOOST_BPS
+        )
     );
 
-    // Timeout both proposals
-    helper.blocktravel(PROPOSAL_VOTING_PERIOD + 1);
+    // +50% voting power for permanent locks
+    helper.set_permanent_boost(&owner, 5000).unwrap();
+    assert_eq!(
+        helper.query_vxastro_config().unwrap().permanent_boost_bps,
+        5000
+    );
+    assert_eq!(helper.total_vp(None).unwrap().u128(), 2_000000);
 
-    let err = helper.register_proposal(1).unwrap_err();
+    let carol = helper.app.api().addr_make("carol");
+    let err = helper.make_permanent(&carol).unwrap_err();
     assert_eq!(
-        err.root_cause().to_string(),
-        "Generic error: Proposal is not active"
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::ZeroBalance {}
     );
 
-    // Emulate outpost vote after a voting period is over.
-    // It shouldn't fail but must not register a vote
-    let resp = helper
-        .mock_packet_receive(VxAstroIbcMsg::GovernanceVote {
-            voter: "osmo1voter".to_string(),
-            voting_power: Default::default(),
-            total_voting_power: Default::default(),
-            proposal_id: 1,
-            vote: ProposalVoteOption::For,
-        })
-        .unwrap();
-    resp.assert_event(
+    let resp = helper.make_permanent(&alice).unwrap();
+    assert!(resp.has_event(
         &Event::new("wasm")
-            .add_attributes([("action", "cast_vote"), ("error", "Voting period ended!")]),
+            .add_attribute("action", "make_permanent")
+            .add_attribute("receiver", alice.to_string())
+            .add_attribute("lock_amount", "1000000")
+    ));
+    assert_eq!(helper.user_vp(&alice, None).unwrap().u128(), 1_500000);
+    assert_eq!(helper.total_vp(None).unwrap().u128(), 2_500000);
+    // Emissions votes are updated right away
+    assert_eq!(
+        helper.query_pool_vp(pool.as_str(), None).unwrap().u128(),
+        2_500000
     );
 
-    // Submit 3rd proposal
-    helper.submit_proposal(&owner).unwrap();
+    let err = helper.make_permanent(&alice).unwrap_err();
+    assert_eq!(
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::PermanentLock {}
+    );
+    let err = helper.unlock(&alice).unwrap_err();
+    assert_eq!(
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::PermanentLock {}
+    );
+    let err = helper.unlock_permanent(&bob).unwrap_err();
+    assert_eq!(
+        vxastro_err(err),
+        astroport_voting_escrow::error::ContractError::NotPermanentLock {}
+    );
 
-    // Emulate vote from osmosis
-    let resp = helper
-        .mock_packet_receive(VxAstroIbcMsg::GovernanceVote {
-            voter: "osmo1voter".to_string(),
-            voting_power: 1_000000u128.into(),
-            total_voting_power: Default::default(),
-            proposal_id: 3,
-            vote: ProposalVoteOption::For,
-        })
-        .unwrap();
+    // Deposits to permanent locks are boosted as well
+    helper.lock(&alice, 1_000000).unwrap();
+    assert_eq!(helper.user_vp(&alice, None).unwrap().u128(), 3_000000);
+    assert_eq!(helper.total_vp(None).unwrap().u128(), 4_000000);
 
-    resp.assert_event(&Event::new("wasm").add_attributes([
-        ("action", "cast_vote"),
-        ("proposal_id", "3"),
-        ("voter", "osmo1voter"),
-        ("vote", "for"),
-        ("voting_power", "1000000"),
-    ]));
+    helper.timetravel(DAY);
+    let boost_change_ts = helper.app.block_info().time.seconds();
 
-    // Ensure voter has been reflected in assembly
-    let proposal = helper
-        .app
-        .wrap()
-        .query_wasm_smart::<assembly::Proposal>(
-            helper.assembly.clone(),
-            &assembly::QueryMsg::Proposal { proposal_id: 3 },
-        )
+    // +100% voting power for permanent locks
+    let resp = helper.set_permanent_boost(&owner, 10000).unwrap();
+    assert!(resp.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "set_permanent_boost")
+            .add_attribute("boost_bps", "10000")
+    ));
+    assert_eq!(helper.user_vp(&alice, None).unwrap().u128(), 4_000000);
+    assert_eq!(helper.total_vp(None).unwrap().u128(), 5_000000);
+    // Historical voting power follows the boost at that time
+    helper.timetravel(1);
+    assert_eq!(
+        helper
+            .user_vp(&alice, Some(boost_change_ts))
+            .unwrap()
+            .u128(),
+        3_000000
+    );
+    assert_eq!(
+        helper.total_vp(Some(boost_change_ts)).unwrap().u128(),
+        4_000000
+    );
+
+    // Emissions votes are stale until permanent lockers are refreshed
+    assert_eq!(
+        helper.query_pool_vp(pool.as_str(), None).unwrap().u128(),
+        4_000000
+    );
+    let resp = helper
+        .refresh_permanent_votes(&random, &[&alice, &bob])
         .unwrap();
-    assert_eq!(proposal.for_power.u128(), 1_000000);
+    assert!(resp.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "refresh_permanent_votes")
+            .add_attribute("refreshed_users", alice.to_string())
+    ));
+    assert_eq!(
+        helper.query_pool_vp(pool.as_str(), None).unwrap().u128(),
+        5_000000
+    );
 
-    let voters = helper
+    // Converting back to a normal lock starts unlocking
+    let unlock_ts = helper.app.block_info().time.seconds();
+    helper.unlock_permanent(&alice).unwrap();
+    assert_eq!(helper.user_vp(&alice, None).unwrap().u128(), 0);
+    assert_eq!(helper.total_vp(None).unwrap().u128(), 1_000000);
+    assert_eq!(
+        helper.query_pool_vp(pool.as_str(), None).unwrap().u128(),
+        1_000000
+    );
+
+    let lock_info: voting_escrow::LockInfoResponse = helper
         .app
         .wrap()
-        .query_wasm_smart::<Vec<ProposalVoterResponse>>(
-            helper.assembly.clone(),
-            &assembly::QueryMsg::ProposalVoters {
-                proposal_id: 3,
-                start_after: None,
-                limit: None,
+        .query_wasm_smart(
+            &helper.vxastro,
+            &voting_escrow::QueryMsg::LockInfo {
+                user: alice.to_string(),
+                timestamp: None,
             },
         )
         .unwrap();
     assert_eq!(
-        voters,
-        vec![ProposalVoterResponse {
-            address: "osmo1voter".to_string(),
-            vote_option: ProposalVoteOption::For,
-        }]
+        lock_info,
+        voting_escrow::LockInfoResponse {
+            amount: 2_000000u128.into(),
+            unlock_status: Some(voting_escrow::UnlockStatus {
+                end: unlock_ts + voting_escrow::DEFAULT_UNLOCK_PERIOD,
+                hub_confirmed: true,
+            }),
+            permanent: false,
+        }
     );
+
+    // Relocked position is a normal lock
+    helper.relock(&alice).unwrap();
+    assert_eq!(helper.user_vp(&alice, None).unwrap().u128(), 2_000000);
+    assert_eq!(helper.total_vp(None).unwrap().u128(), 3_000000);
 }
 
 #[test]
-fn test_change_ownership() {
+fn test_failing_outpost_penalty() {
     let mut helper = ControllerHelper::new();
+    let owner = helper.owner.clone();
 
-    let new_owner = helper.app.api().addr_make("new_owner");
+    let lp_token = "factory/osmo1pool1/astroport/share";
 
-    // New owner
-    let msg = ExecuteMsg::<Empty>::ProposeNewOwner {
-        new_owner: new_owner.to_string(),
-        expires_in: 100, // seconds
+    let osmosis = OutpostInfo {
+        astro_denom: "ibc/6569E05DEE32B339D9286A52BE33DFCEFC97267F23EF9CFDE0C055140967A9A5"
+            .to_string(),
+        params: Some(OutpostParams {
+            emissions_controller: "osmo1emissionscontroller".to_string(),
+            voting_channel: "channel-1".to_string(),
+            ics20_channel: "channel-2".to_string(),
+            escrow_address: Addr::unchecked(""),
+            ibc_timeout: None,
+            relayer_fee: None,
+            pools_per_packet: None,
+        }),
+        astro_pool_config: None,
+        jailed: false,
+        permissions: OUTPOST_PERMIT_ALL,
     };
+    helper.add_outpost("osmo", osmosis).unwrap();
 
-    // Unauthorized check
-    let err = helper
-        .app
-        .execute_contract(
-            helper.app.api().addr_make("not_owner"),
-            helper.emission_controller.clone(),
-            &msg,
-            &[],
-        )
-        .unwrap_err();
-    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
-
-    // Claim before proposal
-    let err = helper
-        .app
-        .execute_contract(
-            new_owner.clone(),
-            helper.emission_controller.clone(),
-            &ExecuteMsg::<Empty>::ClaimOwnership {},
-            &[],
-        )
-        .unwrap_err();
-    assert_eq!(
-        err.root_cause().to_string(),
-        "Generic error: Ownership proposal not found"
-    );
+    let whitelist_fee = helper.whitelisting_fee.clone();
+    helper
+        .mint_tokens(&owner, &[whitelist_fee.clone()])
+        .unwrap();
+    helper
+        .whitelist(&owner, lp_token, &[whitelist_fee.clone()])
+        .unwrap();
 
-    // Propose a new owner
+    let user = helper.app.api().addr_make("user");
+    helper.lock(&user, 1000).unwrap();
     helper
-        .app
-        .execute_contract(
-            helper.owner.clone(),
-            helper.emission_controller.clone(),
-            &msg,
-            &[],
-        )
+        .vote(&user, &[(lp_token.to_string(), Decimal::one())])
         .unwrap();
 
-    // Claim from invalid addr
-    let err = helper
-        .app
-        .execute_contract(
-            helper.app.api().addr_make("invalid_addr"),
-            helper.emission_controller.clone(),
-            &ExecuteMsg::<Empty>::ClaimOwnership {},
-            &[],
+    helper
+        .mint_tokens(
+            &helper.emission_controller.clone(),
+            &coins(50_000_000_000_000, helper.astro.clone()),
         )
-        .unwrap_err();
-    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
+        .unwrap();
 
-    // Drop the ownership proposal
     helper
-        .app
-        .execute_contract(
-            helper.owner.clone(),
-            helper.emission_controller.clone(),
-            &ExecuteMsg::<Empty>::DropOwnershipProposal {},
-            &[],
+        .execute_hub_msg(
+            &owner,
+            HubMsg::UpdateConfig(Box::new(UpdateConfigMsg {
+                max_failed_epochs: Some(2),
+                ..Default::default()
+            })),
         )
         .unwrap();
 
-    // Claim ownership
+    let mock_timeout = |helper: &mut ControllerHelper| {
+        helper
+            .app
+            .wasm_sudo(
+                helper.emission_controller.clone(),
+                &TransferSudoMsg::Timeout {
+                    request: RequestPacket {
+                        sequence: None,
+                        source_port: None,
+                        source_channel: Some("channel-2".to_string()),
+                        destination_port: None,
+                        destination_channel: None,
+                        data: None,
+                        timeout_height: None,
+                        timeout_timestamp: None,
+                    },
+                },
+            )
+            .unwrap();
+    };
+
+    // The first epoch delivery fails
+    helper.timetravel(EPOCH_LENGTH);
+    helper.tune(&owner).unwrap();
+    let first_epoch = get_epoch_start(helper.app.block_info().time.seconds());
+    mock_timeout(&mut helper);
+    assert_eq!(helper.query_outpost_failures().unwrap(), vec![]);
+
+    // Failure is counted once the next epoch starts
+    helper.timetravel(EPOCH_LENGTH);
+    let resp = helper.tune(&owner).unwrap();
+    assert!(!resp.has_event(&Event::new("wasm-outpost_emissions_paused")));
+    let second_epoch = get_epoch_start(helper.app.block_info().time.seconds());
+    assert_eq!(
+        helper.query_outpost_failures().unwrap(),
+        vec![(
+            "osmo".to_string(),
+            OutpostFailures {
+                consecutive_failed_epochs: 1,
+                last_failed_epoch: first_epoch,
+                emissions_paused: false,
+            }
+        )]
+    );
+    mock_timeout(&mut helper);
+
+    // Emissions are paused after the second consecutive failure
+    helper.timetravel(EPOCH_LENGTH);
+    let resp = helper.tune(&owner).unwrap();
+    assert!(resp.has_event(
+        &Event::new("wasm-outpost_emissions_paused")
+            .add_attributes([attr("prefix", "osmo"), attr("failed_epochs", "2"),])
+    ));
+    assert_eq!(
+        helper.query_outpost_failures().unwrap(),
+        vec![(
+            "osmo".to_string(),
+            OutpostFailures {
+                consecutive_failed_epochs: 2,
+                last_failed_epoch: second_epoch,
+                emissions_paused: true,
+            }
+        )]
+    );
+    let tune_info = helper.query_tune_info(None).unwrap();
+    assert!(tune_info.outpost_emissions_statuses.is_empty());
+    assert_eq!(
+        tune_info.skipped_pools,
+        vec![SkippedPool {
+            pool: lp_token.to_string(),
+            reason: SkipReason::OutpostPaused,
+        }]
+    );
+
+    // Only the owner can resume emissions
     let err = helper
-        .app
-        .execute_contract(
-            new_owner.clone(),
-            helper.emission_controller.clone(),
-            &ExecuteMsg::<Empty>::ClaimOwnership {},
-            &[],
+        .execute_hub_msg(
+            &user,
+            HubMsg::ResumeOutpostEmissions {
+                prefix: "osmo".to_string(),
+            },
         )
         .unwrap_err();
     assert_eq!(
-        err.root_cause().to_string(),
-        "Generic error: Ownership proposal not found"
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
     );
-
-    // Propose a new owner again
-    helper
-        .app
-        .execute_contract(
-            helper.owner.clone(),
-            helper.emission_controller.clone(),
-            &msg,
-            &[],
-        )
-        .unwrap();
     helper
-        .app
-        .execute_contract(
-            new_owner.clone(),
-            helper.emission_controller.clone(),
-            &ExecuteMsg::<Empty>::ClaimOwnership {},
-            &[],
+        .execute_hub_msg(
+            &owner,
+            HubMsg::ResumeOutpostEmissions {
+                prefix: "osmo".to_string(),
+            },
         )
         .unwrap();
-
-    assert_eq!(helper.query_config().unwrap().owner.to_string(), new_owner)
-}
-
-#[test]
-fn test_update_config() {
-    let mut helper = ControllerHelper::new();
-
-    let fee_receiver = helper.app.api().addr_make("fee_receiver");
-    let msg = ExecuteMsg::Custom(HubMsg::UpdateConfig {
-        pools_per_outpost: Some(8),
-        whitelisting_fee: Some(coin(100, "astro")),
-        fee_receiver: Some(fee_receiver.to_string()),
-        emissions_multiple: Some(Decimal::percent(90)),
-        max_astro: Some(1_000_000u128.into()),
-    });
+    assert_eq!(helper.query_outpost_failures().unwrap(), vec![]);
 
     let err = helper
-        .app
-        .execute_contract(
-            helper.app.api().addr_make("random"),
-            helper.emission_controller.clone(),
-            &msg,
-            &[],
+        .execute_hub_msg(
+            &owner,
+            HubMsg::ResumeOutpostEmissions {
+                prefix: "osmo".to_string(),
+            },
         )
         .unwrap_err();
     assert_eq!(
         err.downcast::<ContractError>().unwrap(),
-        ContractError::Unauthorized {}
+        ContractError::OutpostEmissionsNotPaused("osmo".to_string())
+    );
+
+    // The outpost receives emissions again
+    helper.timetravel(EPOCH_LENGTH);
+    helper.tune(&owner).unwrap();
+    let tune_info = helper.query_tune_info(None).unwrap();
+    assert_eq!(
+        tune_info.outpost_emissions_statuses,
+        HashMap::from([("osmo".to_string(), OutpostStatus::InProgress)])
     );
+    assert_eq!(tune_info.skipped_pools, vec![]);
+}
 
+#[test]
+fn test_outpost_flow_stats() {
+    let mut helper = ControllerHelper::new();
+
+    let escrow_address =
+        determine_ics20_escrow_address(helper.app.api(), "transfer", "channel-2").unwrap();
     helper
-        .app
-        .execute_contract(
-            helper.owner.clone(),
-            helper.emission_controller.clone(),
-            &msg,
-            &[],
+        .add_outpost(
+            "osmo",
+            OutpostInfo {
+                astro_denom: "ibc/C4CFF46FD6DE35CA4CF4CE031E643C8FDC9BA4B99AE598E9B0ED98FE3A2319F9"
+                    .to_string(),
+                params: Some(OutpostParams {
+                    emissions_controller: "osmo1controller".to_string(),
+                    voting_channel: "channel-1".to_string(),
+                    ics20_channel: "channel-2".to_string(),
+                    escrow_address: escrow_address.clone(),
+                    ibc_timeout: None,
+                    relayer_fee: None,
+                    pools_per_packet: None,
+                }),
+                astro_pool_config: None,
+                jailed: false,
+                permissions: OUTPOST_PERMIT_ALL,
+            },
         )
         .unwrap();
+    let xastro = helper.xastro.clone();
+    helper
+        .mint_tokens(&escrow_address, &coins(10_000000, xastro))
+        .unwrap();
 
-    let config = helper.query_config().unwrap();
+    let report_supply = |helper: &mut ControllerHelper, total_voting_power: u128| {
+        helper
+            .mock_packet_receive(VxAstroIbcMsg::UpdateUserVotes {
+                voter: "osmo1voter".to_string(),
+                voting_power: 1_000000u128.into(),
+                total_voting_power: total_voting_power.into(),
+                is_unlock: false,
+            })
+            .unwrap();
+    };
+
+    let epoch_start = get_epoch_start(helper.app.block_info().time.seconds());
+
+    // The first report only sets the baseline
+    report_supply(&mut helper, 1_000000);
+    let stats = helper.query_flow_stats(epoch_start).unwrap();
+    assert_eq!(stats.total_staked, Uint128::zero());
+    assert_eq!(stats.outposts, vec![]);
 
+    report_supply(&mut helper, 3_000000);
+    report_supply(&mut helper, 2_500000);
+    report_supply(&mut helper, 2_500000);
+
+    // Any timestamp within the epoch resolves to the same bucket
+    let stats = helper.query_flow_stats(epoch_start + DAY).unwrap();
     assert_eq!(
-        config,
-        emissions_controller::hub::Config {
-            owner: helper.owner.clone(),
-            assembly: helper.assembly.clone(),
-            vxastro: helper.vxastro.clone(),
-            factory: helper.factory.clone(),
-            astro_denom: helper.astro.clone(),
-            xastro_denom: helper.xastro.clone(),
-            staking: helper.staking.clone(),
-            incentives_addr: helper.incentives.clone(),
-            pools_per_outpost: 8,
-            whitelisting_fee: coin(100, "astro"),
-            fee_receiver,
-            whitelist_threshold: Decimal::percent(1),
-            emissions_multiple: Decimal::percent(90),
-            max_astro: 1_000_000u128.into(),
+        stats,
+        FlowStatsResponse {
+            epoch_start,
+            total_staked: 2_000000u128.into(),
+            total_unstaked: 500000u128.into(),
+            outposts: vec![(
+                "osmo".to_string(),
+                OutpostFlow {
+                    staked: 2_000000u128.into(),
+                    unstaked: 500000u128.into(),
+                }
+            )],
         }
     );
+
+    helper.timetravel(EPOCH_LENGTH);
+    report_supply(&mut helper, 2_000000);
+
+    let stats = helper.query_flow_stats(epoch_start + EPOCH_LENGTH).unwrap();
+    assert_eq!(stats.total_staked, Uint128::zero());
+    assert_eq!(stats.total_unstaked, Uint128::new(500000));
+
+    // Previous epoch stats are kept
+    let stats = helper.query_flow_stats(epoch_start).unwrap();
+    assert_eq!(stats.total_staked, Uint128::new(2_000000));
 }
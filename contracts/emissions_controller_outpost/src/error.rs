@@ -3,6 +3,7 @@ use cw_utils::{ParseReplyError, PaymentError};
 use thiserror::Error;
 
 use astroport_governance::emissions_controller::consts::MAX_POOLS_TO_VOTE;
+use astroport_governance::emissions_controller::outpost::FailureKind;
 
 /// This enum describes contract errors
 #[derive(Error, Debug, PartialEq)]
@@ -40,6 +41,12 @@ pub enum ContractError {
     #[error("Message contains duplicated pools")]
     DuplicatedVotes {},
 
+    #[error("Vote managers are supported only on the Hub")]
+    VoteManagersNotSupported {},
+
+    #[error("Vote retraction is supported only on the Hub")]
+    RetractVoteNotSupported {},
+
     #[error("Invalid astro amount. Expected: {expected}, actual: {actual}")]
     InvalidAstroAmount { expected: Uint128, actual: Uint128 },
 
@@ -51,4 +58,28 @@ pub enum ContractError {
 
     #[error("Failed to migrate contract")]
     MigrationError {},
+
+    #[error("Proposal {0} is not registered")]
+    ProposalNotRegistered(u64),
+
+    #[error("Proposal {0} is still active or its status is unknown")]
+    ProposalNotConcluded(u64),
+
+    #[error("Tally request for proposal {0} is already pending")]
+    PendingTallyRequest(u64),
+
+    #[error("Failure {0} not found")]
+    FailureNotFound(u64),
+
+    #[error("Failure {failure_id} of kind {kind:?} can't be retried with this endpoint")]
+    InvalidFailureKind { failure_id: u64, kind: FailureKind },
+
+    #[error("Invalid schedule chunk {index} of {total}")]
+    InvalidScheduleChunk { index: u32, total: u32 },
+
+    #[error("Schedule for epoch {epoch} is delivered in {total} chunks")]
+    ScheduleChunksMismatch { epoch: u64, total: u32 },
+
+    #[error("Schedule chunk belongs to stale epoch {0}")]
+    StaleScheduleChunk(u64),
 }
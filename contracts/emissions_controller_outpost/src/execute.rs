@@ -7,17 +7,22 @@ use astroport::incentives::{IncentivesSchedule, InputSchedule};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, coin, coins, ensure, wasm_execute, Addr, Coin, Decimal, DepsMut, Env, IbcMsg,
-    MessageInfo, Response, StdError, Uint128,
+    attr, coin, coins, ensure, to_json_binary, wasm_execute, Addr, Binary, Coin, Decimal, DepsMut,
+    Env, IbcMsg, MessageInfo, Order, Response, StdError, StdResult, Storage, SubMsg, Uint128,
 };
 use cw_utils::{must_pay, nonpayable};
 use itertools::Itertools;
 
-use astroport_governance::assembly::ProposalVoteOption;
-use astroport_governance::emissions_controller::consts::{IBC_TIMEOUT, MAX_POOLS_TO_VOTE};
+use astroport_governance::assembly::{ProposalStatus, ProposalTemplate, ProposalVoteOption};
+use astroport_governance::emissions_controller::consts::{
+    IBC_TIMEOUT, MAX_PAGE_LIMIT, MAX_POOLS_TO_VOTE,
+};
 use astroport_governance::emissions_controller::msg::ExecuteMsg;
 use astroport_governance::emissions_controller::msg::VxAstroIbcMsg;
-use astroport_governance::emissions_controller::outpost::{Config, OutpostMsg};
+use astroport_governance::emissions_controller::outpost::{
+    CachedVotingPower, ChunksProgress, Config, FailureKind, IbcFailure, OutpostMsg,
+    PendingEmissions, PoolEmissions, PoolEmissionsStatus, ProposalDeposit, ScheduleChunk,
+};
 use astroport_governance::emissions_controller::utils::{
     check_lp_token, get_total_voting_power, get_voting_power,
 };
@@ -26,7 +31,12 @@ use astroport_governance::voting_escrow;
 use astroport_governance::voting_escrow::LockInfoResponse;
 
 use crate::error::ContractError;
-use crate::state::{CONFIG, OWNERSHIP_PROPOSAL, PROPOSAL_VOTERS, REGISTERED_PROPOSALS};
+use crate::instantiate::SUBMIT_PROPOSAL_REPLY_ID;
+use crate::state::{
+    CONFIG, LAST_EMISSIONS, OWNERSHIP_PROPOSAL, PENDING_SUBMISSION, PENDING_TALLY_REQUESTS,
+    PROPOSAL_STATUSES, PROPOSAL_TALLIES, PROPOSAL_VOTERS, REGISTERED_PROPOSALS, USER_FAILURES,
+    VOTING_POWER_CACHE,
+};
 use crate::utils::prepare_ibc_packet;
 
 /// Exposes all execute endpoints available in the contract.
@@ -38,7 +48,16 @@ pub fn execute(
     msg: ExecuteMsg<OutpostMsg>,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Vote { votes } => handle_vote(deps, env, info, votes),
+        ExecuteMsg::Vote {
+            votes,
+            on_behalf_of,
+        } => {
+            ensure!(
+                on_behalf_of.is_none(),
+                ContractError::VoteManagersNotSupported {}
+            );
+            handle_vote(deps, env, info, votes)
+        }
         ExecuteMsg::UpdateUserVotes { user, is_unlock } => {
             let config = CONFIG.load(deps.storage)?;
             ensure!(
@@ -55,16 +74,8 @@ pub fn execute(
                 config,
             )
         }
-        ExecuteMsg::RefreshUserVotes {} => {
-            nonpayable(&info)?;
-            let config = CONFIG.load(deps.storage)?;
-            let voting_power = get_voting_power(deps.querier, &config.vxastro, &info.sender, None)?;
-
-            // Blocking updates if new_voting_power is zero.
-            // Potentially reduces IBC spam attack vector
-            ensure!(!voting_power.is_zero(), ContractError::ZeroVotingPower {});
-            handle_update_user(deps, env, info.sender, voting_power, false, config)
-        }
+        ExecuteMsg::RefreshUserVotes {} => refresh_user_votes(deps, env, info),
+        ExecuteMsg::RetractVote { .. } => Err(ContractError::RetractVoteNotSupported {}),
         ExecuteMsg::ProposeNewOwner {
             new_owner,
             expires_in,
@@ -103,7 +114,9 @@ pub fn execute(
             .map_err(Into::into)
         }
         ExecuteMsg::Custom(outpost_msg) => match outpost_msg {
-            OutpostMsg::SetEmissions { schedules } => set_emissions(deps, env, info, schedules),
+            OutpostMsg::SetEmissions { schedules, chunk } => {
+                set_emissions(deps, env, info, schedules, chunk)
+            }
             OutpostMsg::PermissionedSetEmissions { schedules } => {
                 permissioned_set_emissions(deps, env, info, schedules)
             }
@@ -122,6 +135,99 @@ pub fn execute(
             OutpostMsg::CastVote { proposal_id, vote } => {
                 governance_vote(deps, env, info, proposal_id, vote)
             }
+            OutpostMsg::CommitVote {
+                proposal_id,
+                commitment,
+            } => commit_governance_vote(deps, env, info, proposal_id, commitment),
+            OutpostMsg::RevealVote {
+                proposal_id,
+                vote,
+                salt,
+            } => reveal_governance_vote(deps, env, info, proposal_id, vote, salt),
+            OutpostMsg::RetryVote { failure_id } => {
+                let failure = take_failure(
+                    deps.storage,
+                    &info.sender,
+                    failure_id,
+                    FailureKind::EmissionsVote,
+                )?;
+                let VxAstroIbcMsg::EmissionsVote { votes, .. } = failure.msg else {
+                    unreachable!("Failure kind must match IBC message")
+                };
+                handle_vote(deps, env, info, votes.into_iter().collect())
+                    .map(|response| response.add_attribute("failure_id", failure_id.to_string()))
+            }
+            OutpostMsg::RetryRefreshVotes { failure_id } => {
+                take_failure(
+                    deps.storage,
+                    &info.sender,
+                    failure_id,
+                    FailureKind::UpdateVotes,
+                )?;
+                refresh_user_votes(deps, env, info)
+                    .map(|response| response.add_attribute("failure_id", failure_id.to_string()))
+            }
+            OutpostMsg::RetryGovernanceVote { failure_id } => {
+                let failure = take_failure(
+                    deps.storage,
+                    &info.sender,
+                    failure_id,
+                    FailureKind::GovernanceVote,
+                )?;
+                match failure.msg {
+                    VxAstroIbcMsg::GovernanceVote {
+                        proposal_id, vote, ..
+                    } => governance_vote(deps, env, info, proposal_id, vote),
+                    VxAstroIbcMsg::GovernanceVoteCommit {
+                        proposal_id,
+                        commitment,
+                        ..
+                    } => commit_governance_vote(deps, env, info, proposal_id, commitment),
+                    VxAstroIbcMsg::GovernanceVoteReveal {
+                        proposal_id,
+                        vote,
+                        salt,
+                        ..
+                    } => reveal_governance_vote(deps, env, info, proposal_id, vote, salt),
+                    _ => unreachable!("Failure kind must match IBC message"),
+                }
+                .map(|response| response.add_attribute("failure_id", failure_id.to_string()))
+            }
+            OutpostMsg::WhitelistPool { pool } => whitelist_pool(deps, env, info, pool),
+            OutpostMsg::SubmitProposal { proposal } => submit_proposal(deps, env, info, proposal),
+            OutpostMsg::RequestProposalTally { proposal_id } => {
+                nonpayable(&info)?;
+                request_proposal_tally(deps, env, proposal_id)
+            }
+            OutpostMsg::ClearVotingPowerCache { proposal_id, limit } => {
+                nonpayable(&info)?;
+                clear_voting_power_cache(deps, proposal_id, limit)
+            }
+            OutpostMsg::ExecuteHubMsgs { msgs } => {
+                nonpayable(&info)?;
+                ensure!(
+                    info.sender == env.contract.address,
+                    ContractError::Unauthorized {}
+                );
+
+                Ok(Response::new()
+                    .add_messages(msgs)
+                    .add_attribute("action", "execute_hub_msgs"))
+            }
+            OutpostMsg::DismissFailure { failure_id } => {
+                nonpayable(&info)?;
+                let key = (info.sender.as_str(), failure_id);
+                ensure!(
+                    USER_FAILURES.has(deps.storage, key),
+                    ContractError::FailureNotFound(failure_id)
+                );
+                USER_FAILURES.remove(deps.storage, key);
+
+                Ok(Response::default().add_attributes([
+                    attr("action", "dismiss_failure"),
+                    attr("failure_id", failure_id.to_string()),
+                ]))
+            }
         },
     }
 }
@@ -133,6 +239,7 @@ pub fn set_emissions(
     env: Env,
     info: MessageInfo,
     schedules: Vec<(String, InputSchedule)>,
+    chunk: Option<ScheduleChunk>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     let amount = must_pay(&info, &config.astro_denom)?;
@@ -151,7 +258,7 @@ pub fn set_emissions(
     );
 
     let funds = coin(amount.u128(), &config.astro_denom);
-    execute_emissions(deps, env, funds, config, schedules)
+    execute_emissions(deps, env, funds, config, schedules, chunk)
 }
 
 /// Permissioned endpoint to set emissions for given pools.
@@ -184,7 +291,7 @@ pub fn permissioned_set_emissions(
         }
     );
 
-    execute_emissions(deps, env, balance, config, schedules)
+    execute_emissions(deps, env, balance, config, schedules, None)
 }
 
 /// Main function to set emissions for given pools.
@@ -195,19 +302,34 @@ pub fn execute_emissions(
     astro_balance: Coin,
     config: Config,
     schedules: Vec<(String, InputSchedule)>,
+    chunk: Option<ScheduleChunk>,
 ) -> Result<Response, ContractError> {
     // Filter not eligible pools and send leftover funds back to the Hub
     let mut expected_amount = 0u128;
+    let mut pools = vec![];
     let schedules = schedules
         .into_iter()
         .filter(|(pool, schedule)| {
-            determine_asset_info(pool, deps.api)
+            let result = determine_asset_info(pool, deps.api)
                 .and_then(|maybe_lp| check_lp_token(deps.querier, &config.factory, &maybe_lp))
                 .and_then(|_| IncentivesSchedule::from_input(&env, schedule))
                 .map(|_| {
                     expected_amount += schedule.reward.amount.u128();
-                })
-                .is_ok()
+                });
+
+            let status = match &result {
+                Ok(_) => PoolEmissionsStatus::Funded,
+                Err(err) => PoolEmissionsStatus::Failed {
+                    reason: err.to_string(),
+                },
+            };
+            pools.push(PoolEmissions {
+                pool: pool.clone(),
+                schedule: schedule.clone(),
+                status,
+            });
+
+            result.is_ok()
         })
         .collect_vec();
 
@@ -221,9 +343,28 @@ pub fn execute_emissions(
 
     let excess_amount = astro_balance.amount.checked_sub(expected_amount.into())?;
 
+    let mut last_emissions = PendingEmissions {
+        received_at: env.block.time.seconds(),
+        received_amount: astro_balance.amount,
+        funded_amount: expected_amount.into(),
+        returned_amount: excess_amount,
+        pools,
+        chunks: None,
+    };
+    if let Some(chunk) = &chunk {
+        last_emissions = merge_schedule_chunk(deps.storage, chunk, last_emissions)?;
+    }
+    LAST_EMISSIONS.save(deps.storage, &last_emissions)?;
+
     let mut response = Response::default()
         .add_message(incentives_msg)
         .add_attribute("action", "set_emissions");
+    if let Some(chunk) = chunk {
+        response = response.add_attributes([
+            attr("epoch", chunk.epoch.to_string()),
+            attr("chunk", format!("{}/{}", chunk.index + 1, chunk.total)),
+        ]);
+    }
     if !excess_amount.is_zero() {
         // Send excess funds back to the Hub
         let ibc_transfer_msg = IbcMsg::Transfer {
@@ -240,6 +381,70 @@ pub fn execute_emissions(
     Ok(response)
 }
 
+/// Merges a chunk of the emissions schedule into the schedule which is being reassembled.
+/// The first received chunk of a new epoch starts a new schedule.
+/// Duplicated chunks are counted once, but their funds are used for emissions as usual.
+/// They are not rejected as this endpoint is permissionless, and rejection would allow anyone
+/// to block delivery of the Hub's packets.
+fn merge_schedule_chunk(
+    storage: &dyn Storage,
+    chunk: &ScheduleChunk,
+    emissions: PendingEmissions,
+) -> Result<PendingEmissions, ContractError> {
+    ensure!(
+        chunk.index < chunk.total,
+        ContractError::InvalidScheduleChunk {
+            index: chunk.index,
+            total: chunk.total
+        }
+    );
+
+    let last_emissions = LAST_EMISSIONS
+        .may_load(storage)?
+        .and_then(|last| Some((last.chunks.clone()?, last)));
+
+    match last_emissions {
+        Some((progress, last)) if progress.epoch == chunk.epoch => {
+            ensure!(
+                progress.total == chunk.total,
+                ContractError::ScheduleChunksMismatch {
+                    epoch: chunk.epoch,
+                    total: progress.total
+                }
+            );
+
+            let mut received = progress.received;
+            if !received.contains(&chunk.index) {
+                received.push(chunk.index);
+                received.sort_unstable();
+            }
+
+            Ok(PendingEmissions {
+                received_at: emissions.received_at,
+                received_amount: last.received_amount + emissions.received_amount,
+                funded_amount: last.funded_amount + emissions.funded_amount,
+                returned_amount: last.returned_amount + emissions.returned_amount,
+                pools: last.pools.into_iter().chain(emissions.pools).collect(),
+                chunks: Some(ChunksProgress {
+                    received,
+                    ..progress
+                }),
+            })
+        }
+        Some((progress, _)) if progress.epoch > chunk.epoch => {
+            Err(ContractError::StaleScheduleChunk(chunk.epoch))
+        }
+        _ => Ok(PendingEmissions {
+            chunks: Some(ChunksProgress {
+                epoch: chunk.epoch,
+                total: chunk.total,
+                received: vec![chunk.index],
+            }),
+            ..emissions
+        }),
+    }
+}
+
 /// This function performs vote basic validation and sends an IBC packet to the Hub.
 /// Emissions Controller on the Hub is responsible for checking whether user is eligible to vote again
 /// as well as validates pools are whitelisted and correspond to a specific outpost.
@@ -331,6 +536,195 @@ pub fn handle_update_user(
         .add_message(ibc_msg))
 }
 
+/// Permissionless endpoint which sends an IBC packet to the Hub
+/// to refresh sender's contribution to emissions voting.
+pub fn refresh_user_votes(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+    let voting_power = get_voting_power(deps.querier, &config.vxastro, &info.sender, None)?;
+
+    // Blocking updates if new_voting_power is zero.
+    // Potentially reduces IBC spam attack vector
+    ensure!(!voting_power.is_zero(), ContractError::ZeroVotingPower {});
+    handle_update_user(deps, env, info.sender, voting_power, false, config)
+}
+
+/// Permissionless endpoint which sends a whitelisting request for an outpost pool to the Hub.
+/// Caller must send the whitelisting fee in ASTRO. The Hub validates the fee amount.
+/// The fee stays on this contract until the Hub acknowledges the request.
+pub fn whitelist_pool(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let fee = must_pay(&info, &config.astro_denom)?;
+
+    // Perform basic LP token validation. Hub can't query outpost factory
+    let maybe_lp = determine_asset_info(&pool, deps.api)?;
+    check_lp_token(deps.querier, &config.factory, &maybe_lp)?;
+
+    let ibc_msg = prepare_ibc_packet(
+        deps.storage,
+        &env,
+        info.sender.as_str(),
+        VxAstroIbcMsg::WhitelistPool {
+            user: info.sender.to_string(),
+            pool: pool.clone(),
+            fee,
+        },
+        config.voting_ibc_channel,
+    )?;
+
+    Ok(Response::default()
+        .add_attributes([
+            attr("action", "whitelist_pool"),
+            attr("user", &info.sender),
+            attr("pool", pool),
+            attr("fee", fee),
+        ])
+        .add_message(ibc_msg))
+}
+
+/// Submits an Assembly proposal through the Hub.
+/// The xASTRO deposit is escrowed on the outpost until the Hub accepts the submission
+/// and refunded if the Hub rejects it.
+/// Submissions don't block other IBC messages of the submitter.
+pub fn submit_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal: ProposalTemplate,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let vxastro_config: voting_escrow::Config = deps
+        .querier
+        .query_wasm_smart(&config.vxastro, &voting_escrow::QueryMsg::Config {})?;
+    let deposit = must_pay(&info, &vxastro_config.deposit_denom)?;
+
+    // The deposit is saved under the packet sequence in the reply
+    PENDING_SUBMISSION.save(
+        deps.storage,
+        &ProposalDeposit {
+            submitter: info.sender.to_string(),
+            deposit: coin(deposit.u128(), vxastro_config.deposit_denom),
+        },
+    )?;
+    let ibc_msg = IbcMsg::SendPacket {
+        channel_id: config.voting_ibc_channel,
+        data: to_json_binary(&VxAstroIbcMsg::SubmitProposal {
+            submitter: info.sender.to_string(),
+            proposal,
+            deposit,
+        })?,
+        timeout: env.block.time.plus_seconds(IBC_TIMEOUT).into(),
+    };
+
+    Ok(Response::default()
+        .add_attributes([
+            attr("action", "submit_proposal"),
+            attr("submitter", &info.sender),
+            attr("deposit", deposit),
+        ])
+        .add_submessage(SubMsg::reply_on_success(ibc_msg, SUBMIT_PROPOSAL_REPLY_ID)))
+}
+
+/// Requests the live tally of a registered proposal from the Hub.
+/// Only one request per proposal can be in flight.
+pub fn request_proposal_tally(
+    deps: DepsMut,
+    env: Env,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        REGISTERED_PROPOSALS.has(deps.storage, proposal_id),
+        ContractError::ProposalNotRegistered(proposal_id)
+    );
+    ensure!(
+        !PENDING_TALLY_REQUESTS.has(deps.storage, proposal_id),
+        ContractError::PendingTallyRequest(proposal_id)
+    );
+    PENDING_TALLY_REQUESTS.save(deps.storage, proposal_id, &())?;
+
+    let ibc_msg = IbcMsg::SendPacket {
+        channel_id: config.voting_ibc_channel,
+        data: to_json_binary(&VxAstroIbcMsg::ProposalTallyRequest { proposal_id })?,
+        timeout: env.block.time.plus_seconds(IBC_TIMEOUT).into(),
+    };
+
+    Ok(Response::default()
+        .add_attributes([
+            attr("action", "request_proposal_tally"),
+            attr("proposal_id", proposal_id.to_string()),
+        ])
+        .add_message(ibc_msg))
+}
+
+/// Removes cached voting power of a concluded proposal.
+/// The proposal is considered concluded once the Hub reports a non-active status for it
+/// either via status updates or tally responses.
+pub fn clear_voting_power_cache(
+    deps: DepsMut,
+    proposal_id: u64,
+    limit: Option<u8>,
+) -> Result<Response, ContractError> {
+    let status = match PROPOSAL_STATUSES.may_load(deps.storage, proposal_id)? {
+        Some(status) => Some(status),
+        None => PROPOSAL_TALLIES
+            .may_load(deps.storage, proposal_id)?
+            .map(|tally| tally.status),
+    };
+    ensure!(
+        status.is_some_and(|status| status != ProposalStatus::Active),
+        ContractError::ProposalNotConcluded(proposal_id)
+    );
+
+    let voters = VOTING_POWER_CACHE
+        .prefix(proposal_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(limit.unwrap_or(MAX_PAGE_LIMIT) as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+    for voter in &voters {
+        VOTING_POWER_CACHE.remove(deps.storage, (proposal_id, voter));
+    }
+
+    Ok(Response::default().add_attributes([
+        attr("action", "clear_voting_power_cache"),
+        attr("proposal_id", proposal_id.to_string()),
+        attr("cleared", voters.len().to_string()),
+    ]))
+}
+
+/// Removes a failure from the user's inbox ensuring it is of the expected kind.
+/// The follow-up IBC message is composed by the caller.
+fn take_failure(
+    storage: &mut dyn Storage,
+    user: &Addr,
+    failure_id: u64,
+    kind: FailureKind,
+) -> Result<IbcFailure, ContractError> {
+    let key = (user.as_str(), failure_id);
+    let failure = USER_FAILURES
+        .may_load(storage, key)?
+        .ok_or(ContractError::FailureNotFound(failure_id))?;
+    ensure!(
+        failure.kind == kind,
+        ContractError::InvalidFailureKind {
+            failure_id,
+            kind: failure.kind,
+        }
+    );
+    USER_FAILURES.remove(storage, key);
+
+    Ok(failure)
+}
+
 /// Only contract owner can call this function.
 ///
 /// * voting_ibc_channel: new IBC channel to send votes to the Hub.
@@ -376,42 +770,69 @@ fn update_config(
     Ok(Response::default().add_attributes(attrs))
 }
 
-pub fn governance_vote(
+/// Returns voter's and total voting power at the moment the proposal started.
+/// Voting power is queried on the first vote and cached for re-vote attempts
+/// so that all packets of the same voter carry the same snapshot.
+/// Errors if the voter has already voted on this proposal.
+fn proposal_voting_power(
     deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
+    vxastro: &Addr,
+    voter: &str,
     proposal_id: u64,
-    vote: ProposalVoteOption,
-) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    let voter = info.sender.to_string();
-
+) -> Result<CachedVotingPower, ContractError> {
     ensure!(
-        !PROPOSAL_VOTERS.has(deps.storage, (proposal_id, voter.clone())),
+        !PROPOSAL_VOTERS.has(deps.storage, (proposal_id, voter.to_string())),
         ContractError::AlreadyVoted {}
     );
 
+    if let Some(cached) = VOTING_POWER_CACHE.may_load(deps.storage, (proposal_id, voter))? {
+        return Ok(cached);
+    }
+
     let start_time = REGISTERED_PROPOSALS.load(deps.storage, proposal_id)?;
+    let snapshot_time = start_time - 1;
 
     let voting_power = deps
         .querier
         .query_wasm_smart(
-            &config.vxastro,
+            vxastro,
             &voting_escrow::QueryMsg::LockInfo {
-                user: voter.clone(),
-                timestamp: Some(start_time - 1),
+                user: voter.to_string(),
+                timestamp: Some(snapshot_time),
             },
         )
         .map(|resp: LockInfoResponse| resp.amount)?;
     ensure!(!voting_power.is_zero(), ContractError::ZeroVotingPower {});
 
-    let total_voting_power =
-        get_total_voting_power(deps.querier, &config.vxastro, Some(start_time - 1))?;
+    let total_voting_power = get_total_voting_power(deps.querier, vxastro, Some(snapshot_time))?;
+
+    let cached = CachedVotingPower {
+        voting_power,
+        total_voting_power,
+        snapshot_time,
+    };
+    VOTING_POWER_CACHE.save(deps.storage, (proposal_id, voter), &cached)?;
+
+    Ok(cached)
+}
+
+pub fn governance_vote(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    vote: ProposalVoteOption,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let voter = info.sender.to_string();
+
+    let snapshot = proposal_voting_power(deps.branch(), &config.vxastro, &voter, proposal_id)?;
 
     let attrs = vec![
         attr("action", "governance_vote"),
         attr("voter", &info.sender),
-        attr("voting_power", voting_power),
+        attr("voting_power", snapshot.voting_power),
+        attr("snapshot_time", snapshot.snapshot_time.to_string()),
     ];
 
     let ibc_msg = prepare_ibc_packet(
@@ -420,10 +841,52 @@ pub fn governance_vote(
         &voter,
         VxAstroIbcMsg::GovernanceVote {
             voter: voter.clone(),
-            voting_power,
-            total_voting_power,
+            voting_power: snapshot.voting_power,
+            total_voting_power: snapshot.total_voting_power,
             proposal_id,
             vote,
+            snapshot_time: Some(snapshot.snapshot_time),
+        },
+        config.voting_ibc_channel,
+    )?;
+
+    Ok(Response::default()
+        .add_attributes(attrs)
+        .add_message(ibc_msg))
+}
+
+/// Sends a hidden vote on a commit-reveal proposal to the Hub.
+/// The commitment can be replaced until the commit phase ends.
+pub fn commit_governance_vote(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    commitment: Binary,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let voter = info.sender.to_string();
+
+    let snapshot = proposal_voting_power(deps.branch(), &config.vxastro, &voter, proposal_id)?;
+
+    let attrs = vec![
+        attr("action", "commit_governance_vote"),
+        attr("voter", &info.sender),
+        attr("voting_power", snapshot.voting_power),
+        attr("snapshot_time", snapshot.snapshot_time.to_string()),
+    ];
+
+    let ibc_msg = prepare_ibc_packet(
+        deps.storage,
+        &env,
+        &voter,
+        VxAstroIbcMsg::GovernanceVoteCommit {
+            voter: voter.clone(),
+            voting_power: snapshot.voting_power,
+            total_voting_power: snapshot.total_voting_power,
+            proposal_id,
+            commitment,
+            snapshot_time: Some(snapshot.snapshot_time),
         },
         config.voting_ibc_channel,
     )?;
@@ -432,3 +895,39 @@ pub fn governance_vote(
         .add_attributes(attrs)
         .add_message(ibc_msg))
 }
+
+/// Reveals a vote previously committed from this outpost.
+pub fn reveal_governance_vote(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    vote: ProposalVoteOption,
+    salt: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let voter = info.sender.to_string();
+
+    let snapshot = proposal_voting_power(deps.branch(), &config.vxastro, &voter, proposal_id)?;
+
+    let ibc_msg = prepare_ibc_packet(
+        deps.storage,
+        &env,
+        &voter,
+        VxAstroIbcMsg::GovernanceVoteReveal {
+            voter: voter.clone(),
+            total_voting_power: snapshot.total_voting_power,
+            proposal_id,
+            vote,
+            salt,
+        },
+        config.voting_ibc_channel,
+    )?;
+
+    Ok(Response::default()
+        .add_attributes([
+            attr("action", "reveal_governance_vote"),
+            attr("voter", &info.sender),
+        ])
+        .add_message(ibc_msg))
+}
@@ -1,22 +1,34 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure, from_json, wasm_execute, DepsMut, Env, Ibc3ChannelOpenResponse, IbcBasicResponse,
-    IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg, IbcPacketAckMsg,
-    IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse, Never, StdError, StdResult,
-    Storage,
+    coin, coins, ensure, from_json, wasm_execute, BankMsg, DepsMut, Env, Ibc3ChannelOpenResponse,
+    IbcBasicResponse, IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg, IbcMsg,
+    IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse, Never, Order,
+    StdError, StdResult, Storage, SubMsg, Timestamp, Uint128,
 };
+use osmosis_std::types::cosmos::base::v1beta1::Coin as ProtoCoin;
+use osmosis_std::types::ibc::applications::transfer::v1::MsgTransfer;
 
-use astroport_governance::emissions_controller::consts::{IBC_APP_VERSION, IBC_ORDERING};
+use astroport_governance::emissions_controller::consts::{
+    IBC_APP_VERSION, IBC_ORDERING, IBC_TIMEOUT, MAX_USER_FAILURES,
+};
+use astroport_governance::emissions_controller::hub::HubMsg;
 use astroport_governance::emissions_controller::msg::{
-    ack_fail, ack_ok, IbcAckResult, VxAstroIbcMsg,
+    ack_fail, ack_ok, AckError, AckErrorCode, ExecuteMsg, IbcAckResult, VxAstroIbcMsg,
+};
+use astroport_governance::emissions_controller::outpost::{
+    FailureKind, IbcFailure, OutpostMsg, ProposalDeposit, ProposalTally, UserIbcError,
+    VoteReceiptStatus,
 };
-use astroport_governance::emissions_controller::outpost::UserIbcError;
 use astroport_governance::voting_escrow;
 
+use crate::instantiate::EXECUTE_HUB_MSGS_REPLY_ID;
 use crate::state::{
-    CONFIG, PENDING_MESSAGES, PROPOSAL_VOTERS, REGISTERED_PROPOSALS, USER_IBC_ERROR,
+    CONFIG, FAILURES_COUNT, PENDING_MESSAGES, PENDING_PROPOSAL_DEPOSITS, PENDING_TALLY_REQUESTS,
+    PROPOSAL_STATUSES, PROPOSAL_TALLIES, PROPOSAL_VOTERS, REGISTERED_PROPOSALS, USER_FAILURES,
+    USER_IBC_ERROR,
 };
+use crate::utils::save_vote_receipt;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn ibc_channel_open(
@@ -83,7 +95,7 @@ pub fn ibc_packet_receive(
 
 pub fn do_packet_receive(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     msg: IbcPacketReceiveMsg,
 ) -> StdResult<IbcReceiveResponse> {
     // Accept messages only from the trusted channel
@@ -113,6 +125,71 @@ pub fn do_packet_receive(
                 .add_attribute("start_time", start_time.to_string());
             Ok(response)
         }
+        VxAstroIbcMsg::ExecuteMsgs { execution_id, msgs } => {
+            // Messages are executed via self-call thus either all of them succeed or none.
+            // Acknowledgement is overwritten in the reply if execution fails.
+            let execute_msg = wasm_execute(
+                env.contract.address,
+                &ExecuteMsg::Custom(OutpostMsg::ExecuteHubMsgs { msgs }),
+                vec![],
+            )?;
+
+            Ok(IbcReceiveResponse::new()
+                .set_ack(ack_ok())
+                .add_submessage(SubMsg::reply_on_error(
+                    execute_msg,
+                    EXECUTE_HUB_MSGS_REPLY_ID,
+                ))
+                .add_attribute("action", "execute_hub_msgs")
+                .add_attribute("execution_id", execution_id.to_string()))
+        }
+        VxAstroIbcMsg::ProposalStatusUpdate {
+            proposal_id,
+            status,
+        } => {
+            PROPOSAL_STATUSES.save(deps.storage, proposal_id, &status)?;
+
+            Ok(IbcReceiveResponse::new()
+                .set_ack(ack_ok())
+                .add_attribute("action", "proposal_status_update")
+                .add_attribute("proposal_id", proposal_id.to_string())
+                .add_attribute("status", status.to_string()))
+        }
+        VxAstroIbcMsg::RefundProposalDeposit {
+            sequence,
+            submitter,
+            deposit,
+        } => {
+            // The deposit transfer has bounced back or hasn't been sent at all.
+            // Deposits of submissions awaiting the Hub acknowledgement aren't available
+            let vxastro_config: voting_escrow::Config = deps
+                .querier
+                .query_wasm_smart(&config.vxastro, &voting_escrow::QueryMsg::Config {})?;
+            let balance = deps
+                .querier
+                .query_balance(&env.contract.address, &vxastro_config.deposit_denom)?
+                .amount;
+            let pending = PENDING_PROPOSAL_DEPOSITS
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(_, pending)| pending.deposit.amount))
+                .sum::<StdResult<Uint128>>()?;
+            ensure!(
+                balance.saturating_sub(pending) >= deposit,
+                StdError::generic_err(format!(
+                    "Deposit of submission {sequence} hasn't returned to the outpost yet"
+                ))
+            );
+
+            Ok(IbcReceiveResponse::new()
+                .set_ack(ack_ok())
+                .add_message(BankMsg::Send {
+                    to_address: submitter.clone(),
+                    amount: coins(deposit.u128(), vxastro_config.deposit_denom),
+                })
+                .add_attribute("action", "refund_proposal_deposit")
+                .add_attribute("sequence", sequence.to_string())
+                .add_attribute("submitter", submitter))
+        }
         _ => Err(StdError::generic_err("Invalid IBC message type")),
     }
 }
@@ -120,12 +197,12 @@ pub fn do_packet_receive(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn ibc_packet_ack(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     msg: IbcPacketAckMsg,
 ) -> StdResult<IbcBasicResponse> {
     let orig_msg: VxAstroIbcMsg = from_json(&msg.original_packet.data)?;
     match from_json(&msg.acknowledgement.data)? {
-        IbcAckResult::Ok(_) => {
+        IbcAckResult::Ok(data) => {
             let mut response = IbcBasicResponse::new().add_attribute("action", "ibc_packet_ack");
             let voter = match &orig_msg {
                 VxAstroIbcMsg::UpdateUserVotes {
@@ -147,13 +224,121 @@ pub fn ibc_packet_ack(
 
                     voter
                 }
-                VxAstroIbcMsg::UpdateUserVotes { voter, .. }
-                | VxAstroIbcMsg::EmissionsVote { voter, .. } => voter,
-                VxAstroIbcMsg::RegisterProposal { .. } => {
-                    unreachable!("Outpost can't send RegisterProposal ibc msg")
+                VxAstroIbcMsg::UpdateUserVotes { voter, .. } => voter,
+                VxAstroIbcMsg::EmissionsVote {
+                    voter,
+                    voting_power,
+                    votes,
+                    ..
+                } => {
+                    // The receipt belongs to the epoch the vote was sent in
+                    let epoch_start = PENDING_MESSAGES.load(deps.storage, voter)?.epoch_start;
+                    save_vote_receipt(
+                        deps.storage,
+                        &env,
+                        voter,
+                        epoch_start,
+                        votes,
+                        *voting_power,
+                        VoteReceiptStatus::Acknowledged,
+                    )?;
+                    voter
+                }
+                VxAstroIbcMsg::WhitelistPool { user, pool, fee } => {
+                    // Forward the escrowed fee to the Hub fee receiver
+                    let fee_receiver: String = from_json(&data)?;
+                    let config = CONFIG.load(deps.storage)?;
+                    let transfer_msg = IbcMsg::Transfer {
+                        channel_id: config.ics20_channel,
+                        to_address: fee_receiver.clone(),
+                        amount: coin(fee.u128(), &config.astro_denom),
+                        timeout: env.block.time.plus_seconds(IBC_TIMEOUT).into(),
+                    };
+                    response = response
+                        .add_attribute("action", "forward_whitelisting_fee")
+                        .add_attribute("pool", pool)
+                        .add_attribute("fee_receiver", fee_receiver)
+                        .add_message(transfer_msg);
+
+                    user
+                }
+                VxAstroIbcMsg::SubmitProposal { .. } => {
+                    // Submissions aren't bound to the submitter's pending messages
+                    let sequence = msg.original_packet.sequence;
+                    let ProposalDeposit { submitter, deposit } =
+                        PENDING_PROPOSAL_DEPOSITS.load(deps.storage, sequence)?;
+                    PENDING_PROPOSAL_DEPOSITS.remove(deps.storage, sequence);
+                    response = response
+                        .add_attribute("submitter", submitter)
+                        .add_attribute("sequence", sequence.to_string());
+
+                    // ICS20 timeouts are checked against the Hub time,
+                    // thus the transfer can't arrive after the deadline set by the Hub.
+                    // Otherwise, the Hub tells the outpost to return the deposit
+                    let deadline: u64 = from_json(&data)?;
+                    if env.block.time.seconds() >= deadline {
+                        return Ok(response.add_attribute("action", "deposit_transfer_expired"));
+                    }
+
+                    let config = CONFIG.load(deps.storage)?;
+                    let timeout = env
+                        .block
+                        .time
+                        .plus_seconds(IBC_TIMEOUT)
+                        .seconds()
+                        .min(deadline);
+                    let fund_msg = ExecuteMsg::Custom(HubMsg::FundOutpostProposal {
+                        channel: msg.original_packet.dest.channel_id.clone(),
+                        sequence,
+                    });
+                    let memo = serde_json::json!({
+                        "wasm": {
+                            "contract": config.hub_emissions_controller,
+                            "msg": fund_msg,
+                        }
+                    })
+                    .to_string();
+                    let transfer_msg = MsgTransfer {
+                        source_port: "transfer".to_string(),
+                        source_channel: config.ics20_channel,
+                        token: Some(ProtoCoin {
+                            denom: deposit.denom,
+                            amount: deposit.amount.to_string(),
+                        }),
+                        sender: env.contract.address.to_string(),
+                        receiver: config.hub_emissions_controller,
+                        timeout_height: None,
+                        timeout_timestamp: Timestamp::from_seconds(timeout).nanos(),
+                        memo,
+                    };
+
+                    return Ok(response
+                        .add_attribute("action", "forward_proposal_deposit")
+                        .add_message(transfer_msg));
+                }
+                VxAstroIbcMsg::ProposalTallyRequest { proposal_id } => {
+                    // Tally requests aren't bound to any user
+                    let tally: ProposalTally = from_json(&data)?;
+                    PROPOSAL_TALLIES.save(deps.storage, *proposal_id, &tally)?;
+                    PENDING_TALLY_REQUESTS.remove(deps.storage, *proposal_id);
+
+                    return Ok(response
+                        .add_attribute("action", "update_proposal_tally")
+                        .add_attribute("proposal_id", proposal_id.to_string()));
                 }
+                VxAstroIbcMsg::RegisterProposal { .. }
+                | VxAstroIbcMsg::ExecuteMsgs { .. }
+                | VxAstroIbcMsg::ProposalStatusUpdate { .. }
+                | VxAstroIbcMsg::RefundProposalDeposit { .. } => {
+                    unreachable!("Outpost can't send Hub ibc msgs")
+                }
+                // Commitments can be replaced until the commit phase ends
+                VxAstroIbcMsg::GovernanceVoteCommit { voter, .. } => voter,
                 VxAstroIbcMsg::GovernanceVote {
                     voter, proposal_id, ..
+                }
+                | VxAstroIbcMsg::GovernanceVoteReveal {
+                    voter, proposal_id, ..
                 } => {
                     // Mark voter as voted on this proposal
                     PROPOSAL_VOTERS.save(deps.storage, (*proposal_id, voter.clone()), &())?;
@@ -165,20 +350,28 @@ pub fn ibc_packet_ack(
 
             Ok(response)
         }
-        IbcAckResult::Error(err) => process_ibc_error(deps.storage, orig_msg, err),
+        IbcAckResult::Error(err) => process_ibc_error(
+            deps.storage,
+            &env,
+            orig_msg,
+            msg.original_packet.sequence,
+            AckError::parse(&err),
+        ),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn ibc_packet_timeout(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     msg: IbcPacketTimeoutMsg,
 ) -> StdResult<IbcBasicResponse> {
     process_ibc_error(
         deps.storage,
-        from_json(msg.packet.data)?,
-        "IBC packet timeout".to_string(),
+        &env,
+        from_json(&msg.packet.data)?,
+        msg.packet.sequence,
+        AckError::new(AckErrorCode::Timeout, "IBC packet timeout"),
     )
 }
 
@@ -192,15 +385,24 @@ pub fn ibc_channel_close(
     unimplemented!()
 }
 
+/// Records the failed IBC operation in the user's failure inbox and reverts its side effects.
+/// `sequence` is the sequence of the failed packet.
 pub fn process_ibc_error(
     storage: &mut dyn Storage,
+    env: &Env,
     msg: VxAstroIbcMsg,
-    err: String,
+    sequence: u64,
+    err: AckError,
 ) -> StdResult<IbcBasicResponse> {
-    let mut response = IbcBasicResponse::default().add_attribute("action", "process_ibc_error");
-    let voter = match &msg {
+    let mut response = IbcBasicResponse::default().add_attributes([
+        ("action", "process_ibc_error"),
+        ("error_code", &format!("{:?}", err.code)),
+        ("retryable", &err.code.is_retryable().to_string()),
+    ]);
+    let (voter, kind, voting_power) = match &msg {
         VxAstroIbcMsg::UpdateUserVotes {
             voter,
+            voting_power,
             is_unlock: true,
             ..
         } => {
@@ -216,20 +418,130 @@ pub fn process_ibc_error(
             response = response
                 .add_attribute("action", "relock_user_vxastro")
                 .add_message(relock_msg);
-            voter.clone()
+            (voter.clone(), FailureKind::Unlock, *voting_power)
         }
-        VxAstroIbcMsg::EmissionsVote { voter, .. }
-        | VxAstroIbcMsg::UpdateUserVotes { voter, .. }
-        | VxAstroIbcMsg::GovernanceVote { voter, .. } => voter.clone(),
-        VxAstroIbcMsg::RegisterProposal { .. } => {
-            unreachable!("Outpost can't send RegisterProposal ibc msg")
+        VxAstroIbcMsg::EmissionsVote {
+            voter,
+            voting_power,
+            votes,
+            ..
+        } => {
+            let epoch_start = PENDING_MESSAGES.load(storage, voter)?.epoch_start;
+            save_vote_receipt(
+                storage,
+                env,
+                voter,
+                epoch_start,
+                votes,
+                *voting_power,
+                VoteReceiptStatus::Failed {
+                    code: err.code.clone(),
+                },
+            )?;
+            (voter.clone(), FailureKind::EmissionsVote, *voting_power)
+        }
+        VxAstroIbcMsg::UpdateUserVotes {
+            voter,
+            voting_power,
+            ..
+        } => (voter.clone(), FailureKind::UpdateVotes, *voting_power),
+        VxAstroIbcMsg::GovernanceVote {
+            voter,
+            voting_power,
+            ..
+        }
+        | VxAstroIbcMsg::GovernanceVoteCommit {
+            voter,
+            voting_power,
+            ..
+        } => (voter.clone(), FailureKind::GovernanceVote, *voting_power),
+        VxAstroIbcMsg::GovernanceVoteReveal { voter, .. } => {
+            (voter.clone(), FailureKind::GovernanceVote, Uint128::zero())
+        }
+        VxAstroIbcMsg::WhitelistPool { user, fee, .. } => {
+            // Refund the escrowed whitelisting fee
+            let config = CONFIG.load(storage)?;
+            response = response
+                .add_attribute("action", "refund_whitelisting_fee")
+                .add_message(BankMsg::Send {
+                    to_address: user.clone(),
+                    amount: coins(fee.u128(), config.astro_denom),
+                });
+            (user.clone(), FailureKind::WhitelistPool, Uint128::zero())
+        }
+        VxAstroIbcMsg::SubmitProposal { submitter, .. } => {
+            // Refund the escrowed proposal deposit
+            let ProposalDeposit { deposit, .. } =
+                PENDING_PROPOSAL_DEPOSITS.load(storage, sequence)?;
+            PENDING_PROPOSAL_DEPOSITS.remove(storage, sequence);
+            response = response
+                .add_attribute("action", "refund_proposal_deposit")
+                .add_message(BankMsg::Send {
+                    to_address: submitter.clone(),
+                    amount: vec![deposit],
+                });
+            (
+                submitter.clone(),
+                FailureKind::SubmitProposal,
+                Uint128::zero(),
+            )
+        }
+        VxAstroIbcMsg::ProposalTallyRequest { proposal_id } => {
+            // Failed tally requests aren't recorded. Anyone can request the tally again
+            PENDING_TALLY_REQUESTS.remove(storage, *proposal_id);
+            return Ok(response.add_attribute("proposal_id", proposal_id.to_string()));
+        }
+        VxAstroIbcMsg::RegisterProposal { .. }
+        | VxAstroIbcMsg::ExecuteMsgs { .. }
+        | VxAstroIbcMsg::ProposalStatusUpdate { .. }
+        | VxAstroIbcMsg::RefundProposalDeposit { .. } => {
+            unreachable!("Outpost can't send Hub ibc msgs")
         }
     };
 
-    USER_IBC_ERROR.save(storage, &voter, &UserIbcError { msg, err })?;
-    PENDING_MESSAGES.remove(storage, &voter);
+    let failure_id = FAILURES_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    FAILURES_COUNT.save(storage, &failure_id)?;
+    USER_FAILURES.save(
+        storage,
+        (voter.as_str(), failure_id),
+        &IbcFailure {
+            id: failure_id,
+            kind,
+            voting_power,
+            timestamp: env.block.time.seconds(),
+            code: err.code.clone(),
+            reason: err.message.clone(),
+            msg: msg.clone(),
+        },
+    )?;
+
+    // Drop the oldest failures once the inbox is full
+    let failure_ids: Vec<_> = USER_FAILURES
+        .prefix(&voter)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for id in failure_ids
+        .iter()
+        .take(failure_ids.len().saturating_sub(MAX_USER_FAILURES))
+    {
+        USER_FAILURES.remove(storage, (voter.as_str(), *id));
+    }
 
-    Ok(response)
+    // Proposal submissions don't block other IBC messages of the submitter
+    if !matches!(msg, VxAstroIbcMsg::SubmitProposal { .. }) {
+        PENDING_MESSAGES.remove(storage, &voter);
+    }
+    USER_IBC_ERROR.save(
+        storage,
+        &voter,
+        &UserIbcError {
+            msg,
+            err: err.message,
+            code: err.code,
+        },
+    )?;
+
+    Ok(response.add_attribute("failure_id", failure_id.to_string()))
 }
 
 #[cfg(test)]
@@ -9,12 +9,14 @@ use cosmwasm_std::{
 use cw2::set_contract_version;
 use cw_utils::parse_instantiate_response_data;
 
+use astroport_governance::emissions_controller::msg::ack_fail;
 use astroport_governance::emissions_controller::outpost::Config;
 use astroport_governance::emissions_controller::utils::query_incentives_addr;
 use astroport_governance::voting_escrow;
 
 use crate::error::ContractError;
-use crate::state::CONFIG;
+use crate::state::{CONFIG, PENDING_PROPOSAL_DEPOSITS, PENDING_SUBMISSION};
+use crate::utils::parse_ibc_send_sequence;
 
 /// Contract name that is used for migration.
 pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
@@ -22,6 +24,10 @@ pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 /// ID for the vxastro contract instantiate reply
 pub const INSTANTIATE_VXASTRO_REPLY_ID: u64 = 1;
+/// ID for the reply of messages relayed from the Hub governance
+pub const EXECUTE_HUB_MSGS_REPLY_ID: u64 = 2;
+/// ID for the reply of the SubmitProposal packet sent to the Hub
+pub const SUBMIT_PROPOSAL_REPLY_ID: u64 = 3;
 
 /// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -97,6 +103,30 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 
             Ok(Response::new().add_attribute("vxastro", vxastro_contract))
         }
+        // Overwrite the IBC acknowledgement so the Hub learns about the failure
+        Reply {
+            id: EXECUTE_HUB_MSGS_REPLY_ID,
+            result: SubMsgResult::Err(err),
+        } => Ok(Response::new()
+            .add_attribute("action", "execute_hub_msgs_failed")
+            .set_data(ack_fail(err))),
+        // Escrow the deposit under the packet sequence the Hub acknowledgement refers to
+        Reply {
+            id: SUBMIT_PROPOSAL_REPLY_ID,
+            result:
+                SubMsgResult::Ok(SubMsgResponse {
+                    data: Some(data), ..
+                }),
+        } => {
+            let sequence = parse_ibc_send_sequence(&data)?;
+            let deposit = PENDING_SUBMISSION.load(deps.storage)?;
+            PENDING_SUBMISSION.remove(deps.storage);
+            PENDING_PROPOSAL_DEPOSITS.save(deps.storage, sequence, &deposit)?;
+
+            Ok(Response::new()
+                .add_attribute("action", "escrow_proposal_deposit")
+                .add_attribute("sequence", sequence.to_string()))
+        }
         _ => Err(ContractError::FailedToParseReply {}),
     }
 }
@@ -1,25 +1,44 @@
 #![cfg(not(tarpaulin_include))]
 
-use cosmwasm_std::{DepsMut, Empty, Env, Response};
+use cosmwasm_std::{DepsMut, Empty, Env, Order, Response, StdResult};
 use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Map;
+
+use astroport_governance::emissions_controller::msg::VxAstroIbcMsg;
+use astroport_governance::emissions_controller::outpost::PendingMessage;
 
 use crate::error::ContractError;
 use crate::instantiate::{CONTRACT_NAME, CONTRACT_VERSION};
+use crate::state::{CONFIG, PENDING_MESSAGES};
+use crate::utils::get_epoch_start;
 
 #[allow(dead_code)]
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, env: Env, _msg: Empty) -> Result<Response, ContractError> {
     let contract_version = get_contract_version(deps.storage)?;
 
     match contract_version.contract.as_ref() {
         CONTRACT_NAME => match contract_version.version.as_ref() {
-            "1.0.0" => Ok(()),
-            "1.1.0" => Ok(()),
+            "1.0.0" | "1.1.0" | "1.2.0" => Ok(()),
             _ => Err(ContractError::MigrationError {}),
         },
         _ => Err(ContractError::MigrationError {}),
     }?;
 
+    // Persist new config fields with their default values
+    let config = CONFIG.load(deps.storage)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    // Messages sent before the upgrade are attributed to the current epoch
+    let old_pending: Map<&str, VxAstroIbcMsg> = Map::new("pending_messages");
+    let pending = old_pending
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let epoch_start = get_epoch_start(env.block.time.seconds());
+    for (user, msg) in pending {
+        PENDING_MESSAGES.save(deps.storage, &user, &PendingMessage { msg, epoch_start })?;
+    }
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::new()
@@ -6,20 +6,25 @@ use cw_storage_plus::Bound;
 use itertools::Itertools;
 
 use astroport_governance::emissions_controller::outpost::{
-    QueryMsg, RegisteredProposal, UserIbcStatus,
+    QueryMsg, RegisteredProposal, UserIbcStatus, VoteReceiptStatus,
 };
 
 use crate::state::{
-    CONFIG, PENDING_MESSAGES, PROPOSAL_VOTERS, REGISTERED_PROPOSALS, USER_IBC_ERROR,
+    CONFIG, LAST_EMISSIONS, PENDING_MESSAGES, PENDING_PROPOSAL_DEPOSITS, PROPOSAL_STATUSES,
+    PROPOSAL_TALLIES, PROPOSAL_VOTERS, REGISTERED_PROPOSALS, USER_FAILURES, USER_IBC_ERROR,
+    VOTE_RECEIPTS, VOTING_POWER_CACHE,
 };
+use crate::utils::get_epoch_start;
 
 /// Expose available contract queries.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
         QueryMsg::QueryUserIbcStatus { user } => to_json_binary(&UserIbcStatus {
-            pending_msg: PENDING_MESSAGES.may_load(deps.storage, &user)?,
+            pending_msg: PENDING_MESSAGES
+                .may_load(deps.storage, &user)?
+                .map(|pending| pending.msg),
             error: USER_IBC_ERROR.may_load(deps.storage, &user)?,
         }),
         QueryMsg::QueryRegisteredProposals { limit, start_after } => REGISTERED_PROPOSALS
@@ -51,5 +56,46 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 let voters = voters.into_iter().map(|(voter, _)| voter).collect_vec();
                 to_json_binary(&voters)
             }),
+        QueryMsg::UserFailures {
+            user,
+            limit,
+            start_after,
+        } => USER_FAILURES
+            .prefix(&user)
+            .range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit.unwrap_or(MAX_PAGE_LIMIT) as usize)
+            .map(|item| item.map(|(_, failure)| failure))
+            .collect::<StdResult<Vec<_>>>()
+            .and_then(|failures| to_json_binary(&failures)),
+        QueryMsg::CachedVotingPower { proposal_id, user } => {
+            to_json_binary(&VOTING_POWER_CACHE.may_load(deps.storage, (proposal_id, &user))?)
+        }
+        QueryMsg::PendingEmissions {} => to_json_binary(&LAST_EMISSIONS.may_load(deps.storage)?),
+        QueryMsg::ProposalStatus { proposal_id } => {
+            to_json_binary(&PROPOSAL_STATUSES.may_load(deps.storage, proposal_id)?)
+        }
+        QueryMsg::ProposalTally { proposal_id } => {
+            to_json_binary(&PROPOSAL_TALLIES.may_load(deps.storage, proposal_id)?)
+        }
+        QueryMsg::VoteReceipt { user, epoch_start } => {
+            let epoch_start =
+                epoch_start.unwrap_or_else(|| get_epoch_start(env.block.time.seconds()));
+            to_json_binary(&VOTE_RECEIPTS.may_load(deps.storage, (&user, epoch_start))?)
+        }
+        QueryMsg::VerifyVoteReceipt { receipt } => {
+            let is_valid = receipt.status == VoteReceiptStatus::Acknowledged
+                && VOTE_RECEIPTS
+                    .may_load(deps.storage, (&receipt.user, receipt.epoch_start))?
+                    .is_some_and(|stored| stored == receipt);
+            to_json_binary(&is_valid)
+        }
+        QueryMsg::PendingProposalDeposit { sequence } => {
+            to_json_binary(&PENDING_PROPOSAL_DEPOSITS.may_load(deps.storage, sequence)?)
+        }
     }
 }
@@ -1,8 +1,11 @@
 use astroport::common::OwnershipProposal;
 use cw_storage_plus::{Item, Map};
 
-use astroport_governance::emissions_controller::msg::VxAstroIbcMsg;
-use astroport_governance::emissions_controller::outpost::{Config, UserIbcError};
+use astroport_governance::assembly::ProposalStatus;
+use astroport_governance::emissions_controller::outpost::{
+    CachedVotingPower, Config, IbcFailure, PendingEmissions, PendingMessage, ProposalDeposit,
+    ProposalTally, UserIbcError, VoteReceipt,
+};
 
 /// Stores config at the given key.
 pub const CONFIG: Item<Config> = Item::new("config");
@@ -11,12 +14,37 @@ pub const CONFIG: Item<Config> = Item::new("config");
 pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
 /// Stores the latest IBC error and message.
 pub const USER_IBC_ERROR: Map<&str, UserIbcError> = Map::new("user_ibc_error");
+/// Failure inbox. Map (user, failure id) -> failed IBC operation.
+pub const USER_FAILURES: Map<(&str, u64), IbcFailure> = Map::new("user_failures");
+/// Total number of recorded failures. Used to generate failure ids.
+pub const FAILURES_COUNT: Item<u64> = Item::new("failures_count");
 /// Keeps the list of users with pending IBC requests.
 /// The contract blocks any new IBC messages for these users
 /// until the previous one is acknowledged, failed or timed out.
-pub const PENDING_MESSAGES: Map<&str, VxAstroIbcMsg> = Map::new("pending_messages");
+pub const PENDING_MESSAGES: Map<&str, PendingMessage> = Map::new("pending_messages");
 /// Map of registered proposals (proposal id -> start time).
 /// Users are allowed to vote only on registered proposals.
 pub const REGISTERED_PROPOSALS: Map<u64, u64> = Map::new("registered_proposals");
 /// Contains all the voters per proposal. Map proposal id -> voter address.
 pub const PROPOSAL_VOTERS: Map<(u64, String), ()> = Map::new("proposal_votes");
+/// Voting power recorded on the first vote for a proposal.
+/// Map (proposal id, voter) -> cached voting power.
+pub const VOTING_POWER_CACHE: Map<(u64, &str), CachedVotingPower> = Map::new("voting_power_cache");
+/// Stores the last emissions schedule received from the Hub.
+pub const LAST_EMISSIONS: Item<PendingEmissions> = Item::new("last_emissions");
+/// The latest proposal statuses received from the Hub (proposal id -> status).
+pub const PROPOSAL_STATUSES: Map<u64, ProposalStatus> = Map::new("proposal_statuses");
+/// The latest proposal tallies received from the Hub (proposal id -> tally).
+pub const PROPOSAL_TALLIES: Map<u64, ProposalTally> = Map::new("proposal_tallies");
+/// Proposals with a tally request in flight.
+/// New requests for these proposals are blocked until the previous one is resolved.
+pub const PENDING_TALLY_REQUESTS: Map<u64, ()> = Map::new("pending_tally_requests");
+/// Emissions vote receipts. Map (user, epoch start) -> receipt.
+pub const VOTE_RECEIPTS: Map<(&str, u64), VoteReceipt> = Map::new("vote_receipts");
+/// Deposits of proposal submissions awaiting the Hub acknowledgement.
+/// Map SubmitProposal packet sequence -> deposit.
+pub const PENDING_PROPOSAL_DEPOSITS: Map<u64, ProposalDeposit> =
+    Map::new("pending_proposal_deposits");
+/// Deposit of the submission being sent to the Hub.
+/// Used to pass the deposit to the reply which reads the packet sequence.
+pub const PENDING_SUBMISSION: Item<ProposalDeposit> = Item::new("pending_submission");
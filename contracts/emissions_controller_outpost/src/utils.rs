@@ -1,10 +1,18 @@
-use cosmwasm_std::{to_json_binary, Env, IbcMsg, Storage};
+use std::collections::HashMap;
 
-use astroport_governance::emissions_controller::consts::IBC_TIMEOUT;
+use cosmwasm_std::{
+    ensure, to_json_binary, Binary, Decimal, Env, IbcMsg, StdResult, Storage, Uint128,
+};
+use itertools::Itertools;
+
+use astroport_governance::emissions_controller::consts::{EPOCHS_START, EPOCH_LENGTH, IBC_TIMEOUT};
 use astroport_governance::emissions_controller::msg::VxAstroIbcMsg;
+use astroport_governance::emissions_controller::outpost::{
+    PendingMessage, VoteReceipt, VoteReceiptStatus,
+};
 
 use crate::error::ContractError;
-use crate::state::PENDING_MESSAGES;
+use crate::state::{PENDING_MESSAGES, VOTE_RECEIPTS};
 
 /// Ensure voter has no pending IBC requests and prepare an IBC packet.
 pub fn prepare_ibc_packet(
@@ -18,7 +26,10 @@ pub fn prepare_ibc_packet(
     // until the previous one is acknowledged, failed or timed out.
     PENDING_MESSAGES.update(storage, voter, |v| match v {
         Some(_) => Err(ContractError::PendingUser(voter.to_string())),
-        None => Ok(payload.clone()),
+        None => Ok(PendingMessage {
+            msg: payload.clone(),
+            epoch_start: get_epoch_start(env.block.time.seconds()),
+        }),
     })?;
 
     Ok(IbcMsg::SendPacket {
@@ -27,3 +38,73 @@ pub fn prepare_ibc_packet(
         timeout: env.block.time.plus_seconds(IBC_TIMEOUT).into(),
     })
 }
+
+/// Reads the packet sequence from the `MsgIBCSendResponse` returned by wasmd for a sent IBC packet.
+/// The response contains a single varint field: `sequence = 1`.
+pub fn parse_ibc_send_sequence(data: &[u8]) -> Result<u64, ContractError> {
+    let (tag, varint) = data
+        .split_first()
+        .ok_or(ContractError::FailedToParseReply {})?;
+    ensure!(*tag == 0x08, ContractError::FailedToParseReply {});
+
+    let mut sequence = 0u64;
+    for (i, byte) in varint.iter().enumerate().take(10) {
+        sequence |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok(sequence);
+        }
+    }
+
+    Err(ContractError::FailedToParseReply {})
+}
+
+/// Normalize current timestamp to the beginning of the current epoch (Monday).
+pub fn get_epoch_start(timestamp: u64) -> u64 {
+    let rem = timestamp % EPOCHS_START;
+    if rem % EPOCH_LENGTH == 0 {
+        // Hit at the beginning of the current epoch
+        timestamp
+    } else {
+        // Hit somewhere in the middle
+        EPOCHS_START + rem / EPOCH_LENGTH * EPOCH_LENGTH
+    }
+}
+
+/// Records the outcome of an emissions vote packet in the voter's receipt for the epoch
+/// the vote was sent in. Failed votes never overwrite an acknowledged receipt.
+pub fn save_vote_receipt(
+    storage: &mut dyn Storage,
+    env: &Env,
+    voter: &str,
+    epoch_start: u64,
+    votes: &HashMap<String, Decimal>,
+    voting_power: Uint128,
+    status: VoteReceiptStatus,
+) -> StdResult<()> {
+    let key = (voter, epoch_start);
+
+    if status != VoteReceiptStatus::Acknowledged
+        && VOTE_RECEIPTS
+            .may_load(storage, key)?
+            .is_some_and(|receipt| receipt.status == VoteReceiptStatus::Acknowledged)
+    {
+        return Ok(());
+    }
+
+    let mut receipt = VoteReceipt {
+        user: voter.to_string(),
+        epoch_start,
+        votes: votes
+            .iter()
+            .map(|(pool, weight)| (pool.clone(), *weight))
+            .sorted()
+            .collect(),
+        voting_power,
+        status,
+        timestamp: env.block.time.seconds(),
+        digest: Binary::default(),
+    };
+    receipt.digest = receipt.compute_digest(&env.contract.address)?;
+
+    VOTE_RECEIPTS.save(storage, key, &receipt)
+}
@@ -29,11 +29,14 @@ pub fn pair_contract() -> Box<dyn Contract<Empty>> {
 }
 
 pub fn vxastro_contract() -> Box<dyn Contract<Empty>> {
-    Box::new(ContractWrapper::new_with_empty(
-        astroport_voting_escrow::contract::execute,
-        astroport_voting_escrow::contract::instantiate,
-        astroport_voting_escrow::contract::query,
-    ))
+    Box::new(
+        ContractWrapper::new_with_empty(
+            astroport_voting_escrow::contract::execute,
+            astroport_voting_escrow::contract::instantiate,
+            astroport_voting_escrow::contract::query,
+        )
+        .with_reply_empty(astroport_voting_escrow::contract::reply),
+    )
 }
 
 pub fn incentives_contract() -> Box<dyn Contract<Empty>> {
@@ -63,20 +66,23 @@ pub enum TestSudoMsg {
 }
 
 fn sudo(deps: DepsMut, env: Env, msg: TestSudoMsg) -> StdResult<Response> {
+    let into_response = |ibc_response: IbcBasicResponse| {
+        Response::default()
+            .add_attributes(ibc_response.attributes)
+            .add_submessages(ibc_response.messages)
+    };
+
     match msg {
-        TestSudoMsg::Ack(packet) => ibc_packet_ack(deps, env, packet),
-        TestSudoMsg::Timeout(packet) => ibc_packet_timeout(deps, env, packet),
+        TestSudoMsg::Ack(packet) => ibc_packet_ack(deps, env, packet).map(into_response),
+        TestSudoMsg::Timeout(packet) => ibc_packet_timeout(deps, env, packet).map(into_response),
+        // Acknowledgement is returned as response data
         TestSudoMsg::IbcRecv(packet) => do_packet_receive(deps, env, packet).map(|ibc_response| {
-            IbcBasicResponse::default()
+            Response::default()
                 .add_attributes(ibc_response.attributes)
                 .add_submessages(ibc_response.messages)
+                .set_data(ibc_response.acknowledgement)
         }),
     }
-    .map(|ibc_response| {
-        Response::default()
-            .add_attributes(ibc_response.attributes)
-            .add_submessages(ibc_response.messages)
-    })
 }
 
 pub fn emissions_controller() -> Box<dyn Contract<Empty>> {
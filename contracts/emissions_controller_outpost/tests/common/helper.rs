@@ -19,7 +19,7 @@ use astroport_governance::assembly::ProposalVoteOption;
 use astroport_governance::emissions_controller::consts::{EPOCHS_START, EPOCH_LENGTH};
 use astroport_governance::emissions_controller::msg::{ExecuteMsg, IbcAckResult, VxAstroIbcMsg};
 use astroport_governance::emissions_controller::outpost::{
-    OutpostInstantiateMsg, OutpostMsg, RegisteredProposal,
+    OutpostInstantiateMsg, OutpostMsg, RegisteredProposal, ScheduleChunk,
 };
 use astroport_governance::voting_escrow::{LockInfoResponse, UpdateMarketingInfo};
 use astroport_governance::{emissions_controller, voting_escrow};
@@ -272,6 +272,7 @@ impl ControllerHelper {
             self.emission_controller.clone(),
             &ExecuteMsg::<Empty>::Vote {
                 votes: votes.to_vec(),
+                on_behalf_of: None,
             },
             &[],
         )
@@ -282,6 +283,16 @@ impl ControllerHelper {
         sender: &Addr,
         schedules: &[(&str, InputSchedule)],
         funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.set_emissions_chunk(sender, schedules, funds, None)
+    }
+
+    pub fn set_emissions_chunk(
+        &mut self,
+        sender: &Addr,
+        schedules: &[(&str, InputSchedule)],
+        funds: &[Coin],
+        chunk: Option<ScheduleChunk>,
     ) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             sender.clone(),
@@ -292,6 +303,7 @@ impl ControllerHelper {
                         .iter()
                         .map(|(pool, schedule)| (pool.to_string(), schedule.clone()))
                         .collect(),
+                    chunk,
                 },
             ),
             funds,
@@ -326,6 +338,21 @@ impl ControllerHelper {
         )
     }
 
+    pub fn query_vxastro_config(&self) -> StdResult<voting_escrow::Config> {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.vxastro, &voting_escrow::QueryMsg::Config {})
+    }
+
+    pub fn query_pending_emissions(
+        &self,
+    ) -> StdResult<Option<emissions_controller::outpost::PendingEmissions>> {
+        self.app.wrap().query_wasm_smart(
+            &self.emission_controller,
+            &emissions_controller::outpost::QueryMsg::PendingEmissions {},
+        )
+    }
+
     pub fn timetravel(&mut self, time: u64) {
         self.app.update_block(|block| {
             block.time = block.time.plus_seconds(time);
@@ -384,6 +411,55 @@ impl ControllerHelper {
         )
     }
 
+    pub fn query_vote_receipt(
+        &self,
+        user: &Addr,
+        epoch_start: Option<u64>,
+    ) -> StdResult<Option<emissions_controller::outpost::VoteReceipt>> {
+        self.app.wrap().query_wasm_smart(
+            &self.emission_controller,
+            &emissions_controller::outpost::QueryMsg::VoteReceipt {
+                user: user.to_string(),
+                epoch_start,
+            },
+        )
+    }
+
+    pub fn verify_vote_receipt(
+        &self,
+        receipt: &emissions_controller::outpost::VoteReceipt,
+    ) -> StdResult<bool> {
+        self.app.wrap().query_wasm_smart(
+            &self.emission_controller,
+            &emissions_controller::outpost::QueryMsg::VerifyVoteReceipt {
+                receipt: receipt.clone(),
+            },
+        )
+    }
+
+    pub fn query_user_failures(
+        &self,
+        user: &Addr,
+    ) -> StdResult<Vec<emissions_controller::outpost::IbcFailure>> {
+        self.app.wrap().query_wasm_smart(
+            &self.emission_controller,
+            &emissions_controller::outpost::QueryMsg::UserFailures {
+                user: user.to_string(),
+                limit: None,
+                start_after: None,
+            },
+        )
+    }
+
+    pub fn execute_outpost_msg(&mut self, user: &Addr, msg: OutpostMsg) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            user.clone(),
+            self.emission_controller.clone(),
+            &ExecuteMsg::Custom(msg),
+            &[],
+        )
+    }
+
     pub fn set_voting_channel(&mut self) {
         self.update_config(
             &self.owner.clone(),
@@ -404,6 +480,23 @@ impl ControllerHelper {
         } else {
             IbcAckResult::Ok(b"null".into())
         };
+        self.mock_ibc_ack_result(ibc_msg, ack_result)
+    }
+
+    pub fn mock_ibc_ack_result(
+        &mut self,
+        ibc_msg: VxAstroIbcMsg,
+        ack_result: IbcAckResult,
+    ) -> AnyResult<AppResponse> {
+        self.mock_ibc_ack_seq(ibc_msg, 0, ack_result)
+    }
+
+    pub fn mock_ibc_ack_seq(
+        &mut self,
+        ibc_msg: VxAstroIbcMsg,
+        sequence: u64,
+        ack_result: IbcAckResult,
+    ) -> AnyResult<AppResponse> {
         let packet = IbcPacketAckMsg::new(
             IbcAcknowledgement::encode_json(&ack_result).unwrap(),
             IbcPacket::new(
@@ -416,7 +509,7 @@ impl ControllerHelper {
                     port_id: "".to_string(),
                     channel_id: "".to_string(),
                 },
-                0,
+                sequence,
                 Timestamp::from_seconds(0).into(),
             ),
             Addr::unchecked("relayer"),
@@ -426,6 +519,14 @@ impl ControllerHelper {
     }
 
     pub fn mock_ibc_timeout(&mut self, ibc_msg: VxAstroIbcMsg) -> AnyResult<AppResponse> {
+        self.mock_ibc_timeout_seq(ibc_msg, 0)
+    }
+
+    pub fn mock_ibc_timeout_seq(
+        &mut self,
+        ibc_msg: VxAstroIbcMsg,
+        sequence: u64,
+    ) -> AnyResult<AppResponse> {
         let packet = IbcPacketTimeoutMsg::new(
             IbcPacket::new(
                 to_json_binary(&ibc_msg).unwrap(),
@@ -437,7 +538,7 @@ impl ControllerHelper {
                     port_id: "".to_string(),
                     channel_id: "".to_string(),
                 },
-                0,
+                sequence,
                 Timestamp::from_seconds(0).into(),
             ),
             Addr::unchecked("relayer"),
@@ -56,6 +56,20 @@ where
     }
 }
 
+const PACKET_SEQUENCE_KEY: &[u8] = b"mock_ibc_packet_sequence";
+
+/// Encodes `MsgIBCSendResponse { sequence }` protobuf message
+fn encode_ibc_send_response(mut sequence: u64) -> Binary {
+    let mut data = vec![0x08];
+    while sequence >= 0x80 {
+        data.push((sequence as u8 & 0x7f) | 0x80);
+        sequence >>= 7;
+    }
+    data.push(sequence as u8);
+
+    data.into()
+}
+
 pub struct IbcMockModule;
 
 impl Ibc for IbcMockModule {}
@@ -82,9 +96,20 @@ impl Module for IbcMockModule {
             IbcMsg::SendPacket { channel_id, .. } => {
                 let querier = RouterQuerier::new(router, api, storage, block);
                 let querier = QuerierWrapper::new(&querier);
-                check_contract_supports_channel(querier, &sender, &channel_id)
-                    .map(|_| AppResponse::default())
-                    .map_err(Into::into)
+                check_contract_supports_channel(querier, &sender, &channel_id)?;
+
+                // Return MsgIBCSendResponse with the packet sequence same as wasmd does
+                let sequence = storage
+                    .get(PACKET_SEQUENCE_KEY)
+                    .map(|v| from_json::<u64>(v).unwrap())
+                    .unwrap_or_default()
+                    + 1;
+                storage.set(PACKET_SEQUENCE_KEY, &to_json_binary(&sequence)?);
+
+                Ok(AppResponse {
+                    events: vec![],
+                    data: Some(encode_ibc_send_response(sequence)),
+                })
             }
             IbcMsg::Transfer {
                 channel_id,
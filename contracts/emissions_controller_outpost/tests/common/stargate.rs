@@ -1,13 +1,14 @@
 use anyhow::anyhow;
 use cosmwasm_schema::serde::de::DeserializeOwned;
 use cosmwasm_std::{
-    coin, Addr, Api, BankMsg, Binary, BlockInfo, CustomMsg, CustomQuery, Empty, Querier, Storage,
-    SubMsgResponse,
+    attr, coin, Addr, Api, BankMsg, Binary, BlockInfo, CustomMsg, CustomQuery, Empty, Event,
+    Querier, Storage, SubMsgResponse,
 };
 use cw_multi_test::error::AnyResult;
 use cw_multi_test::{
     AppResponse, BankSudo, CosmosRouter, Module, Stargate, StargateMsg, StargateQuery,
 };
+use osmosis_std::types::ibc::applications::transfer::v1::MsgTransfer;
 use osmosis_std::types::osmosis::tokenfactory::v1beta1::{
     MsgBurn, MsgCreateDenom, MsgCreateDenomResponse, MsgMint, MsgSetBeforeSendHook,
     MsgSetDenomMetadata,
@@ -84,6 +85,33 @@ impl Module for StargateModule {
                     burn_msg.into(),
                 )
             }
+            MsgTransfer::TYPE_URL => {
+                // Very simplified IBC transfer processing given cosmwasm-multitest constraints
+                let transfer_msg: MsgTransfer = msg.value.try_into()?;
+                let token = transfer_msg.token.expect("Empty token in MsgTransfer!");
+                let cw_coin = coin(token.amount.parse()?, token.denom);
+                let mut response = router.execute(
+                    api,
+                    storage,
+                    block,
+                    Addr::unchecked(&transfer_msg.sender),
+                    BankMsg::Burn {
+                        amount: vec![cw_coin.clone()],
+                    }
+                    .into(),
+                )?;
+                response.events.push(Event::new("transfer").add_attributes([
+                    attr(
+                        "packet_timeout_timestamp",
+                        transfer_msg.timeout_timestamp.to_string(),
+                    ),
+                    attr("packet_src_channel", transfer_msg.source_channel),
+                    attr("receiver", transfer_msg.receiver),
+                    attr("amount", cw_coin.to_string()),
+                    attr("memo", transfer_msg.memo),
+                ]));
+                Ok(response)
+            }
             MsgSetDenomMetadata::TYPE_URL => Ok(AppResponse::default()),
             MsgSetBeforeSendHook::TYPE_URL => Ok(AppResponse::default()),
             _ => Err(anyhow!(
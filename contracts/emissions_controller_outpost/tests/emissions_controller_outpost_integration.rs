@@ -1,17 +1,27 @@
 use astroport::asset::{Asset, AssetInfo};
 use astroport::incentives::{InputSchedule, RewardType};
-use cosmwasm_std::{attr, coin, coins, Decimal, Decimal256, Empty, Event};
+use cosmwasm_std::{
+    attr, coin, coins, from_json, to_json_binary, to_json_string, Addr, BankMsg, CosmosMsg,
+    Decimal, Decimal256, Empty, Event, StdError, Uint128,
+};
 use cw_multi_test::Executor;
 use cw_utils::PaymentError;
 
 use astroport_emissions_controller_outpost::error::ContractError;
-use astroport_governance::assembly::ProposalVoteOption;
-use astroport_governance::emissions_controller::consts::{EPOCH_LENGTH, IBC_TIMEOUT};
-use astroport_governance::emissions_controller::msg::{ExecuteMsg, VxAstroIbcMsg};
-use astroport_governance::emissions_controller::outpost::{UserIbcError, UserIbcStatus};
+use astroport_governance::assembly::{ProposalStatus, ProposalTemplate, ProposalVoteOption};
+use astroport_governance::emissions_controller::consts::{
+    EPOCH_LENGTH, IBC_TIMEOUT, MAX_USER_FAILURES,
+};
+use astroport_governance::emissions_controller::msg::{
+    ack_ok, AckError, AckErrorCode, ExecuteMsg, IbcAckResult, VxAstroIbcMsg,
+};
+use astroport_governance::emissions_controller::outpost::{
+    CachedVotingPower, ChunksProgress, FailureKind, IbcFailure, OutpostMsg, PoolEmissionsStatus,
+    ProposalDeposit, ProposalTally, ScheduleChunk, UserIbcError, UserIbcStatus, VoteReceipt,
+    VoteReceiptStatus,
+};
 use astroport_governance::voting_escrow::LockInfoResponse;
 use astroport_governance::{emissions_controller, voting_escrow};
-use astroport_voting_escrow::state::UNLOCK_PERIOD;
 
 use crate::common::helper::{get_epoch_start, ControllerHelper};
 
@@ -121,7 +131,37 @@ fn set_emissions_test() {
     let funds = coins(200_001000, &astro);
     helper.mint_tokens(&user, &funds).unwrap();
 
+    // No emissions were received yet
+    assert_eq!(helper.query_pending_emissions().unwrap(), None);
+
     let resp = helper.set_emissions(&user, &schedules, &funds).unwrap();
+
+    let pending_emissions = helper.query_pending_emissions().unwrap().unwrap();
+    assert_eq!(
+        pending_emissions.received_at,
+        helper.app.block_info().time.seconds()
+    );
+    assert_eq!(pending_emissions.received_amount.u128(), 200_001000);
+    assert_eq!(pending_emissions.funded_amount.u128(), 100_000000);
+    assert_eq!(pending_emissions.returned_amount.u128(), 100_001000);
+    let statuses = pending_emissions
+        .pools
+        .iter()
+        .map(|pool| {
+            (
+                pool.pool.as_str(),
+                matches!(pool.status, PoolEmissionsStatus::Funded),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        statuses,
+        [
+            (pool1.as_str(), true),
+            ("random", false),
+            (pool1.as_str(), false)
+        ]
+    );
     // Assert mocked ibc event
     let has_event = resp.has_event(
         &Event::new("transfer").add_attributes([
@@ -162,6 +202,97 @@ fn set_emissions_test() {
     );
 }
 
+#[test]
+fn chunked_set_emissions_test() {
+    let mut helper = ControllerHelper::new();
+    let astro = helper.astro.clone();
+
+    let pool1 = helper.create_pair("token1", "token2");
+    let pool2 = helper.create_pair("token1", "token3");
+    let user = helper.app.api().addr_make("permissionless");
+    helper
+        .mint_tokens(&user, &coins(500_000000, &astro))
+        .unwrap();
+
+    let epoch = get_epoch_start(helper.app.block_info().time.seconds());
+    let chunk = |index: u32, total: u32| ScheduleChunk {
+        epoch,
+        index,
+        total,
+    };
+    let schedule = || InputSchedule {
+        reward: Asset::native(&astro, 100_000000u64),
+        duration_periods: 1,
+    };
+    let schedule1 = [(pool1.as_str(), schedule())];
+    let schedule2 = [(pool2.as_str(), schedule())];
+    let funds = coins(100_000000, &astro);
+
+    let err = helper
+        .set_emissions_chunk(&user, &schedule1, &funds, Some(chunk(2, 2)))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidScheduleChunk { index: 2, total: 2 }
+    );
+
+    helper
+        .set_emissions_chunk(&user, &schedule1, &funds, Some(chunk(0, 2)))
+        .unwrap();
+    let pending_emissions = helper.query_pending_emissions().unwrap().unwrap();
+    let progress = pending_emissions.chunks.unwrap();
+    assert_eq!(
+        progress,
+        ChunksProgress {
+            epoch,
+            total: 2,
+            received: vec![0],
+        }
+    );
+    assert!(!progress.is_complete());
+
+    // Chunks of the same epoch must have the same total
+    let err = helper
+        .set_emissions_chunk(&user, &schedule2, &funds, Some(chunk(1, 3)))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ScheduleChunksMismatch { epoch, total: 2 }
+    );
+
+    helper
+        .set_emissions_chunk(&user, &schedule2, &funds, Some(chunk(1, 2)))
+        .unwrap();
+    let pending_emissions = helper.query_pending_emissions().unwrap().unwrap();
+    assert_eq!(pending_emissions.received_amount.u128(), 200_000000);
+    assert_eq!(pending_emissions.funded_amount.u128(), 200_000000);
+    let pools = pending_emissions
+        .pools
+        .iter()
+        .map(|pool| pool.pool.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(pools, [pool1.as_str(), pool2.as_str()]);
+    assert!(pending_emissions.chunks.unwrap().is_complete());
+
+    // Chunks of older epochs are rejected
+    let err = helper
+        .set_emissions_chunk(
+            &user,
+            &schedule1,
+            &funds,
+            Some(ScheduleChunk {
+                epoch: epoch - EPOCH_LENGTH,
+                index: 0,
+                total: 1,
+            }),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::StaleScheduleChunk(epoch - EPOCH_LENGTH)
+    );
+}
+
 #[test]
 fn permissioned_set_emissions_test() {
     let mut helper = ControllerHelper::new();
@@ -328,6 +459,23 @@ fn test_voting() {
         ContractError::ZeroVotingPower {}
     );
 
+    let err = helper
+        .app
+        .execute_contract(
+            user.clone(),
+            helper.emission_controller.clone(),
+            &ExecuteMsg::<Empty>::Vote {
+                votes: vec![("pool1".to_string(), Decimal::one())],
+                on_behalf_of: Some(helper.app.api().addr_make("another").to_string()),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::VoteManagersNotSupported {}
+    );
+
     let err = helper
         .vote(
             &user,
@@ -404,13 +552,40 @@ fn test_voting() {
     };
     helper.mock_ibc_timeout(mock_packet.clone()).unwrap();
 
+    let ibc_status = helper.query_ibc_status(&user).unwrap();
+    assert_eq!(ibc_status.pending_msg, None);
+    assert_eq!(
+        ibc_status.error,
+        Some(UserIbcError {
+            msg: mock_packet.clone(),
+            err: "IBC packet timeout".to_string(),
+            code: AckErrorCode::Timeout,
+        })
+    );
+
+    // Structured error acknowledgement from the Hub
+    helper
+        .vote(&user, &[("pool1".to_string(), Decimal::one())])
+        .unwrap();
+    let ack_err = to_json_string(&AckError::new(
+        AckErrorCode::VoteCooldown,
+        "Next time you can change your vote is at 1725235200",
+    ))
+    .unwrap();
+    let resp = helper
+        .mock_ibc_ack(mock_packet.clone(), Some(&ack_err))
+        .unwrap();
+    assert!(resp.has_event(
+        &Event::new("wasm").add_attributes([("error_code", "VoteCooldown"), ("retryable", "true")])
+    ));
     let ibc_status = helper.query_ibc_status(&user).unwrap();
     assert_eq!(ibc_status.pending_msg, None);
     assert_eq!(
         ibc_status.error,
         Some(UserIbcError {
             msg: mock_packet,
-            err: "IBC packet timeout".to_string()
+            err: "Next time you can change your vote is at 1725235200".to_string(),
+            code: AckErrorCode::VoteCooldown,
         })
     );
 
@@ -460,7 +635,8 @@ fn test_voting() {
         ibc_status.error,
         Some(UserIbcError {
             msg: mock_packet,
-            err: "error".to_string()
+            err: "error".to_string(),
+            code: AckErrorCode::Unknown,
         })
     );
     let lock_info = helper.lock_info(&user, None).unwrap();
@@ -469,6 +645,7 @@ fn test_voting() {
         LockInfoResponse {
             amount: 1000u128.into(),
             unlock_status: None,
+            permanent: false,
         }
     );
     // Ensure user VP was recovered
@@ -536,7 +713,8 @@ fn test_unlock_and_withdraw() {
         .unwrap();
 
     helper.unlock(&user).unwrap();
-    helper.timetravel(UNLOCK_PERIOD);
+    let unlock_period = helper.query_vxastro_config().unwrap().unlock_period;
+    helper.timetravel(unlock_period);
 
     let err = helper.withdraw(&user).unwrap_err();
     assert_eq!(
@@ -569,6 +747,602 @@ fn test_unlock_and_withdraw() {
     assert_eq!(user_bal, 1000);
 }
 
+#[test]
+fn test_failure_inbox() {
+    let mut helper = ControllerHelper::new();
+    helper.set_voting_channel();
+
+    let user = helper.app.api().addr_make("user");
+    helper.lock(&user, 1000u64.into()).unwrap();
+
+    // Initial voting power update times out
+    let resp = helper.mock_ibc_timeout(update_packet_for(&user)).unwrap();
+    assert!(resp.has_event(&Event::new("wasm").add_attributes([("failure_id", "1")])));
+
+    let now = helper.app.block_info().time.seconds();
+    assert_eq!(
+        helper.query_user_failures(&user).unwrap(),
+        vec![IbcFailure {
+            id: 1,
+            kind: FailureKind::UpdateVotes,
+            voting_power: 1000u128.into(),
+            timestamp: now,
+            code: AckErrorCode::Timeout,
+            reason: "IBC packet timeout".to_string(),
+            msg: update_packet_for(&user),
+        }]
+    );
+
+    // Failure can't be retried with an endpoint for another failure type
+    let err = helper
+        .execute_outpost_msg(&user, OutpostMsg::RetryVote { failure_id: 1 })
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidFailureKind {
+            failure_id: 1,
+            kind: FailureKind::UpdateVotes,
+        }
+    );
+
+    // Other users can't touch the failure
+    let random = helper.app.api().addr_make("random");
+    let err = helper
+        .execute_outpost_msg(&random, OutpostMsg::RetryRefreshVotes { failure_id: 1 })
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::FailureNotFound(1)
+    );
+
+    helper
+        .execute_outpost_msg(&user, OutpostMsg::RetryRefreshVotes { failure_id: 1 })
+        .unwrap();
+    assert_eq!(helper.query_user_failures(&user).unwrap(), vec![]);
+    assert_eq!(
+        helper.query_ibc_status(&user).unwrap().pending_msg,
+        Some(update_packet_for(&user))
+    );
+    helper
+        .mock_ibc_ack(
+            VxAstroIbcMsg::UpdateUserVotes {
+                voter: user.to_string(),
+                voting_power: Default::default(),
+                total_voting_power: Default::default(),
+                is_unlock: false,
+            },
+            None,
+        )
+        .unwrap();
+
+    // Emissions vote is rejected by the Hub
+    helper
+        .vote(&user, &[("pool1".to_string(), Decimal::one())])
+        .unwrap();
+    let vote_packet = VxAstroIbcMsg::EmissionsVote {
+        voter: user.to_string(),
+        voting_power: 1000u128.into(),
+        total_voting_power: 1000u128.into(),
+        votes: [("pool1".to_string(), Decimal::one())].into(),
+    };
+    let ack_err = to_json_string(&AckError::new(
+        AckErrorCode::VoteCooldown,
+        "Next time you can change your vote is at 1725235200",
+    ))
+    .unwrap();
+    helper
+        .mock_ibc_ack(vote_packet.clone(), Some(&ack_err))
+        .unwrap();
+
+    let failures = helper.query_user_failures(&user).unwrap();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].id, 2);
+    assert_eq!(failures[0].kind, FailureKind::EmissionsVote);
+    assert_eq!(failures[0].code, AckErrorCode::VoteCooldown);
+
+    // Retry resends the same votes
+    helper
+        .execute_outpost_msg(&user, OutpostMsg::RetryVote { failure_id: 2 })
+        .unwrap();
+    assert_eq!(
+        helper.query_ibc_status(&user).unwrap().pending_msg,
+        Some(vote_packet.clone())
+    );
+    helper.mock_ibc_timeout(vote_packet).unwrap();
+
+    // Failure can be dismissed
+    let failures = helper.query_user_failures(&user).unwrap();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].id, 3);
+    helper
+        .execute_outpost_msg(&user, OutpostMsg::DismissFailure { failure_id: 3 })
+        .unwrap();
+    assert_eq!(helper.query_user_failures(&user).unwrap(), vec![]);
+    let err = helper
+        .execute_outpost_msg(&user, OutpostMsg::DismissFailure { failure_id: 3 })
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::FailureNotFound(3)
+    );
+
+    // Governance vote failure
+    helper.timetravel(100);
+    let now = helper.app.block_info().time.seconds();
+    helper
+        .mock_packet_receive(
+            VxAstroIbcMsg::RegisterProposal {
+                proposal_id: 1,
+                start_time: now,
+            },
+            "channel-1",
+        )
+        .unwrap();
+    helper.cast_vote(&user, 1).unwrap();
+    let gov_packet = VxAstroIbcMsg::GovernanceVote {
+        voter: user.to_string(),
+        voting_power: 1000u128.into(),
+        total_voting_power: 1000u128.into(),
+        proposal_id: 1,
+        vote: ProposalVoteOption::For,
+        snapshot_time: Some(now - 1),
+    };
+    helper.mock_ibc_timeout(gov_packet.clone()).unwrap();
+
+    helper
+        .execute_outpost_msg(&user, OutpostMsg::RetryGovernanceVote { failure_id: 4 })
+        .unwrap();
+    assert_eq!(
+        helper.query_ibc_status(&user).unwrap().pending_msg,
+        Some(gov_packet.clone())
+    );
+    helper.mock_ibc_ack(gov_packet, None).unwrap();
+    assert_eq!(helper.query_user_failures(&user).unwrap(), vec![]);
+
+    // Inbox keeps only the latest failures
+    for _ in 0..MAX_USER_FAILURES + 2 {
+        helper.mock_ibc_timeout(update_packet_for(&user)).unwrap();
+    }
+    let failures = helper.query_user_failures(&user).unwrap();
+    assert_eq!(failures.len(), MAX_USER_FAILURES);
+    assert_eq!(failures[0].id, 7);
+}
+
+#[test]
+fn test_vote_receipts() {
+    let mut helper = ControllerHelper::new();
+    helper.set_voting_channel();
+
+    let user = helper.app.api().addr_make("user");
+    helper.lock(&user, 1000u64.into()).unwrap();
+    helper.mock_ibc_ack(update_packet_for(&user), None).unwrap();
+
+    let votes = [
+        ("pool2".to_string(), Decimal::percent(40)),
+        ("pool1".to_string(), Decimal::percent(60)),
+    ];
+    let vote_packet = VxAstroIbcMsg::EmissionsVote {
+        voter: user.to_string(),
+        voting_power: 1000u128.into(),
+        total_voting_power: 1000u128.into(),
+        votes: votes.clone().into(),
+    };
+
+    // No receipt before the vote outcome is received
+    helper.vote(&user, &votes).unwrap();
+    assert_eq!(helper.query_vote_receipt(&user, None).unwrap(), None);
+
+    // Rejected vote
+    let ack_err = to_json_string(&AckError::new(
+        AckErrorCode::VoteCooldown,
+        "Next time you can change your vote is at 1725235200",
+    ))
+    .unwrap();
+    helper
+        .mock_ibc_ack(vote_packet.clone(), Some(&ack_err))
+        .unwrap();
+    let receipt = helper.query_vote_receipt(&user, None).unwrap().unwrap();
+    assert_eq!(
+        receipt.status,
+        VoteReceiptStatus::Failed {
+            code: AckErrorCode::VoteCooldown
+        }
+    );
+    assert!(!helper.verify_vote_receipt(&receipt).unwrap());
+
+    // Accepted vote replaces the failed receipt
+    helper.vote(&user, &votes).unwrap();
+    helper.mock_ibc_ack(vote_packet.clone(), None).unwrap();
+
+    let now = helper.app.block_info().time.seconds();
+    let epoch_start = get_epoch_start(now);
+    let receipt = helper.query_vote_receipt(&user, None).unwrap().unwrap();
+    let mut expected = VoteReceipt {
+        user: user.to_string(),
+        epoch_start,
+        votes: vec![
+            ("pool1".to_string(), Decimal::percent(60)),
+            ("pool2".to_string(), Decimal::percent(40)),
+        ],
+        voting_power: 1000u128.into(),
+        status: VoteReceiptStatus::Acknowledged,
+        timestamp: now,
+        digest: Default::default(),
+    };
+    expected.digest = expected
+        .compute_digest(&helper.emission_controller)
+        .unwrap();
+    assert_eq!(receipt, expected);
+    assert_eq!(
+        helper.query_vote_receipt(&user, Some(epoch_start)).unwrap(),
+        Some(receipt.clone())
+    );
+    assert!(helper.verify_vote_receipt(&receipt).unwrap());
+
+    // Tampered receipts are rejected
+    let mut tampered = receipt.clone();
+    tampered.voting_power = 2000u128.into();
+    assert!(!helper.verify_vote_receipt(&tampered).unwrap());
+
+    // Failed vote doesn't overwrite the acknowledged receipt
+    helper.vote(&user, &votes).unwrap();
+    helper.mock_ibc_timeout(vote_packet.clone()).unwrap();
+    assert_eq!(
+        helper.query_vote_receipt(&user, None).unwrap(),
+        Some(receipt.clone())
+    );
+
+    // Receipts are kept per epoch
+    helper.timetravel(EPOCH_LENGTH);
+    assert_eq!(helper.query_vote_receipt(&user, None).unwrap(), None);
+    assert!(helper.verify_vote_receipt(&receipt).unwrap());
+
+    // Votes acknowledged after the epoch boundary are recorded in the epoch they were sent in
+    let sent_epoch = get_epoch_start(helper.app.block_info().time.seconds());
+    helper.vote(&user, &votes).unwrap();
+    helper.timetravel(EPOCH_LENGTH);
+    helper.mock_ibc_ack(vote_packet, None).unwrap();
+    assert_eq!(helper.query_vote_receipt(&user, None).unwrap(), None);
+    let receipt = helper
+        .query_vote_receipt(&user, Some(sent_epoch))
+        .unwrap()
+        .unwrap();
+    assert_eq!(receipt.epoch_start, sent_epoch);
+    assert_eq!(receipt.status, VoteReceiptStatus::Acknowledged);
+}
+
+#[test]
+fn test_whitelist_pool() {
+    let mut helper = ControllerHelper::new();
+    helper.set_voting_channel();
+    let astro = helper.astro.clone();
+
+    let pool = helper.create_pair("token1", "token2");
+    let user = helper.app.api().addr_make("user");
+    let fee = coins(1_000000, &astro);
+    helper.mint_tokens(&user, &fee).unwrap();
+
+    let whitelist_msg = ExecuteMsg::Custom(OutpostMsg::WhitelistPool { pool: pool.clone() });
+
+    // Fee must be paid in ASTRO
+    let err = helper
+        .app
+        .execute_contract(
+            user.clone(),
+            helper.emission_controller.clone(),
+            &whitelist_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PaymentError(PaymentError::NoFunds {})
+    );
+
+    // Invalid LP tokens are rejected on the outpost
+    let err = helper
+        .app
+        .execute_contract(
+            user.clone(),
+            helper.emission_controller.clone(),
+            &ExecuteMsg::Custom(OutpostMsg::WhitelistPool {
+                pool: "random".to_string(),
+            }),
+            &fee,
+        )
+        .unwrap_err();
+    assert!(
+        err.root_cause().to_string().contains("random"),
+        "Unexpected error: {err}"
+    );
+
+    helper
+        .app
+        .execute_contract(
+            user.clone(),
+            helper.emission_controller.clone(),
+            &whitelist_msg,
+            &fee,
+        )
+        .unwrap();
+    let packet = VxAstroIbcMsg::WhitelistPool {
+        user: user.to_string(),
+        pool: pool.clone(),
+        fee: 1_000000u128.into(),
+    };
+    assert_eq!(
+        helper.query_ibc_status(&user).unwrap().pending_msg,
+        Some(packet.clone())
+    );
+
+    // Hub rejected the request. The fee is refunded
+    helper
+        .mock_ibc_ack(packet.clone(), Some("Pool is already whitelisted"))
+        .unwrap();
+    let user_bal = helper.app.wrap().query_balance(&user, &astro).unwrap();
+    assert_eq!(user_bal, fee[0]);
+    let failures = helper.query_user_failures(&user).unwrap();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].kind, FailureKind::WhitelistPool);
+
+    // Timed out request is refunded as well
+    helper
+        .app
+        .execute_contract(
+            user.clone(),
+            helper.emission_controller.clone(),
+            &whitelist_msg,
+            &fee,
+        )
+        .unwrap();
+    helper.mock_ibc_timeout(packet.clone()).unwrap();
+    let user_bal = helper.app.wrap().query_balance(&user, &astro).unwrap();
+    assert_eq!(user_bal, fee[0]);
+
+    // Successful whitelisting forwards the fee to the Hub fee receiver
+    helper
+        .app
+        .execute_contract(
+            user.clone(),
+            helper.emission_controller.clone(),
+            &whitelist_msg,
+            &fee,
+        )
+        .unwrap();
+    let resp = helper
+        .mock_ibc_ack_result(
+            packet,
+            IbcAckResult::Ok(to_json_binary(&"fee_receiver").unwrap()),
+        )
+        .unwrap();
+    let has_event = resp.has_event(&Event::new("transfer").add_attributes([
+        attr("packet_src_channel", "channel-2"),
+        attr("to_address", "fee_receiver"),
+        attr("amount", fee[0].to_string()),
+    ]));
+    assert!(
+        has_event,
+        "Expected IBC transfer event. Actual {:?}",
+        resp.events
+    );
+    let contract_bal = helper
+        .app
+        .wrap()
+        .query_balance(&helper.emission_controller, &astro)
+        .unwrap();
+    assert!(contract_bal.amount.is_zero());
+    assert_eq!(helper.query_ibc_status(&user).unwrap().pending_msg, None);
+}
+
+#[test]
+fn test_submit_proposal() {
+    let mut helper = ControllerHelper::new();
+    helper.set_voting_channel();
+    let xastro = helper.xastro.clone();
+
+    let user = helper.app.api().addr_make("user");
+    let deposit = coins(1_000000, &xastro);
+    helper.mint_tokens(&user, &deposit).unwrap();
+
+    let proposal = ProposalTemplate {
+        title: "Outpost proposal".to_string(),
+        description: "Submitted from the outpost".to_string(),
+        link: None,
+        messages: vec![],
+        ibc_channel: None,
+        commit_reveal: false,
+        tags: vec![],
+    };
+    let submit_msg = OutpostMsg::SubmitProposal {
+        proposal: proposal.clone(),
+    };
+    let query_deposit = |helper: &ControllerHelper, sequence: u64| -> Option<ProposalDeposit> {
+        helper
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &helper.emission_controller,
+                &emissions_controller::outpost::QueryMsg::PendingProposalDeposit { sequence },
+            )
+            .unwrap()
+    };
+
+    // Deposit must be paid in xASTRO
+    let err = helper
+        .execute_outpost_msg(&user, submit_msg.clone())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PaymentError(PaymentError::NoFunds {})
+    );
+
+    // Returns the sequence of the sent packet
+    let submit = |helper: &mut ControllerHelper| -> u64 {
+        let resp = helper
+            .app
+            .execute_contract(
+                user.clone(),
+                helper.emission_controller.clone(),
+                &ExecuteMsg::Custom(submit_msg.clone()),
+                &deposit,
+            )
+            .unwrap();
+        resp.events
+            .iter()
+            .flat_map(|event| &event.attributes)
+            .find(|attr| attr.key == "sequence")
+            .map(|attr| attr.value.parse().unwrap())
+            .unwrap()
+    };
+    let sequence = submit(&mut helper);
+    let packet = VxAstroIbcMsg::SubmitProposal {
+        submitter: user.to_string(),
+        proposal,
+        deposit: deposit[0].amount,
+    };
+    // Submissions don't block other IBC messages of the submitter
+    assert_eq!(helper.query_ibc_status(&user).unwrap().pending_msg, None);
+    let escrowed = ProposalDeposit {
+        submitter: user.to_string(),
+        deposit: deposit[0].clone(),
+    };
+    assert_eq!(query_deposit(&helper, sequence), Some(escrowed.clone()));
+
+    // Hub rejected the submission. The deposit is refunded
+    helper
+        .mock_ibc_ack_seq(
+            packet.clone(),
+            sequence,
+            IbcAckResult::Error("Proposal deposit must be at least".to_string()),
+        )
+        .unwrap();
+    let user_bal = helper.app.wrap().query_balance(&user, &xastro).unwrap();
+    assert_eq!(user_bal, deposit[0]);
+    assert_eq!(query_deposit(&helper, sequence), None);
+    let failures = helper.query_user_failures(&user).unwrap();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].kind, FailureKind::SubmitProposal);
+
+    // Timed out submission is refunded as well
+    let sequence = submit(&mut helper);
+    helper
+        .mock_ibc_timeout_seq(packet.clone(), sequence)
+        .unwrap();
+    let user_bal = helper.app.wrap().query_balance(&user, &xastro).unwrap();
+    assert_eq!(user_bal, deposit[0]);
+    assert_eq!(query_deposit(&helper, sequence), None);
+
+    // Once the Hub acknowledges the submission, the deposit is transferred to the Hub
+    let sequence = submit(&mut helper);
+    let deadline = helper.app.block_info().time.seconds() + 100;
+    let resp = helper
+        .mock_ibc_ack_seq(
+            packet.clone(),
+            sequence,
+            IbcAckResult::Ok(to_json_binary(&deadline).unwrap()),
+        )
+        .unwrap();
+    resp.assert_event(&Event::new("wasm").add_attributes([
+        attr("action", "forward_proposal_deposit"),
+        attr("submitter", user.to_string()),
+        attr("sequence", sequence.to_string()),
+    ]));
+    resp.assert_event(&Event::new("transfer").add_attributes([
+        attr("packet_timeout_timestamp", (deadline * 1_000_000_000).to_string()),
+        attr("packet_src_channel", "channel-2"),
+        attr("receiver", "emissions_controller"),
+        attr("amount", deposit[0].to_string()),
+        attr(
+            "memo",
+            format!(
+                r#"{{"wasm":{{"contract":"emissions_controller","msg":{{"custom":{{"fund_outpost_proposal":{{"channel":"","sequence":{sequence}}}}}}}}}}}"#
+            ),
+        ),
+    ]));
+    assert_eq!(query_deposit(&helper, sequence), None);
+    let contract_bal = helper
+        .app
+        .wrap()
+        .query_balance(&helper.emission_controller, &xastro)
+        .unwrap();
+    assert!(contract_bal.amount.is_zero());
+
+    // Several submissions of the same user can be in flight
+    helper
+        .mint_tokens(&user, &coins(2_000000, &xastro))
+        .unwrap();
+    let expired_sequence = submit(&mut helper);
+    let pending_sequence = submit(&mut helper);
+    assert_eq!(
+        query_deposit(&helper, expired_sequence),
+        Some(escrowed.clone())
+    );
+    assert_eq!(query_deposit(&helper, pending_sequence), Some(escrowed));
+
+    // The deadline has passed. The deposit stays on the outpost until the Hub requests a refund
+    let deadline = helper.app.block_info().time.seconds();
+    let resp = helper
+        .mock_ibc_ack_seq(
+            packet.clone(),
+            expired_sequence,
+            IbcAckResult::Ok(to_json_binary(&deadline).unwrap()),
+        )
+        .unwrap();
+    resp.assert_event(&Event::new("wasm").add_attributes([
+        attr("action", "deposit_transfer_expired"),
+        attr("sequence", expired_sequence.to_string()),
+    ]));
+    assert_eq!(query_deposit(&helper, expired_sequence), None);
+
+    // Deposits of pending submissions can't be used for refunds
+    let err = helper
+        .mock_packet_receive(
+            VxAstroIbcMsg::RefundProposalDeposit {
+                sequence: expired_sequence,
+                submitter: user.to_string(),
+                deposit: deposit[0].amount * Uint128::new(2),
+            },
+            "channel-1",
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<StdError>().unwrap(),
+        StdError::generic_err(format!(
+            "Deposit of submission {expired_sequence} hasn't returned to the outpost yet"
+        ))
+    );
+
+    let resp = helper
+        .mock_packet_receive(
+            VxAstroIbcMsg::RefundProposalDeposit {
+                sequence: expired_sequence,
+                submitter: user.to_string(),
+                deposit: deposit[0].amount,
+            },
+            "channel-1",
+        )
+        .unwrap();
+    assert_eq!(resp.data.unwrap(), ack_ok());
+    let user_bal = helper.app.wrap().query_balance(&user, &xastro).unwrap();
+    assert_eq!(user_bal, deposit[0]);
+    let contract_bal = helper
+        .app
+        .wrap()
+        .query_balance(&helper.emission_controller, &xastro)
+        .unwrap();
+    assert_eq!(contract_bal, deposit[0]);
+    assert!(query_deposit(&helper, pending_sequence).is_some());
+}
+
+fn update_packet_for(user: &Addr) -> VxAstroIbcMsg {
+    VxAstroIbcMsg::UpdateUserVotes {
+        voter: user.to_string(),
+        voting_power: 1000u128.into(),
+        total_voting_power: 1000u128.into(),
+        is_unlock: false,
+    }
+}
+
 #[test]
 fn test_interchain_governance() {
     let mut helper = ControllerHelper::new();
@@ -682,6 +1456,7 @@ fn test_interchain_governance() {
             total_voting_power: Default::default(),
             proposal_id: 2,
             vote: ProposalVoteOption::For,
+            snapshot_time: None,
         })
         .unwrap();
 
@@ -696,6 +1471,7 @@ fn test_interchain_governance() {
                 total_voting_power: Default::default(),
                 proposal_id: 2,
                 vote: ProposalVoteOption::For,
+                snapshot_time: None,
             },
             None,
         )
@@ -771,6 +1547,7 @@ fn test_interchain_governance() {
                 total_voting_power: 0u128.into(),
                 proposal_id: 3,
                 vote: ProposalVoteOption::For,
+                snapshot_time: Some(now - 1),
             }),
             error: None
         }
@@ -922,3 +1699,340 @@ fn test_change_ownership() {
 
     assert_eq!(helper.query_config().unwrap().owner.to_string(), new_owner)
 }
+
+#[test]
+fn test_execute_hub_msgs() {
+    let mut helper = ControllerHelper::new();
+    helper.set_voting_channel();
+    let astro = helper.astro.clone();
+
+    let recipient = helper.app.api().addr_make("recipient");
+    helper
+        .mint_tokens(
+            &helper.emission_controller.clone(),
+            &coins(100_000000, &astro),
+        )
+        .unwrap();
+
+    // Only the contract itself can execute Hub messages
+    let random = helper.app.api().addr_make("random");
+    let err = helper
+        .app
+        .execute_contract(
+            random,
+            helper.emission_controller.clone(),
+            &ExecuteMsg::Custom(OutpostMsg::ExecuteHubMsgs { msgs: vec![] }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    let send_msg: CosmosMsg = BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: coins(10_000000, &astro),
+    }
+    .into();
+
+    // Packets from channels other than the voting channel are rejected
+    let err = helper
+        .mock_packet_receive(
+            VxAstroIbcMsg::ExecuteMsgs {
+                execution_id: 1,
+                msgs: vec![send_msg.clone()],
+            },
+            "channel-2",
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<StdError>().unwrap(),
+        StdError::generic_err("Invalid channel")
+    );
+    let balance = helper.app.wrap().query_balance(&recipient, &astro).unwrap();
+    assert!(balance.amount.is_zero());
+
+    let resp = helper
+        .mock_packet_receive(
+            VxAstroIbcMsg::ExecuteMsgs {
+                execution_id: 1,
+                msgs: vec![send_msg.clone()],
+            },
+            "channel-1",
+        )
+        .unwrap();
+    assert_eq!(resp.data.unwrap(), ack_ok());
+    let balance = helper.app.wrap().query_balance(&recipient, &astro).unwrap();
+    assert_eq!(balance.amount.u128(), 10_000000);
+
+    // If any message fails, all of them are reverted and the Hub receives an error ack
+    let failing_msg: CosmosMsg = BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: coins(1_000000, "nonexistent"),
+    }
+    .into();
+    let resp = helper
+        .mock_packet_receive(
+            VxAstroIbcMsg::ExecuteMsgs {
+                execution_id: 2,
+                msgs: vec![send_msg, failing_msg],
+            },
+            "channel-1",
+        )
+        .unwrap();
+    let ack: IbcAckResult = from_json(resp.data.unwrap()).unwrap();
+    assert!(
+        matches!(ack, IbcAckResult::Error(_)),
+        "Unexpected ack: {ack:?}"
+    );
+    let balance = helper.app.wrap().query_balance(&recipient, &astro).unwrap();
+    assert_eq!(balance.amount.u128(), 10_000000);
+}
+
+#[test]
+fn test_proposal_status_update() {
+    let mut helper = ControllerHelper::new();
+    helper.set_voting_channel();
+
+    let query_status = |helper: &ControllerHelper| -> Option<ProposalStatus> {
+        helper
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &helper.emission_controller,
+                &emissions_controller::outpost::QueryMsg::ProposalStatus { proposal_id: 1 },
+            )
+            .unwrap()
+    };
+    assert_eq!(query_status(&helper), None);
+
+    let packet = VxAstroIbcMsg::ProposalStatusUpdate {
+        proposal_id: 1,
+        status: ProposalStatus::Passed,
+    };
+
+    // Notifications from channels other than the voting channel are rejected
+    let err = helper
+        .mock_packet_receive(packet.clone(), "channel-2")
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<StdError>().unwrap(),
+        StdError::generic_err("Invalid channel")
+    );
+    assert_eq!(query_status(&helper), None);
+
+    let resp = helper.mock_packet_receive(packet, "channel-1").unwrap();
+    assert_eq!(resp.data.unwrap(), ack_ok());
+    assert_eq!(query_status(&helper), Some(ProposalStatus::Passed));
+
+    helper
+        .mock_packet_receive(
+            VxAstroIbcMsg::ProposalStatusUpdate {
+                proposal_id: 1,
+                status: ProposalStatus::Executed,
+            },
+            "channel-1",
+        )
+        .unwrap();
+    assert_eq!(query_status(&helper), Some(ProposalStatus::Executed));
+}
+
+#[test]
+fn test_proposal_tally() {
+    let mut helper = ControllerHelper::new();
+    helper.set_voting_channel();
+
+    let user = helper.app.api().addr_make("user");
+    let query_tally = |helper: &ControllerHelper| -> Option<ProposalTally> {
+        helper
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &helper.emission_controller,
+                &emissions_controller::outpost::QueryMsg::ProposalTally { proposal_id: 1 },
+            )
+            .unwrap()
+    };
+    let request_msg = OutpostMsg::RequestProposalTally { proposal_id: 1 };
+
+    // Tally can be requested only for registered proposals
+    let err = helper
+        .execute_outpost_msg(&user, request_msg.clone())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ProposalNotRegistered(1)
+    );
+
+    helper
+        .mock_packet_receive(
+            VxAstroIbcMsg::RegisterProposal {
+                proposal_id: 1,
+                start_time: helper.app.block_info().time.seconds(),
+            },
+            "channel-1",
+        )
+        .unwrap();
+
+    helper
+        .execute_outpost_msg(&user, request_msg.clone())
+        .unwrap();
+    // Only one request per proposal can be in flight
+    let err = helper
+        .execute_outpost_msg(&user, request_msg.clone())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PendingTallyRequest(1)
+    );
+
+    let packet = VxAstroIbcMsg::ProposalTallyRequest { proposal_id: 1 };
+
+    // Failed requests aren't recorded in any failure inbox
+    helper.mock_ibc_timeout(packet.clone()).unwrap();
+    assert_eq!(query_tally(&helper), None);
+    assert!(helper.query_user_failures(&user).unwrap().is_empty());
+
+    helper.execute_outpost_msg(&user, request_msg).unwrap();
+    let tally = ProposalTally {
+        proposal_id: 1,
+        status: ProposalStatus::Active,
+        for_power: 1000u128.into(),
+        against_power: 500u128.into(),
+        total_voting_power: 10000u128.into(),
+        voting_end_block: 100,
+        blocks_remaining: 20,
+        updated_at: 1724922008,
+    };
+    helper
+        .mock_ibc_ack_result(packet, IbcAckResult::Ok(to_json_binary(&tally).unwrap()))
+        .unwrap();
+    assert_eq!(query_tally(&helper), Some(tally));
+}
+
+#[test]
+fn test_governance_voting_power_cache() {
+    let mut helper = ControllerHelper::new();
+    helper.set_voting_channel();
+
+    let user = helper.app.api().addr_make("user");
+    helper.lock(&user, 1000u64.into()).unwrap();
+    helper.mock_ibc_ack(update_packet_for(&user), None).unwrap();
+
+    helper.timetravel(100);
+    let now = helper.app.block_info().time.seconds();
+    helper
+        .mock_packet_receive(
+            VxAstroIbcMsg::RegisterProposal {
+                proposal_id: 1,
+                start_time: now,
+            },
+            "channel-1",
+        )
+        .unwrap();
+
+    let query_cache = |helper: &ControllerHelper| -> Option<CachedVotingPower> {
+        helper
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &helper.emission_controller,
+                &emissions_controller::outpost::QueryMsg::CachedVotingPower {
+                    proposal_id: 1,
+                    user: user.to_string(),
+                },
+            )
+            .unwrap()
+    };
+    assert_eq!(query_cache(&helper), None);
+
+    // The first vote records voting power and sends the snapshot time to the Hub
+    let resp = helper.cast_vote(&user, 1).unwrap();
+    let snapshot_time = (now - 1).to_string();
+    assert!(resp.has_event(&Event::new("wasm").add_attributes([
+        ("action", "governance_vote"),
+        ("voting_power", "1000"),
+        ("snapshot_time", snapshot_time.as_str()),
+    ])));
+    let cached = CachedVotingPower {
+        voting_power: 1000u128.into(),
+        total_voting_power: 1000u128.into(),
+        snapshot_time: now - 1,
+    };
+    assert_eq!(query_cache(&helper), Some(cached.clone()));
+
+    let gov_packet = VxAstroIbcMsg::GovernanceVote {
+        voter: user.to_string(),
+        voting_power: 1000u128.into(),
+        total_voting_power: 1000u128.into(),
+        proposal_id: 1,
+        vote: ProposalVoteOption::For,
+        snapshot_time: Some(now - 1),
+    };
+    assert_eq!(
+        helper.query_ibc_status(&user).unwrap().pending_msg,
+        Some(gov_packet.clone())
+    );
+
+    // Retries reuse the cached voting power
+    helper.mock_ibc_timeout(gov_packet.clone()).unwrap();
+    helper
+        .execute_outpost_msg(&user, OutpostMsg::RetryGovernanceVote { failure_id: 1 })
+        .unwrap();
+    assert_eq!(
+        helper.query_ibc_status(&user).unwrap().pending_msg,
+        Some(gov_packet.clone())
+    );
+    assert_eq!(query_cache(&helper), Some(cached.clone()));
+    helper.mock_ibc_ack(gov_packet, None).unwrap();
+
+    // Cache can't be cleared while the proposal is active or its status is unknown
+    let clear_msg = OutpostMsg::ClearVotingPowerCache {
+        proposal_id: 1,
+        limit: None,
+    };
+    let random = helper.app.api().addr_make("random");
+    let err = helper
+        .execute_outpost_msg(&random, clear_msg.clone())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ProposalNotConcluded(1)
+    );
+
+    helper
+        .mock_packet_receive(
+            VxAstroIbcMsg::ProposalStatusUpdate {
+                proposal_id: 1,
+                status: ProposalStatus::Active,
+            },
+            "channel-1",
+        )
+        .unwrap();
+    let err = helper
+        .execute_outpost_msg(&random, clear_msg.clone())
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ProposalNotConcluded(1)
+    );
+
+    // Anyone can clear the cache once the proposal concluded
+    helper
+        .mock_packet_receive(
+            VxAstroIbcMsg::ProposalStatusUpdate {
+                proposal_id: 1,
+                status: ProposalStatus::Passed,
+            },
+            "channel-1",
+        )
+        .unwrap();
+    let resp = helper.execute_outpost_msg(&random, clear_msg).unwrap();
+    assert!(resp.has_event(&Event::new("wasm").add_attributes([
+        ("action", "clear_voting_power_cache"),
+        ("proposal_id", "1"),
+        ("cleared", "1"),
+    ])));
+    assert_eq!(query_cache(&helper), None);
+}
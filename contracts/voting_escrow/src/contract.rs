@@ -2,12 +2,15 @@ use astroport::asset::{addr_opt_validate, validate_native_denom};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, coins, ensure, ensure_eq, to_json_binary, wasm_execute, BankMsg, Binary, CosmosMsg, Deps,
-    DepsMut, Empty, Env, MessageInfo, Order, Response, StdError, StdResult, Uint128,
+    attr, coins, ensure, ensure_eq, to_json_binary, wasm_execute, Addr, BankMsg, Binary, CosmosMsg,
+    Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply, Response, StdError, StdResult,
+    Storage, SubMsg, SubMsgResult, Uint128,
 };
 use cw2::set_contract_version;
 use cw20::{BalanceResponse, Logo, LogoInfo, MarketingInfoResponse, TokenInfoResponse};
-use cw20_base::contract::{execute_update_marketing, query_marketing_info};
+use cw20_base::contract::{
+    execute_update_marketing, execute_upload_logo, query_download_logo, query_marketing_info,
+};
 use cw20_base::state::{MinterData, TokenInfo, LOGO, MARKETING_INFO, TOKEN_INFO};
 use cw_storage_plus::Bound;
 use cw_utils::must_pay;
@@ -15,16 +18,31 @@ use cw_utils::must_pay;
 use astroport_governance::emissions_controller;
 use astroport_governance::emissions_controller::consts::MAX_PAGE_LIMIT;
 use astroport_governance::voting_escrow::{
-    Config, ExecuteMsg, InstantiateMsg, LockInfoResponse, QueryMsg,
+    BoostInfoResponse, Config, ExecuteMsg, ExitQueueResponse, InstantiateMsg, LockInfoResponse,
+    PausedResponse, PositionResponse, QueryMsg, QueuedWithdrawalResponse, SubscriberMsg,
+    TokenMetadataResponse, DEFAULT_UNLOCK_PERIOD, MAX_DEPOSIT_FEE_BPS, MAX_EXIT_QUEUE_LIMIT,
+    MAX_INSURANCE_FUND_BPS, MAX_PERMANENT_BOOST_BPS, MAX_SUBSCRIBERS, PAUSE_ALL, PAUSE_DEPOSITS,
+    PAUSE_NEW_LOCKS, PAUSE_WITHDRAWALS, UNLOCK_PERIOD_LIMITS,
 };
 
 use crate::error::ContractError;
-use crate::state::{get_total_vp, Lock, CONFIG, LOCKED, PRIVILEGED};
+use crate::state::{
+    dequeue_withdrawals, enqueue_withdrawal, get_exit_queue_bounds, get_or_assign_position_id,
+    get_processed_in_block, get_total_vp, Lock, ACCRUED_FEES, CONFIG, EXIT_QUEUE, EXIT_QUEUE_TOTAL,
+    FEE_EXEMPTIONS, INSURANCE_CONTRIBUTIONS, INSURANCE_EXEMPTIONS, LOCKED, PERMANENT_BOOST,
+    POSITION_OWNERS, PRIVILEGED, SUBSCRIBERS, TOTAL_INSURANCE_CONTRIBUTIONS, USER_EXIT_QUEUE,
+    USER_POSITIONS,
+};
 
 /// Contract name that is used for migration.
 pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 /// Contract version that is used for migration.
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Reply id for subscriber notifications
+pub const SUBSCRIBER_NOTIFICATION_REPLY_ID: u64 = 1;
+/// Gas limit for subscriber notifications.
+/// Without the limit an out-of-gas subscriber would revert the whole lock change
+pub const SUBSCRIBER_NOTIFICATION_GAS_LIMIT: u64 = 500_000;
 
 /// Creates a new contract with the specified parameters in [`InstantiateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -41,6 +59,14 @@ pub fn instantiate(
     let config = Config {
         deposit_denom: msg.deposit_denom,
         emissions_controller: deps.api.addr_validate(&msg.emissions_controller)?,
+        unlock_period: DEFAULT_UNLOCK_PERIOD,
+        pause_mask: 0,
+        deposit_fee_bps: 0,
+        fee_receiver: None,
+        permanent_boost_bps: 0,
+        insurance_fund_bps: 0,
+        insurance_fund: None,
+        exit_queue_limit: 0,
     };
     CONFIG.save(deps.storage, &config)?;
 
@@ -93,11 +119,55 @@ pub fn execute(
         ExecuteMsg::Lock { receiver } => {
             let config = CONFIG.load(deps.storage)?;
             let deposit = must_pay(&info, &config.deposit_denom)?;
-            let receiver = addr_opt_validate(deps.api, &receiver)?.unwrap_or(info.sender);
+            let receiver = addr_opt_validate(deps.api, &receiver)?.unwrap_or(info.sender.clone());
+            // Exemptions apply to the position owner, not to whoever deposits on their behalf
+            let fee = if FEE_EXEMPTIONS.has(deps.storage, &receiver) {
+                Uint128::zero()
+            } else {
+                config.deposit_fee(deposit)
+            };
+            let contribution = if INSURANCE_EXEMPTIONS.has(deps.storage, &receiver) {
+                Uint128::zero()
+            } else {
+                config.insurance_contribution(deposit)
+            };
             let block_ts = env.block.time.seconds();
 
             let mut position = Lock::load(deps.storage, block_ts, &receiver)?;
-            position.lock(deps.storage, deposit)?;
+            if position.amount.is_zero() {
+                ensure_not_paused(&config, PAUSE_NEW_LOCKS, "New locks")?;
+            } else {
+                ensure_not_paused(&config, PAUSE_DEPOSITS, "Deposits")?;
+            }
+            position.lock(deps.storage, deposit - fee - contribution)?;
+            let position_id = get_or_assign_position_id(deps.storage, &receiver, false)?;
+
+            if !fee.is_zero() {
+                let accrued = ACCRUED_FEES.may_load(deps.storage)?.unwrap_or_default();
+                ACCRUED_FEES.save(deps.storage, &(accrued + fee))?;
+            }
+
+            let mut messages = vec![];
+            if !contribution.is_zero() {
+                INSURANCE_CONTRIBUTIONS.update::<_, StdError>(
+                    deps.storage,
+                    &receiver,
+                    |total| Ok(total.unwrap_or_default() + contribution),
+                )?;
+                let total = TOTAL_INSURANCE_CONTRIBUTIONS
+                    .may_load(deps.storage)?
+                    .unwrap_or_default();
+                TOTAL_INSURANCE_CONTRIBUTIONS.save(deps.storage, &(total + contribution))?;
+
+                let insurance_fund = config
+                    .insurance_fund
+                    .as_ref()
+                    .ok_or(ContractError::InsuranceFundNotSet {})?;
+                messages.push(BankMsg::Send {
+                    to_address: insurance_fund.to_string(),
+                    amount: coins(contribution.u128(), &config.deposit_denom),
+                });
+            }
 
             // Update user votes in emissions controller
             let update_votes_msg = wasm_execute(
@@ -111,19 +181,25 @@ pub fn execute(
 
             Ok(Response::default()
                 .add_message(update_votes_msg)
+                .add_messages(messages)
+                .add_submessages(notify_subscribers(deps.storage, "lock", &position)?)
                 .add_attributes([
                     attr("action", "lock"),
                     attr("receiver", receiver),
+                    attr("position_id", position_id.to_string()),
                     attr("deposit_amount", deposit),
+                    attr("deposit_fee", fee),
+                    attr("insurance_contribution", contribution),
                     attr("new_lock_amount", position.amount),
                 ]))
         }
         ExecuteMsg::Unlock {} => {
+            let config = CONFIG.load(deps.storage)?;
             let mut position = Lock::load(deps.storage, env.block.time.seconds(), &info.sender)?;
-            let unlock_time = position.unlock(deps.storage)?;
+            let unlock_time = position.unlock(deps.storage, config.unlock_period)?;
+            let position_id = get_or_assign_position_id(deps.storage, &info.sender, false)?;
 
             // Update user votes in emissions controller
-            let config = CONFIG.load(deps.storage)?;
             let update_votes_msg = wasm_execute(
                 config.emissions_controller,
                 &emissions_controller::msg::ExecuteMsg::<Empty>::UpdateUserVotes {
@@ -135,9 +211,11 @@ pub fn execute(
 
             Ok(Response::default()
                 .add_message(update_votes_msg)
+                .add_submessages(notify_subscribers(deps.storage, "unlock", &position)?)
                 .add_attributes([
                     attr("action", "unlock"),
                     attr("receiver", info.sender),
+                    attr("position_id", position_id.to_string()),
                     attr("unlocked_amount", position.amount),
                     attr("unlock_time", unlock_time.to_string()),
                 ]))
@@ -149,11 +227,15 @@ pub fn execute(
                 ContractError::Unauthorized {}
             );
 
+            let config = CONFIG.load(deps.storage)?;
+            ensure_not_paused(&config, PAUSE_WITHDRAWALS, "Withdrawals")?;
+
             let mut position = Lock::load(deps.storage, env.block.time.seconds(), &info.sender)?;
             position.instant_unlock(deps.storage, amount)?;
+            let position_id =
+                get_or_assign_position_id(deps.storage, &info.sender, position.amount.is_zero())?;
 
             // Update user votes in emissions controller
-            let config = CONFIG.load(deps.storage)?;
             let update_votes_msg: CosmosMsg = wasm_execute(
                 config.emissions_controller,
                 &emissions_controller::msg::ExecuteMsg::<Empty>::UpdateUserVotes {
@@ -166,26 +248,72 @@ pub fn execute(
             )?
             .into();
 
-            let send_msg: CosmosMsg = BankMsg::Send {
-                to_address: info.sender.to_string(),
-                amount: coins(amount.u128(), config.deposit_denom),
-            }
-            .into();
-
-            Ok(Response::default()
-                .add_messages([update_votes_msg, send_msg])
+            let mut response = Response::default()
+                .add_message(update_votes_msg)
+                .add_submessages(notify_subscribers(
+                    deps.storage,
+                    "instant_unlock",
+                    &position,
+                )?)
                 .add_attributes([
                     attr("action", "instant_unlock"),
-                    attr("receiver", info.sender),
+                    attr("receiver", info.sender.as_str()),
+                    attr("position_id", position_id.to_string()),
                     attr("unlocked_amount", amount),
-                ]))
+                ]);
+
+            // Unlocked xASTRO is paid out in order with other withdrawals if the exit queue is enabled
+            if config.exit_queue_limit > 0 {
+                let queue_id = enqueue_withdrawal(
+                    deps.storage,
+                    &info.sender,
+                    amount,
+                    env.block.time.seconds(),
+                )?;
+                response = response.add_attribute("queue_id", queue_id.to_string());
+            } else {
+                response = response.add_message(BankMsg::Send {
+                    to_address: info.sender.to_string(),
+                    amount: coins(amount.u128(), config.deposit_denom),
+                });
+            }
+
+            Ok(response)
         }
         ExecuteMsg::Relock {} => {
+            let config = CONFIG.load(deps.storage)?;
+            ensure_not_paused(&config, PAUSE_DEPOSITS, "Deposits")?;
+
             let mut position = Lock::load(deps.storage, env.block.time.seconds(), &info.sender)?;
             position.relock(deps.storage)?;
+            let position_id = get_or_assign_position_id(deps.storage, &info.sender, false)?;
 
             // Update user votes in emissions controller
+            let update_votes_msg = wasm_execute(
+                config.emissions_controller,
+                &emissions_controller::msg::ExecuteMsg::<Empty>::UpdateUserVotes {
+                    user: info.sender.to_string(),
+                    is_unlock: false,
+                },
+                vec![],
+            )?;
+
+            Ok(Response::default()
+                .add_message(update_votes_msg)
+                .add_submessages(notify_subscribers(deps.storage, "relock", &position)?)
+                .add_attributes([
+                    attr("action", "relock"),
+                    attr("receiver", info.sender),
+                    attr("position_id", position_id.to_string()),
+                ]))
+        }
+        ExecuteMsg::MakePermanent {} => {
             let config = CONFIG.load(deps.storage)?;
+            let mut position = Lock::load(deps.storage, env.block.time.seconds(), &info.sender)?;
+            position.make_permanent(deps.storage)?;
+            let position_id = get_or_assign_position_id(deps.storage, &info.sender, false)?;
+
+            // Update user votes in emissions controller as the boost applies immediately
             let update_votes_msg = wasm_execute(
                 config.emissions_controller,
                 &emissions_controller::msg::ExecuteMsg::<Empty>::UpdateUserVotes {
@@ -197,7 +325,48 @@ pub fn execute(
 
             Ok(Response::default()
                 .add_message(update_votes_msg)
-                .add_attributes([attr("action", "relock"), attr("receiver", info.sender)]))
+                .add_submessages(notify_subscribers(
+                    deps.storage,
+                    "make_permanent",
+                    &position,
+                )?)
+                .add_attributes([
+                    attr("action", "make_permanent"),
+                    attr("receiver", info.sender),
+                    attr("position_id", position_id.to_string()),
+                    attr("lock_amount", position.amount),
+                ]))
+        }
+        ExecuteMsg::UnlockPermanent {} => {
+            let config = CONFIG.load(deps.storage)?;
+            let mut position = Lock::load(deps.storage, env.block.time.seconds(), &info.sender)?;
+            let unlock_time = position.unlock_permanent(deps.storage, config.unlock_period)?;
+            let position_id = get_or_assign_position_id(deps.storage, &info.sender, false)?;
+
+            // Update user votes in emissions controller
+            let update_votes_msg = wasm_execute(
+                config.emissions_controller,
+                &emissions_controller::msg::ExecuteMsg::<Empty>::UpdateUserVotes {
+                    user: info.sender.to_string(),
+                    is_unlock: true,
+                },
+                vec![],
+            )?;
+
+            Ok(Response::default()
+                .add_message(update_votes_msg)
+                .add_submessages(notify_subscribers(
+                    deps.storage,
+                    "unlock_permanent",
+                    &position,
+                )?)
+                .add_attributes([
+                    attr("action", "unlock_permanent"),
+                    attr("receiver", info.sender),
+                    attr("position_id", position_id.to_string()),
+                    attr("unlocked_amount", position.amount),
+                    attr("unlock_time", unlock_time.to_string()),
+                ]))
         }
         ExecuteMsg::ForceRelock { user } => {
             let config = CONFIG.load(deps.storage)?;
@@ -209,9 +378,15 @@ pub fn execute(
             let user = deps.api.addr_validate(&user)?;
             let mut position = Lock::load(deps.storage, env.block.time.seconds(), &user)?;
             position.relock(deps.storage)?;
+            let position_id = get_or_assign_position_id(deps.storage, &user, false)?;
 
             Ok(Response::default()
-                .add_attributes([attr("action", "force_relock"), attr("receiver", user)]))
+                .add_submessages(notify_subscribers(deps.storage, "force_relock", &position)?)
+                .add_attributes([
+                    attr("action", "force_relock"),
+                    attr("receiver", user),
+                    attr("position_id", position_id.to_string()),
+                ]))
         }
         ExecuteMsg::ConfirmUnlock { user } => {
             let config = CONFIG.load(deps.storage)?;
@@ -223,25 +398,109 @@ pub fn execute(
             let user = deps.api.addr_validate(&user)?;
             let mut position = Lock::load(deps.storage, env.block.time.seconds(), &user)?;
             position.confirm_unlock(deps.storage)?;
+            let position_id = get_or_assign_position_id(deps.storage, &user, false)?;
 
-            Ok(Response::default()
-                .add_attributes([attr("action", "confirm_unlock"), attr("receiver", user)]))
+            Ok(Response::default().add_attributes([
+                attr("action", "confirm_unlock"),
+                attr("receiver", user),
+                attr("position_id", position_id.to_string()),
+            ]))
         }
         ExecuteMsg::Withdraw {} => {
             let mut position = Lock::load(deps.storage, env.block.time.seconds(), &info.sender)?;
             let config = CONFIG.load(deps.storage)?;
+            ensure_not_paused(&config, PAUSE_WITHDRAWALS, "Withdrawals")?;
             let amount = position.withdraw(deps.storage)?;
+            let position_id = get_or_assign_position_id(deps.storage, &info.sender, true)?;
+
+            if config.exit_queue_limit > 0 {
+                let queue_id = enqueue_withdrawal(
+                    deps.storage,
+                    &info.sender,
+                    amount,
+                    env.block.time.seconds(),
+                )?;
+
+                return Ok(Response::new()
+                    .add_submessages(notify_subscribers(deps.storage, "withdraw", &position)?)
+                    .add_attributes([
+                        attr("action", "queue_withdrawal"),
+                        attr("receiver", info.sender),
+                        attr("position_id", position_id.to_string()),
+                        attr("queue_id", queue_id.to_string()),
+                        attr("queued_amount", amount),
+                    ]));
+            }
 
             let send_msg = BankMsg::Send {
                 to_address: info.sender.to_string(),
                 amount: coins(amount.u128(), config.deposit_denom),
             };
 
-            Ok(Response::new().add_message(send_msg).add_attributes([
-                attr("action", "withdraw"),
-                attr("receiver", info.sender),
-                attr("withdrawn_amount", amount),
-            ]))
+            Ok(Response::new()
+                .add_message(send_msg)
+                .add_submessages(notify_subscribers(deps.storage, "withdraw", &position)?)
+                .add_attributes([
+                    attr("action", "withdraw"),
+                    attr("receiver", info.sender),
+                    attr("position_id", position_id.to_string()),
+                    attr("withdrawn_amount", amount),
+                ]))
+        }
+        ExecuteMsg::SlashLock {
+            user,
+            amount,
+            recipient,
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            // Query result deserialization into hub::Config
+            // ensures we can call this endpoint only on the Hub
+            let assembly = deps
+                .querier
+                .query_wasm_smart::<emissions_controller::hub::Config>(
+                    &config.emissions_controller,
+                    &emissions_controller::hub::QueryMsg::Config {},
+                )?
+                .assembly;
+            ensure_eq!(info.sender, assembly, ContractError::Unauthorized {});
+
+            let user = deps.api.addr_validate(&user)?;
+            let recipient = deps.api.addr_validate(&recipient)?;
+            let mut position = Lock::load(deps.storage, env.block.time.seconds(), &user)?;
+            position.slash(deps.storage, amount)?;
+            let position_id =
+                get_or_assign_position_id(deps.storage, &user, position.amount.is_zero())?;
+
+            // Update user votes in emissions controller
+            let update_votes_msg: CosmosMsg = wasm_execute(
+                config.emissions_controller,
+                &emissions_controller::msg::ExecuteMsg::<Empty>::UpdateUserVotes {
+                    user: user.to_string(),
+                    // Slashed xASTRO leaves the contract immediately
+                    is_unlock: false,
+                },
+                vec![],
+            )?
+            .into();
+
+            let send_msg: CosmosMsg = BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: coins(amount.u128(), config.deposit_denom),
+            }
+            .into();
+
+            Ok(Response::default()
+                .add_messages([update_votes_msg, send_msg])
+                .add_submessages(notify_subscribers(deps.storage, "slash_lock", &position)?)
+                .add_attributes([
+                    attr("action", "slash_lock"),
+                    attr("user", user),
+                    attr("position_id", position_id.to_string()),
+                    attr("recipient", recipient),
+                    attr("slashed_amount", amount),
+                    attr("new_lock_amount", position.amount),
+                ]))
         }
         ExecuteMsg::SetPrivilegedList { list } => {
             let config = CONFIG.load(deps.storage)?;
@@ -266,12 +525,342 @@ pub fn execute(
 
             Ok(Response::default().add_attribute("action", "set_privileged_list"))
         }
+        ExecuteMsg::SetPermanentBoost { boost_bps } => {
+            let mut config = CONFIG.load(deps.storage)?;
+
+            let emissions_owner = query_emissions_owner(deps.as_ref(), &config)?;
+            ensure_eq!(info.sender, emissions_owner, ContractError::Unauthorized {});
+            ensure!(
+                is_hub(deps.as_ref(), &config),
+                ContractError::PermanentBoostHubOnly {}
+            );
+
+            ensure!(
+                boost_bps <= MAX_PERMANENT_BOOST_BPS,
+                ContractError::InvalidPermanentBoost(MAX_PERMANENT_BOOST_BPS)
+            );
+
+            config.permanent_boost_bps = boost_bps;
+            CONFIG.save(deps.storage, &config)?;
+            PERMANENT_BOOST.save(deps.storage, &boost_bps, env.block.time.seconds())?;
+
+            // Emissions votes of permanent lockers are refreshed in batches via RefreshPermanentVotes
+            Ok(Response::default().add_attributes([
+                attr("action", "set_permanent_boost"),
+                attr("boost_bps", boost_bps.to_string()),
+            ]))
+        }
+        ExecuteMsg::RefreshPermanentVotes { users } => {
+            let config = CONFIG.load(deps.storage)?;
+            let block_ts = env.block.time.seconds();
+
+            let mut refreshed = vec![];
+            let mut messages = vec![];
+            for user in validate_batch(deps.as_ref(), &users)? {
+                if Lock::load(deps.storage, block_ts, &user)?.permanent {
+                    messages.push(wasm_execute(
+                        &config.emissions_controller,
+                        &emissions_controller::msg::ExecuteMsg::<Empty>::UpdateUserVotes {
+                            user: user.to_string(),
+                            is_unlock: false,
+                        },
+                        vec![],
+                    )?);
+                    refreshed.push(user.to_string());
+                }
+            }
+
+            Ok(Response::default().add_messages(messages).add_attributes([
+                attr("action", "refresh_permanent_votes"),
+                attr("refreshed_users", refreshed.join(",")),
+            ]))
+        }
+        ExecuteMsg::SetUnlockPeriod { unlock_period } => {
+            let mut config = CONFIG.load(deps.storage)?;
+
+            let emissions_owner = query_emissions_owner(deps.as_ref(), &config)?;
+            ensure_eq!(info.sender, emissions_owner, ContractError::Unauthorized {});
+
+            ensure!(
+                UNLOCK_PERIOD_LIMITS.contains(&unlock_period),
+                ContractError::InvalidUnlockPeriod {
+                    min: *UNLOCK_PERIOD_LIMITS.start(),
+                    max: *UNLOCK_PERIOD_LIMITS.end(),
+                }
+            );
+
+            config.unlock_period = unlock_period;
+            CONFIG.save(deps.storage, &config)?;
+
+            Ok(Response::default().add_attributes([
+                attr("action", "set_unlock_period"),
+                attr("unlock_period", unlock_period.to_string()),
+            ]))
+        }
+        ExecuteMsg::SetExitQueueLimit { limit } => {
+            let mut config = CONFIG.load(deps.storage)?;
+            let emissions_owner = query_emissions_owner(deps.as_ref(), &config)?;
+            ensure_eq!(info.sender, emissions_owner, ContractError::Unauthorized {});
+
+            ensure!(
+                limit <= MAX_EXIT_QUEUE_LIMIT,
+                ContractError::InvalidExitQueueLimit(MAX_EXIT_QUEUE_LIMIT)
+            );
+            if limit == 0 {
+                let (head, tail) = get_exit_queue_bounds(deps.storage)?;
+                ensure!(head == tail, ContractError::ExitQueueNotEmpty {});
+            }
+
+            config.exit_queue_limit = limit;
+            CONFIG.save(deps.storage, &config)?;
+
+            Ok(Response::default().add_attributes([
+                attr("action", "set_exit_queue_limit"),
+                attr("limit", limit.to_string()),
+            ]))
+        }
+        ExecuteMsg::ProcessExitQueue { limit } => {
+            let config = CONFIG.load(deps.storage)?;
+            ensure_not_paused(&config, PAUSE_WITHDRAWALS, "Withdrawals")?;
+
+            let (head, tail) = get_exit_queue_bounds(deps.storage)?;
+            ensure!(head < tail, ContractError::ExitQueueEmpty {});
+
+            let processed = get_processed_in_block(deps.storage, env.block.height)?;
+            let remaining = config.exit_queue_limit.saturating_sub(processed);
+            ensure!(
+                remaining > 0,
+                ContractError::ExitQueueBlockLimitReached(config.exit_queue_limit)
+            );
+
+            let withdrawals = dequeue_withdrawals(
+                deps.storage,
+                env.block.height,
+                limit.unwrap_or(remaining).min(remaining),
+            )?;
+
+            let total_amount: Uint128 = withdrawals.iter().map(|w| w.amount).sum();
+            let send_msgs = withdrawals.iter().map(|withdrawal| BankMsg::Send {
+                to_address: withdrawal.user.to_string(),
+                amount: coins(withdrawal.amount.u128(), &config.deposit_denom),
+            });
+
+            Ok(Response::default().add_messages(send_msgs).add_attributes([
+                attr("action", "process_exit_queue"),
+                attr("processed", withdrawals.len().to_string()),
+                attr("amount", total_amount),
+            ]))
+        }
+        ExecuteMsg::SetSubscriber { contract, enabled } => {
+            let config = CONFIG.load(deps.storage)?;
+            let emissions_owner = query_emissions_owner(deps.as_ref(), &config)?;
+            ensure_eq!(info.sender, emissions_owner, ContractError::Unauthorized {});
+
+            let contract = deps.api.addr_validate(&contract)?;
+            if !SUBSCRIBERS.has(deps.storage, &contract) {
+                let subscribers_count = SUBSCRIBERS
+                    .keys(deps.storage, None, None, Order::Ascending)
+                    .count();
+                ensure!(
+                    subscribers_count < MAX_SUBSCRIBERS,
+                    ContractError::SubscribersLimitReached(MAX_SUBSCRIBERS)
+                );
+            }
+            SUBSCRIBERS.save(deps.storage, &contract, &enabled)?;
+
+            Ok(Response::default().add_attributes([
+                attr("action", "set_subscriber"),
+                attr("contract", contract),
+                attr("enabled", enabled.to_string()),
+            ]))
+        }
+        ExecuteMsg::RemoveSubscriber { contract } => {
+            let config = CONFIG.load(deps.storage)?;
+            let emissions_owner = query_emissions_owner(deps.as_ref(), &config)?;
+            ensure_eq!(info.sender, emissions_owner, ContractError::Unauthorized {});
+
+            let contract = deps.api.addr_validate(&contract)?;
+            SUBSCRIBERS.remove(deps.storage, &contract);
+
+            Ok(Response::default().add_attributes([
+                attr("action", "remove_subscriber"),
+                attr("contract", contract),
+            ]))
+        }
+        ExecuteMsg::SetPauseMask { pause_mask } => {
+            let mut config = CONFIG.load(deps.storage)?;
+            let emissions_owner = query_emissions_owner(deps.as_ref(), &config)?;
+            ensure_eq!(info.sender, emissions_owner, ContractError::Unauthorized {});
+
+            ensure!(
+                pause_mask & !PAUSE_ALL == 0,
+                ContractError::InvalidPauseMask(pause_mask)
+            );
+
+            config.pause_mask = pause_mask;
+            CONFIG.save(deps.storage, &config)?;
+
+            Ok(Response::default().add_attributes([
+                attr("action", "set_pause_mask"),
+                attr("pause_mask", pause_mask.to_string()),
+            ]))
+        }
+        ExecuteMsg::SetDepositFee {
+            fee_bps,
+            fee_receiver,
+        } => {
+            let mut config = CONFIG.load(deps.storage)?;
+            let emissions_owner = query_emissions_owner(deps.as_ref(), &config)?;
+            ensure_eq!(info.sender, emissions_owner, ContractError::Unauthorized {});
+
+            ensure!(
+                fee_bps <= MAX_DEPOSIT_FEE_BPS,
+                ContractError::InvalidDepositFee(MAX_DEPOSIT_FEE_BPS)
+            );
+            if let Some(fee_receiver) = fee_receiver {
+                config.fee_receiver = Some(deps.api.addr_validate(&fee_receiver)?);
+            }
+            ensure!(
+                fee_bps == 0 || config.fee_receiver.is_some(),
+                ContractError::FeeReceiverNotSet {}
+            );
+
+            config.deposit_fee_bps = fee_bps;
+            CONFIG.save(deps.storage, &config)?;
+
+            let mut attrs = vec![
+                attr("action", "set_deposit_fee"),
+                attr("fee_bps", fee_bps.to_string()),
+            ];
+            if let Some(fee_receiver) = &config.fee_receiver {
+                attrs.push(attr("fee_receiver", fee_receiver));
+            }
+
+            Ok(Response::default().add_attributes(attrs))
+        }
+        ExecuteMsg::SetFeeExemptions { add, remove } => {
+            let config = CONFIG.load(deps.storage)?;
+            let emissions_owner = query_emissions_owner(deps.as_ref(), &config)?;
+            ensure_eq!(info.sender, emissions_owner, ContractError::Unauthorized {});
+
+            for addr in &remove {
+                FEE_EXEMPTIONS.remove(deps.storage, &deps.api.addr_validate(addr)?);
+            }
+            for addr in &add {
+                FEE_EXEMPTIONS.save(deps.storage, &deps.api.addr_validate(addr)?, &())?;
+            }
+
+            let mut attrs = vec![attr("action", "set_fee_exemptions")];
+            if !add.is_empty() {
+                attrs.push(attr("added", add.join(",")));
+            }
+            if !remove.is_empty() {
+                attrs.push(attr("removed", remove.join(",")));
+            }
+
+            Ok(Response::default().add_attributes(attrs))
+        }
+        ExecuteMsg::ForwardFees {} => {
+            let config = CONFIG.load(deps.storage)?;
+            let fee_receiver = config
+                .fee_receiver
+                .ok_or(ContractError::FeeReceiverNotSet {})?;
+
+            let amount = ACCRUED_FEES.may_load(deps.storage)?.unwrap_or_default();
+            ensure!(!amount.is_zero(), ContractError::NoFeesToForward {});
+            ACCRUED_FEES.save(deps.storage, &Uint128::zero())?;
+
+            let send_msg = BankMsg::Send {
+                to_address: fee_receiver.to_string(),
+                amount: coins(amount.u128(), config.deposit_denom),
+            };
+
+            Ok(Response::default().add_message(send_msg).add_attributes([
+                attr("action", "forward_fees"),
+                attr("fee_receiver", fee_receiver),
+                attr("amount", amount),
+            ]))
+        }
+        ExecuteMsg::SetInsuranceFund {
+            contribution_bps,
+            fund,
+        } => {
+            let mut config = CONFIG.load(deps.storage)?;
+            let emissions_owner = query_emissions_owner(deps.as_ref(), &config)?;
+            ensure_eq!(info.sender, emissions_owner, ContractError::Unauthorized {});
+
+            ensure!(
+                contribution_bps <= MAX_INSURANCE_FUND_BPS,
+                ContractError::InvalidInsuranceContribution(MAX_INSURANCE_FUND_BPS)
+            );
+            if let Some(fund) = fund {
+                config.insurance_fund = Some(deps.api.addr_validate(&fund)?);
+            }
+            ensure!(
+                contribution_bps == 0 || config.insurance_fund.is_some(),
+                ContractError::InsuranceFundNotSet {}
+            );
+
+            config.insurance_fund_bps = contribution_bps;
+            CONFIG.save(deps.storage, &config)?;
+
+            let mut attrs = vec![
+                attr("action", "set_insurance_fund"),
+                attr("contribution_bps", contribution_bps.to_string()),
+            ];
+            if let Some(fund) = &config.insurance_fund {
+                attrs.push(attr("insurance_fund", fund));
+            }
+
+            Ok(Response::default().add_attributes(attrs))
+        }
+        ExecuteMsg::SetInsuranceExemptions { add, remove } => {
+            let config = CONFIG.load(deps.storage)?;
+            let emissions_owner = query_emissions_owner(deps.as_ref(), &config)?;
+            ensure_eq!(info.sender, emissions_owner, ContractError::Unauthorized {});
+
+            for addr in &remove {
+                INSURANCE_EXEMPTIONS.remove(deps.storage, &deps.api.addr_validate(addr)?);
+            }
+            for addr in &add {
+                INSURANCE_EXEMPTIONS.save(deps.storage, &deps.api.addr_validate(addr)?, &())?;
+            }
+
+            let mut attrs = vec![attr("action", "set_insurance_exemptions")];
+            if !add.is_empty() {
+                attrs.push(attr("added", add.join(",")));
+            }
+            if !remove.is_empty() {
+                attrs.push(attr("removed", remove.join(",")));
+            }
+
+            Ok(Response::default().add_attributes(attrs))
+        }
         ExecuteMsg::UpdateMarketing {
             project,
             description,
             marketing,
         } => execute_update_marketing(deps, env, info, project, description, marketing)
             .map_err(Into::into),
+        ExecuteMsg::UploadLogo(logo) => {
+            execute_upload_logo(deps, env, info, logo).map_err(Into::into)
+        }
+    }
+}
+
+/// Handles failed subscriber notifications.
+/// Subscriber errors are only reported in attributes and never revert the vxASTRO operation.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg {
+        Reply {
+            id: SUBSCRIBER_NOTIFICATION_REPLY_ID,
+            result: SubMsgResult::Err(err),
+        } => Ok(Response::new().add_attributes([
+            attr("action", "subscriber_notification_failed"),
+            attr("error", err),
+        ])),
+        _ => Err(StdError::generic_err(format!("Unexpected reply id {}", msg.id)).into()),
     }
 }
 
@@ -293,6 +882,30 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 Lock::load_at_ts(deps.storage, env.block.time.seconds(), &user, timestamp)?.into();
             to_json_binary(&lock_info_resp)
         }
+        QueryMsg::BalancesBatch { users, timestamp } => {
+            let balances = validate_batch(deps, &users)?
+                .into_iter()
+                .map(|user| {
+                    let voting_power =
+                        Lock::load_at_ts(deps.storage, env.block.time.seconds(), &user, timestamp)?
+                            .get_voting_power();
+                    Ok((user, voting_power))
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&balances)
+        }
+        QueryMsg::LockInfoBatch { users } => {
+            let lock_infos = validate_batch(deps, &users)?
+                .into_iter()
+                .map(|user| {
+                    let lock_info_resp: LockInfoResponse =
+                        Lock::load_at_ts(deps.storage, env.block.time.seconds(), &user, None)?
+                            .into();
+                    Ok((user, lock_info_resp))
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&lock_infos)
+        }
         QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
         QueryMsg::Balance { address } => {
             let user_vp = query_user_voting_power(deps, env, address, None)?;
@@ -300,7 +913,77 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         }
         QueryMsg::TokenInfo {} => to_json_binary(&query_token_info(deps, env)?),
         QueryMsg::MarketingInfo {} => to_json_binary(&query_marketing_info(deps)?),
+        QueryMsg::DownloadLogo {} => to_json_binary(&query_download_logo(deps)?),
+        QueryMsg::TokenMetadata {} => to_json_binary(&query_token_metadata(deps, env)?),
         QueryMsg::PrivilegedList {} => to_json_binary(&PRIVILEGED.load(deps.storage)?),
+        QueryMsg::Paused {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_json_binary(&PausedResponse {
+                pause_mask: config.pause_mask,
+                new_locks: config.is_paused(PAUSE_NEW_LOCKS),
+                deposits: config.is_paused(PAUSE_DEPOSITS),
+                withdrawals: config.is_paused(PAUSE_WITHDRAWALS),
+            })
+        }
+        QueryMsg::BoostInfo { user } => to_json_binary(&query_boost_info(deps, env, user)?),
+        QueryMsg::PositionById { position_id } => {
+            to_json_binary(&query_position_by_id(deps, env, position_id)?)
+        }
+        QueryMsg::UserPosition { user } => {
+            let user = deps.api.addr_validate(&user)?;
+            let position = USER_POSITIONS
+                .may_load(deps.storage, &user)?
+                .map(|position_id| query_position_by_id(deps, env, position_id))
+                .transpose()?;
+            to_json_binary(&position)
+        }
+        QueryMsg::ExitQueue {} => {
+            let config = CONFIG.load(deps.storage)?;
+            let (head, tail) = get_exit_queue_bounds(deps.storage)?;
+            to_json_binary(&ExitQueueResponse {
+                limit: config.exit_queue_limit,
+                length: tail - head,
+                total_amount: EXIT_QUEUE_TOTAL.may_load(deps.storage)?.unwrap_or_default(),
+                processed_in_block: get_processed_in_block(deps.storage, env.block.height)?,
+            })
+        }
+        QueryMsg::ExitQueuePosition { user } => {
+            to_json_binary(&query_exit_queue_position(deps, user)?)
+        }
+        QueryMsg::AccruedFees {} => {
+            to_json_binary(&ACCRUED_FEES.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::FeeExemptions {} => {
+            let exemptions = FEE_EXEMPTIONS
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&exemptions)
+        }
+        QueryMsg::InsuranceContributions { user } => {
+            let user = deps.api.addr_validate(&user)?;
+            to_json_binary(
+                &INSURANCE_CONTRIBUTIONS
+                    .may_load(deps.storage, &user)?
+                    .unwrap_or_default(),
+            )
+        }
+        QueryMsg::TotalInsuranceContributions {} => to_json_binary(
+            &TOTAL_INSURANCE_CONTRIBUTIONS
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::InsuranceExemptions {} => {
+            let exemptions = INSURANCE_EXEMPTIONS
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&exemptions)
+        }
+        QueryMsg::Subscribers {} => {
+            let subscribers = SUBSCRIBERS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&subscribers)
+        }
         QueryMsg::UsersLockInfo {
             limit,
             start_after,
@@ -346,6 +1029,108 @@ pub fn query_token_info(deps: Deps, env: Env) -> StdResult<TokenInfoResponse> {
     Ok(res)
 }
 
+/// Fetch the vxASTRO token information together with its marketing information.
+pub fn query_token_metadata(deps: Deps, env: Env) -> StdResult<TokenMetadataResponse> {
+    let token_info = query_token_info(deps, env)?;
+    let marketing_info = query_marketing_info(deps)?;
+
+    Ok(TokenMetadataResponse {
+        name: token_info.name,
+        symbol: token_info.symbol,
+        decimals: token_info.decimals,
+        total_supply: token_info.total_supply,
+        project: marketing_info.project,
+        description: marketing_info.description,
+        logo: marketing_info.logo,
+        marketing: marketing_info.marketing,
+    })
+}
+
+/// Returns normalized boost parameters of the user's vxASTRO position.
+pub fn query_boost_info(deps: Deps, env: Env, user: String) -> StdResult<BoostInfoResponse> {
+    let user = deps.api.addr_validate(&user)?;
+    let block_time = env.block.time.seconds();
+    let lock = Lock::load(deps.storage, block_time, &user)?;
+    let voting_power = lock.get_voting_power();
+    let total_voting_power = get_total_vp(deps.storage, block_time, None)?;
+
+    let power_share = if total_voting_power.is_zero() {
+        Decimal::zero()
+    } else {
+        Decimal::checked_from_ratio(voting_power, total_voting_power)
+            .map_err(|err| StdError::generic_err(err.to_string()))?
+    };
+
+    Ok(BoostInfoResponse {
+        voting_power,
+        total_voting_power,
+        power_share,
+        lock_amount: lock.amount,
+    })
+}
+
+/// Returns the owner and the lock info of the position with the given id.
+/// Closed positions are returned with empty lock info.
+pub fn query_position_by_id(deps: Deps, env: Env, position_id: u64) -> StdResult<PositionResponse> {
+    let owner = POSITION_OWNERS.load(deps.storage, position_id)?;
+    let open = USER_POSITIONS.may_load(deps.storage, &owner)? == Some(position_id);
+    let lock_info = if open {
+        Lock::load(deps.storage, env.block.time.seconds(), &owner)?.into()
+    } else {
+        Lock::default().into()
+    };
+
+    Ok(PositionResponse {
+        position_id,
+        owner,
+        open,
+        lock_info,
+    })
+}
+
+/// Returns the user's queued withdrawals with their positions in the exit queue.
+/// ETA assumes the queue is processed every block up to the per block limit.
+pub fn query_exit_queue_position(
+    deps: Deps,
+    user: String,
+) -> StdResult<Vec<QueuedWithdrawalResponse>> {
+    let user = deps.api.addr_validate(&user)?;
+    let config = CONFIG.load(deps.storage)?;
+    let (head, _) = get_exit_queue_bounds(deps.storage)?;
+    let limit = config.exit_queue_limit.max(1) as u64;
+
+    USER_EXIT_QUEUE
+        .prefix(&user)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|id| {
+            let id = id?;
+            let withdrawal = EXIT_QUEUE.load(deps.storage, id)?;
+            let position = id - head;
+            Ok(QueuedWithdrawalResponse {
+                id,
+                amount: withdrawal.amount,
+                queued_at: withdrawal.queued_at,
+                position,
+                eta_blocks: position / limit + 1,
+            })
+        })
+        .collect()
+}
+
+/// Validates addresses of a batch query and ensures the batch doesn't exceed MAX_PAGE_LIMIT.
+fn validate_batch(deps: Deps, users: &[String]) -> StdResult<Vec<Addr>> {
+    if users.len() > MAX_PAGE_LIMIT as usize {
+        return Err(StdError::generic_err(format!(
+            "Too many users in a batch query. Max: {MAX_PAGE_LIMIT}"
+        )));
+    }
+
+    users
+        .iter()
+        .map(|user| deps.api.addr_validate(user))
+        .collect()
+}
+
 pub fn query_user_voting_power(
     deps: Deps,
     env: Env,
@@ -358,3 +1143,71 @@ pub fn query_user_voting_power(
             .get_voting_power();
     Ok(voting_power)
 }
+
+/// Returns the owner of the Emissions Controller contract.
+/// The vxASTRO contract is deployed both on the Hub and on outposts,
+/// thus both config formats are tried.
+fn query_emissions_owner(deps: Deps, config: &Config) -> StdResult<Addr> {
+    deps.querier
+        .query_wasm_smart::<emissions_controller::hub::Config>(
+            &config.emissions_controller,
+            &emissions_controller::hub::QueryMsg::Config {},
+        )
+        .map(|hub_config| hub_config.owner)
+        .or_else(|_| {
+            deps.querier
+                .query_wasm_smart::<emissions_controller::outpost::Config>(
+                    &config.emissions_controller,
+                    &emissions_controller::outpost::QueryMsg::Config {},
+                )
+                .map(|outpost_config| outpost_config.owner)
+        })
+}
+
+/// Returns whether the vxASTRO contract is deployed on the Hub.
+/// Only the Hub Emissions Controller config deserializes into [`emissions_controller::hub::Config`].
+fn is_hub(deps: Deps, config: &Config) -> bool {
+    deps.querier
+        .query_wasm_smart::<emissions_controller::hub::Config>(
+            &config.emissions_controller,
+            &emissions_controller::hub::QueryMsg::Config {},
+        )
+        .is_ok()
+}
+
+/// Returns an error if the operation guarded by the pause flag is paused.
+fn ensure_not_paused(config: &Config, flag: u8, operation: &str) -> Result<(), ContractError> {
+    ensure!(
+        !config.is_paused(flag),
+        ContractError::Paused(operation.to_string())
+    );
+    Ok(())
+}
+
+/// Builds lock change notifications for all enabled subscribers.
+/// Notifications are sent with reply on error and a gas limit, thus subscriber failures are isolated.
+fn notify_subscribers(
+    storage: &dyn Storage,
+    action: &str,
+    position: &Lock,
+) -> StdResult<Vec<SubMsg>> {
+    let notification = SubscriberMsg::LockChanged {
+        user: position.user.to_string(),
+        action: action.to_string(),
+        lock_info: position.clone().into(),
+    };
+
+    SUBSCRIBERS
+        .range(storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((contract, true)) => {
+                Some(wasm_execute(contract, &notification, vec![]).map(|msg| {
+                    SubMsg::reply_on_error(msg, SUBSCRIBER_NOTIFICATION_REPLY_ID)
+                        .with_gas_limit(SUBSCRIBER_NOTIFICATION_GAS_LIMIT)
+                }))
+            }
+            Ok((_, false)) => None,
+            Err(err) => Some(Err(err)),
+        })
+        .collect()
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::{OverflowError, StdError};
+use cosmwasm_std::{OverflowError, StdError, Uint128};
 use cw20_base::ContractError as CW20Error;
 use cw_utils::PaymentError;
 use thiserror::Error;
@@ -33,9 +33,66 @@ pub enum ContractError {
     #[error("Position is already unlocking. Consider relocking to lock more tokens")]
     PositionUnlocking {},
 
+    #[error("Unlock period must be within [{min}, {max}] seconds")]
+    InvalidUnlockPeriod { min: u64, max: u64 },
+
+    #[error("Subscribers limit reached: {0}")]
+    SubscribersLimitReached(usize),
+
     #[error("Hub has not yet confirmed the unlock")]
     HubNotConfirmed {},
 
+    #[error("{0} are paused")]
+    Paused(String),
+
+    #[error("Invalid pause mask: {0}")]
+    InvalidPauseMask(u8),
+
+    #[error("Slash amount must be greater than zero")]
+    ZeroSlashAmount {},
+
+    #[error("Slash amount exceeds the position amount {0}")]
+    SlashAmountExceedsLock(Uint128),
+
+    #[error("Deposit fee must not exceed {0} bps")]
+    InvalidDepositFee(u16),
+
+    #[error("Fee receiver is not set")]
+    FeeReceiverNotSet {},
+
+    #[error("No fees to forward")]
+    NoFeesToForward {},
+
+    #[error("Insurance fund contribution must not exceed {0} bps")]
+    InvalidInsuranceContribution(u16),
+
+    #[error("Insurance fund is not set")]
+    InsuranceFundNotSet {},
+
+    #[error("Position is a permanent lock. Consider unlocking it with UnlockPermanent")]
+    PermanentLock {},
+
+    #[error("Position is not a permanent lock")]
+    NotPermanentLock {},
+
+    #[error("Permanent lock boost must not exceed {0} bps")]
+    InvalidPermanentBoost(u16),
+
+    #[error("Permanent lock boost can be set only on the Hub")]
+    PermanentBoostHubOnly {},
+
+    #[error("Exit queue limit must not exceed {0}")]
+    InvalidExitQueueLimit(u32),
+
+    #[error("Exit queue can't be disabled while it has queued withdrawals")]
+    ExitQueueNotEmpty {},
+
+    #[error("Exit queue is empty")]
+    ExitQueueEmpty {},
+
+    #[error("Exit queue limit of {0} withdrawals per block is reached")]
+    ExitQueueBlockLimitReached(u32),
+
     #[error("Failed to migrate contract")]
     MigrationError {},
 }
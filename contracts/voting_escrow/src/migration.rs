@@ -1,24 +1,50 @@
 #![cfg(not(tarpaulin_include))]
 
-use cosmwasm_std::{DepsMut, Empty, Env, Response};
+use cosmwasm_std::{Addr, DepsMut, Empty, Env, Order, Response, StdResult, Uint128};
 use cw2::{get_contract_version, set_contract_version};
 
 use crate::contract::{CONTRACT_NAME, CONTRACT_VERSION};
 use crate::error::ContractError;
+use crate::state::{get_or_assign_position_id, CONFIG, LOCKED, PERMANENT_BOOST, TOTAL_PERMANENT};
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, env: Env, _msg: Empty) -> Result<Response, ContractError> {
     let contract_version = get_contract_version(deps.storage)?;
 
     match contract_version.contract.as_ref() {
         CONTRACT_NAME => match contract_version.version.as_ref() {
-            "1.0.0" => Ok(()),
-            "1.1.0" => Ok(()),
+            "1.0.0" | "1.1.0" | "1.2.0" => Ok(()),
             _ => Err(ContractError::MigrationError {}),
         },
         _ => Err(ContractError::MigrationError {}),
     }?;
 
+    // Persist new config fields with their default values
+    let config = CONFIG.load(deps.storage)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    // Checkpoint permanent lock totals so historical queries start from the migration time
+    let block_ts = env.block.time.seconds();
+    if TOTAL_PERMANENT.may_load(deps.storage)?.is_none() {
+        TOTAL_PERMANENT.save(deps.storage, &Uint128::zero(), block_ts)?;
+    }
+    if PERMANENT_BOOST.may_load(deps.storage)?.is_none() {
+        PERMANENT_BOOST.save(deps.storage, &config.permanent_boost_bps, block_ts)?;
+    }
+
+    // Assign ids to positions opened before position ids were introduced
+    let open_positions = LOCKED
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((user, lock)) if !lock.amount.is_zero() => Some(Ok(user)),
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        })
+        .collect::<StdResult<Vec<Addr>>>()?;
+    for user in &open_positions {
+        get_or_assign_position_id(deps.storage, user, false)?;
+    }
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::new()
@@ -1,17 +1,47 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{ensure, Addr, StdResult, Storage, Uint128};
-use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
 
-use astroport_governance::voting_escrow::{Config, LockInfoResponse, UnlockStatus};
+use astroport_governance::voting_escrow::{
+    Config, LockInfoResponse, QueuedWithdrawal, UnlockStatus,
+};
 
 use crate::error::ContractError;
 
-pub const UNLOCK_PERIOD: u64 = 86400 * 14; // 2 weeks
-
 /// Stores the contract config at the given key
 pub const CONFIG: Item<Config> = Item::new("config");
 /// Keeps the list of addresses that are allowed to instantly unlock xASTRO
 pub const PRIVILEGED: Item<Vec<Addr>> = Item::new("privileged");
+/// Contracts subscribed to lock changes with their enable flags
+pub const SUBSCRIBERS: Map<&Addr, bool> = Map::new("subscribers");
+/// Collected deposit fees which are not yet forwarded to the fee receiver
+pub const ACCRUED_FEES: Item<Uint128> = Item::new("accrued_fees");
+/// Addresses exempted from the deposit fee
+pub const FEE_EXEMPTIONS: Map<&Addr, ()> = Map::new("fee_exemptions");
+/// Addresses exempted from insurance fund contributions
+pub const INSURANCE_EXEMPTIONS: Map<&Addr, ()> = Map::new("insurance_exemptions");
+/// Lifetime insurance fund contributions per user
+pub const INSURANCE_CONTRIBUTIONS: Map<&Addr, Uint128> = Map::new("insurance_contributions");
+/// Lifetime insurance fund contributions of all users
+pub const TOTAL_INSURANCE_CONTRIBUTIONS: Item<Uint128> = Item::new("total_insurance_contributions");
+/// Id of the last assigned position
+pub const POSITION_COUNT: Item<u64> = Item::new("position_count");
+/// Owners of all positions ever opened. Closed positions are kept for reference
+pub const POSITION_OWNERS: Map<u64, Addr> = Map::new("position_owners");
+/// Ids of users' open positions
+pub const USER_POSITIONS: Map<&Addr, u64> = Map::new("user_positions");
+/// Queued withdrawals by their id. Ids are assigned sequentially and processed in FIFO order
+pub const EXIT_QUEUE: Map<u64, QueuedWithdrawal> = Map::new("exit_queue");
+/// Ids of users' queued withdrawals. (user, withdrawal id) -> ()
+pub const USER_EXIT_QUEUE: Map<(&Addr, u64), ()> = Map::new("user_exit_queue");
+/// Id of the next withdrawal to process
+pub const EXIT_QUEUE_HEAD: Item<u64> = Item::new("exit_queue_head");
+/// Id assigned to the next queued withdrawal
+pub const EXIT_QUEUE_TAIL: Item<u64> = Item::new("exit_queue_tail");
+/// Total amount of xASTRO reserved for queued withdrawals
+pub const EXIT_QUEUE_TOTAL: Item<Uint128> = Item::new("exit_queue_total");
+/// Number of withdrawals processed in a block. (block height, processed)
+pub const EXIT_QUEUE_PROCESSED: Item<(u64, u32)> = Item::new("exit_queue_processed");
 
 fn default_addr() -> Addr {
     Addr::unchecked("")
@@ -23,12 +53,18 @@ pub struct Lock {
     pub amount: Uint128,
     /// Unlocking status. None for positions in Locked state
     pub unlock_status: Option<UnlockStatus>,
+    /// Whether the position is a permanent lock which can't be unlocked directly
+    #[serde(default)]
+    pub permanent: bool,
     /// NOTE: The fields below are not stored in the state, they are used only in the contract logic
     #[serde(default = "default_addr", skip)]
     pub user: Addr,
     /// Current block timestamp.
     #[serde(skip)]
     pub block_time: u64,
+    /// Permanent lock boost in basis points at the time the lock was loaded
+    #[serde(skip)]
+    pub permanent_boost_bps: u16,
 }
 
 impl Default for Lock {
@@ -36,8 +72,10 @@ impl Default for Lock {
         Lock {
             amount: Uint128::zero(),
             unlock_status: None,
+            permanent: false,
             user: default_addr(),
             block_time: 0,
+            permanent_boost_bps: 0,
         }
     }
 }
@@ -58,6 +96,7 @@ impl Lock {
         Ok(Lock {
             user: user.clone(),
             block_time,
+            permanent_boost_bps: get_permanent_boost(storage, block_time, timestamp)?,
             ..lock
         })
     }
@@ -78,6 +117,11 @@ impl Lock {
 
         self.amount += amount;
         LOCKED.save(storage, &self.user, self, self.block_time)?;
+        if self.permanent {
+            TOTAL_PERMANENT.update(storage, self.block_time, |total| -> StdResult<_> {
+                Ok(total.unwrap_or_default() + amount)
+            })?;
+        }
         TOTAL_POWER
             .update(storage, self.block_time, |total| {
                 Ok(total.unwrap_or_default() + amount)
@@ -85,14 +129,54 @@ impl Lock {
             .map(|_| ())
     }
 
-    pub fn unlock(&mut self, storage: &mut dyn Storage) -> Result<u64, ContractError> {
+    /// Converts the locked position into a permanent lock.
+    pub fn make_permanent(&mut self, storage: &mut dyn Storage) -> Result<(), ContractError> {
+        ensure!(!self.amount.is_zero(), ContractError::ZeroBalance {});
+        ensure!(
+            self.unlock_status.is_none(),
+            ContractError::PositionUnlocking {}
+        );
+        ensure!(!self.permanent, ContractError::PermanentLock {});
+
+        self.permanent = true;
+        LOCKED.save(storage, &self.user, self, self.block_time)?;
+
+        TOTAL_PERMANENT.update(storage, self.block_time, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default() + self.amount)
+        })?;
+
+        Ok(())
+    }
+
+    /// Converts the permanent lock back to a normal one and starts unlocking it.
+    pub fn unlock_permanent(
+        &mut self,
+        storage: &mut dyn Storage,
+        unlock_period: u64,
+    ) -> Result<u64, ContractError> {
+        ensure!(self.permanent, ContractError::NotPermanentLock {});
+
+        self.permanent = false;
+        TOTAL_PERMANENT.update(storage, self.block_time, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default().checked_sub(self.amount)?)
+        })?;
+
+        self.unlock(storage, unlock_period)
+    }
+
+    pub fn unlock(
+        &mut self,
+        storage: &mut dyn Storage,
+        unlock_period: u64,
+    ) -> Result<u64, ContractError> {
         ensure!(!self.amount.is_zero(), ContractError::ZeroBalance {});
         ensure!(
             self.unlock_status.is_none(),
             ContractError::PositionUnlocking {}
         );
+        ensure!(!self.permanent, ContractError::PermanentLock {});
 
-        let end = self.block_time + UNLOCK_PERIOD;
+        let end = self.block_time + unlock_period;
         self.unlock_status = Some(UnlockStatus {
             end,
             hub_confirmed: false,
@@ -112,6 +196,8 @@ impl Lock {
         storage: &mut dyn Storage,
         amount: Uint128,
     ) -> Result<(), ContractError> {
+        ensure!(!self.permanent, ContractError::PermanentLock {});
+
         self.amount = self.amount.checked_sub(amount)?;
         LOCKED.save(storage, &self.user, self, self.block_time)?;
 
@@ -123,6 +209,43 @@ impl Lock {
         Ok(())
     }
 
+    /// Reduces the position by the given amount.
+    /// Voting power is removed from the total only for locked positions
+    /// as unlocking positions are already excluded from it.
+    /// Permanent locks can be slashed as well. Fully slashed positions are removed.
+    pub fn slash(
+        &mut self,
+        storage: &mut dyn Storage,
+        amount: Uint128,
+    ) -> Result<(), ContractError> {
+        ensure!(!amount.is_zero(), ContractError::ZeroSlashAmount {});
+        self.amount = self
+            .amount
+            .checked_sub(amount)
+            .map_err(|_| ContractError::SlashAmountExceedsLock(self.amount))?;
+
+        if self.unlock_status.is_none() {
+            TOTAL_POWER.update(storage, self.block_time, |total| -> StdResult<_> {
+                Ok(total.unwrap_or_default().checked_sub(amount)?)
+            })?;
+        }
+        if self.permanent {
+            TOTAL_PERMANENT.update(storage, self.block_time, |total| -> StdResult<_> {
+                Ok(total.unwrap_or_default().checked_sub(amount)?)
+            })?;
+        }
+
+        if self.amount.is_zero() {
+            self.unlock_status = None;
+            self.permanent = false;
+            LOCKED.remove(storage, &self.user, self.block_time)?;
+        } else {
+            LOCKED.save(storage, &self.user, self, self.block_time)?;
+        }
+
+        Ok(())
+    }
+
     pub fn confirm_unlock(&mut self, storage: &mut dyn Storage) -> StdResult<()> {
         // If for some reason the unlock status is not set,
         // we skip it silently so relayer can finish IBC transaction.
@@ -174,6 +297,8 @@ impl Lock {
     pub fn get_voting_power(&self) -> Uint128 {
         if self.unlock_status.is_some() {
             Uint128::zero()
+        } else if self.permanent {
+            self.amount + apply_permanent_boost(self.amount, self.permanent_boost_bps)
         } else {
             self.amount
         }
@@ -185,22 +310,147 @@ impl From<Lock> for LockInfoResponse {
         LockInfoResponse {
             amount: lock.amount,
             unlock_status: lock.unlock_status,
+            permanent: lock.permanent,
         }
     }
 }
 
-pub fn get_total_vp(
+/// Returns the id of the user's position. Assigns and saves a new id if the user has no open position.
+/// If the position is closed, its id is released so the next lock opens a new position.
+pub fn get_or_assign_position_id(
+    storage: &mut dyn Storage,
+    user: &Addr,
+    closed: bool,
+) -> StdResult<u64> {
+    let position_id = match USER_POSITIONS.may_load(storage, user)? {
+        Some(position_id) => position_id,
+        None => {
+            let position_id = POSITION_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+            POSITION_COUNT.save(storage, &position_id)?;
+            POSITION_OWNERS.save(storage, position_id, user)?;
+            USER_POSITIONS.save(storage, user, &position_id)?;
+            position_id
+        }
+    };
+
+    if closed {
+        USER_POSITIONS.remove(storage, user);
+    }
+
+    Ok(position_id)
+}
+
+/// Puts the withdrawal at the end of the exit queue and returns its id.
+/// Queued xASTRO is reserved in the contract until the withdrawal is processed.
+pub fn enqueue_withdrawal(
+    storage: &mut dyn Storage,
+    user: &Addr,
+    amount: Uint128,
+    block_time: u64,
+) -> StdResult<u64> {
+    let id = EXIT_QUEUE_TAIL.may_load(storage)?.unwrap_or_default();
+    EXIT_QUEUE_TAIL.save(storage, &(id + 1))?;
+    EXIT_QUEUE.save(
+        storage,
+        id,
+        &QueuedWithdrawal {
+            user: user.clone(),
+            amount,
+            queued_at: block_time,
+        },
+    )?;
+    USER_EXIT_QUEUE.save(storage, (user, id), &())?;
+    let total = EXIT_QUEUE_TOTAL.may_load(storage)?.unwrap_or_default();
+    EXIT_QUEUE_TOTAL.save(storage, &(total + amount))?;
+
+    Ok(id)
+}
+
+/// Returns the ids of the next withdrawal to process and the next queued withdrawal.
+pub fn get_exit_queue_bounds(storage: &dyn Storage) -> StdResult<(u64, u64)> {
+    Ok((
+        EXIT_QUEUE_HEAD.may_load(storage)?.unwrap_or_default(),
+        EXIT_QUEUE_TAIL.may_load(storage)?.unwrap_or_default(),
+    ))
+}
+
+/// Returns the number of withdrawals processed in the given block.
+pub fn get_processed_in_block(storage: &dyn Storage, height: u64) -> StdResult<u32> {
+    Ok(match EXIT_QUEUE_PROCESSED.may_load(storage)? {
+        Some((processed_height, processed)) if processed_height == height => processed,
+        _ => 0,
+    })
+}
+
+/// Removes up to `limit` withdrawals from the head of the exit queue and returns them.
+pub fn dequeue_withdrawals(
+    storage: &mut dyn Storage,
+    height: u64,
+    limit: u32,
+) -> StdResult<Vec<QueuedWithdrawal>> {
+    let (head, tail) = get_exit_queue_bounds(storage)?;
+    let new_head = tail.min(head + limit as u64);
+
+    let withdrawals = (head..new_head)
+        .map(|id| {
+            let withdrawal = EXIT_QUEUE.load(storage, id)?;
+            EXIT_QUEUE.remove(storage, id);
+            USER_EXIT_QUEUE.remove(storage, (&withdrawal.user, id));
+            Ok(withdrawal)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let amount: Uint128 = withdrawals.iter().map(|w| w.amount).sum();
+    let total = EXIT_QUEUE_TOTAL.may_load(storage)?.unwrap_or_default();
+    EXIT_QUEUE_TOTAL.save(storage, &total.checked_sub(amount)?)?;
+    EXIT_QUEUE_HEAD.save(storage, &new_head)?;
+
+    let processed = get_processed_in_block(storage, height)?;
+    EXIT_QUEUE_PROCESSED.save(storage, &(height, processed + withdrawals.len() as u32))?;
+
+    Ok(withdrawals)
+}
+
+/// Returns the extra voting power of a permanent lock of the given amount.
+fn apply_permanent_boost(amount: Uint128, boost_bps: u16) -> Uint128 {
+    amount.multiply_ratio(boost_bps, 10000u16)
+}
+
+/// Returns the permanent lock boost in basis points.
+pub fn get_permanent_boost(
     storage: &dyn Storage,
     block_time: u64,
     timestamp: Option<u64>,
-) -> StdResult<Uint128> {
+) -> StdResult<u16> {
     match timestamp.unwrap_or(block_time) {
-        timestamp if timestamp == block_time => TOTAL_POWER.may_load(storage),
-        timestamp => TOTAL_POWER.may_load_at_height(storage, timestamp),
+        timestamp if timestamp == block_time => PERMANENT_BOOST.may_load(storage),
+        timestamp => PERMANENT_BOOST.may_load_at_height(storage, timestamp),
     }
     .map(Option::unwrap_or_default)
 }
 
+/// Returns the total voting power including the boost of permanent locks.
+pub fn get_total_vp(
+    storage: &dyn Storage,
+    block_time: u64,
+    timestamp: Option<u64>,
+) -> StdResult<Uint128> {
+    let (total, total_permanent) = match timestamp.unwrap_or(block_time) {
+        timestamp if timestamp == block_time => (
+            TOTAL_POWER.may_load(storage)?,
+            TOTAL_PERMANENT.may_load(storage)?,
+        ),
+        timestamp => (
+            TOTAL_POWER.may_load_at_height(storage, timestamp)?,
+            TOTAL_PERMANENT.may_load_at_height(storage, timestamp)?,
+        ),
+    };
+    let boost_bps = get_permanent_boost(storage, block_time, timestamp)?;
+
+    Ok(total.unwrap_or_default()
+        + apply_permanent_boost(total_permanent.unwrap_or_default(), boost_bps))
+}
+
 /// Stores historical balances for each account
 pub const LOCKED: SnapshotMap<&Addr, Lock> = SnapshotMap::new(
     "locked",
@@ -209,9 +459,26 @@ pub const LOCKED: SnapshotMap<&Addr, Lock> = SnapshotMap::new(
     Strategy::EveryBlock,
 );
 
+/// Stores historical total amount of locked xASTRO (excluding the boost of permanent locks)
 pub const TOTAL_POWER: SnapshotItem<Uint128> = SnapshotItem::new(
     "total_power",
     "total_power__checkpoints",
     "total_power__changelog",
     Strategy::EveryBlock,
 );
+
+/// Stores historical total amount of xASTRO locked in permanent locks
+pub const TOTAL_PERMANENT: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_permanent",
+    "total_permanent__checkpoints",
+    "total_permanent__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Stores historical permanent lock boost in basis points
+pub const PERMANENT_BOOST: SnapshotItem<u16> = SnapshotItem::new(
+    "permanent_boost",
+    "permanent_boost__checkpoints",
+    "permanent_boost__changelog",
+    Strategy::EveryBlock,
+);
@@ -7,7 +7,7 @@ use cw_multi_test::{AppResponse, BankSudo, BasicApp, Contract, ContractWrapper,
 
 use astroport_governance::emissions_controller;
 use astroport_governance::voting_escrow::{
-    ExecuteMsg, InstantiateMsg, LockInfoResponse, QueryMsg, UpdateMarketingInfo,
+    ExecuteMsg, InstantiateMsg, LockInfoResponse, PositionResponse, QueryMsg, UpdateMarketingInfo,
 };
 
 fn vxastro_contract() -> Box<dyn Contract<Empty>> {
@@ -184,4 +184,20 @@ impl EscrowHelper {
             },
         )
     }
+
+    pub fn position_by_id(&self, position_id: u64) -> StdResult<PositionResponse> {
+        self.app.wrap().query_wasm_smart(
+            &self.vxastro_contract,
+            &QueryMsg::PositionById { position_id },
+        )
+    }
+
+    pub fn user_position(&self, user: &Addr) -> StdResult<Option<PositionResponse>> {
+        self.app.wrap().query_wasm_smart(
+            &self.vxastro_contract,
+            &QueryMsg::UserPosition {
+                user: user.to_string(),
+            },
+        )
+    }
 }
@@ -1,11 +1,17 @@
-use cosmwasm_std::{coin, Addr};
-use cw20::{BalanceResponse, LogoInfo, MarketingInfoResponse, TokenInfoResponse};
-use cw_multi_test::Executor;
+use cosmwasm_std::{coin, Addr, Binary, Decimal, Uint128};
+use cw20::{
+    BalanceResponse, DownloadLogoResponse, EmbeddedLogo, Logo, LogoInfo, MarketingInfoResponse,
+    TokenInfoResponse,
+};
+use cw_multi_test::{AppResponse, Executor};
 use cw_utils::PaymentError;
 
-use astroport_governance::voting_escrow::{Config, LockInfoResponse, QueryMsg, UnlockStatus};
+use astroport_governance::emissions_controller::consts::MAX_PAGE_LIMIT;
+use astroport_governance::voting_escrow::{
+    BoostInfoResponse, Config, ExecuteMsg, LockInfoResponse, PositionResponse, QueryMsg,
+    TokenMetadataResponse, UnlockStatus, DEFAULT_UNLOCK_PERIOD,
+};
 use astroport_voting_escrow::error::ContractError;
-use astroport_voting_escrow::state::UNLOCK_PERIOD;
 
 use crate::helper::EscrowHelper;
 
@@ -139,16 +145,17 @@ fn test_unlock() {
         LockInfoResponse {
             amount: xastro_coin.amount,
             unlock_status: Some(UnlockStatus {
-                end: start_ts + UNLOCK_PERIOD,
+                end: start_ts + DEFAULT_UNLOCK_PERIOD,
                 hub_confirmed: false
             }),
+            permanent: false,
         }
     );
 
     // Try to withdraw before unlock
     let err = helper.withdraw(&user1).unwrap_err();
     assert_eq!(
-        ContractError::UnlockPeriodNotExpired(start_ts + UNLOCK_PERIOD),
+        ContractError::UnlockPeriodNotExpired(start_ts + DEFAULT_UNLOCK_PERIOD),
         err.downcast().unwrap()
     );
 
@@ -169,6 +176,7 @@ fn test_unlock() {
         LockInfoResponse {
             amount: xastro_coin.amount,
             unlock_status: None,
+            permanent: false,
         }
     );
 
@@ -179,7 +187,7 @@ fn test_unlock() {
         .unwrap()
         .amount;
     helper.unlock(&user1).unwrap();
-    helper.timetravel(UNLOCK_PERIOD);
+    helper.timetravel(DEFAULT_UNLOCK_PERIOD);
 
     let err = helper.withdraw(&user1).unwrap_err();
     assert_eq!(
@@ -204,7 +212,7 @@ fn test_unlock() {
     // Relocks before hub confirmation doesn't harm
     helper.lock(&user1, &[xastro_coin.clone()]).unwrap();
     helper.unlock(&user1).unwrap();
-    helper.timetravel(UNLOCK_PERIOD);
+    helper.timetravel(DEFAULT_UNLOCK_PERIOD);
     // Confirm cant withdraw
     let err = helper.withdraw(&user1).unwrap_err();
     assert_eq!(
@@ -217,7 +225,7 @@ fn test_unlock() {
 
     let start_ts = helper.app.block_info().time.seconds();
     helper.unlock(&user1).unwrap();
-    helper.timetravel(UNLOCK_PERIOD);
+    helper.timetravel(DEFAULT_UNLOCK_PERIOD);
     helper.confirm_unlock(&user1).unwrap();
 
     // Can withdraw now
@@ -230,9 +238,10 @@ fn test_unlock() {
         LockInfoResponse {
             amount: xastro_coin.amount,
             unlock_status: Some(UnlockStatus {
-                end: start_ts + UNLOCK_PERIOD,
+                end: start_ts + DEFAULT_UNLOCK_PERIOD,
                 hub_confirmed: false
             }),
+            permanent: false,
         }
     );
 }
@@ -253,6 +262,7 @@ fn test_general_queries() {
         .query_wasm_smart(&helper.vxastro_contract, &QueryMsg::Config {})
         .unwrap();
     assert_eq!(config.deposit_denom, xastro_denom);
+    assert_eq!(config.unlock_period, DEFAULT_UNLOCK_PERIOD);
 
     let token_info: TokenInfoResponse = helper
         .app
@@ -349,3 +359,310 @@ fn test_general_queries() {
         }
     );
 }
+
+#[test]
+fn test_logo_and_token_metadata() {
+    let xastro_denom = "xastro";
+    let mut helper = EscrowHelper::new(xastro_denom);
+
+    let user1 = Addr::unchecked("user1");
+    let xastro_coin = coin(100, xastro_denom);
+    helper.mint_tokens(&user1, &[xastro_coin.clone()]).unwrap();
+    helper.lock(&user1, &[xastro_coin.clone()]).unwrap();
+
+    let png_header = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+    let logo = Logo::Embedded(EmbeddedLogo::Png(Binary::from(png_header.as_slice())));
+    let upload_msg = ExecuteMsg::UploadLogo(logo);
+
+    let err = helper
+        .app
+        .execute_contract(
+            Addr::unchecked("random"),
+            helper.vxastro_contract.clone(),
+            &upload_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Cw20Base(cw20_base::ContractError::Unauthorized {}),
+        err.downcast().unwrap(),
+    );
+
+    let owner = helper.owner.clone();
+    helper
+        .app
+        .execute_contract(owner, helper.vxastro_contract.clone(), &upload_msg, &[])
+        .unwrap();
+
+    let logo: DownloadLogoResponse = helper
+        .app
+        .wrap()
+        .query_wasm_smart(&helper.vxastro_contract, &QueryMsg::DownloadLogo {})
+        .unwrap();
+    assert_eq!(
+        logo,
+        DownloadLogoResponse {
+            mime_type: "image/png".to_string(),
+            data: Binary::from(png_header.as_slice()),
+        }
+    );
+
+    let metadata: TokenMetadataResponse = helper
+        .app
+        .wrap()
+        .query_wasm_smart(&helper.vxastro_contract, &QueryMsg::TokenMetadata {})
+        .unwrap();
+    assert_eq!(
+        metadata,
+        TokenMetadataResponse {
+            name: "Vote Escrowed xASTRO".to_string(),
+            symbol: "vxASTRO".to_string(),
+            decimals: 6,
+            total_supply: xastro_coin.amount,
+            project: None,
+            description: None,
+            logo: Some(LogoInfo::Embedded),
+            marketing: Some(helper.owner.clone()),
+        }
+    );
+}
+
+#[test]
+fn test_batch_queries() {
+    let xastro_denom = "xastro";
+    let mut helper = EscrowHelper::new(xastro_denom);
+
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+    let no_lock_user = Addr::unchecked("no_lock_user");
+    for (user, amount) in [(&user1, 100), (&user2, 200)] {
+        let xastro_coin = coin(amount, xastro_denom);
+        helper.mint_tokens(user, &[xastro_coin.clone()]).unwrap();
+        helper.lock(user, &[xastro_coin]).unwrap();
+    }
+    let users = vec![
+        user1.to_string(),
+        user2.to_string(),
+        no_lock_user.to_string(),
+    ];
+
+    helper.timetravel(100);
+    // Snapshots taken at a timestamp reflect the state before that block's changes
+    let lock_ts = helper.app.block_info().time.seconds();
+    helper.timetravel(1);
+    helper.unlock(&user2).unwrap();
+
+    let balances: Vec<(Addr, Uint128)> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &helper.vxastro_contract,
+            &QueryMsg::BalancesBatch {
+                users: users.clone(),
+                timestamp: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        balances,
+        vec![
+            (user1.clone(), Uint128::new(100)),
+            (user2.clone(), Uint128::zero()),
+            (no_lock_user.clone(), Uint128::zero()),
+        ]
+    );
+
+    let balances: Vec<(Addr, Uint128)> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &helper.vxastro_contract,
+            &QueryMsg::BalancesBatch {
+                users: users.clone(),
+                timestamp: Some(lock_ts),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        balances,
+        vec![
+            (user1.clone(), Uint128::new(100)),
+            (user2.clone(), Uint128::new(200)),
+            (no_lock_user.clone(), Uint128::zero()),
+        ]
+    );
+
+    let lock_infos: Vec<(Addr, LockInfoResponse)> = helper
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &helper.vxastro_contract,
+            &QueryMsg::LockInfoBatch {
+                users: users.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        lock_infos,
+        vec![
+            (user1.clone(), helper.lock_info(&user1, None).unwrap()),
+            (user2.clone(), helper.lock_info(&user2, None).unwrap()),
+            (
+                no_lock_user.clone(),
+                helper.lock_info(&no_lock_user, None).unwrap()
+            ),
+        ]
+    );
+
+    let too_many_users = (0..=MAX_PAGE_LIMIT)
+        .map(|i| format!("user{i}"))
+        .collect::<Vec<_>>();
+    let err = helper
+        .app
+        .wrap()
+        .query_wasm_smart::<Vec<(Addr, LockInfoResponse)>>(
+            &helper.vxastro_contract,
+            &QueryMsg::LockInfoBatch {
+                users: too_many_users,
+            },
+        )
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Too many users in a batch query. Max: 50"));
+}
+
+#[test]
+fn test_boost_info() {
+    let xastro_denom = "xastro";
+    let mut helper = EscrowHelper::new(xastro_denom);
+
+    let query_boost_info = |helper: &EscrowHelper, user: &Addr| -> BoostInfoResponse {
+        helper
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &helper.vxastro_contract,
+                &QueryMsg::BoostInfo {
+                    user: user.to_string(),
+                },
+            )
+            .unwrap()
+    };
+
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    // No positions yet
+    assert_eq!(
+        query_boost_info(&helper, &user1),
+        BoostInfoResponse {
+            voting_power: Uint128::zero(),
+            total_voting_power: Uint128::zero(),
+            power_share: Decimal::zero(),
+            lock_amount: Uint128::zero(),
+        }
+    );
+
+    for (user, amount) in [(&user1, 100), (&user2, 300)] {
+        let xastro_coin = coin(amount, xastro_denom);
+        helper.mint_tokens(user, &[xastro_coin.clone()]).unwrap();
+        helper.lock(user, &[xastro_coin]).unwrap();
+    }
+
+    assert_eq!(
+        query_boost_info(&helper, &user1),
+        BoostInfoResponse {
+            voting_power: Uint128::new(100),
+            total_voting_power: Uint128::new(400),
+            power_share: Decimal::percent(25),
+            lock_amount: Uint128::new(100),
+        }
+    );
+
+    // Unlocking positions have no boost
+    helper.unlock(&user2).unwrap();
+    assert_eq!(
+        query_boost_info(&helper, &user2),
+        BoostInfoResponse {
+            voting_power: Uint128::zero(),
+            total_voting_power: Uint128::new(100),
+            power_share: Decimal::zero(),
+            lock_amount: Uint128::new(300),
+        }
+    );
+    assert_eq!(
+        query_boost_info(&helper, &user1).power_share,
+        Decimal::one()
+    );
+}
+
+#[test]
+fn test_position_ids() {
+    let xastro_denom = "xastro";
+    let mut helper = EscrowHelper::new(xastro_denom);
+
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+    let xastro_coin = coin(100, xastro_denom);
+    helper
+        .mint_tokens(&user1, &[coin(300, xastro_denom)])
+        .unwrap();
+    helper.mint_tokens(&user2, &[xastro_coin.clone()]).unwrap();
+
+    let position_id_attr = |resp: AppResponse| {
+        resp.events
+            .iter()
+            .flat_map(|event| &event.attributes)
+            .find(|attr| attr.key == "position_id")
+            .map(|attr| attr.value.clone())
+            .unwrap()
+    };
+
+    let resp = helper.lock(&user1, &[xastro_coin.clone()]).unwrap();
+    assert_eq!(position_id_attr(resp), "1");
+    let resp = helper.lock(&user2, &[xastro_coin.clone()]).unwrap();
+    assert_eq!(position_id_attr(resp), "2");
+
+    // Deposits to an existing position keep its id
+    let resp = helper.lock(&user1, &[xastro_coin.clone()]).unwrap();
+    assert_eq!(position_id_attr(resp), "1");
+
+    assert_eq!(
+        helper.position_by_id(1).unwrap(),
+        PositionResponse {
+            position_id: 1,
+            owner: user1.clone(),
+            open: true,
+            lock_info: LockInfoResponse {
+                amount: Uint128::new(200),
+                unlock_status: None,
+                permanent: false,
+            },
+        }
+    );
+
+    let resp = helper.unlock(&user1).unwrap();
+    assert_eq!(position_id_attr(resp), "1");
+    helper.timetravel(DEFAULT_UNLOCK_PERIOD);
+    helper.confirm_unlock(&user1).unwrap();
+    let resp = helper.withdraw(&user1).unwrap();
+    assert_eq!(position_id_attr(resp), "1");
+
+    // Withdrawn position is closed
+    let position = helper.position_by_id(1).unwrap();
+    assert!(!position.open);
+    assert_eq!(position.lock_info.amount, Uint128::zero());
+    assert_eq!(helper.user_position(&user1).unwrap(), None);
+
+    // New lock opens a new position
+    let resp = helper.lock(&user1, &[xastro_coin]).unwrap();
+    assert_eq!(position_id_attr(resp), "3");
+    let position = helper.position_by_id(3).unwrap();
+    assert_eq!(position.owner, user1);
+    assert!(position.open);
+    assert_eq!(position.lock_info.amount.u128(), 100);
+    assert_eq!(helper.user_position(&user1).unwrap(), Some(position));
+
+    helper.position_by_id(4).unwrap_err();
+}
@@ -1,9 +1,14 @@
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter, Result};
 use std::ops::RangeInclusive;
 use std::str::FromStr;
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, CosmosMsg, Decimal, StdError, StdResult, Uint128, Uint64};
+use cosmwasm_std::{
+    from_json, to_json_vec, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, StdError, StdResult,
+    Uint128, Uint64, WasmMsg,
+};
+use sha2::{Digest, Sha256};
 
 pub const MINIMUM_PROPOSAL_REQUIRED_THRESHOLD_PERCENTAGE: u64 = 33;
 pub const MAX_PROPOSAL_REQUIRED_THRESHOLD_PERCENTAGE: u64 = 100;
@@ -13,10 +18,26 @@ pub const MINIMUM_PROPOSAL_REQUIRED_QUORUM_PERCENTAGE: &str = "0.01";
 pub const VOTING_PERIOD_INTERVAL: RangeInclusive<u64> = 33230..=7 * 33230;
 /// From 0.5 to 2 days in blocks
 pub const DELAY_INTERVAL: RangeInclusive<u64> = 16615..=66460;
+/// Reveal window of commit-reveal proposals. From 0.5 to 2 days in blocks
+pub const REVEAL_PERIOD_INTERVAL: RangeInclusive<u64> = 16615..=66460;
+/// 1 day in blocks
+pub const DEFAULT_REVEAL_PERIOD: u64 = 33230;
 /// From 1 to 14 days in blocks
 pub const EXPIRATION_PERIOD_INTERVAL: RangeInclusive<u64> = 33230..=14 * 33230;
+/// Max review window during which the proposer can amend or withdraw a proposal. 1 day in blocks
+pub const MAX_REVIEW_PERIOD: u64 = 33230;
+/// Max minimum xASTRO age required to submit a proposal. 30 days in seconds
+pub const MAX_XASTRO_AGE: u64 = 30 * 86400;
+/// Period in seconds over which relayed votes are rate-limited. 1 day
+pub const RELAY_QUOTA_PERIOD: u64 = 86400;
+/// Max number of votes relayed on behalf of a single user per [`RELAY_QUOTA_PERIOD`]
+pub const MAX_USER_RELAYED_VOTES: u64 = 5;
+/// Max number of the latest concluded proposals whose turnout is used to compute the adaptive quorum
+pub const MAX_QUORUM_LOOKBACK: u64 = 30;
 // from 10k to 60k $xASTRO
 pub const DEPOSIT_INTERVAL: RangeInclusive<u128> = 10000000000..=60000000000;
+/// Proposals labeled with this tag pause registered target contracts while they are live
+pub const CRITICAL_PROPOSAL_TAG: &str = "critical";
 
 /// Proposal validation attributes
 const MIN_TITLE_LENGTH: usize = 4;
@@ -25,6 +46,13 @@ const MIN_DESC_LENGTH: usize = 4;
 const MAX_DESC_LENGTH: usize = 1024;
 const MIN_LINK_LENGTH: usize = 12;
 const MAX_LINK_LENGTH: usize = 128;
+const MAX_TAG_LENGTH: usize = 32;
+/// Max number of tags a proposal can be labeled with
+pub const MAX_PROPOSAL_TAGS: usize = 5;
+/// Max number of votes cast in a single batch
+pub const MAX_BATCH_VOTES: usize = 20;
+/// Max number of registered conclusion hooks
+pub const MAX_CONCLUSION_HOOKS: usize = 10;
 
 /// Special characters that are allowed in proposal text
 const SAFE_TEXT_CHARS: &str = "!&?#()*+'-./\"";
@@ -67,6 +95,12 @@ pub enum ExecuteMsg {
         messages: Vec<CosmosMsg>,
         /// If proposal should be executed on a remote chain this field should specify governance channel
         ibc_channel: Option<String>,
+        /// Whether votes are hidden until the reveal window (commit-reveal voting)
+        #[serde(default)]
+        commit_reveal: bool,
+        /// Tags from the governance-managed vocabulary the proposal is labeled with
+        #[serde(default)]
+        tags: Vec<String>,
     },
     /// Cast a vote for an active proposal
     CastVote {
@@ -75,6 +109,13 @@ pub enum ExecuteMsg {
         /// Vote option
         vote: ProposalVoteOption,
     },
+    /// Cast votes on multiple proposals at once.
+    /// Votes which can't be cast (e.g. the sender already voted or voting has ended) are skipped.
+    /// Outcome of each vote is reported in a separate `batch_vote` event
+    CastVotes {
+        /// (proposal identifier, vote option) pairs
+        votes: Vec<(u64, ProposalVoteOption)>,
+    },
     /// Cast a vote for an active proposal.
     /// Permissioned to emissions controller contract.
     /// Called on an IBC packet receive.
@@ -87,11 +128,69 @@ pub enum ExecuteMsg {
         /// Vote option
         vote: ProposalVoteOption,
     },
+    /// Commit a hidden vote for an active commit-reveal proposal.
+    /// Commitment can be updated until the voting period ends.
+    CommitVote {
+        /// Proposal identifier
+        proposal_id: u64,
+        /// Hash produced by [`vote_commitment`]
+        commitment: Binary,
+    },
+    /// Reveal a previously committed vote during the reveal window
+    RevealVote {
+        /// Proposal identifier
+        proposal_id: u64,
+        /// Vote option
+        vote: ProposalVoteOption,
+        /// Salt used to produce the commitment
+        salt: String,
+    },
+    /// Commit a hidden vote for an active commit-reveal proposal.
+    /// Permissioned to emissions controller contract.
+    /// Called on an IBC packet receive.
+    CommitVoteOutpost {
+        voter: String,
+        /// Voting power reported from outpost
+        voting_power: Uint128,
+        /// Proposal identifier
+        proposal_id: u64,
+        /// Hash produced by [`vote_commitment`]
+        commitment: Binary,
+    },
+    /// Reveal a previously committed outpost vote.
+    /// Permissioned to emissions controller contract.
+    /// Called on an IBC packet receive.
+    RevealVoteOutpost {
+        voter: String,
+        /// Proposal identifier
+        proposal_id: u64,
+        /// Vote option
+        vote: ProposalVoteOption,
+        /// Salt used to produce the commitment
+        salt: String,
+    },
     /// Set the status of a proposal that expired
     EndProposal {
         /// Proposal identifier
         proposal_id: u64,
     },
+    /// Amend a proposal during its review window. A proposal can be amended only once.
+    /// Fields which are not specified stay unchanged.
+    /// ## Executor
+    /// Only the proposal submitter can amend the proposal
+    AmendProposal {
+        /// Proposal identifier
+        proposal_id: u64,
+        description: Option<String>,
+        messages: Option<Vec<CosmosMsg>>,
+    },
+    /// Withdraw a proposal during its review window. The deposit is fully refunded.
+    /// ## Executor
+    /// Only the proposal submitter can withdraw the proposal
+    WithdrawProposal {
+        /// Proposal identifier
+        proposal_id: u64,
+    },
     /// Checks that proposal messages are correct.
     CheckMessages(Vec<CosmosMsg>),
     /// The last endpoint which is executed only if all proposal messages have been passed
@@ -113,6 +212,109 @@ pub enum ExecuteMsg {
         status: ProposalStatus,
     },
     ExecuteFromMultisig(Vec<CosmosMsg>),
+    /// Register a migration plan and execute its steps sequentially.
+    /// If any step fails, the plan is halted and the remaining steps are skipped.
+    /// ## Executor
+    /// Only the Assembly contract is allowed to register migration plans (through a successful proposal)
+    RegisterMigrationPlan {
+        steps: Vec<MigrationStep>,
+    },
+    /// Add or remove governable parameters of other contracts.
+    /// Existing parameters are overwritten.
+    /// ## Executor
+    /// Only the Assembly contract is allowed to update the registry (through a successful proposal)
+    UpdateParamRegistry {
+        #[serde(default)]
+        add: Vec<GovernableParam>,
+        /// List of (contract address, parameter key) pairs to remove
+        #[serde(default)]
+        remove: Vec<(String, String)>,
+    },
+    /// Submit a proposal which changes registered parameters within their bounds.
+    /// Changes are rendered into the target contracts' update messages.
+    /// Requires the same deposit as a regular proposal.
+    SubmitParamChangeProposal {
+        title: String,
+        description: String,
+        link: Option<String>,
+        changes: Vec<ParamChange>,
+    },
+    /// Add or remove vote relayers. Existing relayers are overwritten, their usage is reset.
+    /// ## Executor
+    /// Only the Assembly contract is allowed to update the allowlist (through a successful proposal)
+    UpdateVoteRelayers {
+        #[serde(default)]
+        add: Vec<VoteRelayer>,
+        #[serde(default)]
+        remove: Vec<String>,
+    },
+    /// Register the secp256k1 public key the sender signs relayed votes with.
+    /// ## Executor
+    /// Any user
+    SetRelayPubkey {
+        pubkey: Binary,
+    },
+    /// Cast a vote on behalf of a user with the user's signed payload.
+    /// The signature of [`RelayVotePayload`] is verified against the user's registered public key.
+    /// The nonce must match the user's current relay nonce which prevents replaying the same payload.
+    /// ## Executor
+    /// Only allowlisted relayers within their quota
+    RelayVote(RelayedVote),
+    /// Grant a fee allowance from the Assembly to a user so the user can register the relay public key
+    /// (or vote directly) without holding gas tokens. The allowance is capped by the relayer's `fee_grant`
+    /// and expires after [`RELAY_QUOTA_PERIOD`]. Each grant consumes one vote of the relayer quota.
+    /// ## Executor
+    /// Only allowlisted relayers within their quota
+    GrantRelayFeeAllowance {
+        grantee: String,
+    },
+    /// Add or remove contracts which are paused while a critical proposal targeting them is live.
+    /// Existing targets are overwritten.
+    /// ## Executor
+    /// Only the Assembly contract is allowed to update the registry (through a successful proposal)
+    UpdatePausableTargets {
+        #[serde(default)]
+        add: Vec<PausableTarget>,
+        #[serde(default)]
+        remove: Vec<String>,
+    },
+    /// Schedule a follow-up proposal to be opened at a future timestamp
+    /// (e.g. the second phase of a two-phase migration).
+    /// Scheduled proposals don't require a deposit and are submitted by the Assembly itself.
+    /// ## Executor
+    /// Only the Assembly contract is allowed to schedule proposals (through a successful proposal)
+    ScheduleProposal {
+        /// Timestamp in seconds after which the proposal can be opened
+        open_at: u64,
+        template: ProposalTemplate,
+    },
+    /// Open a scheduled proposal once its time has come. Anyone can call this.
+    OpenScheduledProposal {
+        id: u64,
+    },
+    /// Cancel a scheduled proposal which is not opened yet.
+    /// ## Executor
+    /// Only the Assembly contract is allowed to cancel scheduled proposals (through a successful proposal)
+    CancelScheduledProposal {
+        id: u64,
+    },
+    /// Add or remove contracts notified with [`ConclusionHookMsg`] when a matching proposal concludes.
+    /// Existing hooks are overwritten.
+    /// ## Executor
+    /// Only the Assembly contract is allowed to update the registry (through a successful proposal)
+    UpdateConclusionHooks {
+        #[serde(default)]
+        add: Vec<ConclusionHook>,
+        #[serde(default)]
+        remove: Vec<String>,
+    },
+    /// Enable or disable a registered conclusion hook without changing its filter.
+    /// ## Executor
+    /// Only the Assembly contract is allowed to toggle hooks (through a successful proposal)
+    SetConclusionHookEnabled {
+        contract: String,
+        enabled: bool,
+    },
 }
 
 /// Thie enum describes all the queries available in the contract.
@@ -140,18 +342,118 @@ pub enum QueryMsg {
         /// The amount of proposals to return
         limit: Option<u32>,
     },
+    /// Return proposals labeled with a specific tag and/or in a specific status
+    #[returns(Vec<Proposal>)]
+    FilteredProposals {
+        /// Tag proposals must be labeled with
+        tag: Option<String>,
+        /// Status proposals must be in
+        status: Option<ProposalStatus>,
+        /// Proposal id after which to query
+        start_after: Option<u64>,
+        /// The amount of proposals to return
+        limit: Option<u32>,
+    },
     /// Return information about a specific proposal
     #[returns(Proposal)]
     Proposal { proposal_id: u64 },
+    /// Return the current phase of a specific proposal
+    #[returns(ProposalPhase)]
+    ProposalPhase { proposal_id: u64 },
     /// Return information about the votes cast on a specific proposal
     #[returns(ProposalVotesResponse)]
     ProposalVotes { proposal_id: u64 },
+    /// Return the vote option cast by a user on a specific proposal if any
+    #[returns(Option<ProposalVoteOption>)]
+    UserVote { proposal_id: u64, user: String },
+    /// Return the unrevealed vote commitment of a user on a specific commit-reveal proposal if any
+    #[returns(Option<VoteCommitment>)]
+    VoteCommitment { proposal_id: u64, user: String },
+    /// Return the per-source voting power breakdown a voter was counted with on a specific proposal if any
+    #[returns(Option<VotingPowerBreakdown>)]
+    VotingPowerBreakdown { proposal_id: u64, voter: String },
+    /// Return per-source voting power breakdowns of all voters on a specific proposal
+    #[returns(Vec<(String, VotingPowerBreakdown)>)]
+    VotingPowerBreakdowns {
+        /// Proposal unique id
+        proposal_id: u64,
+        /// Address after which to query
+        start_after: Option<String>,
+        /// The amount of voters to return
+        limit: Option<u32>,
+    },
     /// Return user voting power for a specific proposal
     #[returns(Uint128)]
     UserVotingPower { user: String, proposal_id: u64 },
     /// Return total voting power for a specific proposal
     #[returns(Uint128)]
     TotalVotingPower { proposal_id: u64 },
+    /// Return a migration plan with the status of each step
+    #[returns(MigrationPlan)]
+    MigrationPlan { plan_id: u64 },
+    /// Return registered governable parameters
+    #[returns(Vec<GovernableParam>)]
+    ParamRegistry {
+        /// (contract address, parameter key) pair after which to query
+        start_after: Option<(String, String)>,
+        /// The amount of parameters to return
+        limit: Option<u32>,
+    },
+    /// Return participation statistics of a specific voter
+    #[returns(VoterStats)]
+    VoterStats { address: String },
+    /// Return aggregated governance statistics
+    #[returns(GovernanceStatsResponse)]
+    GovernanceStats {},
+    /// Check whether an address holds enough aged xASTRO to submit a proposal
+    #[returns(ProposalEligibilityResponse)]
+    ProposalEligibility { address: String },
+    /// Return the general quorum which would be required for a proposal submitted right now
+    #[returns(AdaptiveQuorumResponse)]
+    AdaptiveQuorum {},
+    /// Return allowlisted vote relayers along with their quota usage
+    #[returns(Vec<(Addr, VoteRelayerInfo)>)]
+    VoteRelayers {},
+    /// Return the nonce the next vote relayed on behalf of a user must be signed with
+    #[returns(u64)]
+    RelayNonce { user: String },
+    /// Return the public key relayed votes of a user are verified against
+    #[returns(Option<Binary>)]
+    RelayPubkey { user: String },
+    /// Run static checks against proposal messages to spot messages which can't be executed
+    #[returns(SimulateExecutionResponse)]
+    SimulateExecution { proposal_id: u64 },
+    /// Return contracts registered to be paused during critical proposals
+    #[returns(Vec<(Addr, PausableTargetInfo)>)]
+    PausableTargets {},
+    /// Return currently paused contracts along with the proposals holding them paused
+    #[returns(Vec<(Addr, PausedTarget)>)]
+    PausedTargets {},
+    /// Return the queue of scheduled proposals which are not opened yet
+    #[returns(Vec<ScheduledProposal>)]
+    ScheduledProposals {
+        /// Scheduled proposal id after which to query
+        start_after: Option<u64>,
+        /// The amount of scheduled proposals to return
+        limit: Option<u32>,
+    },
+    /// Return contracts notified when a matching proposal concludes
+    #[returns(Vec<(Addr, ConclusionHookInfo)>)]
+    ConclusionHooks {},
+}
+
+/// This enum describes all sudo endpoints available in the contract.
+/// Intended to be wired with chain end-blocker or cron modules (e.g. Neutron cron).
+#[cw_serde]
+pub enum SudoMsg {
+    /// Concludes all active proposals whose voting period has ended.
+    /// Proposals are processed in ascending order of their last voting block.
+    /// Already concluded proposals are skipped.
+    /// Vote commitments which were never revealed before their proposals concluded are pruned.
+    EndExpiredProposals {
+        /// The max amount of proposals to conclude in a single call
+        limit: Option<u32>,
+    },
 }
 
 /// This structure stores general parameters for the Assembly contract.
@@ -183,6 +485,37 @@ pub struct Config {
     pub proposal_required_threshold: Decimal,
     /// Whitelisted links
     pub whitelisted_links: Vec<String>,
+    /// Whether ASTRO locked in the builder unlock contract counts towards voting power
+    #[serde(default = "default_true")]
+    pub include_builder_vp: bool,
+    /// Impact class specific proposal parameters
+    #[serde(default)]
+    pub impact_classes: ImpactClassesConfig,
+    /// Reveal window of commit-reveal proposals in blocks
+    #[serde(default = "default_reveal_period")]
+    pub proposal_reveal_period: u64,
+    /// Minimum time in seconds the proposer must hold the required deposit amount of xASTRO.
+    /// 0 disables the check. Outpost proposals submitted by the emissions controller are exempt
+    #[serde(default)]
+    pub min_xastro_age: u64,
+    /// Review window in blocks during which the proposer can amend or withdraw a proposal.
+    /// Voting starts right after the window. 0 disables the review window
+    #[serde(default)]
+    pub proposal_review_period: u64,
+    /// Vocabulary of tags proposals can be labeled with
+    #[serde(default)]
+    pub proposal_tags: Vec<String>,
+    /// Whether the general quorum is static or adapts to historical turnout
+    #[serde(default)]
+    pub quorum_mode: QuorumMode,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_reveal_period() -> u64 {
+    DEFAULT_REVEAL_PERIOD
 }
 
 impl Config {
@@ -232,6 +565,30 @@ impl Config {
             )));
         }
 
+        if !REVEAL_PERIOD_INTERVAL.contains(&self.proposal_reveal_period) {
+            return Err(StdError::generic_err(format!(
+                "The reveal period for a proposal cannot be lower than {} or higher than {}",
+                REVEAL_PERIOD_INTERVAL.start(),
+                REVEAL_PERIOD_INTERVAL.end()
+            )));
+        }
+
+        if self.min_xastro_age > MAX_XASTRO_AGE {
+            return Err(StdError::generic_err(format!(
+                "The minimum xASTRO age cannot be higher than {MAX_XASTRO_AGE} seconds"
+            )));
+        }
+
+        if self.proposal_review_period > MAX_REVIEW_PERIOD {
+            return Err(StdError::generic_err(format!(
+                "The review period for a proposal cannot be higher than {MAX_REVIEW_PERIOD} blocks"
+            )));
+        }
+
+        if let QuorumMode::Adaptive(params) = &self.quorum_mode {
+            params.validate()?;
+        }
+
         if !DEPOSIT_INTERVAL.contains(&self.proposal_required_deposit.u128()) {
             return Err(StdError::generic_err(format!(
                 "The required deposit for a proposal cannot be lower than {} or higher than {}",
@@ -240,12 +597,121 @@ impl Config {
             )));
         }
 
+        self.impact_classes.validate()
+    }
+
+    /// Returns voting period, quorum and threshold applied to proposals of the given impact class.
+    pub fn class_params(&self, class: ProposalImpactClass) -> ImpactClassParams {
+        let general = ImpactClassParams {
+            min_voting_period: self.proposal_voting_period,
+            required_quorum: self.proposal_required_quorum,
+            required_threshold: self.proposal_required_threshold,
+        };
+
+        match self.impact_classes.params(class) {
+            Some(params) => ImpactClassParams {
+                min_voting_period: general.min_voting_period.max(params.min_voting_period),
+                required_quorum: general.required_quorum.max(params.required_quorum),
+                required_threshold: general.required_threshold.max(params.required_threshold),
+            },
+            None => general,
+        }
+    }
+
+    /// Returns the quorum required for the proposal to pass.
+    /// Adaptive quorum sealed at submission replaces the general quorum,
+    /// while the impact class quorum still applies on top of it.
+    pub fn required_quorum(&self, proposal: &Proposal) -> Decimal {
+        match proposal.adaptive_quorum {
+            Some(adaptive_quorum) => self
+                .impact_classes
+                .params(proposal.impact_class)
+                .map_or(adaptive_quorum, |params| {
+                    adaptive_quorum.max(params.required_quorum)
+                }),
+            None => self.class_params(proposal.impact_class).required_quorum,
+        }
+    }
+}
+
+/// Defines how the general proposal quorum is determined.
+#[cw_serde]
+#[derive(Default)]
+pub enum QuorumMode {
+    /// `proposal_required_quorum` is applied to all proposals
+    #[default]
+    Static,
+    /// Quorum follows the trailing average turnout and is sealed at proposal submission
+    Adaptive(AdaptiveQuorumParams),
+}
+
+/// Parameters of the adaptive quorum.
+/// Required quorum = average turnout of the last `lookback` concluded proposals * `turnout_factor`,
+/// bounded by `min_quorum` and `max_quorum`.
+#[cw_serde]
+pub struct AdaptiveQuorumParams {
+    /// Number of the latest concluded proposals used to compute the average turnout
+    pub lookback: u64,
+    /// Share of the average turnout required as quorum
+    pub turnout_factor: Decimal,
+    /// Lower bound of the adaptive quorum
+    pub min_quorum: Decimal,
+    /// Upper bound of the adaptive quorum
+    pub max_quorum: Decimal,
+}
+
+impl AdaptiveQuorumParams {
+    pub fn validate(&self) -> StdResult<()> {
+        if self.lookback == 0 || self.lookback > MAX_QUORUM_LOOKBACK {
+            return Err(StdError::generic_err(format!(
+                "The adaptive quorum lookback must be between 1 and {MAX_QUORUM_LOOKBACK} proposals"
+            )));
+        }
+
+        if self.turnout_factor.is_zero() {
+            return Err(StdError::generic_err(
+                "The adaptive quorum turnout factor must be greater than zero",
+            ));
+        }
+
+        let max_quorum = Decimal::from_str(MAX_PROPOSAL_REQUIRED_QUORUM_PERCENTAGE)?;
+        let min_quorum = Decimal::from_str(MINIMUM_PROPOSAL_REQUIRED_QUORUM_PERCENTAGE)?;
+        if self.min_quorum < min_quorum
+            || self.max_quorum > max_quorum
+            || self.min_quorum > self.max_quorum
+        {
+            return Err(StdError::generic_err(format!(
+                "The adaptive quorum bounds must be ordered and lie between {}% and {}%",
+                min_quorum * Decimal::from_ratio(100u8, 1u8),
+                max_quorum * Decimal::from_ratio(100u8, 1u8)
+            )));
+        }
+
         Ok(())
     }
+
+    /// Computes the quorum from the turnouts of the latest concluded proposals.
+    /// Falls back to **static_quorum** if there are no concluded proposals yet.
+    pub fn compute(&self, turnouts: &[Decimal], static_quorum: Decimal) -> Decimal {
+        average_turnout(turnouts)
+            .map_or(static_quorum, |average| average * self.turnout_factor)
+            .clamp(self.min_quorum, self.max_quorum)
+    }
+}
+
+/// Returns the average of the given turnouts or None if there are no turnouts.
+pub fn average_turnout(turnouts: &[Decimal]) -> Option<Decimal> {
+    if turnouts.is_empty() {
+        return None;
+    }
+
+    let turnout_sum = turnouts.iter().fold(Decimal::zero(), |acc, t| acc + t);
+    Some(turnout_sum / Decimal::from_ratio(turnouts.len() as u64, 1u64))
 }
 
 /// This structure stores the params used when updating the main Assembly contract params.
 #[cw_serde]
+#[derive(Default)]
 pub struct UpdateConfig {
     /// Astroport IBC controller contract
     pub ibc_controller: Option<String>,
@@ -270,6 +736,173 @@ pub struct UpdateConfig {
     /// Set vxASTRO and emissions controller contract at the same time.
     /// Emissions controller is queried from the vxASTRO contract.
     pub vxastro: Option<String>,
+    /// Whether ASTRO locked in the builder unlock contract counts towards voting power
+    pub include_builder_vp: Option<bool>,
+    /// Impact class specific proposal parameters
+    pub impact_classes: Option<ImpactClassesConfig>,
+    /// Reveal window of commit-reveal proposals in blocks
+    pub proposal_reveal_period: Option<u64>,
+    /// Minimum time in seconds the proposer must hold the required deposit amount of xASTRO
+    pub min_xastro_age: Option<u64>,
+    /// Review window in blocks during which the proposer can amend or withdraw a proposal
+    pub proposal_review_period: Option<u64>,
+    /// Tags to add to the proposal tags vocabulary
+    pub tags_add: Option<Vec<String>>,
+    /// Tags to remove from the proposal tags vocabulary.
+    /// Proposals which were already labeled with them keep their tags
+    pub tags_remove: Option<Vec<String>>,
+    /// Whether the general quorum is static or adapts to historical turnout.
+    /// Proposals which were already submitted keep their quorum
+    pub quorum_mode: Option<QuorumMode>,
+}
+
+/// Proposal impact classes sorted from the lowest to the highest impact.
+/// A proposal gets the highest class among its messages.
+#[cw_serde]
+#[derive(Default, Copy, PartialOrd, Ord, Eq)]
+pub enum ProposalImpactClass {
+    /// Default class for proposals which neither spend treasury funds nor migrate contracts
+    #[default]
+    ParameterChange,
+    /// Treasury spend within `large_spend_threshold`
+    SmallTreasurySpend,
+    /// Treasury spend above `large_spend_threshold`
+    LargeTreasurySpend,
+    /// Contract migration or admin change
+    ContractMigration,
+}
+
+impl Display for ProposalImpactClass {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            ProposalImpactClass::ParameterChange => fmt.write_str("parameter_change"),
+            ProposalImpactClass::SmallTreasurySpend => fmt.write_str("small_treasury_spend"),
+            ProposalImpactClass::LargeTreasurySpend => fmt.write_str("large_treasury_spend"),
+            ProposalImpactClass::ContractMigration => fmt.write_str("contract_migration"),
+        }
+    }
+}
+
+/// Proposal parameters for a specific impact class.
+/// They can only make proposals of this class stricter than the general config.
+#[cw_serde]
+pub struct ImpactClassParams {
+    /// Minimum voting period in blocks
+    pub min_voting_period: u64,
+    /// Minimum required quorum
+    pub required_quorum: Decimal,
+    /// Minimum required threshold
+    pub required_threshold: Decimal,
+}
+
+/// Impact class specific proposal parameters.
+/// Classes without params follow the general config.
+#[cw_serde]
+#[derive(Default)]
+pub struct ImpactClassesConfig {
+    /// Max amount per denom which is considered as a small treasury spend.
+    /// Spending any denom which is not listed here is considered as a large spend.
+    pub large_spend_threshold: Vec<Coin>,
+    pub parameter_change: Option<ImpactClassParams>,
+    pub small_treasury_spend: Option<ImpactClassParams>,
+    pub large_treasury_spend: Option<ImpactClassParams>,
+    pub contract_migration: Option<ImpactClassParams>,
+}
+
+impl ImpactClassesConfig {
+    pub fn params(&self, class: ProposalImpactClass) -> Option<&ImpactClassParams> {
+        match class {
+            ProposalImpactClass::ParameterChange => self.parameter_change.as_ref(),
+            ProposalImpactClass::SmallTreasurySpend => self.small_treasury_spend.as_ref(),
+            ProposalImpactClass::LargeTreasurySpend => self.large_treasury_spend.as_ref(),
+            ProposalImpactClass::ContractMigration => self.contract_migration.as_ref(),
+        }
+    }
+
+    /// Determines the impact class of a proposal based on the messages it contains.
+    pub fn classify(&self, messages: &[CosmosMsg]) -> ProposalImpactClass {
+        let mut spends: HashMap<&str, Uint128> = HashMap::new();
+        let mut class = ProposalImpactClass::ParameterChange;
+
+        for msg in messages {
+            let funds = match msg {
+                CosmosMsg::Wasm(WasmMsg::Migrate { .. })
+                | CosmosMsg::Wasm(WasmMsg::UpdateAdmin { .. })
+                | CosmosMsg::Wasm(WasmMsg::ClearAdmin { .. }) => {
+                    return ProposalImpactClass::ContractMigration
+                }
+                CosmosMsg::Wasm(WasmMsg::Execute { msg, funds, .. }) => {
+                    if let Ok(ExecuteMsg::RegisterMigrationPlan { .. }) = from_json(msg) {
+                        return ProposalImpactClass::ContractMigration;
+                    }
+                    funds
+                }
+                CosmosMsg::Wasm(WasmMsg::Instantiate { funds, .. }) => funds,
+                CosmosMsg::Bank(BankMsg::Send { amount, .. })
+                | CosmosMsg::Bank(BankMsg::Burn { amount }) => amount,
+                _ => continue,
+            };
+
+            for coin in funds {
+                *spends.entry(&coin.denom).or_default() += coin.amount;
+                class = class.max(ProposalImpactClass::SmallTreasurySpend);
+            }
+        }
+
+        let is_large_spend = spends.into_iter().any(|(denom, amount)| {
+            self.large_spend_threshold
+                .iter()
+                .find(|threshold| threshold.denom == denom)
+                .map_or(true, |threshold| amount > threshold.amount)
+        });
+
+        if is_large_spend {
+            ProposalImpactClass::LargeTreasurySpend
+        } else {
+            class
+        }
+    }
+
+    pub fn validate(&self) -> StdResult<()> {
+        let max_quorum = Decimal::from_str(MAX_PROPOSAL_REQUIRED_QUORUM_PERCENTAGE)?;
+
+        for (class, params) in [
+            (ProposalImpactClass::ParameterChange, &self.parameter_change),
+            (
+                ProposalImpactClass::SmallTreasurySpend,
+                &self.small_treasury_spend,
+            ),
+            (
+                ProposalImpactClass::LargeTreasurySpend,
+                &self.large_treasury_spend,
+            ),
+            (
+                ProposalImpactClass::ContractMigration,
+                &self.contract_migration,
+            ),
+        ] {
+            if let Some(params) = params {
+                if !VOTING_PERIOD_INTERVAL.contains(&params.min_voting_period) {
+                    return Err(StdError::generic_err(format!(
+                        "The {class} voting period should be more than {} or less than {} blocks.",
+                        VOTING_PERIOD_INTERVAL.start(),
+                        VOTING_PERIOD_INTERVAL.end()
+                    )));
+                }
+
+                if params.required_quorum > max_quorum
+                    || params.required_threshold
+                        > Decimal::percent(MAX_PROPOSAL_REQUIRED_THRESHOLD_PERCENTAGE)
+                {
+                    return Err(StdError::generic_err(format!(
+                        "The {class} required quorum and threshold cannot be higher than 100%"
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// This structure stores data for a proposal.
@@ -309,9 +942,56 @@ pub struct Proposal {
     pub ibc_channel: Option<String>,
     /// Total voting power 1 second before the proposal was created
     pub total_voting_power: Uint128,
+    /// Impact class determined at submission
+    #[serde(default)]
+    pub impact_class: ProposalImpactClass,
+    /// Last block of the reveal window. Set only for commit-reveal proposals
+    #[serde(default)]
+    pub reveal_end_block: Option<u64>,
+    /// Last block of the review window. Set only if the review window was enabled at submission
+    #[serde(default)]
+    pub review_end_block: Option<u64>,
+    /// Whether the proposal was amended during the review window
+    #[serde(default)]
+    pub amended: bool,
+    /// Tags the proposal is labeled with
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// General quorum sealed at submission. Set only if the adaptive quorum was enabled at submission
+    #[serde(default)]
+    pub adaptive_quorum: Option<Decimal>,
 }
 
 impl Proposal {
+    /// Returns the voting phase of the proposal at the given block height
+    pub fn phase(&self, height: u64) -> ProposalPhase {
+        if matches!(self.review_end_block, Some(review_end_block) if height <= review_end_block) {
+            return ProposalPhase::Review;
+        }
+
+        match self.reveal_end_block {
+            None if height <= self.end_block => ProposalPhase::Voting,
+            Some(_) if height <= self.end_block => ProposalPhase::Commit,
+            Some(reveal_end_block) if height <= reveal_end_block => ProposalPhase::Reveal,
+            _ => ProposalPhase::Closed,
+        }
+    }
+
+    /// Returns the last block when votes can be cast or revealed
+    pub fn voting_end_block(&self) -> u64 {
+        self.reveal_end_block.unwrap_or(self.end_block)
+    }
+
+    /// Returns the deposit the Assembly has refunded to the submitter.
+    /// Deposits are refunded once the proposal is concluded or withdrawn.
+    pub fn refunded_deposit(&self) -> Uint128 {
+        if self.status == ProposalStatus::Active {
+            Uint128::zero()
+        } else {
+            self.deposit_amount
+        }
+    }
+
     pub fn validate(&self, whitelisted_links: Vec<String>) -> StdResult<()> {
         // Title validation
         if self.title.len() < MIN_TITLE_LENGTH {
@@ -375,6 +1055,7 @@ pub enum ProposalStatus {
     Failed,
     Executed,
     Expired,
+    Withdrawn,
 }
 
 impl Display for ProposalStatus {
@@ -387,10 +1068,24 @@ impl Display for ProposalStatus {
             ProposalStatus::Failed => fmt.write_str("failed"),
             ProposalStatus::Executed {} => fmt.write_str("executed"),
             ProposalStatus::Expired {} => fmt.write_str("expired"),
+            ProposalStatus::Withdrawn => fmt.write_str("withdrawn"),
         }
     }
 }
 
+/// This enum describes voting phases of a proposal.
+/// Proposals submitted with the review window enabled start in the review phase.
+/// Commit-reveal proposals go through the commit and reveal phases,
+/// while regular proposals accept plain votes during the voting phase.
+#[cw_serde]
+pub enum ProposalPhase {
+    Review,
+    Voting,
+    Commit,
+    Reveal,
+    Closed,
+}
+
 /// This enum describes available options for voting on a proposal.
 #[cw_serde]
 pub enum ProposalVoteOption {
@@ -418,6 +1113,294 @@ pub struct ProposalVotesResponse {
     pub against_power: Uint128,
 }
 
+/// Participation statistics of a single voter
+#[cw_serde]
+#[derive(Default)]
+pub struct VoterStats {
+    /// The amount of proposals the address has voted on
+    pub proposals_voted: u64,
+    /// The amount of `for` votes cast by the address
+    pub for_votes: u64,
+    /// The amount of `against` votes cast by the address
+    pub against_votes: u64,
+    /// Voting power used across all votes
+    pub total_voting_power: Uint128,
+    /// The latest proposal the address has voted on
+    pub last_voted_proposal: Option<u64>,
+}
+
+/// Vote relayer settings
+#[cw_serde]
+pub struct VoteRelayer {
+    /// Relayer address
+    pub address: String,
+    /// Max number of votes the relayer can relay per [`RELAY_QUOTA_PERIOD`]
+    pub quota: u64,
+    /// Spend limit of fee allowances the relayer can grant to users. Empty disables fee grants
+    #[serde(default)]
+    pub fee_grant: Vec<Coin>,
+}
+
+/// Vote relayed on behalf of a user
+#[cw_serde]
+pub struct RelayedVote {
+    /// Address of the user who signed the vote
+    pub voter: String,
+    /// Proposal identifier
+    pub proposal_id: u64,
+    /// Vote option
+    pub vote: ProposalVoteOption,
+    /// User's relay nonce
+    pub nonce: u64,
+    /// secp256k1 signature of [`RelayVotePayload::digest`]
+    pub signature: Binary,
+}
+
+/// Payload a user signs to have a vote relayed
+#[cw_serde]
+pub struct RelayVotePayload {
+    /// Chain id the vote is valid on
+    pub chain_id: String,
+    /// Assembly contract address
+    pub assembly: String,
+    pub voter: String,
+    pub proposal_id: u64,
+    pub vote: ProposalVoteOption,
+    pub nonce: u64,
+}
+
+impl RelayVotePayload {
+    /// Computes sha256 of the JSON serialized payload. This is the message hash the user signs.
+    pub fn digest(&self) -> StdResult<Vec<u8>> {
+        Ok(Sha256::digest(to_json_vec(self)?).to_vec())
+    }
+}
+
+/// Number of votes relayed within the current quota period
+#[cw_serde]
+#[derive(Default)]
+pub struct RelayUsage {
+    /// Start of the current quota period
+    pub period_start: u64,
+    /// The amount of votes relayed since the period start
+    pub relayed: u64,
+}
+
+impl RelayUsage {
+    /// Counts a relayed vote. Starts a new period if the current one has passed.
+    /// Returns false if the limit is already reached within the current period.
+    pub fn try_consume(&mut self, now: u64, limit: u64) -> bool {
+        if now >= self.period_start + RELAY_QUOTA_PERIOD {
+            self.period_start = now;
+            self.relayed = 0;
+        }
+
+        if self.relayed >= limit {
+            return false;
+        }
+
+        self.relayed += 1;
+        true
+    }
+}
+
+/// Allowlisted vote relayer
+#[cw_serde]
+pub struct VoteRelayerInfo {
+    /// Max number of votes the relayer can relay per [`RELAY_QUOTA_PERIOD`]
+    pub quota: u64,
+    pub usage: RelayUsage,
+    /// Spend limit of fee allowances the relayer can grant to users
+    #[serde(default)]
+    pub fee_grant: Vec<Coin>,
+}
+
+/// Pausable target settings
+#[cw_serde]
+pub struct PausableTarget {
+    /// Target contract address
+    pub contract: String,
+    /// Message executed on the target when a critical proposal targeting it is submitted
+    pub pause_msg: Binary,
+    /// Message executed on the target once no live critical proposal targets it anymore
+    pub unpause_msg: Binary,
+}
+
+/// Stored pausable target settings
+#[cw_serde]
+pub struct PausableTargetInfo {
+    pub pause_msg: Binary,
+    pub unpause_msg: Binary,
+}
+
+/// A contract currently held paused by live critical proposals
+#[cw_serde]
+pub struct PausedTarget {
+    /// Proposals holding the target paused
+    pub proposals: Vec<u64>,
+    /// Message to execute once the last proposal concludes.
+    /// Stored at pause time so later registry updates don't leave the target paused.
+    pub unpause_msg: Binary,
+}
+
+/// Parameters of a proposal opened automatically by the Assembly
+#[cw_serde]
+pub struct ProposalTemplate {
+    pub title: String,
+    pub description: String,
+    pub link: Option<String>,
+    #[serde(default)]
+    pub messages: Vec<CosmosMsg>,
+    /// If proposal should be executed on a remote chain this field should specify governance channel
+    pub ibc_channel: Option<String>,
+    /// Whether votes are hidden until the reveal window (commit-reveal voting)
+    #[serde(default)]
+    pub commit_reveal: bool,
+    /// Tags from the governance-managed vocabulary the proposal is labeled with
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// A follow-up proposal waiting in the queue to be opened
+#[cw_serde]
+pub struct ScheduledProposal {
+    /// Scheduled proposal id. Not related to the id the proposal receives when opened
+    pub id: u64,
+    /// Timestamp in seconds after which the proposal can be opened
+    pub open_at: u64,
+    pub template: ProposalTemplate,
+}
+
+/// Proposals a conclusion hook is interested in.
+/// Empty lists match any proposal.
+#[cw_serde]
+#[derive(Default)]
+pub struct ConclusionHookFilter {
+    /// Proposal must be labeled with at least one of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Proposal impact class must be one of these
+    #[serde(default)]
+    pub impact_classes: Vec<ProposalImpactClass>,
+}
+
+impl ConclusionHookFilter {
+    pub fn matches(&self, proposal: &Proposal) -> bool {
+        (self.tags.is_empty() || proposal.tags.iter().any(|tag| self.tags.contains(tag)))
+            && (self.impact_classes.is_empty()
+                || self.impact_classes.contains(&proposal.impact_class))
+    }
+}
+
+/// Conclusion hook settings
+#[cw_serde]
+pub struct ConclusionHook {
+    /// Contract notified with [`ConclusionHookMsg`]
+    pub contract: String,
+    pub filter: ConclusionHookFilter,
+    pub enabled: bool,
+}
+
+/// Stored conclusion hook settings
+#[cw_serde]
+pub struct ConclusionHookInfo {
+    pub filter: ConclusionHookFilter,
+    pub enabled: bool,
+}
+
+/// Message executed on hook contracts when a matching proposal concludes
+/// (ends or is withdrawn). Hook failures never revert the proposal lifecycle.
+#[cw_serde]
+pub enum ConclusionHookMsg {
+    ProposalConcluded {
+        proposal_id: u64,
+        status: ProposalStatus,
+        tags: Vec<String>,
+        impact_class: ProposalImpactClass,
+    },
+}
+
+/// Static check results of a single proposal message
+#[cw_serde]
+pub struct MessageDiagnostics {
+    /// Message index in the proposal
+    pub index: u32,
+    /// Message type, e.g. "wasm_execute" or "bank_send"
+    pub msg_type: String,
+    /// Problems found in the message. Empty if all checks passed
+    pub issues: Vec<String>,
+}
+
+/// This structure describes the response of a proposal execution dry-run
+#[cw_serde]
+pub struct SimulateExecutionResponse {
+    pub proposal_id: u64,
+    pub status: ProposalStatus,
+    /// Whether the proposal passed and all its messages passed static checks
+    pub executable: bool,
+    /// Per-message diagnostics
+    pub messages: Vec<MessageDiagnostics>,
+}
+
+/// Governance statistics maintained on each vote and proposal conclusion
+#[cw_serde]
+#[derive(Default)]
+pub struct GovernanceStats {
+    /// The amount of proposals which reached the end of the voting period
+    pub concluded_proposals: u64,
+    /// The amount of concluded proposals which passed
+    pub passed_proposals: u64,
+    /// The amount of concluded proposals which were rejected
+    pub rejected_proposals: u64,
+    /// The amount of votes cast on all proposals
+    pub total_votes: u64,
+    /// The amount of `for` votes cast on all proposals
+    pub for_votes: u64,
+    /// The amount of `against` votes cast on all proposals
+    pub against_votes: u64,
+    /// Sum of turnouts of all concluded proposals
+    pub turnout_sum: Decimal,
+}
+
+/// This structure describes a governance statistics response.
+#[cw_serde]
+pub struct GovernanceStatsResponse {
+    /// The amount of submitted proposals
+    pub total_proposals: u64,
+    /// Aggregated statistics
+    pub stats: GovernanceStats,
+    /// Average turnout of concluded proposals
+    pub average_turnout: Decimal,
+    /// Turnouts of the latest concluded proposals, starting from the most recent one
+    pub latest_turnouts: Vec<(u64, Decimal)>,
+}
+
+/// This structure describes an adaptive quorum response.
+#[cw_serde]
+pub struct AdaptiveQuorumResponse {
+    /// Current quorum mode
+    pub quorum_mode: QuorumMode,
+    /// Average turnout of the sampled proposals. Zero if the quorum is static
+    pub average_turnout: Decimal,
+    /// The amount of the latest concluded proposals used to compute the average turnout
+    pub sampled_proposals: u64,
+    /// General quorum required for a proposal submitted right now
+    pub required_quorum: Decimal,
+}
+
+/// This structure describes a proposal submission eligibility response.
+#[cw_serde]
+pub struct ProposalEligibilityResponse {
+    /// Whether the address can submit a proposal
+    pub eligible: bool,
+    /// xASTRO balance held for at least `min_xastro_age` seconds
+    pub aged_balance: Uint128,
+    /// Required proposal deposit
+    pub required_deposit: Uint128,
+    /// Minimum xASTRO age in seconds
+    pub min_xastro_age: u64,
+}
+
 /// This structure describes a proposal list response.
 #[cw_serde]
 pub struct ProposalListResponse {
@@ -435,12 +1418,190 @@ pub struct ProposalVoterResponse {
     pub vote_option: ProposalVoteOption,
 }
 
+/// Hidden vote committed to a commit-reveal proposal
+#[cw_serde]
+pub struct VoteCommitment {
+    /// Hash produced by [`vote_commitment`]
+    pub commitment: Binary,
+    /// Voting power sealed at commit
+    pub voting_power: Uint128,
+    /// Per-source breakdown of the sealed voting power
+    #[serde(default)]
+    pub breakdown: VotingPowerBreakdown,
+}
+
+/// Voting power a vote was counted with, split by source
+#[cw_serde]
+#[derive(Default)]
+pub struct VotingPowerBreakdown {
+    /// xASTRO balance before the proposal start
+    pub xastro: Uint128,
+    /// vxASTRO locked amount before the proposal start
+    pub vxastro: Uint128,
+    /// ASTRO not withdrawn from builder allocations before the proposal start
+    pub builder: Uint128,
+    /// Voting power reported by an outpost through the emissions controller
+    pub outpost: Uint128,
+}
+
+impl VotingPowerBreakdown {
+    /// Returns voting power combined over all sources.
+    pub fn total(&self) -> Uint128 {
+        self.xastro + self.vxastro + self.builder + self.outpost
+    }
+}
+
+/// Computes a commitment for a hidden vote as sha256 of `{proposal_id}:{vote}:{salt}`.
+pub fn vote_commitment(proposal_id: u64, vote: &ProposalVoteOption, salt: &str) -> Binary {
+    Binary::from(Sha256::digest(format!("{proposal_id}:{vote}:{salt}")).as_slice())
+}
+
+/// This structure describes a single contract migration within a migration plan.
+#[cw_serde]
+pub struct MigrationStep {
+    /// Contract to migrate. The Assembly must be its admin
+    pub contract_addr: String,
+    /// Code id to migrate the contract to
+    pub new_code_id: u64,
+    /// Migrate message
+    pub msg: Binary,
+}
+
+/// This enum describes available statuses for a migration step.
+#[cw_serde]
+pub enum MigrationStepStatus {
+    Pending,
+    Succeeded,
+    Failed { error: String },
+    Skipped,
+}
+
+/// This enum describes available statuses for a migration plan.
+#[cw_serde]
+pub enum MigrationPlanStatus {
+    InProgress,
+    Completed,
+    Halted,
+}
+
+/// This structure describes a migration plan with the status of each step.
+#[cw_serde]
+pub struct MigrationPlan {
+    /// Unique migration plan identifier
+    pub plan_id: u64,
+    /// Overall plan status
+    pub status: MigrationPlanStatus,
+    /// Migration steps in execution order along with their statuses
+    pub steps: Vec<(MigrationStep, MigrationStepStatus)>,
+}
+
+/// This enum describes the type of a governable parameter along with its bounds (inclusive).
+#[cw_serde]
+pub enum ParamKind {
+    /// Rendered as a JSON number
+    U64 {
+        min: u64,
+        max: u64,
+    },
+    /// Rendered as a JSON string
+    Uint128 {
+        min: Uint128,
+        max: Uint128,
+    },
+    /// Rendered as a JSON string
+    Decimal {
+        min: Decimal,
+        max: Decimal,
+    },
+    Bool,
+}
+
+impl ParamKind {
+    /// Returns None if the value type doesn't match the parameter type.
+    /// Otherwise, returns whether the value is within bounds.
+    pub fn is_within_bounds(&self, value: &ParamValue) -> Option<bool> {
+        match (self, value) {
+            (ParamKind::U64 { min, max }, ParamValue::U64(value)) => {
+                Some((min..=max).contains(&value))
+            }
+            (ParamKind::Uint128 { min, max }, ParamValue::Uint128(value)) => {
+                Some((min..=max).contains(&value))
+            }
+            (ParamKind::Decimal { min, max }, ParamValue::Decimal(value)) => {
+                Some((min..=max).contains(&value))
+            }
+            (ParamKind::Bool, ParamValue::Bool(_)) => Some(true),
+            _ => None,
+        }
+    }
+
+    pub fn has_valid_bounds(&self) -> bool {
+        match self {
+            ParamKind::U64 { min, max } => min <= max,
+            ParamKind::Uint128 { min, max } => min <= max,
+            ParamKind::Decimal { min, max } => min <= max,
+            ParamKind::Bool => true,
+        }
+    }
+}
+
+/// This enum describes a new value of a governable parameter.
+#[cw_serde]
+pub enum ParamValue {
+    U64(u64),
+    Uint128(Uint128),
+    Decimal(Decimal),
+    Bool(bool),
+}
+
+/// This structure describes a parameter of another contract which can be changed
+/// via a parameter change proposal.
+#[cw_serde]
+pub struct GovernableParam {
+    /// Contract which owns the parameter. The Assembly must be allowed to update it
+    pub contract: String,
+    /// Parameter key in the target contract's update message
+    pub key: String,
+    /// Parameter type and bounds
+    pub kind: ParamKind,
+    /// Keys of the nested execute message wrapping the parameter.
+    /// For example, `["update_config"]` renders `{"update_config":{"<key>":<value>}}`
+    pub msg_path: Vec<String>,
+}
+
+/// This structure describes a change of a registered parameter.
+#[cw_serde]
+pub struct ParamChange {
+    pub contract: String,
+    pub key: String,
+    pub value: ParamValue,
+}
+
 /// Checks if the link is valid. Returns a boolean value.
 pub fn is_safe_link(link: &str) -> bool {
     link.chars()
         .all(|c| c.is_ascii_alphanumeric() || SAFE_LINK_CHARS.contains(c))
 }
 
+/// Validates tags added to the proposal tags vocabulary.
+/// A tag must be a short string of lowercase alphanumeric characters and dashes.
+pub fn validate_tags(tags: &[String]) -> StdResult<()> {
+    for tag in tags {
+        if tag.is_empty()
+            || tag.len() > MAX_TAG_LENGTH
+            || !tag
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            return Err(StdError::generic_err(format!(
+                "Tag is not properly formatted: {tag}. Tag must be at most {MAX_TAG_LENGTH} lowercase alphanumeric characters or dashes."
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 /// Validating the list of links. Returns an error if a list has an invalid link.
 pub fn validate_links(links: &[String]) -> StdResult<()> {
     for link in links {
@@ -1,5 +1,14 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Decimal, StdError, StdResult, Uint128};
+use cosmwasm_std::{Addr, Binary, Decimal, StdError, StdResult, Uint128};
+
+/// Delay after which a newly set fallback receiver becomes effective (7 days)
+pub const FALLBACK_RECEIVER_TIMELOCK: u64 = 7 * 86400;
+/// Minimum inactivity period after which a fallback receiver can claim an allocation (90 days)
+pub const MIN_FALLBACK_INACTIVITY_PERIOD: u64 = 90 * 86400;
+/// Period of upcoming unlocks (30 days) against which ASTRO can be advanced
+pub const ADVANCE_WINDOW: u64 = 30 * 86400;
+/// Max number of milestones in an unlock schedule
+pub const MAX_UNLOCK_MILESTONES: usize = 32;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -42,11 +51,72 @@ pub enum ExecuteMsg {
     /// Claim contract ownership
     ClaimOwnership {},
     /// Update parameters in the contract configuration
-    UpdateConfig { new_max_allocations_amount: Uint128 },
+    UpdateConfig {
+        new_max_allocations_amount: Uint128,
+        /// Share of ASTRO unlocking during the next [`ADVANCE_WINDOW`] which can be advanced
+        new_advance_ltv: Option<Decimal>,
+        /// Assembly contract used to verify proposal votes of allocation holders
+        #[serde(default)]
+        new_assembly: Option<String>,
+        /// Max amount of ASTRO distributed as voting rebates per epoch
+        #[serde(default)]
+        new_rebate_per_epoch: Option<Uint128>,
+    },
     /// Update a schedule of allocation for specified accounts
     UpdateUnlockSchedules {
         new_unlock_schedules: Vec<(String, Schedule)>,
     },
+    /// Register a merkle drop of allocations (only accessible to the owner).
+    /// The owner must send the total ASTRO amount of all allocations in the tree.
+    /// Leaves are sha256 hashes of JSON serialized (index, address, CreateAllocationParams) tuples.
+    RegisterMerkleDrop {
+        /// Merkle root (32 bytes sha256 hash)
+        merkle_root: Binary,
+        /// Timestamp after which allocations can't be claimed anymore
+        expiration: u64,
+    },
+    /// Create an allocation for the message sender from a merkle drop
+    ClaimMerkleAllocation {
+        drop_id: u64,
+        /// Leaf index in the merkle tree
+        index: u64,
+        /// Allocation parameters
+        params: CreateAllocationParams,
+        /// Merkle proof
+        proof: Vec<Binary>,
+    },
+    /// Return unclaimed ASTRO of an expired merkle drop back to unallocated tokens
+    ExpireMerkleDrop { drop_id: u64 },
+    /// Set a fallback receiver which can claim the sender's allocation
+    /// if the sender doesn't withdraw for `inactivity_period` seconds.
+    /// The fallback receiver becomes effective after [`FALLBACK_RECEIVER_TIMELOCK`].
+    SetFallbackReceiver {
+        receiver: String,
+        inactivity_period: u64,
+    },
+    /// Remove the sender's fallback receiver
+    RemoveFallbackReceiver {},
+    /// Allows a fallback receiver to claim the allocation of an inactive account
+    ClaimFallback { account: String },
+    /// Advance ASTRO which unlocks during the next [`ADVANCE_WINDOW`] to the sender.
+    /// The advance is interest-free and is repaid automatically from future unlocks.
+    TakeAdvance { amount: Uint128 },
+    /// Top up the voting rebate pool with ASTRO (only accessible to the owner)
+    FundRebatePool {},
+    /// Record the sender's vote in an Assembly proposal submitted in the current epoch.
+    /// Each allocation holder which recorded a vote gets an equal share of the epoch rebate.
+    /// Rebates of finished epochs are paid out on withdrawal.
+    RecordProposalVote { proposal_id: u64 },
+    /// Create a named unlock schedule template which allocations can reference
+    /// instead of an inline schedule (only accessible to the owner)
+    CreateScheduleTemplate { name: String, schedule: Schedule },
+    /// Update a schedule template (only accessible to the owner).
+    /// Only allocations created after the update are affected
+    UpdateScheduleTemplate {
+        template_id: u64,
+        new_name: Option<String>,
+        new_schedule: Option<Schedule>,
+    },
 }
 
 /// This enum describes all the queries available in the contract.
@@ -91,6 +161,71 @@ pub enum QueryMsg {
         /// Timestamp used to simulate how much ASTRO the account can withdraw
         timestamp: Option<u64>,
     },
+    /// SimulateWithdrawBatch simulates withdrawals for several accounts at once.
+    /// At most [`crate::MAX_LIMIT`] accounts can be queried at once
+    #[returns(Vec<(String, SimulateWithdrawResponse)>)]
+    SimulateWithdrawBatch {
+        /// Accounts for which we simulate withdrawals
+        accounts: Vec<String>,
+        /// Timestamp used to simulate how much ASTRO the accounts can withdraw
+        timestamp: Option<u64>,
+    },
+    /// AllocationsByStatus returns allocations currently in the specified status
+    #[returns(Vec<(Addr, AllocationResponse)>)]
+    AllocationsByStatus {
+        status: AllocationStatusFilter,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// VotingPowerAt returns the amount of ASTRO which is allocated but not withdrawn yet
+    /// (including unlocked tokens and excluding outstanding advances) and thus can be used as voting power
+    #[returns(Uint128)]
+    VotingPowerAt {
+        /// Account whose voting power we query
+        account: String,
+        // Timestamp at which we query. If none uses current block timestamp
+        timestamp: Option<u64>,
+    },
+    /// MerkleDrop returns the merkle drop info
+    #[returns(MerkleDrop)]
+    MerkleDrop { drop_id: u64 },
+    /// IsClaimed returns whether the leaf of a merkle drop has been already claimed
+    #[returns(bool)]
+    IsClaimed { drop_id: u64, index: u64 },
+    /// FallbackReceiver returns the fallback receiver configured for an allocation
+    #[returns(Option<FallbackReceiver>)]
+    FallbackReceiver { account: String },
+    /// FallbackReceivers returns a vector of configured fallback receivers
+    #[returns(Vec<(Addr, FallbackReceiver)>)]
+    FallbackReceivers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Advance returns the outstanding advance of an allocation and how much ASTRO can still be advanced
+    #[returns(AdvanceResponse)]
+    Advance { account: String },
+    /// EpochRebate returns the rebate reserved for the epoch and the number of participants
+    #[returns(Option<EpochRebate>)]
+    EpochRebate { epoch_start: u64 },
+    /// Rebates returns the voting rebates of an allocation holder
+    #[returns(RebatesResponse)]
+    Rebates { account: String },
+    /// ScheduleTemplate returns a schedule template by its id
+    #[returns(ScheduleTemplate)]
+    ScheduleTemplate { template_id: u64 },
+    /// ScheduleTemplates returns a vector of schedule templates by their ids
+    #[returns(Vec<(u64, ScheduleTemplate)>)]
+    ScheduleTemplates {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// TemplateAllocations returns accounts whose unlock schedule follows the schedule template
+    #[returns(Vec<Addr>)]
+    TemplateAllocations {
+        template_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 /// This structure stores the parameters used to return the response when querying for an allocation data.
@@ -102,6 +237,37 @@ pub struct AllocationResponse {
     pub status: AllocationStatus,
 }
 
+/// Status used to filter allocations
+#[cw_serde]
+#[derive(Copy)]
+pub enum AllocationStatusFilter {
+    /// Allocation still holds ASTRO which is neither withdrawn nor advanced
+    Active,
+    /// All allocated ASTRO was either withdrawn or advanced
+    FullyWithdrawn,
+    /// A new receiver was proposed for the allocation
+    ProposedTransfer,
+}
+
+impl AllocationStatusFilter {
+    /// Returns whether an allocation is in this status.
+    pub fn matches(&self, allocation: &AllocationResponse) -> bool {
+        let locked = allocation
+            .status
+            .amount
+            .saturating_sub(allocation.status.astro_withdrawn)
+            .saturating_sub(allocation.status.advanced);
+
+        match self {
+            AllocationStatusFilter::Active => !locked.is_zero(),
+            AllocationStatusFilter::FullyWithdrawn => locked.is_zero(),
+            AllocationStatusFilter::ProposedTransfer => {
+                allocation.params.proposed_receiver.is_some()
+            }
+        }
+    }
+}
+
 /// This structure stores the parameters used to return a response when simulating a withdrawal.
 #[cw_serde]
 pub struct SimulateWithdrawResponse {
@@ -109,6 +275,34 @@ pub struct SimulateWithdrawResponse {
     pub astro_to_withdraw: Uint128,
 }
 
+/// This structure stores the parameters used to return a response when querying for an allocation advance.
+#[cw_serde]
+pub struct AdvanceResponse {
+    /// Advanced ASTRO which is not covered by unlocked tokens yet
+    pub outstanding: Uint128,
+    /// Max amount of ASTRO which can be advanced right now
+    pub available: Uint128,
+}
+
+/// This structure stores the voting rebate of an epoch.
+#[cw_serde]
+#[derive(Default)]
+pub struct EpochRebate {
+    /// Amount of ASTRO reserved from the rebate pool for the epoch
+    pub amount: Uint128,
+    /// Number of allocation holders which recorded a proposal vote in the epoch
+    pub participants: u32,
+}
+
+/// This structure describes the response when querying voting rebates of an allocation holder.
+#[cw_serde]
+pub struct RebatesResponse {
+    /// ASTRO rebates of finished epochs which are paid out on the next withdrawal
+    pub claimable: Uint128,
+    /// Epochs with a recorded vote which are not paid out yet
+    pub epochs: Vec<u64>,
+}
+
 /// This structure stores general parameters for the builder unlock contract.
 #[cw_serde]
 pub struct Config {
@@ -118,6 +312,18 @@ pub struct Config {
     pub astro_denom: String,
     /// Max ASTRO tokens to allocate
     pub max_allocations_amount: Uint128,
+    /// Share of ASTRO unlocking during the next [`ADVANCE_WINDOW`] which can be advanced.
+    /// Zero disables advances.
+    #[serde(default)]
+    pub advance_ltv: Decimal,
+    /// Assembly contract used to verify proposal votes of allocation holders.
+    /// Voting rebates are disabled if not set
+    #[serde(default)]
+    pub assembly: Option<Addr>,
+    /// Max amount of ASTRO distributed as voting rebates per epoch.
+    /// Zero disables voting rebates
+    #[serde(default)]
+    pub rebate_per_epoch: Uint128,
 }
 
 /// This structure stores the total and the remaining amount of ASTRO to be unlocked by all accounts.
@@ -132,6 +338,42 @@ pub struct State {
     pub unallocated_astro_tokens: Uint128,
 }
 
+/// This structure stores a merkle drop of allocations.
+#[cw_serde]
+pub struct MerkleDrop {
+    /// Merkle root (32 bytes sha256 hash)
+    pub merkle_root: Binary,
+    /// Total amount of ASTRO tokens in the drop
+    pub total_amount: Uint128,
+    /// Amount of ASTRO tokens already claimed as allocations
+    pub claimed_amount: Uint128,
+    /// Timestamp after which allocations can't be claimed anymore
+    pub expiration: u64,
+    /// Whether unclaimed ASTRO has been returned to unallocated tokens
+    pub expired: bool,
+}
+
+/// This structure stores the fallback receiver of an allocation.
+#[cw_serde]
+pub struct FallbackReceiver {
+    /// Address which can claim the allocation once the holder becomes inactive
+    pub receiver: Addr,
+    /// Period without withdrawals (in seconds) after which the allocation can be claimed
+    pub inactivity_period: u64,
+    /// Timestamp from which the fallback receiver is effective
+    pub effective_from: u64,
+    /// Timestamp of the last holder's activity (a withdrawal or the fallback receiver update)
+    pub last_activity: u64,
+}
+
+impl FallbackReceiver {
+    /// Returns timestamp from which the allocation can be claimed by the fallback receiver
+    pub fn claimable_from(&self) -> u64 {
+        self.effective_from
+            .max(self.last_activity + self.inactivity_period)
+    }
+}
+
 /// This structure stores the parameters describing a typical unlock schedule.
 #[cw_serde]
 #[derive(Default)]
@@ -144,6 +386,78 @@ pub struct Schedule {
     pub duration: u64,
     /// Percentage of tokens unlocked at the cliff
     pub percent_at_cliff: Option<Decimal>,
+    /// Explicit unlock milestones used instead of the cliff and linear unlock.
+    /// Tokens unlock linearly between consecutive milestones.
+    /// `cliff`, `duration` and `percent_at_cliff` must be omitted if milestones are set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub milestones: Option<Vec<UnlockMilestone>>,
+}
+
+impl Schedule {
+    /// Returns timestamp at which all tokens are unlocked
+    pub fn end_time(&self) -> u64 {
+        match &self.milestones {
+            Some(milestones) => {
+                self.start_time + milestones.last().map(|m| m.offset).unwrap_or_default()
+            }
+            None => self.start_time + self.duration,
+        }
+    }
+
+    /// Validates unlock milestones if they are set.
+    /// Offsets and cumulative percentages must be non-decreasing and the last milestone must unlock all tokens.
+    pub fn validate_milestones(&self) -> StdResult<()> {
+        let Some(milestones) = &self.milestones else {
+            return Ok(());
+        };
+
+        if self.cliff != 0 || self.duration != 0 || self.percent_at_cliff.is_some() {
+            return Err(StdError::generic_err(
+                "Cliff, duration and percent at cliff must be omitted if unlock milestones are set",
+            ));
+        }
+
+        if milestones.is_empty() || milestones.len() > MAX_UNLOCK_MILESTONES {
+            return Err(StdError::generic_err(format!(
+                "Number of unlock milestones must be within [1, {MAX_UNLOCK_MILESTONES}]"
+            )));
+        }
+
+        for pair in milestones.windows(2) {
+            if pair[1].offset < pair[0].offset || pair[1].percent < pair[0].percent {
+                return Err(StdError::generic_err(
+                    "Unlock milestones must be ordered by offset and cumulative percent",
+                ));
+            }
+        }
+
+        if milestones[milestones.len() - 1].percent != Decimal::one() {
+            return Err(StdError::generic_err(
+                "The last unlock milestone must unlock 100% of tokens",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// This structure describes a point of a milestone unlock schedule.
+#[cw_serde]
+pub struct UnlockMilestone {
+    /// Seconds since the schedule start time.
+    /// Two milestones with the same offset describe an instant unlock at that time
+    pub offset: u64,
+    /// Cumulative percentage of tokens unlocked at the milestone
+    pub percent: Decimal,
+}
+
+/// This structure stores a named unlock schedule shared by several allocations.
+#[cw_serde]
+pub struct ScheduleTemplate {
+    /// Human-readable template name
+    pub name: String,
+    /// Unlock schedule applied to allocations referencing the template
+    pub schedule: Schedule,
 }
 
 /// This structure stores the parameters used to describe an ASTRO allocation.
@@ -151,13 +465,19 @@ pub struct Schedule {
 pub struct CreateAllocationParams {
     /// Total amount of ASTRO tokens allocated to a specific account
     pub amount: Uint128,
-    /// Parameters controlling the unlocking process
+    /// Parameters controlling the unlocking process. Must be omitted if `schedule_template` is set
+    #[serde(default)]
     pub unlock_schedule: Schedule,
+    /// Schedule template to take the unlock schedule from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule_template: Option<u64>,
 }
 
 impl CreateAllocationParams {
     pub fn validate(&self, account: &str) -> StdResult<()> {
-        if self.unlock_schedule.cliff >= self.unlock_schedule.duration {
+        if self.unlock_schedule.milestones.is_some() {
+            self.unlock_schedule.validate_milestones()?;
+        } else if self.unlock_schedule.cliff >= self.unlock_schedule.duration {
             return Err(StdError::generic_err(format!(
                 "The new cliff value must be less than the duration: {} < {}. Account: {account}",
                 self.unlock_schedule.cliff, self.unlock_schedule.duration
@@ -181,10 +501,33 @@ pub struct AllocationParams {
     pub unlock_schedule: Schedule,
     /// Proposed new receiver who will get the ASTRO allocation
     pub proposed_receiver: Option<Addr>,
+    /// Schedule template the unlock schedule was taken from.
+    /// Cleared once the schedule is updated individually
+    #[serde(default)]
+    pub schedule_template: Option<u64>,
 }
 
 impl AllocationParams {
     pub fn update_schedule(&mut self, new_schedule: Schedule, account: &str) -> StdResult<()> {
+        new_schedule.validate_milestones()?;
+
+        if new_schedule.milestones.is_some() || self.unlock_schedule.milestones.is_some() {
+            // Cliff and duration are not comparable between schedule kinds.
+            // The new schedule must not start or end earlier than the old one
+            if new_schedule.start_time < self.unlock_schedule.start_time
+                || new_schedule.end_time() < self.unlock_schedule.end_time()
+            {
+                return Err(StdError::generic_err(format!(
+                    "The new schedule must not start or end earlier than the old one. Account error: {}",
+                    account
+                )));
+            }
+
+            self.unlock_schedule = new_schedule;
+            self.schedule_template = None;
+            return Ok(());
+        }
+
         if new_schedule.cliff < self.unlock_schedule.cliff {
             return Err(StdError::generic_err(format!(
                 "The new cliff value should be greater than or equal to the old one: {} >= {}. Account error: {}",
@@ -207,6 +550,7 @@ impl AllocationParams {
         }
 
         self.unlock_schedule = new_schedule;
+        self.schedule_template = None;
         Ok(())
     }
 }
@@ -221,4 +565,7 @@ pub struct AllocationStatus {
     pub astro_withdrawn: Uint128,
     /// Already unlocked amount after decreasing
     pub unlocked_amount_checkpoint: Uint128,
+    /// Amount of ASTRO advanced ahead of the unlock schedule and not repaid yet
+    #[serde(default)]
+    pub advanced: Uint128,
 }
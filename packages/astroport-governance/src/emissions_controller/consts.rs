@@ -9,15 +9,49 @@ pub const DAY: u64 = 86400;
 pub const EPOCH_LENGTH: u64 = DAY * 14;
 /// Timeout for IBC messages in seconds. Used for both `ics20` and `vxastro-ibc-v1` packets.
 pub const IBC_TIMEOUT: u64 = 3600;
+/// Allowed range for per-outpost IBC timeout overrides (from 5 minutes to 7 days)
+pub const IBC_TIMEOUT_LIMITS: RangeInclusive<u64> = 300..=DAY * 7;
+/// Storage key of the total voting power in the vxASTRO contract. Read by interchain queries
+pub const VXASTRO_TOTAL_POWER_KEY: &str = "total_power";
 /// Denom used to pay IBC fees
 pub const FEE_DENOM: &str = "untrn";
 /// Max number of pools allowed per outpost added
 pub const POOL_NUMBER_LIMIT: RangeInclusive<u64> = 1..=10;
+/// Max number of full epochs newly whitelisted pools must wait before receiving emissions
+pub const MAX_NEW_POOL_COOLDOWN_EPOCHS: u64 = 26;
+/// Number of latest epochs kept in the user's emissions votes history on the Hub
+pub const USER_VOTES_HISTORY_EPOCHS: u64 = 26;
 /// Maximum number of pools that can be voted for
 pub const MAX_POOLS_TO_VOTE: usize = 5;
+/// Max number of failures kept in the user's failure inbox on the outpost.
+/// The oldest failure is dropped once the limit is reached.
+pub const MAX_USER_FAILURES: usize = 10;
+/// Waiting time before the first retry of a timed out packet. Doubles with every timeout
+pub const PACKET_RETRY_BACKOFF: u64 = 600;
+/// Max number of times a packet is sent before its retries are expired
+pub const MAX_PACKET_ATTEMPTS: u8 = 5;
 /// Max items per page in queries
 pub const MAX_PAGE_LIMIT: u8 = 50;
+/// Max number of voters whose votes are moved to the new LP token in a single pool migration transaction
+pub const POOL_MIGRATION_BATCH: u32 = 100;
 /// vxASTRO IBC version
 pub const IBC_APP_VERSION: &str = "vxastro-ibc-v1";
 /// IBC ordering
 pub const IBC_ORDERING: IbcOrder = IbcOrder::Unordered;
+/// Outpost permission to relay vxASTRO lock updates which change users' emissions votes.
+/// vxASTRO unlocks are always accepted
+pub const OUTPOST_PERMIT_LOCK_UPDATE: u8 = 1;
+/// Outpost permission to relay emissions votes
+pub const OUTPOST_PERMIT_EMISSIONS_VOTE: u8 = 1 << 1;
+/// Outpost permission to relay governance votes
+pub const OUTPOST_PERMIT_GOVERNANCE_VOTE: u8 = 1 << 2;
+/// Outpost permission to whitelist its pools
+pub const OUTPOST_PERMIT_WHITELIST_POOL: u8 = 1 << 3;
+/// Outpost permission to submit Assembly proposals on behalf of its users
+pub const OUTPOST_PERMIT_SUBMIT_PROPOSAL: u8 = 1 << 4;
+/// All outpost actions are permitted
+pub const OUTPOST_PERMIT_ALL: u8 = OUTPOST_PERMIT_LOCK_UPDATE
+    | OUTPOST_PERMIT_EMISSIONS_VOTE
+    | OUTPOST_PERMIT_GOVERNANCE_VOTE
+    | OUTPOST_PERMIT_WHITELIST_POOL
+    | OUTPOST_PERMIT_SUBMIT_PROPOSAL;
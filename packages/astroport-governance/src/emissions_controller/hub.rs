@@ -1,10 +1,16 @@
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 
 use astroport::asset::validate_native_denom;
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{ensure, Addr, Coin, Decimal, StdError, StdResult, Uint128};
+use cosmwasm_std::{
+    ensure, Addr, Binary, Coin, CosmosMsg, Decimal, Fraction, StdError, StdResult, Uint128,
+};
 
-use crate::emissions_controller::consts::POOL_NUMBER_LIMIT;
+use crate::assembly::{ProposalStatus, ProposalTemplate};
+use crate::emissions_controller::consts::{
+    IBC_TIMEOUT, MAX_NEW_POOL_COOLDOWN_EPOCHS, OUTPOST_PERMIT_ALL, POOL_NUMBER_LIMIT,
+};
 use crate::voting_escrow::UpdateMarketingInfo;
 
 /// This structure describes the basic settings for creating a contract.
@@ -50,6 +56,28 @@ pub struct HubInstantiateMsg {
     pub ema: Uint128,
 }
 
+/// Contract configuration fields which can be changed by the owner.
+/// Fields set to None are left unchanged.
+#[cw_serde]
+#[derive(Default)]
+pub struct UpdateConfigMsg {
+    pub pools_per_outpost: Option<u64>,
+    pub whitelisting_fee: Option<Coin>,
+    pub fee_receiver: Option<String>,
+    pub emissions_multiple: Option<Decimal>,
+    pub max_astro: Option<Uint128>,
+    pub new_pool_cooldown_epochs: Option<u64>,
+    pub max_weight_per_pool: Option<Decimal>,
+    pub tune_incentive: Option<Uint128>,
+    pub tvl_oracle: Option<String>,
+    pub tvl_weight: Option<Decimal>,
+    pub tvl_max_age: Option<u64>,
+    pub whitelist_auction: Option<bool>,
+    pub min_pool_emissions: Option<Uint128>,
+    pub max_failed_epochs: Option<u64>,
+    pub max_voter_share: Option<Decimal>,
+}
+
 #[cw_serde]
 pub enum HubMsg {
     /// TunePools transforms the latest vote distribution into ASTRO emissions
@@ -57,15 +85,16 @@ pub enum HubMsg {
     /// Repeats IBC transfer messages with IBC hook for all outposts in Failed state.
     RetryFailedOutposts {},
     /// Update the contract configuration
-    UpdateConfig {
-        pools_per_outpost: Option<u64>,
-        whitelisting_fee: Option<Coin>,
-        fee_receiver: Option<String>,
-        emissions_multiple: Option<Decimal>,
-        max_astro: Option<Uint128>,
-    },
-    /// Whitelists a pool to receive ASTRO emissions. Requires fee payment
+    UpdateConfig(Box<UpdateConfigMsg>),
+    /// Whitelists a pool to receive ASTRO emissions. Requires fee payment.
+    /// If the whitelist auction is enabled and the whitelist is full, use BidWhitelistSlot instead
     WhitelistPool { lp_token: String },
+    /// Bids ASTRO for a whitelist slot when the whitelist is full and the auction is enabled.
+    /// The bid must be at least the whitelisting fee and exceed the current bid for the same pool.
+    /// Outbid bidders are refunded immediately.
+    /// Bids are settled during the next tune: the highest bids displace the lowest voted
+    /// incumbents which are not in the cooldown period. Losing bids are refunded.
+    BidWhitelistSlot { lp_token: String },
     /// Manages pool blacklist.
     /// Blacklisting prevents voting for it.
     /// If the pool is whitelisted, it will be removed from the whitelist.
@@ -80,6 +109,16 @@ pub enum HubMsg {
         #[serde(default)]
         remove: Vec<String>,
     },
+    /// Moves whitelist status and accumulated votes of a pool to its new LP token.
+    /// Is used when a pool migrates to another LP token (e.g. after pair upgrade).
+    /// User votes for the old LP token are moved to the new one.
+    /// If there are too many voters, the remaining votes are moved with MigratePoolVotes.
+    /// The old LP token can't be whitelisted again.
+    /// Only contract owner or Assembly can call this endpoint.
+    MigratePool { old_lp: String, new_lp: String },
+    /// Moves the remaining user votes of the pool migration in progress to the new LP token.
+    /// Anyone can call this endpoint.
+    MigratePoolVotes { limit: Option<u32> },
     /// Register or update an outpost
     UpdateOutpost {
         /// Bech32 prefix
@@ -90,19 +129,99 @@ pub enum HubMsg {
         /// This field also serves as marker whether it is The hub (params: None) or
         /// remote outpost (Some(params))
         outpost_params: Option<InputOutpostParams>,
-        /// A pool that must receive flat ASTRO emissions. Optional.
+        /// ASTRO pools that must receive flat ASTRO emissions. Optional.
         astro_pool_config: Option<AstroPoolConfig>,
+        /// Bitmask of actions the outpost is allowed to relay (see OUTPOST_PERMIT_* constants).
+        /// None keeps current permissions. New outposts are allowed to relay all actions.
+        #[serde(default)]
+        permissions: Option<u8>,
     },
     /// Jail an outpost.
     /// Jailed outposts can't participate in the voting process but still allow
     /// outpost users to unlock their vxASTRO.
     JailOutpost { prefix: String },
+    /// Resumes emissions to an outpost paused after consecutive failed deliveries
+    /// and resets its failure counter.
+    /// Only contract owner can call this endpoint.
+    ResumeOutpostEmissions { prefix: String },
     /// Unjail an outpost.
     /// Unjailed outposts retain all previous configurations but will need to whitelist pools and
     /// start a voting process from scratch.
     UnjailOutpost { prefix: String },
+    /// Moves an outpost to new IBC channels, e.g. when the previous ones expired or got frozen.
+    /// Packets in flight over old channels are still matched against the outpost, and xASTRO
+    /// escrowed for old ics20 channels keeps backing the outpost voting power.
+    /// Old channels can't be used again.
+    /// Only contract owner or Assembly can call this endpoint.
+    MigrateOutpostChannel {
+        prefix: String,
+        /// New wasm<>wasm IBC channel for voting. None keeps the current one
+        new_voting_channel: Option<String>,
+        /// New ics20 channel for fungible token transfers. None keeps the current one
+        new_ics20_channel: Option<String>,
+    },
     /// Permissionless endpoint to stream proposal info from the Hub to all outposts
     RegisterProposal { proposal_id: u64 },
+    /// Registers a Neutron interchain query which periodically reads the total voting power
+    /// from the outpost vxASTRO contract state.
+    /// Interchain query deposit is charged from the contract balance.
+    /// Only contract owner can call this endpoint.
+    RegisterSupplyQuery {
+        /// Outpost bech32 prefix
+        prefix: String,
+        /// IBC connection to the outpost chain
+        connection_id: String,
+        /// vxASTRO contract on the outpost
+        vxastro: String,
+        /// Query update period in blocks
+        update_period: u64,
+    },
+    /// Removes the supply query of an outpost along with its verification state.
+    /// Only contract owner can call this endpoint.
+    RemoveSupplyQuery { prefix: String },
+    /// Permissionless endpoint which compares the latest total voting power reported by the outpost
+    /// with the one verified via interchain query.
+    /// The outpost is flagged if the reported voting power exceeds the verified one.
+    ReconcileOutpostSupply { prefix: String },
+    /// Forwards messages approved by the Hub governance to an outpost for execution.
+    /// Execution status is updated upon IBC acknowledgement.
+    /// Only Assembly can call this endpoint.
+    ExecuteOnOutpost {
+        outpost_prefix: String,
+        msgs: Vec<CosmosMsg>,
+    },
+    /// Authorizes an address to cast emissions votes on behalf of the sender.
+    /// A user can have only one vote manager. None revokes the current one.
+    SetVoteManager { manager: Option<String> },
+    /// Subscribes or unsubscribes an outpost from proposal lifecycle notifications.
+    /// Only contract owner can call this endpoint.
+    SetProposalSubscription { prefix: String, enabled: bool },
+    /// Permissionless endpoint which sends the current proposal status to all subscribed outposts.
+    /// Assembly calls it on every proposal status change.
+    /// Outposts which have already been notified about the current status are skipped
+    /// unless the previous delivery failed.
+    NotifyProposalStatus { proposal_id: u64 },
+    /// Reports the latest TVL of pools which is used to weight ASTRO emissions.
+    /// Only the TVL oracle can call this endpoint.
+    ReportPoolTvls { tvls: Vec<(String, Uint128)> },
+    /// Escrows the deposit of a proposal submission relayed from an outpost
+    /// and submits the proposal to the Assembly.
+    /// The outpost calls it through an IBC hook of the ICS20 transfer carrying the deposit.
+    /// `channel` and `sequence` identify the SubmitProposal packet received by the Hub.
+    FundOutpostProposal { channel: String, sequence: u64 },
+    /// Permissionless endpoint which refunds the deposit of an outpost submission.
+    /// Deposits of submitted proposals are refunded over ICS20 once the Assembly refunds them,
+    /// deposits which haven't reached the Hub before the deadline are returned by the outpost.
+    /// Refunds are sent automatically, thus this endpoint is meant to retry failed refunds.
+    RefundOutpostDeposit { channel: String, sequence: u64 },
+    /// Permissionless endpoint which removes outpost vote origins of a concluded proposal.
+    /// The first batch is removed automatically once Assembly notifies about the conclusion.
+    /// Up to `limit` entries are removed per call.
+    ClearVoterOrigins { proposal_id: u64, limit: Option<u8> },
+    /// Permissionless endpoint which resends a timed out packet to the outpost.
+    /// Every timeout doubles the waiting time before the next retry.
+    /// Packets which timed out MAX_PACKET_ATTEMPTS times are expired.
+    RetryPacket { id: u64 },
 }
 
 /// This structure describes the query messages available in the contract.
@@ -163,10 +282,96 @@ pub enum QueryMsg {
     /// This query is useful for the UI to show the expected ASTRO emissions
     /// as well as might be useful for integrator estimations.
     /// It filters out pools which don't belong to any of outposts and invalid Hub-based LP tokens.
+    /// Pools with equal votes are ranked by LP token in lexicographic order
+    /// and pools below min_pool_emissions are reported as skipped.
     /// Returns TuneResultResponse object which contains
     /// emissions state and next pools grouped by outpost prefix.
     #[returns(SimulateTuneResponse)]
     SimulateTune {},
+    /// PoolStatus returns whitelisting status of a pool and the time
+    /// since when it is eligible to receive ASTRO emissions.
+    #[returns(PoolStatusResponse)]
+    PoolStatus { pool: String },
+    /// VoterOrigins returns outpost governance voters of a proposal along with the channel
+    /// their vote was relayed from. Voters are outpost addresses as reported by outposts.
+    /// The query is paginated.
+    /// If 'start_after' is provided, it yields a list **excluding** 'start_after'.
+    #[returns(Vec<(String, String)>)]
+    VoterOrigins {
+        proposal_id: u64,
+        limit: Option<u8>,
+        start_after: Option<String>,
+    },
+    /// SupplyChecks returns interchain query based supply verification state
+    /// of all outposts with registered supply queries.
+    #[returns(Vec<(String, SupplyCheck)>)]
+    SupplyChecks {},
+    /// OutpostExecution returns messages forwarded to an outpost and their execution status
+    #[returns(OutpostExecution)]
+    OutpostExecution { execution_id: u64 },
+    /// ProposalSubscribers returns outposts subscribed to proposal lifecycle notifications
+    #[returns(Vec<String>)]
+    ProposalSubscribers {},
+    /// ProposalNotifications returns the last proposal status notification sent to each outpost
+    #[returns(Vec<(String, ProposalNotification)>)]
+    ProposalNotifications { proposal_id: u64 },
+    /// PoolTvl returns the latest TVL reported by the oracle for a pool
+    #[returns(Option<PoolTvl>)]
+    PoolTvl { pool: String },
+    /// WhitelistBids returns pending bids for whitelist slots
+    #[returns(Vec<(String, WhitelistBid)>)]
+    WhitelistBids {},
+    /// UserVotesHistory returns the user's emissions votes per epoch.
+    /// Only the last [`crate::emissions_controller::consts::USER_VOTES_HISTORY_EPOCHS`] epochs are kept.
+    /// The query is paginated by epoch start.
+    /// If 'start_after' is provided, it yields a list **excluding** 'start_after'.
+    #[returns(Vec<UserEpochVotes>)]
+    UserVotesHistory {
+        user: String,
+        limit: Option<u8>,
+        start_after: Option<u64>,
+    },
+    /// OutpostFailures returns failed emissions delivery counters of outposts
+    /// along with whether emissions to them are paused
+    #[returns(Vec<(String, OutpostFailures)>)]
+    OutpostFailures {},
+    /// SimulateVote returns how a Hub user's vote would be applied to pools right now.
+    /// Voting power above the max_voter_share cap is reported separately
+    /// and doesn't contribute to pool votes.
+    #[returns(SimulateVoteResponse)]
+    SimulateVote {
+        voter: String,
+        votes: Vec<(String, Decimal)>,
+    },
+    /// OutpostProposal returns the outpost origin of a proposal submitted via IBC
+    #[returns(Option<OutpostProposal>)]
+    OutpostProposal { proposal_id: u64 },
+    /// OutpostSubmission returns a proposal submission relayed from an outpost
+    /// along with the refund status of its deposit
+    #[returns(Option<OutpostSubmission>)]
+    OutpostSubmission { channel: String, sequence: u64 },
+    /// PendingPoolMigration returns the pool migration whose user votes aren't moved entirely yet
+    #[returns(Option<PoolMigration>)]
+    PendingPoolMigration {},
+    /// FlowStats returns vxASTRO supply flows reported by outposts during the epoch
+    /// which contains the given timestamp
+    #[returns(FlowStatsResponse)]
+    FlowStats { epoch_start: u64 },
+    /// Metrics returns contract health counters as a flat list of (key, value) pairs
+    /// sorted by key: sent, acknowledged, failed and timed out packets per type,
+    /// vxASTRO staked and unstaked on outposts, ASTRO of failed emissions deliveries
+    /// of the latest tune and the number of outposts.
+    #[returns(Vec<(String, Uint128)>)]
+    Metrics {},
+    /// PacketRetry returns the retry state of a timed out packet
+    #[returns(PacketRetry)]
+    PacketRetry { id: u64 },
+    /// PacketRetries returns the retry state of timed out packets
+    #[returns(Vec<(u64, PacketRetry)>)]
+    PacketRetries {
+        limit: Option<u8>,
+        start_after: Option<u64>,
+    },
 }
 
 /// General contract configuration
@@ -208,6 +413,46 @@ pub struct Config {
     pub emissions_multiple: Decimal,
     /// Max ASTRO allowed per epoch. Parameter of the dynamic emissions curve.
     pub max_astro: Uint128,
+    /// Number of full epochs newly whitelisted pools must wait before receiving ASTRO emissions.
+    /// Pools accumulate votes during the cooldown.
+    #[serde(default)]
+    pub new_pool_cooldown_epochs: u64,
+    /// Max fraction of a single user's voting power that can be assigned to one pool.
+    /// None means no limit.
+    #[serde(default)]
+    pub max_weight_per_pool: Option<Decimal>,
+    /// ASTRO amount paid to whoever successfully executes tuning.
+    /// Paid out of the contract's ASTRO balance. Zero disables the incentive.
+    #[serde(default)]
+    pub tune_incentive: Uint128,
+    /// Oracle which reports pool TVLs. None disables TVL reports
+    #[serde(default)]
+    pub tvl_oracle: Option<Addr>,
+    /// Share of ASTRO emissions distributed proportionally to pool TVL rather than votes.
+    /// Zero means pure vote weighting
+    #[serde(default)]
+    pub tvl_weight: Decimal,
+    /// Max age in seconds of TVL reports used in tuning.
+    /// If any selected pool lacks a fresh report, the tune falls back to pure vote weighting
+    #[serde(default)]
+    pub tvl_max_age: u64,
+    /// Whether new pools bid for whitelist slots once the whitelist is full.
+    /// The whitelist is full when it contains pools_per_outpost * number of active outposts pools
+    #[serde(default)]
+    pub whitelist_auction: bool,
+    /// Min ASTRO a pool must receive in the tune.
+    /// Pools below it are excluded and their share is redistributed among the remaining pools.
+    /// Zero disables the threshold
+    #[serde(default)]
+    pub min_pool_emissions: Uint128,
+    /// Number of consecutive epochs with failed emissions delivery after which
+    /// emissions to the outpost are paused. Zero disables the penalty
+    #[serde(default)]
+    pub max_failed_epochs: u64,
+    /// Max share of the total vxASTRO supply a single address can apply to pool votes.
+    /// Voting power above the cap is ignored. None means no limit
+    #[serde(default)]
+    pub max_voter_share: Option<Decimal>,
 }
 
 impl Config {
@@ -238,8 +483,70 @@ impl Config {
             StdError::generic_err("max_astro must be greater than 0")
         );
 
+        ensure!(
+            self.new_pool_cooldown_epochs <= MAX_NEW_POOL_COOLDOWN_EPOCHS,
+            StdError::generic_err(format!(
+                "new_pool_cooldown_epochs must not exceed {MAX_NEW_POOL_COOLDOWN_EPOCHS}"
+            ))
+        );
+
+        if let Some(max_weight_per_pool) = self.max_weight_per_pool {
+            ensure!(
+                !max_weight_per_pool.is_zero() && max_weight_per_pool <= Decimal::one(),
+                StdError::generic_err("max_weight_per_pool must be within (0, 1]")
+            );
+        }
+
+        ensure!(
+            self.tune_incentive < self.max_astro,
+            StdError::generic_err("tune_incentive must be lower than max_astro")
+        );
+
+        ensure!(
+            self.tvl_weight <= Decimal::one(),
+            StdError::generic_err("tvl_weight must be within [0, 1]")
+        );
+
+        if !self.tvl_weight.is_zero() {
+            ensure!(
+                self.tvl_oracle.is_some() && self.tvl_max_age > 0,
+                StdError::generic_err("TVL weighting requires tvl_oracle and non-zero tvl_max_age")
+            );
+        }
+
+        ensure!(
+            self.min_pool_emissions < self.max_astro,
+            StdError::generic_err("min_pool_emissions must be lower than max_astro")
+        );
+
+        if let Some(max_voter_share) = self.max_voter_share {
+            ensure!(
+                !max_voter_share.is_zero() && max_voter_share <= Decimal::one(),
+                StdError::generic_err("max_voter_share must be within (0, 1]")
+            );
+        }
+
         Ok(())
     }
+
+    /// Splits the user's voting power into the part applied to pool votes
+    /// and the excess above the max_voter_share cap.
+    /// **total_voting_power** is the total vxASTRO supply where the voting power comes from.
+    pub fn split_voting_power(
+        &self,
+        voting_power: Uint128,
+        total_voting_power: Uint128,
+    ) -> (Uint128, Uint128) {
+        match self.max_voter_share {
+            Some(max_share) => {
+                let cap = total_voting_power
+                    .multiply_ratio(max_share.numerator(), max_share.denominator());
+                let effective = voting_power.min(cap);
+                (effective, voting_power - effective)
+            }
+            None => (voting_power, Uint128::zero()),
+        }
+    }
 }
 
 #[cw_serde]
@@ -252,6 +559,26 @@ pub struct OutpostParams {
     pub ics20_channel: String,
     /// ICS20 transfer escrow address on Neutron. Calculated automatically based on channel id
     pub escrow_address: Addr,
+    /// Timeout (in seconds) for IBC packets sent to this outpost.
+    /// Defaults to [`IBC_TIMEOUT`]
+    #[serde(default)]
+    pub ibc_timeout: Option<u64>,
+    /// NTRN ack and timeout fee paid to relayers for ics20 transfers to this outpost.
+    /// The minimal Neutron IBC fee is used if not set or if it is lower
+    #[serde(default)]
+    pub relayer_fee: Option<Uint128>,
+    /// Max number of pools carried in a single emissions packet.
+    /// Larger schedules are split into multiple packets. Not limited if not set.
+    /// NOTE: the outpost emissions controller must support chunked schedules
+    #[serde(default)]
+    pub pools_per_packet: Option<u32>,
+}
+
+impl OutpostParams {
+    /// Returns the timeout (in seconds) for IBC packets sent to this outpost
+    pub fn packet_timeout(&self) -> u64 {
+        self.ibc_timeout.unwrap_or(IBC_TIMEOUT)
+    }
 }
 
 #[cw_serde]
@@ -262,16 +589,226 @@ pub struct InputOutpostParams {
     pub voting_channel: String,
     /// General IBC channel for fungible token transfers
     pub ics20_channel: String,
+    /// Timeout (in seconds) for IBC packets sent to this outpost.
+    /// Must be within [`crate::emissions_controller::consts::IBC_TIMEOUT_LIMITS`]
+    #[serde(default)]
+    pub ibc_timeout: Option<u64>,
+    /// NTRN ack and timeout fee paid to relayers for ics20 transfers to this outpost
+    #[serde(default)]
+    pub relayer_fee: Option<Uint128>,
+    /// Max number of pools carried in a single emissions packet. Must be greater than zero
+    #[serde(default)]
+    pub pools_per_packet: Option<u32>,
 }
 
-/// Each outpost may have one pool that receives flat ASTRO emissions.
-/// This pools doesn't participate in the voting process.
+/// Each outpost may have ASTRO pools that receive flat ASTRO emissions.
+/// These pools don't participate in the voting process.
 #[cw_serde]
 pub struct AstroPoolConfig {
     /// Pool with ASTRO which needs to receive flat emissions
     pub astro_pool: String,
     /// Amount of ASTRO per epoch
     pub constant_emissions: Uint128,
+    /// Other ASTRO pools on the same outpost (e.g. ASTRO paired with a different asset),
+    /// each receiving its own flat emissions
+    #[serde(default)]
+    pub extra_pools: Vec<AstroPoolEmissions>,
+    /// Max ASTRO per epoch across all ASTRO pools of the outpost.
+    /// If constant emissions exceed the cap, they are scaled down proportionally. Optional
+    #[serde(default)]
+    pub emissions_cap: Option<Uint128>,
+}
+
+impl AstroPoolConfig {
+    /// Returns all ASTRO pools with their constant emissions per epoch
+    pub fn pools(&self) -> Vec<(String, Uint128)> {
+        let mut pools = vec![(self.astro_pool.clone(), self.constant_emissions)];
+        pools.extend(
+            self.extra_pools
+                .iter()
+                .map(|conf| (conf.pool.clone(), conf.constant_emissions)),
+        );
+        pools
+    }
+
+    /// Returns whether the pool is one of the outpost ASTRO pools
+    pub fn contains(&self, pool: &str) -> bool {
+        self.astro_pool == pool || self.extra_pools.iter().any(|conf| conf.pool == pool)
+    }
+
+    /// Returns ASTRO pools with their emissions per epoch after applying the aggregate cap
+    pub fn capped_pools(&self) -> Vec<(String, Uint128)> {
+        let pools = self.pools();
+        let total: Uint128 = pools.iter().map(|(_, amount)| amount).sum();
+        match self.emissions_cap {
+            Some(cap) if total > cap => pools
+                .into_iter()
+                .map(|(pool, amount)| (pool, amount.multiply_ratio(cap, total)))
+                .collect(),
+            _ => pools,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct AstroPoolEmissions {
+    /// Pool with ASTRO which needs to receive flat emissions
+    pub pool: String,
+    /// Amount of ASTRO per epoch
+    pub constant_emissions: Uint128,
+}
+
+#[cw_serde]
+pub enum OutpostExecutionStatus {
+    /// IBC packet is in-flight
+    Pending,
+    /// Outpost executed all messages
+    Executed,
+    /// Outpost failed to execute messages or IBC packet timed out
+    Failed { reason: String },
+}
+
+/// Messages forwarded by the Hub governance to an outpost
+#[cw_serde]
+pub struct OutpostExecution {
+    /// Outpost bech32 prefix
+    pub prefix: String,
+    /// Messages to be executed on the outpost
+    pub msgs: Vec<CosmosMsg>,
+    /// Execution status
+    pub status: OutpostExecutionStatus,
+    /// Timestamp when messages were sent
+    pub created_at: u64,
+}
+
+#[cw_serde]
+pub enum DeliveryStatus {
+    /// IBC packet is in-flight
+    Pending,
+    /// Outpost acknowledged the notification
+    Delivered,
+    /// Outpost rejected the notification or IBC packet timed out
+    Failed { reason: String },
+}
+
+#[cw_serde]
+pub enum PacketRetryStatus {
+    /// Packet timed out and awaits the next retry
+    Pending,
+    /// Resent packet is in-flight
+    InFlight,
+    /// Outpost acknowledged the packet
+    Delivered,
+    /// Outpost rejected the packet. Rejected packets aren't retried
+    Failed { reason: String },
+    /// Packet reached the max number of attempts or has been superseded by a newer one
+    Expired,
+}
+
+/// Timed out packet sent over the outpost voting channel
+#[cw_serde]
+pub struct PacketRetry {
+    /// Outpost prefix
+    pub prefix: String,
+    /// Original packet data
+    pub data: Binary,
+    /// Number of times the packet has been sent
+    pub attempts: u8,
+    /// Time when the packet can be resent
+    pub next_retry_at: u64,
+    pub status: PacketRetryStatus,
+}
+
+/// Proposal status notification sent to an outpost
+#[cw_serde]
+pub struct ProposalNotification {
+    /// Proposal status sent to the outpost
+    pub status: ProposalStatus,
+    /// Delivery status of the notification
+    pub delivery: DeliveryStatus,
+}
+
+/// Pool migration whose user votes haven't been moved entirely yet
+#[cw_serde]
+pub struct PoolMigration {
+    /// Migrated LP token
+    pub old_lp: String,
+    /// LP token the votes are moved to
+    pub new_lp: String,
+    /// The last voter whose votes were moved
+    pub last_voter: String,
+}
+
+/// Assembly proposal submitted on behalf of an outpost user
+#[cw_serde]
+pub struct OutpostProposal {
+    /// Outpost bech32 prefix
+    pub prefix: String,
+    /// Hub voting channel the submission was received over
+    pub channel: String,
+    /// Sequence of the SubmitProposal packet
+    pub sequence: u64,
+    /// Proposal submitter on the outpost
+    pub submitter: String,
+    /// xASTRO deposit
+    pub deposit: Uint128,
+}
+
+/// Proposal submission relayed from an outpost.
+/// The deposit is transferred from the outpost over ICS20 once the Hub accepts the submission.
+#[cw_serde]
+pub struct OutpostSubmission {
+    /// Outpost bech32 prefix
+    pub prefix: String,
+    /// Proposal submitter on the outpost
+    pub submitter: String,
+    pub proposal: ProposalTemplate,
+    /// xASTRO deposit
+    pub deposit: Uint128,
+    /// Time after which the deposit transfer can't reach the Hub anymore
+    pub deadline: u64,
+    pub status: OutpostSubmissionStatus,
+    /// Delivery status of the deposit refund. None until the refund is sent
+    pub refund: Option<DeliveryStatus>,
+}
+
+impl OutpostSubmission {
+    /// Whether the deposit refund can be sent
+    pub fn can_refund(&self) -> bool {
+        matches!(self.refund, None | Some(DeliveryStatus::Failed { .. }))
+    }
+}
+
+#[cw_serde]
+pub enum OutpostSubmissionStatus {
+    /// The Hub awaits the deposit transfer from the outpost
+    AwaitingDeposit,
+    /// The proposal has been submitted to the Assembly
+    Submitted { proposal_id: u64 },
+    /// The Assembly rejected the proposal. The deposit is refunded over ICS20
+    Rejected { reason: String },
+    /// The deposit transfer hasn't reached the Hub before the deadline.
+    /// The deposit is returned by the outpost
+    Expired,
+}
+
+/// Verification state of the total voting power reported by an outpost
+#[cw_serde]
+pub struct SupplyCheck {
+    /// Interchain query id. Zero until the query is registered on Neutron
+    pub query_id: u64,
+    /// vxASTRO contract on the outpost
+    pub vxastro: String,
+    /// The latest total voting power reported by the outpost in IBC messages
+    pub reported_supply: Uint128,
+    /// Time of the latest report
+    pub reported_at: u64,
+    /// Total voting power read from the outpost vxASTRO state via interchain query
+    pub verified_supply: Uint128,
+    /// Outpost chain height of the verified value
+    pub verified_height: u64,
+    /// Whether the reported voting power exceeded the verified one during the latest reconciliation
+    pub mismatch: bool,
 }
 
 #[cw_serde]
@@ -282,11 +819,18 @@ pub struct OutpostInfo {
     pub params: Option<OutpostParams>,
     /// ASTRO token denom
     pub astro_denom: String,
-    /// A pool that must receive flat ASTRO emissions. Optional.
+    /// ASTRO pools that must receive flat ASTRO emissions. Optional.
     pub astro_pool_config: Option<AstroPoolConfig>,
     /// Defines whether outpost is jailed. Jailed outposts can't participate in the voting process,
     /// but they still allow remote users to unstake their vxASTRO.
     pub jailed: bool,
+    /// Bitmask of actions the outpost is allowed to relay (see OUTPOST_PERMIT_* constants)
+    #[serde(default = "default_outpost_permissions")]
+    pub permissions: u8,
+}
+
+fn default_outpost_permissions() -> u8 {
+    OUTPOST_PERMIT_ALL
 }
 
 #[cw_serde]
@@ -298,6 +842,27 @@ pub struct UserInfo {
     pub voting_power: Uint128,
     /// Vote distribution for all the pools a user picked
     pub votes: HashMap<String, Decimal>,
+    /// Vote manager who cast the vote. None if the user voted themselves
+    #[serde(default)]
+    pub voted_by: Option<Addr>,
+    /// Voting power above the max_voter_share cap which is not applied to pool votes
+    #[serde(default)]
+    pub excess_voting_power: Uint128,
+}
+
+/// The last emissions vote cast by a user within an epoch
+#[cw_serde]
+pub struct UserEpochVotes {
+    /// Start of the epoch
+    pub epoch_start: u64,
+    /// Time when a user voted
+    pub vote_ts: u64,
+    /// Voting power used for the vote
+    pub voting_power: Uint128,
+    /// Voting power distribution sorted by pool
+    pub votes: Vec<(String, Decimal)>,
+    /// Vote manager who cast the vote. None if the user voted themselves
+    pub voted_by: Option<Addr>,
 }
 
 #[cw_serde]
@@ -310,6 +875,46 @@ pub struct UserInfoResponse {
     pub votes: HashMap<String, Decimal>,
     /// Actual applied votes. This list excludes non-whitelisted pools
     pub applied_votes: HashMap<String, Decimal>,
+    /// Vote manager who cast the vote. None if the user voted themselves
+    pub voted_by: Option<Addr>,
+    /// Address currently authorized to vote on behalf of the user
+    pub vote_manager: Option<Addr>,
+    /// Voting power above the max_voter_share cap which is not applied to pool votes
+    pub excess_voting_power: Uint128,
+}
+
+#[cw_serde]
+pub struct SimulateVoteResponse {
+    /// Current user's vxASTRO voting power
+    pub voting_power: Uint128,
+    /// Voting power which would be applied to pool votes
+    pub effective_voting_power: Uint128,
+    /// Voting power above the max_voter_share cap which would be ignored
+    pub excess_voting_power: Uint128,
+    /// Voting power each pool would receive
+    pub pools: Vec<(String, Uint128)>,
+}
+
+/// vxASTRO supply flow of an outpost during an epoch
+#[cw_serde]
+#[derive(Default)]
+pub struct OutpostFlow {
+    /// Total voting power increase reported by the outpost
+    pub staked: Uint128,
+    /// Total voting power decrease reported by the outpost
+    pub unstaked: Uint128,
+}
+
+#[cw_serde]
+pub struct FlowStatsResponse {
+    /// Start of the epoch
+    pub epoch_start: u64,
+    /// Voting power staked on all outposts during the epoch
+    pub total_staked: Uint128,
+    /// Voting power unstaked on all outposts during the epoch
+    pub total_unstaked: Uint128,
+    /// Flows per outpost prefix. Outposts without reported changes are omitted
+    pub outposts: Vec<(String, OutpostFlow)>,
 }
 
 #[cw_serde]
@@ -346,6 +951,29 @@ pub enum OutpostStatus {
     Done,
 }
 
+/// Delivery status of an emissions schedule split into multiple packets
+#[cw_serde]
+pub struct ChunkedDelivery {
+    /// Max number of pools carried in a single packet at the time of the tune
+    pub pools_per_packet: u32,
+    /// Status of each packet by chunk index
+    pub statuses: Vec<OutpostStatus>,
+}
+
+impl ChunkedDelivery {
+    /// Returns the combined status of all packets.
+    /// The delivery is in progress until all packets are acknowledged or failed.
+    pub fn status(&self) -> OutpostStatus {
+        if self.statuses.contains(&OutpostStatus::InProgress) {
+            OutpostStatus::InProgress
+        } else if self.statuses.contains(&OutpostStatus::Failed) {
+            OutpostStatus::Failed
+        } else {
+            OutpostStatus::Done
+        }
+    }
+}
+
 #[cw_serde]
 pub struct TuneInfo {
     /// Last time when the tune was executed.
@@ -354,15 +982,120 @@ pub struct TuneInfo {
     /// Map of outpost prefix -> array of pools with their emissions
     pub pools_grouped: HashMap<String, Vec<(String, Uint128)>>,
     /// Map of outpost prefix -> IBC status. Hub should never enter this map.
+    /// For schedules delivered in multiple packets this is the combined status of all packets.
     pub outpost_emissions_statuses: HashMap<String, OutpostStatus>,
+    /// Map of outpost prefix -> delivery status of each packet.
+    /// Contains only outposts which received the schedule in multiple packets.
+    #[serde(default)]
+    pub outpost_chunk_statuses: HashMap<String, ChunkedDelivery>,
     /// State of the dynamic emissions curve
     pub emissions_state: EmissionsState,
+    /// Hub pools which were excluded from the tune.
+    /// ASTRO allocated to them stays in the emissions controller.
+    #[serde(default)]
+    pub skipped_pools: Vec<SkippedPool>,
+}
+
+/// The reason why a pool was excluded from the tune
+#[cw_serde]
+#[derive(Copy)]
+pub enum SkipReason {
+    /// LP token is not a valid Astroport LP token
+    InvalidLpToken,
+    /// The pool has reached the limit of external rewards in the incentives contract
+    RewardLimitReached,
+    /// The pool would receive less ASTRO than min_pool_emissions
+    BelowMinEmissions,
+    /// Emissions to the pool's outpost are paused after consecutive failed deliveries
+    OutpostPaused,
+}
+
+impl Display for SkipReason {
+    fn fmt(&self, fmt: &mut Formatter) -> std::fmt::Result {
+        match self {
+            SkipReason::InvalidLpToken => fmt.write_str("invalid_lp_token"),
+            SkipReason::RewardLimitReached => fmt.write_str("reward_limit_reached"),
+            SkipReason::BelowMinEmissions => fmt.write_str("below_min_emissions"),
+            SkipReason::OutpostPaused => fmt.write_str("outpost_paused"),
+        }
+    }
+}
+
+/// Failed emissions deliveries of an outpost
+#[cw_serde]
+#[derive(Default)]
+pub struct OutpostFailures {
+    /// Number of consecutive epochs whose emissions were not delivered to the outpost.
+    /// Reset once a delivery succeeds
+    pub consecutive_failed_epochs: u64,
+    /// Start of the latest epoch whose emissions delivery failed
+    pub last_failed_epoch: u64,
+    /// Whether emissions to the outpost are paused. Only the owner can resume them
+    pub emissions_paused: bool,
+}
+
+#[cw_serde]
+pub struct SkippedPool {
+    /// LP token
+    pub pool: String,
+    /// Why the pool was excluded from the tune
+    pub reason: SkipReason,
 }
 
 #[cw_serde]
 pub struct SimulateTuneResponse {
     pub new_emissions_state: EmissionsState,
     pub next_pools_grouped: HashMap<String, Vec<(String, Uint128)>>,
+    pub skipped_pools: Vec<SkippedPool>,
+    /// Whether emissions are weighted by pool TVL in addition to votes
+    pub tvl_weighted: bool,
+    /// Emissions weights of selected pools
+    pub pool_weights: Vec<PoolWeight>,
+}
+
+/// Pending bid for a whitelist slot
+#[cw_serde]
+pub struct WhitelistBid {
+    /// Address which placed the bid and receives the refund if the bid loses
+    pub bidder: Addr,
+    /// Bid amount in the whitelisting fee denom
+    pub amount: Uint128,
+}
+
+/// TVL of a pool reported by the oracle
+#[cw_serde]
+pub struct PoolTvl {
+    /// Pool TVL
+    pub tvl: Uint128,
+    /// Time of the report
+    pub updated_at: u64,
+}
+
+/// Share of ASTRO emissions a pool receives in the tune
+#[cw_serde]
+pub struct PoolWeight {
+    /// LP token
+    pub pool: String,
+    /// Pool share of the votes among selected pools
+    pub vote_weight: Decimal,
+    /// Pool share of the TVL among selected pools. Zero if emissions aren't weighted by TVL
+    pub tvl_weight: Decimal,
+    /// Resulting share of ASTRO emissions
+    pub weight: Decimal,
+}
+
+#[cw_serde]
+pub struct PoolStatusResponse {
+    /// Whether the pool is whitelisted
+    pub is_whitelisted: bool,
+    /// Whether the pool is blacklisted
+    pub is_blacklisted: bool,
+    /// Time when the pool was whitelisted
+    pub whitelisted_ts: Option<u64>,
+    /// Epoch start from which the pool is eligible to receive ASTRO emissions
+    pub eligible_from: Option<u64>,
+    /// Voting power the pool received
+    pub voting_power: Uint128,
 }
 
 #[cw_serde]
@@ -400,6 +1133,16 @@ mod unit_tests {
             whitelist_threshold: Decimal::percent(10),
             emissions_multiple: Decimal::percent(80),
             max_astro: 1_400_000_000_000u128.into(),
+            new_pool_cooldown_epochs: 0,
+            max_weight_per_pool: None,
+            tune_incentive: Uint128::zero(),
+            tvl_oracle: None,
+            tvl_weight: Decimal::zero(),
+            tvl_max_age: 0,
+            whitelist_auction: false,
+            min_pool_emissions: Uint128::zero(),
+            max_failed_epochs: 0,
+            max_voter_share: None,
         };
         assert_eq!(
             config.validate().unwrap_err(),
@@ -447,7 +1190,86 @@ mod unit_tests {
         );
 
         config.max_astro = 1_400_000_000_000u128.into();
+        config.new_pool_cooldown_epochs = MAX_NEW_POOL_COOLDOWN_EPOCHS + 1;
+
+        assert_eq!(
+            config.validate().unwrap_err(),
+            StdError::generic_err("new_pool_cooldown_epochs must not exceed 26")
+        );
+
+        config.new_pool_cooldown_epochs = 2;
+        config.max_weight_per_pool = Some(Decimal::zero());
+
+        assert_eq!(
+            config.validate().unwrap_err(),
+            StdError::generic_err("max_weight_per_pool must be within (0, 1]")
+        );
+
+        config.max_weight_per_pool = Some(Decimal::percent(101));
+
+        assert_eq!(
+            config.validate().unwrap_err(),
+            StdError::generic_err("max_weight_per_pool must be within (0, 1]")
+        );
+
+        config.max_weight_per_pool = Some(Decimal::percent(50));
+        config.tune_incentive = config.max_astro;
+
+        assert_eq!(
+            config.validate().unwrap_err(),
+            StdError::generic_err("tune_incentive must be lower than max_astro")
+        );
+
+        config.tune_incentive = 10_000000u128.into();
+        config.tvl_weight = Decimal::percent(101);
+
+        assert_eq!(
+            config.validate().unwrap_err(),
+            StdError::generic_err("tvl_weight must be within [0, 1]")
+        );
+
+        config.tvl_weight = Decimal::percent(30);
+
+        assert_eq!(
+            config.validate().unwrap_err(),
+            StdError::generic_err("TVL weighting requires tvl_oracle and non-zero tvl_max_age")
+        );
+
+        config.tvl_oracle = Some(Addr::unchecked("oracle"));
+        config.tvl_max_age = 86400;
+        config.min_pool_emissions = config.max_astro;
+
+        assert_eq!(
+            config.validate().unwrap_err(),
+            StdError::generic_err("min_pool_emissions must be lower than max_astro")
+        );
+
+        config.min_pool_emissions = 100_000000u128.into();
+        config.max_voter_share = Some(Decimal::zero());
+
+        assert_eq!(
+            config.validate().unwrap_err(),
+            StdError::generic_err("max_voter_share must be within (0, 1]")
+        );
+
+        config.max_voter_share = Some(Decimal::percent(101));
+
+        assert_eq!(
+            config.validate().unwrap_err(),
+            StdError::generic_err("max_voter_share must be within (0, 1]")
+        );
+
+        config.max_voter_share = Some(Decimal::percent(10));
 
         config.validate().unwrap();
+
+        assert_eq!(
+            config.split_voting_power(50u128.into(), 1000u128.into()),
+            (50u128.into(), Uint128::zero())
+        );
+        assert_eq!(
+            config.split_voting_power(150u128.into(), 1000u128.into()),
+            (100u128.into(), 50u128.into())
+        );
     }
 }
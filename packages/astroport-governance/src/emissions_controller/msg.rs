@@ -2,21 +2,33 @@ use std::collections::HashMap;
 use std::fmt::Display;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_json_binary, Binary, Decimal, Uint128};
+use cosmwasm_std::{
+    from_json, to_json_binary, to_json_string, Binary, CosmosMsg, Decimal, Uint128,
+};
 
-use crate::assembly::ProposalVoteOption;
+use crate::assembly::{ProposalStatus, ProposalTemplate, ProposalVoteOption};
 
 #[cw_serde]
 pub enum ExecuteMsg<T> {
     /// Vote allows a vxASTRO holders
-    /// to cast votes on which pools should get ASTRO emissions in the next epoch
-    Vote { votes: Vec<(String, Decimal)> },
+    /// to cast votes on which pools should get ASTRO emissions in the next epoch.
+    /// A vote manager can vote with the full voting power of a user via 'on_behalf_of'.
+    /// Vote managers are supported only on the Hub.
+    Vote {
+        votes: Vec<(String, Decimal)>,
+        #[serde(default)]
+        on_behalf_of: Option<String>,
+    },
     /// Only vxASTRO contract can call this endpoint.
     /// Updates user votes according to the current voting power.
     UpdateUserVotes { user: String, is_unlock: bool },
     /// Permissionless endpoint which allows user to update their
     /// voting power contribution in case of IBC failures or if pool has been re-added to whitelist.
     RefreshUserVotes {},
+    /// Removes the sender's vote from a single pool. The voting power dedicated to the pool
+    /// becomes unallocated while the rest of the votes and the vote cooldown stay intact.
+    /// Supported only on the Hub.
+    RetractVote { pool: String },
     /// ProposeNewOwner proposes a new owner for the contract
     ProposeNewOwner {
         /// Newly proposed contract owner
@@ -42,9 +54,75 @@ pub enum IbcAckResult {
     Error(String),
 }
 
+/// Current version of the structured error acknowledgement
+pub const ACK_ERROR_VERSION: u8 = 1;
+
+/// Machine-readable error codes carried in error acknowledgements
+#[cw_serde]
+#[derive(Default)]
+pub enum AckErrorCode {
+    /// Unclassified error or legacy free-form error string
+    #[default]
+    Unknown,
+    /// IBC packet timed out
+    Timeout,
+    /// User has already voted in this epoch
+    VoteCooldown,
+    /// Outpost is jailed on the Hub
+    JailedOutpost,
+    /// Votes contain pools which can't be voted for or have invalid weights,
+    /// or a governance vote duplicates a vote already cast from another origin
+    InvalidVotes,
+    /// Outpost isn't permitted to relay this action
+    ActionNotPermitted,
+}
+
+impl AckErrorCode {
+    /// Whether the same message might succeed if sent again later
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, AckErrorCode::Timeout | AckErrorCode::VoteCooldown)
+    }
+}
+
+/// Structured error serialized into [`IbcAckResult::Error`]
+#[cw_serde]
+pub struct AckError {
+    /// Envelope version. Legacy free-form errors are parsed with version 0
+    pub version: u8,
+    pub code: AckErrorCode,
+    /// Human-readable error message
+    pub message: String,
+}
+
+impl AckError {
+    pub fn new(code: AckErrorCode, message: impl Display) -> Self {
+        Self {
+            version: ACK_ERROR_VERSION,
+            code,
+            message: message.to_string(),
+        }
+    }
+
+    /// Parses an error string from an acknowledgement.
+    /// Falls back to [`AckErrorCode::Unknown`] for legacy free-form errors.
+    pub fn parse(err: &str) -> Self {
+        from_json(err.as_bytes()).unwrap_or_else(|_| Self {
+            version: 0,
+            code: AckErrorCode::Unknown,
+            message: err.to_string(),
+        })
+    }
+}
+
 /// Create a serialized error message
 pub fn ack_fail(err: impl Display) -> Binary {
-    to_json_binary(&IbcAckResult::Error(err.to_string())).unwrap()
+    ack_fail_with_code(AckErrorCode::Unknown, err)
+}
+
+/// Create a serialized error message with a specific error code
+pub fn ack_fail_with_code(code: AckErrorCode, err: impl Display) -> Binary {
+    let err = to_json_string(&AckError::new(code, err)).unwrap();
+    to_json_binary(&IbcAckResult::Error(err)).unwrap()
 }
 
 /// Create a serialized success message
@@ -52,6 +130,11 @@ pub fn ack_ok() -> Binary {
     to_json_binary(&IbcAckResult::Ok(b"ok".into())).unwrap()
 }
 
+/// Create a serialized success message carrying response data
+pub fn ack_ok_with_data(data: Binary) -> Binary {
+    to_json_binary(&IbcAckResult::Ok(data)).unwrap()
+}
+
 /// Internal IBC messages for hub and outposts interactions
 #[cw_serde]
 pub enum VxAstroIbcMsg {
@@ -88,5 +171,85 @@ pub enum VxAstroIbcMsg {
         proposal_id: u64,
         /// Vote option
         vote: ProposalVoteOption,
+        /// vxASTRO timestamp the voting power was taken at.
+        /// The Hub verifies it matches the proposal snapshot and rejects votes without it
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        snapshot_time: Option<u64>,
+    },
+    /// Sender: Outpost.
+    /// Hidden vote for a commit-reveal proposal
+    GovernanceVoteCommit {
+        voter: String,
+        /// Actual voting power reported from outpost
+        voting_power: Uint128,
+        /// Current total voting power on this outpost
+        total_voting_power: Uint128,
+        /// Proposal id
+        proposal_id: u64,
+        /// Vote commitment
+        commitment: Binary,
+        /// vxASTRO timestamp the voting power was taken at.
+        /// The Hub verifies it matches the proposal snapshot and rejects votes without it
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        snapshot_time: Option<u64>,
+    },
+    /// Sender: Outpost.
+    /// Reveals a hidden vote during the reveal window
+    GovernanceVoteReveal {
+        voter: String,
+        /// Current total voting power on this outpost
+        total_voting_power: Uint128,
+        /// Proposal id
+        proposal_id: u64,
+        /// Vote option
+        vote: ProposalVoteOption,
+        /// Salt used to produce the commitment
+        salt: String,
+    },
+    /// Sender: Outpost.
+    /// Whitelists an outpost pool with the fee paid on the outpost.
+    /// The Hub acknowledges success with its fee receiver address.
+    WhitelistPool {
+        user: String,
+        /// LP token on the outpost
+        pool: String,
+        /// Fee escrowed on the outpost
+        fee: Uint128,
+    },
+    /// Sender: Hub.
+    /// Messages approved by the Hub governance which must be executed on the outpost.
+    ExecuteMsgs {
+        execution_id: u64,
+        msgs: Vec<CosmosMsg>,
+    },
+    /// Sender: Hub.
+    /// Notifies a subscribed outpost about a proposal status change.
+    ProposalStatusUpdate {
+        proposal_id: u64,
+        status: ProposalStatus,
+    },
+    /// Sender: Outpost.
+    /// Requests the live tally of a Hub proposal.
+    /// The Hub acknowledges success with [`crate::emissions_controller::outpost::ProposalTally`].
+    ProposalTallyRequest { proposal_id: u64 },
+    /// Sender: Outpost.
+    /// Requests an Assembly proposal submission on behalf of an outpost user.
+    /// The Hub acknowledges success with the deadline for the deposit transfer.
+    /// The outpost then transfers the escrowed deposit to the Hub over ICS20 with an IBC hook
+    /// which submits the proposal to the Assembly.
+    SubmitProposal {
+        submitter: String,
+        proposal: ProposalTemplate,
+        /// xASTRO deposit escrowed on the outpost
+        deposit: Uint128,
+    },
+    /// Sender: Hub.
+    /// Returns the deposit of a submission whose deposit transfer hasn't reached the Hub
+    /// before the deadline. The timed out transfer returns the deposit to the outpost.
+    RefundProposalDeposit {
+        /// Sequence of the SubmitProposal packet
+        sequence: u64,
+        submitter: String,
+        deposit: Uint128,
     },
 }
@@ -1,9 +1,10 @@
 use astroport::incentives::InputSchedule;
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
+use cosmwasm_std::{to_json_vec, Addr, Binary, Coin, CosmosMsg, Decimal, StdResult, Uint128};
+use sha2::{Digest, Sha256};
 
-use crate::assembly::ProposalVoteOption;
-use crate::emissions_controller::msg::VxAstroIbcMsg;
+use crate::assembly::{ProposalStatus, ProposalTemplate, ProposalVoteOption};
+use crate::emissions_controller::msg::{AckErrorCode, VxAstroIbcMsg};
 use crate::voting_escrow::UpdateMarketingInfo;
 
 /// This structure describes the basic settings for creating a contract.
@@ -31,8 +32,12 @@ pub struct OutpostInstantiateMsg {
 pub enum OutpostMsg {
     /// SetEmissions is a permissionless endpoint that allows setting ASTRO emissions for the next epoch
     /// from the Hub by leveraging IBC hooks.
+    /// Large schedules are split by the Hub into multiple packets, each carrying its own ASTRO funds.
     SetEmissions {
         schedules: Vec<(String, InputSchedule)>,
+        /// Set only if the schedule is delivered in multiple packets
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        chunk: Option<ScheduleChunk>,
     },
     /// Same as SetEmissions but it allows using funds from contract balance (if available).
     /// This endpoint can be called only by contract owner. It is meant to be used in case of
@@ -49,6 +54,53 @@ pub enum OutpostMsg {
         /// Vote option
         vote: ProposalVoteOption,
     },
+    /// Commits a hidden vote on a commit-reveal proposal.
+    /// The commitment is produced by [`crate::assembly::vote_commitment`]
+    /// and can be replaced until the commit phase ends.
+    CommitVote {
+        /// Proposal id
+        proposal_id: u64,
+        /// Vote commitment
+        commitment: Binary,
+    },
+    /// Reveals a vote committed from this outpost during the reveal phase.
+    RevealVote {
+        /// Proposal id
+        proposal_id: u64,
+        /// Vote option
+        vote: ProposalVoteOption,
+        /// Salt used to produce the commitment
+        salt: String,
+    },
+    /// Resends a failed emissions vote from the user's failure inbox
+    /// with the current user voting power.
+    RetryVote { failure_id: u64 },
+    /// Resends a failed voting power update from the user's failure inbox
+    /// with the current user voting power.
+    RetryRefreshVotes { failure_id: u64 },
+    /// Resends a failed governance vote from the user's failure inbox.
+    RetryGovernanceVote { failure_id: u64 },
+    /// Removes a failure from the user's inbox without retrying it.
+    DismissFailure { failure_id: u64 },
+    /// Permissionless endpoint which removes cached voting power of a concluded proposal.
+    /// The proposal status must be known from the Hub (via status updates or tally requests)
+    /// and must not be active anymore. Up to `limit` entries are removed per call.
+    ClearVotingPowerCache { proposal_id: u64, limit: Option<u8> },
+    /// Permissionless endpoint which requests the live tally of a registered proposal from the Hub.
+    /// The result is cached and exposed via [`QueryMsg::ProposalTally`].
+    RequestProposalTally { proposal_id: u64 },
+    /// Whitelists a pool on the Hub. The whitelisting fee is paid in ASTRO on this outpost.
+    /// The fee is escrowed until the Hub acknowledges the request.
+    /// It is forwarded to the Hub fee receiver via ICS20 on success and refunded on failure.
+    WhitelistPool { pool: String },
+    /// Submits an Assembly proposal through the Hub. The deposit is paid in xASTRO on this outpost.
+    /// The deposit is refunded if the Hub rejects the submission.
+    /// Otherwise, it stays escrowed until the Hub reports the proposal is concluded.
+    SubmitProposal { proposal: ProposalTemplate },
+    /// Executes messages relayed from the Hub governance.
+    /// Messages are executed atomically: either all of them succeed or none.
+    /// Only the contract itself can call this endpoint.
+    ExecuteHubMsgs { msgs: Vec<CosmosMsg> },
     UpdateConfig {
         /// Voting IBC wasm<>wasm channel
         voting_ibc_channel: Option<String>,
@@ -70,6 +122,14 @@ pub enum QueryMsg {
     /// Whether they have a pending request or an error.
     #[returns(UserIbcStatus)]
     QueryUserIbcStatus { user: String },
+    /// UserFailures returns the user's failure inbox, i.e., failed IBC operations
+    /// which can be retried or dismissed. The query is paginated by failure id.
+    #[returns(Vec<IbcFailure>)]
+    UserFailures {
+        user: String,
+        limit: Option<u8>,
+        start_after: Option<u64>,
+    },
     /// QueryRegisteredProposals returns the list of registered proposals.
     #[returns(Vec<RegisteredProposal>)]
     QueryRegisteredProposals {
@@ -83,6 +143,157 @@ pub enum QueryMsg {
         limit: Option<u8>,
         start_after: Option<String>,
     },
+    /// CachedVotingPower returns the user's voting power recorded on the first vote for the proposal.
+    /// Re-vote attempts (retries, recommits and reveals) reuse it.
+    #[returns(Option<CachedVotingPower>)]
+    CachedVotingPower { proposal_id: u64, user: String },
+    /// PendingEmissions returns the last emissions schedule received from the Hub
+    /// along with the status of each pool.
+    #[returns(Option<PendingEmissions>)]
+    PendingEmissions {},
+    /// ProposalStatus returns the latest proposal status received from the Hub
+    #[returns(Option<ProposalStatus>)]
+    ProposalStatus { proposal_id: u64 },
+    /// ProposalTally returns the latest proposal tally received from the Hub.
+    /// Use [`OutpostMsg::RequestProposalTally`] to refresh it.
+    #[returns(Option<ProposalTally>)]
+    ProposalTally { proposal_id: u64 },
+    /// VoteReceipt returns the user's emissions vote receipt for the given epoch.
+    /// If epoch_start is not set, the current epoch is used.
+    #[returns(Option<VoteReceipt>)]
+    VoteReceipt {
+        user: String,
+        epoch_start: Option<u64>,
+    },
+    /// VerifyVoteReceipt checks that the receipt matches the stored one
+    /// and the vote was acknowledged by the Hub.
+    /// Intended to be called by third-party protocols to distribute vote incentives.
+    #[returns(bool)]
+    VerifyVoteReceipt { receipt: VoteReceipt },
+    /// PendingProposalDeposit returns the deposit of a proposal submission awaiting the Hub acknowledgement.
+    /// `sequence` is the sequence of the SubmitProposal packet
+    #[returns(Option<ProposalDeposit>)]
+    PendingProposalDeposit { sequence: u64 },
+}
+
+/// User's voting power recorded on the first vote for a proposal
+#[cw_serde]
+pub struct CachedVotingPower {
+    /// User's vxASTRO voting power
+    pub voting_power: Uint128,
+    /// Total vxASTRO voting power on this outpost
+    pub total_voting_power: Uint128,
+    /// vxASTRO timestamp the voting power was taken at (1 second before the proposal start)
+    pub snapshot_time: u64,
+}
+
+/// Describes the acknowledgement status of an emissions vote packet
+#[cw_serde]
+pub enum VoteReceiptStatus {
+    /// The Hub applied the vote
+    Acknowledged,
+    /// The Hub rejected the vote or the packet timed out
+    Failed { code: AckErrorCode },
+}
+
+/// Receipt of an emissions vote sent to the Hub through this outpost.
+/// A failed vote never overwrites an acknowledged receipt of the same epoch.
+#[cw_serde]
+pub struct VoteReceipt {
+    pub user: String,
+    /// Start of the epoch when the vote outcome was received
+    pub epoch_start: u64,
+    /// Voting power distribution sorted by pool
+    pub votes: Vec<(String, Decimal)>,
+    /// Voting power reported to the Hub
+    pub voting_power: Uint128,
+    pub status: VoteReceiptStatus,
+    /// Timestamp when the vote outcome was received
+    pub timestamp: u64,
+    /// sha256 hash binding the receipt to this outpost. See [`VoteReceipt::compute_digest`]
+    pub digest: Binary,
+}
+
+impl VoteReceipt {
+    /// Computes sha256 of the JSON serialized outpost address and receipt fields (except the digest itself).
+    pub fn compute_digest(&self, outpost: &Addr) -> StdResult<Binary> {
+        let pre_image = to_json_vec(&(
+            outpost,
+            &self.user,
+            self.epoch_start,
+            &self.votes,
+            self.voting_power,
+            &self.status,
+            self.timestamp,
+        ))?;
+
+        Ok(Binary::from(Sha256::digest(pre_image).as_slice()))
+    }
+}
+
+/// Describes the outcome of setting emissions for a pool
+#[cw_serde]
+pub enum PoolEmissionsStatus {
+    /// Schedule was sent to the incentives contract
+    Funded,
+    /// Pool was filtered out and its ASTRO was sent back to the Hub
+    Failed { reason: String },
+}
+
+/// Contains the emissions schedule for a specific pool
+#[cw_serde]
+pub struct PoolEmissions {
+    pub pool: String,
+    pub schedule: InputSchedule,
+    pub status: PoolEmissionsStatus,
+}
+
+/// Contains the last emissions schedule received from the Hub
+#[cw_serde]
+pub struct PendingEmissions {
+    /// Timestamp when the schedule was received
+    pub received_at: u64,
+    /// Amount of ASTRO available for the schedule
+    pub received_amount: Uint128,
+    /// Amount of ASTRO sent to the incentives contract
+    pub funded_amount: Uint128,
+    /// Amount of ASTRO sent back to the Hub
+    pub returned_amount: Uint128,
+    /// Emissions schedule per pool
+    pub pools: Vec<PoolEmissions>,
+    /// Reassembly progress of a schedule delivered in multiple packets.
+    /// None if the schedule was delivered in a single packet
+    #[serde(default)]
+    pub chunks: Option<ChunksProgress>,
+}
+
+/// Describes a part of the emissions schedule delivered in multiple packets
+#[cw_serde]
+pub struct ScheduleChunk {
+    /// Epoch start the schedule belongs to
+    pub epoch: u64,
+    /// Zero-based chunk index
+    pub index: u32,
+    /// Total number of chunks
+    pub total: u32,
+}
+
+/// Reassembly progress of a chunked emissions schedule
+#[cw_serde]
+pub struct ChunksProgress {
+    /// Epoch start the schedule belongs to
+    pub epoch: u64,
+    /// Total number of chunks
+    pub total: u32,
+    /// Indexes of received chunks
+    pub received: Vec<u32>,
+}
+
+impl ChunksProgress {
+    /// Returns whether all chunks have been received
+    pub fn is_complete(&self) -> bool {
+        self.received.len() == self.total as usize
+    }
 }
 
 /// Contains failed IBC along with the error message
@@ -90,6 +301,52 @@ pub enum QueryMsg {
 pub struct UserIbcError {
     pub msg: VxAstroIbcMsg,
     pub err: String,
+    /// Error code reported by the Hub
+    #[serde(default)]
+    pub code: AckErrorCode,
+}
+
+/// Type of the failed IBC operation
+#[cw_serde]
+pub enum FailureKind {
+    /// Emissions vote. Retried with [`OutpostMsg::RetryVote`]
+    EmissionsVote,
+    /// Voting power update. Retried with [`OutpostMsg::RetryRefreshVotes`]
+    UpdateVotes,
+    /// vxASTRO unlock. vxASTRO is relocked on failure thus the user must unlock again
+    Unlock,
+    /// Governance vote, commitment or reveal. Retried with [`OutpostMsg::RetryGovernanceVote`]
+    GovernanceVote,
+    /// Pool whitelisting. The fee is refunded on failure thus the user must whitelist again
+    WhitelistPool,
+    /// Proposal submission. The deposit is refunded on failure thus the user must submit again
+    SubmitProposal,
+}
+
+/// Failed IBC operation stored in the user's failure inbox
+#[cw_serde]
+pub struct IbcFailure {
+    pub id: u64,
+    pub kind: FailureKind,
+    /// Voting power carried by the failed message
+    pub voting_power: Uint128,
+    /// Timestamp when the failure was recorded
+    pub timestamp: u64,
+    /// Error code reported by the Hub
+    pub code: AckErrorCode,
+    /// Human-readable error message
+    pub reason: String,
+    /// Original IBC message
+    pub msg: VxAstroIbcMsg,
+}
+
+/// IBC message awaiting the Hub acknowledgement
+#[cw_serde]
+pub struct PendingMessage {
+    /// The message sent to the Hub
+    pub msg: VxAstroIbcMsg,
+    /// Start of the epoch the message was sent in
+    pub epoch_start: u64,
 }
 
 /// Contains the pending IBC message or an error
@@ -120,6 +377,13 @@ pub struct Config {
     pub ics20_channel: String,
 }
 
+/// Deposit escrowed for a proposal submission until the Hub acknowledges it
+#[cw_serde]
+pub struct ProposalDeposit {
+    pub submitter: String,
+    pub deposit: Coin,
+}
+
 /// Contains the proposal id and the start time.
 /// Used exclusively in query response.
 #[cw_serde]
@@ -127,3 +391,23 @@ pub struct RegisteredProposal {
     pub id: u64,
     pub start_time: u64,
 }
+
+/// Live tally of a Hub proposal.
+/// Block heights refer to the Hub chain.
+#[cw_serde]
+pub struct ProposalTally {
+    pub proposal_id: u64,
+    pub status: ProposalStatus,
+    /// `For` power of proposal
+    pub for_power: Uint128,
+    /// `Against` power of proposal
+    pub against_power: Uint128,
+    /// Total voting power 1 second before the proposal was created
+    pub total_voting_power: Uint128,
+    /// Last block when votes can be cast or revealed
+    pub voting_end_block: u64,
+    /// Number of blocks left until voting ends
+    pub blocks_remaining: u64,
+    /// Hub block timestamp when the tally was taken
+    pub updated_at: u64,
+}
@@ -1,6 +1,32 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Uint128};
-use cw20::{BalanceResponse, Logo, MarketingInfoResponse, TokenInfoResponse};
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::{
+    BalanceResponse, DownloadLogoResponse, Logo, LogoInfo, MarketingInfoResponse, TokenInfoResponse,
+};
+use std::ops::RangeInclusive;
+
+/// Default unlock period (2 weeks)
+pub const DEFAULT_UNLOCK_PERIOD: u64 = 86400 * 14;
+/// Allowed range for the unlock period (from 1 day to 8 weeks)
+pub const UNLOCK_PERIOD_LIMITS: RangeInclusive<u64> = 86400..=86400 * 56;
+/// Max number of contracts subscribed to lock changes
+pub const MAX_SUBSCRIBERS: usize = 10;
+/// Pause flag which disables creation of new positions
+pub const PAUSE_NEW_LOCKS: u8 = 1;
+/// Pause flag which disables deposits to existing positions and relocks
+pub const PAUSE_DEPOSITS: u8 = 1 << 1;
+/// Pause flag which disables withdrawals and instant unlocks
+pub const PAUSE_WITHDRAWALS: u8 = 1 << 2;
+/// All supported pause flags
+pub const PAUSE_ALL: u8 = PAUSE_NEW_LOCKS | PAUSE_DEPOSITS | PAUSE_WITHDRAWALS;
+/// Max deposit fee in basis points (5%)
+pub const MAX_DEPOSIT_FEE_BPS: u16 = 500;
+/// Max insurance fund contribution in basis points (5%)
+pub const MAX_INSURANCE_FUND_BPS: u16 = 500;
+/// Max voting power boost of permanent locks in basis points (+100%)
+pub const MAX_PERMANENT_BOOST_BPS: u16 = 10000;
+/// Max number of queued withdrawals processed per block
+pub const MAX_EXIT_QUEUE_LIMIT: u32 = 100;
 
 /// This structure stores marketing information for vxASTRO.
 #[cw_serde]
@@ -39,6 +65,12 @@ pub enum ExecuteMsg {
     InstantUnlock { amount: Uint128 },
     /// Cancel unlocking
     Relock {},
+    /// Convert the locked position into a permanent lock.
+    /// Permanent locks can't be unlocked directly,
+    /// but their voting power is boosted by the permanent lock boost
+    MakePermanent {},
+    /// Convert the permanent lock back to a normal one and start unlocking it
+    UnlockPermanent {},
     /// Permissioned to the Emissions Controller contract.
     /// Confirms unlocking for a specific user.
     /// Unconfirmed unlocks can't be withdrawn.
@@ -48,12 +80,88 @@ pub enum ExecuteMsg {
     /// This is used on IBC failures/timeouts.
     /// Allows users to retry unlocking.
     ForceRelock { user: String },
-    /// Withdraw xASTRO from the vxASTRO contract
+    /// Withdraw xASTRO from the vxASTRO contract.
+    /// If the exit queue is enabled, the withdrawal is queued and paid out by [`ExecuteMsg::ProcessExitQueue`]
     Withdraw {},
+    /// Forcibly reduce a user's position and send the slashed xASTRO to the recipient.
+    /// Both locked and unlocking positions can be slashed.
+    /// Only the Assembly contract can call this.
+    /// NOTE: the Assembly is queried from the Emissions Controller, thus this feature is enabled only on the hub.
+    SlashLock {
+        user: String,
+        amount: Uint128,
+        recipient: String,
+    },
+    /// Set the voting power boost of permanent locks in basis points.
+    /// Only Emissions Controller owner can call this.
+    /// NOTE: outposts report their total voting power against bridged xASTRO,
+    /// thus this feature is enabled only on the hub.
+    /// Emissions votes of permanent lockers must be refreshed via RefreshPermanentVotes.
+    SetPermanentBoost { boost_bps: u16 },
+    /// Refresh emissions votes of permanent lockers with the current permanent lock boost.
+    /// Users without permanent locks are skipped. Anyone can call this.
+    /// The number of users is limited by MAX_PAGE_LIMIT
+    RefreshPermanentVotes { users: Vec<String> },
+    /// Set the max number of queued withdrawals processed per block.
+    /// Non-zero limit enables the exit queue: withdrawals and instant unlocks are queued
+    /// and paid out in FIFO order. The queue can be disabled only when it is empty.
+    /// Only Emissions Controller owner can call this.
+    SetExitQueueLimit { limit: u32 },
+    /// Pay out queued withdrawals in FIFO order.
+    /// At most `limit` withdrawals are processed, bounded by the remaining per block limit.
+    /// Anyone can call this.
+    ProcessExitQueue { limit: Option<u32> },
     /// Set the list of addresses that allowed to instantly unlock xASTRO.
     /// Only contract owner can call this.
     /// NOTE: due to async nature of IBC this feature will be enabled only on the hub.
     SetPrivilegedList { list: Vec<String> },
+    /// Set the unlock period applied to new unlocks.
+    /// Positions which are already unlocking keep their original end time.
+    /// Only Emissions Controller owner can call this.
+    SetUnlockPeriod { unlock_period: u64 },
+    /// Add a contract to the lock changes subscribers or update its enable flag.
+    /// Enabled subscribers receive [`SubscriberMsg`] every time a user's lock changes.
+    /// Only Emissions Controller owner can call this.
+    SetSubscriber { contract: String, enabled: bool },
+    /// Remove a contract from the lock changes subscribers.
+    /// Only Emissions Controller owner can call this.
+    RemoveSubscriber { contract: String },
+    /// Set the pause mask composed of [`PAUSE_NEW_LOCKS`], [`PAUSE_DEPOSITS`] and [`PAUSE_WITHDRAWALS`] flags.
+    /// Zero mask unpauses the contract.
+    /// Only Emissions Controller owner can call this.
+    SetPauseMask { pause_mask: u8 },
+    /// Set the fee in basis points charged on deposits to new and existing positions.
+    /// Collected fees are forwarded to the fee receiver (e.g. escrow fee distributor).
+    /// The fee receiver must be set to enable the fee.
+    /// Only Emissions Controller owner can call this.
+    SetDepositFee {
+        fee_bps: u16,
+        fee_receiver: Option<String>,
+    },
+    /// Add or remove addresses exempted from the deposit fee (e.g. protocol contracts).
+    /// The exemption applies to deposits credited to the address, no matter who sends them.
+    /// Only Emissions Controller owner can call this.
+    SetFeeExemptions {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// Forward accrued deposit fees to the fee receiver. Anyone can call this.
+    ForwardFees {},
+    /// Set the contribution in basis points of deposits routed to the insurance fund.
+    /// The fund backstops interchain incidents; contributions are sent to it with every deposit.
+    /// The insurance fund must be set to enable contributions.
+    /// Only Emissions Controller owner can call this.
+    SetInsuranceFund {
+        contribution_bps: u16,
+        fund: Option<String>,
+    },
+    /// Add or remove addresses exempted from insurance fund contributions.
+    /// The exemption applies to deposits credited to the address, no matter who sends them.
+    /// Only Emissions Controller owner can call this.
+    SetInsuranceExemptions {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
     /// Update the marketing info for the vxASTRO contract
     UpdateMarketing {
         /// A URL pointing to the project behind this token
@@ -63,6 +171,9 @@ pub enum ExecuteMsg {
         /// The address (if any) that can update this data structure
         marketing: Option<String>,
     },
+    /// Upload a logo for vxASTRO. Either a URL or an embedded SVG/PNG logo is accepted.
+    /// Only the marketing address can call this.
+    UploadLogo(Logo),
 }
 
 /// This structure describes the query messages available in the contract.
@@ -78,7 +189,15 @@ pub enum QueryMsg {
     /// Fetch vxASTRO's marketing information
     #[returns(MarketingInfoResponse)]
     MarketingInfo {},
-    /// Return the current total amount of vxASTRO
+    /// Download vxASTRO's embedded logo
+    #[returns(DownloadLogoResponse)]
+    DownloadLogo {},
+    /// Fetch vxASTRO's token info and marketing info in a single query
+    #[returns(TokenMetadataResponse)]
+    TokenMetadata {},
+    /// Return the total amount of vxASTRO at the given timestamp or the current one.
+    /// Totals are checkpointed every block, thus historical values are resolved
+    /// with a single checkpoint lookup without iterating user locks
     #[returns(Uint128)]
     TotalVotingPower { timestamp: Option<u64> },
     /// Return the user's current voting power (vxASTRO balance)
@@ -93,6 +212,17 @@ pub enum QueryMsg {
         user: String,
         timestamp: Option<u64>,
     },
+    /// Return voting power of multiple users in a single query.
+    /// The number of users is limited by MAX_PAGE_LIMIT
+    #[returns(Vec<(Addr, Uint128)>)]
+    BalancesBatch {
+        users: Vec<String>,
+        timestamp: Option<u64>,
+    },
+    /// Fetch lock information of multiple users in a single query.
+    /// The number of users is limited by MAX_PAGE_LIMIT
+    #[returns(Vec<(Addr, LockInfoResponse)>)]
+    LockInfoBatch { users: Vec<String> },
     /// Return the vxASTRO contract configuration
     #[returns(Config)]
     Config {},
@@ -106,6 +236,58 @@ pub enum QueryMsg {
         start_after: Option<String>,
         timestamp: Option<u64>,
     },
+    /// Returns the list of lock changes subscribers with their enable flags
+    #[returns(Vec<(Addr, bool)>)]
+    Subscribers {},
+    /// Returns which operations are currently paused
+    #[returns(PausedResponse)]
+    Paused {},
+    /// Returns normalized user's boost parameters for external protocols.
+    /// See [`BoostInfoResponse`] for details.
+    #[returns(BoostInfoResponse)]
+    BoostInfo { user: String },
+    /// Returns the amount of collected deposit fees which are not yet forwarded to the fee receiver
+    #[returns(Uint128)]
+    AccruedFees {},
+    /// Returns the list of addresses exempted from the deposit fee
+    #[returns(Vec<Addr>)]
+    FeeExemptions {},
+    /// Returns the lifetime insurance fund contributions of a user
+    #[returns(Uint128)]
+    InsuranceContributions { user: String },
+    /// Returns the lifetime insurance fund contributions of all users
+    #[returns(Uint128)]
+    TotalInsuranceContributions {},
+    /// Returns the list of addresses exempted from insurance fund contributions
+    #[returns(Vec<Addr>)]
+    InsuranceExemptions {},
+    /// Returns the owner and the lock info of a position by its id.
+    /// Position ids are emitted in all lock events as the `position_id` attribute
+    #[returns(PositionResponse)]
+    PositionById { position_id: u64 },
+    /// Returns the user's open position if any
+    #[returns(Option<PositionResponse>)]
+    UserPosition { user: String },
+    /// Returns the exit queue state
+    #[returns(ExitQueueResponse)]
+    ExitQueue {},
+    /// Returns the user's queued withdrawals with their queue positions and ETAs
+    #[returns(Vec<QueuedWithdrawalResponse>)]
+    ExitQueuePosition { user: String },
+}
+
+/// This message is sent to subscribed contracts whenever a user's lock changes.
+/// Subscriber failures don't revert the vxASTRO operation.
+#[cw_serde]
+pub enum SubscriberMsg {
+    LockChanged {
+        /// User whose lock has changed
+        user: String,
+        /// vxASTRO action which changed the lock (lock, unlock, relock etc.)
+        action: String,
+        /// The user's lock state after the change
+        lock_info: LockInfoResponse,
+    },
 }
 
 /// This structure stores the main parameters for the voting escrow contract.
@@ -115,6 +297,103 @@ pub struct Config {
     pub deposit_denom: String,
     /// Astroport Emissions Controller contract
     pub emissions_controller: Addr,
+    /// Unlock period in seconds
+    #[serde(default = "default_unlock_period")]
+    pub unlock_period: u64,
+    /// Bitmask of paused operations
+    #[serde(default)]
+    pub pause_mask: u8,
+    /// Fee in basis points charged on deposits
+    #[serde(default)]
+    pub deposit_fee_bps: u16,
+    /// Address receiving collected deposit fees
+    #[serde(default)]
+    pub fee_receiver: Option<Addr>,
+    /// Voting power boost of permanent locks in basis points
+    #[serde(default)]
+    pub permanent_boost_bps: u16,
+    /// Insurance fund contribution in basis points of deposits
+    #[serde(default)]
+    pub insurance_fund_bps: u16,
+    /// Address receiving insurance fund contributions
+    #[serde(default)]
+    pub insurance_fund: Option<Addr>,
+    /// Max number of queued withdrawals processed per block. Zero disables the exit queue
+    #[serde(default)]
+    pub exit_queue_limit: u32,
+}
+
+fn default_unlock_period() -> u64 {
+    DEFAULT_UNLOCK_PERIOD
+}
+
+impl Config {
+    /// Returns whether any of the given pause flags is set
+    pub fn is_paused(&self, flags: u8) -> bool {
+        self.pause_mask & flags != 0
+    }
+
+    /// Returns the deposit fee charged on the given amount
+    pub fn deposit_fee(&self, amount: Uint128) -> Uint128 {
+        amount.multiply_ratio(self.deposit_fee_bps, 10000u16)
+    }
+
+    /// Returns the insurance fund contribution taken from the given amount
+    pub fn insurance_contribution(&self, amount: Uint128) -> Uint128 {
+        amount.multiply_ratio(self.insurance_fund_bps, 10000u16)
+    }
+}
+
+/// This structure describes which vxASTRO operations are paused.
+#[cw_serde]
+pub struct PausedResponse {
+    /// Raw pause mask
+    pub pause_mask: u8,
+    /// Whether new positions can't be created
+    pub new_locks: bool,
+    /// Whether deposits to existing positions and relocks are disabled
+    pub deposits: bool,
+    /// Whether withdrawals and instant unlocks are disabled
+    pub withdrawals: bool,
+}
+
+/// This structure describes a withdrawal waiting in the exit queue.
+#[cw_serde]
+pub struct QueuedWithdrawal {
+    /// Withdrawal recipient
+    pub user: Addr,
+    /// Amount of xASTRO to pay out
+    pub amount: Uint128,
+    /// Timestamp when the withdrawal was queued
+    pub queued_at: u64,
+}
+
+/// This structure describes the exit queue state.
+#[cw_serde]
+pub struct ExitQueueResponse {
+    /// Max number of queued withdrawals processed per block. Zero means the exit queue is disabled
+    pub limit: u32,
+    /// Number of queued withdrawals
+    pub length: u64,
+    /// Total amount of xASTRO reserved for queued withdrawals
+    pub total_amount: Uint128,
+    /// Number of withdrawals processed in the current block
+    pub processed_in_block: u32,
+}
+
+/// This structure describes a user's withdrawal in the exit queue.
+#[cw_serde]
+pub struct QueuedWithdrawalResponse {
+    /// Queued withdrawal id
+    pub id: u64,
+    /// Amount of xASTRO to pay out
+    pub amount: Uint128,
+    /// Timestamp when the withdrawal was queued
+    pub queued_at: u64,
+    /// Number of withdrawals ahead in the queue
+    pub position: u64,
+    /// Number of blocks until the withdrawal is processed assuming the queue is processed every block
+    pub eta_blocks: u64,
 }
 
 #[derive(Copy)]
@@ -132,4 +411,59 @@ pub struct LockInfoResponse {
     pub amount: Uint128,
     /// Unlocking status. None for positions in locked state
     pub unlock_status: Option<UnlockStatus>,
+    /// Whether the position is a permanent lock
+    #[serde(default)]
+    pub permanent: bool,
+}
+
+/// This structure describes a vxASTRO position identified by its id.
+/// Each address holds at most one open position, as deposits always extend it.
+/// A new id is assigned every time a user opens a position, thus ids of closed positions are never reused.
+#[cw_serde]
+pub struct PositionResponse {
+    /// Position id
+    pub position_id: u64,
+    /// Position owner
+    pub owner: Addr,
+    /// Whether the position is still open
+    pub open: bool,
+    /// The position's current lock info. Empty for closed positions
+    pub lock_info: LockInfoResponse,
+}
+
+/// Aggregated vxASTRO token metadata used by wallets and explorers
+#[cw_serde]
+pub struct TokenMetadataResponse {
+    /// Token name
+    pub name: String,
+    /// Token symbol
+    pub symbol: String,
+    /// Token decimals
+    pub decimals: u8,
+    /// Current total voting power
+    pub total_supply: Uint128,
+    /// A URL pointing to the project behind this token
+    pub project: Option<String>,
+    /// A longer description of the token and its utility
+    pub description: Option<String>,
+    /// Token logo
+    pub logo: Option<LogoInfo>,
+    /// The address (if any) that can update marketing info and logo
+    pub marketing: Option<Addr>,
+}
+
+/// Normalized boost parameters of a vxASTRO position.
+/// This is a stable interface for external protocols which boost rewards for vxASTRO lockers.
+/// New fields might be added in the future, but existing ones won't be changed or removed.
+/// vxASTRO positions have no lock duration, thus there is no duration based multiplier.
+#[cw_serde]
+pub struct BoostInfoResponse {
+    /// The user's current voting power
+    pub voting_power: Uint128,
+    /// The current total voting power
+    pub total_voting_power: Uint128,
+    /// The user's share of the total voting power in [0, 1] range
+    pub power_share: Decimal,
+    /// The amount of xASTRO locked in the position
+    pub lock_amount: Uint128,
 }